@@ -0,0 +1,116 @@
+"""
+Cargo build scripts
+
+A crate's `build.rs` runs at build time and compiles against [build-dependencies], not the
+crate's own dependencies. Every `build.rs`, and the files it pulls in with `include!("...")`, is
+treated as the `build-script` scope: its imports carry `scope: build-script` on their edges, count
+as the `build` import context when crates are cross-checked against their dep_kind, and each
+script is reported under `analyzer_details.build_scripts` with the crates it imports and the
+native libraries it links through `cargo:rustc-link-lib=[KIND[:MODIFIERS]=]NAME` instructions
+"""
+
+import re
+from pathlib import PurePosixPath
+
+from gardener.common.file_helpers import read_repo_file
+from gardener.common.repo_paths import join_dir
+
+BUILD_SCRIPT_SCOPE = "build-script"
+
+INCLUDE_RE = re.compile(r'\binclude!\s*\(\s*"([^"]+)"\s*\)')
+LINK_LIB_RE = re.compile(r'cargo::?rustc-link-lib=([^"\s\\]+)')
+
+
+def parse_link_lib(value):
+    """
+    Split a rustc-link-lib value into (name, kind); kind is None when unspecified
+
+    Returns None for names filled in at run time (`{}` format arguments)
+    """
+    kind, _, name = value.rpartition("=")
+    name = name.split(":", 1)[0]
+    if not name or "{" in name:
+        return None
+    return name, kind.split(":", 1)[0] or None
+
+
+def collect_build_scripts(source_files, secure_file_ops=None, logger=None):
+    """
+    Find build.rs scripts and the files they include
+
+    Args:
+        source_files (dict): Relative file path -> file info with 'language' and 'absolute_path'
+        secure_file_ops (SecureFileOps|None): Secure file operations or None
+        logger (Logger|None): Optional logger
+
+    Returns:
+        dict: build.rs path -> {'includes': [...], 'native_libraries': [{'name', 'kind', 'file', 'line'}]}
+    """
+    scripts = {}
+    for rel_path in sorted(source_files):
+        if PurePosixPath(rel_path).name != "build.rs" or source_files[rel_path].get("language") != "rust":
+            continue
+        script = scripts[rel_path] = {"includes": [], "native_libraries": []}
+        pending, seen = [rel_path], {rel_path}
+        while pending:
+            current = pending.pop(0)
+            try:
+                content = read_repo_file(source_files[current]["absolute_path"], secure_file_ops)
+            except Exception as exc:
+                if logger:
+                    logger.warning(f"Could not read build script {current}: {exc}")
+                continue
+            for number, line in enumerate(content.splitlines(), 1):
+                for value in LINK_LIB_RE.findall(line):
+                    parsed = parse_link_lib(value)
+                    if parsed:
+                        name, kind = parsed
+                        script["native_libraries"].append({"name": name, "kind": kind, "file": current, "line": number})
+                for target in INCLUDE_RE.findall(line):
+                    included = join_dir(str(PurePosixPath(current).parent), target)
+                    if included in source_files and included not in seen:
+                        seen.add(included)
+                        script["includes"].append(included)
+                        pending.append(included)
+    return scripts
+
+
+def annotate_build_scripts(scripts, external_packages, file_imports, file_import_annotations):
+    """
+    Mark build-script imports with the build-script scope and list the crates each script imports
+
+    Args:
+        scripts (dict): Result of collect_build_scripts, updated in place with 'imports'
+        external_packages (dict): Package metadata keyed by distribution name
+        file_imports (dict): Relative file path -> list of import names
+        file_import_annotations (dict): Relative file path -> {import name: annotations}, updated in place
+
+    Returns:
+        list: Reports {'file', 'includes', 'imports': [{'import', 'package', 'dep_kind'}], 'native_libraries'}
+    """
+    by_import = {}
+    for name, info in external_packages.items():
+        if info.get("ecosystem") == "cargo":
+            for import_name in info.get("import_names") or [name.replace("-", "_")]:
+                by_import.setdefault(import_name, name)
+
+    reports = []
+    for rel_path, script in sorted(scripts.items()):
+        imports = {}
+        for path in [rel_path] + script["includes"]:
+            annotations = file_import_annotations.setdefault(path, {})
+            for import_name in file_imports.get(path, []):
+                annotations.setdefault(import_name, {})["scope"] = BUILD_SCRIPT_SCOPE
+                package = by_import.get(import_name)
+                imports.setdefault(
+                    import_name,
+                    {
+                        "import": import_name,
+                        "package": package,
+                        "dep_kind": external_packages[package].get("dep_kind") if package else None,
+                    },
+                )
+            if not annotations:
+                del file_import_annotations[path]
+        reports.append({"file": rel_path, **script, "imports": [imports[name] for name in sorted(imports)]})
+    return reports
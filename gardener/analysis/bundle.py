@@ -0,0 +1,269 @@
+"""
+Air-gapped analysis bundles
+
+`gardener bundle --repos repos.txt --out bundle/` snapshots each listed repository and records
+every registry response its analysis needs. `gardener <repo> --from-bundle bundle/` then runs
+against the snapshot with all registry lookups answered from the recording and no network
+access, so a funding round can be re-run and audited offline
+"""
+
+import argparse
+import contextlib
+import json
+import os
+import shutil
+import urllib.error
+import urllib.request
+
+from gardener.package_metadata import url_resolver
+from gardener.package_metadata.url_resolver import resolve_package_urls
+
+BUNDLE_FORMAT = 1
+BUNDLE_MANIFEST = "bundle.json"
+RESPONSES_FILE = "responses.json"
+REPOS_DIR = "repos"
+
+
+def read_repo_list(path):
+    """
+    Read repository URLs or local paths, one per line
+
+    Args:
+        path (str): List file; blank lines and `#` comments are ignored
+
+    Returns:
+        list: Repository inputs in file order
+    """
+    with open(path, "r", encoding="utf-8") as handle:
+        lines = [line.split("#", 1)[0].strip() for line in handle]
+    return [line for line in lines if line]
+
+
+def _normalize_source(repo_input):
+    """
+    Normalize a repository input for matching against bundled sources
+
+    Args:
+        repo_input (str): Repository URL or path
+
+    Returns:
+        str: Comparable form without trailing slash or `.git`
+    """
+    normalized = repo_input.strip().rstrip("/")
+    if normalized.endswith(".git"):
+        normalized = normalized[:-4]
+    return normalized.lower() if "://" in normalized else os.path.abspath(normalized)
+
+
+def _bundle_dir_name(repo_input, taken):
+    """
+    Pick a unique directory name for a repository snapshot
+
+    Args:
+        repo_input (str): Repository URL or path
+        taken (set): Names already used in this bundle, updated in place
+
+    Returns:
+        str: Directory name under `repos/`
+    """
+    parts = _normalize_source(repo_input).split("/")
+    base = "_".join(parts[-2:]) if "://" in repo_input else parts[-1]
+    name, suffix = base or "repo", 2
+    while name in taken:
+        name, suffix = f"{base}_{suffix}", suffix + 1
+    taken.add(name)
+    return name
+
+
+class ResponseRecorder:
+    """
+    Request hook that performs real registry requests and keeps every response
+
+    Responses are stored as text; a 404 is stored as None so the replay answers it the same way
+    """
+
+    def __init__(self):
+        self.responses = {}
+
+    def __call__(self, url):
+        if url in self.responses:
+            return self.responses[url]
+        request = urllib.request.Request(url, headers={"User-Agent": url_resolver.USER_AGENT})
+        try:
+            with urllib.request.urlopen(request, timeout=url_resolver.REQUEST_TIMEOUT) as response:
+                content = response.read().decode("utf-8", errors="ignore")
+        except urllib.error.HTTPError as exc:
+            if exc.code != 404:
+                raise
+            content = None
+        self.responses[url] = content
+        return content
+
+
+class ResponseReplayer:
+    """
+    Request hook that answers only from recorded responses
+
+    Unrecorded URLs are answered as not found and listed in `misses`
+    """
+
+    def __init__(self, responses):
+        self.responses = responses
+        self.misses = []
+
+    def __call__(self, url):
+        if url not in self.responses:
+            self.misses.append(url)
+            return None
+        return self.responses[url]
+
+
+@contextlib.contextmanager
+def request_hook(hook):
+    """
+    Route url_resolver requests through a hook for the duration of the block
+
+    Args:
+        hook (callable): Function taking a URL and returning response text or None
+    """
+    url_resolver.set_request_fn(hook)
+    try:
+        yield hook
+    finally:
+        url_resolver.set_request_fn(None)
+
+
+def _commit_of(path):
+    """
+    Return the checked-out commit of a git repository, or None
+
+    Args:
+        path (str): Repository path
+    """
+    try:
+        import git
+
+        return git.Repo(path).head.commit.hexsha
+    except Exception:
+        return None
+
+
+def create_bundle(repo_inputs, out_dir, logger, focus_languages=None):
+    """
+    Snapshot repositories and record the registry responses their analysis needs
+
+    Args:
+        repo_inputs (list): Repository URLs or local paths
+        out_dir (str): Bundle directory to create
+        logger (Logger): Logger for progress
+        focus_languages (list|None): Languages to analyze, or None for all
+
+    Returns:
+        dict: Bundle manifest as written to `bundle.json`
+    """
+    from gardener.analysis.main import DependencyAnalyzer
+    from gardener.analysis.reproduce import distribution_version
+    from gardener.common.utils import get_repo
+
+    os.makedirs(os.path.join(out_dir, REPOS_DIR), exist_ok=True)
+    recorder = ResponseRecorder()
+    repos, taken = [], set()
+    for repo_input in repo_inputs:
+        source_path = get_repo(repo_input)
+        name = _bundle_dir_name(repo_input, taken)
+        snapshot = os.path.join(out_dir, REPOS_DIR, name)
+        logger.info(f"Bundling {repo_input} -> {snapshot}")
+        if os.path.exists(snapshot):
+            shutil.rmtree(snapshot)
+        shutil.copytree(source_path, snapshot, symlinks=True, ignore=shutil.ignore_patterns(".git"))
+
+        analyzer = DependencyAnalyzer(verbose=False)
+        with request_hook(recorder):
+            packages = analyzer.discover_packages(os.path.abspath(snapshot), focus_languages)
+            resolve_package_urls(packages, analyzer.logger)
+        repos.append(
+            {
+                "source": repo_input,
+                "path": f"{REPOS_DIR}/{name}",
+                "commit": _commit_of(source_path),
+                "packages": len(packages),
+            }
+        )
+
+    with open(os.path.join(out_dir, RESPONSES_FILE), "w", encoding="utf-8") as handle:
+        json.dump(recorder.responses, handle, indent=2, sort_keys=True)
+    manifest = {
+        "format": BUNDLE_FORMAT,
+        "gardener_version": distribution_version("gardener") or "unknown",
+        "focus_languages": focus_languages,
+        "repos": repos,
+        "responses": len(recorder.responses),
+    }
+    with open(os.path.join(out_dir, BUNDLE_MANIFEST), "w", encoding="utf-8") as handle:
+        json.dump(manifest, handle, indent=2)
+    logger.info(f"Bundle written to {out_dir}: {len(repos)} repositories, {len(recorder.responses)} responses")
+    return manifest
+
+
+class Bundle:
+    """
+    A bundle loaded for offline analysis
+
+    Attributes:
+        path (str): Bundle directory
+        manifest (dict): Contents of `bundle.json`
+        replayer (ResponseReplayer): Request hook answering from the recorded responses
+    """
+
+    def __init__(self, path):
+        self.path = os.path.abspath(path)
+        with open(os.path.join(self.path, BUNDLE_MANIFEST), "r", encoding="utf-8") as handle:
+            self.manifest = json.load(handle)
+        if self.manifest.get("format") != BUNDLE_FORMAT:
+            raise ValueError(f"Unsupported bundle format {self.manifest.get('format')} in {path}")
+        with open(os.path.join(self.path, RESPONSES_FILE), "r", encoding="utf-8") as handle:
+            self.replayer = ResponseReplayer(json.load(handle))
+
+    def repo_path(self, repo_input):
+        """
+        Return the snapshot path for a repository input, matched by URL/path or snapshot name
+
+        Args:
+            repo_input (str): Repository URL or path as given on the command line
+
+        Returns:
+            str|None: Absolute snapshot path, or None when the repository is not bundled
+        """
+        wanted = _normalize_source(repo_input)
+        for repo in self.manifest.get("repos", []):
+            if _normalize_source(repo["source"]) == wanted or os.path.basename(repo["path"]) == repo_input:
+                return os.path.join(self.path, repo["path"])
+        return None
+
+
+def main(argv=None):
+    """
+    Entry point for `gardener bundle --repos <repos.txt> --out <dir>`
+
+    Args:
+        argv (list|None): Arguments after the `bundle` subcommand
+
+    Returns:
+        int: Process exit status
+    """
+    from gardener.common.utils import Logger, RepositoryError
+
+    parser = argparse.ArgumentParser(prog="gardener bundle")
+    parser.add_argument("--repos", required=True, help="File listing repository URLs or paths, one per line")
+    parser.add_argument("--out", required=True, help="Bundle directory to write")
+    parser.add_argument("-l", "--languages", help="Comma-separated list of languages to focus on")
+    args = parser.parse_args(argv)
+
+    logger = Logger(verbose=True)
+    focus_languages = [lang.strip().lower() for lang in args.languages.split(",")] if args.languages else None
+    try:
+        create_bundle(read_repo_list(args.repos), args.out, logger, focus_languages)
+    except (OSError, RepositoryError) as exc:
+        logger.error(f"Bundle failed: {exc}")
+        return 1
+    return 0
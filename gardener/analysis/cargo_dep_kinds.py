@@ -0,0 +1,95 @@
+"""
+Cargo dependency kinds against import evidence
+
+Cargo crates carry a `dep_kind` from the table declaring them: `normal` ([dependencies]), `dev`
+([dev-dependencies]) or `build` ([build-dependencies]); crates declared only under
+[target.'cfg(...)'.*] tables also list those `targets`. After import extraction each crate gets
+the `import_contexts` it is used from — `build` (build.rs and the files it includes), `dev`
+(tests/, benches/, examples/, and doctests, which compile against dev-dependencies) and `normal`
+(everything else) — and a `dep_kind_mismatch` note when the two disagree
+"""
+
+from pathlib import PurePosixPath
+
+from gardener.treewalk.rust import DOCTEST_SCOPE
+
+DEV_DIRS = ("tests", "benches", "examples")
+
+
+def import_context(rel_path, build_files=()):
+    """
+    Return the dependency kind a Rust file compiles with: 'build', 'dev' or 'normal'
+
+    Args:
+        rel_path (str): Relative file path
+        build_files (Collection): Files included by build scripts, which also compile as 'build'
+    """
+    path = PurePosixPath(rel_path)
+    if path.name == "build.rs" or rel_path in build_files:
+        return "build"
+    if any(part in DEV_DIRS for part in path.parts[:-1]):
+        return "dev"
+    return "normal"
+
+
+def _mismatch(dep_kind, contexts):
+    """
+    Describe a disagreement between the declared kind and the import contexts, or None
+    """
+    if dep_kind == "dev" and "normal" in contexts:
+        return "dev-dependency imported outside tests/, benches/ and examples/"
+    if dep_kind == "build" and contexts - {"build"}:
+        return "build-dependency imported outside build.rs"
+    if dep_kind == "normal" and contexts == {"dev"}:
+        return "only imported from tests/, benches/ or examples/; could be a dev-dependency"
+    if dep_kind in ("normal", "dev") and contexts == {"build"}:
+        return "only imported from build.rs; should be a build-dependency"
+    return None
+
+
+def cross_check_dep_kinds(
+    external_packages, file_imports, source_files, logger=None, build_files=(), file_import_annotations=None
+):
+    """
+    Annotate Cargo packages with their import contexts and any dep_kind mismatch
+
+    Args:
+        external_packages (dict): Package metadata keyed by distribution name, updated in place
+        file_imports (dict): Relative file path -> list of import names
+        source_files (dict): Relative file path -> file info with 'language'
+        logger (Logger|None): Optional logger
+        build_files (Collection): Files included by build scripts
+        file_import_annotations (dict|None): Relative file path -> import name -> annotations; imports
+            with `scope: doctest` count as the 'dev' context
+
+    Returns:
+        dict: Distribution name -> mismatch note for the crates that disagree
+    """
+    by_import = {}
+    for name, info in external_packages.items():
+        if info.get("ecosystem") == "cargo" and info.get("dep_kind"):
+            for import_name in info.get("import_names") or [name.replace("-", "_")]:
+                by_import.setdefault(import_name, name)
+
+    contexts = {}
+    for rel_path, names in file_imports.items():
+        if (source_files.get(rel_path) or {}).get("language") != "rust":
+            continue
+        annotations = (file_import_annotations or {}).get(rel_path) or {}
+        for import_name in names:
+            name = by_import.get(import_name)
+            if name:
+                doctest = (annotations.get(import_name) or {}).get("scope") == DOCTEST_SCOPE
+                contexts.setdefault(name, set()).add("dev" if doctest else import_context(rel_path, build_files))
+
+    mismatches = {}
+    for name, used_in in contexts.items():
+        info = external_packages[name]
+        info["import_contexts"] = sorted(used_in)
+        note = _mismatch(info["dep_kind"], used_in)
+        if note:
+            info["dep_kind_mismatch"] = note
+            mismatches[name] = note
+            if logger:
+                logger.debug(f"Cargo crate '{name}' ({info['dep_kind']}): {note}")
+    return mismatches
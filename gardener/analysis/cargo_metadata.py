@@ -0,0 +1,219 @@
+"""
+Optional `cargo metadata` integration
+
+The static Cargo analysis reads manifests and lockfiles without a toolchain. With
+USE_CARGO_METADATA (`--use-cargo-metadata`) and `cargo` on the PATH, `cargo metadata
+--format-version 1` is also run for every workspace in the repository and its resolved package
+graph is merged in: the crates Cargo resolved each dependency to (renames included), the features
+enabled on them, the exact versions of transitive crates, proc-macro targets and workspace members
+the static reading missed. Entries confirmed by Cargo carry `cargo_metadata: true`, and the run is
+summarized under `analyzer_details.cargo_metadata`. Without a toolchain, or when the command fails,
+the static results are kept as they are
+"""
+
+import json
+import os
+import shutil
+from pathlib import PurePosixPath
+
+from gardener.analysis.cargo_proc_macros import PROC_MACRO
+from gardener.analysis.cargo_workspace import WORKSPACE_MEMBER
+from gardener.common.defaults import GraphAnalysisConfig as cfg
+from gardener.common.network import is_offline
+from gardener.common.subprocess import SecureSubprocess, SubprocessSecurityError
+from gardener.treewalk.rust import stronger_dep_kind
+
+METADATA_TIMEOUT = 300  # seconds
+# Large workspaces print tens of megabytes of metadata
+METADATA_MAX_OUTPUT = 64 * 1024 * 1024
+# Cargo needs its home directories and toolchain selection to find the registry cache
+CARGO_ENV_VARS = ("HOME", "CARGO_HOME", "RUSTUP_HOME", "RUSTUP_TOOLCHAIN")
+DEP_KINDS = {None: "normal", "dev": "dev", "build": "build"}
+
+
+def _relative(path, repo_path):
+    """
+    Return a repository-relative POSIX path for an absolute path reported by Cargo, or None outside it
+    """
+    rel = os.path.relpath(os.path.realpath(path), os.path.realpath(repo_path))
+    return None if rel.startswith("..") else PurePosixPath(*rel.split(os.sep)).as_posix()
+
+
+def run_cargo_metadata(repo_path, crates, logger=None, cargo=None):
+    """
+    Run `cargo metadata` once per workspace in the repository
+
+    `--locked` is always passed, so Cargo never writes or updates a Cargo.lock in the analyzed
+    repository (a workspace whose lockfile is missing or stale keeps the static results).
+    `--offline` is added for bundle runs and under STRICT_NETWORK, so the crates index is not
+    updated either
+
+    Args:
+        repo_path (str): Absolute repository path
+        crates (dict): Crate directory -> (relative manifest path, parsed TOML) from read_cargo_manifests
+        logger (Logger|None): Optional logger
+        cargo (str|None): cargo executable; looked up on the PATH when None
+
+    Returns:
+        list: (relative manifest path, metadata dict) per workspace; empty without a toolchain
+    """
+    cargo = cargo or shutil.which("cargo")
+    if not cargo:
+        if logger:
+            logger.warning("cargo not found in PATH; --use-cargo-metadata falls back to static Cargo analysis")
+        return []
+    runner = SecureSubprocess(
+        allowed_root=repo_path,
+        timeout=METADATA_TIMEOUT,
+        max_output_size=METADATA_MAX_OUTPUT,
+        allowed_env_vars=CARGO_ENV_VARS,
+        extra_path_dirs=[os.path.dirname(cargo)],
+    )
+    results, covered = [], set()
+    # Shallowest manifests first, so a workspace root covers its members
+    for crate_dir in sorted(crates, key=lambda crate_dir: (len(PurePosixPath(crate_dir).parts), crate_dir)):
+        manifest = crates[crate_dir][0]
+        if manifest in covered:
+            continue
+        command = [cargo, "metadata", "--format-version", "1", "--locked", "--manifest-path", manifest]
+        if is_offline() or cfg.STRICT_NETWORK:
+            # Resolve from the local registry cache instead of updating the crates index
+            command.append("--offline")
+        try:
+            completed = runner.run(command, cwd=repo_path, env=dict(os.environ), capture_output=True, check=True)
+            metadata = json.loads(completed.stdout)
+        except (SubprocessSecurityError, ValueError) as exc:
+            if logger:
+                logger.warning(f"cargo metadata failed for {manifest}: {exc}")
+            covered.add(manifest)
+            continue
+        for package in metadata.get("packages") or []:
+            rel = _relative(package.get("manifest_path") or "", repo_path)
+            if rel:
+                covered.add(rel)
+        covered.add(manifest)
+        results.append((manifest, metadata))
+    return results
+
+
+def _lib_target(package):
+    """
+    Return the library target of a metadata package (lib, rlib, proc-macro ...), or None
+    """
+    for target in package.get("targets") or []:
+        if {"lib", "rlib", "dylib", "cdylib", "staticlib", "proc-macro"} & set(target.get("kind") or []):
+            return target
+    return None
+
+
+def merge_cargo_metadata(external_packages, workspace_members, results, repo_path, logger=None):
+    """
+    Merge resolved `cargo metadata` package graphs into the static Cargo analysis
+
+    Args:
+        external_packages (dict): Package metadata keyed by distribution name, updated in place
+        workspace_members (dict): Workspace member entries keyed by crate name, updated in place
+        results (list): Result of run_cargo_metadata
+        repo_path (str): Absolute repository path
+        logger (Logger|None): Optional logger
+
+    Returns:
+        dict|None: {'manifests', 'packages', 'workspace_members', 'added'}, or None without results
+    """
+    if not results:
+        return None
+    added = set()
+    members_seen = set()
+    package_count = 0
+    for _, metadata in results:
+        packages = {package["id"]: package for package in metadata.get("packages") or [] if package.get("id")}
+        package_count += len(packages)
+        members = set(metadata.get("workspace_members") or [])
+        nodes = {node["id"]: node for node in (metadata.get("resolve") or {}).get("nodes") or [] if node.get("id")}
+
+        for package_id in sorted(members & set(packages)):
+            package = packages[package_id]
+            name = package["name"]
+            members_seen.add(name)
+            if (external_packages.get(name) or {}).get("ecosystem") == "cargo":
+                del external_packages[name]
+            manifest = _relative(package.get("manifest_path") or "", repo_path)
+            lib = _lib_target(package)
+            entry = workspace_members.setdefault(
+                name,
+                {
+                    "annotation": WORKSPACE_MEMBER,
+                    "manifest": manifest,
+                    "path": str(PurePosixPath(manifest).parent) if manifest else None,
+                    "root": _relative(lib["src_path"], repo_path) if lib and lib.get("src_path") else None,
+                    "import_names": [],
+                    "via": [],
+                },
+            )
+            import_name = (lib or {}).get("name") or name
+            if import_name.replace("-", "_") not in entry["import_names"]:
+                entry["import_names"].append(import_name.replace("-", "_"))
+            if "cargo-metadata" not in entry["via"]:
+                entry["via"].append("cargo-metadata")
+
+        direct = set()
+        for package_id in sorted(members & set(nodes)):
+            for dep in nodes[package_id].get("deps") or []:
+                target = packages.get(dep.get("pkg"))
+                if target is None or dep.get("pkg") in members:
+                    continue
+                direct.add(dep["pkg"])
+                entry = external_packages.get(target["name"])
+                if entry is None:
+                    if not target.get("source"):
+                        # A path crate outside the workspace the manifests did not reveal is local code
+                        continue
+                    entry = external_packages[target["name"]] = {"ecosystem": "cargo"}
+                    added.add(target["name"])
+                elif entry.get("ecosystem", "cargo") != "cargo":
+                    continue
+                # `dep.name` is the name code uses: the rename, or the library name
+                import_name = str(dep.get("name") or target["name"]).replace("-", "_")
+                import_names = entry.setdefault("import_names", [target["name"].replace("-", "_")])
+                if import_name not in import_names:
+                    import_names.append(import_name)
+                for dep_kind in dep.get("dep_kinds") or [{}]:
+                    kind = DEP_KINDS.get(dep_kind.get("kind"), "normal")
+                    entry["dep_kind"] = stronger_dep_kind(entry.get("dep_kind"), kind)
+                entry["direct"] = True
+                entry.pop("transitive", None)
+
+        for package_id, package in sorted(packages.items()):
+            if package_id in members:
+                continue
+            name = package["name"]
+            entry = external_packages.get(name)
+            if entry is None:
+                if not package.get("source"):
+                    continue
+                entry = external_packages[name] = {"ecosystem": "cargo", "direct": False, "transitive": True}
+                added.add(name)
+            elif entry.get("ecosystem", "cargo") != "cargo":
+                continue
+            entry["cargo_metadata"] = True
+            if package_id in direct or not entry.get("locked_version"):
+                entry["locked_version"] = str(package.get("version", ""))
+            features = (nodes.get(package_id) or {}).get("features")
+            if features:
+                entry["enabled_features"] = sorted(set(entry.get("enabled_features") or []) | set(features))
+            lib = _lib_target(package)
+            if lib and "proc-macro" in (lib.get("kind") or []) and not entry.get("role"):
+                entry["role"] = PROC_MACRO
+                entry["role_source"] = "cargo-metadata"
+
+    if logger:
+        logger.info(
+            f"... Merged cargo metadata for {len(results)} workspaces ({package_count} packages, "
+            f"{len(added)} not found statically)"
+        )
+    return {
+        "manifests": [manifest for manifest, _ in results],
+        "packages": package_count,
+        "workspace_members": sorted(members_seen),
+        "added": sorted(added),
+    }
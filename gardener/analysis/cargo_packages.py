@@ -0,0 +1,80 @@
+"""
+Rust evidence grouped by Cargo package
+
+Repositories often hold several Cargo packages that share no workspace (a library next to a CLI
+and a fuzzing harness, each with its own Cargo.toml), packages nested inside one another, and
+crates with both a src/lib.rs and a src/main.rs root. Every Cargo.toml with a [package] is read;
+each package owns the Rust files under its directory (nested packages keep their own) and
+`crate::` paths resolve against the root of the target a file belongs to. After import extraction
+the evidence is grouped per package under `analyzer_details.rust_packages` instead of one flat
+file list: the package's `path`, `manifest`, whether it is a `workspace_member`, its target
+`roots`, the crates it imports with the files importing each, its module graph (`modules`: file ->
+the files of the package it links to) and the other packages its files reach through local
+imports (`depends_on`)
+"""
+
+from pathlib import PurePosixPath
+
+
+def _owner(rel_path, crate_dirs):
+    """
+    Return the innermost crate directory containing a file, or None
+    """
+    parents = {str(parent) for parent in PurePosixPath(rel_path).parents}
+    return next((crate_dir for crate_dir in crate_dirs if crate_dir in parents), None)
+
+
+def group_by_package(crate_table, external_packages, workspace_members, file_imports, local_imports_map):
+    """
+    Group Rust import evidence and module links by the Cargo package owning each file
+
+    Args:
+        crate_table (dict): Result of cargo_targets.crate_target_table
+        external_packages (dict): Package metadata keyed by distribution name
+        workspace_members (dict): Workspace member entries keyed by crate name
+        file_imports (dict): Relative file path -> list of import names
+        local_imports_map (dict): Relative file path -> local files it imports
+
+    Returns:
+        list: {'package', 'path', 'manifest', 'workspace_member', 'roots', 'files', 'imports', 'modules',
+            'depends_on'} per package, sorted by path
+    """
+    by_import = {}
+    for name, info in external_packages.items():
+        if info.get("ecosystem") == "cargo":
+            for import_name in info.get("import_names") or [name.replace("-", "_")]:
+                by_import.setdefault(import_name, name)
+    # Innermost package first, so nested packages claim their own files
+    crate_dirs = sorted(crate_table, key=lambda crate_dir: (-len(PurePosixPath(crate_dir).parts), crate_dir))
+
+    reports = []
+    for crate_dir, crate in sorted(crate_table.items()):
+        files = set(crate["files"])
+        imports, modules, depends_on = {}, {}, set()
+        for rel_path in crate["files"]:
+            for import_name in file_imports.get(rel_path) or []:
+                name = by_import.get(import_name)
+                if name and rel_path not in imports.setdefault(name, []):
+                    imports[name].append(rel_path)
+            for target in local_imports_map.get(rel_path) or []:
+                if target in files:
+                    if target != rel_path:
+                        modules.setdefault(rel_path, []).append(target)
+                else:
+                    owner = _owner(target, crate_dirs)
+                    if owner is not None and owner != crate_dir:
+                        depends_on.add(owner)
+        reports.append(
+            {
+                "package": crate["name"],
+                "path": crate_dir,
+                "manifest": crate["manifest"],
+                "workspace_member": crate["name"] in workspace_members,
+                "roots": [target["path"] for target in crate["targets"]],
+                "files": len(files),
+                "imports": {name: sorted(paths) for name, paths in sorted(imports.items())},
+                "modules": {path: sorted(set(targets)) for path, targets in sorted(modules.items())},
+                "depends_on": sorted(depends_on),
+            }
+        )
+    return reports
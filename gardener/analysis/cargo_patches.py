@@ -0,0 +1,81 @@
+"""
+Cargo [patch] and [replace] overrides
+
+A workspace can swap a dependency for a git fork or a local checkout with `[patch.crates-io]`
+(or `[patch."<git url>"]` for git sources) and the deprecated `[replace]` table. The package keeps
+its original identity, so URL resolution still credits the upstream crate, but it is flagged
+`patched: true` with a `patch` record of the original source and the effective one that actually
+gets built
+"""
+
+from gardener.treewalk.rust import GIT_PIN_KEYS
+
+
+def effective_source(spec):
+    """
+    Describe the source a patch or replace entry substitutes
+
+    Args:
+        spec (str|dict): Entry value, e.g. { git = "...", branch = "fix" } or { path = "../foo" }
+
+    Returns:
+        dict: {'git', 'rev'|'tag'|'branch'}, {'path'} or {'registry'?, 'version'?}; None when unusable
+    """
+    if isinstance(spec, str):
+        return {"version": spec}
+    if not isinstance(spec, dict):
+        return None
+    if isinstance(spec.get("path"), str):
+        return {"path": spec["path"]}
+    if isinstance(spec.get("git"), str):
+        source = {"git": spec["git"]}
+        source.update({key: str(spec[key]) for key in GIT_PIN_KEYS if isinstance(spec.get(key), str)})
+        return source
+    source = {key: spec[key] for key in ("registry", "version") if isinstance(spec.get(key), str)}
+    return source or None
+
+
+def _overrides(data):
+    """
+    Yield (crate name, original source, original version, spec, section) for a manifest's overrides
+    """
+    patch = data.get("patch")
+    for registry, table in (patch or {}).items() if isinstance(patch, dict) else []:
+        for key, spec in (table or {}).items() if isinstance(table, dict) else []:
+            name = spec.get("package", key) if isinstance(spec, dict) else key
+            yield str(name), registry, None, spec, f"patch.{registry}"
+    replace = data.get("replace")
+    for key, spec in (replace or {}).items() if isinstance(replace, dict) else []:
+        # Package ID spec: 'name', 'name:version' or 'name@version'
+        name, _, version = str(key).replace("@", ":").partition(":")
+        yield name, "crates-io", version or None, spec, "replace"
+
+
+def apply_cargo_patches(external_packages, crates, logger=None):
+    """
+    Flag Cargo packages overridden by a [patch] or [replace] entry in the repository's manifests
+
+    Args:
+        external_packages (dict): Package metadata keyed by distribution name, updated in place
+        crates (dict): Crate directory -> (relative manifest path, parsed TOML) from read_cargo_manifests
+        logger (Logger|None): Optional logger
+
+    Returns:
+        list: Sorted names of the patched packages
+    """
+    patched = set()
+    for _, (rel_path, data) in sorted(crates.items()):
+        for name, original_source, original_version, spec, section in _overrides(data):
+            entry = external_packages.get(name)
+            effective = effective_source(spec)
+            if entry is None or entry.get("ecosystem", "cargo") != "cargo" or effective is None:
+                continue
+            original = {"name": name, "source": original_source}
+            if original_version:
+                original["version"] = original_version
+            entry["patched"] = True
+            entry["patch"] = {"manifest": rel_path, "section": section, "original": original, "effective": effective}
+            patched.add(name)
+    if logger and patched:
+        logger.info(f"... {len(patched)} Cargo packages are patched: {', '.join(sorted(patched))}")
+    return sorted(patched)
@@ -0,0 +1,49 @@
+"""
+Procedural macro crates
+
+Proc-macro crates (`[lib] proc-macro = true`) run inside the compiler and are never linked into
+the built artifact, so they are tagged `role: proc-macro` for scoring to tell them apart. The
+flag is read from Cargo.toml files in the repository (vendored crates and workspace members) and,
+for crates.io dependencies, from the registry metadata fetched during URL resolution
+(`role_source` is 'manifest' or 'registry')
+"""
+
+PROC_MACRO = "proc-macro"
+
+
+def is_proc_macro_manifest(data):
+    """
+    Return True when a parsed Cargo.toml declares a proc-macro library
+    """
+    lib = data.get("lib") if isinstance(data, dict) else None
+    return isinstance(lib, dict) and (lib.get("proc-macro") is True or lib.get("proc_macro") is True)
+
+
+def tag_local_proc_macros(external_packages, workspace_members, crates, logger=None):
+    """
+    Tag Cargo packages and workspace members whose manifest in the repository is a proc-macro crate
+
+    Args:
+        external_packages (dict): Package metadata keyed by distribution name, updated in place
+        workspace_members (dict): Workspace member entries keyed by crate name, updated in place
+        crates (dict): Crate directory -> (relative manifest path, parsed TOML) from read_cargo_manifests
+        logger (Logger|None): Optional logger
+
+    Returns:
+        list: Sorted names of the tagged crates
+    """
+    tagged = set()
+    for _, data in crates.values():
+        package = data.get("package")
+        name = package.get("name") if isinstance(package, dict) else None
+        if not isinstance(name, str) or not is_proc_macro_manifest(data):
+            continue
+        for entries in (external_packages, workspace_members):
+            entry = entries.get(name)
+            if entry is not None and entry.get("ecosystem", "cargo") == "cargo":
+                entry["role"] = PROC_MACRO
+                entry["role_source"] = "manifest"
+                tagged.add(name)
+    if logger and tagged:
+        logger.debug(f"Proc-macro crates declared in the repository: {', '.join(sorted(tagged))}")
+    return sorted(tagged)
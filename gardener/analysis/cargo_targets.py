@@ -0,0 +1,214 @@
+"""
+Rust compile targets
+
+A crate compiles into several targets: its library, binaries, examples, benchmarks and
+integration tests, each with dependencies of its own. Targets come from the `[lib]`, `[[bin]]`,
+`[[example]]`, `[[bench]]` and `[[test]]` sections of Cargo.toml and from Cargo's conventional
+layout (src/lib.rs, src/main.rs, src/bin/, examples/, benches/, tests/), with build.rs as the
+`build-script`. Every Rust file of a crate is assigned the kind of the target it belongs to, and
+each Cargo package lists the `target_kinds` of the files importing it, so a crate only used in
+examples/ stands apart from one the library target needs
+"""
+
+from pathlib import PurePosixPath
+
+from gardener.analysis.cargo_unused import crate_files as rust_crate_files
+from gardener.analysis.cargo_unused import packaged_crates
+
+LIB, BIN, EXAMPLE, BENCH, TEST, BUILD_SCRIPT = "lib", "bin", "example", "bench", "test", "build-script"
+# Manifest array sections, the conventional directory of their targets and the [package] key turning off discovery
+TARGET_SECTIONS = {
+    "bin": (BIN, "src/bin", "autobins"),
+    "example": (EXAMPLE, "examples", "autoexamples"),
+    "bench": (BENCH, "benches", "autobenches"),
+    "test": (TEST, "tests", "autotests"),
+}
+# Order in which kinds are listed
+KIND_ORDER = (LIB, BIN, EXAMPLE, BENCH, TEST, BUILD_SCRIPT)
+
+
+def _join(crate_dir, path):
+    return path if crate_dir == "." else f"{crate_dir}/{path}"
+
+
+def _auto_targets(crate_dir, files, directory):
+    """
+    Yield (name, root) for the targets Cargo discovers in a directory: DIR/NAME.rs and DIR/NAME/main.rs
+    """
+    base = PurePosixPath(_join(crate_dir, directory))
+    for rel_path in sorted(files):
+        parts = PurePosixPath(rel_path).relative_to(base).parts if base in PurePosixPath(rel_path).parents else ()
+        if len(parts) == 1 and parts[0].endswith(".rs"):
+            yield parts[0][:-3], rel_path
+        elif len(parts) == 2 and parts[1] == "main.rs":
+            yield parts[0], rel_path
+
+
+def crate_targets(crate_dir, data, crate_files):
+    """
+    List the compile targets of a crate
+
+    Args:
+        crate_dir (str): Crate directory ('.' for the repository root)
+        data (dict): Parsed Cargo.toml
+        crate_files (Collection): Relative paths of the crate's Rust files
+
+    Returns:
+        list: {'kind', 'name', 'path'} per target, declared targets first
+    """
+    package = data.get("package") or {}
+    name = str(package.get("name", "")).replace("-", "_")
+    targets = {}
+
+    def add(kind, target_name, path):
+        if path and path not in targets:
+            targets[path] = {"kind": kind, "name": target_name, "path": path}
+
+    lib = data.get("lib") if isinstance(data.get("lib"), dict) else {}
+    if lib.get("path"):
+        add(LIB, lib.get("name") or name, _join(crate_dir, lib["path"]))
+    for section, (kind, directory, _) in TARGET_SECTIONS.items():
+        for target in data.get(section) or []:
+            if isinstance(target, dict) and target.get("name"):
+                default = "src/main.rs" if kind == BIN and target["name"] == package.get("name") else None
+                path = target.get("path") or default or f"{directory}/{target['name']}.rs"
+                add(kind, str(target["name"]), _join(crate_dir, path))
+
+    if lib.get("path") is None and _join(crate_dir, "src/lib.rs") in crate_files:
+        add(LIB, lib.get("name") or name, _join(crate_dir, "src/lib.rs"))
+    if _join(crate_dir, "src/main.rs") in crate_files:
+        add(BIN, str(package.get("name", "")), _join(crate_dir, "src/main.rs"))
+    for kind, directory, auto in TARGET_SECTIONS.values():
+        if package.get(auto) is not False:
+            for target_name, path in _auto_targets(crate_dir, crate_files, directory):
+                add(kind, target_name, path)
+    build = package.get("build")
+    if build is not False:
+        build_path = _join(crate_dir, build if isinstance(build, str) else "build.rs")
+        if build_path in crate_files:
+            add(BUILD_SCRIPT, "build-script-build", build_path)
+    return list(targets.values())
+
+
+def file_target_kind(rel_path, crate_dir, targets):
+    """
+    Return the kind of the target a Rust file of a crate compiles into
+
+    Target roots map to their kind. Other files belong to the target whose root directory contains
+    them (examples/, benches/, tests/ and src/bin/ subdirectories), and the rest of src/ to the
+    library, or to the binary of a crate without one
+
+    Args:
+        rel_path (str): Relative file path
+        crate_dir (str): Crate directory
+        targets (list): Result of crate_targets
+
+    Returns:
+        str|None: Target kind, or None for a file outside every target
+    """
+    by_path = {target["path"]: target["kind"] for target in targets}
+    if rel_path in by_path:
+        return by_path[rel_path]
+    parents = PurePosixPath(rel_path).parents
+    src = PurePosixPath(_join(crate_dir, "src"))
+    # Deepest enclosing target directory first; src/ itself is decided below
+    for target in sorted(targets, key=lambda target: -len(PurePosixPath(target["path"]).parts)):
+        root_dir = PurePosixPath(target["path"]).parent
+        if root_dir in parents and root_dir != src and root_dir != PurePosixPath(crate_dir):
+            return target["kind"]
+    for kind, directory, _ in TARGET_SECTIONS.values():
+        if PurePosixPath(_join(crate_dir, directory)) in parents:
+            return kind
+    if src in parents:
+        kinds = {target["kind"] for target in targets}
+        return LIB if LIB in kinds or BIN not in kinds else BIN
+    return None
+
+
+def crate_target_table(crates, source_files):
+    """
+    List the Rust files and compile targets of every crate in the repository
+
+    Args:
+        crates (dict): Crate directory -> (relative manifest path, parsed TOML) from read_cargo_manifests
+        source_files (dict): Relative file path -> file info with 'language'
+
+    Returns:
+        dict: Crate directory -> {'name', 'manifest', 'files', 'targets'}; nested crates own their files
+    """
+    packaged = packaged_crates(crates)
+    # Every Rust file, not only those with imports, so target roots are discovered
+    files = rust_crate_files(packaged, source_files, source_files)
+    return {
+        crate_dir: {
+            "name": str(data["package"]["name"]),
+            "manifest": manifest,
+            "files": files[crate_dir],
+            "targets": crate_targets(crate_dir, data, set(files[crate_dir])),
+        }
+        for crate_dir, (manifest, data) in sorted(packaged.items())
+    }
+
+
+def crate_editions(crates):
+    """
+    Map every crate directory to its Rust edition
+
+    Args:
+        crates (dict): Crate directory -> (relative manifest path, parsed TOML) from read_cargo_manifests
+
+    Returns:
+        dict: Crate directory -> edition ('2015' when the manifest sets none, as Cargo does);
+            `edition.workspace = true` takes the workspace's `[workspace.package] edition`
+    """
+    workspace_edition = None
+    for _, data in crates.values():
+        edition = ((data.get("workspace") or {}).get("package") or {}).get("edition")
+        if isinstance(edition, str):
+            workspace_edition = edition
+    editions = {}
+    for crate_dir, (_, data) in packaged_crates(crates).items():
+        edition = (data.get("package") or {}).get("edition")
+        if isinstance(edition, dict) and edition.get("workspace"):
+            edition = workspace_edition
+        editions[crate_dir] = str(edition) if edition else "2015"
+    return editions
+
+
+def classify_targets(external_packages, crates, file_imports, source_files, logger=None):
+    """
+    Tag every Rust file of the repository's crates with its target kind and record the kinds per package
+
+    Args:
+        external_packages (dict): Package metadata keyed by distribution name, updated in place
+        crates (dict): Crate directory -> (relative manifest path, parsed TOML) from read_cargo_manifests
+        file_imports (dict): Relative file path -> list of import names
+        source_files (dict): Relative file path -> file info with 'language'
+        logger (Logger|None): Optional logger
+
+    Returns:
+        tuple: ([{'crate', 'manifest', 'targets'}] per crate, {relative file path: target kind})
+    """
+    reports, file_kinds = [], {}
+    for crate_dir, crate in crate_target_table(crates, source_files).items():
+        reports.append({"crate": crate["name"], "manifest": crate["manifest"], "targets": crate["targets"]})
+        for rel_path in crate["files"]:
+            kind = file_target_kind(rel_path, crate_dir, crate["targets"])
+            if kind:
+                file_kinds[rel_path] = kind
+
+    by_import = {}
+    for name, info in external_packages.items():
+        if info.get("ecosystem") == "cargo":
+            for import_name in info.get("import_names") or [name.replace("-", "_")]:
+                by_import.setdefault(import_name, name)
+    kinds = {}
+    for rel_path, kind in file_kinds.items():
+        for import_name in file_imports.get(rel_path) or []:
+            if import_name in by_import:
+                kinds.setdefault(by_import[import_name], set()).add(kind)
+    for name, used_in in kinds.items():
+        external_packages[name]["target_kinds"] = [kind for kind in KIND_ORDER if kind in used_in]
+    if logger and reports:
+        logger.debug(f"Classified {len(file_kinds)} Rust files into the targets of {len(reports)} crates")
+    return reports, file_kinds
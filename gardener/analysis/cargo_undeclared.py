@@ -0,0 +1,78 @@
+"""
+Crates used without being declared
+
+The counterpart of analysis/cargo_unused.py: a crate whose files import a crate missing from its
+own Cargo.toml relies on something outside its manifest, typically a dependency declared by a
+sibling crate, a dependency removed from the manifest but still referenced behind a `#[cfg]` that
+is not compiled, or a name that only resolves transitively. Such imports are listed per crate
+under `analyzer_details.used_undeclared` with the files using them.
+
+Only path evidence counts (`use foo::Bar`, `#[foo::main]`, `foo::bar()` when foo is declared
+elsewhere in the repository): a bare attribute such as `#[inline]` or a derive helper such as
+`#[serde(...)]` is recorded as an import but names no crate. The crate's own library name (used by
+its tests, examples and doctests), workspace members and the standard library crates are never
+reported
+"""
+
+from gardener.analysis.cargo_unused import _declared_dependencies, crate_files, packaged_crates
+from gardener.treewalk.rust import RustImportVisitor
+
+# Crates every crate can name without declaring them
+BUILTIN_CRATES = RustImportVisitor.STD_CRATES | {"proc_macro", "test"}
+
+
+def _own_import_names(data):
+    """
+    Return the names a crate's own targets are imported under (package name and [lib] name)
+    """
+    names = {str(data["package"]["name"]).replace("-", "_")}
+    lib = data.get("lib")
+    if isinstance(lib, dict) and isinstance(lib.get("name"), str):
+        names.add(lib["name"].replace("-", "_"))
+    return names
+
+
+def find_undeclared_imports(
+    crates, workspace_members, file_imports, file_package_components, source_files, logger=None
+):
+    """
+    List the crates each crate's files import without declaring them in its Cargo.toml
+
+    Args:
+        crates (dict): Crate directory -> (relative manifest path, parsed TOML) from read_cargo_manifests
+        workspace_members (dict): Workspace member entries keyed by crate name
+        file_imports (dict): Relative file path -> list of import names
+        file_package_components (dict): Relative file path -> list of (root, path) components
+        source_files (dict): Relative file path -> file info with 'language'
+        logger (Logger|None): Optional logger
+
+    Returns:
+        list: {'ecosystem', 'package', 'manifest', 'imports': [{'name', 'files'}]} per crate with undeclared
+            imports
+    """
+    packaged = packaged_crates(crates)
+    members = {name.replace("-", "_") for name in workspace_members}
+    reports = []
+    for crate_dir, rel_paths in sorted(crate_files(packaged, file_imports, source_files).items()):
+        manifest, data = packaged[crate_dir]
+        allowed = BUILTIN_CRATES | members | _own_import_names(data)
+        allowed |= {key.replace("-", "_") for key, _, _, _ in _declared_dependencies(data)}
+        undeclared = {}
+        for rel_path in rel_paths:
+            roots = {root for root, _ in file_package_components.get(rel_path) or []}
+            for name in file_imports[rel_path]:
+                if name in roots and name not in allowed:
+                    undeclared.setdefault(name, []).append(rel_path)
+        if undeclared:
+            reports.append(
+                {
+                    "ecosystem": "cargo",
+                    "package": str(data["package"]["name"]),
+                    "manifest": manifest,
+                    "imports": [{"name": name, "files": undeclared[name]} for name in sorted(undeclared)],
+                }
+            )
+    if logger and reports:
+        count = sum(len(report["imports"]) for report in reports)
+        logger.info(f"... {count} crates are imported without being declared in the importing crate's Cargo.toml")
+    return reports
@@ -0,0 +1,129 @@
+"""
+Declared-but-unused Cargo dependencies
+
+Every crate in the repository (a Cargo.toml with a [package]) owns the Rust files under its
+directory, minus those of crates nested inside it. After import extraction each dependency the
+crate declares, in any [dependencies], [dev-dependencies] or [build-dependencies] table
+(target-specific ones included), is looked up in the evidence collected from those files: `use`
+declarations, `extern crate`, crate attributes, fully-qualified paths and doctests. Dependencies
+with no evidence are listed per crate under `analyzer_details.unused_declared`, and a package that
+no declaring crate uses is flagged `unused_declared: true` so funders can down-weight stale
+manifest entries. Workspace members referenced with `{ path = "..." }` resolve as local code and
+are not checked
+"""
+
+from pathlib import PurePosixPath
+
+from gardener.treewalk.rust import DEPENDENCY_KINDS
+
+
+def _declared_dependencies(data):
+    """
+    Yield (key, package name, dep_kind, target) for every dependency a manifest declares
+    """
+    tables = [(data, None)]
+    targets = data.get("target")
+    if isinstance(targets, dict):
+        tables += [(table, target) for target, table in targets.items() if isinstance(table, dict)]
+    for table, target in tables:
+        for section, dep_kind in DEPENDENCY_KINDS.items():
+            deps = table.get(section)
+            for key, spec in (deps or {}).items() if isinstance(deps, dict) else []:
+                package = spec.get("package") if isinstance(spec, dict) else None
+                yield str(key), str(package or key), dep_kind, target
+
+
+def packaged_crates(crates):
+    """
+    Keep the manifests that define a crate ([package] with a name), dropping virtual workspace roots
+
+    Args:
+        crates (dict): Crate directory -> (relative manifest path, parsed TOML) from read_cargo_manifests
+
+    Returns:
+        dict: The same mapping restricted to crates
+    """
+    return {
+        crate_dir: entry
+        for crate_dir, entry in crates.items()
+        if isinstance(entry[1].get("package"), dict) and entry[1]["package"].get("name")
+    }
+
+
+def crate_files(packaged, file_imports, source_files):
+    """
+    Assign each Rust file with imports to the innermost crate containing it
+
+    Args:
+        packaged (dict): Result of packaged_crates
+        file_imports (dict): Relative file path -> list of import names
+        source_files (dict): Relative file path -> file info with 'language'
+
+    Returns:
+        dict: Crate directory -> sorted relative file paths (every crate present, possibly empty)
+    """
+    # Innermost crate first, so nested crates claim their own files
+    crate_dirs = sorted(packaged, key=lambda crate_dir: (-len(PurePosixPath(crate_dir).parts), crate_dir))
+    files = {crate_dir: [] for crate_dir in packaged}
+    for rel_path in sorted(file_imports):
+        if (source_files.get(rel_path) or {}).get("language") != "rust":
+            continue
+        parents = {str(parent) for parent in PurePosixPath(rel_path).parents}
+        crate_dir = next((crate_dir for crate_dir in crate_dirs if crate_dir in parents), None)
+        if crate_dir is not None:
+            files[crate_dir].append(rel_path)
+    return files
+
+
+def find_unused_dependencies(external_packages, crates, workspace_members, file_imports, source_files, logger=None):
+    """
+    List the dependencies each crate declares but never imports, and flag packages unused everywhere
+
+    Args:
+        external_packages (dict): Package metadata keyed by distribution name, updated in place
+        crates (dict): Crate directory -> (relative manifest path, parsed TOML) from read_cargo_manifests
+        workspace_members (dict): Workspace member entries keyed by crate name
+        file_imports (dict): Relative file path -> list of import names
+        source_files (dict): Relative file path -> file info with 'language'
+        logger (Logger|None): Optional logger
+
+    Returns:
+        list: {'crate', 'manifest', 'unused': [{'name', 'package', 'dep_kind'[, 'target']}]} per crate with
+            unused dependencies
+    """
+    packaged = packaged_crates(crates)
+    imported = {
+        crate_dir: {name for rel_path in rel_paths for name in file_imports[rel_path]}
+        for crate_dir, rel_paths in crate_files(packaged, file_imports, source_files).items()
+    }
+
+    reports, used, unused = [], set(), set()
+    for crate_dir in sorted(packaged):
+        rel_path, data = packaged[crate_dir]
+        entries = {}
+        for key, package, dep_kind, target in _declared_dependencies(data):
+            entry = external_packages.get(package)
+            if package in workspace_members or entry is None or entry.get("ecosystem") != "cargo":
+                continue
+            if key.replace("-", "_") in imported[crate_dir]:
+                used.add(package)
+                continue
+            unused.add(package)
+            item = {"name": key, "package": package, "dep_kind": dep_kind}
+            if target is not None:
+                item["target"] = str(target)
+            entries[(key, dep_kind, item.get("target", ""))] = item
+        if entries:
+            reports.append(
+                {
+                    "crate": str(data["package"]["name"]),
+                    "manifest": rel_path,
+                    "unused": [entries[key] for key in sorted(entries)],
+                }
+            )
+
+    for package in unused - used:
+        external_packages[package]["unused_declared"] = True
+    if logger and reports:
+        logger.info(f"... {len(unused - used)} declared Cargo dependencies are never imported")
+    return reports
@@ -0,0 +1,153 @@
+"""
+Vendored Cargo sources
+
+`cargo vendor` copies every dependency into a directory and the repository's
+`.cargo/config.toml` redirects crates.io to it:
+
+    [source.crates-io]
+    replace-with = "vendored-sources"
+
+    [source.vendored-sources]
+    directory = "vendor"
+
+The copies are registry packages, not project code: their sources and manifests are left out of the
+scan (so their own dependencies are not reported as the project's), and the declared packages they
+copy are flagged `vendored: true` with their `vendored_path`. The directory and the crates found
+there are listed under `analyzer_details.cargo_vendor`
+"""
+
+import tomllib
+from pathlib import PurePosixPath
+
+from gardener.common.repo_paths import normalize_dir
+
+CARGO_CONFIG_FILES = (".cargo/config.toml", ".cargo/config")
+
+
+def read_vendor_config(secure_file_ops, logger=None):
+    """
+    Find the vendored directory crates.io is replaced with in the repository's Cargo config
+
+    Args:
+        secure_file_ops (SecureFileOps|None): Secure file operations or None
+        logger (Logger|None): Optional logger
+
+    Returns:
+        dict|None: {'config', 'source', 'directory'} with the repository-relative directory, or None
+    """
+    if not secure_file_ops:
+        return None
+    for config_path in CARGO_CONFIG_FILES:
+        if not secure_file_ops.is_file(config_path):
+            continue
+        try:
+            data = tomllib.loads(secure_file_ops.read_file(config_path))
+        except Exception as exc:
+            if logger:
+                logger.warning(f"Could not read Cargo config {config_path}: {exc}")
+            return None
+        sources = data.get("source") if isinstance(data.get("source"), dict) else {}
+        name, seen = "crates-io", set()
+        while isinstance(sources.get(name), dict) and sources[name].get("replace-with") and name not in seen:
+            seen.add(name)
+            name = str(sources[name]["replace-with"])
+        directory = (sources.get(name) or {}).get("directory") if name != "crates-io" else None
+        if not isinstance(directory, str) or PurePosixPath(directory).is_absolute():
+            return None
+        directory = normalize_dir(directory)
+        if directory in (None, "."):
+            return None
+        return {"config": config_path, "source": name, "directory": directory}
+    return None
+
+
+def _is_vendored(rel_path, directory):
+    """
+    Return True when a repository-relative path lies inside the vendored directory
+    """
+    return PurePosixPath(rel_path).as_posix().startswith(directory + "/")
+
+
+def exclude_vendored_files(vendor, source_files, manifest_files, secure_file_ops):
+    """
+    Drop the vendored directory's sources and manifests from the scan results
+
+    Args:
+        vendor (dict): Result of read_vendor_config
+        source_files (dict): Relative path -> file info, updated in place
+        manifest_files (list): Absolute manifest paths, updated in place
+        secure_file_ops (SecureFileOps): Secure file operations
+
+    Returns:
+        int: Number of source files dropped
+    """
+    directory = vendor["directory"]
+    dropped = [rel_path for rel_path in source_files if _is_vendored(rel_path, directory)]
+    for rel_path in dropped:
+        del source_files[rel_path]
+    manifest_files[:] = [
+        path for path in manifest_files if not _is_vendored(secure_file_ops.get_relative_path(path), directory)
+    ]
+    return len(dropped)
+
+
+def read_vendored_crates(vendor, secure_file_ops, logger=None):
+    """
+    Read the Cargo.toml of every crate in the vendored directory
+
+    Args:
+        vendor (dict): Result of read_vendor_config
+        secure_file_ops (SecureFileOps): Secure file operations
+        logger (Logger|None): Optional logger
+
+    Returns:
+        dict: Crate directory -> (relative manifest path, parsed TOML), like read_cargo_manifests
+    """
+    crates = {}
+    try:
+        entries = sorted(secure_file_ops.list_dir(vendor["directory"]), key=lambda entry: entry.name)
+    except Exception as exc:
+        if logger:
+            logger.warning(f"Could not list vendored Cargo sources in {vendor['directory']}: {exc}")
+        return crates
+    for entry in entries:
+        crate_dir = f"{vendor['directory']}/{entry.name}"
+        manifest = f"{crate_dir}/Cargo.toml"
+        if not secure_file_ops.is_file(manifest):
+            continue
+        try:
+            crates[crate_dir] = (manifest, tomllib.loads(secure_file_ops.read_file(manifest)))
+        except Exception as exc:
+            if logger:
+                logger.warning(f"Could not read vendored Cargo manifest {manifest}: {exc}")
+    return crates
+
+
+def tag_vendored_crates(external_packages, vendor, vendored_crates, logger=None):
+    """
+    Flag the Cargo packages copied into the vendored directory
+
+    Args:
+        external_packages (dict): Package metadata keyed by distribution name, updated in place
+        vendor (dict): Result of read_vendor_config
+        vendored_crates (dict): Result of read_vendored_crates
+        logger (Logger|None): Optional logger
+
+    Returns:
+        dict: vendor with the 'crates' found there ({'name', 'version', 'path'})
+    """
+    crates = []
+    for crate_dir, (_, data) in sorted(vendored_crates.items()):
+        package = data.get("package") if isinstance(data.get("package"), dict) else {}
+        name = package.get("name")
+        if not isinstance(name, str):
+            continue
+        version = package.get("version") if isinstance(package.get("version"), str) else None
+        crates.append({"name": name, "version": version, "path": crate_dir})
+        entry = external_packages.get(name)
+        if entry is not None and entry.get("ecosystem", "cargo") == "cargo":
+            entry["vendored"] = True
+            entry.setdefault("vendored_path", crate_dir)
+    if logger:
+        logger.info(f"... Found {len(crates)} vendored Cargo crates in {vendor['directory']}")
+    return {**vendor, "crates": crates}
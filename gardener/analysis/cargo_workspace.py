@@ -0,0 +1,160 @@
+"""
+Cargo workspace members
+
+Sibling crates of a Cargo workspace are part of the analyzed repository, not crates.io packages.
+A crate counts as a member when a `[workspace] members` glob covers its directory (minus
+`exclude`) or when another manifest in the repository depends on it with `{ path = "..." }`.
+Members are dropped from the external packages, and `use member_crate::...` resolves to the
+member's sources, so its imports become local edges. Members are reported under
+`analyzer_details.workspace_members` with the `workspace-member` annotation
+"""
+
+import fnmatch
+import tomllib
+from pathlib import PurePosixPath
+
+from gardener.common.file_helpers import read_repo_file
+from gardener.common.repo_paths import join_dir, normalize_dir
+
+WORKSPACE_MEMBER = "workspace-member"
+DEPENDENCY_TABLES = ("dependencies", "dev-dependencies", "build-dependencies")
+
+
+def _glob_matches(rel_dir, pattern):
+    """
+    Match a directory against a Cargo members glob; `*` does not cross path separators
+    """
+    dir_parts = [] if rel_dir == "." else rel_dir.split("/")
+    pattern_parts = [] if pattern == "." else pattern.split("/")
+    return len(dir_parts) == len(pattern_parts) and all(
+        fnmatch.fnmatchcase(part, glob) for part, glob in zip(dir_parts, pattern_parts)
+    )
+
+
+def _dependency_tables(data):
+    """
+    Yield every dependency table of a manifest, including target-specific ones and [workspace.dependencies]
+    """
+    for key in DEPENDENCY_TABLES:
+        yield data.get(key)
+    workspace = data.get("workspace")
+    if isinstance(workspace, dict):
+        yield workspace.get("dependencies")
+    for target in (data.get("target") or {}).values():
+        if isinstance(target, dict):
+            for key in DEPENDENCY_TABLES:
+                yield target.get(key)
+
+
+def _lib_root(crate_dir, data, source_files):
+    """
+    Return the member's crate root file: `[lib] path`, else src/lib.rs, else src/main.rs
+    """
+    lib = data.get("lib") if isinstance(data.get("lib"), dict) else {}
+    candidates = [lib.get("path"), "src/lib.rs", "src/main.rs"]
+    for candidate in candidates:
+        if candidate:
+            root = join_dir(crate_dir, candidate)
+            if root in source_files:
+                return root
+    return None
+
+
+def read_cargo_manifests(manifest_files, secure_file_ops, logger, repo_path):
+    """
+    Parse every Cargo.toml among the manifest files
+
+    Args:
+        manifest_files (list): Absolute manifest paths; only Cargo.toml files are read
+        secure_file_ops (SecureFileOps|None): Secure file operations or None
+        logger (Logger|None): Optional logger
+        repo_path (str): Absolute repository path
+
+    Returns:
+        dict: Normalized crate directory -> (relative manifest path, parsed TOML)
+    """
+    crates = {}
+    for path in manifest_files:
+        if PurePosixPath(path).name != "Cargo.toml":
+            continue
+        try:
+            if secure_file_ops:
+                rel_path = secure_file_ops.get_relative_path(path)
+            else:
+                rel_path = str(PurePosixPath(path).relative_to(repo_path))
+            data = tomllib.loads(read_repo_file(path, secure_file_ops))
+        except Exception as exc:
+            if logger:
+                logger.warning(f"Could not read Cargo manifest {path}: {exc}")
+            continue
+        crates[normalize_dir(str(PurePosixPath(rel_path).parent))] = (rel_path, data)
+    return crates
+
+
+def collect_workspace_members(manifest_files, secure_file_ops, logger, repo_path, source_files=None, crates=None):
+    """
+    Collect the Cargo crates of this repository referenced as workspace members or path dependencies
+
+    Args:
+        manifest_files (list): Absolute manifest paths; only Cargo.toml files are read
+        secure_file_ops (SecureFileOps|None): Secure file operations or None
+        logger (Logger|None): Optional logger
+        repo_path (str): Absolute repository path
+        source_files (dict): Optional relative path -> file info, used to locate each member's crate root
+        crates (dict): Optional result of read_cargo_manifests, to avoid reading the manifests again
+
+    Returns:
+        dict: Crate name -> {'annotation': 'workspace-member', 'manifest', 'path', 'root',
+            'import_names', 'via'}, where via lists 'workspace' and/or 'path-dependency'
+    """
+    source_files = source_files or {}
+    if crates is None:
+        crates = read_cargo_manifests(manifest_files, secure_file_ops, logger, repo_path)
+
+    members = {}
+
+    def _add(crate_dir, via, import_name=None):
+        if crate_dir not in crates:
+            return
+        rel_path, data = crates[crate_dir]
+        package = data.get("package")
+        name = package.get("name") if isinstance(package, dict) else None
+        if not isinstance(name, str):
+            return
+        entry = members.setdefault(
+            name,
+            {
+                "annotation": WORKSPACE_MEMBER,
+                "manifest": rel_path,
+                "path": crate_dir,
+                "root": _lib_root(crate_dir, data, source_files),
+                "import_names": [],
+                "via": [],
+            },
+        )
+        lib = data.get("lib") if isinstance(data.get("lib"), dict) else {}
+        for candidate in (lib.get("name"), name, import_name):
+            if isinstance(candidate, str) and candidate.replace("-", "_") not in entry["import_names"]:
+                entry["import_names"].append(candidate.replace("-", "_"))
+        if via not in entry["via"]:
+            entry["via"].append(via)
+
+    for crate_dir, (rel_path, data) in sorted(crates.items()):
+        workspace = data.get("workspace")
+        if isinstance(workspace, dict):
+            excluded = {join_dir(crate_dir, p) for p in workspace.get("exclude") or [] if isinstance(p, str)}
+            for pattern in workspace.get("members") or []:
+                if not isinstance(pattern, str):
+                    continue
+                glob = join_dir(crate_dir, pattern.rstrip("/"))
+                for candidate in sorted(crates):
+                    if glob and candidate not in excluded and _glob_matches(candidate, glob):
+                        _add(candidate, "workspace")
+        for table in _dependency_tables(data):
+            for dep_name, spec in (table or {}).items() if isinstance(table, dict) else []:
+                if isinstance(spec, dict) and isinstance(spec.get("path"), str):
+                    _add(join_dir(crate_dir, spec["path"]), "path-dependency", dep_name)
+
+    if logger and members:
+        logger.info(f"... Found {len(members)} Cargo workspace member crates: {', '.join(sorted(members))}")
+    return members
@@ -0,0 +1,343 @@
+"""
+Fixture-based conformance harness
+
+Runs the analysis pipeline offline over a directory of fixture repositories and checks each
+one against the expectations in its `gardener-expected.yml` (or `.json`) file. In watch mode
+the language handler modules are reloaded and the suite re-run whenever a handler or fixture
+changes, so visitor edits are picked up without restarting the process
+"""
+
+import argparse
+import importlib
+import inspect
+import json
+import os
+import sys
+import time
+from pathlib import Path
+
+try:
+    import yaml
+except Exception:  # pragma: no cover - optional dependency
+    yaml = None
+
+EXPECTATION_FILENAMES = ("gardener-expected.yml", "gardener-expected.yaml", "gardener-expected.json")
+
+# Packages whose loaded modules are reloaded in watch mode (this harness excepted)
+RELOADABLE_PACKAGES = ("gardener.treewalk", "gardener.analysis")
+
+
+def load_expectations(path):
+    """
+    Load a fixture expectation file
+
+    Args:
+        path (str): Path to a YAML or JSON expectation file
+
+    Returns:
+        dict: Parsed expectations
+    """
+    with open(path, "r", encoding="utf-8") as handle:
+        if path.endswith(".json"):
+            return json.load(handle) or {}
+        if yaml is None:
+            raise RuntimeError("pyyaml is not installed; cannot load YAML expectations")
+        return yaml.safe_load(handle) or {}
+
+
+def discover_fixtures(fixtures_dir):
+    """
+    Find fixture repositories that carry an expectation file
+
+    Args:
+        fixtures_dir (str): Directory holding one fixture repository per subdirectory,
+            or a single fixture repository
+
+    Returns:
+        list: (fixture name, absolute fixture path, expectation file path) tuples sorted by name
+    """
+    root = Path(fixtures_dir).resolve()
+    candidates = [root] + sorted(p for p in root.iterdir() if p.is_dir() and not p.name.startswith("."))
+    fixtures = []
+    for candidate in candidates:
+        for filename in EXPECTATION_FILENAMES:
+            expectation = candidate / filename
+            if expectation.is_file():
+                fixtures.append((candidate.name, str(candidate), str(expectation)))
+                break
+    return fixtures
+
+
+def graph_spec_failures(graph_data, spec):
+    """
+    Collect mismatches between a serialized graph and the `nodes`/`edges` sections of a spec
+
+    Args:
+        graph_data (dict): node_link_data structure
+        spec (dict): Spec with sections like {'nodes': {'file': {'include': [...]}}, 'edges': {...}}
+
+    Returns:
+        list: Human-readable failure messages
+    """
+    failures = []
+    by_id = {node.get("id"): node for node in graph_data.get("nodes", [])}
+    links = graph_data.get("links", [])
+
+    for node_type, rules in (spec.get("nodes") or {}).items():
+        for node_id in (rules or {}).get("include", []):
+            node = by_id.get(node_id)
+            if node is None:
+                failures.append(f"Missing required node: {node_id}")
+            elif node_type and node.get("type") != node_type:
+                failures.append(f"Node {node_id} expected type {node_type} but got {node.get('type')}")
+        for node_id in (rules or {}).get("exclude", []):
+            if node_id in by_id:
+                failures.append(f"Unexpected node: {node_id}")
+
+    for edge_type, pairs in (spec.get("edges") or {}).items():
+        for src, dst in pairs:
+            if not any(
+                link.get("source") == src and link.get("target") == dst and link.get("type") == edge_type
+                for link in links
+            ):
+                failures.append(f"Missing required edge: {src} -[{edge_type}]-> {dst}")
+    return failures
+
+
+def expectation_failures(results, expectations):
+    """
+    Compare analysis results with fixture expectations
+
+    Supported sections: `packages` (include/exclude over discovered packages), `imports`
+    (file -> external imports that must be extracted), `nodes` and `edges` (graph spec)
+
+    Args:
+        results (dict): Output of DependencyAnalyzer
+        expectations (dict): Parsed expectation file
+
+    Returns:
+        list: Human-readable failure messages
+    """
+    failures = []
+    packages = set(results.get("external_packages", {}))
+    package_rules = expectations.get("packages") or {}
+    if isinstance(package_rules, list):
+        package_rules = {"include": package_rules}
+    for name in package_rules.get("include", []):
+        if name not in packages:
+            failures.append(f"Missing expected package: {name}")
+    for name in package_rules.get("exclude", []):
+        if name in packages:
+            failures.append(f"Unexpected package: {name}")
+
+    file_imports = results.get("analyzer_details", {}).get("file_imports", {})
+    for rel_path, expected_imports in (expectations.get("imports") or {}).items():
+        found = set(file_imports.get(rel_path, []))
+        for name in expected_imports:
+            if name not in found:
+                failures.append(f"Missing import evidence: {rel_path} -> {name}")
+
+    failures.extend(graph_spec_failures(results.get("dependency_graph") or {}, expectations))
+    return failures
+
+
+def run_fixture(fixture_path, expectations, verbose=False):
+    """
+    Analyze one fixture without network access
+
+    Args:
+        fixture_path (str): Absolute fixture repository path
+        expectations (dict): Parsed expectations; `languages` limits the handlers used
+        verbose (bool): Enable verbose analysis logging
+
+    Returns:
+        dict: Analysis results
+    """
+    from gardener.common.utils import Logger
+
+    # Resolve through the module so reloaded handler classes are used
+    main_mod = importlib.import_module("gardener.analysis.main")
+    analyzer = main_mod.DependencyAnalyzer(verbose=verbose, logger=Logger(verbose=verbose, quiet=True))
+    external_packages = analyzer.discover_packages(fixture_path, expectations.get("languages"))
+    for package_info in external_packages.values():
+        package_info.setdefault("repository_url", "")
+    return analyzer.analyze_dependencies(external_packages)
+
+
+def run_conformance(fixtures_dir, logger, verbose=False):
+    """
+    Run every fixture under a directory and report results
+
+    Args:
+        fixtures_dir (str): Fixture directory
+        logger (Logger): Logger for the report
+        verbose (bool): Enable verbose analysis logging
+
+    Returns:
+        dict: Map of fixture name to list of failure messages (empty when passing)
+    """
+    fixtures = discover_fixtures(fixtures_dir)
+    if not fixtures:
+        logger.warning(f"No fixtures with {EXPECTATION_FILENAMES[0]} found under {fixtures_dir}")
+    report = {}
+    for name, fixture_path, expectation_path in fixtures:
+        try:
+            expectations = load_expectations(expectation_path)
+            failures = expectation_failures(run_fixture(fixture_path, expectations, verbose), expectations)
+        except Exception as exc:
+            failures = [f"Analysis error: {exc}"]
+        report[name] = failures
+        # The logger suppresses repeated messages; identical failures in different fixtures must all print
+        logger.seen_messages.clear()
+        if failures:
+            logger.info(f"FAIL {name}")
+            for failure in failures:
+                logger.info(f"  - {failure}")
+        else:
+            logger.info(f"PASS {name}")
+    logger.seen_messages.clear()
+    passed = sum(1 for failures in report.values() if not failures)
+    logger.info(f"\n{passed}/{len(report)} fixtures passed")
+    return report
+
+
+def _module_dependencies(module, candidates):
+    """
+    Return the candidate modules a module binds names from (modules, classes, functions)
+    """
+    dependencies = set()
+    for value in vars(module).values():
+        name = value.__name__ if inspect.ismodule(value) else getattr(value, "__module__", None)
+        if name in candidates and name != module.__name__:
+            dependencies.add(name)
+    return dependencies
+
+
+def reloadable_modules():
+    """
+    List the loaded handler and analysis modules in reload order
+
+    Reload order matters: shared helpers come first, then the handlers that import them, then
+    the orchestrator that binds handler classes at import time
+
+    Returns:
+        list: Module names, every module after the modules it imports from
+    """
+    candidates = {
+        name: module
+        for name, module in list(sys.modules.items())
+        if module is not None
+        and name != __name__
+        and any(name.startswith(f"{package}.") for package in RELOADABLE_PACKAGES)
+    }
+    ordered = []
+    visiting = set()
+
+    def visit(name):
+        if name in visiting or name in ordered:
+            return
+        visiting.add(name)
+        for dependency in sorted(_module_dependencies(candidates[name], candidates)):
+            visit(dependency)
+        ordered.append(name)
+
+    for name in sorted(candidates):
+        visit(name)
+    return ordered
+
+
+def reload_language_handlers():
+    """
+    Re-import language handler modules and the orchestrator that binds them
+    """
+    for module_name in reloadable_modules():
+        module = sys.modules.get(module_name)
+        if module is not None:
+            importlib.reload(module)
+
+
+def _watched_mtimes(paths):
+    """
+    Snapshot modification times of files under the given paths
+
+    Args:
+        paths (list): Files or directories to watch
+
+    Returns:
+        dict: Map of file path to mtime
+    """
+    snapshot = {}
+    for base in paths:
+        for dirpath, dirnames, filenames in os.walk(base):
+            dirnames[:] = [d for d in dirnames if not d.startswith(".") and d != "__pycache__"]
+            for filename in filenames:
+                file_path = os.path.join(dirpath, filename)
+                try:
+                    snapshot[file_path] = os.stat(file_path).st_mtime
+                except OSError:
+                    continue
+    return snapshot
+
+
+def watch_conformance(fixtures_dir, logger, verbose=False, interval=1.0):
+    """
+    Re-run the suite whenever a language handler or fixture file changes
+
+    Args:
+        fixtures_dir (str): Fixture directory
+        logger (Logger): Logger for the report
+        verbose (bool): Enable verbose analysis logging
+        interval (float): Polling interval in seconds
+    """
+    treewalk_dir = str(Path(__file__).resolve().parent.parent / "treewalk")
+    watched = [treewalk_dir, fixtures_dir]
+    snapshot = _watched_mtimes(watched)
+    run_conformance(fixtures_dir, logger, verbose)
+    logger.info("Watching for changes (Ctrl-C to stop)")
+    try:
+        while True:
+            time.sleep(interval)
+            current = _watched_mtimes(watched)
+            if current == snapshot:
+                continue
+            snapshot = current
+            logger.info("\nChange detected; reloading language handlers")
+            try:
+                reload_language_handlers()
+            except Exception as exc:
+                logger.error(f"Reload failed: {exc}")
+                continue
+            run_conformance(fixtures_dir, logger, verbose)
+    except KeyboardInterrupt:
+        pass
+
+
+def main(argv=None):
+    """
+    Entry point for `gardener conformance <fixtures_dir>`
+
+    Args:
+        argv (list|None): Arguments after the `conformance` subcommand
+
+    Returns:
+        int: Process exit status (1 when any fixture fails)
+    """
+    from gardener.common.utils import Logger
+
+    parser = argparse.ArgumentParser(prog="gardener conformance")
+    parser.add_argument("fixtures_dir", help="Directory of fixture repositories, each with a gardener-expected.yml")
+    parser.add_argument("-v", "--verbose", action="store_true", help="Enable verbose analysis logging")
+    parser.add_argument(
+        "-w", "--watch", action="store_true", help="Reload language handlers and re-run when sources or fixtures change"
+    )
+    args = parser.parse_args(argv)
+
+    logger = Logger(verbose=True)
+    if not os.path.isdir(args.fixtures_dir):
+        logger.error(f"Fixture directory not found: {args.fixtures_dir}")
+        return 1
+    if args.watch:
+        watch_conformance(args.fixtures_dir, logger, args.verbose)
+        return 0
+    report = run_conformance(args.fixtures_dir, logger, args.verbose)
+    return 1 if any(report.values()) else 0
@@ -0,0 +1,61 @@
+"""
+Aggregated import evidence
+
+By default the report lists evidence per file (`analyzer_details.file_imports` and
+`file_package_components`). On generated-heavy repositories thousands of files carry the same
+observation, so with `EVIDENCE = "aggregated"` (CLI `--evidence aggregated`) identical
+observations are collapsed into counted groups under `analyzer_details.evidence`:
+`{"imports": [{"import", "count", "sample_files"}], "components": [{"package", "component",
+"count", "sample_files"}]}`. The dependency graph is unchanged
+"""
+
+from collections import defaultdict
+
+EVIDENCE_MODES = ("files", "aggregated")
+
+
+def _groups(observations, sample_size, fields):
+    """
+    Collapse (key, file) observations into counted groups, largest first
+
+    Args:
+        observations (dict): Observation key tuple -> set of files
+        sample_size (int): Number of files kept per group
+        fields (tuple): Names of the key tuple's elements in the output
+
+    Returns:
+        list: Groups {<fields>, 'count', 'sample_files'}
+    """
+    groups = []
+    for key, files in sorted(observations.items(), key=lambda item: (-len(item[1]), item[0])):
+        group = dict(zip(fields, key))
+        group["count"] = len(files)
+        group["sample_files"] = sorted(files)[:sample_size]
+        groups.append(group)
+    return groups
+
+
+def aggregate_evidence(file_imports, file_package_components, sample_size=5):
+    """
+    Group identical per-file import observations
+
+    Args:
+        file_imports (dict): Relative file path -> list of import names
+        file_package_components (dict): Relative file path -> list of (package, component) pairs
+        sample_size (int): Number of example files kept per group
+
+    Returns:
+        dict: {'imports': [...], 'components': [...]} with groups sorted by descending count
+    """
+    imports = defaultdict(set)
+    for rel_path, names in file_imports.items():
+        for name in names:
+            imports[(name,)].add(rel_path)
+    components = defaultdict(set)
+    for rel_path, pairs in file_package_components.items():
+        for package, component in pairs:
+            components[(package, component)].add(rel_path)
+    return {
+        "imports": _groups(imports, sample_size, ("import",)),
+        "components": _groups(components, sample_size, ("package", "component")),
+    }
@@ -0,0 +1,184 @@
+"""
+Go modules of the repository
+
+Every go.mod in the repository defines one of its own modules, and a go.work lists the modules of a
+workspace (`use ./api`). Import paths equal to or below one of those module paths (`example.com/app`,
+`example.com/app/internal/db`, `example.com/app/tools/gen` from a nested tools/go.mod) are the
+repository's own packages, not external dependencies, and so are modules a go.mod replaces with a
+directory inside the repository. Such imports are dropped from the external imports and linked to
+the non-test files of the package directory as local imports, requirements of sibling modules
+are not reported as packages, and each module is listed under `analyzer_details.workspace_members`
+with `ecosystem: go`
+"""
+
+from pathlib import PurePosixPath
+
+from gardener.analysis.cargo_workspace import WORKSPACE_MEMBER
+from gardener.common.file_helpers import read_repo_file
+from gardener.common.repo_paths import join_dir, normalize_dir
+from gardener.treewalk.go_modules import GO_MOD_FILE, GO_WORK_FILE, parse_go_mod, parse_go_work
+
+
+def _member(manifest, path, via):
+    return {"annotation": WORKSPACE_MEMBER, "ecosystem": "go", "manifest": manifest, "path": path, "via": [via]}
+
+
+def collect_go_modules(manifest_files, secure_file_ops, logger, repo_path):
+    """
+    Collect the Go modules this repository defines
+
+    Args:
+        manifest_files (list): Absolute manifest paths; go.mod and go.work are read
+        secure_file_ops (SecureFileOps|None): Secure file operations or None
+        logger (Logger|None): Optional logger
+        repo_path (str): Absolute repository path
+
+    Returns:
+        dict: Module path -> {'annotation': 'workspace-member', 'ecosystem': 'go', 'manifest', 'path',
+            'via'[, 'replaces']}; 'replaces' lists other module paths a go.mod replaces with the module
+    """
+    parsed = {}
+    for path in sorted(manifest_files):
+        basename = PurePosixPath(path).name
+        if basename not in (GO_MOD_FILE, GO_WORK_FILE):
+            continue
+        try:
+            if secure_file_ops:
+                rel_path = secure_file_ops.get_relative_path(path)
+            else:
+                rel_path = str(PurePosixPath(path).relative_to(repo_path))
+            content = read_repo_file(path, secure_file_ops)
+            parsed[rel_path] = parse_go_mod(content) if basename == GO_MOD_FILE else parse_go_work(content)
+        except Exception as exc:
+            if logger:
+                logger.debug(f"Could not read the Go module file {path}: {exc}")
+
+    members, by_dir = {}, {}
+    for rel_path, go_mod in parsed.items():
+        if PurePosixPath(rel_path).name == GO_MOD_FILE and go_mod["module"]:
+            module_dir = normalize_dir(str(PurePosixPath(rel_path).parent))
+            members.setdefault(go_mod["module"], _member(rel_path, module_dir, GO_MOD_FILE))
+            by_dir.setdefault(module_dir, go_mod["module"])
+
+    for rel_path, parsed_file in parsed.items():
+        base_dir = normalize_dir(str(PurePosixPath(rel_path).parent))
+        if PurePosixPath(rel_path).name == GO_WORK_FILE:
+            for use in parsed_file["use"]:
+                module = by_dir.get(join_dir(base_dir, use))
+                if module and GO_WORK_FILE not in members[module]["via"]:
+                    members[module]["via"].append(GO_WORK_FILE)
+            continue
+        for replace in parsed_file["replace"]:
+            target = join_dir(base_dir, replace["new_path"]) if replace["local"] else None
+            if target is None or replace["path"] in members:
+                continue
+            module = by_dir.get(target)
+            if module:
+                replaces = members[module].setdefault("replaces", [])
+                if replace["path"] not in replaces:
+                    replaces.append(replace["path"])
+            else:
+                # A directory of the repository without a go.mod of its own
+                members[replace["path"]] = _member(rel_path, target, "replace")
+
+    if logger and members:
+        logger.info(f"... Found {len(members)} Go modules defined by the repository: {', '.join(sorted(members))}")
+    return members
+
+
+def module_roots(members):
+    """
+    Map every import path prefix of the repository's Go modules to the module directory
+
+    Args:
+        members (dict): Workspace members; those with ecosystem 'go' are used
+
+    Returns:
+        dict: Module path (or replaced module path) -> repository-relative directory
+    """
+    roots = {}
+    for name, member in members.items():
+        if member.get("ecosystem") != "go":
+            continue
+        for module in [name] + list(member.get("replaces") or []):
+            roots.setdefault(module, member["path"])
+    return roots
+
+
+def module_for_import(import_path, roots):
+    """
+    Return the longest module path of the repository that an import path is equal to or below
+
+    Args:
+        import_path (str): Go import path
+        roots (dict): Result of module_roots
+
+    Returns:
+        str|None: Module path, or None for imports of other modules
+    """
+    matching = [module for module in roots if import_path == module or import_path.startswith(module + "/")]
+    return max(matching, key=len) if matching else None
+
+
+def drop_internal_packages(external_packages, members, logger=None):
+    """
+    Remove requirements of the repository's own Go modules from the external packages
+
+    Args:
+        external_packages (dict): Package metadata keyed by name, updated in place
+        members (dict): Result of collect_go_modules
+
+    Returns:
+        list: Names dropped, sorted
+    """
+    roots = module_roots(members)
+    dropped = sorted(
+        name for name, entry in external_packages.items() if entry.get("ecosystem") == "go" and name in roots
+    )
+    for name in dropped:
+        del external_packages[name]
+    if logger and dropped:
+        logger.debug(f"Dropped the repository's own Go modules: {', '.join(dropped)}")
+    return dropped
+
+
+def internalize_imports(file_imports, local_imports_map, source_files, members):
+    """
+    Turn imports of the repository's own Go packages into local imports of the package's files
+
+    Args:
+        file_imports (dict): Relative file path -> list of import names, updated in place
+        local_imports_map (dict): Relative file path -> list of local file paths, updated in place
+        source_files (dict): Relative file path -> file info with 'language'
+        members (dict): Workspace members; those with ecosystem 'go' are used
+
+    Returns:
+        dict: Relative file path -> import paths made internal, for the files that had any
+    """
+    roots = module_roots(members)
+    if not roots:
+        return {}
+    package_files = {}
+    for rel_path, info in source_files.items():
+        if info.get("language") == "go" and rel_path.endswith(".go") and not rel_path.endswith("_test.go"):
+            package_files.setdefault(str(PurePosixPath(rel_path).parent), []).append(rel_path)
+
+    internal = {}
+    for rel_path, names in file_imports.items():
+        if (source_files.get(rel_path) or {}).get("language") != "go":
+            continue
+        own = [name for name in names if module_for_import(str(name), roots)]
+        if not own:
+            continue
+        internal[rel_path] = own
+        file_imports[rel_path] = [name for name in names if name not in own]
+        local = local_imports_map.setdefault(rel_path, [])
+        for name in own:
+            module = module_for_import(name, roots)
+            package_dir = join_dir(roots[module], name[len(module) :].lstrip("/") or ".")
+            for target in sorted(package_files.get(package_dir) or []):
+                if target != rel_path and target not in local:
+                    local.append(target)
+        if not local:
+            del local_imports_map[rel_path]
+    return internal
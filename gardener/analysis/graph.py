@@ -2,13 +2,64 @@
 Graph building and PageRank calculation
 """
 
-from collections import defaultdict
+from collections import Counter, defaultdict
 
 import networkx as nx
 from gardener.common.language_detection import filename_to_lang
 
 from gardener.analysis.centrality import CentralityCalculator
 from gardener.common.defaults import GraphAnalysisConfig as cfg
+from gardener.treewalk.annotations import is_platform_excluded
+
+
+def import_distribution_resolver(external_packages):
+    """
+    Build a function mapping an import name to the distribution providing it
+
+    The first distribution in lexicographic order claims an import name, and an unknown name falls
+    back to its longest '/'-separated prefix that is known
+
+    Args:
+        external_packages (dict): External packages metadata
+
+    Returns:
+        callable: Import name -> distribution name, or None
+    """
+    import_to_dist = {}
+    for dist in sorted(external_packages):
+        for import_name in external_packages[dist].get("import_names") or [dist]:
+            import_to_dist.setdefault(import_name, dist)
+
+    def dist_for(import_name):
+        prefix = import_name
+        while prefix:
+            if prefix in import_to_dist:
+                return import_to_dist[prefix]
+            prefix = prefix.rsplit("/", 1)[0] if "/" in prefix else ""
+        return None
+
+    return dist_for
+
+
+def preliminary_weights(external_packages, file_imports):
+    """
+    Estimate package weight before the graph is built: the number of source files importing it
+
+    Import names map to distributions with the same lexicographic and longest-prefix rules the
+    graph builder uses, so the estimate tracks the eventual ranking closely
+
+    Args:
+        external_packages (dict): External packages metadata
+        file_imports (dict): File path -> list of imported package names
+
+    Returns:
+        Counter: Distribution name -> number of importing files
+    """
+    dist_for = import_distribution_resolver(external_packages)
+    weights = Counter()
+    for imports in file_imports.values():
+        weights.update({dist_for(name) for name in imports} - {None})
+    return weights
 
 
 class DependencyGraphBuilder:
@@ -32,6 +83,7 @@ class DependencyGraphBuilder:
     _STDLIB_ECOSYSTEM_MAP = {
         "go": "go_stdlib",
         "python": "python_stdlib",
+        "notebook": "python_stdlib",
         "rust": "rust_stdlib",
         "javascript": "js_stdlib",
         "typescript": "ts_stdlib",
@@ -54,6 +106,7 @@ class DependencyGraphBuilder:
         self.local_imports_map = {}
         self.source_files = {}
         self.file_imports = {}
+        self.file_import_annotations = {}
         self.centrality_calculator = CentralityCalculator(logger=logger)
 
         # Initialize instance-level edge weights from configuration so CLI overrides apply
@@ -63,7 +116,13 @@ class DependencyGraphBuilder:
         self.EDGE_W_USES_COMPONENT = cfg.EDGE_W_USES_COMPONENT
 
     def build_dependency_graph(
-        self, source_files, external_packages, file_imports, file_package_components, local_imports_map
+        self,
+        source_files,
+        external_packages,
+        file_imports,
+        file_package_components,
+        local_imports_map,
+        file_import_annotations=None,
     ):
         """
         Build dependency graph focusing on File, Package, and PackageComponent nodes,
@@ -75,6 +134,7 @@ class DependencyGraphBuilder:
             file_imports (dict): Dictionary mapping file paths to lists of imported package names
             file_package_components (dict): Dictionary mapping file paths to lists of (package, component) tuples
             local_imports_map (dict): Dictionary mapping file paths to lists of imported local file paths
+            file_import_annotations (dict): Optional mapping of file paths to {import name: annotations}
 
         Returns:
             NetworkX directed graph
@@ -92,6 +152,7 @@ class DependencyGraphBuilder:
         self.file_imports = file_imports
         self.file_package_components = file_package_components
         self.local_imports_map = local_imports_map
+        self.file_import_annotations = file_import_annotations or {}
 
         # Build import-to-distribution map (logs ambiguous warnings identically)
         self.import_to_dist = self._build_import_to_dist_map(external_packages)
@@ -408,7 +469,7 @@ class DependencyGraphBuilder:
             first_seg = package_name.split("/", 1)[0]
             if "." not in first_seg:
                 ecosystem = self._stdlib_ecosystem_for_language(file_lang)
-        elif file_lang == "python":
+        elif file_lang in ("python", "notebook"):
             ecosystem = self._stdlib_ecosystem_for_language(file_lang)
         elif file_lang == "rust":
             # Recognize Rust standard crates by name
@@ -477,6 +538,12 @@ class DependencyGraphBuilder:
             )
         return dist_node_for_edge
 
+    def _import_annotation(self, file_path, package_name):
+        """
+        Return annotations recorded by the language visitor for an import in a file, if any
+        """
+        return (self.file_import_annotations.get(file_path) or {}).get(package_name)
+
     def _add_imports_package_edge(self, G, file_path, dist_node_for_edge, package_name):
         """
         Add file -> imports_package -> package edge using internal constants
@@ -484,6 +551,9 @@ class DependencyGraphBuilder:
         edge_attrs = {"ident": package_name}
         if package_name in self._ambiguous_choices:
             edge_attrs["ambiguity_resolution"] = "lexicographic"
+        annotation = self._import_annotation(file_path, package_name)
+        if annotation:
+            edge_attrs.update(annotation)
         self._add_edge(
             G,
             file_path,
@@ -623,6 +693,8 @@ class DependencyGraphBuilder:
                 continue
 
             for pkg_name, component_name_from_visitor in components:
+                if is_platform_excluded(self._import_annotation(file_path, pkg_name), cfg.EXCLUDED_PLATFORMS):
+                    continue
                 dist_node, dist, ecosystem = self._resolve_distribution_context(G, pkg_name)
                 if not dist_node:
                     continue
@@ -661,6 +733,11 @@ class DependencyGraphBuilder:
                 continue
 
             for package_name in package_names:
+                if is_platform_excluded(self._import_annotation(file_path, package_name), cfg.EXCLUDED_PLATFORMS):
+                    if self.logger:
+                        self.logger.debug(f"Skipping platform-excluded import '{package_name}' in {file_path}")
+                    continue
+
                 dist_node = self._resolve_dist_node_for_import(package_name)
                 dist_node_for_edge = dist_node
                 ecosystem = None
@@ -8,17 +8,34 @@ using tree‑sitter parsers
 
 import logging
 import os
+import re
 import signal
 from collections import defaultdict
 from contextlib import contextmanager
 from pathlib import Path
 
+from gardener.analysis.js_subpaths import PackageScopes
+from gardener.analysis.minified import JS_LANGUAGES, minified_reason
+from gardener.analysis.pipeline import PipelineTimings, StageThread
 from gardener.common.defaults import ResourceLimits
+from gardener.common.secure_file_ops import FileOperationError, SecurityError
 from gardener.common.tsl import get_parser
+from gardener.treewalk.idl import go_package_import_path, parse_idl
+from gardener.treewalk.stylesheet import STYLESHEET_EXTENSIONS, stylesheet_package_name
 
 JS_TS_SOURCE_EXTS = [".js", ".jsx", ".ts", ".tsx", ".mjs", ".cjs"]
 JSONLIKE_EXTS = [".json"]
 
+# Matches `mod foo;`, `pub mod foo;`, `#[cfg(test)] pub(crate) mod foo { ... }` declarations
+RUST_MOD_DECL_RE = re.compile(
+    r"^\s*(?:#\[[^\]]*\]\s*)*(?:pub(?:\([^)]*\))?\s+)?mod\s+([A-Za-z_][A-Za-z0-9_]*)\s*[;{]", re.MULTILINE
+)
+# `#[path = "unix.rs"] mod imp;` declarations, which load the module from another file
+RUST_ATTRIBUTED_MOD_RE = re.compile(
+    r"^\s*((?:#\[[^\]]*\]\s*)+)(?:pub(?:\([^)]*\))?\s+)?mod\s+([A-Za-z_][A-Za-z0-9_]*)\s*;", re.MULTILINE
+)
+RUST_PATH_ATTRIBUTE_RE = re.compile(r'#\[\s*path\s*=\s*"([^"]+)"\s*\]')
+
 
 class TimeoutError(Exception):
     """
@@ -74,11 +91,22 @@ class LocalImportResolver:
         hardhat_remappings (dict): Solidity remappings derived from Hardhat config
         solidity_src_path (str|None): Foundry src path when available
         logger (Logger|None): Optional logger for debug and warnings
+        external_packages (dict|None): Declared external packages used to detect shadowed specifiers
+        rust_workspace_crates (dict|None): Import name -> crate root file of Cargo workspace members
+        rust_crate_roots (list|None): Root files of every compile target of the repository's Cargo packages
+        rust_crate_editions (dict|None): Cargo package directory -> Rust edition ('2015', '2018', ...)
+        js_workspace_packages (dict|None): npm/Yarn/pnpm workspace package name -> repo-relative directory
+        python_source_roots (list|None): Source roots of the repository's Python projects (src layouts)
+        go_module_roots (dict|None): Module path -> repo-relative directory of the repository's Go modules
+        secure_file_ops (SecureFileOps|None): Secure file operations used to read package.json files, IDL
+            files and Rust sources scanned for module declarations
     """
 
     def __init__(self, repo_path, source_files, alias_resolver, js_ts_base_url,
                  js_ts_path_aliases, go_module_path, remappings, hardhat_remappings,
-                 solidity_src_path, logger):
+                 solidity_src_path, logger, external_packages=None, rust_workspace_crates=None,
+                 rust_crate_roots=None, js_workspace_packages=None, python_source_roots=None,
+                 go_module_roots=None, rust_crate_editions=None, secure_file_ops=None):
         self.repo_path = repo_path
         self.source_files = source_files
         self.alias_resolver = alias_resolver
@@ -89,6 +117,60 @@ class LocalImportResolver:
         self.hardhat_remappings = hardhat_remappings or {}
         self.solidity_src_path = solidity_src_path
         self.logger = logger
+        self.secure_file_ops = secure_file_ops
+        self.external_import_names = set()
+        for dist_name, pkg_data in (external_packages or {}).items():
+            self.external_import_names.add(dist_name)
+            self.external_import_names.update((pkg_data or {}).get("import_names", []) or [])
+        self.rust_workspace_crates = rust_workspace_crates or {}
+        self.rust_crate_roots = rust_crate_roots or []
+        self.rust_crate_editions = rust_crate_editions or {}
+        self.package_scopes = PackageScopes(repo_path, js_workspace_packages, secure_file_ops)
+        self.python_source_roots = python_source_roots or []
+        self.go_module_roots = dict(go_module_roots or {})
+        if go_module_path:
+            self.go_module_roots.setdefault(go_module_path, ".")
+        self.disambiguations = []
+        self._disambiguation_keys = set()
+        self._rust_declared_modules_cache = {}
+        self._rust_path_modules_cache = None
+        self._idl_hints_cache = None
+
+    # --- Disambiguation helpers ---
+    def _record_disambiguation(self, language, importing_file_rel_path, specifier, chosen,
+                               local_candidate, external_candidate, rule):
+        """
+        Record how a specifier matching both a local module and an external package was classified
+
+        Args:
+            language (str): Language of the importing file
+            importing_file_rel_path (str): Importing file path relative to the repo
+            specifier (str): Import specifier as written in source
+            chosen (str): 'local' or 'external'
+            local_candidate (str): Repo‑relative path of the local module candidate
+            external_candidate (str): External package/import name candidate
+            rule (str): Short description of the language rule that decided the outcome
+        """
+        key = (importing_file_rel_path, specifier)
+        if key in self._disambiguation_keys:
+            return
+        self._disambiguation_keys.add(key)
+        self.disambiguations.append(
+            {
+                "language": language,
+                "file": importing_file_rel_path,
+                "specifier": specifier,
+                "chosen": chosen,
+                "local_candidate": local_candidate,
+                "external_candidate": external_candidate,
+                "rule": rule,
+            }
+        )
+        if self.logger:
+            self.logger.debug(
+                f"Disambiguated '{specifier}' in {importing_file_rel_path} as {chosen} "
+                f"(local: {local_candidate}, external: {external_candidate}): {rule}"
+            )
 
     # --- Python helpers ---
     def _py_is_invalid_blank_absolute(self, module_str, level):
@@ -150,7 +232,66 @@ class LocalImportResolver:
         if self._py_is_invalid_blank_absolute(module_str, relative_level):
             return None
         candidates = self._py_target_paths(importing_file_rel_path, module_str, relative_level)
-        return self._py_first_existing(candidates)
+        resolved = self._py_first_existing(candidates)
+        if relative_level > 0:
+            return resolved
+
+        top_level = module_str.split(".")[0]
+        rule = None
+        if resolved:
+            rule = "repository root is on sys.path ahead of site-packages"
+        else:
+            resolved = self._py_source_root_module(module_str)
+            if resolved:
+                rule = "the repository's own package, installed from its source root"
+        if not resolved:
+            resolved = self._py_script_dir_module(importing_file_rel_path, module_str)
+            if resolved:
+                rule = "script directory (sys.path[0]) shadows installed packages"
+        if resolved and top_level in self.external_import_names:
+            self._record_disambiguation(
+                "python", importing_file_rel_path, module_str, "local", resolved, top_level, rule
+            )
+        return resolved
+
+    def _py_source_root_module(self, module_str):
+        """
+        Resolve an absolute import against the source roots of the repository's Python projects
+        (`src/mypkg` of a src layout), as an installed or editable copy of the project would
+
+        Args:
+            module_str (str): Dotted module name
+
+        Returns:
+            str|None: Repo‑relative module path if found, otherwise None
+        """
+        for root in self.python_source_roots:
+            import_path_base = Path(root).joinpath(*module_str.split("."))
+            resolved = self._py_first_existing([f"{import_path_base}.py", str(import_path_base / "__init__.py")])
+            if resolved:
+                return resolved
+        return None
+
+    def _py_script_dir_module(self, importing_file_rel_path, module_str):
+        """
+        Resolve an absolute import against the importing file's directory when that directory
+        is not itself a package, mirroring how Python puts a script's directory on sys.path
+
+        Args:
+            importing_file_rel_path (str): Importing file path relative to the repo
+            module_str (str): Dotted module name
+
+        Returns:
+            str|None: Repo‑relative module path if found, otherwise None
+        """
+        base_dir = Path(importing_file_rel_path).parent
+        if str(base_dir) == ".":
+            return None
+        if str(base_dir / "__init__.py") in self.source_files:
+            return None
+        module_parts = module_str.split(".")
+        import_path_base = base_dir.joinpath(*module_parts)
+        return self._py_first_existing([f"{import_path_base}.py", str(import_path_base / "__init__.py")])
 
     # --- JS/TS helpers ---
     def _join_norm(self, *parts):
@@ -334,6 +475,96 @@ class LocalImportResolver:
                 return candidate
         return None
 
+    def _js_find_local_module(self, rel_base, module_str):
+        with_ext = self._js_try_with_source_exts(rel_base, module_str)
+        if with_ext:
+            return with_ext
+        return self._js_try_index_files(rel_base, module_str)
+
+    def _js_package_name(self, module_str):
+        parts = module_str.split("/")
+        if module_str.startswith("@") and len(parts) >= 2:
+            return f"{parts[0]}/{parts[1]}"
+        return parts[0]
+
+    def _js_resolve_subpath_map(self, importing_file_rel_path, module_str):
+        """
+        Resolve `#` imports, self-references and imports of workspace packages through package.json
+
+        Args:
+            importing_file_rel_path (str): Importing file path relative to the repo
+            module_str (str): Bare or `#` import specifier
+
+        Returns:
+            str|None: Repo‑relative target path, '__PACKAGE:<name>' for an `imports` entry naming a
+            package, or None when no map applies or the target is not a source file (e.g. build output)
+        """
+        kind, target = self.package_scopes.resolve(importing_file_rel_path, module_str)
+        if kind == "package":
+            return f"__PACKAGE:{self._js_package_name(target)}"
+        for candidate in target if kind == "file" else []:
+            candidate = str(Path(candidate))
+            # Maps often name build output (./utils.js) of a TypeScript source (utils.ts)
+            found = self._js_try_as_is_or_data_like(candidate) or self._js_find_local_module(
+                os.path.splitext(candidate)[0], module_str
+            )
+            if found:
+                return found
+        return None
+
+    def _js_resolve_bare_specifier(self, importing_file_rel_path, module_str):
+        """
+        Apply TypeScript/Node rules to a bare specifier that no alias claimed
+
+        With a configured baseUrl, TypeScript resolves bare specifiers against it before
+        node_modules, so a matching local module wins. Without one, Node only consults
+        node_modules and a same-named local directory never shadows the package; the decision
+        is recorded when the package is declared
+
+        Args:
+            importing_file_rel_path (str): Importing file path relative to the repo
+            module_str (str): Bare import specifier
+
+        Returns:
+            str|None: Repo‑relative target path when resolved locally, otherwise None
+        """
+        if module_str.startswith(("node:", "/", "$")):
+            return None
+        package_name = self._js_package_name(module_str)
+
+        if self.js_ts_base_url is not None:
+            local = self._js_find_local_module(self._join_norm(self.js_ts_base_url, module_str), module_str)
+            if local:
+                if package_name in self.external_import_names:
+                    self._record_disambiguation(
+                        "javascript",
+                        importing_file_rel_path,
+                        module_str,
+                        "local",
+                        local,
+                        package_name,
+                        "tsconfig baseUrl is searched before node_modules",
+                    )
+                return local
+            return None
+
+        importing_dir = str(Path(importing_file_rel_path).parent)
+        for base_dir in dict.fromkeys([importing_dir, "."]):
+            local = self._js_find_local_module(self._join_norm(base_dir, module_str), module_str)
+            # Only a declared package is known to exist in node_modules to take the specifier
+            if local and package_name in self.external_import_names:
+                self._record_disambiguation(
+                    "javascript",
+                    importing_file_rel_path,
+                    module_str,
+                    "external",
+                    local,
+                    package_name,
+                    "bare specifiers resolve through node_modules, never sibling paths",
+                )
+                break
+        return None
+
     def resolve_js(self, importing_file_rel_path, module_str):
         """
         Resolve a JavaScript/TypeScript import to a local file when it is relative
@@ -353,13 +584,19 @@ class LocalImportResolver:
             resolved = self._js_resolve_path_alias(importing_file_rel_path, module_str)
             if resolved:
                 return resolved
+            package_name = self.alias_resolver.get_package_name(module_str)
+            if package_name:
+                return f"__PACKAGE:{package_name}"
         elif self.js_ts_path_aliases:
             legacy = self._js_legacy_path_alias(importing_file_rel_path, module_str)
             if legacy:
                 return legacy
 
         if not module_str.startswith("."):
-            return None
+            subpath = self._js_resolve_subpath_map(importing_file_rel_path, module_str)
+            if subpath:
+                return subpath
+            return self._js_resolve_bare_specifier(importing_file_rel_path, module_str)
 
         rel_base = self._js_resolve_relative_base(importing_file_rel_path, module_str)
         if rel_base is None:
@@ -385,7 +622,7 @@ class LocalImportResolver:
             return "", "", []
         first_part = use_path_parts[0]
         if first_part == "crate":
-            return "src", "crate", use_path_parts[1:]
+            return self._rust_crate_dir(importing_file_rel_path), "crate", use_path_parts[1:]
         if first_part == "self":
             return str(Path(importing_file_rel_path).parent), "self", use_path_parts[1:]
         if first_part == "super":
@@ -457,17 +694,267 @@ class LocalImportResolver:
                 return candidate_mod
         return None
 
-    def resolve_rust(self, importing_file_rel_path, use_path_parts):
+    def _rust_declared_modules(self, importing_file_rel_path):
+        """
+        Return module names declared with `mod` in the importing file, or None when unreadable
+
+        Args:
+            importing_file_rel_path (str): Importing file path relative to the repo
+
+        Returns:
+            set|None: Declared module names
+        """
+        if importing_file_rel_path in self._rust_declared_modules_cache:
+            return self._rust_declared_modules_cache[importing_file_rel_path]
+        content = self._read_source_file(importing_file_rel_path)
+        declared = set(RUST_MOD_DECL_RE.findall(content)) if content is not None else None
+        self._rust_declared_modules_cache[importing_file_rel_path] = declared
+        return declared
+
+    def _read_source_file(self, rel_path):
+        """
+        Return the text of a repository file, or None when unreadable, rejected by SecureFileOps or
+        larger than ResourceLimits.MAX_FILE_SIZE
+        """
+        file_info = self.source_files.get(rel_path) or {}
+        abs_path = file_info.get("absolute_path") if isinstance(file_info, dict) else None
+        abs_path = abs_path or str(Path(self.repo_path) / rel_path)
+        try:
+            if Path(abs_path).stat().st_size > ResourceLimits.MAX_FILE_SIZE:
+                return None
+            if self.secure_file_ops is not None:
+                return self.secure_file_ops.read_file(rel_path, encoding="utf-8")
+            with open(abs_path, "r", encoding="utf-8", errors="ignore") as handle:
+                return handle.read()
+        except (OSError, FileOperationError, SecurityError):
+            return None
+
+    def _rust_module_path_target(self, declaring_file_rel_path, module_path):
+        """
+        Resolve a `#[path = "..."]` value against the directory of the declaring file
+
+        Returns:
+            str|None: Repo‑relative path of the module file when it is a source file
+        """
+        target = os.path.normpath(str(Path(declaring_file_rel_path).parent / module_path.replace("\\", "/")))
+        return target if target in self.source_files else None
+
+    def _rust_path_modules(self):
+        """
+        Scan the Rust sources once for `#[path = "..."] mod name;` declarations
+
+        Returns:
+            tuple: (relocated file -> (declaring file, module name),
+                (declaring file, module name) -> relocated file, the first one when cfg alternatives differ)
+        """
+        if self._rust_path_modules_cache is None:
+            relocated, targets = {}, {}
+            for rel_path in sorted(self.source_files):
+                if not rel_path.endswith(".rs"):
+                    continue
+                content = self._read_source_file(rel_path)
+                if not content or "path" not in content:
+                    continue
+                for attributes, module_name in RUST_ATTRIBUTED_MOD_RE.findall(content):
+                    match = RUST_PATH_ATTRIBUTE_RE.search(attributes)
+                    target = match and self._rust_module_path_target(rel_path, match.group(1))
+                    if target:
+                        relocated.setdefault(target, (rel_path, module_name))
+                        targets.setdefault((rel_path, module_name), target)
+            self._rust_path_modules_cache = (relocated, targets)
+        return self._rust_path_modules_cache
+
+    def _rust_origin_file(self, rel_path):
+        """
+        Follow `#[path]` relocations back to the file whose location reflects the module tree
+        """
+        relocated = self._rust_path_modules()[0]
+        seen = set()
+        while rel_path in relocated and rel_path not in seen:
+            seen.add(rel_path)
+            rel_path = relocated[rel_path][0]
+        return rel_path
+
+    def _rust_crate_dir(self, importing_file_rel_path):
+        """
+        Return the source directory `crate::` paths resolve against for a file
+
+        Files loaded through `#[path]` belong to the crate of the file declaring them; the
+        directory is that of the innermost enclosing crate root (a workspace member's, or any
+        target root of a Cargo package: src/lib.rs, src/bin/tool.rs, examples/demo.rs ...), else 'src'
+        """
+        origin = self._rust_origin_file(importing_file_rel_path)
+        best = None
+        for crate_root in [*self.rust_workspace_crates.values(), *self.rust_crate_roots]:
+            root_dir = str(Path(crate_root).parent)
+            if origin.startswith(root_dir + "/") and (best is None or len(root_dir) > len(best)):
+                best = root_dir
+        return best or "src"
+
+    def _rust_resolve_path_module(self, importing_file_rel_path, first_part, current_dir, remainder):
+        """
+        Resolve paths through modules loaded with `#[path]`, or `super` from such a module
+
+        Returns:
+            tuple: (resolved path or None, handled flag)
+        """
+        relocated, targets = self._rust_path_modules()
+        if first_part == "super" and importing_file_rel_path in relocated:
+            # The parent of a relocated module is the module declaring it, not its directory
+            declaring = relocated[importing_file_rel_path][0]
+            declaring_dir = Path(declaring).parent
+            if Path(declaring).name not in ("mod.rs", "lib.rs", "main.rs") and declaring not in relocated:
+                declaring_dir = declaring_dir / Path(declaring).stem
+            return self._rust_try_module_candidates(str(declaring_dir), remainder) or declaring, True
+        if first_part == "crate" and remainder:
+            for crate_root in (str(Path(current_dir) / "lib.rs"), str(Path(current_dir) / "main.rs")):
+                target = targets.get((crate_root, remainder[0]))
+                if target:
+                    return self._rust_try_module_candidates(str(Path(target).parent), remainder[1:]) or target, True
+        if first_part not in ("crate", "self", "super"):
+            target = targets.get((importing_file_rel_path, first_part))
+            if target:
+                return self._rust_try_module_candidates(str(Path(target).parent), remainder[1:]) or target, True
+        return None, False
+
+    def _rust_check_shadowing(self, importing_file_rel_path, use_path_parts, current_dir, remainder):
+        """
+        Decide whether a path whose first segment is not crate/self/super names a local module
+
+        Rust 2018 resolves such a segment to a module only when that module is declared in the
+        current scope; otherwise it names an extern crate, even if a same-named file exists
+
+        Returns:
+            bool: True when the path must be treated as external
+        """
+        first_part = use_path_parts[0]
+        declared = self._rust_declared_modules(importing_file_rel_path)
+        if declared is None:
+            return False
+        if first_part not in declared:
+            local = self._rust_try_module_candidates(current_dir, remainder)
+            if local:
+                self._record_disambiguation(
+                    "rust",
+                    importing_file_rel_path,
+                    first_part,
+                    "external",
+                    local,
+                    first_part,
+                    f"no `mod {first_part}` in scope, so the path names the extern crate",
+                )
+            return True
+        if first_part in self.external_import_names:
+            local = self._rust_try_module_candidates(current_dir, remainder)
+            if local:
+                self._record_disambiguation(
+                    "rust",
+                    importing_file_rel_path,
+                    first_part,
+                    "local",
+                    local,
+                    first_part,
+                    f"`mod {first_part}` declared in scope shadows the extern crate",
+                )
+        return False
+
+    def _rust_edition(self, importing_file_rel_path):
+        """
+        Return the edition of the Cargo package owning a file, or None when no package does
+        """
+        origin = self._rust_origin_file(importing_file_rel_path)
+        best = None
+        for crate_dir in self.rust_crate_editions:
+            if (crate_dir == "." or origin.startswith(crate_dir + "/")) and (
+                best is None or best == "." or len(crate_dir) > len(best)
+            ):
+                best = crate_dir
+        return self.rust_crate_editions.get(best)
+
+    def _rust_crate_root_file(self, importing_file_rel_path):
+        """
+        Return the root file of the crate a file belongs to (its lib.rs when the crate has one)
+        """
+        crate_dir = self._rust_crate_dir(importing_file_rel_path)
+        roots = [root for root in self.rust_crate_roots if str(Path(root).parent) == crate_dir]
+        for name in ("lib.rs", "main.rs"):
+            candidate = str(Path(crate_dir) / name)
+            if candidate in roots or candidate in self.source_files:
+                return candidate
+        return roots[0] if roots else None
+
+    def _rust_resolve_2015(self, importing_file_rel_path, use_path_parts):
+        """
+        Resolve a `use` path of a 2015 edition crate, where paths start at the crate root
+
+        A module the crate root declares with `mod` takes the name, shadowing a same-named
+        extern crate; any other leading segment names an extern crate
+
+        Returns:
+            str|None: Repo‑relative module path if the path names a module of the crate, otherwise None
+        """
+        first_part = use_path_parts[0]
+        crate_dir = self._rust_crate_dir(importing_file_rel_path)
+        local = self._rust_try_module_candidates(crate_dir, use_path_parts)
+        crate_root = self._rust_crate_root_file(importing_file_rel_path)
+        declared = self._rust_declared_modules(crate_root) if crate_root else None
+        if declared is None:
+            return local
+        if first_part in declared:
+            if local and first_part in self.external_import_names:
+                self._record_disambiguation(
+                    "rust",
+                    importing_file_rel_path,
+                    first_part,
+                    "local",
+                    local,
+                    first_part,
+                    f"2015 edition paths start at the crate root, where `mod {first_part}` shadows the extern crate",
+                )
+            return local
+        if local:
+            self._record_disambiguation(
+                "rust",
+                importing_file_rel_path,
+                first_part,
+                "external",
+                local,
+                first_part,
+                f"no `mod {first_part}` at the 2015 edition crate root, so the path names the extern crate",
+            )
+        return None
+
+    def _rust_resolve_workspace_crate(self, crate_import_name, remainder):
+        """
+        Resolve a path into a sibling workspace crate: its module file when one matches, else its crate root
+
+        Args:
+            crate_import_name (str): Import name of the workspace member
+            remainder (list): Path segments after the crate name
+
+        Returns:
+            str: Repo‑relative module path
+        """
+        crate_root = self.rust_workspace_crates[crate_import_name]
+        if not remainder or remainder == ["*"]:
+            return crate_root
+        return self._rust_try_module_candidates(str(Path(crate_root).parent), remainder) or crate_root
+
+    def resolve_rust(self, importing_file_rel_path, use_path_parts, module_path=None):
         """
         Resolve a Rust `use` path to a module file when possible
 
         Args:
             importing_file_rel_path (str): Importing file path relative to the repo
             use_path_parts (list): Components of the `use` path split on '::'
+            module_path (str|None): `#[path = "..."]` value of a `mod` declaration, which names the
+                module file relative to the importing file's directory
 
         Returns:
             str|None: Repo‑relative module path if resolved, otherwise None
         """
+        if module_path:
+            return self._rust_module_path_target(importing_file_rel_path, module_path)
         if not use_path_parts:
             return None
 
@@ -475,12 +962,31 @@ class LocalImportResolver:
             importing_file_rel_path, use_path_parts
         )
 
+        resolved, handled = self._rust_resolve_path_module(
+            importing_file_rel_path, first_part, current_dir, remainder
+        )
+        if handled:
+            return resolved
+
         resolved, handled = self._rust_handle_empty_or_wildcard(
             first_part, importing_file_rel_path, current_dir, remainder
         )
         if handled:
             return resolved
 
+        if first_part in self.rust_workspace_crates and first_part not in (
+            self._rust_declared_modules(importing_file_rel_path) or ()
+        ):
+            return self._rust_resolve_workspace_crate(first_part, use_path_parts[1:])
+
+        if first_part not in ("crate", "self", "super") and self._rust_edition(importing_file_rel_path) == "2015":
+            return self._rust_resolve_2015(importing_file_rel_path, use_path_parts)
+
+        if first_part not in ("crate", "self", "super") and self._rust_check_shadowing(
+            importing_file_rel_path, use_path_parts, current_dir, remainder
+        ):
+            return None
+
         resolved = self._rust_try_module_candidates(current_dir, remainder)
         if resolved:
             return resolved
@@ -492,8 +998,12 @@ class LocalImportResolver:
         return None
 
     # --- Go helpers ---
-    def _go_is_module_absolute(self, module_str):
-        return bool(self.go_module_path and module_str.startswith(self.go_module_path))
+    def _go_module_for(self, module_str):
+        # Longest module of the repository the import path is equal to or below
+        matching = [
+            module for module in self.go_module_roots if module_str == module or module_str.startswith(module + "/")
+        ]
+        return max(matching, key=len) if matching else None
 
     def _go_import_path_for_relative(self, importing_file_rel_path, module_str):
         abs_dir = str((Path(self.repo_path) / importing_file_rel_path).parent)
@@ -536,11 +1046,12 @@ class LocalImportResolver:
             str|None: Repo‑relative `.go` file if uniquely determined, otherwise None
         """
         if not module_str.startswith("."):
-            if self._go_is_module_absolute(module_str):
-                relative_part = module_str[len(self.go_module_path) :].lstrip("/")
-                import_path = str(Path(relative_part).resolve())
+            module = self._go_module_for(module_str)
+            if module is not None:
+                relative_part = module_str[len(module) :].lstrip("/")
+                import_path = os.path.normpath(os.path.join(self.go_module_roots[module], relative_part))
             else:
-                return None
+                return self._go_idl_target(module_str)
         else:
             import_path = self._go_import_path_for_relative(importing_file_rel_path, module_str)
 
@@ -557,7 +1068,24 @@ class LocalImportResolver:
                 f"Go resolver: Found multiple .go files in directory '{import_path}' "
                 f"for import '{module_str}' from '{importing_file_rel_path}': {single_or_list}. Resolution is ambiguous."  # noqa
             )
-        return None
+        return self._go_idl_target(module_str)
+
+    def _go_idl_target(self, module_str):
+        """
+        Link a Go import of generated protobuf/Thrift code to the IDL file declaring its go_package
+
+        Args:
+            module_str (str): Go import path
+
+        Returns:
+            str|None: Repo‑relative IDL file, or None when no schema declares that package
+        """
+        candidates = sorted(
+            rel_path
+            for rel_path, hints in self.idl_package_hints().items()
+            if hints.get("go_package") and go_package_import_path(hints["go_package"]) == module_str
+        )
+        return candidates[0] if candidates else None
 
     # --- Solidity helpers ---
     def _solidity_try_remappings(self, import_path_str, remappings_dict):
@@ -627,11 +1155,221 @@ class LocalImportResolver:
             return target
         return None
 
+    # --- IDL helpers ---
+    def idl_package_hints(self):
+        """
+        Read package and target-language hints (go_package, java_package, ...) from IDL files
+
+        Returns:
+            dict: Map of repo‑relative IDL path to {'package': ..., '<lang>_package': ...}
+        """
+        if self._idl_hints_cache is not None:
+            return self._idl_hints_cache
+        hints = {}
+        for rel_path, file_info in self.source_files.items():
+            if not isinstance(file_info, dict) or file_info.get("language") != "idl":
+                continue
+            content = self._read_source_file(rel_path)
+            if content is None:
+                continue
+            document = parse_idl(content, rel_path)
+            entry = dict(document.options)
+            if document.package:
+                entry["package"] = document.package
+            if entry:
+                hints[rel_path] = entry
+        self._idl_hints_cache = hints
+        return hints
+
+    def resolve_idl(self, importing_file_rel_path, import_path_str):
+        """
+        Resolve a Protobuf/Thrift/Avro IDL import to a schema file in the repository
+
+        Tries the importing file's directory, then the repository root, then any directory acting
+        as an include root (the shortest path ending with the import path)
+
+        Args:
+            importing_file_rel_path (str): Importing file path relative to the repo
+            import_path_str (str): Import path as written in the schema
+
+        Returns:
+            str|None: Repo‑relative path if resolved, otherwise None
+        """
+        base_dir = str(Path(importing_file_rel_path).parent)
+        for candidate in (os.path.join(base_dir, import_path_str), import_path_str):
+            normalized = os.path.normpath(candidate)
+            if normalized in self.source_files:
+                return normalized
+        if import_path_str.startswith("."):
+            return None
+        suffix = os.sep + os.path.normpath(import_path_str)
+        matches = sorted(
+            (rel_path for rel_path in self.source_files if rel_path.endswith(suffix)),
+            key=lambda rel_path: (len(rel_path), rel_path),
+        )
+        return matches[0] if matches else None
+
+    def resolve_stylesheet(self, importing_file_rel_path, target):
+        """
+        Resolve a CSS/Sass/Less import to a stylesheet in the repository or the npm package it loads
+
+        Local candidates follow Sass rules: the path itself, with a stylesheet extension, as a
+        `_partial` and as a directory `_index`/`index` file. `~pkg/...` and `pkg:pkg/...` always
+        name packages; other bare targets only when they name a declared package, since Sass load
+        paths make `@import 'variables'` ambiguous
+
+        Args:
+            importing_file_rel_path (str): Importing file path relative to the repo
+            target (str): Import target as written
+
+        Returns:
+            str|None: Repo-relative path, '__PACKAGE:<name>', or None when unresolved
+        """
+        if target.startswith(("~", "pkg:")):
+            package_name = stylesheet_package_name(target[1:] if target.startswith("~") else target[4:])
+            return f"__PACKAGE:{package_name}" if package_name else None
+        base_dir = "." if target.startswith("/") else str(Path(importing_file_rel_path).parent)
+        path = os.path.normpath(os.path.join(base_dir, target.lstrip("/")))
+        directory, name = os.path.split(path)
+        candidates = [path]
+        for extension in STYLESHEET_EXTENSIONS:
+            candidates += [
+                f"{path}{extension}",
+                os.path.join(directory, f"_{name}{extension}"),
+                os.path.join(path, f"_index{extension}"),
+                os.path.join(path, f"index{extension}"),
+            ]
+        for candidate in candidates:
+            if candidate in self.source_files:
+                return candidate
+        if self.alias_resolver and not target.startswith("."):
+            resolved = self.alias_resolver.resolve(importing_file_rel_path, target)
+            if resolved:
+                return resolved
+        if target.startswith((".", "/")):
+            return None
+        package_name = stylesheet_package_name(target)
+        if package_name in self.external_import_names:
+            return f"__PACKAGE:{package_name}"
+        return None
+
+
+    def resolve_html(self, importing_file_rel_path, target):
+        """
+        Resolve a page's `src`/`href` or import map target to a file in the repository
+
+        Args:
+            importing_file_rel_path (str): Importing page path relative to the repo
+            target (str): Path as written; a leading `/` is relative to the repository root
+
+        Returns:
+            str|None: Repo-relative path, or None when the file is not a source file
+        """
+        target = re.split(r"[?#]", target, maxsplit=1)[0]
+        if not target:
+            return None
+        base_dir = "." if target.startswith("/") else str(Path(importing_file_rel_path).parent)
+        path = os.path.normpath(os.path.join(base_dir, target.lstrip("/")))
+        return path if path in self.source_files else None
+
+
+def _read_sources(items, language_handlers, secure_file_ops, logger, sources, panics=None, skipped=None):
+    """
+    Read stage of import extraction: put each parsable file's source on the queue for the parse stage
+
+    Files without a registered handler, over ResourceLimits.MAX_FILE_SIZE, unreadable, or minified or
+    bundled JS/TS are left out. The queue is closed when every file was read or the parse stage abandoned it
+
+    Args:
+        items (list): (rel_path, file_info) pairs in parse order
+        language_handlers (dict): Registered language handlers keyed by language name
+        secure_file_ops (SecureFileOps|None): Secure file operations or None
+        logger (Logger|None): Optional logger for warnings
+        sources (BoundedQueue): Queue receiving (index, rel_path, file_info, code)
+        panics (PanicLog|None): Optional log receiving a diagnostic for each file whose read raised
+        skipped (list|None): Optional list receiving {'file', 'reason'} for each minified or bundled file
+    """
+    try:
+        for index, (rel_path, file_info) in enumerate(items):
+            language = file_info["language"]
+            if not language or language not in language_handlers:
+                continue
+            try:
+                code = _read_source(rel_path, file_info, secure_file_ops, logger, skipped)
+            except Exception as exc:
+                if panics is not None:
+                    panics.record(language, "imports", rel_path, exc, logger)
+                elif logger:
+                    logger.exception(f"Unexpected error reading file {rel_path}")
+                continue
+            if code is not None and not sources.put((index, rel_path, file_info, code)):
+                return
+    finally:
+        sources.close()
+
+
+def _read_source(rel_path, file_info, secure_file_ops, logger, skipped=None):
+    """
+    Read one source file for parsing
+
+    Returns:
+        str|None: The source, or None when the file is skipped
+    """
+    abs_path = file_info["absolute_path"]
+    try:
+        file_size = Path(abs_path).stat().st_size
+        if file_size > ResourceLimits.MAX_FILE_SIZE:
+            if logger:
+                logger.warning(
+                    f"Skipping {rel_path}: file size ({file_size / 1024 / 1024:.1f}MB) "
+                    f"exceeds limit ({ResourceLimits.MAX_FILE_SIZE / 1024 / 1024}MB)"
+                )
+            return None
+    except Exception as exc:
+        if logger:
+            logger.warning(f"Could not check file size for {abs_path}: {exc}")
 
-def extract_imports(source_files, language_handlers, repo_path, secure_file_ops, local_resolver, logger):
+    try:
+        if secure_file_ops:
+            code = secure_file_ops.read_file(rel_path, encoding="utf-8")
+        else:
+            with open(abs_path, "r", encoding="utf-8", errors="ignore") as handle:
+                code = handle.read()
+    except Exception as exc:
+        if logger:
+            logger.error(f"Could not read file {abs_path}: {exc}, skipping")
+        return None
+
+    reason = minified_reason(rel_path, code) if file_info["language"] in JS_LANGUAGES else None
+    if reason:
+        if skipped is not None:
+            skipped.append({"file": rel_path, "reason": reason})
+        if logger:
+            logger.debug(f"Skipping {rel_path}: minified or bundled ({reason})")
+        return None
+    return code
+
+
+def extract_imports(
+    source_files,
+    language_handlers,
+    repo_path,
+    secure_file_ops,
+    local_resolver,
+    logger,
+    cancellation=None,
+    panics=None,
+    skipped=None,
+    timings=None,
+    on_file_imports=None,
+):
     """
     Extract imports from source files using provided handlers
 
+    Files are read on a reader thread into a bounded queue (ResourceLimits.PIPELINE_QUEUE_SIZE) and
+    parsed here, on the calling thread, as they arrive. When cancellation is requested the file being
+    parsed is finished and the rest are skipped
+
     Args:
         source_files (dict): Map of repo‑relative paths to file metadata
         language_handlers (dict): Registered language handlers keyed by language name
@@ -639,106 +1377,165 @@ def extract_imports(source_files, language_handlers, repo_path, secure_file_ops,
         secure_file_ops (SecureFileOps|None): Secure file operations or None
         local_resolver (LocalImportResolver): Resolver for local file imports
         logger (Logger|None): Optional logger for progress and warnings
+        cancellation (Cancellation|None): Optional flag checked before each file; records
+            'imports' progress when it stops the loop
+        panics (PanicLog|None): Optional log receiving a diagnostic for each file whose parse or
+            import extraction raised; that file's partial results are discarded
+        skipped (list|None): Optional list receiving {'file', 'reason'} for each minified or bundled
+            JS/TS file left unparsed
+        timings (PipelineTimings|None): Optional recorder of the 'read' and 'parse' stages and the
+            'sources' queue between them
+        on_file_imports (callable|None): Optional callback run with (rel_path, external_imports) as
+            each file with external imports is parsed
 
     Returns:
-        Tuple of (file_imports, local_imports_map, file_package_components)
+        Tuple of (file_imports, local_imports_map, file_package_components, file_import_annotations)
     """
     file_imports = defaultdict(list)
     local_imports_map = defaultdict(list)
     file_package_components = defaultdict(list)
+    file_import_annotations = {}
 
     processed_files = 0
 
-    for rel_path, file_info in list(source_files.items()):
-        abs_path = file_info["absolute_path"]
-        language = file_info["language"]
-        if not language or language not in language_handlers:
-            continue
-        handler = language_handlers[language]
-
-        try:
-            try:
-                parser = get_parser(language)
-            except Exception as exc:
-                if logger:
-                    logger.warning(f"Failed to get parser for {language}: {str(exc)}, skipping file {rel_path}")
-                continue
-
-            try:
-                file_size = Path(abs_path).stat().st_size
-                if file_size > ResourceLimits.MAX_FILE_SIZE:
+    timings = timings or PipelineTimings()
+    items = list(source_files.items())
+    sources = timings.queue("sources", ResourceLimits.PIPELINE_QUEUE_SIZE)
+
+    def read():
+        with timings.stage("read"):
+            _read_sources(items, language_handlers, secure_file_ops, logger, sources, panics, skipped)
+
+    reader = StageThread("read", read)
+    reader.start()
+    try:
+        with timings.stage("parse"):
+            for index, rel_path, file_info, code in sources:
+                if cancellation is not None and cancellation.requested:
+                    cancellation.record("imports", index, len(items) - index)
                     if logger:
-                        logger.warning(
-                            f"Skipping {rel_path}: file size ({file_size / 1024 / 1024:.1f}MB) "
-                            f"exceeds limit ({ResourceLimits.MAX_FILE_SIZE / 1024 / 1024}MB)"
-                        )
-                    continue
-            except Exception as exc:
-                if logger:
-                    logger.warning(f"Could not check file size for {abs_path}: {exc}")
-
-            try:
-                if secure_file_ops:
-                    code = secure_file_ops.read_file(rel_path, encoding="utf-8")
-                else:
-                    with open(abs_path, "r", encoding="utf-8", errors="ignore") as handle:
-                        code = handle.read()
-            except Exception as exc:
-                if logger:
-                    logger.error(f"Could not read file {abs_path}: {exc}, skipping")
-                continue
-
-            if logger:
-                logger.debug(f"Parsing {rel_path} ({len(code)} bytes)")
-
-            try:
-                with timeout(ResourceLimits.PARSE_TIMEOUT):
-                    tree = parser.parse(bytes(code, "utf-8"))
-            except TimeoutError as exc:
-                if logger:
-                    logger.warning(f"Parsing timed out for {rel_path}: {str(exc)}, skipping")
-                continue
-            except Exception as exc:
-                if logger:
-                    logger.warning(f"Failed to parse {rel_path}: {str(exc)}, skipping")
-                continue
-
-            resolver_func = None
-            if language == "python":
-                resolver_func = local_resolver.resolve_python
-            elif language in ["javascript", "typescript"]:
-                resolver_func = local_resolver.resolve_js
-            elif language == "rust":
-                resolver_func = local_resolver.resolve_rust
-            elif language == "go":
-                resolver_func = local_resolver.resolve_go
-            elif language == "solidity":
-                resolver_func = local_resolver.resolve_solidity
-
-            try:
-                external_imports, local_imports = handler.extract_imports(
-                    tree.root_node,
+                        logger.warning(f"Import extraction cancelled after {index}/{len(items)} files")
+                    break
+                if _parse_file(
                     rel_path,
+                    file_info,
+                    code,
+                    language_handlers[file_info["language"]],
+                    local_resolver,
+                    logger,
+                    panics,
+                    file_imports,
+                    local_imports_map,
                     file_package_components,
-                    resolver_func,
-                    logger=logger,
-                )
+                    file_import_annotations,
+                ):
+                    processed_files += 1
+                    if on_file_imports is not None and file_imports.get(rel_path):
+                        on_file_imports(rel_path, file_imports[rel_path])
+    finally:
+        sources.abandon()
+        reader.join()
+    reader.join_result()
 
-                if external_imports:
-                    file_imports[rel_path] = external_imports
-                if local_imports:
-                    local_imports_map[rel_path] = local_imports
+    if logger:
+        logger.info(f"... Processed {processed_files}/{len(source_files)} files for imports")
 
-                processed_files += 1
-            except Exception as exc:
-                if logger:
-                    logger.warning(f"Error extracting imports from {rel_path}: {str(exc)}")
+    return file_imports, local_imports_map, file_package_components, file_import_annotations
+
+
+def _parse_file(
+    rel_path,
+    file_info,
+    code,
+    handler,
+    local_resolver,
+    logger,
+    panics,
+    file_imports,
+    local_imports_map,
+    file_package_components,
+    file_import_annotations,
+):
+    """
+    Parse stage of import extraction for one file, recording its imports in the result maps
 
-        except Exception as exc:
+    Returns:
+        bool: True when the file's imports were extracted
+    """
+    language = file_info["language"]
+    try:
+        if logger:
+            logger.debug(f"Parsing {rel_path} ({len(code)} bytes)")
+
+        try:
+            with timeout(ResourceLimits.PARSE_TIMEOUT):
+                root_node = handler.parse_source(code, rel_path)
+                if root_node is None:
+                    try:
+                        parser = get_parser(language)
+                    except Exception as exc:
+                        if logger:
+                            logger.warning(f"Failed to get parser for {language}: {str(exc)}, skipping file {rel_path}")
+                        return False
+                    root_node = parser.parse(bytes(code, "utf-8")).root_node
+        except TimeoutError as exc:
             if logger:
-                logger.exception(f"Unexpected error processing file {rel_path}")
+                logger.warning(f"Parsing timed out for {rel_path}: {str(exc)}, skipping")
+            return False
+        except Exception as exc:
+            if panics is not None:
+                panics.record(language, "parse", rel_path, exc, logger)
+            elif logger:
+                logger.warning(f"Failed to parse {rel_path}: {str(exc)}, skipping")
+            return False
+
+        resolver_func = None
+        if language in ("python", "notebook"):
+            resolver_func = local_resolver.resolve_python
+        elif language in ["javascript", "typescript"]:
+            resolver_func = local_resolver.resolve_js
+        elif language == "rust":
+            resolver_func = local_resolver.resolve_rust
+        elif language == "go":
+            resolver_func = local_resolver.resolve_go
+        elif language == "solidity":
+            resolver_func = local_resolver.resolve_solidity
+        elif language == "idl":
+            resolver_func = local_resolver.resolve_idl
+        elif language == "stylesheet":
+            resolver_func = local_resolver.resolve_stylesheet
+        elif language == "html":
+            resolver_func = local_resolver.resolve_html
 
-    if logger:
-        logger.info(f"... Processed {processed_files}/{len(source_files)} files for imports")
+        try:
+            import_annotations = {}
+            external_imports, local_imports = handler.extract_imports(
+                root_node,
+                rel_path,
+                file_package_components,
+                resolver_func,
+                logger=logger,
+                import_annotations=import_annotations,
+            )
 
-    return file_imports, local_imports_map, file_package_components
+            if external_imports:
+                file_imports[rel_path] = external_imports
+            if local_imports:
+                local_imports_map[rel_path] = local_imports
+            if import_annotations:
+                file_import_annotations[rel_path] = import_annotations
+            return True
+        except Exception as exc:
+            # Drop components recorded before the failure so the file contributes nothing
+            file_package_components.pop(rel_path, None)
+            if panics is not None:
+                panics.record(language, "imports", rel_path, exc, logger)
+            elif logger:
+                logger.warning(f"Error extracting imports from {rel_path}: {str(exc)}")
+
+    except Exception as exc:
+        if panics is not None:
+            panics.record(language, "imports", rel_path, exc, logger)
+        elif logger:
+            logger.exception(f"Unexpected error processing file {rel_path}")
+    return False
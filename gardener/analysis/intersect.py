@@ -0,0 +1,126 @@
+"""
+Shared dependencies across repositories
+
+`gardener intersect a.json b.json ...` reads the reports of several repositories (for example all
+of an organization's) and lists the packages that appear in the `top_dependencies` of at least
+`--min-repos` of them (default 2), most widely shared first. Each package gets its share in every
+report and a `combined_percentage`, the mean of its shares over all the reports given (a report
+without the package counts as 0), so the combined column adds up to at most 100 and ranks the
+upstreams the organization as a whole depends on most. Packages are matched by ecosystem and name,
+or by repository URL with `--by repository` so that packages published from one repository are
+counted together
+"""
+
+import argparse
+import json
+import sys
+
+from gardener.analysis.ownership import repository_key
+
+
+def _entry_key(dep, by):
+    """
+    Return the key a top dependency entry is matched on across reports, or None when it has none
+    """
+    if by == "repository":
+        url = dep.get("package_url") or dep.get("repository_url")
+        return repository_key(url) if url else None
+    return f"{dep.get('ecosystem', 'unknown')}:{dep['package_name']}"
+
+
+def intersect_reports(reports, min_repos=2, by="package"):
+    """
+    Combine the top dependencies of several reports and keep those shared by enough of them
+
+    Args:
+        reports (dict): Report label -> analysis results
+        min_repos (int): Minimum number of reports a package must appear in
+        by (str): 'package' (ecosystem and name) or 'repository' (repository URL)
+
+    Returns:
+        dict: {'reports', 'by', 'min_repos', 'shared': [{'key', 'packages', 'ecosystems', 'package_url',
+            'repositories', 'combined_percentage', 'shares'}], 'unmatched': entries without a repository URL}
+    """
+    combined, unmatched = {}, 0
+    for label, report in reports.items():
+        for dep in report.get("top_dependencies") or []:
+            if not isinstance(dep, dict) or not dep.get("package_name"):
+                continue
+            key = _entry_key(dep, by)
+            if key is None:
+                unmatched += 1
+                continue
+            entry = combined.setdefault(
+                key, {"key": key, "packages": set(), "ecosystems": set(), "package_url": "", "shares": {}}
+            )
+            entry["packages"].add(dep["package_name"])
+            entry["ecosystems"].add(dep.get("ecosystem", "unknown"))
+            entry["package_url"] = entry["package_url"] or dep.get("package_url") or dep.get("repository_url") or ""
+            entry["shares"][label] = entry["shares"].get(label, 0) + (dep.get("percentage") or 0)
+
+    shared = []
+    for entry in combined.values():
+        if len(entry["shares"]) < min_repos:
+            continue
+        shared.append(
+            {
+                "key": entry["key"],
+                "packages": sorted(entry["packages"]),
+                "ecosystems": sorted(entry["ecosystems"]),
+                "package_url": entry["package_url"],
+                "repositories": len(entry["shares"]),
+                "combined_percentage": sum(entry["shares"].values()) / len(reports) if reports else 0,
+                "shares": dict(sorted(entry["shares"].items())),
+            }
+        )
+    shared.sort(key=lambda item: (-item["repositories"], -item["combined_percentage"], item["key"]))
+    return {"reports": list(reports), "by": by, "min_repos": min_repos, "shared": shared, "unmatched": unmatched}
+
+
+def main(argv=None):
+    """
+    Entry point for `gardener intersect <report.json> <report.json> ...`
+
+    Args:
+        argv (list|None): Arguments after the `intersect` subcommand
+
+    Returns:
+        int: Process exit status (1 when a report cannot be read)
+    """
+    from gardener.common.utils import Logger
+
+    parser = argparse.ArgumentParser(prog="gardener intersect")
+    parser.add_argument("reports", nargs="+", help="Analysis results JSON files, one per repository")
+    parser.add_argument("--min-repos", type=int, default=2, help="Keep packages found in at least this many reports")
+    parser.add_argument(
+        "--by", choices=("package", "repository"), default="package", help="Match packages by name or by repository"
+    )
+    parser.add_argument("-o", "--output", help="Write the comparison to this file instead of stdout")
+    args = parser.parse_args(argv)
+
+    logger = Logger(verbose=True)
+    reports = {}
+    for path in args.reports:
+        try:
+            with open(path, "r", encoding="utf-8") as handle:
+                report = json.load(handle)
+        except (OSError, ValueError) as exc:
+            logger.error(f"Could not read report {path}: {exc}")
+            return 1
+        if not isinstance(report, dict) or not isinstance(report.get("top_dependencies"), list):
+            logger.error(f"Report {path} has no top_dependencies")
+            return 1
+        reports[path] = report
+
+    result = intersect_reports(reports, max(args.min_repos, 1), args.by)
+    body = json.dumps(result, indent=2) + "\n"
+    if args.output:
+        with open(args.output, "w", encoding="utf-8") as handle:
+            handle.write(body)
+        logger.info(
+            f"{len(result['shared'])} packages shared by at least {result['min_repos']} of {len(reports)} "
+            f"reports; comparison written to {args.output}"
+        )
+    else:
+        sys.stdout.write(body)
+    return 0
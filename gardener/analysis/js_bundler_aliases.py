@@ -0,0 +1,269 @@
+"""
+webpack, Vite and Rollup `resolve.alias` maps
+
+Bundlers rewrite import specifiers before resolving them, so `@/components/Button` can name a
+file under src/ and `react` can be served by `preact/compat`. The `alias` entries of
+webpack.config.*, vite.config.* and rollup.config.* (`resolve.alias` objects, Vite's and
+@rollup/plugin-alias `[{ find, replacement }]` arrays) are read without executing the config, so
+only static values count: string literals, `path.resolve`/`path.join` of `__dirname` or
+`process.cwd()` with string literals, and `fileURLToPath(new URL('./src', import.meta.url))`.
+Targets are relative to the config's directory; a bare target, or one inside node_modules, names
+the package the alias stands for. Keys ending in `$` (webpack) only match the exact specifier
+"""
+
+import os
+import posixpath
+import re
+from pathlib import PurePosixPath
+
+from gardener.analysis.js_ts_aliases import package_path_aliases
+from gardener.common.secure_file_ops import FileOperationError
+
+BUNDLER_CONFIG_NAMES = ("webpack.config", "vite.config", "rollup.config")
+BUNDLER_CONFIG_EXTENSIONS = (".js", ".mjs", ".cjs", ".ts", ".mts", ".cts")
+# Strings are matched first so they are kept; `//` only starts a comment after whitespace or punctuation,
+# which leaves regex literals such as /^@\// intact
+COMMENT_REGEX = re.compile(
+    r"'(?:\\.|[^'\\])*'|\"(?:\\.|[^\"\\])*\"|`(?:\\.|[^`\\])*`|/\*.*?\*/|(?:^|(?<=[\s,;{(\[]))//[^\n]*", re.S
+)
+# `alias: {...}`, `alias: [...]` and `alias({ entries: ... })`
+ALIAS_REGEX = re.compile(r"\balias\s*(?::|\(\s*\{[^{}\[\]]*?\bentries\s*:)\s*([\[{])")
+KEY_REGEX = re.compile(r"^(?:(['\"])(.+?)\1|([\w$@/.-]+))\s*:\s*(.+)$", re.S)
+STRING_REGEX = re.compile(r"^(['\"`])(.*)\1$", re.S)
+PATH_CALL_REGEX = re.compile(
+    r"^(?:path\.|require\(\s*['\"](?:node:)?path['\"]\s*\)\.)?(resolve|join)\s*\((.*)\)$", re.S
+)
+FILE_URL_REGEX = re.compile(r"new\s+URL\(\s*(['\"])(.+?)\1\s*,\s*import\.meta\.url\s*\)")
+DIRNAME_EXPRESSIONS = ("__dirname", "process.cwd()", "import.meta.dirname")
+
+
+def is_bundler_config(rel_path):
+    """
+    Return True for webpack, Vite and Rollup config files ('vite.config.ts', 'webpack.config.prod.js')
+    """
+    name = PurePosixPath(rel_path).name
+    stem, extension = os.path.splitext(name)
+    return extension in BUNDLER_CONFIG_EXTENSIONS and any(
+        stem == config_name or stem.startswith(config_name + ".") for config_name in BUNDLER_CONFIG_NAMES
+    )
+
+
+def _strip_comments(content):
+    return COMMENT_REGEX.sub(lambda match: match.group(0) if match.group(0)[0] in "'\"`" else "", content)
+
+
+def _closing_index(text, start):
+    """
+    Return the index of the bracket closing the one at `start`, or -1 when unbalanced
+    """
+    pairs = {"{": "}", "[": "]", "(": ")"}
+    stack, quote, index = [], None, start
+    while index < len(text):
+        char = text[index]
+        if quote:
+            if char == "\\":
+                index += 1
+            elif char == quote:
+                quote = None
+        elif char in "'\"`":
+            quote = char
+        elif char in pairs:
+            stack.append(pairs[char])
+        elif stack and char == stack[-1]:
+            stack.pop()
+            if not stack:
+                return index
+        index += 1
+    return -1
+
+
+def _split_top_level(body):
+    """
+    Split comma-separated items of an object or array body, ignoring nested commas
+    """
+    items, depth, quote, current = [], 0, None, []
+    index = 0
+    while index < len(body):
+        char = body[index]
+        current.append(char)
+        if quote:
+            if char == "\\" and index + 1 < len(body):
+                current.append(body[index + 1])
+                index += 1
+            elif char == quote:
+                quote = None
+        elif char in "'\"`":
+            quote = char
+        elif char in "{[(":
+            depth += 1
+        elif char in "}])":
+            depth -= 1
+        elif char == "," and depth == 0:
+            current.pop()
+            items.append("".join(current).strip())
+            current = []
+        index += 1
+    items.append("".join(current).strip())
+    return [item for item in items if item]
+
+
+def _string_value(expression):
+    match = STRING_REGEX.match(expression.strip())
+    if not match or (match.group(1) == "`" and "${" in match.group(2)):
+        return None
+    return match.group(2)
+
+
+def _static_target(expression, config_dir):
+    """
+    Evaluate an alias value without running the config
+
+    Args:
+        expression (str): Value source text
+        config_dir (str): Repo-relative directory of the config
+
+    Returns:
+        tuple: ('path', repo-relative path), ('package', specifier), or (None, None) for dynamic values
+    """
+    expression = expression.strip()
+    if expression.startswith("[") and expression.endswith("]"):
+        # webpack 5 alternatives: the first static one
+        for item in _split_top_level(expression[1:-1]):
+            kind, value = _static_target(item, config_dir)
+            if kind:
+                return kind, value
+        return None, None
+    literal = _string_value(expression)
+    if literal is not None:
+        if literal.startswith((".", "/")):
+            return "path", posixpath.normpath(posixpath.join(config_dir, literal.lstrip("/")))
+        return ("package", literal) if literal else (None, None)
+    match = FILE_URL_REGEX.search(expression)
+    if match:
+        return "path", posixpath.normpath(posixpath.join(config_dir, match.group(2)))
+    match = PATH_CALL_REGEX.match(expression)
+    if match:
+        args = _split_top_level(match.group(2))
+        if args and args[0] in DIRNAME_EXPRESSIONS:
+            args = args[1:]
+        parts = [_string_value(arg) for arg in args]
+        if parts and all(part is not None for part in parts):
+            joined = posixpath.join(*parts)
+            if not joined.startswith("/"):
+                return "path", posixpath.normpath(posixpath.join(config_dir, joined))
+    return None, None
+
+
+def _entries(text, opener, end):
+    """
+    Yield (find, value source) pairs of an alias object or array
+    """
+    body = text[opener + 1 : end]
+    if text[opener] == "{":
+        for item in _split_top_level(body):
+            match = KEY_REGEX.match(item)
+            if match:
+                yield match.group(2) or match.group(3), match.group(4)
+        return
+    for item in _split_top_level(body):
+        if not item.startswith("{"):
+            continue
+        fields = {}
+        for field in _split_top_level(item[1:-1]):
+            match = KEY_REGEX.match(field)
+            if match:
+                fields[match.group(2) or match.group(3)] = match.group(4)
+        # Regular expression `find` values cannot be matched statically
+        find = _string_value(fields.get("find", ""))
+        if find and "replacement" in fields:
+            yield find, fields["replacement"]
+
+
+def parse_bundler_aliases(content, config_rel):
+    """
+    Read the static alias entries of a bundler config
+
+    Args:
+        content (str): Config source
+        config_rel (str): Repo-relative config path
+
+    Returns:
+        list: {'config', 'alias', 'exact', 'target' | 'package'} per entry, in declaration order
+    """
+    text = _strip_comments(content)
+    config_dir = posixpath.dirname(config_rel) or "."
+    aliases = []
+    for match in ALIAS_REGEX.finditer(text):
+        opener = match.start(1)
+        end = _closing_index(text, opener)
+        if end < 0:
+            continue
+        for find, value in _entries(text, opener, end):
+            exact = find.endswith("$")
+            find = find[:-1] if exact else find
+            kind, target = _static_target(value, config_dir)
+            if not kind or not find or target.startswith(".."):
+                continue
+            entry = {"config": config_rel, "alias": find, "exact": exact}
+            package_name = package_path_aliases({find: [target]}).get(find) if kind == "path" else None
+            if kind == "package" or package_name:
+                parts = target.split("/")
+                entry["package"] = package_name or ("/".join(parts[:2]) if target.startswith("@") else parts[0])
+            else:
+                entry["target"] = target
+            aliases.append(entry)
+    return aliases
+
+
+def collect_bundler_aliases(repo_path, source_files, secure_file_ops, logger):
+    """
+    Read the alias maps of every webpack, Vite and Rollup config in the repository
+
+    Args:
+        repo_path (str): Absolute repository path
+        source_files (dict): Map of repo-relative paths to file metadata
+        secure_file_ops (SecureFileOps|None): Secure file operations or None
+        logger (Logger|None): Optional logger
+
+    Returns:
+        list: Alias entries from parse_bundler_aliases, configs closest to the root first
+    """
+    configs = sorted(
+        (PurePosixPath(rel_path).as_posix() for rel_path in source_files if is_bundler_config(rel_path)),
+        key=lambda rel_path: (len(PurePosixPath(rel_path).parts), rel_path),
+    )
+    aliases = []
+    for config_rel in configs:
+        try:
+            if secure_file_ops:
+                content = secure_file_ops.read_file(config_rel)
+            else:
+                with open(os.path.join(repo_path, config_rel), "r", encoding="utf-8") as handle:
+                    content = handle.read()
+        except (OSError, UnicodeDecodeError, FileOperationError) as exc:
+            if logger:
+                logger.debug(f"Could not read bundler config {config_rel}: {exc}")
+            continue
+        found = parse_bundler_aliases(content, config_rel)
+        if found and logger:
+            logger.info(f"Extracted {len(found)} aliases from {config_rel}: {', '.join(e['alias'] for e in found)}")
+        aliases.extend(found)
+    return aliases
+
+
+def apply_bundler_aliases(config, aliases):
+    """
+    Add bundler aliases to an alias configuration: local targets as custom rules, package aliases
+    to the package map
+
+    Args:
+        config (AliasConfiguration): Configuration to update
+        aliases (list): Entries from collect_bundler_aliases
+    """
+    for entry in aliases:
+        pattern = entry["alias"] if entry["exact"] else f"{entry['alias']}/*"
+        if "package" in entry:
+            config.bundler_package_paths.setdefault(pattern, entry["package"])
+        else:
+            target = entry["target"] if entry["exact"] else f"{entry['target']}/*"
+            config.add_custom_rule(pattern, target, description=f"{entry['config']} alias")
@@ -0,0 +1,77 @@
+"""
+ESM or CommonJS module system of JavaScript/TypeScript files
+
+Node.js loads `.mjs`/`.mts` files as ES modules and `.cjs`/`.cts` files as CommonJS; other files
+follow the `type` field of the nearest package.json (`module` or `commonjs`). Files under no
+`type` are classified by their syntax: import/export statements make an ES module, `require()`
+calls and `module.exports` assignments a CommonJS one, and files with neither default to CommonJS
+as in Node.js. Each file is listed under `analyzer_details.module_systems` with its `format`, the
+`basis` of the decision and how it loads each package (`import` for import/export statements and
+`import()`, `require` for `require()`), and npm packages get the union as `loaded_by`, since
+dual-package ecosystems ship different entry points to the two
+"""
+
+from pathlib import PurePosixPath
+
+from gardener.analysis.js_workspace import read_package_manifests
+from gardener.analysis.npm_undeclared import JS_LANGUAGES, _owning_package
+from gardener.treewalk.sfc import SFC_EXTENSIONS
+
+ESM = "esm"
+CJS = "cjs"
+EXTENSION_FORMATS = {".mjs": ESM, ".mts": ESM, ".cjs": CJS, ".cts": CJS, **{ext: ESM for ext in SFC_EXTENSIONS}}
+PACKAGE_TYPES = {"module": ESM, "commonjs": CJS}
+
+
+def _file_format(rel_path, syntax, manifests):
+    """
+    Return (format, basis) for a file from its extension, package.json `type` or syntax
+    """
+    extension = PurePosixPath(rel_path).suffix.lower()
+    if extension in EXTENSION_FORMATS:
+        return EXTENSION_FORMATS[extension], "extension"
+    package_dir = _owning_package(rel_path, manifests)
+    package_type = manifests[package_dir][1].get("type") if package_dir is not None else None
+    if package_type in PACKAGE_TYPES:
+        return PACKAGE_TYPES[package_type], "package.json"
+    if syntax in (ESM, "mixed"):
+        return ESM, "syntax"
+    return CJS, "syntax" if syntax == CJS else "default"
+
+
+def classify_module_systems(
+    source_files, module_systems, external_packages, manifest_files, secure_file_ops=None, logger=None, repo_path=None
+):
+    """
+    Classify JS/TS files as ESM or CommonJS and record how they load their packages
+
+    Args:
+        source_files (dict): Relative file path -> file info with 'language'
+        module_systems (dict): File -> {'syntax', 'loaded_by'} collected by the JavaScript handler
+        external_packages (dict): Package metadata keyed by name, updated in place with `loaded_by`
+        manifest_files (list): Absolute manifest paths; only package.json files are read
+        secure_file_ops (SecureFileOps|None): Secure file operations or None
+        logger (Logger|None): Optional logger
+        repo_path (str|None): Absolute repository path
+
+    Returns:
+        dict: File -> {'format', 'basis', 'imports': package -> ['import'|'require', ...]}
+    """
+    manifests = read_package_manifests(manifest_files, secure_file_ops, logger, repo_path)
+    files = {}
+    package_kinds = {}
+    for rel_path, record in sorted(module_systems.items()):
+        if (source_files.get(rel_path) or {}).get("language") not in JS_LANGUAGES:
+            continue
+        module_format, basis = _file_format(rel_path, record.get("syntax"), manifests)
+        files[rel_path] = {"format": module_format, "basis": basis, "imports": dict(record.get("loaded_by") or {})}
+        for package, kinds in files[rel_path]["imports"].items():
+            package_kinds.setdefault(package, set()).update(kinds)
+    for package, kinds in package_kinds.items():
+        entry = external_packages.get(package)
+        if entry and entry.get("ecosystem") == "npm":
+            entry["loaded_by"] = sorted(kinds)
+    if logger and files:
+        esm = sum(1 for record in files.values() if record["format"] == ESM)
+        logger.info(f"... {esm} of {len(files)} JS/TS files are ES modules")
+    return files
@@ -0,0 +1,309 @@
+"""
+JavaScript/TypeScript barrel re-export chains
+
+A barrel file re-exports names defined elsewhere (`export * from './button'`,
+`export { debounce } from 'lodash-es'` in src/index.ts), and `import { debounce } from './lib'`
+resolves to the barrel. After extraction, every name a file imports from a local module is followed
+through the `export ... from` declarations of the files it reaches, explicit names first and then
+`export *` modules that provide the name, until the module defining it. A local definition is linked
+from the importing file like a direct import of it; a name re-exported from a package makes the
+package an import of the importing file, with the chain under `reexported_via` in its annotations.
+A namespace import (`import * as lib from './lib'`) reaches every package the barrel re-exports.
+Each followed chain is listed under `analyzer_details.reexport_chains`, next to the Rust ones
+"""
+
+import os
+import re
+
+from gardener.common.file_helpers import read_repo_file
+from gardener.treewalk.javascript import TYPE_ONLY_EVIDENCE
+from gardener.treewalk.sfc import SFC_EXTENSIONS, component_script
+
+JS_LANGUAGES = ("javascript", "typescript")
+# Strings are kept so `//` inside a specifier does not start a comment
+COMMENT_RE = re.compile(r"'(?:\\.|[^'\\\n])*'|\"(?:\\.|[^\"\\\n])*\"|//[^\n]*|/\*.*?\*/", re.S)
+EXPORT_FROM_RE = re.compile(
+    r"\bexport\s+(?:type\s+)?(?:\*\s*(?:as\s+([\w$]+))?|\{([^}]*)\})\s*from\s*(['\"])([^'\"]+)\3"
+)
+IMPORT_FROM_RE = re.compile(r"\bimport\s+(type\s+)?([\w$\s,*{}]*?)\s*from\s*(['\"])([^'\"]+)\3")
+DEFINITION_RE = (
+    r"\bexport\s+(?:declare\s+)?(?:abstract\s+)?(?:async\s+)?"
+    r"(?:const|let|var|function\s*\*?|class|interface|type|enum|namespace)\s+{}\b"
+)
+LOCAL_EXPORT_LIST_RE = re.compile(r"\bexport\s+(?:type\s+)?\{([^}]*)\}(?!\s*from)")
+# Longest chain followed; real barrels are one or two levels deep
+MAX_CHAIN = 8
+
+
+def _strip_comments(code):
+    return COMMENT_RE.sub(lambda match: match.group(0) if match.group(0)[0] in "'\"" else "", code)
+
+
+def _specifiers(body):
+    """
+    Yield (local name, exported name) for each `a`, `a as b` or `type a` entry of a braced list
+    """
+    for item in body.split(","):
+        parts = item.split()
+        if parts[:1] == ["type"] and len(parts) > 1:
+            parts = parts[1:]
+        if len(parts) == 3 and parts[1] == "as":
+            yield parts[0], parts[2]
+        elif len(parts) == 1:
+            yield parts[0], parts[0]
+
+
+def parse_reexports(code):
+    """
+    Collect the `export ... from` declarations of a JS/TS source file
+
+    Args:
+        code (str): JavaScript or TypeScript source
+
+    Returns:
+        dict: Exported name -> (specifier, name in that module); `export *` specifiers are listed under '*'
+    """
+    exports = {}
+    for namespace, body, _, specifier in EXPORT_FROM_RE.findall(_strip_comments(code)):
+        if namespace:
+            exports[namespace] = (specifier, "*")
+        elif body:
+            for original, exported in _specifiers(body):
+                exports.setdefault(exported, (specifier, original))
+        else:
+            exports.setdefault("*", []).append(specifier)
+    return exports
+
+
+def parse_imported_names(code):
+    """
+    Collect the names a JS/TS source file imports with `import ... from`
+
+    Args:
+        code (str): JavaScript or TypeScript source
+
+    Returns:
+        list: (specifier, imported name, type-only) tuples; 'default' for default and '*' for namespace imports
+    """
+    imported = []
+    for type_only, clause, _, specifier in IMPORT_FROM_RE.findall(_strip_comments(code)):
+        braced = re.search(r"\{([^}]*)\}", clause)
+        names = [original for original, _ in _specifiers(braced.group(1))] if braced else []
+        rest = re.sub(r"\{[^}]*\}", "", clause)
+        if "*" in rest:
+            names.append("*")
+        elif re.match(r"\s*[\w$]", rest):
+            names.insert(0, "default")
+        imported.extend((specifier, name, bool(type_only)) for name in names)
+    return imported
+
+
+class _BarrelWalker:
+    """
+    Follows re-export chains with cached export tables and sources
+    """
+
+    def __init__(self, resolve, read_source, package_name):
+        self.resolve = resolve
+        self.read_source = read_source
+        self.package_name = package_name
+        self.sources = {}
+        self.tables = {}
+
+    def _source(self, rel_path):
+        if rel_path not in self.sources:
+            self.sources[rel_path] = _strip_comments(self.read_source(rel_path) or "")
+        return self.sources[rel_path]
+
+    def reexports(self, rel_path):
+        if rel_path not in self.tables:
+            self.tables[rel_path] = parse_reexports(self._source(rel_path))
+        return self.tables[rel_path]
+
+    def target(self, rel_path, specifier):
+        """
+        Return ('local', file) or ('package', name) for a specifier, or None when it cannot be resolved
+        """
+        resolved = self.resolve(rel_path, specifier)
+        if resolved and resolved.startswith("__PACKAGE:"):
+            return "package", resolved[len("__PACKAGE:") :]
+        if resolved:
+            return "local", resolved
+        package = None if specifier.startswith(".") else self.package_name(specifier)
+        return ("package", package) if package else None
+
+    def _provides(self, rel_path, name, seen):
+        """
+        Return True when a local module defines or re-exports a name
+        """
+        if rel_path in seen:
+            return False
+        seen.add(rel_path)
+        source = self._source(rel_path)
+        if name == "default":
+            return bool(re.search(r"\bexport\s+default\b", source)) or name in self.reexports(rel_path)
+        if re.search(DEFINITION_RE.format(re.escape(name)), source) or name in self.reexports(rel_path):
+            return True
+        for body in LOCAL_EXPORT_LIST_RE.findall(source):
+            if any(exported == name for _, exported in _specifiers(body)):
+                return True
+        for specifier in self.reexports(rel_path).get("*", []):
+            target = self.target(rel_path, specifier)
+            if target and target[0] == "local" and self._provides(target[1], name, seen):
+                return True
+        return False
+
+    def _entry(self, rel_path, name):
+        """
+        Return the (specifier, name) a file re-exports a name from, or None
+        """
+        exports = self.reexports(rel_path)
+        if name in exports and name != "*":
+            return exports[name]
+        if name == "default":
+            # `export *` never re-exports a default export
+            return None
+        packages = []
+        for specifier in exports.get("*", []):
+            target = self.target(rel_path, specifier)
+            if target and target[0] == "local" and self._provides(target[1], name, set()):
+                return specifier, name
+            if target and target[0] == "package":
+                packages.append(specifier)
+        # Package export lists are unknown; a name no local module provides comes from the only package
+        return (packages[0], name) if len(packages) == 1 else None
+
+    def walk(self, module, name):
+        """
+        Follow re-exports of a name from a module file
+
+        Returns:
+            tuple: (chain of files, defining file or None, package or None, name in the package or None)
+        """
+        chain = [module]
+        while len(chain) <= MAX_CHAIN:
+            entry = self._entry(chain[-1], name)
+            if entry is None:
+                break
+            specifier, name = entry
+            target = self.target(chain[-1], specifier)
+            if target is None:
+                break
+            if target[0] == "package":
+                return chain, None, target[1], name
+            if target[1] in chain:
+                break
+            chain.append(target[1])
+            if name == "*":
+                # `export * as ns from './x'` re-exports the whole module
+                break
+        return chain, chain[-1], None, None
+
+    def packages(self, module, chain=None):
+        """
+        Yield (chain, package) for every package a module re-exports, directly or through local barrels
+        """
+        chain = (chain or []) + [module]
+        exports = self.reexports(module)
+        specifiers = list(exports.get("*", [])) + [entry[0] for key, entry in exports.items() if key != "*"]
+        for specifier in dict.fromkeys(specifiers):
+            target = self.target(module, specifier)
+            if target is None:
+                continue
+            if target[0] == "package":
+                yield chain, target[1]
+            elif target[1] not in chain and len(chain) <= MAX_CHAIN:
+                yield from self.packages(target[1], chain)
+
+
+def attribute_reexports(
+    source_files,
+    file_imports,
+    local_imports_map,
+    file_package_components,
+    file_import_annotations,
+    resolve,
+    package_name,
+    secure_file_ops=None,
+    logger=None,
+):
+    """
+    Attribute names imported through JS/TS barrels to the module or package defining them
+
+    Args:
+        source_files (dict): Relative file path -> file info with 'language' and 'absolute_path'
+        file_imports (dict): File -> external import names, updated in place
+        local_imports_map (dict): File -> local files it imports, updated in place
+        file_package_components (dict): File -> (import name, component path) tuples, updated in place
+        file_import_annotations (dict): File -> import name -> annotations, updated in place
+        resolve (callable): JS/TS local resolver, (importing file, specifier) -> file, '__PACKAGE:<name>' or None
+        package_name (callable): Maps a bare specifier to its package name, or None
+        secure_file_ops (SecureFileOps|None): Secure file operations or None
+        logger (Logger|None): Optional logger
+
+    Returns:
+        list: {'file', 'import', 'from', 'chain', and 'defined_in' or 'package'} for every chain of at least
+            one re-export
+    """
+
+    def read_source(rel_path):
+        try:
+            code = read_repo_file(source_files[rel_path]["absolute_path"], secure_file_ops)
+        except Exception:
+            return None
+        extension = os.path.splitext(rel_path)[1].lower()
+        return component_script(code, extension)[0] if code and extension in SFC_EXTENSIONS else code
+
+    def credit(rel_path, package, chain, component, type_only):
+        barrel_imports = file_imports.get(chain[-1]) or []
+        if package not in barrel_imports:
+            # The barrel's own extraction decided the specifier names no package (filtered or internal)
+            return False
+        imports = file_imports.setdefault(rel_path, [])
+        if package not in imports:
+            imports.append(package)
+            annotations = file_import_annotations.setdefault(rel_path, {}).setdefault(package, {})
+            annotations["reexported_via"] = chain
+            if type_only:
+                annotations["evidence"] = TYPE_ONLY_EVIDENCE
+        file_package_components.setdefault(rel_path, []).append((package, component))
+        return True
+
+    walker = _BarrelWalker(resolve, read_source, package_name)
+    chains = []
+    for rel_path in sorted(local_imports_map):
+        if (source_files.get(rel_path) or {}).get("language") not in JS_LANGUAGES:
+            continue
+        seen = set()
+        for specifier, name, type_only in parse_imported_names(read_source(rel_path) or ""):
+            if (specifier, name) in seen:
+                continue
+            seen.add((specifier, name))
+            target = walker.target(rel_path, specifier)
+            if target is None or target[0] != "local" or not walker.reexports(target[1]):
+                continue
+            module = target[1]
+            if name == "*":
+                for chain, package in walker.packages(module):
+                    if credit(rel_path, package, chain, package, type_only):
+                        chains.append(
+                            {"file": rel_path, "import": name, "from": specifier, "chain": chain, "package": package}
+                        )
+                continue
+            chain, defined_in, package, original = walker.walk(module, name)
+            if package is not None:
+                component = package if original in ("*", "default") else f"{package}.{original}"
+                if credit(rel_path, package, chain, component, type_only):
+                    chains.append(
+                        {"file": rel_path, "import": name, "from": specifier, "chain": chain, "package": package}
+                    )
+            elif len(chain) > 1:
+                local = local_imports_map.setdefault(rel_path, [])
+                if defined_in != rel_path and defined_in not in local:
+                    local.append(defined_in)
+                chains.append(
+                    {"file": rel_path, "import": name, "from": specifier, "chain": chain, "defined_in": defined_in}
+                )
+    if logger and chains:
+        logger.debug(f"Followed {len(chains)} JS/TS re-export chains to the defining module or package")
+    return chains
@@ -0,0 +1,192 @@
+"""
+package.json `exports` and `imports` subpath maps
+
+Node resolves `#name` specifiers through the `imports` field of the nearest package.json, and a
+package importing itself by name (`my-lib/utils` inside my-lib) through its `exports` field.
+Workspace siblings are linked into node_modules, so imports of them resolve through `exports` too,
+or without one through `module`/`main` and paths under the package directory, with the matching
+path under the sibling's src/ as fallback for build output. Targets are followed with Node's rules:
+exact keys before `*` patterns (longest prefix first), conditional objects by the first matching
+condition in key order, arrays by their first usable entry. `./` targets are files of the
+package; bare targets in `imports` name the package the import belongs to
+"""
+
+import json
+import os
+import posixpath
+
+from gardener.common.file_helpers import read_repo_file
+from gardener.common.secure_file_ops import FileOperationError, SecurityError
+
+# Conditions a Node or bundler build matches; `types` and `browser` are left to type checkers and browsers
+CONDITIONS = ("node", "import", "require", "module", "default")
+
+
+def conditional_target(value):
+    """
+    Return the target string a subpath map value selects, or None when excluded (null) or unmatched
+
+    Args:
+        value (str|dict|list|None): Subpath map value
+
+    Returns:
+        str|None: Target
+    """
+    if isinstance(value, str):
+        return value
+    if isinstance(value, list):
+        return next((target for target in map(conditional_target, value) if target), None)
+    if isinstance(value, dict):
+        for condition, nested in value.items():
+            if condition in CONDITIONS:
+                return conditional_target(nested)
+    return None
+
+
+def match_subpath(mapping, subpath):
+    """
+    Look up a subpath in an `exports` or `imports` map
+
+    Args:
+        mapping (dict): Subpath keys ('.', './utils', './features/*', '#internal/*') -> values
+        subpath (str): Requested subpath
+
+    Returns:
+        str|None: Target with the `*` match substituted, or None without a match
+    """
+    if subpath in mapping and "*" not in subpath:
+        return conditional_target(mapping[subpath])
+    best = None
+    for key in mapping:
+        if key.count("*") == 1:
+            prefix, suffix = key.split("*")
+            if subpath.startswith(prefix) and subpath.endswith(suffix) and len(subpath) >= len(key) - 1:
+                if best is None or len(prefix) > len(best[1]):
+                    best = (key, prefix, subpath[len(prefix) : len(subpath) - len(suffix)])
+        elif key.endswith("/") and subpath.startswith(key):
+            if best is None or len(key) > len(best[1]):
+                best = (key, key, None)
+    if best is None:
+        return None
+    key, prefix, match = best
+    target = conditional_target(mapping[key])
+    if target is None:
+        return None
+    return target.replace("*", match) if match is not None else target + subpath[len(prefix) :]
+
+
+def exports_map(exports):
+    """
+    Normalize the `exports` field to a subpath map ('.'-keyed)
+    """
+    if isinstance(exports, dict) and any(key.startswith(".") for key in exports):
+        return exports
+    if exports is None:
+        return {}
+    return {".": exports}
+
+
+class PackageScopes:
+    """
+    package.json files enclosing source files, read on demand
+
+    Attributes:
+        repo_path (str): Absolute repository path
+        workspace_packages (dict): Workspace package name -> repo-relative directory
+        secure_file_ops (SecureFileOps|None): Secure file operations or None
+        manifests (dict): Repo-relative directory -> parsed package.json, or None when absent
+    """
+
+    def __init__(self, repo_path, workspace_packages=None, secure_file_ops=None):
+        self.repo_path = repo_path
+        self.workspace_packages = workspace_packages or {}
+        self.secure_file_ops = secure_file_ops
+        self.manifests = {}
+
+    def _manifest(self, directory):
+        if directory not in self.manifests:
+            data = None
+            path = os.path.join(self.repo_path, directory, "package.json")
+            exists = self.secure_file_ops.is_file(path) if self.secure_file_ops else os.path.isfile(path)
+            if exists:
+                try:
+                    data = json.loads(read_repo_file(path, self.secure_file_ops).lstrip("\ufeff"))
+                except (FileOperationError, SecurityError, OSError, ValueError):
+                    pass
+            self.manifests[directory] = data if isinstance(data, dict) else None
+        return self.manifests[directory]
+
+    def nearest(self, rel_path):
+        """
+        Return (repo-relative directory, parsed package.json) of the package containing a file
+
+        Args:
+            rel_path (str): Repo-relative file path
+
+        Returns:
+            tuple: (directory, data), or (None, None) outside every package
+        """
+        directory = posixpath.dirname(rel_path.replace(os.sep, "/")) or "."
+        while True:
+            data = self._manifest(directory)
+            if data is not None:
+                return directory, data
+            if directory == ".":
+                return None, None
+            directory = posixpath.dirname(directory) or "."
+
+    def _workspace_package(self, specifier):
+        """
+        Return (name, directory) of the workspace package a bare specifier imports, or (None, None)
+        """
+        parts = specifier.split("/")
+        name = "/".join(parts[:2]) if specifier.startswith("@") else parts[0]
+        if name in self.workspace_packages:
+            return name, self.workspace_packages[name]
+        return None, None
+
+    def resolve(self, importing_file, specifier):
+        """
+        Resolve a `#` import, a self-reference or an import of a workspace package through package.json
+
+        Args:
+            importing_file (str): Repo-relative importing file
+            specifier (str): Import specifier
+
+        Returns:
+            tuple: ('file', [repo-relative candidate paths, preferred first]) or ('package', bare target
+            specifier), or (None, None)
+        """
+        directory, data = self.nearest(importing_file)
+        name = data.get("name") if data is not None else None
+        sibling = False
+        if specifier.startswith("#"):
+            imports = data.get("imports") if data is not None else None
+            target = match_subpath(imports, specifier) if isinstance(imports, dict) else None
+        elif isinstance(name, str) and (specifier == name or specifier.startswith(name + "/")):
+            target = match_subpath(exports_map(data.get("exports")), "." + specifier[len(name) :])
+        else:
+            name, directory = self._workspace_package(specifier)
+            data = self._manifest(directory) if directory else None
+            if data is None:
+                return None, None
+            subpath = "." + specifier[len(name) :]
+            sibling = True
+            if data.get("exports") is None:
+                entry = data.get("module") or data.get("main")
+                target = subpath if subpath != "." else entry if isinstance(entry, str) else "./index"
+                target = target if target.startswith(".") else f"./{target}"
+            else:
+                target = match_subpath(exports_map(data.get("exports")), subpath)
+        if not target:
+            return None, None
+        if target.startswith("./"):
+            candidates = [posixpath.normpath(posixpath.join(directory, target))]
+            if sibling:
+                # TypeScript siblings often point at build output that only exists after a build
+                rest = specifier[len(name) + 1 :] or "index"
+                candidates.append(posixpath.normpath(posixpath.join(directory, "src", rest)))
+            return "file", candidates
+        if specifier.startswith("#") and not target.startswith(("/", "../")):
+            return "package", target
+        return None, None
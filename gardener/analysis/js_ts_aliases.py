@@ -2,20 +2,178 @@
 JS/TS alias configuration helpers for RepositoryAnalyzer
 
 Parses tsconfig.json/jsconfig.json to extract baseUrl and paths and
-constructs a UnifiedAliasResolver for consistent alias handling
+constructs a UnifiedAliasResolver for consistent alias handling. `extends` chains
+(relative files, packages under node_modules, arrays) are followed with the
+compiler's precedence, and baseUrl/paths inherited from another directory are
+rebased onto the repository root. `paths` targets inside node_modules are package
+aliases: imports matching them count as that package rather than a local file
 """
 
 import json
 import os
+import posixpath
 import re
 from pathlib import Path
 
 from gardener.common.alias_config import AliasConfiguration, UnifiedAliasResolver
+from gardener.common.secure_file_ops import FileOperationError
+
+# Longest extends chain followed before giving up
+MAX_EXTENDS_DEPTH = 16
+COMMENT_REGEX = r"'(\\'|[^'])*?'|\"(\\\"|[^\"])*?\"|//[^\r\n]*|/\*(?:(?!\*/).)*\*/"
+
+
+def _parse_jsonc(content):
+    """
+    Parse tsconfig-style JSON, which allows comments and trailing commas
+    """
+    content = re.sub(
+        COMMENT_REGEX,
+        lambda match: match.group(0) if match.group(0).startswith('"') or match.group(0).startswith("'") else "",
+        content,
+        flags=re.S,
+    )
+    content = re.sub(r",\s*([\]}])", r"\1", content)
+    return json.loads(content)
+
+
+def _extends_candidates(config_rel, specifier):
+    """
+    List the repo-relative files an `extends` specifier may name, most likely first
+
+    Relative specifiers are resolved against the extending config's directory; package specifiers
+    against node_modules directories from there up to the repository root
+
+    Args:
+        config_rel (str): Repo-relative path of the extending config
+        specifier (str): `extends` value
+
+    Returns:
+        list: Candidate repo-relative paths; empty for specifiers leaving the repository
+    """
+    config_dir = posixpath.dirname(config_rel) or "."
+    if specifier.startswith((".", "/")):
+        bases = [posixpath.normpath(posixpath.join(config_dir, specifier))]
+    else:
+        bases, directory = [], config_dir
+        while True:
+            bases.append(posixpath.normpath(posixpath.join(directory, "node_modules", specifier)))
+            if directory == ".":
+                break
+            directory = posixpath.dirname(directory) or "."
+    candidates = []
+    for base in bases:
+        if base.startswith(".."):
+            continue
+        candidates.extend([base, f"{base}.json"] if not base.endswith(".json") else [base])
+        candidates.append(posixpath.join(base, "tsconfig.json"))
+    return candidates
+
+
+def load_compiler_options(config_rel, read, logger=None, _seen=None):
+    """
+    Read baseUrl and paths from a config and the configs it extends
+
+    A config's own options override those it extends (later entries of an `extends` array override
+    earlier ones). baseUrl is relative to the config declaring it, and paths to baseUrl or, without
+    one, to the config declaring them
+
+    Args:
+        config_rel (str): Repo-relative config path
+        read (callable): Repo-relative path -> file content; raises OSError or FileOperationError when missing
+        logger (Logger|None): Optional logger
+
+    Returns:
+        dict: {'base_url': repo-relative dir or None, 'paths': dict, 'paths_dir': repo-relative dir,
+            'chain': [configs read, extending config first]}
+    """
+    seen = _seen if _seen is not None else []
+    seen.append(config_rel)
+    data = _parse_jsonc(read(config_rel))
+    options = {"base_url": None, "paths": {}, "paths_dir": ".", "chain": [config_rel]}
+    extends = data.get("extends") if isinstance(data, dict) else None
+    for specifier in [extends] if isinstance(extends, str) else extends if isinstance(extends, list) else []:
+        if not isinstance(specifier, str) or len(seen) >= MAX_EXTENDS_DEPTH:
+            continue
+        for candidate in _extends_candidates(config_rel, specifier):
+            if candidate in seen:
+                break
+            try:
+                parent = load_compiler_options(candidate, read, logger, seen)
+            except ValueError as exc:
+                if logger:
+                    logger.warning(f"Could not parse {candidate} (extended by {config_rel}): {exc}")
+                break
+            except (OSError, FileOperationError):
+                continue
+            if parent["base_url"]:
+                options["base_url"] = parent["base_url"]
+            if parent["paths"]:
+                options["paths"], options["paths_dir"] = parent["paths"], parent["paths_dir"]
+            options["chain"].extend(parent["chain"])
+            break
+        else:
+            if logger:
+                logger.debug(f"{config_rel} extends '{specifier}', which is not in the repository")
+
+    compiler_options = data.get("compilerOptions") if isinstance(data, dict) else None
+    compiler_options = compiler_options if isinstance(compiler_options, dict) else {}
+    config_dir = posixpath.dirname(config_rel) or "."
+    base_url = compiler_options.get("baseUrl")
+    if isinstance(base_url, str):
+        options["base_url"] = posixpath.normpath(posixpath.join(config_dir, base_url))
+    paths = compiler_options.get("paths")
+    if isinstance(paths, dict):
+        options["paths"] = {pattern: targets for pattern, targets in paths.items() if isinstance(targets, list)}
+        options["paths_dir"] = config_dir
+    return options
+
+
+def rebased_paths(options):
+    """
+    Return (base_url, paths) with paths targets relative to base_url, or to the repository root without one
+
+    Args:
+        options (dict): Result of load_compiler_options
+
+    Returns:
+        Tuple of (base_url or None, paths dict)
+    """
+    if options["base_url"] or options["paths_dir"] == ".":
+        return options["base_url"], options["paths"]
+    return None, {
+        pattern: [posixpath.normpath(posixpath.join(options["paths_dir"], target)) for target in targets]
+        for pattern, targets in options["paths"].items()
+    }
+
+
+def package_path_aliases(paths):
+    """
+    Map paths patterns whose first target lies in node_modules to the package it names
+
+    Example: {"react": ["./node_modules/preact/compat"]} -> {"react": "preact"}
+
+    Args:
+        paths (dict): paths configuration
+
+    Returns:
+        dict: Pattern -> npm package name
+    """
+    aliases = {}
+    for pattern, targets in (paths or {}).items():
+        target = next((target for target in targets if isinstance(target, str)), "")
+        if "node_modules/" not in target:
+            continue
+        parts = target.rsplit("node_modules/", 1)[1].split("/")
+        package_name = "/".join(parts[:2]) if parts[0].startswith("@") else parts[0]
+        if package_name and "*" not in package_name and not package_name.endswith("/"):
+            aliases[pattern] = package_name
+    return aliases
 
 
 def parse_ts_js_config(repo_path, js_config_files, ts_config_files, secure_file_ops, logger):
     """
-    Parse root-level tsconfig/jsconfig for baseUrl and paths
+    Parse root-level tsconfig/jsconfig, and the configs it extends, for baseUrl and paths
 
     Args:
         repo_path (str): Absolute repository path
@@ -25,7 +183,7 @@ def parse_ts_js_config(repo_path, js_config_files, ts_config_files, secure_file_
         logger (Logger|None): Optional logger for notes and warnings
 
     Returns:
-        Tuple of (base_url or None, paths dict)
+        Tuple of (base_url or None, paths dict), both relative to the repository root
     """
 
     def _root_relative(path):
@@ -63,42 +221,24 @@ def parse_ts_js_config(repo_path, js_config_files, ts_config_files, secure_file_
             )
         return None, {}
 
-    try:
+    def _read(rel_path):
         if secure_file_ops:
-            rel_path = secure_file_ops.get_relative_path(chosen_config)
-            content = secure_file_ops.read_file(rel_path, encoding="utf-8-sig")
-        else:
-            with open(chosen_config, "r", encoding="utf-8-sig") as handle:
-                content = handle.read()
-
-        comment_regex = r"'(\\'|[^'])*?'|\"(\\\"|[^\"])*?\"|//[^\r\n]*|/\*(?:(?!\*/).)*\*/"
-        content = re.sub(
-            comment_regex,
-            lambda match: match.group(0)
-            if match.group(0).startswith('"') or match.group(0).startswith("'")
-            else "",
-            content,
-            flags=re.S,
-        )
-        content = re.sub(r",\s*([\]}])", r"\1", content)
-
-        data = json.loads(content)
-        compiler_options = data.get("compilerOptions", {})
-
-        base_url = compiler_options.get("baseUrl")
-        if base_url is not None and not isinstance(base_url, str):
-            base_url = None
-
-        paths = compiler_options.get("paths")
-        if paths is not None and not isinstance(paths, dict):
-            paths = {}
+            return secure_file_ops.read_file(rel_path, encoding="utf-8-sig")
+        with open(os.path.join(repo_path, rel_path), "r", encoding="utf-8-sig") as handle:
+            return handle.read()
+
+    try:
+        options = load_compiler_options(Path(_root_relative(chosen_config)).as_posix(), _read, logger)
+        base_url, paths = rebased_paths(options)
 
+        if len(options["chain"]) > 1 and logger:
+            logger.info(f"Followed {config_type} extends chain: {' -> '.join(options['chain'])}")
         if base_url and logger:
             logger.info(f"Extracted baseUrl '{base_url}' from {config_type}")
         if paths and logger:
             logger.info(f"Extracted paths configuration from {config_type}: {paths}")
 
-        return base_url, paths or {}
+        return base_url, paths
 
     except FileNotFoundError:
         if logger:
@@ -134,6 +274,7 @@ def create_alias_resolver(repo_path, source_files, base_url, paths, logger):
     config = AliasConfiguration()
     if paths:
         config.ts_js_paths = paths
+        config.ts_js_package_paths = package_path_aliases(paths)
     if base_url:
         config.base_url = base_url
     resolver = UnifiedAliasResolver(
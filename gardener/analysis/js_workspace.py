@@ -0,0 +1,151 @@
+"""
+npm, Yarn and pnpm workspace members
+
+Monorepos list their packages in the root package.json `workspaces` field (an array, or Yarn's
+`{"packages": [...]}`) or in pnpm-workspace.yaml `packages`, as directory globs (`packages/*`,
+`apps/**`, `!**/test/**`). Every package.json under a matching directory is a member: it is not
+an npm dependency even when siblings declare it with a registry version range, and imports of it
+(`@acme/ui`, `@acme/ui/button`) resolve to the sibling's sources through its `exports`, `module`
+or `main` fields, or the subpath under its directory, so they become local monorepo edges.
+Members are reported under `analyzer_details.workspace_members` with the `workspace-member`
+annotation, `ecosystem: npm`, the sibling's `path` and its `dependencies` by package.json section
+"""
+
+import fnmatch
+import json
+from pathlib import PurePosixPath
+
+import yaml
+
+from gardener.analysis.cargo_workspace import WORKSPACE_MEMBER
+from gardener.common.file_helpers import read_repo_file
+from gardener.common.repo_paths import normalize_dir
+from gardener.treewalk.javascript import NPM_DEP_KIND_ORDER
+
+PNPM_WORKSPACE_FILE = "pnpm-workspace.yaml"
+
+
+def _glob_matches(dir_parts, pattern_parts):
+    """
+    Match directory parts against glob parts, where `**` spans any number of directories
+    """
+    if not pattern_parts:
+        return not dir_parts
+    if pattern_parts[0] == "**":
+        return any(_glob_matches(dir_parts[index:], pattern_parts[1:]) for index in range(len(dir_parts) + 1))
+    return bool(dir_parts) and (
+        fnmatch.fnmatchcase(dir_parts[0], pattern_parts[0]) and _glob_matches(dir_parts[1:], pattern_parts[1:])
+    )
+
+
+def workspace_globs(root_data, pnpm_workspace):
+    """
+    Collect member globs from the root package.json and pnpm-workspace.yaml
+
+    Args:
+        root_data (dict): Parsed root package.json, or {}
+        pnpm_workspace (dict): Parsed pnpm-workspace.yaml, or {}
+
+    Returns:
+        list: (glob, via) pairs; globs starting with `!` exclude directories
+    """
+    globs = []
+    workspaces = root_data.get("workspaces")
+    if isinstance(workspaces, dict):
+        workspaces = workspaces.get("packages")
+    for pattern in workspaces if isinstance(workspaces, list) else []:
+        if isinstance(pattern, str):
+            globs.append((pattern, "workspaces"))
+    for pattern in pnpm_workspace.get("packages") or [] if isinstance(pnpm_workspace, dict) else []:
+        if isinstance(pattern, str):
+            globs.append((pattern, "pnpm-workspace"))
+    return globs
+
+
+def _matches(rel_dir, pattern):
+    normalized = normalize_dir(pattern.lstrip("!").rstrip("/"))
+    if not normalized:
+        return False
+    dir_parts = [] if rel_dir == "." else rel_dir.split("/")
+    return _glob_matches(dir_parts, [] if normalized == "." else normalized.split("/"))
+
+
+def read_package_manifests(manifest_files, secure_file_ops, logger, repo_path):
+    """
+    Read the package.json files of this repository, skipping node_modules
+
+    Args:
+        manifest_files (list): Absolute manifest paths; only package.json files are read
+        secure_file_ops (SecureFileOps|None): Secure file operations or None
+        logger (Logger|None): Optional logger
+        repo_path (str): Absolute repository path
+
+    Returns:
+        dict: Package directory relative to the repository ('.' for the root) -> (relative manifest path, data)
+    """
+    packages = {}
+    for path in manifest_files:
+        pure = PurePosixPath(path)
+        if pure.name != "package.json" or "node_modules" in pure.parts:
+            continue
+        try:
+            if secure_file_ops:
+                rel_path = secure_file_ops.get_relative_path(path)
+            else:
+                rel_path = str(pure.relative_to(repo_path))
+            data = json.loads(read_repo_file(path, secure_file_ops))
+        except Exception as exc:
+            if logger:
+                logger.debug(f"Could not read {path} for workspace members: {exc}")
+            continue
+        if isinstance(data, dict):
+            packages[normalize_dir(str(PurePosixPath(rel_path).parent))] = (rel_path, data)
+    return packages
+
+
+def collect_js_workspace_members(manifest_files, secure_file_ops, logger, repo_path):
+    """
+    Collect the package.json packages of this repository covered by workspace globs
+
+    Args:
+        manifest_files (list): Absolute manifest paths; only package.json files are read
+        secure_file_ops (SecureFileOps|None): Secure file operations or None
+        logger (Logger|None): Optional logger
+        repo_path (str): Absolute repository path
+
+    Returns:
+        dict: Package name -> {'annotation': 'workspace-member', 'ecosystem': 'npm', 'manifest', 'path',
+            'import_names', 'via', 'dependencies'}, where via lists 'workspaces' and/or 'pnpm-workspace'
+            and dependencies maps each package.json dependency section to the names it declares
+    """
+    packages = read_package_manifests(manifest_files, secure_file_ops, logger, repo_path)
+    try:
+        pnpm_path = str(PurePosixPath(repo_path) / PNPM_WORKSPACE_FILE)
+        pnpm_workspace = yaml.safe_load(read_repo_file(pnpm_path, secure_file_ops))
+    except Exception:
+        pnpm_workspace = {}
+    globs = workspace_globs((packages.get(".") or (None, {}))[1], pnpm_workspace or {})
+
+    members = {}
+    for package_dir, (rel_path, data) in sorted(packages.items()):
+        name = data.get("name")
+        if package_dir == "." or not isinstance(name, str):
+            continue
+        included = [via for pattern, via in globs if not pattern.startswith("!") and _matches(package_dir, pattern)]
+        if not included or any(_matches(package_dir, pattern) for pattern, _ in globs if pattern.startswith("!")):
+            continue
+        members[name] = {
+            "annotation": WORKSPACE_MEMBER,
+            "ecosystem": "npm",
+            "manifest": rel_path,
+            "path": package_dir,
+            "import_names": [name],
+            "via": sorted(set(included)),
+            "dependencies": {
+                kind: sorted(data[kind]) for kind in NPM_DEP_KIND_ORDER if isinstance(data.get(kind), dict)
+            },
+        }
+
+    if logger and members:
+        logger.info(f"... Found {len(members)} JS workspace packages: {', '.join(sorted(members))}")
+    return members
@@ -2,22 +2,58 @@
 Main analysis module with persistence abstraction
 """
 
+import contextlib
 import os
 
 import networkx as nx
 
+from gardener.analysis.bundle import Bundle, request_hook
 from gardener.analysis.centrality import CentralityCalculator
-from gardener.analysis.graph import DependencyGraphBuilder
+from gardener.analysis.evidence import aggregate_evidence
+from gardener.analysis.graph import DependencyGraphBuilder, import_distribution_resolver, preliminary_weights
+from gardener.analysis.normalization import language_sizes, normalize_by_language, package_languages
+from gardener.analysis.ownership import classify_ownership
+from gardener.analysis.pipeline import PipelineTimings, StageThread
+from gardener.analysis.reproduce import build_reproduce_block
+from gardener.analysis.signing import sign_report
+from gardener.analysis.smoothing import load_previous_report, smooth_top_dependencies
+from gardener.analysis.stdlib_usage import stdlib_usage
+from gardener.analysis.suggestions import run_suggestion_passes
+from gardener.analysis.thresholds import apply_evidence_thresholds
 from gardener.analysis.tree import RepositoryAnalyzer
-from gardener.common.defaults import ConfigOverride, GraphAnalysisConfig as cfg, apply_config_overrides
-from gardener.common.utils import Logger, get_repo
+from gardener.common.cancellation import Cancellation
+from gardener.common.defaults import (
+    ConfigOverride,
+    GraphAnalysisConfig as cfg,
+    ResourceLimits,
+    apply_config_overrides,
+    cache_path,
+)
+from gardener.common.git_snapshot import GitSnapshot, is_bare_repository
+from gardener.common.network import offline
+from gardener.common.utils import Logger, RepositoryError, get_repo
+from gardener.package_metadata.crates_index import CratesIndex
+from gardener.package_metadata.enrichment import SKIPPED_BUDGET, SKIPPED_CANCELLED
+from gardener.package_metadata.github_auth import github_auth_scope
+from gardener.package_metadata.health import ResolutionHealth
+from gardener.package_metadata.name_resolvers.python import load_import_map
+from gardener.package_metadata.seed import ResolutionSeed
+from gardener.package_metadata.tls_pins import PinMismatchError, RegistryPins
 from gardener.package_metadata.url_resolver import resolve_package_urls
 from gardener.persistence.file import FilePersistence
+from gardener.persistence.ipfs import cid_from_uri
 from gardener.treewalk.go import GoLanguageHandler
+from gardener.treewalk.helm import HelmLanguageHandler
+from gardener.treewalk.homebrew import HomebrewLanguageHandler
+from gardener.treewalk.html import HtmlLanguageHandler
+from gardener.treewalk.idl import IdlLanguageHandler
+from gardener.treewalk.ios import IosLanguageHandler
 from gardener.treewalk.javascript import JavaScriptLanguageHandler
+from gardener.treewalk.notebook import NotebookLanguageHandler
 from gardener.treewalk.python import PythonLanguageHandler
 from gardener.treewalk.rust import RustLanguageHandler
 from gardener.treewalk.solidity import SolidityLanguageHandler
+from gardener.treewalk.stylesheet import StylesheetLanguageHandler
 from gardener.treewalk.typescript import TypeScriptLanguageHandler
 
 
@@ -28,16 +64,26 @@ class DependencyAnalyzer:
     This class is persistence-agnostic and returns pure data structures
     """
 
-    def __init__(self, verbose=False):
+    def __init__(self, verbose=False, cancellation=None, logger=None):
         """
         Args:
             verbose (bool): Enable verbose logging
+            cancellation (Cancellation): Optional flag; once requested, import extraction and URL
+                resolution stop early and the results are marked partial
+            logger (Logger): Optional logger to use instead of one built from `verbose`
         """
         self.verbose = verbose
-        self.logger = Logger(verbose=verbose)
+        self.logger = logger or Logger(verbose=verbose)
+        self.cancellation = cancellation or Cancellation()
 
         # Initialize components that persist across analysis phases
         self.repo_analyzer = None
+        self.cache_state = None
+        self.enrichment_state = None
+        self.health_state = None
+        self.pins_state = None
+        self.crates_index_state = None
+        self.timings = PipelineTimings()
         self.graph_builder = DependencyGraphBuilder(self.logger)
         self.centrality_calculator = CentralityCalculator(self.logger)
 
@@ -52,6 +98,13 @@ class DependencyAnalyzer:
             "go": GoLanguageHandler(self.logger),
             "rust": RustLanguageHandler(self.logger),
             "solidity": SolidityLanguageHandler(self.logger),
+            "idl": IdlLanguageHandler(self.logger),
+            "stylesheet": StylesheetLanguageHandler(self.logger),
+            "html": HtmlLanguageHandler(self.logger),
+            "notebook": NotebookLanguageHandler(self.logger),
+            "helm": HelmLanguageHandler(self.logger),
+            "ios": IosLanguageHandler(self.logger),
+            "homebrew": HomebrewLanguageHandler(self.logger),
         }
         for language, handler in language_handlers.items():
             self.repo_analyzer.register_language_handler(language, handler)
@@ -63,8 +116,10 @@ class DependencyAnalyzer:
         Returns:
             Dict of external packages
         """
-        self.repo_analyzer.scan_repo()
-        return self.repo_analyzer.process_manifest_files()
+        with self.timings.stage("scan"):
+            self.repo_analyzer.scan_repo()
+        with self.timings.stage("manifests"):
+            return self.repo_analyzer.process_manifest_files()
 
     def discover_packages(self, repo_path, specific_languages=None):
         """
@@ -77,7 +132,10 @@ class DependencyAnalyzer:
         Returns:
             Dictionary of external packages found
         """
-        self.repo_analyzer = RepositoryAnalyzer(repo_path, specific_languages, self.logger)
+        self.timings = PipelineTimings()
+        self.repo_analyzer = RepositoryAnalyzer(
+            repo_path, specific_languages, self.logger, cancellation=self.cancellation, timings=self.timings
+        )
         self._register_language_handlers()
         return self._scan_and_process_manifests()
 
@@ -95,6 +153,7 @@ class DependencyAnalyzer:
             self.repo_analyzer.file_imports,
             self.repo_analyzer.file_package_components,
             self.repo_analyzer.local_imports_map,
+            file_import_annotations=self.repo_analyzer.file_import_annotations,
         )
         return graph
 
@@ -126,8 +185,23 @@ class DependencyAnalyzer:
             if root_dist_name in self.repo_analyzer.external_packages:
                 import_names = self.repo_analyzer.external_packages[root_dist_name].get("import_names", [])
                 all_self_package_names.update(import_names)
+        # Cargo `{ path = "..." }` crates outside the workspace are local code as well
+        for name, info in self.repo_analyzer.external_packages.items():
+            if info.get("ecosystem") == "cargo" and info.get("local_path"):
+                all_self_package_names.add(name)
         return all_self_package_names
 
+    def _first_party_packages(self):
+        """
+        Classify external packages against FIRST_PARTY_OWNERS
+
+        Returns:
+            Set[str] of first-party package names to leave out of the top dependencies
+            (empty when EXCLUDE_FIRST_PARTY is off)
+        """
+        first_party = classify_ownership(self.repo_analyzer.external_packages, cfg.FIRST_PARTY_OWNERS, self.logger)
+        return first_party if cfg.EXCLUDE_FIRST_PARTY else set()
+
     def _normalize_top_dependencies(self, top_deps_tuples):
         """
         Convert top dependency tuples into enriched dicts with percentages and URLs
@@ -163,7 +237,12 @@ class DependencyAnalyzer:
         Assemble final results dict with graph data and analyzer details
 
         Returns:
-            Dict with keys: external_packages, dependency_graph, top_dependencies, analyzer_details
+            Dict with keys: external_packages, dependency_graph, top_dependencies, analyzer_details,
+            reproduce, toolchains, suggestions (plus stdlib_usage when STDLIB_USAGE is enabled,
+            analyzer_details.evidence in place of the per-file evidence when EVIDENCE is 'aggregated',
+            resolution_health when repository URLs were resolved, registry_pins when registry TLS
+            fingerprints were checked, crates_index when crates were answered from the crates.io cache
+            or the sparse index was unreachable, and partial/cancellation when the run was cancelled)
         """
         results = {
             "external_packages": self.repo_analyzer.external_packages,
@@ -173,6 +252,27 @@ class DependencyAnalyzer:
                 "local_imports_map": self.repo_analyzer.local_imports_map,
                 "file_imports": self.repo_analyzer.file_imports,
                 "file_package_components": self.repo_analyzer.file_package_components,
+                "file_import_annotations": self.repo_analyzer.file_import_annotations,
+                "import_disambiguations": self.repo_analyzer.import_disambiguations,
+                "tooling_dependencies": self.repo_analyzer.tooling_dependencies,
+                "idl_package_hints": self.repo_analyzer.idl_package_hints,
+                "workspace_members": self.repo_analyzer.workspace_members,
+                "panics": self.repo_analyzer.panics.summary(),
+                "manifest_diagnostics": self.repo_analyzer.manifest_diagnostics,
+                "build_scripts": self.repo_analyzer.build_scripts,
+                "unused_declared": self.repo_analyzer.unused_declared,
+                "used_undeclared": self.repo_analyzer.used_undeclared,
+                "reexport_chains": self.repo_analyzer.reexport_chains,
+                "rust_targets": self.repo_analyzer.rust_targets,
+                "file_target_kinds": self.repo_analyzer.file_target_kinds,
+                "rust_packages": self.repo_analyzer.rust_packages,
+                "ts_projects": self.repo_analyzer.ts_projects,
+                "python_extras": self.repo_analyzer.python_extras,
+                "yarn_pnp": self.repo_analyzer.yarn_pnp,
+                "dynamic_imports": self.repo_analyzer.dynamic_imports,
+                "module_systems": self.repo_analyzer.module_systems,
+                "skipped_files": self.repo_analyzer.skipped_files,
+                "bundler_aliases": self.repo_analyzer.bundler_aliases,
                 "total_files": len(self.repo_analyzer.source_files),
                 "languages_detected": (
                     list(
@@ -185,15 +285,47 @@ class DependencyAnalyzer:
                     else []
                 ),
             },
+            "reproduce": build_reproduce_block(self.repo_analyzer, self.cache_state),
+            "toolchains": self.repo_analyzer.toolchains,
+            "suggestions": run_suggestion_passes(self.repo_analyzer.external_packages, self.logger),
         }
+        if cfg.EVIDENCE == "aggregated":
+            details = results["analyzer_details"]
+            details["evidence"] = aggregate_evidence(
+                details.pop("file_imports"), details.pop("file_package_components"), cfg.EVIDENCE_SAMPLE_FILES
+            )
+        if cfg.STDLIB_USAGE:
+            results["stdlib_usage"] = stdlib_usage(self.repo_analyzer.file_imports, self.repo_analyzer.source_files)
+        if self.repo_analyzer.cargo_vendor:
+            results["analyzer_details"]["cargo_vendor"] = self.repo_analyzer.cargo_vendor
+        if self.repo_analyzer.cargo_metadata:
+            results["analyzer_details"]["cargo_metadata"] = self.repo_analyzer.cargo_metadata
+        if self.health_state is not None:
+            results["resolution_health"] = self.health_state
+        if self.pins_state is not None:
+            results["registry_pins"] = self.pins_state
+        if self.crates_index_state is not None:
+            results["crates_index"] = self.crates_index_state
+        if self.cancellation.requested:
+            results["partial"] = True
+            results["cancellation"] = self.cancellation.summary()
+        if self.enrichment_state:
+            results["analyzer_details"]["enrichment"] = self.enrichment_state
+            results["reproduce"]["enrichment_budget"] = {
+                "max_requests": self.enrichment_state["max_requests"],
+                "timeout_seconds": self.enrichment_state["timeout_seconds"],
+            }
         return results
 
-    def analyze_dependencies(self, external_packages_with_urls):
+    def analyze_dependencies(self, external_packages_with_urls, enrich=None, extract_imports=True):
         """
         Analyze dependencies after URLs have been resolved for external packages
 
         Args:
             external_packages_with_urls (dict): The external packages dict, now with 'repository_url' populated
+            enrich (callable): Optional callback run after ranking with the ranked package names, used to
+                resolve URLs highest-weight first
+            extract_imports (bool): Set to False when imports were already extracted for this run
 
         Returns:
             The final analysis results dictionary
@@ -205,49 +337,232 @@ class DependencyAnalyzer:
         self.repo_analyzer.external_packages = external_packages_with_urls
 
         # Extract imports from files
-        self.repo_analyzer.extract_imports_from_all_files()
+        if extract_imports:
+            with self.timings.stage("imports"):
+                self.repo_analyzer.extract_imports_from_all_files()
 
         # Build dependency graph and calculate scores
-        graph = self._build_dependency_graph()
-        ranked_scores = self._calculate_importance_scores(graph)
+        with self.timings.stage("graph"):
+            graph = self._build_dependency_graph()
+        with self.timings.stage("ranking"):
+            ranked_scores = self._calculate_importance_scores(graph)
 
         # Get top dependencies tuples and normalize
         all_self_package_names = self._collect_self_package_names()
+        first_party = self._first_party_packages()
         top_deps_tuples = self.graph_builder.get_top_dependencies(
-            ranked_scores, all_self_package_names=all_self_package_names
+            ranked_scores, all_self_package_names=all_self_package_names | first_party
+        )
+        if enrich:
+            enrich([package_name for package_name, _ in top_deps_tuples])
+            # Resolved repository URLs can reveal more first-party packages
+            first_party = self._first_party_packages()
+            top_deps_tuples = [(name, score) for name, score in top_deps_tuples if name not in first_party]
+        top_deps_tuples, below_threshold = apply_evidence_thresholds(
+            top_deps_tuples,
+            self.repo_analyzer.external_packages,
+            self.repo_analyzer.file_imports,
+            cfg.MIN_IMPORT_COUNT,
+            cfg.MIN_FILES,
         )
+        normalization = None
+        if cfg.WEIGHT_NORMALIZATION != "global":
+            top_deps_tuples, normalization = self._normalize_by_language(top_deps_tuples)
         top_deps = self._normalize_top_dependencies(top_deps_tuples)
 
         # Assemble and return results
-        return self._assemble_results(graph, top_deps)
+        with self.timings.stage("assembly"):
+            results = self._assemble_results(graph, top_deps)
+        if cfg.MIN_IMPORT_COUNT or cfg.MIN_FILES:
+            results["below_threshold"] = below_threshold
+        if normalization is not None:
+            for dep in top_deps:
+                if dep["package_name"] in normalization["package_languages"]:
+                    dep["language"] = normalization["package_languages"][dep["package_name"]]
+            results["analyzer_details"]["normalization"] = {
+                "mode": cfg.WEIGHT_NORMALIZATION,
+                "languages": normalization["languages"],
+            }
+        if cfg.TIMINGS:
+            results["timings"] = self.timings.summary()
+            self.logger.info(
+                "... Timings: "
+                + ", ".join(f"{name} {seconds:.2f}s" for name, seconds in results["timings"]["stages"].items())
+            )
+        return results
+
+    def _normalize_by_language(self, top_deps_tuples):
+        """
+        Rebalance ranked packages across languages according to WEIGHT_NORMALIZATION
+
+        Args:
+            top_deps_tuples: List of (package_name, score)
+
+        Returns:
+            tuple: (rescaled tuples, {'package_languages', 'languages'})
+        """
+        analyzer = self.repo_analyzer
+        basis = "loc" if cfg.WEIGHT_NORMALIZATION == "language-loc" else "files"
+        sizes = language_sizes(analyzer.source_files, basis, analyzer.secure_file_ops.read_file)
+        languages = package_languages(analyzer.external_packages, analyzer.file_imports, analyzer.source_files)
+        rescaled, details = normalize_by_language(top_deps_tuples, languages, sizes)
+        self.logger.info(f"... Normalized top dependency weights across {len(details)} languages by {basis}")
+        return rescaled, {"package_languages": languages, "languages": details}
+
+    def record_cache_state(self, external_packages, url_cache):
+        """
+        Summarize the URL cache used for a run for the reproduce block
+
+        Args:
+            external_packages (dict): External packages mapping
+            url_cache (dict|None): URL cache keyed 'ecosystem:package_name'
+        """
+        cache = url_cache or {}
+        hits = sum(1 for name, info in external_packages.items() if f"{info.get('ecosystem')}:{name}" in cache)
+        self.cache_state = {"url_cache": {"provided": url_cache is not None, "entries": len(cache), "hits": hits}}
 
-    def _resolve_repository_urls(self, external_packages, url_cache=None):
+    def _resolve_repository_urls(self, external_packages, url_cache=None, budget=None, priority=None, requests=None):
         """
         Resolve repository URLs with cache and robust defaults
 
         Args:
             external_packages (dict): External packages mapping
             url_cache (dict): Optional URL cache
+            budget (EnrichmentBudget): Optional request/time cap; packages past it are marked skipped-budget
+            priority (list): Optional package names to resolve first, highest weight first
+            requests (BoundedQueue): Optional queue of (package_name, info) pairs to resolve instead, in the
+                order they arrive; external_packages is only read once the queue is closed
 
         Returns:
             Dict of external_packages with 'repository_url' keys ensured
         """
         self.logger.info("... Resolving repository URLs for external packages")
+        ordered = external_packages if requests is None else requests
+        if priority:
+            ordered = {name: external_packages[name] for name in priority if name in external_packages}
+            ordered.update((name, info) for name, info in external_packages.items() if name not in ordered)
+        health = ResolutionHealth()
+        pins = None
+        if cfg.PIN_REGISTRIES:
+            pins = RegistryPins(cache_path(cfg.REGISTRY_PINS_FILE), strict=cfg.STRICT_NETWORK, logger=self.logger)
+        crates_index = None
+        if cfg.CRATES_INDEX_CACHE or cfg.CRATES_INDEX_SNAPSHOT:
+            crates_index = CratesIndex(cache_path(cfg.CRATES_INDEX_CACHE), cfg.CRATES_INDEX_SNAPSHOT)
         try:
-            resolved_urls = resolve_package_urls(external_packages, self.logger, cache=url_cache)
+            resolved_urls = resolve_package_urls(
+                ordered,
+                self.logger,
+                cache=url_cache,
+                budget=budget,
+                health=health,
+                cancellation=self.cancellation,
+                pins=pins,
+                crates_index=crates_index,
+            )
             for package_name, url in resolved_urls.items():
                 if package_name in external_packages:
                     external_packages[package_name]["repository_url"] = url
             for package_name in external_packages:
                 if "repository_url" not in external_packages[package_name]:
                     external_packages[package_name]["repository_url"] = ""
+        except PinMismatchError:
+            raise
         except Exception as e:
             self.logger.warning(f"Error during bulk URL resolution: {e}")
+            if requests is not None:
+                # Let import extraction finish adding packages before they are defaulted
+                for _ in requests:
+                    pass
             for package_name in external_packages:
                 external_packages[package_name].setdefault("repository_url", "")
+        self.record_cache_state(external_packages, url_cache)
+        self.health_state = health.summary()
+        if pins is not None and pins.checked:
+            self.pins_state = pins.summary()
+        if crates_index is not None:
+            crates_index.save(self.logger)
+            if crates_index.online is False or any(crates_index.fallbacks.values()):
+                self.crates_index_state = crates_index.summary()
+        if self.cancellation.requested:
+            cancelled = sum(1 for info in external_packages.values() if info.get("enrichment") == SKIPPED_CANCELLED)
+            self.cancellation.record("url_resolution", len(external_packages) - cancelled, cancelled)
+        if budget is not None:
+            skipped = sum(1 for info in external_packages.values() if info.get("enrichment") == SKIPPED_BUDGET)
+            self.enrichment_state = budget.summary(skipped)
+            if skipped:
+                self.logger.warning(
+                    f"Enrichment budget exhausted ({budget.exhausted_by}): "
+                    f"{skipped} lower-weight packages marked '{SKIPPED_BUDGET}'"
+                )
         return external_packages
 
-    def analyze(self, repo_path, specific_languages=None, url_cache=None):
+    def _preliminary_priority(self, external_packages):
+        """
+        Order packages by preliminary weight, direct dependencies ahead of transitive ones on ties
+
+        Args:
+            external_packages (dict): External packages mapping
+
+        Returns:
+            List of package names, heaviest first
+        """
+        weights = preliminary_weights(external_packages, self.repo_analyzer.file_imports)
+        return sorted(
+            external_packages,
+            key=lambda name: (-weights[name], bool(external_packages[name].get("transitive"))),
+        )
+
+    def _stream_repository_urls(self, external_packages, url_cache=None, budget=None):
+        """
+        Extract imports and resolve repository URLs concurrently
+
+        The parse stage puts each package on a bounded request queue the first time a parsed file
+        imports it, and a resolver thread works through the queue while parsing goes on; a full queue
+        holds parsing back until the resolver catches up. Packages no file imports (and CDN packages
+        registered after parsing) follow in declared order
+
+        Args:
+            external_packages (dict): External packages mapping
+            url_cache (dict): Optional URL cache
+            budget (EnrichmentBudget): Optional request/time cap; packages past it are marked skipped-budget
+
+        Returns:
+            Dict of external_packages with 'repository_url' keys ensured
+
+        Raises:
+            PinMismatchError: With strict pins, when a registry presents a public key other than the pinned one
+        """
+        requests = self.timings.queue("resolution_requests", ResourceLimits.PIPELINE_QUEUE_SIZE)
+        dist_for = import_distribution_resolver(external_packages)
+        requested = set()
+
+        def request(package_names):
+            for name in package_names:
+                if name and name not in requested and name in external_packages:
+                    requested.add(name)
+                    requests.put((name, external_packages[name]))
+
+        def resolve():
+            try:
+                with self.timings.stage("url_resolution"):
+                    return self._resolve_repository_urls(external_packages, url_cache, budget=budget, requests=requests)
+            finally:
+                requests.abandon()
+
+        resolver = StageThread("resolve", resolve)
+        resolver.start()
+        try:
+            with self.timings.stage("imports"):
+                self.repo_analyzer.extract_imports_from_all_files(
+                    on_file_imports=lambda rel_path, imports: request(map(dist_for, imports))
+                )
+            request(list(external_packages))
+        finally:
+            requests.close()
+            resolver.join()
+        return resolver.join_result()
+
+    def analyze(self, repo_path, specific_languages=None, url_cache=None, enrichment_budget=None):
         """
         Analyze a repository and return the results as a data structure
 
@@ -257,25 +572,64 @@ class DependencyAnalyzer:
             repo_path (str): Path to the repository to analyze
             specific_languages (list): Optional list of languages to analyze
             url_cache (dict): Optional pre-populated cache for package URLs
+            enrichment_budget (EnrichmentBudget): Optional cap on URL resolution; it is spent in
+                URL_RESOLUTION_ORDER, heaviest packages first
 
         Returns:
             Dictionary containing:
                 - external_packages: Package metadata with URLs
                 - dependency_graph: NetworkX graph as dictionary
                 - top_dependencies: List of top dependencies with percentages
+                - below_threshold: Packages left out of top_dependencies by MIN_IMPORT_COUNT/MIN_FILES
+                  (only when a threshold is set)
+                - timings: Seconds per stage and pipeline queue statistics (only with TIMINGS)
                 - analyzer_details: Additional analysis metadata
         """
         # Step 1: Discover packages from manifests
         external_packages = self.discover_packages(repo_path, specific_languages)
 
-        # Step 2: Resolve repository URLs for external packages
-        external_packages = self._resolve_repository_urls(external_packages, url_cache)
+        order = cfg.URL_RESOLUTION_ORDER
+        if order == "ranked":
+            # Rank before resolving so lookups (and any budget) go to the highest-weight packages
+            def enrich(ranked):
+                with self.timings.stage("url_resolution"):
+                    self._resolve_repository_urls(
+                        external_packages, url_cache, budget=enrichment_budget, priority=ranked
+                    )
+
+            return self.analyze_dependencies(external_packages, enrich=enrich)
+
+        if order == "streaming":
+            # Resolve while parsing, each package as soon as a parsed file imports it
+            external_packages = self._stream_repository_urls(external_packages, url_cache, enrichment_budget)
+            return self.analyze_dependencies(external_packages, extract_imports=False)
+
+        # Step 2: Resolve repository URLs for external packages, heaviest first by import count
+        priority = None
+        if order == "preliminary":
+            with self.timings.stage("imports"):
+                self.repo_analyzer.extract_imports_from_all_files()
+            priority = self._preliminary_priority(external_packages)
+        elif order != "declared":
+            self.logger.warning(f"Unknown URL_RESOLUTION_ORDER '{order}'; resolving in declared order")
+        with self.timings.stage("url_resolution"):
+            external_packages = self._resolve_repository_urls(
+                external_packages, url_cache, budget=enrichment_budget, priority=priority
+            )
 
         # Step 3: Analyze dependencies with resolved URLs
-        return self.analyze_dependencies(external_packages)
+        return self.analyze_dependencies(external_packages, extract_imports=priority is None)
 
 
-def analyze_repository(repo_path, specific_languages=None, verbose=False, overrides=None, url_cache=None):
+def analyze_repository(
+    repo_path,
+    specific_languages=None,
+    verbose=False,
+    overrides=None,
+    url_cache=None,
+    enrichment_budget=None,
+    cancellation=None,
+):
     """
     Convenience function to analyze a repository
 
@@ -286,16 +640,20 @@ def analyze_repository(repo_path, specific_languages=None, verbose=False, overri
         specific_languages (list): Optional list of languages to analyze
         verbose (bool): Enable verbose logging
         url_cache (dict): Optional pre-populated cache for package URLs
+        enrichment_budget (EnrichmentBudget): Optional cap on registry requests / time for URL resolution
+        cancellation (Cancellation): Optional flag that cuts the run short with partial results
 
     Returns:
         Dictionary containing analysis results
     """
-    analyzer = DependencyAnalyzer(verbose=verbose)
+    analyzer = DependencyAnalyzer(verbose=verbose, cancellation=cancellation)
     # Prefer scoped overrides when provided to avoid global mutation during tests
     if overrides:
         with ConfigOverride(overrides, logger=analyzer.logger):
-            return analyzer.analyze(repo_path, specific_languages, url_cache=url_cache)
-    return analyzer.analyze(repo_path, specific_languages, url_cache=url_cache)
+            return analyzer.analyze(
+                repo_path, specific_languages, url_cache=url_cache, enrichment_budget=enrichment_budget
+            )
+    return analyzer.analyze(repo_path, specific_languages, url_cache=url_cache, enrichment_budget=enrichment_budget)
 
 
 def save_analysis_results(results, output_prefix, persistence, logger):
@@ -428,6 +786,9 @@ def _determine_output_prefix(abs_path, output_prefix):
     """
     if output_prefix is None:
         repo_name = os.path.basename(abs_path.rstrip("/"))
+        # Bare repositories are conventionally named 'repo.git'
+        if repo_name.endswith(".git") and len(repo_name) > 4:
+            repo_name = repo_name[:-4]
         return repo_name
     return output_prefix
 
@@ -452,6 +813,19 @@ def _persist_and_visualize(results, output_prefix, persistence, logger, minimal_
         logger.warning("Failed to generate some visualizations")
 
 
+def _report_published_cid(persistence, logger):
+    """
+    Print the CID of a report published with `--output ipfs://`
+
+    Args:
+        persistence (object): Persistence backend
+        logger (Logger): Logger instance
+    """
+    uri = (getattr(persistence, "written", None) or {}).get("_dependency_analysis.json") or ""
+    if uri.startswith("ipfs://"):
+        logger.info(f"\nReport CID: {cid_from_uri(uri)} (retrieve and validate with `gardener fetch {uri}`)")
+
+
 def _report_top_dependencies(results, logger):
     """
     Print top dependencies with percentages and URLs using cfg.CENTRALITY_METRIC
@@ -474,6 +848,20 @@ def _report_top_dependencies(results, logger):
         logger.info("\nNo dependencies were found, or calculation failed")
 
 
+def _report_panics(results, logger):
+    """
+    Print the number of files each analyzer failed on, if any
+
+    Args:
+        results (dict): Analysis results
+        logger (Logger): Logger instance
+    """
+    counts = (results.get("analyzer_details", {}).get("panics") or {}).get("counts")
+    if counts:
+        summary = ", ".join(f"{analyzer}: {count}" for analyzer, count in counts.items())
+        logger.warning(f"\nAnalyzer failures (files skipped): {summary}; see analyzer_details.panics")
+
+
 def run_analysis(
     repo_path,
     output_prefix=None,
@@ -482,6 +870,15 @@ def run_analysis(
     focus_languages_str=None,
     config_overrides=None,
     persistence=None,
+    sign_key=None,
+    from_bundle=None,
+    enrichment_budget=None,
+    seed=None,
+    cancellation=None,
+    ref=None,
+    github_auth=None,
+    smooth=None,
+    previous=None,
 ):
     """
     Run the full dependency analysis with the specified persistence backend
@@ -494,31 +891,105 @@ def run_analysis(
         focus_languages_str (str): Comma-separated list of languages to focus on
         config_overrides (dict): Optional dictionary of configuration parameter overrides
         persistence (object): Persistence backend to use (defaults to FilePersistence)
+        sign_key (str): Optional PEM private key path; when set the results carry a `signature` block
+        from_bundle (str): Optional bundle directory; the repository and all registry lookups are
+            served from it without network access
+        enrichment_budget (EnrichmentBudget): Optional cap on URL resolution requests / time
+        seed (str): Optional previous report whose unexpired repository URLs pre-populate the URL cache
+        cancellation (Cancellation): Optional flag; once requested the run stops early and the
+            partial results are still persisted
+        ref (str): Optional git ref to analyze; the tree at the ref is read from the object store.
+            Bare repositories are read this way at HEAD when no ref is given
+        github_auth (GitHubAppAuth|TokenAuth): Optional credentials for requests to GitHub hosts
+        smooth (float): Optional weight of the previous report's shares in an exponential moving average
+        previous (str): Previous report to smooth the top dependency shares against (with smooth)
 
     Returns:
         Dict of analysis results
     """
     logger = Logger(verbose=verbose)
+    snapshot = None
 
     # Use default file persistence if none provided
     if persistence is None:
         persistence = FilePersistence()
 
     try:
+        bundle = None
+        if from_bundle:
+            bundle = Bundle(from_bundle)
+            bundled_path = bundle.repo_path(repo_path)
+            if bundled_path is None and not os.path.isdir(repo_path):
+                raise RepositoryError(f"{repo_path} is not in bundle {from_bundle}")
+            repo_path = bundled_path or repo_path
+
         abs_path = _prepare_repository_path(repo_path, logger)
-        logger.info(f"Analyzing repository: {abs_path}")
+        source_path = abs_path
+        if ref or is_bare_repository(abs_path):
+            snapshot = GitSnapshot(abs_path, ref, logger)
+            abs_path = snapshot.path
+        logger.info(f"Analyzing repository: {source_path}")
 
         focus_languages = _parse_focus_languages(focus_languages_str, logger)
-        # Use scoped overrides for the run to avoid global state bleed-through
-        results = analyze_repository(
-            repo_path=abs_path, specific_languages=focus_languages, verbose=verbose, overrides=config_overrides
-        )
+        previous_report = previous_digest = None
+        if previous:
+            previous_report, previous_digest = load_previous_report(previous)
+        with ConfigOverride(config_overrides):
+            # A bad import map is a configuration error, reported before the run rather than during name resolution
+            load_import_map(cfg.PYTHON_IMPORT_MAP)
+        resolution_seed = None
+        if seed:
+            with ConfigOverride(config_overrides):
+                resolution_seed = ResolutionSeed.load(seed, cfg.URL_SEED_TTL_DAYS, logger=logger)
+        # Use scoped overrides for the run to avoid global state bleed-through; bundle runs
+        # replay registry lookups and refuse every other network access
+        scopes = contextlib.ExitStack()
+        if bundle:
+            scopes.enter_context(request_hook(bundle.replayer))
+            scopes.enter_context(offline())
+        with scopes, github_auth_scope(github_auth):
+            results = analyze_repository(
+                repo_path=abs_path,
+                specific_languages=focus_languages,
+                verbose=verbose,
+                overrides=config_overrides,
+                url_cache=resolution_seed.cache if resolution_seed else None,
+                enrichment_budget=enrichment_budget,
+                cancellation=cancellation,
+            )
+        if snapshot:
+            results["reproduce"]["git"] = snapshot.info
+        if resolution_seed:
+            results["analyzer_details"]["seed"] = resolution_seed.summary(results["external_packages"])
+        if previous_report is not None:
+            results["top_dependencies"], smoothing = smooth_top_dependencies(
+                results["top_dependencies"], previous_report, smooth or 0, results["external_packages"]
+            )
+            results["analyzer_details"]["smoothing"] = {**smoothing, "previous": previous, "digest": previous_digest}
+        if bundle and bundle.replayer.misses:
+            logger.warning(
+                f"{len(bundle.replayer.misses)} registry lookups were not in the bundle and were treated as not found"
+            )
+
+        if sign_key:
+            signature = sign_report(results, sign_key)
+            logger.info(f"Signed analysis results ({signature['algorithm']}, {signature['public_key_fingerprint']})")
 
-        output_prefix = _determine_output_prefix(abs_path, output_prefix)
+        output_prefix = _determine_output_prefix(source_path, output_prefix)
         _persist_and_visualize(results, output_prefix, persistence, logger, minimal_outputs)
+        if results.get("partial"):
+            logger.warning(
+                "Analysis was cancelled; the saved report is partial. Pass it to --seed to reuse the "
+                "repository URLs resolved so far"
+            )
         _report_top_dependencies(results, logger)
+        _report_panics(results, logger)
+        _report_published_cid(persistence, logger)
         return results
 
     except Exception as e:
         logger.error(f"Analysis failed: {e}")
         raise
+    finally:
+        if snapshot:
+            snapshot.cleanup()
@@ -0,0 +1,149 @@
+"""
+Robust manifest parsing
+
+Every manifest and lockfile is checked before a language handler reads it: the bytes must be
+UTF-8 and structured formats (JSON, JSONC, TOML, YAML) must parse. A file that fails is skipped,
+so truncated or mis-encoded input never reaches a handler, and is reported under
+`analyzer_details.manifest_diagnostics` as {file, format, error, line, column}. With the
+`STRICT_MANIFESTS` config flag (CLI `--strict-manifests`) the first malformed manifest, or a
+handler raising on one, aborts the run with ManifestError instead
+"""
+
+import json
+import re
+import tomllib
+from pathlib import Path
+
+import yaml
+
+from gardener.treewalk.deno import load_jsonc
+
+JSON_MANIFESTS = (
+    "package.json",
+    "package-lock.json",
+    "npm-shrinkwrap.json",
+    "import_map.json",
+    "Pipfile.lock",
+    "Brewfile.lock.json",
+)
+JSONC_MANIFESTS = ("deno.json", "deno.jsonc")
+TOML_MANIFESTS = ("Cargo.toml", "Cargo.lock", "pyproject.toml", "foundry.toml", "Pipfile", "poetry.lock", "uv.lock")
+YAML_MANIFESTS = (
+    "Chart.yaml",
+    "Chart.lock",
+    "requirements.yaml",
+    "requirements.lock",
+    "Podfile.lock",
+    "environment.yml",
+    "environment.yaml",
+    "pnpm-lock.yaml",
+)
+
+_TOML_POSITION = re.compile(r"\(at line (\d+), column (\d+)\)")
+_WIDE_BOMS = (b"\xff\xfe", b"\xfe\xff")
+
+
+class ManifestError(Exception):
+    """Raised in strict mode when a manifest cannot be parsed"""
+
+    def __init__(self, diagnostic):
+        self.diagnostic = diagnostic
+        position = f":{diagnostic['line']}:{diagnostic['column']}" if diagnostic.get("line") else ""
+        super().__init__(f"Malformed manifest {diagnostic['file']}{position}: {diagnostic['error']}")
+
+
+def manifest_format(basename):
+    """
+    Return the structured format of a manifest, or 'text' for line-based and script manifests
+    """
+    for fmt, names in (
+        ("json", JSON_MANIFESTS),
+        ("jsonc", JSONC_MANIFESTS),
+        ("toml", TOML_MANIFESTS),
+        ("yaml", YAML_MANIFESTS),
+    ):
+        if basename in names:
+            return fmt
+    return "text"
+
+
+def _diagnostic(rel_path, fmt, error, line=None, column=None):
+    return {"file": rel_path, "format": fmt, "error": error, "line": line, "column": column}
+
+
+def check_manifest_content(rel_path, data):
+    """
+    Check that manifest bytes decode and parse as their format
+
+    Args:
+        rel_path (str): Repository-relative manifest path; its basename selects the format
+        data (bytes): Raw file content
+
+    Returns:
+        dict|None: Diagnostic {file, format, error, line, column}, or None when the manifest is well-formed
+    """
+    fmt = manifest_format(Path(rel_path).name)
+    if data.startswith(_WIDE_BOMS):
+        return _diagnostic(rel_path, "encoding", "UTF-16 byte-order mark; manifests must be UTF-8", 1, 1)
+    try:
+        text = data.decode("utf-8")
+    except UnicodeDecodeError as exc:
+        line_start = data.rfind(b"\n", 0, exc.start) + 1
+        return _diagnostic(
+            rel_path,
+            "encoding",
+            f"invalid UTF-8 byte 0x{data[exc.start]:02x}",
+            data.count(b"\n", 0, exc.start) + 1,
+            exc.start - line_start + 1,
+        )
+
+    try:
+        if fmt == "json":
+            json.loads(text)
+        elif fmt == "jsonc":
+            load_jsonc(text)
+        elif fmt == "toml":
+            tomllib.loads(text)
+        elif fmt == "yaml":
+            yaml.safe_load(text)
+    except json.JSONDecodeError as exc:
+        return _diagnostic(rel_path, fmt, exc.msg, exc.lineno, exc.colno)
+    except tomllib.TOMLDecodeError as exc:
+        position = _TOML_POSITION.search(str(exc))
+        message = _TOML_POSITION.sub("", str(exc)).strip()
+        if position:
+            return _diagnostic(rel_path, fmt, message, int(position.group(1)), int(position.group(2)))
+        return _diagnostic(rel_path, fmt, message)
+    except yaml.YAMLError as exc:
+        mark = getattr(exc, "problem_mark", None)
+        message = getattr(exc, "problem", None) or str(exc)
+        if mark is not None:
+            return _diagnostic(rel_path, fmt, message, mark.line + 1, mark.column + 1)
+        return _diagnostic(rel_path, fmt, message)
+    except (ValueError, RecursionError) as exc:
+        return _diagnostic(rel_path, fmt, f"{type(exc).__name__}: {exc}")
+    return None
+
+
+def check_manifest(path, rel_path, secure_file_ops=None):
+    """
+    Read a manifest and check it with check_manifest_content
+
+    Args:
+        path (str): Absolute manifest path
+        rel_path (str): Repository-relative path used in the diagnostic
+        secure_file_ops (SecureFileOps|None): Secure file operations or None
+
+    Returns:
+        dict|None: Diagnostic, or None when the manifest is well-formed
+    """
+    try:
+        if secure_file_ops:
+            with secure_file_ops.open_file(rel_path, "rb") as handle:
+                data = handle.read()
+        else:
+            with open(path, "rb") as handle:
+                data = handle.read()
+    except Exception as exc:
+        return _diagnostic(rel_path, "io", str(exc))
+    return check_manifest_content(rel_path, data)
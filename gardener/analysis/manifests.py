@@ -10,28 +10,22 @@ import os
 import re
 from pathlib import Path
 
+from gardener.analysis.manifest_check import ManifestError, check_manifest
+from gardener.common.file_helpers import read_repo_file
 from gardener.package_metadata.name_resolvers.go import GoResolver
 from gardener.package_metadata.name_resolvers.json_manifest import JsonManifestResolver
-from gardener.package_metadata.name_resolvers.python import PythonResolver
+from gardener.package_metadata.name_resolvers.python import CondaResolver, PythonResolver
 from gardener.package_metadata.name_resolvers.rust import RustResolver
+from gardener.treewalk.deno import load_jsonc
+from gardener.treewalk.javascript import stronger_npm_dep_kind
+from gardener.treewalk.python import stronger_pypi_dep_kind
+from gardener.treewalk.rust import stronger_dep_kind
 
-
-def _read_file(path, secure_file_ops):
-    """
-    Read a text file using SecureFileOps when available
-
-    Args:
-        path (str): Absolute file path
-        secure_file_ops (SecureFileOps|None): Secure file operations instance or None
-
-    Returns:
-        str: File content as text
-    """
-    if secure_file_ops:
-        rel = secure_file_ops.get_relative_path(path)
-        return secure_file_ops.read_file(rel)
-    with open(path, "r", encoding="utf-8") as handle:
-        return handle.read()
+# Keys whose lists are unioned when the same package is declared by several manifests, e.g. a
+# crate imported under a Cargo rename alias in one workspace member and its own name in another
+MERGED_LIST_KEYS = ("import_names", "aliases", "optional_features", "requirements", "conda_environment", "extras")
+# Keys describing a Cargo crate reachable only behind optional features; dropped once any manifest requires it
+OPTIONAL_KEYS = ("optional", "optional_features", "default_enabled")
 
 
 def _read_json(path, secure_file_ops):
@@ -132,8 +126,11 @@ def _get_package_name_from_manifest(path, basename, secure_file_ops, logger, rep
         if basename == "package.json":
             data = _read_json(path, secure_file_ops)
             return data.get("name"), None
+        if basename in ("deno.json", "deno.jsonc"):
+            data = load_jsonc(read_repo_file(path, secure_file_ops))
+            return data.get("name"), None
 
-        content = _read_file(path, secure_file_ops)
+        content = read_repo_file(path, secure_file_ops)
         if basename == "pyproject.toml":
             match = re.search(
                 r"\[project\]\s*.*?name\s*=\s*['\"]([^'\"]+)['\"]", content, re.DOTALL | re.IGNORECASE
@@ -157,7 +154,9 @@ def _get_package_name_from_manifest(path, basename, secure_file_ops, logger, rep
     return None, None
 
 
-def process_manifests(manifest_files, language_handlers, secure_file_ops, logger):
+def process_manifests(
+    manifest_files, language_handlers, secure_file_ops, logger, panics=None, diagnostics=None, strict=False
+):
     """
     Process manifests using registered language handlers with deduplication semantics
 
@@ -166,17 +165,34 @@ def process_manifests(manifest_files, language_handlers, secure_file_ops, logger
         language_handlers (dict): Language handler instances keyed by language
         secure_file_ops (SecureFileOps|None): Secure file operations or None
         logger (Logger|None): Optional logger for status and errors
+        panics (PanicLog|None): Optional log receiving a diagnostic for each handler that raised
+        diagnostics (list|None): Optional list receiving a diagnostic for each malformed manifest, which is skipped
+        strict (bool): Raise ManifestError on a malformed manifest or a handler failure instead
 
     Returns:
         dict: External package metadata map keyed by distribution name
+
+    Raises:
+        ManifestError: In strict mode, for the first manifest that cannot be processed
     """
     external_packages = {}
 
     for manifest_path in list(manifest_files):
         basename = Path(manifest_path).name
-        for handler_lang, handler in language_handlers.items():
-            if basename not in handler.get_manifest_files():
-                continue
+        handlers = [(lang, h) for lang, h in language_handlers.items() if basename in h.get_manifest_files()]
+        if not handlers:
+            continue
+        rel_path = secure_file_ops.get_relative_path(manifest_path) if secure_file_ops else manifest_path
+        diagnostic = check_manifest(manifest_path, rel_path, secure_file_ops)
+        if diagnostic:
+            if strict:
+                raise ManifestError(diagnostic)
+            if diagnostics is not None:
+                diagnostics.append(diagnostic)
+            if logger:
+                logger.warning(f"Skipping malformed manifest {rel_path}: {diagnostic['error']}")
+            continue
+        for handler_lang, handler in handlers:
             try:
                 temp_packages = {}
                 handler.process_manifest(manifest_path, temp_packages, secure_file_ops)
@@ -192,7 +208,13 @@ def process_manifests(manifest_files, language_handlers, secure_file_ops, logger
                         package_info["found_in_manifests"] = [manifest_path]
                         external_packages[package_name] = package_info
             except Exception as exc:
-                if logger:
+                if strict:
+                    raise ManifestError(
+                        {"file": rel_path, "format": handler_lang, "error": f"{type(exc).__name__}: {exc}"}
+                    ) from exc
+                if panics is not None:
+                    panics.record(handler_lang, "manifest", rel_path, exc, logger)
+                elif logger:
                     logger.exception(
                         f"Error processing manifest {manifest_path} with {handler_lang} handler"
                     )
@@ -238,11 +260,41 @@ def _deduplicate_package(package_name, existing_package, new_package_info, manif
     elif new_version and not existing_version:
         existing_package["version"] = new_version
 
+    # Lockfile entries (no dep_kind) say nothing about optional features; only two manifest
+    # declarations can disagree, and a lockfile entry keeps what the manifest declared
+    both_declared = "dep_kind" in existing_package and "dep_kind" in new_package_info
+    if both_declared and existing_package.get("optional") != new_package_info.get("optional"):
+        for key in OPTIONAL_KEYS:
+            existing_package.pop(key, None)
+    elif both_declared and existing_package.get("optional"):
+        existing_package["default_enabled"] = existing_package.get("default_enabled") or new_package_info.get(
+            "default_enabled"
+        )
+
     for key, value in new_package_info.items():
         if key in ["version", "ecosystem"]:
             continue
+        if key in OPTIONAL_KEYS and both_declared and "optional" not in existing_package:
+            continue
         if key not in existing_package:
             existing_package[key] = value
+        elif key == "dep_kind":
+            # A crate that is a normal dependency of one workspace member stays normal
+            stronger = {
+                "npm": stronger_npm_dep_kind,
+                "pypi": stronger_pypi_dep_kind,
+                "conda": stronger_pypi_dep_kind,
+            }.get(existing_package.get("ecosystem"), stronger_dep_kind)
+            existing_package[key] = stronger(existing_package[key], value)
+        elif key == "direct":
+            existing_package[key] = bool(existing_package[key] or value)
+        elif key == "manifest_dep_kinds" and isinstance(value, dict):
+            existing_package[key] = {**existing_package[key], **value}
+        elif key in MERGED_LIST_KEYS and isinstance(value, list):
+            existing_package[key] = existing_package[key] + [v for v in value if v not in existing_package[key]]
+    # A package one manifest or lockfile requires directly is not only transitive
+    if existing_package.get("direct"):
+        existing_package.pop("transitive", None)
 
     return existing_package
 
@@ -261,6 +313,7 @@ def attach_import_names(external_packages, secure_file_ops, logger):
     """
     resolvers = {
         "pypi": PythonResolver,
+        "conda": CondaResolver,
         "go": GoResolver,
         "cargo": RustResolver,
         "npm": JsonManifestResolver,
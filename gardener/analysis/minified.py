@@ -0,0 +1,51 @@
+"""
+Minified and bundled JavaScript detection
+
+Committed build output (`dist/app.min.js`, vendored jQuery, webpack bundles) inlines the code of
+every package it was built from, so extracting its imports credits the repository with hundreds of
+packages it never uses directly. JS/TS files are skipped before parsing when their name marks them
+minified (`.min.js`, `-min.js`), when they end with a `sourceMappingURL` comment left by a bundler
+or compiler, when a single line is enormous, or when nearly all their identifiers are one or two
+characters long as minifiers leave them. Each skipped file is listed under
+`analyzer_details.skipped_files` with the heuristic that matched
+"""
+
+import re
+from pathlib import PurePosixPath
+
+JS_LANGUAGES = ("javascript", "typescript")
+
+MINIFIED_NAME_RE = re.compile(r"[.-]min\.[cm]?js$", re.I)
+SOURCE_MAP_RE = re.compile(r"^\s*//[#@]\s*sourceMappingURL=\S+\s*$", re.M)
+IDENTIFIER_RE = re.compile(r"[A-Za-z_$][\w$]*")
+# Lines this long are not written by hand
+MAX_LINE_CHARS = 5000
+# Files need this many identifiers before their lengths are judged
+MIN_IDENTIFIERS = 500
+# Share of one- or two-character identifiers above which code counts as minified
+SHORT_IDENTIFIER_RATIO = 0.6
+
+
+def minified_reason(rel_path, code):
+    """
+    Return why a JS/TS file looks minified or bundled
+
+    Args:
+        rel_path (str): Relative path of the file
+        code (str): File content
+
+    Returns:
+        str|None: 'min-name', 'source-map', 'long-line' or 'short-identifiers', or None for source code
+    """
+    if MINIFIED_NAME_RE.search(PurePosixPath(rel_path).name):
+        return "min-name"
+    if SOURCE_MAP_RE.search(code[-1000:]):
+        return "source-map"
+    if any(len(line) > MAX_LINE_CHARS for line in code.splitlines()):
+        return "long-line"
+    identifiers = IDENTIFIER_RE.findall(code)
+    if len(identifiers) >= MIN_IDENTIFIERS:
+        short = sum(1 for identifier in identifiers if len(identifier) <= 2)
+        if short / len(identifiers) > SHORT_IDENTIFIER_RATIO:
+            return "short-identifiers"
+    return None
@@ -0,0 +1,114 @@
+"""
+Language-weighted normalization of the funding split
+
+Import styles differ between languages: a Go file imports a handful of modules while a JavaScript
+file can pull in dozens of small packages, so in a polyglot repository one ecosystem tends to
+dominate the centrality ranking. WEIGHT_NORMALIZATION (`--normalization`) `language-files` or
+`language-loc` rebalances the split so each language's packages together get that language's share
+of the codebase, by source file count or by lines of code. Packages belong to the language of most
+of the files importing them and keep their relative weights within it; packages without importing
+files keep their raw share. `global` (the default) leaves the ranking as computed
+"""
+
+from gardener.analysis.graph import import_distribution_resolver
+from gardener.common.secure_file_ops import FileOperationError, SecurityError
+
+NORMALIZATION_MODES = ("global", "language-files", "language-loc")
+
+
+def language_sizes(source_files, basis, read_file=None):
+    """
+    Measure each language's share of the codebase
+
+    Args:
+        source_files (dict): Relative file path -> file info with 'language'
+        basis (str): 'files' (source file count) or 'loc' (non-blank lines)
+        read_file (callable|None): Reads a relative path to text; required for 'loc'
+
+    Returns:
+        dict: Language -> size
+    """
+    sizes = {}
+    for rel_path, info in source_files.items():
+        language = (info or {}).get("language")
+        if not language:
+            continue
+        if basis == "loc":
+            try:
+                size = sum(1 for line in read_file(rel_path).splitlines() if line.strip())
+            except (FileOperationError, SecurityError, OSError, UnicodeDecodeError):
+                size = 0
+        else:
+            size = 1
+        sizes[language] = sizes.get(language, 0) + size
+    return sizes
+
+
+def package_languages(external_packages, file_imports, source_files):
+    """
+    Assign each imported package the language of most of the files importing it
+
+    Args:
+        external_packages (dict): External packages metadata
+        file_imports (dict): File path -> list of imported package names
+        source_files (dict): Relative file path -> file info with 'language'
+
+    Returns:
+        dict: Distribution name -> language (ties go to the alphabetically first language)
+    """
+    dist_for = import_distribution_resolver(external_packages)
+    counts = {}
+    for rel_path, imports in file_imports.items():
+        language = (source_files.get(rel_path) or {}).get("language")
+        if not language:
+            continue
+        for dist in {dist_for(name) for name in imports} - {None}:
+            by_language = counts.setdefault(dist, {})
+            by_language[language] = by_language.get(language, 0) + 1
+    return {dist: min(by_language, key=lambda lang: (-by_language[lang], lang)) for dist, by_language in counts.items()}
+
+
+def normalize_by_language(top_deps_tuples, languages, sizes):
+    """
+    Rescale package scores so each language's packages share in proportion to the language's size
+
+    Args:
+        top_deps_tuples (list): (package_name, score) tuples ordered by importance
+        languages (dict): Distribution name -> language, from package_languages
+        sizes (dict): Language -> size, from language_sizes
+
+    Returns:
+        tuple: (rescaled tuples ordered by score, {language: {'size', 'raw_share', 'share'}} with shares in percent)
+    """
+    total = sum(score for _, score in top_deps_tuples)
+    group_totals = {}
+    for name, score in top_deps_tuples:
+        if name in languages:
+            group_totals[languages[name]] = group_totals.get(languages[name], 0) + score
+    # Groups whose packages all scored 0 have nothing to rescale; they keep a share of 0 and their
+    # language's size goes to the other groups, so the shares still add up to the attributed total
+    size_total = sum(sizes.get(language, 0) for language, group_total in group_totals.items() if group_total > 0)
+    if total <= 0 or size_total <= 0:
+        return list(top_deps_tuples), {}
+
+    attributed = sum(group_totals.values()) / total
+    details = {}
+    for language in sorted(group_totals):
+        size = sizes.get(language, 0) if group_totals[language] > 0 else 0
+        details[language] = {
+            "size": sizes.get(language, 0),
+            "raw_share": group_totals[language] / total * 100,
+            "share": attributed * size / size_total * 100,
+        }
+
+    rescaled = []
+    for name, score in top_deps_tuples:
+        language = languages.get(name)
+        if language is None:
+            rescaled.append((name, score / total))
+        elif group_totals[language] > 0:
+            rescaled.append((name, details[language]["share"] / 100 * score / group_totals[language]))
+        else:
+            rescaled.append((name, 0.0))
+    rescaled.sort(key=lambda item: (-item[1], item[0]))
+    return rescaled, details
@@ -0,0 +1,254 @@
+"""
+pnpm catalogs and npm/Yarn/pnpm version overrides
+
+pnpm monorepos declare shared versions once in pnpm-workspace.yaml (`catalog:` and named
+`catalogs:`) and reference them from package.json as `"react": "catalog:"` or `"catalog:react17"`;
+these specifiers are replaced by the catalog's range, with the catalog name under `catalog`.
+Overrides in the root package.json (`pnpm.overrides`, Yarn's `resolutions`, npm's `overrides`) or
+pnpm-workspace.yaml `overrides` force the version that actually installs, so a package they cover
+reports the overriding range as its `version` and keeps what package.json asked for as
+`declared_version`, with an `override` record of the manifest, section and selector. A selector
+with a range (`semver@<7.5.2`) applies only when the locked version, or else the lowest version the
+declared range allows, falls in it. Selectors scoped to a parent package (`webpack>lodash`,
+`webpack/**/lodash`) only affect transitive copies and are ignored
+"""
+
+import json
+import re
+from pathlib import PurePosixPath
+
+import yaml
+
+from gardener.analysis.js_workspace import PNPM_WORKSPACE_FILE
+from gardener.common.file_helpers import read_repo_file
+from gardener.treewalk.javascript import NPM_DEP_KIND_ORDER
+
+CATALOG_PROTOCOL = "catalog:"
+# pnpm's value removing a dependency instead of replacing its version
+REMOVE_OVERRIDE = "-"
+_COMPARATOR_RE = re.compile(
+    r"^(<=|>=|<|>|=|\^|~)?v?(\d+|[xX*])(?:\.(\d+|[xX*]))?(?:\.(\d+|[xX*]))?(?:[-+][0-9A-Za-z.-]+)?$"
+)
+_HYPHEN_RE = re.compile(r"^(\S+)\s+-\s+(\S+)$")
+_VERSION_RE = re.compile(r"(\d+)(?:\.(\d+))?(?:\.(\d+))?")
+
+
+def _load(repo_path, name, loader, secure_file_ops):
+    try:
+        data = loader(read_repo_file(str(PurePosixPath(repo_path) / name), secure_file_ops))
+    except Exception:
+        return {}
+    return data if isinstance(data, dict) else {}
+
+
+def catalog_version(specifier, package, pnpm_workspace):
+    """
+    Return the catalog a `catalog:` specifier refers to and the range it gives a package
+
+    Args:
+        specifier (str): Declared version, e.g. 'catalog:' or 'catalog:react17'
+        package (str): Package name
+        pnpm_workspace (dict): Parsed pnpm-workspace.yaml
+
+    Returns:
+        tuple|None: (catalog name, version range), or None when the catalog has no entry for the package
+    """
+    name = specifier[len(CATALOG_PROTOCOL) :].strip() or "default"
+    named = pnpm_workspace.get("catalogs")
+    catalog = named.get(name) if isinstance(named, dict) else None
+    if name == "default" and not isinstance(catalog, dict):
+        catalog = pnpm_workspace.get("catalog")
+    version = catalog.get(package) if isinstance(catalog, dict) else None
+    return (name, str(version)) if version is not None else None
+
+
+def _selector_package(selector):
+    """
+    Return (package, range) for an override selector targeting a package directly ('foo', 'foo@<2', '**/foo'),
+    with range None when it covers every version, or None for a selector scoped to a parent package
+    """
+    selector = selector[3:] if selector.startswith("**/") else selector
+    at = selector.find("@", 1)
+    name, version_range = (selector[:at], selector[at + 1 :].strip()) if at > 0 else (selector, "")
+    if ">" in name or name.count("/") > (1 if name.startswith("@") else 0):
+        return None
+    # 'webpack@4>lodash' names a parent too; its range won't parse
+    if version_range and _parse_range(version_range) is None:
+        return None
+    return (name, version_range or None) if name else None
+
+
+def _bump(parts):
+    """
+    Return the first version above every version a partial (major[, minor[, patch]]) covers
+    """
+    bumped = [int(part) for part in parts]
+    bumped[-1] += 1
+    return tuple(bumped) + (0,) * (3 - len(bumped))
+
+
+def _comparator_bounds(comparator):
+    """
+    Return [(operator, version)] bounds equivalent to a single npm comparator, or None when it doesn't parse
+    """
+    match = _COMPARATOR_RE.match(comparator)
+    if match is None:
+        return None
+    operator = match.group(1) or "="
+    parts = [part for part in match.group(2, 3, 4) if part is not None]
+    exact = []
+    for part in parts:
+        if part in "xX*":
+            break
+        exact.append(int(part))
+    if any(part not in "xX*" for part in parts[len(exact) :]):
+        return None
+    if not exact:
+        return [("<", (0, 0, 0))] if operator in (">", "<") else []
+    low = tuple(exact) + (0,) * (3 - len(exact))
+    if operator == "^":
+        # the left-most non-zero component may not change
+        significant = next((index for index, part in enumerate(exact) if part), len(exact) - 1)
+        return [(">=", low), ("<", _bump(exact[: significant + 1]))]
+    if operator == "~":
+        return [(">=", low), ("<", _bump(exact[:2]))]
+    if len(exact) == 3:
+        return [(operator, low)]
+    # partial versions cover every version they prefix
+    return {
+        "=": [(">=", low), ("<", _bump(exact))],
+        ">": [(">=", _bump(exact))],
+        ">=": [(">=", low)],
+        "<": [("<", low)],
+        "<=": [("<", _bump(exact))],
+    }[operator]
+
+
+def _parse_range(version_range):
+    """
+    Parse an npm range into alternatives of (operator, version) bounds, or None when it doesn't parse
+    """
+    alternatives = []
+    for alternative in version_range.split("||"):
+        alternative = _HYPHEN_RE.sub(r">=\1 <=\2", alternative.strip())
+        bounds = []
+        for comparator in re.sub(r"([<>]=?|=|\^|~)\s+", r"\1", alternative).split():
+            parsed = _comparator_bounds(comparator)
+            if parsed is None:
+                return None
+            bounds.extend(parsed)
+        alternatives.append(bounds)
+    return alternatives
+
+
+def _satisfies(version, version_range):
+    """
+    Return whether a (major, minor, patch) version falls in an npm range; prerelease tags are ignored
+    """
+    checks = {
+        "=": lambda bound: version == bound,
+        ">": lambda bound: version > bound,
+        ">=": lambda bound: version >= bound,
+        "<": lambda bound: version < bound,
+        "<=": lambda bound: version <= bound,
+    }
+    alternatives = _parse_range(version_range) or []
+    return any(all(checks[operator](bound) for operator, bound in bounds) for bounds in alternatives)
+
+
+def _installed_version(entry):
+    """
+    Return the locked version of a package, else the lowest version its declared range allows, or None
+    """
+    for version in (entry.get("locked_version"), entry.get("version")):
+        match = _VERSION_RE.search(str(version or ""))
+        if match:
+            return tuple(int(part or 0) for part in match.groups())
+    return None
+
+
+def _override_entries(root_data, pnpm_workspace):
+    """
+    Yield (manifest, section, selector, value) for every override of the root package.json and pnpm-workspace.yaml
+    """
+    pnpm = root_data.get("pnpm")
+    sections = [
+        ("pnpm-workspace.yaml", "overrides", pnpm_workspace.get("overrides")),
+        ("package.json", "pnpm.overrides", pnpm.get("overrides") if isinstance(pnpm, dict) else None),
+        ("package.json", "resolutions", root_data.get("resolutions")),
+        ("package.json", "overrides", root_data.get("overrides")),
+    ]
+    for manifest, section, table in sections:
+        for selector, value in table.items() if isinstance(table, dict) else []:
+            # npm nests overrides of a package's dependencies; "." is the package itself
+            if isinstance(value, dict):
+                value = value.get(".")
+            if isinstance(value, str):
+                yield manifest, section, str(selector), value
+
+
+def apply_npm_overrides(external_packages, repo_path, secure_file_ops=None, logger=None):
+    """
+    Resolve `catalog:` versions and apply root overrides to npm packages
+
+    Args:
+        external_packages (dict): Package metadata keyed by distribution name, updated in place
+        repo_path (str): Absolute repository path
+        secure_file_ops (SecureFileOps|None): Secure file operations or None
+        logger (Logger|None): Optional logger
+
+    Returns:
+        dict: {'catalog': names resolved through a catalog, 'overridden': names with an overridden version}
+    """
+    pnpm_workspace = _load(repo_path, PNPM_WORKSPACE_FILE, yaml.safe_load, secure_file_ops)
+    root_data = _load(repo_path, "package.json", json.loads, secure_file_ops)
+    npm = {name: entry for name, entry in external_packages.items() if entry.get("ecosystem") == "npm"}
+
+    resolved = []
+    for name, entry in sorted(npm.items()):
+        version = str(entry.get("version") or "")
+        if not version.startswith(CATALOG_PROTOCOL):
+            continue
+        match = catalog_version(version, name, pnpm_workspace)
+        if match is None:
+            if logger:
+                logger.debug(f"No pnpm catalog entry for {name} ({version})")
+            continue
+        entry["catalog"], entry["version"] = match
+        entry["declared_version"] = version
+        resolved.append(name)
+
+    # `$name` values reuse the version the root package.json declares for `name`
+    root_versions = {}
+    for kind in reversed(NPM_DEP_KIND_ORDER):
+        section = root_data.get(kind)
+        root_versions.update(section if isinstance(section, dict) else {})
+
+    overridden = []
+    for manifest, section, selector, value in _override_entries(root_data, pnpm_workspace):
+        target = _selector_package(selector)
+        name, version_range = target or (None, None)
+        entry = npm.get(name)
+        if entry is None or value == REMOVE_OVERRIDE or "override" in entry:
+            continue
+        if version_range is not None:
+            installed = _installed_version(entry)
+            if installed is None or not _satisfies(installed, version_range):
+                continue
+        if value.startswith("$"):
+            value = root_versions.get(value[1:])
+            if not isinstance(value, str):
+                continue
+        if value.startswith(CATALOG_PROTOCOL):
+            match = catalog_version(value, name, pnpm_workspace)
+            if match is None:
+                continue
+            value = match[1]
+        entry.setdefault("declared_version", entry.get("version"))
+        entry["version"] = value
+        entry["override"] = {"manifest": manifest, "section": section, "selector": selector}
+        overridden.append(name)
+
+    if logger and (resolved or overridden):
+        logger.info(f"... {len(resolved)} npm versions resolved through pnpm catalogs, {len(overridden)} overridden")
+    return {"catalog": resolved, "overridden": sorted(overridden)}
@@ -0,0 +1,120 @@
+"""
+npm packages used without being declared (phantom dependencies)
+
+npm, Yarn 1 and pnpm's `shamefully-hoist` install every package of a monorepo, and the
+dependencies of those packages, into a root node_modules, so a file can import a package its own
+package.json never declares: one a sibling workspace or the root declares, or one only installed
+for another dependency. Such imports work until the hoisting changes and break strict installs
+(pnpm, Yarn PnP). They are listed under `analyzer_details.used_undeclared` next to the Cargo ones,
+per package.json owning the importing files, with the manifests that do declare the package or
+`transitive: true` when only a lockfile installs it.
+
+Only packages known to install count (declared by some package.json or locked); imports credited
+through a barrel re-export, the package's own name, workspace members and Node.js builtins are never
+reported
+"""
+
+from pathlib import PurePosixPath
+
+from gardener.analysis.js_workspace import read_package_manifests
+from gardener.analysis.stdlib_usage import stdlib_module
+from gardener.treewalk.javascript import NPM_DEP_KIND_ORDER
+
+JS_LANGUAGES = ("javascript", "typescript")
+
+
+def _owning_package(rel_path, package_dirs):
+    """
+    Return the directory of the nearest package.json above a file, or None
+    """
+    parent = PurePosixPath(rel_path).parent
+    for directory in [parent, *parent.parents]:
+        key = str(directory) if str(directory) not in ("", ".") else "."
+        if key in package_dirs:
+            return key
+    return None
+
+
+def _declared(data):
+    names = {data["name"]} if isinstance(data.get("name"), str) else set()
+    for kind in NPM_DEP_KIND_ORDER + ("bundleDependencies", "bundledDependencies"):
+        section = data.get(kind)
+        names.update(section if isinstance(section, (dict, list)) else [])
+    return names
+
+
+def find_phantom_imports(
+    manifest_files,
+    external_packages,
+    workspace_members,
+    file_imports,
+    source_files,
+    file_import_annotations=None,
+    secure_file_ops=None,
+    logger=None,
+    repo_path=None,
+):
+    """
+    List the npm packages each package.json's files import without declaring them
+
+    Args:
+        manifest_files (list): Absolute manifest paths; only package.json files are read
+        external_packages (dict): Package metadata keyed by distribution name
+        workspace_members (dict): Workspace member entries keyed by package name
+        file_imports (dict): Relative file path -> list of import names
+        source_files (dict): Relative file path -> file info with 'language'
+        file_import_annotations (dict|None): File -> import name -> annotations
+        secure_file_ops (SecureFileOps|None): Secure file operations or None
+        logger (Logger|None): Optional logger
+        repo_path (str|None): Absolute repository path
+
+    Returns:
+        list: {'ecosystem', 'package', 'manifest', 'imports': [{'name', 'files', 'declared_in'[, 'transitive']}]}
+            per package.json with phantom imports
+    """
+    manifests = read_package_manifests(manifest_files, secure_file_ops, logger, repo_path)
+    file_import_annotations = file_import_annotations or {}
+    phantoms = {}
+    for rel_path, names in sorted(file_imports.items()):
+        language = (source_files.get(rel_path) or {}).get("language")
+        package_dir = _owning_package(rel_path, manifests) if language in JS_LANGUAGES else None
+        if package_dir is None:
+            continue
+        allowed = _declared(manifests[package_dir][1]) | set(workspace_members)
+        for name in names:
+            entry = external_packages.get(name) or {}
+            annotations = (file_import_annotations.get(rel_path) or {}).get(name) or {}
+            installed = entry.get("manifest_dep_kinds") or entry.get("transitive")
+            if (
+                name in allowed
+                or entry.get("ecosystem") != "npm"
+                or not installed
+                or "reexported_via" in annotations
+                or stdlib_module(language, name)
+            ):
+                continue
+            phantoms.setdefault(package_dir, {}).setdefault(name, []).append(rel_path)
+
+    reports = []
+    for package_dir, imports in sorted(phantoms.items()):
+        manifest, data = manifests[package_dir]
+        records = []
+        for name in sorted(imports):
+            entry = external_packages[name]
+            declared_in = sorted(entry.get("manifest_dep_kinds") or {})
+            record = {"name": name, "files": imports[name], "declared_in": declared_in}
+            if not record["declared_in"]:
+                record["transitive"] = True
+            records.append(record)
+        reports.append(
+            {
+                "ecosystem": "npm",
+                "package": data["name"] if isinstance(data.get("name"), str) else package_dir,
+                "manifest": manifest,
+                "imports": records,
+            }
+        )
+    if logger and reports:
+        count = sum(len(report["imports"]) for report in reports)
+        logger.info(f"... {count} npm packages are imported without being declared in the importing package.json")
+    return reports
@@ -0,0 +1,83 @@
+"""
+First-party vs third-party packages
+
+FIRST_PARTY_OWNERS lists the orgs and scopes the analyzed project owns as globs, e.g.
+`@drips/*` or `github.com/drips-network/*`. A package whose name or repository URL (scheme,
+`www.` and `.git` stripped) matches one is `ownership: first-party`, every other package
+`third-party`. First-party packages stay in `external_packages` and the graph but are left out
+of `top_dependencies` (the funding split) unless EXCLUDE_FIRST_PARTY is turned off
+"""
+
+import fnmatch
+import re
+
+FIRST_PARTY = "first-party"
+THIRD_PARTY = "third-party"
+
+_RE_URL_PREFIX = re.compile(r"^(?:[a-z+]+://)?(?:[^@/]+@)?(?:www\.)?", re.IGNORECASE)
+_RE_SCP_COLON = re.compile(r"^([^/:]+):(?!\d+/)")
+
+
+def repository_key(repository_url):
+    """
+    Reduce a repository URL to a lowercase 'host/path' key, the form repositories are matched on
+    ('https://github.com/Org/repo.git' -> 'github.com/org/repo')
+
+    Args:
+        repository_url (str): Repository URL, scp-like 'host:path' included
+
+    Returns:
+        str: The key, empty for an empty URL
+    """
+    key = _RE_URL_PREFIX.sub("", repository_url.strip())
+    # scp-like 'github.com:org/repo' (but not a 'host:port/' URL)
+    key = _RE_SCP_COLON.sub(r"\1/", key).rstrip("/")
+    return (key[:-4] if key.endswith(".git") else key).lower()
+
+
+def owner_rule(name, info, patterns):
+    """
+    Return the first ownership pattern matching a package's name or repository URL, or None
+
+    Args:
+        name (str): Distribution name
+        info (dict): Package metadata with optional 'repository_url'
+        patterns (Iterable): Owned org/scope globs
+    """
+    candidates = [name.lower()]
+    url_key = repository_key(info.get("repository_url") or "")
+    if url_key:
+        candidates.append(url_key)
+    for pattern in patterns:
+        if any(fnmatch.fnmatchcase(candidate, pattern.lower().rstrip("/")) for candidate in candidates):
+            return pattern
+    return None
+
+
+def classify_ownership(external_packages, patterns, logger=None):
+    """
+    Mark every package first-party or third-party
+
+    Args:
+        external_packages (dict): Package metadata keyed by distribution name, updated in place
+        patterns (Iterable): Owned org/scope globs; nothing is marked when empty
+        logger (Logger|None): Optional logger
+
+    Returns:
+        set: Names of the first-party packages
+    """
+    patterns = [pattern for pattern in patterns or () if isinstance(pattern, str) and pattern.strip()]
+    if not patterns:
+        return set()
+    first_party = set()
+    for name, info in external_packages.items():
+        rule = owner_rule(name, info, patterns)
+        if rule:
+            info["ownership"] = FIRST_PARTY
+            info["ownership_rule"] = rule
+            first_party.add(name)
+        else:
+            info["ownership"] = THIRD_PARTY
+    if logger and first_party:
+        logger.info(f"... {len(first_party)} first-party packages: {', '.join(sorted(first_party))}")
+    return first_party
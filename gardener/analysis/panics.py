@@ -0,0 +1,51 @@
+"""
+Per-file panic isolation
+
+Each source file and manifest is analyzed behind a boundary: an unexpected exception from a
+language analyzer (a handler bug, or a pathological input such as a deeply nested tree) is
+recorded as a diagnostic for that file and the run moves on to the next one. The report lists
+the diagnostics and the panic count per analyzer under `analyzer_details.panics`
+"""
+
+from collections import Counter
+
+
+class PanicLog:
+    """
+    Diagnostics for files whose analysis raised
+
+    Attributes:
+        diagnostics (list): One entry per failure with keys: analyzer, stage, file, error
+        counts (Counter): Analyzer -> number of failures
+    """
+
+    def __init__(self):
+        self.diagnostics = []
+        self.counts = Counter()
+
+    def record(self, analyzer, stage, path, exc, logger=None):
+        """
+        Record a failure of one analyzer on one file
+
+        Args:
+            analyzer (str): Language handler that failed, e.g. 'python'
+            stage (str): 'manifest', 'parse' or 'imports'
+            path (str): File the analyzer was working on
+            exc (Exception): The exception it raised
+            logger (Logger): Optional logger
+        """
+        self.counts[analyzer] += 1
+        self.diagnostics.append(
+            {"analyzer": analyzer, "stage": stage, "file": path, "error": f"{type(exc).__name__}: {exc}"}
+        )
+        if logger:
+            logger.warning(
+                f"{analyzer} analyzer failed on {path} ({stage}): {type(exc).__name__}: {exc}; skipping file"
+            )
+
+    def summary(self):
+        """
+        Returns:
+            dict: {'counts': analyzer -> failures, 'diagnostics': [...]}
+        """
+        return {"counts": dict(sorted(self.counts.items())), "diagnostics": list(self.diagnostics)}
@@ -0,0 +1,215 @@
+"""
+Bounded queues between the concurrent stages of an analysis run
+
+Import extraction reads source files on a reader thread and parses them on the main thread (parse
+timeouts rely on SIGALRM, which only the main thread receives); with URL_RESOLUTION_ORDER 'streaming'
+the parse stage also feeds a resolver thread one resolution request per imported package. Each hop
+is a BoundedQueue of at most ResourceLimits.PIPELINE_QUEUE_SIZE items, so a fast stage waits for a
+slow one instead of buffering its whole output: at most that many file contents are held between
+read and parse. PipelineTimings records the seconds spent in each stage and each queue's depth
+statistics, reported as the `timings` section with TIMINGS (`--timings`)
+"""
+
+import contextlib
+import queue
+import threading
+import time
+
+# How often a producer blocked on a full queue checks whether its consumer gave up
+_POLL_SECONDS = 0.05
+
+_CLOSED = object()
+
+
+class BoundedQueue:
+    """
+    FIFO between one producer and one consumer stage, with backpressure
+
+    put() blocks while the queue is full; close() marks the end of the stream for the consumer, and
+    abandon() releases a producer whose consumer stopped early
+
+    Attributes:
+        name (str): Queue name used in the timings report
+        capacity (int): Maximum number of items waiting in the queue
+        count (int): Items put so far
+        max_depth (int): Most items that were waiting at once
+        blocked_seconds (float): Time the producer spent waiting for room
+    """
+
+    def __init__(self, name, capacity):
+        self.name = name
+        self.capacity = max(1, int(capacity))
+        self.count = 0
+        self.max_depth = 0
+        self.blocked_seconds = 0.0
+        self._queue = queue.Queue(maxsize=self.capacity)
+        self._abandoned = threading.Event()
+
+    def _put(self, item):
+        try:
+            self._queue.put_nowait(item)
+            return True
+        except queue.Full:
+            pass
+        started = time.monotonic()
+        try:
+            while not self._abandoned.is_set():
+                try:
+                    self._queue.put(item, timeout=_POLL_SECONDS)
+                    return True
+                except queue.Full:
+                    continue
+            return False
+        finally:
+            self.blocked_seconds += time.monotonic() - started
+
+    def put(self, item):
+        """
+        Add an item, waiting while the queue is full
+
+        Args:
+            item: Item for the consumer
+
+        Returns:
+            bool: False when the consumer abandoned the queue and the item was dropped
+        """
+        if not self._put(item):
+            return False
+        self.count += 1
+        self.max_depth = max(self.max_depth, self._queue.qsize())
+        return True
+
+    def close(self):
+        """
+        End the stream; the consumer's iteration stops after the items already queued
+        """
+        self._put(_CLOSED)
+
+    def abandon(self):
+        """
+        Stop consuming; a blocked or later put() returns False instead of waiting
+        """
+        self._abandoned.set()
+
+    def __iter__(self):
+        while True:
+            item = self._queue.get()
+            if item is _CLOSED:
+                return
+            yield item
+
+    def items(self):
+        """
+        Iterate the (key, value) pairs put on the queue, so it can stand in for a dict read with .items()
+
+        Returns:
+            iterator: Pairs in the order they were put, until close()
+        """
+        return iter(self)
+
+    def summary(self):
+        """
+        Returns:
+            dict: {'capacity', 'items', 'max_depth', 'blocked_seconds'}
+        """
+        return {
+            "capacity": self.capacity,
+            "items": self.count,
+            "max_depth": self.max_depth,
+            "blocked_seconds": round(self.blocked_seconds, 3),
+        }
+
+
+class StageThread(threading.Thread):
+    """
+    Daemon thread running one pipeline stage
+
+    Attributes:
+        result: Return value of the stage once it finished
+        error (BaseException|None): Exception the stage raised, re-raised by join_result()
+    """
+
+    def __init__(self, name, target):
+        super().__init__(name=f"gardener-{name}", daemon=True)
+        self._stage = target
+        self.result = None
+        self.error = None
+
+    def run(self):
+        try:
+            self.result = self._stage()
+        except BaseException as exc:
+            self.error = exc
+
+    def join_result(self):
+        """
+        Wait for the stage to finish
+
+        Returns:
+            The stage's return value
+
+        Raises:
+            The exception the stage raised, if any
+        """
+        self.join()
+        if self.error is not None:
+            raise self.error
+        return self.result
+
+
+class PipelineTimings:
+    """
+    Seconds spent in each stage of one run and the statistics of its queues
+
+    Stages on different threads overlap, so their seconds can add up to more than total_seconds
+
+    Attributes:
+        stages (dict): Stage name -> seconds, in the order the stages first ran (a stage entered
+            several times accumulates)
+        queues (dict): Queue name -> BoundedQueue
+    """
+
+    def __init__(self):
+        self.stages = {}
+        self.queues = {}
+        self._started = time.monotonic()
+
+    @contextlib.contextmanager
+    def stage(self, name):
+        """
+        Time the enclosed block as part of a stage
+
+        Args:
+            name (str): Stage name
+        """
+        self.stages.setdefault(name, 0.0)
+        started = time.monotonic()
+        try:
+            yield
+        finally:
+            self.stages[name] += time.monotonic() - started
+
+    def queue(self, name, capacity):
+        """
+        Create a BoundedQueue reported under this run's timings
+
+        Args:
+            name (str): Queue name
+            capacity (int): Maximum number of waiting items
+
+        Returns:
+            BoundedQueue: The new queue (a queue created again under the same name replaces the first)
+        """
+        self.queues[name] = BoundedQueue(name, capacity)
+        return self.queues[name]
+
+    def summary(self):
+        """
+        Returns:
+            dict: {'stages': {name: seconds}, 'queues': {name: queue summary}, 'total_seconds'}
+        """
+        return {
+            "stages": {name: round(seconds, 3) for name, seconds in self.stages.items()},
+            "queues": {name: bounded.summary() for name, bounded in self.queues.items()},
+            "total_seconds": round(time.monotonic() - self._started, 3),
+        }
@@ -0,0 +1,100 @@
+"""
+Python extras against import evidence
+
+Packages a pyproject.toml, setup.cfg or setup.py declares only under extras
+(`[project.optional-dependencies]`, Poetry extras, `extras_require`) keep the extra names as
+`extras` and get `scope: extra`. After import extraction each one gets the `import_contexts` it is
+imported from — `test` (tests/ directories, test_*.py, *_test.py and conftest.py), `docs` (docs/
+directories) and `runtime` (everything else) — and `production`: true when runtime code imports it,
+or when nothing imports it but a feature extra (not a dev, test, lint or docs one) declares it.
+Dev-style extras imported from runtime code get an `extra_mismatch` note
+"""
+
+import re
+from pathlib import PurePosixPath
+
+from gardener.package_metadata.name_resolvers.python import mapped_import_names
+
+EXTRA_SCOPE = "extra"
+# Extras installing development tooling rather than optional features
+DEV_EXTRA_RE = re.compile(r"^(dev|develop|development|test|tests|testing|lint|linting|docs?|typing|format|ci)$", re.I)
+TEST_DIRS = ("test", "tests", "testing")
+DOCS_DIRS = ("doc", "docs")
+
+
+def import_context(rel_path):
+    """
+    Return the context a Python file runs in: 'test', 'docs' or 'runtime'
+
+    Args:
+        rel_path (str): Relative file path
+    """
+    path = PurePosixPath(rel_path)
+    if any(part in TEST_DIRS for part in path.parts[:-1]) or path.name == "conftest.py":
+        return "test"
+    if path.stem.startswith("test_") or path.stem.endswith("_test"):
+        return "test"
+    if any(part in DOCS_DIRS for part in path.parts[:-1]):
+        return "docs"
+    return "runtime"
+
+
+def _import_names(name, info):
+    """
+    Names a distribution is imported under: its resolved or mapped import names and its own name
+    """
+    names = list(info.get("import_names") or mapped_import_names(name))
+    return names + [name.lower().replace("-", "_").replace(".", "_")]
+
+
+def scope_extras(external_packages, file_imports, source_files, logger=None):
+    """
+    Tag extra-only Python packages with the extra scope and whether production code needs them
+
+    Args:
+        external_packages (dict): Package metadata keyed by distribution name, updated in place
+        file_imports (dict): Relative file path -> list of import names
+        source_files (dict): Relative file path -> file info with 'language'
+        logger (Logger|None): Optional logger
+
+    Returns:
+        list: {'package', 'extras', 'import_contexts', 'production'[, 'extra_mismatch']} per
+            extra-only package, sorted by name
+    """
+    by_import = {}
+    for name, info in external_packages.items():
+        if info.get("ecosystem") == "pypi" and info.get("dep_kind") == "optional-dependencies" and info.get("extras"):
+            info["scope"] = EXTRA_SCOPE
+            for import_name in _import_names(name, info):
+                by_import.setdefault(import_name, name)
+
+    contexts = {}
+    for rel_path, names in file_imports.items():
+        if (source_files.get(rel_path) or {}).get("language") != "python":
+            continue
+        for import_name in names:
+            name = by_import.get(str(import_name).split(".", 1)[0])
+            if name:
+                contexts.setdefault(name, set()).add(import_context(rel_path))
+
+    reports = []
+    for name in sorted(set(by_import.values())):
+        info = external_packages[name]
+        used_in = contexts.get(name, set())
+        dev_only = all(DEV_EXTRA_RE.match(extra) for extra in info["extras"])
+        info["import_contexts"] = sorted(used_in)
+        info["production"] = "runtime" in used_in or (not used_in and not dev_only)
+        report = {
+            "package": name,
+            "extras": list(info["extras"]),
+            "import_contexts": info["import_contexts"],
+            "production": info["production"],
+        }
+        if dev_only and "runtime" in used_in:
+            note = f"declared only under {', '.join(info['extras'])} but imported from runtime code"
+            info["extra_mismatch"] = report["extra_mismatch"] = note
+        reports.append(report)
+    if logger and reports:
+        production = sum(1 for report in reports if report["production"])
+        logger.info(f"... Scoped {len(reports)} Python extra-only dependencies ({production} needed in production)")
+    return reports
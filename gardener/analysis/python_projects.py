@@ -0,0 +1,291 @@
+"""
+Python projects of the repository
+
+A pyproject.toml, setup.cfg or setup.py naming a distribution makes the packages it builds part of
+the analyzed repository, not PyPI packages. Their source roots come from the build configuration
+(`[tool.setuptools] package-dir`, `[tool.setuptools.packages.find] where`, Poetry `packages`
+`from`, Hatch wheel `packages`, setup.cfg `package_dir` and `packages.find`, setup.py
+`package_dir`), else `src/` when it holds Python files (src layout), else the manifest's
+directory. Every top-level package or module under a src-layout root belongs to the project; in
+flat layouts only the packages the configuration lists or that are named after the distribution
+do. Directories without `__init__.py` are implicit namespace packages (PEP 420) and are listed as
+`namespace_packages`. Imports resolve against the source roots, so `import mypkg` from tests/
+links to `src/mypkg/__init__.py`, and imports of the project's names that reach no file (a
+namespace package itself) are dropped from the external imports. A declared dependency on the
+project itself, or an editable install of a local directory (`-e .`, `-e ./libs/core#egg=core`),
+is not an external package either. Projects are reported under `analyzer_details.workspace_members`
+with `ecosystem: pypi`, keyed `pypi:<name>` when a member of another ecosystem already has the name
+(a pyo3/maturin project named after its Cargo crate)
+"""
+
+import ast
+import configparser
+import re
+import tomllib
+from pathlib import PurePosixPath
+
+from gardener.analysis.cargo_workspace import WORKSPACE_MEMBER
+from gardener.common.file_helpers import read_repo_file
+from gardener.common.repo_paths import join_dir, normalize_dir
+
+PYTHON_PROJECT_FILES = ("pyproject.toml", "setup.cfg", "setup.py")
+# Top-level modules of a flat layout that are tooling, not part of the distribution
+TOOLING_MODULES = ("setup", "conftest", "noxfile", "fabfile", "tasks", "manage")
+
+
+def _canonical(name):
+    return re.sub(r"[-_.]+", "-", str(name)).lower()
+
+
+def _pyproject_layout(content):
+    """
+    Read the distribution name, source roots and listed packages of a pyproject.toml
+    """
+    data = tomllib.loads(content)
+    tool = data.get("tool") if isinstance(data.get("tool"), dict) else {}
+    project = data.get("project") if isinstance(data.get("project"), dict) else {}
+    poetry = tool.get("poetry") if isinstance(tool.get("poetry"), dict) else {}
+    name = project.get("name") or poetry.get("name")
+    roots, packages = [], []
+
+    setuptools = tool.get("setuptools") if isinstance(tool.get("setuptools"), dict) else {}
+    package_dir = setuptools.get("package-dir")
+    if isinstance(package_dir, dict) and isinstance(package_dir.get(""), str):
+        roots.append(package_dir[""])
+    packages_table = setuptools.get("packages") if isinstance(setuptools.get("packages"), dict) else {}
+    find = packages_table.get("find") if isinstance(packages_table.get("find"), dict) else {}
+    roots.extend(where for where in find.get("where") or [] if isinstance(where, str))
+    if isinstance(setuptools.get("packages"), list):
+        packages.extend(package for package in setuptools["packages"] if isinstance(package, str))
+
+    for entry in poetry.get("packages") or []:
+        if isinstance(entry, dict) and isinstance(entry.get("include"), str):
+            roots.append(entry.get("from") or ".")
+            packages.append(entry["include"])
+
+    hatch = tool.get("hatch") if isinstance(tool.get("hatch"), dict) else {}
+    wheel = ((hatch.get("build") or {}).get("targets") or {}).get("wheel") or {}
+    for path in wheel.get("packages") or [] if isinstance(wheel, dict) else []:
+        if isinstance(path, str):
+            parent = str(PurePosixPath(path).parent)
+            roots.append(parent)
+            packages.append(PurePosixPath(path).name)
+    return name, roots, packages
+
+
+def _setup_cfg_layout(content):
+    """
+    Read the distribution name, source roots and listed packages of a setup.cfg
+    """
+    cfg = configparser.ConfigParser(interpolation=None)
+    cfg.read_string(content)
+    name = cfg.get("metadata", "name", fallback=None)
+    roots, packages = [], []
+    for line in cfg.get("options", "package_dir", fallback="").splitlines():
+        key, separator, value = line.partition("=")
+        if separator and not key.strip() and value.strip():
+            roots.append(value.strip())
+    roots.extend(cfg.get("options.packages.find", "where", fallback="").split())
+    listed = cfg.get("options", "packages", fallback="")
+    if not listed.strip().startswith("find"):
+        packages.extend(listed.split())
+    return name, roots, packages
+
+
+def _setup_py_layout(content):
+    """
+    Read the literal `name`, `package_dir` and `packages` passed to setup() in a setup.py
+    """
+    name, roots, packages = None, [], []
+    for node in ast.walk(ast.parse(content)):
+        func = getattr(node, "func", None)
+        if not isinstance(node, ast.Call) or getattr(func, "attr", getattr(func, "id", None)) != "setup":
+            continue
+        for keyword in node.keywords:
+            try:
+                value = ast.literal_eval(keyword.value)
+            except (ValueError, TypeError, SyntaxError, MemoryError, RecursionError):
+                continue
+            if keyword.arg == "name" and isinstance(value, str):
+                name = value
+            elif keyword.arg == "package_dir" and isinstance(value, dict) and isinstance(value.get(""), str):
+                roots.append(value[""])
+            elif keyword.arg == "packages" and isinstance(value, (list, tuple)):
+                packages.extend(package for package in value if isinstance(package, str))
+    return name, roots, packages
+
+
+LAYOUT_READERS = {"pyproject.toml": _pyproject_layout, "setup.cfg": _setup_cfg_layout, "setup.py": _setup_py_layout}
+
+
+def _top_level_names(root, python_files):
+    """
+    Map the top-level package and module names under a source root to whether they are namespace packages
+    """
+    names = {}
+    prefix = "" if root == "." else f"{root}/"
+    for rel_path in python_files:
+        if not rel_path.startswith(prefix):
+            continue
+        parts = PurePosixPath(rel_path[len(prefix) :]).parts
+        if len(parts) == 1:
+            names.setdefault(PurePosixPath(parts[0]).stem, False)
+        else:
+            names[parts[0]] = f"{prefix}{parts[0]}/__init__.py" not in python_files
+    return names
+
+
+def collect_python_projects(manifest_files, source_files, secure_file_ops, logger, repo_path):
+    """
+    Collect the Python distributions this repository builds and the packages they contain
+
+    Args:
+        manifest_files (list): Absolute manifest paths; pyproject.toml, setup.cfg and setup.py are read
+        source_files (dict): Relative file path -> file info with 'language'
+        secure_file_ops (SecureFileOps|None): Secure file operations or None
+        logger (Logger|None): Optional logger
+        repo_path (str): Absolute repository path
+
+    Returns:
+        dict: Distribution name -> {'annotation': 'workspace-member', 'ecosystem': 'pypi', 'manifest',
+            'path', 'source_roots', 'import_names', 'namespace_packages', 'via'}
+    """
+    python_files = {
+        PurePosixPath(rel_path).as_posix()
+        for rel_path, info in source_files.items()
+        if info.get("language") == "python" and rel_path.endswith((".py", ".pyi"))
+    }
+    members = {}
+    for path in sorted(manifest_files):
+        basename = PurePosixPath(path).name
+        if basename not in PYTHON_PROJECT_FILES:
+            continue
+        try:
+            if secure_file_ops:
+                rel_path = secure_file_ops.get_relative_path(path)
+            else:
+                rel_path = str(PurePosixPath(path).relative_to(repo_path))
+            name, roots, packages = LAYOUT_READERS[basename](read_repo_file(path, secure_file_ops))
+        except Exception as exc:
+            if logger:
+                logger.debug(f"Could not read the Python project layout of {path}: {exc}")
+            continue
+        if not isinstance(name, str) or not name.strip():
+            continue
+        project_dir = normalize_dir(str(PurePosixPath(rel_path).parent))
+        roots = [root for root in (join_dir(project_dir, root) for root in roots) if root is not None]
+        if not roots:
+            src = join_dir(project_dir, "src")
+            prefix = "" if src == "." else f"{src}/"
+            roots = [src] if any(file.startswith(prefix) for file in python_files) else [project_dir]
+
+        member = members.setdefault(
+            name.strip(),
+            {
+                "annotation": WORKSPACE_MEMBER,
+                "ecosystem": "pypi",
+                "manifest": rel_path,
+                "path": project_dir,
+                "source_roots": [],
+                "import_names": [],
+                "namespace_packages": [],
+                "via": [],
+            },
+        )
+        listed = {package.replace("/", ".").split(".")[0] for package in packages}
+        # 'acme-tools' is built from an `acme_tools` package, or from `acme/tools` in an `acme` namespace
+        own_name, own_namespace = _canonical(name).replace("-", "_"), re.split(r"[-_.]", name.strip())[0]
+        for root in roots:
+            flat = root == project_dir
+            for import_name, namespace in sorted(_top_level_names(root, python_files).items()):
+                own = import_name == own_name or (namespace and import_name == own_namespace)
+                if flat and import_name not in listed and (not own or import_name in TOOLING_MODULES):
+                    continue
+                if not flat and import_name in ("tests", "test", "conftest"):
+                    continue
+                if import_name not in member["import_names"]:
+                    member["import_names"].append(import_name)
+                if namespace and import_name not in member["namespace_packages"]:
+                    member["namespace_packages"].append(import_name)
+            if root not in member["source_roots"]:
+                member["source_roots"].append(root)
+        if basename not in member["via"]:
+            member["via"].append(basename)
+
+    if logger and members:
+        logger.info(f"... Found {len(members)} Python projects built by the repository: {', '.join(sorted(members))}")
+    return members
+
+
+def drop_internal_packages(external_packages, members, logger=None):
+    """
+    Remove the repository's own Python distributions and editable local installs from the external packages
+
+    Editable installs of local directories that name no project of the repository become members
+    with `via: ['editable']`
+
+    Args:
+        external_packages (dict): Package metadata keyed by name, updated in place
+        members (dict): Result of collect_python_projects, updated in place
+
+    Returns:
+        list: Names dropped, sorted
+    """
+    own = {_canonical(name): name for name in members}
+    dropped = []
+    for name, entry in list(external_packages.items()):
+        if entry.get("ecosystem") != "pypi":
+            continue
+        origins = entry.get("requirements") or []
+        editable = [origin for origin in origins if origin.get("editable") and not origin.get("url")]
+        if _canonical(name) in own:
+            member = members[own[_canonical(name)]]
+            if editable and "editable" not in member["via"]:
+                member["via"].append("editable")
+        elif editable and len(editable) == len(origins):
+            members[name] = {
+                "annotation": WORKSPACE_MEMBER,
+                "ecosystem": "pypi",
+                "manifest": editable[0]["file"],
+                "path": join_dir(str(PurePosixPath(editable[0]["file"]).parent), editable[0].get("path") or "."),
+                "source_roots": [],
+                "import_names": [_canonical(name).replace("-", "_")],
+                "namespace_packages": [],
+                "via": ["editable"],
+            }
+        else:
+            continue
+        del external_packages[name]
+        dropped.append(name)
+    if logger and dropped:
+        logger.debug(f"Dropped the repository's own Python distributions: {', '.join(sorted(dropped))}")
+    return sorted(dropped)
+
+
+def internalize_imports(file_imports, source_files, members):
+    """
+    Drop imports of the repository's own Python packages that reached no file from the external imports
+
+    Args:
+        file_imports (dict): Relative file path -> list of import names, updated in place
+        source_files (dict): Relative file path -> file info with 'language'
+        members (dict): Workspace members; those with ecosystem 'pypi' are used
+
+    Returns:
+        dict: Relative file path -> import names dropped, for the files that had any
+    """
+    internal = {
+        import_name
+        for member in members.values()
+        if member.get("ecosystem") == "pypi"
+        for import_name in member.get("import_names") or []
+    }
+    dropped = {}
+    for rel_path, names in file_imports.items():
+        if (source_files.get(rel_path) or {}).get("language") != "python":
+            continue
+        kept = [name for name in names if str(name).split(".", 1)[0] not in internal]
+        if len(kept) != len(names):
+            dropped[rel_path] = [name for name in names if name not in kept]
+            file_imports[rel_path] = kept
+    return dropped
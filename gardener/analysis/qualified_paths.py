@@ -0,0 +1,60 @@
+"""
+Fully-qualified crate paths
+
+Rust code can call into a crate without any `use` line (`serde_json::from_str(...)`,
+`tokio::spawn(...)`, `tokio::select!`). The Rust extractor reports the first segment of such paths
+as candidate crates annotated `evidence: qualified-path`. A candidate is kept, with
+`confidence: low`, only when it is the import name of a declared Cargo dependency; anything else
+(local modules, enum-like items) is dropped together with its components
+"""
+
+from gardener.treewalk.rust import QUALIFIED_PATH_EVIDENCE
+
+LOW_CONFIDENCE = "low"
+
+
+def filter_qualified_path_imports(
+    external_packages, file_imports, file_package_components, file_import_annotations, logger=None
+):
+    """
+    Keep qualified-path crate candidates that match a declared Cargo dependency
+
+    Args:
+        external_packages (dict): Package metadata keyed by distribution name
+        file_imports (dict): File -> external import names, updated in place
+        file_package_components (dict): File -> (import name, component path) tuples, updated in place
+        file_import_annotations (dict): File -> import name -> annotations, updated in place
+        logger (Logger|None): Optional logger
+
+    Returns:
+        int: Number of file-level usages kept
+    """
+    declared = set()
+    for name, info in external_packages.items():
+        if info.get("ecosystem") == "cargo":
+            declared.add(name.replace("-", "_"))
+            declared.update(info.get("import_names") or [])
+
+    kept = 0
+    for rel_path, annotations in list(file_import_annotations.items()):
+        for import_name, annotation in list(annotations.items()):
+            if annotation.get("evidence") != QUALIFIED_PATH_EVIDENCE:
+                continue
+            if import_name in declared:
+                annotation["confidence"] = LOW_CONFIDENCE
+                kept += 1
+                continue
+            del annotations[import_name]
+            imports = file_imports.get(rel_path) or []
+            if import_name in imports:
+                imports.remove(import_name)
+            if not imports:
+                file_imports.pop(rel_path, None)
+            components = file_package_components.get(rel_path)
+            if components:
+                components[:] = [component for component in components if component[0] != import_name]
+        if not annotations:
+            del file_import_annotations[rel_path]
+    if logger and kept:
+        logger.debug(f"Kept {kept} crate usages found only through fully-qualified paths")
+    return kept
@@ -0,0 +1,243 @@
+"""
+Drip list reconciliation
+
+`gardener reconcile --drip-list <id> report.json` fetches the receivers of an existing Drips list
+from the Drips GraphQL API (`--endpoint` or DRIPS_API_URL) and diffs them against the splits the
+report computes, listing the receivers to add, remove and reweight. Splits are grouped by
+repository URL (several packages can share one repository) and scaled to the Drips total weight of
+1,000,000 with largest-remainder rounding; packages without a repository URL cannot be receivers
+and are reported as unresolved, with the rest of the split renormalized. Address and drip list
+receivers are not produced by gardener and are listed as unmanaged rather than removed
+"""
+
+import argparse
+import json
+import os
+import sys
+import urllib.request
+
+from gardener.analysis.ownership import repository_key
+
+TOTAL_WEIGHT = 1_000_000
+MAX_RECEIVERS = 200
+REQUEST_TIMEOUT = 30
+
+DRIP_LIST_QUERY = """
+query DripList($id: ID!, $chain: SupportedChain!) {
+  dripList(id: $id, chain: $chain) {
+    name
+    splits {
+      __typename
+      ... on ProjectReceiver {
+        weight
+        project {
+          ... on ClaimedProject { source { url } }
+          ... on UnClaimedProject { source { url } }
+        }
+      }
+      ... on AddressReceiver { weight account { address } }
+      ... on DripListReceiver { weight dripList { account { accountId } } }
+    }
+  }
+}
+"""
+
+
+def _post_json(url, payload):
+    """POST a JSON payload and return the decoded JSON response"""
+    req = urllib.request.Request(
+        url,
+        data=json.dumps(payload).encode("utf-8"),
+        headers={"Content-Type": "application/json", "Accept": "application/json"},
+        method="POST",
+    )
+    with urllib.request.urlopen(req, timeout=REQUEST_TIMEOUT) as response:
+        return json.loads(response.read().decode("utf-8"))
+
+
+def _receiver_key(split):
+    """
+    Return (key, label) for a drip list receiver; repository receivers are keyed by 'host/owner/repo'
+    """
+    project = split.get("project") or {}
+    url = (project.get("source") or {}).get("url")
+    if url:
+        return repository_key(url), url
+    address = (split.get("account") or {}).get("address")
+    if address:
+        return f"address:{address.lower()}", address
+    account_id = ((split.get("dripList") or {}).get("account") or {}).get("accountId")
+    if account_id:
+        return f"drip-list:{account_id}", f"drip list {account_id}"
+    return None, None
+
+
+def fetch_drip_list(list_id, endpoint, chain="MAINNET", post_fn=None):
+    """
+    Fetch the receivers of a drip list
+
+    Args:
+        list_id (str): Drip list ID
+        endpoint (str): Drips GraphQL API URL
+        chain (str): Drips chain name (e.g. 'MAINNET', 'SEPOLIA')
+        post_fn (callable|None): Replacement for the JSON POST, taking (url, payload)
+
+    Returns:
+        dict: {'name', 'receivers': {key: {'receiver', 'weight', 'type'}}}
+
+    Raises:
+        ValueError: If the API reports an error or the list does not exist
+    """
+    response = (post_fn or _post_json)(
+        endpoint, {"query": DRIP_LIST_QUERY, "variables": {"id": str(list_id), "chain": chain}}
+    )
+    if response.get("errors"):
+        raise ValueError(f"Drips API error: {response['errors'][0].get('message', response['errors'][0])}")
+    drip_list = (response.get("data") or {}).get("dripList")
+    if not drip_list:
+        raise ValueError(f"Drip list {list_id} not found on {chain}")
+    receivers = {}
+    for split in drip_list.get("splits") or []:
+        key, label = _receiver_key(split)
+        if key:
+            weight = int(split.get("weight") or 0)
+            receivers[key] = {"receiver": label, "weight": weight, "type": split.get("__typename")}
+    return {"name": drip_list.get("name"), "receivers": receivers}
+
+
+def _allocate(shares, total):
+    """
+    Scale shares to integer weights summing to total, largest remainder first
+    """
+    share_sum = sum(shares.values())
+    if share_sum <= 0:
+        return {}
+    exact = {key: share / share_sum * total for key, share in shares.items()}
+    weights = {key: int(value) for key, value in exact.items()}
+    leftover = total - sum(weights.values())
+    for key in sorted(exact, key=lambda k: (-(exact[k] - weights[k]), k))[:leftover]:
+        weights[key] += 1
+    return {key: weight for key, weight in weights.items() if weight > 0}
+
+
+def target_splits(report, max_receivers=MAX_RECEIVERS):
+    """
+    Compute drip list receivers and weights from a report's top dependencies
+
+    Args:
+        report (dict): Analysis results
+        max_receivers (int): Keep only the largest repositories (Drips lists allow 200 receivers)
+
+    Returns:
+        tuple: ({key: {'receiver', 'weight', 'packages'}}, [unresolved package names], unresolved share)
+    """
+    shares, labels, packages = {}, {}, {}
+    unresolved, unresolved_share = [], 0.0
+    for dep in report.get("top_dependencies") or []:
+        if not isinstance(dep, dict) or not dep.get("package_name"):
+            continue
+        url = dep.get("package_url") or dep.get("repository_url")
+        share = dep.get("percentage") or 0
+        if not url:
+            unresolved.append(dep["package_name"])
+            unresolved_share += share
+            continue
+        key = repository_key(url)
+        shares[key] = shares.get(key, 0) + share
+        labels.setdefault(key, url)
+        packages.setdefault(key, []).append(dep["package_name"])
+    kept = dict(sorted(shares.items(), key=lambda item: (-item[1], item[0]))[:max_receivers])
+    weights = _allocate(kept, TOTAL_WEIGHT)
+    targets = {
+        key: {"receiver": labels[key], "weight": weight, "packages": sorted(packages[key])}
+        for key, weight in weights.items()
+    }
+    return targets, sorted(unresolved), unresolved_share
+
+
+def reconcile(report, current, tolerance=0, max_receivers=MAX_RECEIVERS):
+    """
+    Diff a drip list's receivers against the splits computed from a report
+
+    Args:
+        report (dict): Analysis results
+        current (dict): Result of fetch_drip_list
+        tolerance (int): Weight difference (out of 1,000,000) below which a receiver is left unchanged
+        max_receivers (int): Maximum number of receivers to propose
+
+    Returns:
+        dict: {'add', 'remove', 'reweight', 'unchanged', 'unmanaged', 'unresolved', 'unresolved_share'}
+    """
+    targets, unresolved, unresolved_share = target_splits(report, max_receivers)
+    existing = current.get("receivers") or {}
+    diff = {"add": [], "remove": [], "reweight": [], "unchanged": 0, "unmanaged": []}
+    for key, target in sorted(targets.items(), key=lambda item: -item[1]["weight"]):
+        entry = existing.get(key)
+        if entry is None:
+            diff["add"].append(target)
+        elif abs(entry["weight"] - target["weight"]) > tolerance:
+            diff["reweight"].append({**target, "current_weight": entry["weight"]})
+        else:
+            diff["unchanged"] += 1
+    for key, entry in sorted(existing.items()):
+        if key in targets:
+            continue
+        if entry.get("type") in ("AddressReceiver", "DripListReceiver"):
+            diff["unmanaged"].append(entry)
+        else:
+            diff["remove"].append(entry)
+    diff["unresolved"] = unresolved
+    diff["unresolved_share"] = unresolved_share
+    return diff
+
+
+def main(argv=None):
+    """
+    Entry point for `gardener reconcile --drip-list <id> <report.json>`
+
+    Args:
+        argv (list|None): Arguments after the `reconcile` subcommand
+
+    Returns:
+        int: Process exit status (1 on errors, or with --check when the list needs changes)
+    """
+    from gardener.common.utils import Logger
+
+    parser = argparse.ArgumentParser(prog="gardener reconcile")
+    parser.add_argument("report", help="Analysis results JSON")
+    parser.add_argument("--drip-list", required=True, help="ID of the drip list to reconcile")
+    parser.add_argument("--endpoint", help="Drips GraphQL API URL (default: DRIPS_API_URL)")
+    parser.add_argument("--chain", default="MAINNET", help="Drips chain name (default MAINNET)")
+    parser.add_argument("--tolerance", type=int, default=0, help="Ignore weight changes up to this (of 1,000,000)")
+    parser.add_argument("--max-receivers", type=int, default=MAX_RECEIVERS, help="Receiver limit (default 200)")
+    parser.add_argument("--check", action="store_true", help="Exit with status 1 when the list needs changes")
+    parser.add_argument("-o", "--output", help="Write the diff to this file instead of stdout")
+    args = parser.parse_args(argv)
+
+    logger = Logger(verbose=True)
+    endpoint = args.endpoint or os.environ.get("DRIPS_API_URL")
+    if not endpoint:
+        logger.error("No Drips API endpoint: pass --endpoint or set DRIPS_API_URL")
+        return 1
+    try:
+        with open(args.report, "r", encoding="utf-8") as handle:
+            report = json.load(handle)
+        current = fetch_drip_list(args.drip_list, endpoint, args.chain)
+    except (OSError, ValueError) as exc:
+        logger.error(f"Reconciliation failed: {exc}")
+        return 1
+
+    diff = {"drip_list": args.drip_list, "name": current["name"], "chain": args.chain}
+    diff.update(reconcile(report, current, args.tolerance, args.max_receivers))
+    body = json.dumps(diff, indent=2) + "\n"
+    if args.output:
+        with open(args.output, "w", encoding="utf-8") as handle:
+            handle.write(body)
+        logger.info(
+            f"Drip list {args.drip_list}: {len(diff['add'])} to add, {len(diff['remove'])} to remove, "
+            f"{len(diff['reweight'])} to reweight, {diff['unchanged']} unchanged; diff written to {args.output}"
+        )
+    else:
+        sys.stdout.write(body)
+    changes = len(diff["add"]) + len(diff["remove"]) + len(diff["reweight"])
+    return 1 if args.check and changes else 0
@@ -0,0 +1,150 @@
+"""
+Per-run reproducibility manifest
+
+Builds the `reproduce` block attached to analysis results: the gardener version, the enabled
+analyzers and the grammars they parse with, the effective configuration, the URL cache state
+and a digest of every input file. Two runs with equal blocks are expected to produce identical
+output
+"""
+
+import hashlib
+import os
+from importlib import metadata
+
+from gardener.common.defaults import effective_config
+from gardener.common.secure_file_ops import FileOperationError, SecurityError
+from gardener.common.tsl import USING_TSL_PACK
+
+# Analyzers that do not parse with their own tree-sitter grammar: None means a regex parser,
+# "manifest" a manifest-only analyzer with no source files, and any other string names the
+# grammar borrowed from another language
+PARSER_BACKENDS = {
+    "idl": None,
+    "stylesheet": None,
+    "html": None,
+    "notebook": "python",
+    "helm": "manifest",
+    "ios": "manifest",
+    "homebrew": "manifest",
+}
+
+
+def distribution_version(name):
+    """
+    Return the installed version of a distribution
+
+    Args:
+        name (str): Distribution name
+
+    Returns:
+        str|None: Version string, or None when not installed
+    """
+    try:
+        return metadata.version(name)
+    except metadata.PackageNotFoundError:
+        return None
+
+
+def analyzer_versions(language_handlers, focus_languages=None):
+    """
+    Describe the enabled analyzers and the grammar versions they parse with
+
+    Args:
+        language_handlers (dict): Registered handlers keyed by language
+        focus_languages (list|None): Languages selected for the run, or None for all
+
+    Returns:
+        dict: Language key -> {'handler', 'parser', 'grammar', 'grammar_version'}
+    """
+    grammar_package = "tree-sitter-language-pack" if USING_TSL_PACK else "tree-sitter-languages"
+    grammar_version = distribution_version(grammar_package)
+    runtime_version = distribution_version("tree-sitter")
+
+    analyzers = {}
+    for language in sorted(language_handlers):
+        if focus_languages and language not in focus_languages:
+            continue
+        grammar = PARSER_BACKENDS.get(language, language)
+        entry = {"handler": type(language_handlers[language]).__name__}
+        if grammar is None:
+            entry.update({"parser": "regex", "grammar": None, "grammar_version": None})
+        elif grammar == "manifest":
+            entry.update({"parser": "manifest-only", "grammar": None, "grammar_version": None})
+        else:
+            entry.update(
+                {
+                    "parser": f"tree-sitter {runtime_version}" if runtime_version else "tree-sitter",
+                    "grammar": f"{grammar_package}:{grammar}",
+                    "grammar_version": grammar_version,
+                }
+            )
+        analyzers[language] = entry
+    return analyzers
+
+
+def input_digest(repo_path, paths, secure_file_ops=None):
+    """
+    Hash the contents of the analyzed input files
+
+    Args:
+        repo_path (str): Repository root
+        paths (iterable): Absolute paths of files read during analysis
+        secure_file_ops (SecureFileOps|None): Secure file operations the files are read through, so
+            a symlink leading out of the repository is never hashed; plain reads when None
+
+    Returns:
+        dict: {'algorithm', 'digest', 'file_count'}; unreadable files and files SecureFileOps rejects
+            contribute their path only
+    """
+    digest = hashlib.sha256()
+    entries = sorted({os.path.relpath(path, repo_path) for path in paths})
+    for rel_path in entries:
+        digest.update(rel_path.encode("utf-8") + b"\0")
+        try:
+            if secure_file_ops is not None:
+                with secure_file_ops.open_file(rel_path, "rb") as handle:
+                    content = handle.read()
+            else:
+                with open(os.path.join(repo_path, rel_path), "rb") as handle:
+                    content = handle.read()
+            digest.update(hashlib.sha256(content).digest())
+        except (OSError, FileOperationError, SecurityError):
+            digest.update(b"unreadable")
+    return {"algorithm": "sha256", "digest": digest.hexdigest(), "file_count": len(entries)}
+
+
+def analyzer_inputs(repo_analyzer):
+    """
+    List the files an analysis run reads
+
+    Args:
+        repo_analyzer (RepositoryAnalyzer): Analyzer after scanning
+
+    Returns:
+        list: Absolute paths of source, manifest, script and JS/TS config files
+    """
+    inputs = [info["absolute_path"] for info in repo_analyzer.source_files.values() if "absolute_path" in info]
+    inputs += repo_analyzer.manifest_files + repo_analyzer.script_files
+    inputs += repo_analyzer.js_config_files + repo_analyzer.ts_config_files
+    return inputs
+
+
+def build_reproduce_block(repo_analyzer, cache_state=None):
+    """
+    Assemble the reproducibility manifest for a finished run
+
+    Args:
+        repo_analyzer (RepositoryAnalyzer): Analyzer holding the scanned inputs and handlers
+        cache_state (dict|None): URL cache summary recorded during URL resolution
+
+    Returns:
+        dict: Reproduce block
+    """
+    return {
+        "gardener_version": distribution_version("gardener") or "unknown",
+        "focus_languages": sorted(repo_analyzer.focus_languages) if repo_analyzer.focus_languages else None,
+        "analyzers": analyzer_versions(repo_analyzer.language_handlers, repo_analyzer.focus_languages),
+        "config": effective_config(),
+        "cache": cache_state or {"url_cache": {"provided": False, "entries": 0, "hits": 0}},
+        "input": input_digest(repo_analyzer.repo_path, analyzer_inputs(repo_analyzer), repo_analyzer.secure_file_ops),
+    }
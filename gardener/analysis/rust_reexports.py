@@ -0,0 +1,232 @@
+"""
+Rust `pub use` re-export chains
+
+A facade module re-exports items defined elsewhere (`pub use client::ApiClient;` in api/mod.rs,
+`pub use reqwest::Client;` in net.rs), and `use crate::api::ApiClient` resolves to the facade's
+file. After extraction, every local `use` of an item (not a module) is followed through the
+`pub use` declarations of the files it reaches, explicit names first and then globs whose module
+defines the name, until the module defining the item. A local definition is linked from the
+importing file like a direct import of it; an item re-exported from a crate makes the crate an
+import of the importing file, with the chain under `reexported_via` in its annotations. Each
+followed chain is listed under `analyzer_details.reexport_chains`. The facade keeps its own link:
+it is usually also reached through a `mod` declaration
+"""
+
+import re
+
+from gardener.common.file_helpers import read_repo_file
+
+PUB_USE_RE = re.compile(r"\bpub(?:\s*\([^)]*\))?\s+use\s+([^;]+);")
+ITEM_DEFINITION_RE = r"\b(?:struct|enum|union|trait|fn|type|const|static|mod|macro_rules!)\s+{}\b"
+ALIAS_RE = re.compile(r"(.*?)\s+as\s+(\w+)", re.DOTALL)
+COMMENT_RE = re.compile(r"//[^\n]*|/\*.*?\*/", re.DOTALL)
+LOCAL_ROOTS = ("crate", "self", "super")
+# Longest chain followed; real facades are one or two levels deep
+MAX_CHAIN = 8
+
+
+def _split_top_level(text):
+    """
+    Split a use-list body on the commas that are not inside nested braces
+    """
+    parts, depth, current = [], 0, ""
+    for char in text:
+        if char == "," and depth == 0:
+            parts.append(current)
+            current = ""
+            continue
+        depth += {"{": 1, "}": -1}.get(char, 0)
+        current += char
+    return [part for part in parts + [current] if part.strip()]
+
+
+def _expand_use_tree(prefix, tree):
+    """
+    Yield (exported name, target path parts) for each item of a `pub use` tree; globs export '*'
+    """
+    tree = tree.strip()
+    if tree.endswith("}") and "{" in tree:
+        head, body = tree[:-1].split("{", 1)
+        head_parts = [part for part in re.sub(r"\s+", "", head).split("::") if part]
+        for item in _split_top_level(body):
+            yield from _expand_use_tree(prefix + head_parts, item)
+        return
+    match = ALIAS_RE.fullmatch(tree)
+    path, alias = (match.group(1), match.group(2)) if match else (tree, None)
+    parts = prefix + [part for part in re.sub(r"\s+", "", path).split("::") if part]
+    if not parts:
+        return
+    if parts[-1] == "*":
+        yield "*", parts[:-1]
+    elif parts[-1] == "self":
+        if len(parts) > 1:
+            yield alias or parts[-2], parts[:-1]
+    else:
+        yield alias or parts[-1], parts
+
+
+def parse_reexports(code):
+    """
+    Collect the `pub use` declarations of a Rust source file
+
+    Args:
+        code (str): Rust source
+
+    Returns:
+        dict: Exported name -> list of target path parts; globs are listed under '*'
+    """
+    exports = {}
+    for match in PUB_USE_RE.finditer(COMMENT_RE.sub("", code)):
+        for name, parts in _expand_use_tree([], match.group(1)):
+            exports.setdefault(name, []).append(parts)
+    return exports
+
+
+class _ReexportWalker:
+    """
+    Follows re-export chains with cached `pub use` tables and sources
+    """
+
+    def __init__(self, resolve, read_source, crate_names):
+        self.resolve = resolve
+        self.read_source = read_source
+        self.crate_names = crate_names
+        self.sources = {}
+        self.tables = {}
+
+    def _source(self, rel_path):
+        if rel_path not in self.sources:
+            self.sources[rel_path] = self.read_source(rel_path) or ""
+        return self.sources[rel_path]
+
+    def _reexports(self, rel_path):
+        if rel_path not in self.tables:
+            self.tables[rel_path] = parse_reexports(self._source(rel_path))
+        return self.tables[rel_path]
+
+    def module_of(self, rel_path, parts):
+        """
+        Return the file of the module an item path points into, or None when the path names a module
+        """
+        if len(parts) < 2:
+            return None
+        module = rel_path if parts[:-1] == ["self"] else self.resolve(rel_path, parts[:-1])
+        if module is None or self.resolve(rel_path, parts) not in (None, module):
+            return None
+        return module
+
+    def _target(self, rel_path, name):
+        """
+        Return the path parts a file re-exports a name from, or None
+        """
+        exports = self._reexports(rel_path)
+        if name in exports:
+            return exports[name][0]
+        for glob in exports.get("*", []):
+            module = self.resolve(rel_path, glob) if glob else None
+            if module and (
+                re.search(ITEM_DEFINITION_RE.format(re.escape(name)), self._source(module))
+                or name in self._reexports(module)
+            ):
+                return glob + [name]
+        return None
+
+    def walk(self, module, name):
+        """
+        Follow re-exports of a name from a module file
+
+        Returns:
+            tuple: (chain of files, defining file or None, crate name or None, crate path or None)
+        """
+        chain = [module]
+        while len(chain) <= MAX_CHAIN:
+            target = self._target(chain[-1], name)
+            if target is None:
+                break
+            next_module = self.module_of(chain[-1], target)
+            if next_module is None:
+                resolved = self.resolve(chain[-1], target)
+                if resolved is not None:
+                    # `pub use inner::submodule;` re-exports a whole module
+                    return chain + [resolved], resolved, None, None
+                if target[0] in self.crate_names:
+                    return chain, None, target[0], "::".join(target)
+                break
+            if next_module in chain:
+                break
+            chain.append(next_module)
+            name = target[-1]
+        return chain, chain[-1], None, None
+
+
+def attribute_reexports(
+    source_files,
+    external_packages,
+    file_imports,
+    local_imports_map,
+    file_package_components,
+    file_import_annotations,
+    resolve,
+    secure_file_ops=None,
+    logger=None,
+):
+    """
+    Attribute uses of re-exported Rust items to the module or crate defining them
+
+    Args:
+        source_files (dict): Relative file path -> file info with 'language' and 'absolute_path'
+        external_packages (dict): Package metadata keyed by distribution name
+        file_imports (dict): File -> external import names, updated in place
+        local_imports_map (dict): File -> local files it imports, updated in place
+        file_package_components (dict): File -> (import name, component path) tuples, updated in place
+        file_import_annotations (dict): File -> import name -> annotations, updated in place
+        resolve (callable): Rust local resolver, (importing file, path parts) -> file or None
+        secure_file_ops (SecureFileOps|None): Secure file operations or None
+        logger (Logger|None): Optional logger
+
+    Returns:
+        list: {'file', 'import', 'chain', and 'defined_in' or 'crate'} for every chain of at least one re-export
+    """
+    crate_names = set()
+    for name, info in external_packages.items():
+        if info.get("ecosystem") == "cargo":
+            crate_names.add(name.replace("-", "_"))
+            crate_names.update(info.get("import_names") or [])
+
+    def read_source(rel_path):
+        try:
+            return read_repo_file(source_files[rel_path]["absolute_path"], secure_file_ops)
+        except Exception:
+            return None
+
+    walker = _ReexportWalker(resolve, read_source, crate_names)
+    chains = []
+    for rel_path in sorted(file_package_components):
+        if (source_files.get(rel_path) or {}).get("language") != "rust":
+            continue
+        seen = set()
+        for root, path in list(file_package_components[rel_path]):
+            if root not in LOCAL_ROOTS or path in seen:
+                continue
+            seen.add(path)
+            parts = path.split("::")
+            module = walker.module_of(rel_path, parts)
+            if module is None:
+                continue
+            chain, defined_in, crate, crate_path = walker.walk(module, parts[-1])
+            if crate is not None:
+                imports = file_imports.setdefault(rel_path, [])
+                if crate not in imports:
+                    imports.append(crate)
+                    annotations = file_import_annotations.setdefault(rel_path, {})
+                    annotations.setdefault(crate, {})["reexported_via"] = chain
+                file_package_components[rel_path].append((crate, crate_path))
+                chains.append({"file": rel_path, "import": path, "chain": chain, "crate": crate})
+            elif len(chain) > 1:
+                local = local_imports_map.setdefault(rel_path, [])
+                if defined_in != rel_path and defined_in not in local:
+                    local.append(defined_in)
+                chains.append({"file": rel_path, "import": path, "chain": chain, "defined_in": defined_in})
+    if logger and chains:
+        logger.debug(f"Followed {len(chains)} Rust re-export chains to the defining module or crate")
+    return chains
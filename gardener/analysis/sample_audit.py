@@ -0,0 +1,232 @@
+"""
+Spot-audit worksheets
+
+Before publishing splits, funders verify a handful of packages by hand. `gardener sample-audit
+report.json --n 20` draws packages from `top_dependencies` at random, weighted by their share, so
+the packages that move the most funds are the most likely to be checked, while every package can
+be drawn. For each one the worksheet lists the evidence locations (manifests and importing files),
+the resolution receipt (how the repository URL was obtained) and the URLs to verify, as JSON or as
+CSV with empty `verified` / `notes` columns for the auditor
+"""
+
+import argparse
+import csv
+import json
+import random
+import sys
+
+SAMPLE_FILES = 10
+
+REGISTRY_PAGES = {
+    "npm": "https://www.npmjs.com/package/{name}",
+    "pypi": "https://pypi.org/project/{name}/",
+    "cargo": "https://crates.io/crates/{name}",
+    "go": "https://pkg.go.dev/{name}",
+    "jsr": "https://jsr.io/{name}",
+    "cocoapods": "https://cocoapods.org/pods/{name}",
+    "homebrew": "https://formulae.brew.sh/formula/{name}",
+}
+
+CSV_COLUMNS = (
+    "package",
+    "ecosystem",
+    "share",
+    "repository_url",
+    "registry_page",
+    "resolution",
+    "manifests",
+    "sample_files",
+    "verified",
+    "notes",
+)
+
+
+def weighted_sample(weights, n, rng):
+    """
+    Draw up to n distinct keys, each with probability proportional to its weight
+
+    Uses Efraimidis-Spirakis keys (u ** (1 / weight)); keys with no positive weight are never drawn
+
+    Args:
+        weights (dict): Key -> weight
+        n (int): Sample size
+        rng (random.Random): Random source
+
+    Returns:
+        list: Drawn keys, highest sampling key first
+    """
+    keyed = [(rng.random() ** (1.0 / weight), key) for key, weight in weights.items() if weight > 0]
+    keyed.sort(key=lambda item: item[0], reverse=True)
+    return [key for _, key in keyed[:n]]
+
+
+def _evidence_files(report, import_names):
+    """
+    Return the files importing any of the import names, from per-file or aggregated evidence
+    """
+    details = report.get("analyzer_details") or {}
+    files = set()
+    for rel_path, names in (details.get("file_imports") or {}).items():
+        if import_names.intersection(names):
+            files.add(rel_path)
+    for group in (details.get("evidence") or {}).get("imports") or []:
+        if group.get("import") in import_names:
+            files.update(group.get("sample_files") or [])
+    return sorted(files)
+
+
+def _resolution_receipt(report, name, info):
+    """
+    Describe how a package's repository URL was obtained
+    """
+    ecosystem = info.get("ecosystem")
+    seeded = (((report.get("analyzer_details") or {}).get("seed") or {}).get("resolved_at") or {}).get(
+        f"{ecosystem}:{name}"
+    )
+    receipt = {"repository_url": info.get("repository_url") or None}
+    if info.get("enrichment"):
+        receipt["source"] = info["enrichment"]
+    elif not info.get("repository_url"):
+        receipt["source"] = "unresolved"
+    elif seeded:
+        receipt["source"] = "seed"
+        receipt["resolved_at"] = seeded
+    elif info.get("gitmodules_url"):
+        receipt["source"] = "gitmodules"
+    elif ecosystem == "cargo" and (info.get("git_url") or str(info.get("source") or "").startswith("git+")):
+        receipt["source"] = "cargo-git"
+        pin = dict(info.get("git_pin") or {})
+        if info.get("git_commit"):
+            pin["commit"] = info["git_commit"]
+        if pin:
+            receipt["pin"] = pin
+    else:
+        receipt["source"] = "registry"
+    return receipt
+
+
+def audit_worksheet(report, n=20, seed=None, report_path=None):
+    """
+    Build an audit worksheet for a weighted random sample of a report's top dependencies
+
+    Args:
+        report (dict): Analysis results
+        n (int): Number of packages to draw
+        seed (int|None): Random seed; recorded in the worksheet so the draw can be repeated
+        report_path (str|None): Report location recorded in the worksheet
+
+    Returns:
+        dict: {'report', 'input_digest', 'seed', 'population', 'sampled_share', 'packages': [...]}
+    """
+    if seed is None:
+        seed = random.SystemRandom().randrange(2**32)
+    shares = {dep["package_name"]: dep.get("percentage") or 0 for dep in report.get("top_dependencies") or []}
+    drawn = weighted_sample(shares, n, random.Random(seed))
+
+    packages = []
+    external = report.get("external_packages") or {}
+    for name in drawn:
+        info = external.get(name) or {}
+        ecosystem = info.get("ecosystem", "unknown")
+        page = REGISTRY_PAGES.get(ecosystem)
+        import_names = set(info.get("import_names") or [name])
+        files = _evidence_files(report, import_names)
+        registry_page = page.format(name=name) if page else None
+        receipt = _resolution_receipt(report, name, info)
+        packages.append(
+            {
+                "package": name,
+                "ecosystem": ecosystem,
+                "share": shares[name],
+                "evidence": {
+                    "manifests": info.get("found_in_manifests") or [],
+                    "files": len(files),
+                    "sample_files": files[:SAMPLE_FILES],
+                },
+                "resolution": receipt,
+                "registry_page": registry_page,
+                "verify_urls": [url for url in (receipt["repository_url"], registry_page) if url],
+            }
+        )
+
+    return {
+        "report": report_path,
+        "input_digest": ((report.get("reproduce") or {}).get("input") or {}).get("digest"),
+        "seed": seed,
+        "population": len(shares),
+        "sampled_share": sum(shares[name] for name in drawn),
+        "packages": packages,
+    }
+
+
+def write_csv(worksheet, handle):
+    """
+    Write the worksheet's packages as CSV rows with blank verified/notes columns
+    """
+    writer = csv.DictWriter(handle, fieldnames=CSV_COLUMNS)
+    writer.writeheader()
+    for entry in worksheet["packages"]:
+        writer.writerow(
+            {
+                "package": entry["package"],
+                "ecosystem": entry["ecosystem"],
+                "share": f"{entry['share']:.4f}",
+                "repository_url": entry["resolution"]["repository_url"] or "",
+                "registry_page": entry["registry_page"] or "",
+                "resolution": entry["resolution"]["source"],
+                "manifests": " ".join(entry["evidence"]["manifests"]),
+                "sample_files": " ".join(entry["evidence"]["sample_files"]),
+                "verified": "",
+                "notes": "",
+            }
+        )
+
+
+def main(argv=None):
+    """
+    Entry point for `gardener sample-audit <report.json> --n 20`
+
+    Args:
+        argv (list|None): Arguments after the `sample-audit` subcommand
+
+    Returns:
+        int: Process exit status
+    """
+    from gardener.common.utils import Logger
+
+    parser = argparse.ArgumentParser(prog="gardener sample-audit")
+    parser.add_argument("report", help="Analysis results JSON")
+    parser.add_argument("-n", "--n", type=int, default=20, help="Number of packages to draw (default 20)")
+    parser.add_argument("--seed", type=int, help="Random seed, to repeat a draw")
+    parser.add_argument("--format", choices=("json", "csv"), default="json", help="Worksheet format")
+    parser.add_argument("-o", "--output", help="Write the worksheet to this file instead of stdout")
+    args = parser.parse_args(argv)
+
+    logger = Logger(verbose=True)
+    try:
+        with open(args.report, "r", encoding="utf-8") as handle:
+            report = json.load(handle)
+    except (OSError, ValueError) as exc:
+        logger.error(f"Could not read report {args.report}: {exc}")
+        return 1
+    if args.n < 1:
+        logger.error("--n must be at least 1")
+        return 1
+
+    worksheet = audit_worksheet(report, args.n, args.seed, report_path=args.report)
+    handle = open(args.output, "w", encoding="utf-8", newline="") if args.output else sys.stdout
+    try:
+        if args.format == "csv":
+            write_csv(worksheet, handle)
+        else:
+            json.dump(worksheet, handle, indent=2)
+            handle.write("\n")
+    finally:
+        if args.output:
+            handle.close()
+    if args.output:
+        logger.info(
+            f"Wrote audit worksheet for {len(worksheet['packages'])} of {worksheet['population']} packages "
+            f"({worksheet['sampled_share']:.1f}% of the split, seed {worksheet['seed']}) to {args.output}"
+        )
+    return 0
@@ -8,6 +8,7 @@ from pathlib import Path
 
 import pathspec
 
+from gardener.analysis.tooling import HIDDEN_SCRIPT_FILE_NAMES, is_script_file
 from gardener.common.defaults import ResourceLimits
 from gardener.common.language_detection import filename_to_lang
 
@@ -133,13 +134,15 @@ def _scan_secure(repo_path, secure_file_ops, gitignore_spec, all_manifest_files,
         logger (Logger|None): Optional logger for progress and warnings
 
     Returns:
-        Tuple of (source_files, manifest_files, root_manifest_files, js_config_files, ts_config_files)
+        Tuple of (source_files, manifest_files, root_manifest_files, js_config_files, ts_config_files,
+            script_files)
     """
     source_files = {}
     manifest_files = []
     root_manifest_files = []
     js_config_files = []
     ts_config_files = []
+    script_files = []
 
     visited_dirs = set()
 
@@ -171,7 +174,7 @@ def _scan_secure(repo_path, secure_file_ops, gitignore_spec, all_manifest_files,
             entries = list(entries)
 
         for entry in entries:
-            if entry.name.startswith("."):
+            if entry.name.startswith(".") and entry.name not in HIDDEN_SCRIPT_FILE_NAMES:
                 continue
 
             full_path = str(entry)
@@ -206,6 +209,9 @@ def _scan_secure(repo_path, secure_file_ops, gitignore_spec, all_manifest_files,
             elif basename == "tsconfig.json":
                 ts_config_files.append(full_path)
 
+            if is_script_file(basename):
+                script_files.append(full_path)
+
             if ext in all_extensions:
                 language = filename_to_lang(full_path)
                 if language is None:
@@ -223,6 +229,7 @@ def _scan_secure(repo_path, secure_file_ops, gitignore_spec, all_manifest_files,
         root_manifest_files,
         js_config_files,
         ts_config_files,
+        script_files,
     )
 
 
@@ -239,19 +246,21 @@ def _scan_standard(repo_path, gitignore_spec, all_manifest_files, all_extensions
         logger (Logger|None): Optional logger for progress and warnings
 
     Returns:
-        Tuple of (source_files, manifest_files, root_manifest_files, js_config_files, ts_config_files)
+        Tuple of (source_files, manifest_files, root_manifest_files, js_config_files, ts_config_files,
+            script_files)
     """
     source_files = {}
     manifest_files = []
     root_manifest_files = []
     js_config_files = []
     ts_config_files = []
+    script_files = []
 
     for root, dirs, files in os.walk(repo_path, topdown=True):
         filtered_dirs = [
             d
             for d in dirs
-            if not d.startswith(".")
+            if (not d.startswith(".") or d in HIDDEN_SCRIPT_FILE_NAMES)
             and not _is_ignored(str(Path(root) / d), repo_path, gitignore_spec, None)
         ]
         if not ResourceLimits.FOLLOW_SYMLINKS:
@@ -284,6 +293,9 @@ def _scan_standard(repo_path, gitignore_spec, all_manifest_files, all_extensions
             elif basename == "tsconfig.json":
                 ts_config_files.append(file_path)
 
+            if is_script_file(basename):
+                script_files.append(file_path)
+
             if ext in all_extensions:
                 language = filename_to_lang(file_path)
                 if language is None:
@@ -297,12 +309,13 @@ def _scan_standard(repo_path, gitignore_spec, all_manifest_files, all_extensions
         root_manifest_files,
         js_config_files,
         ts_config_files,
+        script_files,
     )
 
 
 def parse_gitmodules(repo_path, secure_file_ops, logger):
     """
-    Parse .gitmodules and return {normalized_path: url}
+    Parse .gitmodules and return {repo_relative_path: url}
 
     Args:
         repo_path (str): Absolute repository path
@@ -310,7 +323,7 @@ def parse_gitmodules(repo_path, secure_file_ops, logger):
         logger (Logger|None): Optional logger for warnings and errors
 
     Returns:
-        dict: Map of normalized submodule paths to repository URLs
+        dict: Map of repo-relative POSIX submodule paths to repository URLs
     """
     import configparser
 
@@ -338,7 +351,7 @@ def parse_gitmodules(repo_path, secure_file_ops, logger):
             if config.has_option(section, "path") and config.has_option(section, "url"):
                 sub_path_raw = config.get(section, "path")
                 url = config.get(section, "url")
-                normalized_path = str(Path(sub_path_raw).resolve()).rstrip(os.sep)
+                normalized_path = os.path.normpath(sub_path_raw.strip()).replace(os.sep, "/")
                 parsed[normalized_path] = url
         return parsed
     except configparser.Error as exc:
@@ -366,7 +379,7 @@ def scan_repository(repo_path, secure_file_ops, focus_languages, language_handle
 
     Returns:
         dict: Keys: source_files, manifest_files, root_manifest_files, js_config_files,
-            ts_config_files, solidity_src_path, submodule_data, gitignore_spec
+            ts_config_files, script_files, solidity_src_path, submodule_data, gitignore_spec
     """
     gitignore_spec = load_gitignore(secure_file_ops, logger)
 
@@ -388,6 +401,7 @@ def scan_repository(repo_path, secure_file_ops, focus_languages, language_handle
             root_manifest_files,
             js_config_files,
             ts_config_files,
+            script_files,
         ) = _scan_secure(
             repo_path,
             secure_file_ops,
@@ -404,6 +418,7 @@ def scan_repository(repo_path, secure_file_ops, focus_languages, language_handle
             root_manifest_files,
             js_config_files,
             ts_config_files,
+            script_files,
         ) = _scan_standard(
             repo_path,
             gitignore_spec,
@@ -422,6 +437,7 @@ def scan_repository(repo_path, secure_file_ops, focus_languages, language_handle
         "root_manifest_files": root_manifest_files,
         "js_config_files": js_config_files,
         "ts_config_files": ts_config_files,
+        "script_files": script_files,
         "solidity_src_path": solidity_src_path,
         "submodule_data": submodule_data,
         "gitignore_spec": gitignore_spec,
@@ -0,0 +1,224 @@
+"""
+Report signing and verification
+
+Analysis reports can be signed with an Ed25519, RSA or ECDSA private key (`gardener <repo>
+--sign-key key.pem`). `gardener verify report.json --key pub.pem` checks the signature and,
+given the analyzed checkout, that the input digest in the `reproduce` block still matches, so
+funding pipelines can refuse unverified reports before creating on-chain splits
+"""
+
+import argparse
+import base64
+import hashlib
+import json
+import os
+
+SIGNATURE_KEY = "signature"
+
+
+def _crypto():
+    """
+    Import the optional cryptography backend
+
+    Returns:
+        tuple: (InvalidSignature, hashes, serialization, ec, ed25519, padding, rsa)
+    """
+    try:
+        from cryptography.exceptions import InvalidSignature
+        from cryptography.hazmat.primitives import hashes, serialization
+        from cryptography.hazmat.primitives.asymmetric import ec, ed25519, padding, rsa
+    except ImportError as exc:
+        raise RuntimeError("Report signing requires the 'cryptography' package (install extras 'signing')") from exc
+    return InvalidSignature, hashes, serialization, ec, ed25519, padding, rsa
+
+
+def canonical_payload(report):
+    """
+    Serialize a report deterministically for signing, excluding any existing signature
+
+    Args:
+        report (dict): Analysis results
+
+    Returns:
+        bytes: Canonical JSON encoding
+    """
+    unsigned = {key: value for key, value in report.items() if key != SIGNATURE_KEY}
+    # Round-trip first so values the report writer stringifies (default=str) are signed as written
+    unsigned = json.loads(json.dumps(unsigned, default=str))
+    return json.dumps(unsigned, sort_keys=True, separators=(",", ":"), ensure_ascii=False).encode("utf-8")
+
+
+def _public_key_fingerprint(public_key, serialization):
+    """
+    Return the SHA-256 fingerprint of a public key's SubjectPublicKeyInfo encoding
+    """
+    der = public_key.public_bytes(serialization.Encoding.DER, serialization.PublicFormat.SubjectPublicKeyInfo)
+    return "sha256:" + hashlib.sha256(der).hexdigest()
+
+
+def sign_report(report, private_key_path, password=None):
+    """
+    Attach a detached signature over the canonical report payload
+
+    Args:
+        report (dict): Analysis results, updated in place
+        private_key_path (str): PEM-encoded Ed25519, RSA or EC private key
+        password (bytes|None): Key passphrase
+
+    Returns:
+        dict: The signature block added under `signature`
+    """
+    _, hashes, serialization, ec, ed25519, padding, rsa = _crypto()
+    with open(private_key_path, "rb") as handle:
+        private_key = serialization.load_pem_private_key(handle.read(), password=password)
+
+    payload = canonical_payload(report)
+    if isinstance(private_key, ed25519.Ed25519PrivateKey):
+        algorithm, value = "ed25519", private_key.sign(payload)
+    elif isinstance(private_key, rsa.RSAPrivateKey):
+        pss = padding.PSS(mgf=padding.MGF1(hashes.SHA256()), salt_length=padding.PSS.MAX_LENGTH)
+        algorithm, value = "rsa-pss-sha256", private_key.sign(payload, pss, hashes.SHA256())
+    elif isinstance(private_key, ec.EllipticCurvePrivateKey):
+        algorithm, value = "ecdsa-sha256", private_key.sign(payload, ec.ECDSA(hashes.SHA256()))
+    else:
+        raise ValueError(f"Unsupported key type: {type(private_key).__name__}")
+
+    report[SIGNATURE_KEY] = {
+        "algorithm": algorithm,
+        "payload_sha256": hashlib.sha256(payload).hexdigest(),
+        "public_key_fingerprint": _public_key_fingerprint(private_key.public_key(), serialization),
+        "value": base64.b64encode(value).decode("ascii"),
+    }
+    return report[SIGNATURE_KEY]
+
+
+def signature_failures(report, public_key_pem):
+    """
+    Check a report's signature against a public key
+
+    Args:
+        report (dict): Signed analysis results
+        public_key_pem (bytes): PEM-encoded public key
+
+    Returns:
+        list: Human-readable failure messages (empty when the signature is valid)
+    """
+    InvalidSignature, hashes, serialization, ec, ed25519, padding, rsa = _crypto()
+    signature = report.get(SIGNATURE_KEY)
+    if not isinstance(signature, dict) or "value" not in signature:
+        return ["Report is not signed"]
+
+    public_key = serialization.load_pem_public_key(public_key_pem)
+    payload = canonical_payload(report)
+    failures = []
+    if signature.get("payload_sha256") != hashlib.sha256(payload).hexdigest():
+        failures.append("Report content does not match the signed payload digest")
+    if signature.get("public_key_fingerprint") != _public_key_fingerprint(public_key, serialization):
+        failures.append("Report was signed with a different key")
+
+    algorithm = signature.get("algorithm")
+    try:
+        value = base64.b64decode(signature["value"])
+        if algorithm == "ed25519" and isinstance(public_key, ed25519.Ed25519PublicKey):
+            public_key.verify(value, payload)
+        elif algorithm == "rsa-pss-sha256" and isinstance(public_key, rsa.RSAPublicKey):
+            pss = padding.PSS(mgf=padding.MGF1(hashes.SHA256()), salt_length=padding.PSS.AUTO)
+            public_key.verify(value, payload, pss, hashes.SHA256())
+        elif algorithm == "ecdsa-sha256" and isinstance(public_key, ec.EllipticCurvePublicKey):
+            public_key.verify(value, payload, ec.ECDSA(hashes.SHA256()))
+        else:
+            failures.append(f"Signature algorithm '{algorithm}' does not match the {type(public_key).__name__}")
+    except (InvalidSignature, ValueError):
+        failures.append("Signature is invalid")
+    return failures
+
+
+def recorded_config_overrides(report):
+    """
+    Flatten the effective configuration a report recorded into override parameters
+
+    Args:
+        report (dict): Analysis results
+
+    Returns:
+        dict: Parameter name -> value, empty when the report recorded no configuration
+    """
+    config = (report.get("reproduce") or {}).get("config") or {}
+    return {key: value for values in config.values() if isinstance(values, dict) for key, value in values.items()}
+
+
+def input_digest_failures(report, repo_path, logger=None):
+    """
+    Re-scan a checkout and compare its input digest with the report's `reproduce` block
+
+    The scan runs under the configuration the report recorded, so overrides that changed which
+    files were analyzed apply to the re-scan as well
+
+    Args:
+        report (dict): Analysis results
+        repo_path (str): Path of the checkout the report claims to describe
+        logger (Logger): Optional logger for the re-scan; defaults to one reporting only warnings and errors
+
+    Returns:
+        list: Human-readable failure messages
+    """
+    from gardener.analysis.main import DependencyAnalyzer
+    from gardener.analysis.reproduce import analyzer_inputs, input_digest
+    from gardener.common.defaults import ConfigOverride
+    from gardener.common.utils import Logger
+
+    expected = (report.get("reproduce") or {}).get("input") or {}
+    if not expected.get("digest"):
+        return ["Report has no input digest"]
+
+    analyzer = DependencyAnalyzer(logger=logger or Logger(quiet=True))
+    with ConfigOverride(recorded_config_overrides(report), analyzer.logger):
+        analyzer.discover_packages(os.path.abspath(repo_path), report["reproduce"].get("focus_languages"))
+        repo_analyzer = analyzer.repo_analyzer
+        actual = input_digest(repo_analyzer.repo_path, analyzer_inputs(repo_analyzer), repo_analyzer.secure_file_ops)
+    if actual["digest"] != expected["digest"]:
+        return [
+            f"Input digest mismatch: report {expected['digest']} ({expected.get('file_count')} files), "
+            f"checkout {actual['digest']} ({actual['file_count']} files)"
+        ]
+    return []
+
+
+def main(argv=None):
+    """
+    Entry point for `gardener verify <report.json> --key <pub.pem>`
+
+    Args:
+        argv (list|None): Arguments after the `verify` subcommand
+
+    Returns:
+        int: Process exit status (1 when verification fails)
+    """
+    from gardener.common.utils import Logger
+
+    parser = argparse.ArgumentParser(prog="gardener verify")
+    parser.add_argument("report", help="Signed analysis results JSON")
+    parser.add_argument("-k", "--key", required=True, help="PEM public key of the expected signer")
+    parser.add_argument("--repo", help="Checkout to re-scan and compare against the report's input digest")
+    args = parser.parse_args(argv)
+
+    logger = Logger(verbose=True)
+    try:
+        with open(args.report, "r", encoding="utf-8") as handle:
+            report = json.load(handle)
+        with open(args.key, "rb") as handle:
+            public_key_pem = handle.read()
+        failures = signature_failures(report, public_key_pem)
+        if args.repo:
+            failures += input_digest_failures(report, args.repo)
+    except (OSError, ValueError, RuntimeError) as exc:
+        logger.error(f"Verification failed: {exc}")
+        return 1
+
+    for failure in failures:
+        logger.error(failure)
+    if failures:
+        return 1
+    digest = (report.get("reproduce") or {}).get("input", {}).get("digest")
+    logger.info(f"Report verified (input digest {digest})" if digest else "Report verified")
+    return 0
@@ -0,0 +1,97 @@
+"""
+Historical weight smoothing
+
+`--smooth 0.5 --previous last-report.json` blends this run's `top_dependencies` shares with the
+previous report's as an exponential moving average: smoothed = (1 - factor) * raw + factor *
+previous, so a single refactor moves the funding split only part of the way. The previous report's
+shares are its smoothed ones, which carries the whole history forward. Packages missing from this
+run's ranking but still declared keep decaying from their previous share instead of dropping to
+zero at once. Every entry records `raw_percentage` next to the smoothed `percentage`, and the
+shares are renormalized to 100
+"""
+
+import hashlib
+import json
+
+
+def load_previous_report(path):
+    """
+    Read a previous analysis report
+
+    Args:
+        path (str): Report path
+
+    Returns:
+        tuple: (report dict, 'sha256:<hex>' digest of the file)
+
+    Raises:
+        ValueError: If the file cannot be read or has no top_dependencies
+    """
+    try:
+        with open(path, "rb") as handle:
+            body = handle.read()
+        report = json.loads(body.decode("utf-8"))
+    except (OSError, UnicodeDecodeError, ValueError) as exc:
+        raise ValueError(f"Could not read previous report {path}: {exc}") from exc
+    if not isinstance(report, dict) or not isinstance(report.get("top_dependencies"), list):
+        raise ValueError(f"Previous report {path} has no top_dependencies")
+    return report, f"sha256:{hashlib.sha256(body).hexdigest()}"
+
+
+def validate_smoothing(factor, previous):
+    """
+    Check the --smooth / --previous options
+
+    Raises:
+        ValueError: If only one of them is given or the factor is outside [0, 1)
+    """
+    if factor is None and previous is None:
+        return
+    if factor is None or previous is None:
+        raise ValueError("--smooth and --previous must be used together")
+    if not 0 <= factor < 1:
+        raise ValueError(f"--smooth must be at least 0 and below 1, got {factor}")
+
+
+def smooth_top_dependencies(top_dependencies, previous_report, factor, external_packages=None):
+    """
+    Blend top dependency shares with a previous report's as an exponential moving average
+
+    Args:
+        top_dependencies (list): This run's entries with 'package_name' and 'percentage'
+        previous_report (dict): Previous analysis results
+        factor (float): Weight of the previous share, in [0, 1); 0 keeps the raw shares
+        external_packages (dict|None): This run's packages; previous entries are carried over only
+            for packages still declared here
+
+    Returns:
+        tuple: (smoothed entries sorted by share, {'factor', 'carried_over', 'dropped'})
+    """
+    previous = {
+        dep["package_name"]: dep
+        for dep in previous_report.get("top_dependencies") or []
+        if isinstance(dep, dict) and dep.get("package_name")
+    }
+    current = {dep["package_name"]: dep for dep in top_dependencies}
+    entries = []
+    for name, dep in current.items():
+        prior = (previous.get(name) or {}).get("percentage") or 0
+        raw = dep.get("percentage") or 0
+        entries.append({**dep, "percentage": (1 - factor) * raw + factor * prior, "raw_percentage": raw})
+
+    carried = dropped = 0
+    for name, dep in previous.items():
+        if name in current:
+            continue
+        if external_packages is not None and name not in external_packages:
+            dropped += 1
+            continue
+        entries.append({**dep, "percentage": factor * (dep.get("percentage") or 0), "raw_percentage": 0})
+        carried += 1
+
+    total = sum(entry["percentage"] for entry in entries)
+    for entry in entries:
+        entry["percentage"] = entry["percentage"] / total * 100 if total > 0 else 0
+    entries = [entry for entry in entries if entry["percentage"] > 0 or entry["raw_percentage"] > 0]
+    entries.sort(key=lambda entry: (-entry["percentage"], entry["package_name"]))
+    return entries, {"factor": factor, "carried_over": carried, "dropped": dropped}
@@ -1,13 +1,16 @@
 """
 Solidity-specific metadata helpers for RepositoryAnalyzer
 
-Parses Solidity remappings, optionally extracts Hardhat remappings via a Node helper,
-and associates Solidity packages with git submodules for better URL attribution
+Parses Solidity remappings (remappings.txt, foundry.toml and the ones forge infers from `lib/`),
+optionally extracts Hardhat remappings via a Node helper, and associates Solidity packages with
+git submodules for better URL attribution
 """
 
 import json
 import os
+import re
 import shutil
+import tomllib
 from pathlib import Path
 
 from gardener.common.input_validation import InputValidator, ValidationError
@@ -34,16 +37,50 @@ def canonicalize_solidity_package_name(name):
     return name
 
 
+def _normalize_remapping_target(path):
+    """
+    Normalize a remapping target to a repo-relative POSIX path
+
+    Args:
+        path (str): Target as written in the remapping
+
+    Returns:
+        str: Normalized path without trailing slash
+    """
+    return os.path.normpath(path.strip()).replace(os.sep, "/")
+
+
+def parse_remapping(line):
+    """
+    Split a solc remapping line into (prefix, target)
+
+    Forge's `context:prefix=target` form is accepted; the context is dropped
+
+    Args:
+        line (str): Remapping such as '@openzeppelin/=lib/openzeppelin-contracts/'
+
+    Returns:
+        tuple|None: (prefix, normalized target) or None when malformed
+    """
+    parts = line.split("=", 1)
+    if len(parts) != 2 or not parts[0].strip() or not parts[1].strip():
+        return None
+    prefix = parts[0].strip()
+    if ":" in prefix:
+        prefix = prefix.split(":", 1)[1]
+    return prefix, _normalize_remapping_target(parts[1])
+
+
 def parse_remappings_txt(secure_file_ops, logger):
     """
-    Parse remappings.txt into {prefix: absolute_path}
+    Parse remappings.txt into {prefix: repo_relative_path}
 
     Args:
         secure_file_ops (SecureFileOps|None): Secure file operations or None
         logger (Logger|None): Optional logger for notes and warnings
 
     Returns:
-        dict: Map of remapping prefixes to absolute paths
+        dict: Map of remapping prefixes to repo-relative paths
     """
     remappings = {}
     if not secure_file_ops:
@@ -62,14 +99,12 @@ def parse_remappings_txt(secure_file_ops, logger):
             line = line.strip()
             if not line or line.startswith("#"):
                 continue
-            parts = line.split("=", 1)
-            if len(parts) != 2:
+            parsed = parse_remapping(line)
+            if not parsed:
                 if logger:
                     logger.warning(f"Skipping malformed line {index + 1} in remappings.txt: '{line}'")
                 continue
-            prefix = parts[0].strip()
-            path = parts[1].strip()
-            normalized = str(Path(path).resolve())
+            prefix, normalized = parsed
             remappings[prefix] = normalized
             if logger:
                 logger.debug(f"  Parsed remapping: '{prefix}' -> '{normalized}'")
@@ -79,6 +114,104 @@ def parse_remappings_txt(secure_file_ops, logger):
     return remappings
 
 
+def load_foundry_profile(secure_file_ops, logger):
+    """
+    Load the default profile from foundry.toml at the repository root
+
+    Args:
+        secure_file_ops (SecureFileOps|None): Secure file operations or None
+        logger (Logger|None): Optional logger for errors
+
+    Returns:
+        dict|None: `[profile.default]` (or legacy `[default]`) table, or None without foundry.toml
+    """
+    if not secure_file_ops or not secure_file_ops.exists("foundry.toml"):
+        return None
+    try:
+        config = tomllib.loads(secure_file_ops.read_file("foundry.toml"))
+    except Exception as exc:
+        if logger:
+            logger.error(f"Error reading or parsing foundry.toml: {exc}")
+        return {}
+    profile = config.get("profile", {}).get("default")
+    if not isinstance(profile, dict):
+        profile = config.get("default")
+    return profile if isinstance(profile, dict) else {}
+
+
+def parse_foundry_remappings(foundry_profile, logger):
+    """
+    Parse the `remappings` array of a foundry.toml profile
+
+    Args:
+        foundry_profile (dict|None): Profile returned by load_foundry_profile
+        logger (Logger|None): Optional logger for warnings
+
+    Returns:
+        dict: Map of remapping prefixes to repo-relative paths
+    """
+    remappings = {}
+    for line in (foundry_profile or {}).get("remappings", []) or []:
+        parsed = parse_remapping(line) if isinstance(line, str) else None
+        if not parsed:
+            if logger:
+                logger.warning(f"Skipping malformed remapping in foundry.toml: {line!r}")
+            continue
+        prefix, target = parsed
+        remappings[prefix] = target
+    return remappings
+
+
+def infer_lib_remappings(secure_file_ops, foundry_profile, submodule_data, remappings, logger):
+    """
+    Infer the remappings forge derives from library directories
+
+    Forge maps `<name>/` to `lib/<name>/src/` (or `lib/<name>/` when there is no `src`) for every
+    library without an explicit remapping. Library directories come from the profile's `libs`
+    (default `lib`) in Foundry projects, and from git submodules under `lib/` otherwise.
+    node_modules is left to package.json
+
+    Args:
+        secure_file_ops (SecureFileOps|None): Secure file operations or None
+        foundry_profile (dict|None): Profile returned by load_foundry_profile
+        submodule_data (dict): Map of repo-relative submodule paths to URLs
+        remappings (dict): Explicit remappings; libraries they already target are skipped
+        logger (Logger|None): Optional logger for debug output
+
+    Returns:
+        dict: Inferred map of prefixes to repo-relative paths
+    """
+    if not secure_file_ops:
+        return {}
+    if foundry_profile is not None:
+        lib_dirs = foundry_profile.get("libs") or ["lib"]
+    else:
+        lib_dirs = ["lib"] if any(path.startswith("lib/") for path in submodule_data or {}) else []
+
+    targets = [target + "/" for target in (remappings or {}).values()]
+    inferred = {}
+    for lib_dir in lib_dirs:
+        lib_dir = _normalize_remapping_target(lib_dir)
+        if lib_dir == "node_modules" or lib_dir.endswith("/node_modules") or not secure_file_ops.is_dir(lib_dir):
+            continue
+        try:
+            entries = sorted(secure_file_ops.list_dir(lib_dir), key=lambda entry: entry.name)
+        except Exception:
+            continue
+        for entry in entries:
+            library = f"{lib_dir}/{entry.name}"
+            if entry.name.startswith(".") or not secure_file_ops.is_dir(library):
+                continue
+            prefix = f"{entry.name}/"
+            if prefix in remappings or any(target.startswith(library + "/") for target in targets):
+                continue
+            has_src = secure_file_ops.is_dir(f"{library}/src")
+            inferred[prefix] = f"{library}/src" if has_src else library
+            if logger:
+                logger.debug(f"  Inferred remapping: '{prefix}' -> '{inferred[prefix]}'")
+    return inferred
+
+
 def get_hardhat_remappings(repo_path, logger):
     """
     Invoke Node helper to extract Hardhat remappings
@@ -166,6 +299,19 @@ def get_hardhat_remappings(repo_path, logger):
         return {}
 
 
+def _names_align(package_name, directory_name):
+    """
+    Return True when a package name and a library directory name plausibly name the same project
+
+    Punctuation is ignored, so '@openzeppelin/contracts' aligns with 'openzeppelin-contracts'
+    """
+    package_cmp = re.sub(r"[^a-z0-9]", "", package_name.lower())
+    dir_cmp = re.sub(r"[^a-z0-9]", "", directory_name.lower())
+    if not package_cmp or not dir_cmp:
+        return False
+    return package_cmp in dir_cmp or dir_cmp in package_cmp
+
+
 def associate_submodules_with_solidity_packages(external_packages, remappings,
                                                 hardhat_remappings, submodule_data, logger):
     """
@@ -173,9 +319,9 @@ def associate_submodules_with_solidity_packages(external_packages, remappings,
 
     Args:
         external_packages (dict): Package metadata map, mutated in place when URLs found
-        remappings (dict): remappings.txt map of prefix to absolute path
-        hardhat_remappings (dict): Hardhat derived remappings of prefix to absolute path
-        submodule_data (dict): Map of normalized submodule paths to URLs
+        remappings (dict): remappings.txt / foundry.toml / inferred map of prefix to repo-relative path
+        hardhat_remappings (dict): Hardhat derived remappings of prefix to path
+        submodule_data (dict): Map of repo-relative submodule paths to URLs
         logger (Logger|None): Optional logger for info and warnings
 
     Returns:
@@ -225,16 +371,7 @@ def associate_submodules_with_solidity_packages(external_packages, remappings,
 
         if fast_seg:
             candidate = f"lib/{fast_seg}"
-            package_cmp = package_name.replace("-", "").replace("_", "").lower()
-            seg_cmp = fast_seg.replace("-", "").replace("_", "").lower()
-            aligned = (
-                package_cmp == seg_cmp
-                or seg_cmp.startswith(package_cmp)
-                or package_cmp.startswith(seg_cmp)
-                or package_cmp in seg_cmp
-                or seg_cmp in package_cmp
-            )
-            if aligned and candidate in submodule_data:
+            if _names_align(package_name, fast_seg) and candidate in submodule_data:
                 assigned_url = submodule_data[candidate]
                 assigned_path = candidate
 
@@ -243,16 +380,7 @@ def associate_submodules_with_solidity_packages(external_packages, remappings,
             for sm_path, sm_url in submodule_data.items():
                 normalized_sm = sm_path
                 if normalized.startswith(normalized_sm + "/") or normalized == normalized_sm:
-                    base = Path(normalized_sm).name
-                    package_cmp = package_name.replace("-", "").replace("_", "").lower()
-                    base_cmp = base.replace("-", "").replace("_", "").lower()
-                    aligned = (
-                        package_cmp == base_cmp
-                        or base_cmp.startswith(package_cmp)
-                        or package_cmp.startswith(base_cmp)
-                        or package_cmp in base_cmp
-                        or base_cmp in package_cmp
-                    )
+                    aligned = _names_align(package_name, Path(normalized_sm).name)
                     if aligned and len(normalized_sm) > best_len:
                         best_len = len(normalized_sm)
                         assigned_url = sm_url
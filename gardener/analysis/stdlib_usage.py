@@ -0,0 +1,85 @@
+"""
+Standard library usage statistics
+
+Builtin and standard library modules are never external packages, but how much a project leans
+on them is useful when asking whether a dependency could be replaced by the standard library.
+Enabled with the `STDLIB_USAGE` config flag, the report gains a `stdlib_usage` section counting,
+per language, the files importing each standard library module
+"""
+
+import sys
+from collections import Counter
+
+# Node.js builtin modules, importable with or without the 'node:' scheme
+NODE_BUILTIN_MODULES = {
+    "assert", "async_hooks", "buffer", "child_process", "cluster", "console", "constants", "crypto", "dgram",
+    "diagnostics_channel", "dns", "domain", "events", "fs", "http", "http2", "https", "inspector", "module", "net",
+    "os", "path", "perf_hooks", "process", "punycode", "querystring", "readline", "repl", "stream",
+    "string_decoder", "sys", "timers", "tls", "trace_events", "tty", "url", "util", "v8", "vm", "wasi",
+    "worker_threads", "zlib",
+}
+# Only importable with the 'node:' scheme
+NODE_SCHEME_ONLY_MODULES = {"sea", "sqlite", "test"}
+# Crates shipped with the Rust toolchain
+RUST_STD_CRATES = {"std", "core", "alloc", "proc_macro", "test"}
+
+
+def stdlib_module(language, import_name):
+    """
+    Return the standard library module an import refers to, or None for anything else
+
+    Args:
+        language (str): Language of the importing file
+        import_name (str): Import as recorded in file_imports
+
+    Returns:
+        str|None: Module name such as 'os', 'fs', 'net/http' or 'std'
+    """
+    if language in ("python", "notebook"):
+        module = import_name.split(".", 1)[0]
+        return module if module in sys.stdlib_module_names else None
+    if language in ("javascript", "typescript"):
+        scheme = import_name.startswith("node:")
+        module = import_name[len("node:") :] if scheme else import_name
+        module = module.split("/", 1)[0]
+        if module in NODE_BUILTIN_MODULES or (scheme and module in NODE_SCHEME_ONLY_MODULES):
+            return module
+        return None
+    if language == "go":
+        # Standard library import paths have no dot in their first element
+        return import_name if "." not in import_name.split("/", 1)[0] else None
+    if language == "rust":
+        return import_name if import_name in RUST_STD_CRATES else None
+    return None
+
+
+def stdlib_usage(file_imports, source_files):
+    """
+    Count standard library module usage per language
+
+    Python, notebooks, JavaScript/TypeScript (Node.js builtins), Go and Rust are counted
+
+    Args:
+        file_imports (dict): Relative file path -> import names
+        source_files (dict): Relative file path -> file info with 'language'
+
+    Returns:
+        dict: Language -> {'files': files importing any standard library module,
+            'modules': module -> importing file count, most used first}
+    """
+    modules = {}
+    files = Counter()
+    for rel_path, imports in file_imports.items():
+        language = (source_files.get(rel_path) or {}).get("language")
+        used = {stdlib_module(language, name) for name in imports} - {None}
+        if not used:
+            continue
+        files[language] += 1
+        modules.setdefault(language, Counter()).update(used)
+    return {
+        language: {
+            "files": files[language],
+            "modules": dict(sorted(modules[language].items(), key=lambda item: (-item[1], item[0]))),
+        }
+        for language in sorted(modules)
+    }
@@ -0,0 +1,158 @@
+"""
+Advisory suggestion passes
+
+Suggestion passes look at the declared external packages after analysis and emit advisory
+findings; they never change the graph or the ranking. The built-in `alternatives` pass flags
+packages with a known lighter or maintained alternative (`request` → `undici` / built-in
+`fetch`) from an equivalence dataset that `ALTERNATIVES_DATASET` can extend or override. More
+passes can be added with `register_suggestion_pass`
+"""
+
+import yaml
+
+from gardener.common.defaults import GraphAnalysisConfig as cfg
+
+# Built-in equivalence dataset: ecosystem -> package -> alternatives and the reason to consider them
+DEFAULT_ALTERNATIVES = {
+    "npm": {
+        "request": {"alternatives": ["undici", "fetch (built-in)"], "reason": "Deprecated since 2020"},
+        "node-fetch": {"alternatives": ["fetch (built-in)"], "reason": "Node.js 18+ ships a global fetch"},
+        "moment": {"alternatives": ["date-fns", "dayjs", "Temporal"], "reason": "In maintenance mode"},
+        "left-pad": {"alternatives": ["String.prototype.padStart (built-in)"], "reason": "Covered by the language"},
+        "uuid": {"alternatives": ["crypto.randomUUID (built-in)"], "reason": "Covered by Node.js and browsers"},
+        "rimraf": {"alternatives": ["fs.rm (built-in)"], "reason": "fs.rm supports { recursive: true }"},
+        "mkdirp": {"alternatives": ["fs.mkdir (built-in)"], "reason": "fs.mkdir supports { recursive: true }"},
+        "tslint": {"alternatives": ["eslint", "typescript-eslint"], "reason": "Deprecated since 2019"},
+    },
+    "pypi": {
+        "mock": {"alternatives": ["unittest.mock (stdlib)"], "reason": "Part of the standard library"},
+        "nose": {"alternatives": ["pytest"], "reason": "Unmaintained"},
+        "pytz": {"alternatives": ["zoneinfo (stdlib)"], "reason": "Python 3.9+ ships zoneinfo"},
+        "simplejson": {"alternatives": ["json (stdlib)"], "reason": "Part of the standard library"},
+        "toml": {"alternatives": ["tomllib (stdlib)", "tomli"], "reason": "Python 3.11+ ships tomllib"},
+        "six": {"alternatives": [], "reason": "Python 2 compatibility layer, removable on Python 3"},
+    },
+    "go": {
+        "github.com/pkg/errors": {"alternatives": ["errors (stdlib)"], "reason": "Archived; Go 1.13+ wraps errors"},
+        "github.com/golang/protobuf": {
+            "alternatives": ["google.golang.org/protobuf"],
+            "reason": "Superseded by the APIv2 module",
+        },
+        "github.com/dgrijalva/jwt-go": {"alternatives": ["github.com/golang-jwt/jwt"], "reason": "Unmaintained"},
+    },
+    "cargo": {
+        "lazy_static": {
+            "alternatives": ["std::sync::LazyLock (std)", "once_cell"],
+            "reason": "Rust 1.80+ ships LazyLock",
+        },
+        "failure": {"alternatives": ["thiserror", "anyhow"], "reason": "Deprecated"},
+        "structopt": {"alternatives": ["clap"], "reason": "Merged into clap 3"},
+    },
+}
+
+
+def load_equivalences(path=None):
+    """
+    Return the equivalence dataset: the built-in entries, extended or overridden by a JSON/YAML file
+
+    The file uses the same shape as DEFAULT_ALTERNATIVES; setting a package to null removes its
+    built-in entry
+
+    Args:
+        path (str): Optional dataset file
+
+    Returns:
+        dict: ecosystem -> package -> {'alternatives': [...], 'reason': str}
+
+    Raises:
+        ValueError: If the file cannot be read or is not a mapping of ecosystems
+    """
+    dataset = {ecosystem: dict(entries) for ecosystem, entries in DEFAULT_ALTERNATIVES.items()}
+    if not path:
+        return dataset
+    try:
+        with open(path, "r", encoding="utf-8") as handle:
+            overrides = yaml.safe_load(handle) or {}
+    except (OSError, yaml.YAMLError) as e:
+        raise ValueError(f"Could not read alternatives dataset {path}: {e}")
+    if not isinstance(overrides, dict) or not all(isinstance(v, dict) for v in overrides.values()):
+        raise ValueError(f"Alternatives dataset {path} must map ecosystems to packages")
+    for ecosystem, entries in overrides.items():
+        target = dataset.setdefault(ecosystem, {})
+        for name, entry in entries.items():
+            if entry is None:
+                target.pop(name, None)
+            elif isinstance(entry, dict):
+                alternatives = list(entry.get("alternatives") or [])
+                target[name] = {"alternatives": alternatives, "reason": entry.get("reason", "")}
+    return dataset
+
+
+def suggest_alternatives(external_packages, dataset=None):
+    """
+    Suggestion pass flagging packages with known lighter or maintained alternatives
+
+    Args:
+        external_packages (dict): Package metadata keyed by distribution name
+        dataset (dict): Optional equivalence dataset; loaded from ALTERNATIVES_DATASET when omitted
+
+    Returns:
+        list: Advisory findings with keys: kind, severity, package, ecosystem, alternatives, reason
+    """
+    if dataset is None:
+        dataset = load_equivalences(cfg.ALTERNATIVES_DATASET)
+    findings = []
+    for name in sorted(external_packages):
+        ecosystem = external_packages[name].get("ecosystem")
+        entry = (dataset.get(ecosystem) or {}).get(name)
+        if entry is None:
+            continue
+        findings.append(
+            {
+                "kind": "alternative",
+                "severity": "advisory",
+                "package": name,
+                "ecosystem": ecosystem,
+                "alternatives": entry["alternatives"],
+                "reason": entry["reason"],
+            }
+        )
+    return findings
+
+
+# Registered passes by name; each takes the external packages and returns a list of findings
+SUGGESTION_PASSES = {"alternatives": suggest_alternatives}
+
+
+def register_suggestion_pass(name, suggestion_pass):
+    """
+    Register an additional suggestion pass
+
+    Args:
+        name (str): Pass name, recorded on its findings as `pass`
+        suggestion_pass (callable): Function taking external_packages and returning a list of findings
+    """
+    SUGGESTION_PASSES[name] = suggestion_pass
+
+
+def run_suggestion_passes(external_packages, logger=None):
+    """
+    Run every registered suggestion pass; a failing pass is logged and skipped
+
+    Args:
+        external_packages (dict): Package metadata keyed by distribution name
+        logger (Logger): Optional logger
+
+    Returns:
+        list: Findings of all passes, each tagged with the `pass` that produced it
+    """
+    findings = []
+    for name, suggestion_pass in SUGGESTION_PASSES.items():
+        try:
+            produced = suggestion_pass(external_packages)
+        except Exception as e:
+            if logger:
+                logger.warning(f"Suggestion pass '{name}' failed: {e}")
+            continue
+        findings.extend({**finding, "pass": name} for finding in produced)
+    return findings
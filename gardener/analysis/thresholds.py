@@ -0,0 +1,67 @@
+"""
+Minimum-evidence thresholds for the funding split
+
+MIN_IMPORT_COUNT and MIN_FILES keep packages with too little import evidence out of
+`top_dependencies`, so a one-off experimental import does not earn a share. A package's import
+count is the number of (file, import name) pairs mapping to it, so a file importing two modules of
+one package counts twice; its file count is the number of distinct importing files. Packages below
+either threshold stay in `external_packages` and are listed under `below_threshold` with their
+counts; the remaining shares are renormalized. Both thresholds default to 0, which turns them off
+"""
+
+from gardener.analysis.graph import import_distribution_resolver
+
+
+def evidence_counts(external_packages, file_imports):
+    """
+    Count the import evidence of each package
+
+    Args:
+        external_packages (dict): External packages metadata
+        file_imports (dict): File path -> list of imported package names
+
+    Returns:
+        dict: Distribution name -> {'import_count', 'files'}
+    """
+    dist_for = import_distribution_resolver(external_packages)
+    counts = {}
+    for imports in file_imports.values():
+        seen = set()
+        for import_name in imports:
+            dist = dist_for(import_name)
+            if dist is None:
+                continue
+            entry = counts.setdefault(dist, {"import_count": 0, "files": 0})
+            entry["import_count"] += 1
+            if dist not in seen:
+                entry["files"] += 1
+                seen.add(dist)
+    return counts
+
+
+def apply_evidence_thresholds(top_deps_tuples, external_packages, file_imports, min_import_count=0, min_files=0):
+    """
+    Split ranked packages into those meeting the evidence thresholds and those below them
+
+    Args:
+        top_deps_tuples (list): (package_name, score) tuples ordered by importance
+        external_packages (dict): External packages metadata
+        file_imports (dict): File path -> list of imported package names
+        min_import_count (int): Minimum number of imports; 0 disables the check
+        min_files (int): Minimum number of importing files; 0 disables the check
+
+    Returns:
+        tuple: (kept tuples, below-threshold entries {'package_name', 'ecosystem', 'import_count', 'files'})
+    """
+    if not min_import_count and not min_files:
+        return list(top_deps_tuples), []
+    counts = evidence_counts(external_packages, file_imports)
+    kept, below = [], []
+    for package_name, score in top_deps_tuples:
+        count = counts.get(package_name, {"import_count": 0, "files": 0})
+        if count["import_count"] >= min_import_count and count["files"] >= min_files:
+            kept.append((package_name, score))
+            continue
+        ecosystem = (external_packages.get(package_name) or {}).get("ecosystem", "unknown")
+        below.append({"package_name": package_name, "ecosystem": ecosystem, **count})
+    return kept, below
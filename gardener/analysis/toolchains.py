@@ -0,0 +1,208 @@
+"""
+Toolchain version manifests
+
+Collects the language runtimes and tools a repository pins for its development environment
+(asdf `.tool-versions`, mise `mise.toml`, nvm/nodenv `.nvmrc`/`.node-version`, pyenv
+`.python-version`, rustup `rust-toolchain.toml`, Volta's `package.json` `volta` block) into the
+`toolchains` section of the results. Toolchains are not packages, so they are not scored
+"""
+
+import json
+import re
+import tomllib
+from pathlib import Path
+
+from gardener.common.file_helpers import read_repo_file
+
+# Single-version files: file name -> (tool, source)
+VERSION_FILES = {
+    ".nvmrc": ("node", "nvm"),
+    ".node-version": ("node", "node-version"),
+    ".python-version": ("python", "pyenv"),
+    ".ruby-version": ("ruby", "rbenv"),
+    ".terraform-version": ("terraform", "tfenv"),
+    ".go-version": ("go", "goenv"),
+}
+TOOL_VERSIONS_FILE = ".tool-versions"
+MISE_FILE_NAMES = {"mise.toml", ".mise.toml"}
+RUST_TOOLCHAIN_FILE_NAMES = {"rust-toolchain.toml", "rust-toolchain"}
+TOOLCHAIN_FILE_NAMES = set(VERSION_FILES) | {TOOL_VERSIONS_FILE} | MISE_FILE_NAMES | RUST_TOOLCHAIN_FILE_NAMES
+# Volta pins live in package.json, which is read for this section only
+VOLTA_MANIFEST = "package.json"
+
+# asdf plugin names that differ from the tool's usual name
+TOOL_ALIASES = {"nodejs": "node", "golang": "go"}
+
+# `v20.11.0` -> `20.11.0`, leaving names like `lts/iron` alone
+VERSION_PREFIX_RE = re.compile(r"^v(?=\d)")
+
+
+def _line_of(raw_lines, text):
+    """
+    Return the 1-based number of the first line containing text, or 0 when not found
+    """
+    for index, raw in enumerate(raw_lines, start=1):
+        if text in raw:
+            return index
+    return 0
+
+
+def _entry(tool, version, source, rel_path, line, **details):
+    """
+    Build a toolchain entry
+    """
+    entry = {
+        "tool": TOOL_ALIASES.get(tool, tool),
+        "version": VERSION_PREFIX_RE.sub("", str(version).strip()),
+        "source": source,
+        "file": rel_path,
+        "line": line,
+    }
+    entry.update({key: value for key, value in details.items() if value})
+    return entry
+
+
+def _parse_version_file(content, basename, rel_path):
+    """
+    Parse a single-version file such as `.nvmrc`; the first non-comment line is the version
+    """
+    tool, source = VERSION_FILES[basename]
+    for line_number, line in enumerate(content.splitlines(), start=1):
+        version = line.split("#", 1)[0].strip()
+        if version:
+            return [_entry(tool, version, source, rel_path, line_number)]
+    return []
+
+
+def _parse_tool_versions(content, rel_path):
+    """
+    Parse asdf `.tool-versions`: `<tool> <version> [<fallback versions>...]` per line
+    """
+    entries = []
+    for line_number, line in enumerate(content.splitlines(), start=1):
+        fields = line.split("#", 1)[0].split()
+        if len(fields) >= 2:
+            entries.append(_entry(fields[0], fields[1], "asdf", rel_path, line_number, fallbacks=fields[2:]))
+    return entries
+
+
+def _parse_mise(content, rel_path):
+    """
+    Parse the `[tools]` table of a mise config; a tool may pin one version, a list or `{ version = ... }`
+    """
+    tools = tomllib.loads(content).get("tools")
+    raw_lines = content.splitlines()
+    entries = []
+    for tool, spec in tools.items() if isinstance(tools, dict) else []:
+        versions = spec if isinstance(spec, list) else [spec]
+        versions = [v.get("version") if isinstance(v, dict) else v for v in versions]
+        versions = [str(v) for v in versions if v is not None]
+        if versions:
+            line = _line_of(raw_lines, tool)
+            entries.append(_entry(tool, versions[0], "mise", rel_path, line, fallbacks=versions[1:]))
+    return entries
+
+
+def _parse_rust_toolchain(content, basename, rel_path):
+    """
+    Parse `rust-toolchain.toml`, or a legacy `rust-toolchain` holding either TOML or a bare channel
+    """
+    try:
+        toolchain = tomllib.loads(content).get("toolchain") or {}
+    except tomllib.TOMLDecodeError:
+        if basename != "rust-toolchain":
+            raise
+        toolchain = {"channel": content.strip()}
+    channel = toolchain.get("channel")
+    if not channel:
+        return []
+    return [
+        _entry(
+            "rust",
+            channel,
+            "rustup",
+            rel_path,
+            _line_of(content.splitlines(), channel),
+            components=list(toolchain.get("components") or []),
+            targets=list(toolchain.get("targets") or []),
+        )
+    ]
+
+
+def _parse_volta(content, rel_path):
+    """
+    Parse the `volta` block of a package.json (`node`, `npm`, `yarn`, `pnpm` pins)
+    """
+    volta = json.loads(content).get("volta")
+    raw_lines = content.splitlines()
+    entries = []
+    for tool, version in volta.items() if isinstance(volta, dict) else []:
+        if tool != "extends" and isinstance(version, str):
+            entries.append(_entry(tool, version, "volta", rel_path, _line_of(raw_lines, f'"{tool}"')))
+    return entries
+
+
+def extract_toolchains(content, basename, rel_path):
+    """
+    Extract pinned toolchains from one version manifest
+
+    Args:
+        content (str): File content
+        basename (str): File name, which selects the format
+        rel_path (str): Repository-relative path used in entries
+
+    Returns:
+        list: Entries with keys: tool, version, source, file, line and, when present, fallbacks
+            (further versions), components and targets (rustup)
+    """
+    if basename in VERSION_FILES:
+        return _parse_version_file(content, basename, rel_path)
+    if basename == TOOL_VERSIONS_FILE:
+        return _parse_tool_versions(content, rel_path)
+    if basename in MISE_FILE_NAMES:
+        return _parse_mise(content, rel_path)
+    if basename in RUST_TOOLCHAIN_FILE_NAMES:
+        return _parse_rust_toolchain(content, basename, rel_path)
+    if basename == VOLTA_MANIFEST:
+        return _parse_volta(content, rel_path)
+    return []
+
+
+def collect_toolchains(files, secure_file_ops, logger, repo_path):
+    """
+    Collect pinned toolchains from version manifests and package.json files
+
+    Args:
+        files (list): Absolute paths of candidate files; other file names are ignored
+        secure_file_ops (SecureFileOps|None): Secure file operations or None
+        logger (Logger|None): Optional logger
+        repo_path (str): Absolute repository path
+
+    Returns:
+        list: Toolchain entries ordered by file and line
+    """
+    toolchains = []
+    for path in sorted(set(files)):
+        basename = Path(path).name
+        if basename not in TOOLCHAIN_FILE_NAMES and basename != VOLTA_MANIFEST:
+            continue
+        try:
+            if secure_file_ops:
+                rel_path = secure_file_ops.get_relative_path(path)
+            else:
+                rel_path = str(Path(path).relative_to(repo_path))
+        except ValueError:
+            rel_path = path
+        try:
+            toolchains.extend(extract_toolchains(read_repo_file(path, secure_file_ops), basename, rel_path))
+        except (ValueError, AttributeError) as exc:
+            # Malformed TOML/JSON (TOMLDecodeError and JSONDecodeError are ValueErrors) or a non-object document
+            if logger:
+                logger.warning(f"Could not parse toolchain manifest {rel_path}: {exc}")
+        except Exception as exc:
+            if logger:
+                logger.warning(f"Could not read toolchain manifest {path}: {exc}")
+
+    if logger and toolchains:
+        logger.info(f"... Found {len(toolchains)} pinned toolchains")
+    return toolchains
@@ -0,0 +1,956 @@
+"""
+Tooling dependency detection
+
+Finds dependencies installed imperatively by shell scripts, Makefile recipes and notebook
+magics (`pip install`, `npm install -g`, `cargo install`, `go install`, `curl ... | sh`,
+`%pip install`) rather than declared in a manifest, the external tools (protoc, terraform,
+docker, ...) that Makefile, justfile and Taskfile recipes invoke, the hook repositories
+pinned in `.pre-commit-config.yaml`, the image, features and lifecycle-command installs of
+Dev Container definitions (`.devcontainer/devcontainer.json`), and the npm binaries that
+package.json `scripts` run (`eslint .`, `npx tsup`), linked to the package providing them
+"""
+
+import json
+import re
+import shlex
+from pathlib import Path
+from urllib.parse import urlsplit
+
+import yaml
+
+from gardener.analysis.toolchains import TOOLCHAIN_FILE_NAMES
+from gardener.common.file_helpers import read_repo_file
+from gardener.treewalk.deno import load_jsonc
+from gardener.treewalk.notebook import notebook_code_cells
+
+SCRIPT_FILE_EXTENSIONS = {".sh", ".bash", ".ipynb"}
+NOTEBOOK_SHELL_CELL_MAGICS = {"bash", "sh", "script"}
+SCRIPT_FILE_NAMES = {"Makefile", "makefile", "GNUmakefile"}
+JUSTFILE_NAMES = {"justfile", "Justfile", ".justfile"}
+TASKFILE_NAMES = {"Taskfile.yml", "Taskfile.yaml", "taskfile.yml", "taskfile.yaml"}
+TASK_RUNNER_FILE_NAMES = SCRIPT_FILE_NAMES | JUSTFILE_NAMES | TASKFILE_NAMES
+PRE_COMMIT_CONFIG_NAMES = {".pre-commit-config.yaml", ".pre-commit-config.yml"}
+DEVCONTAINER_DIR = ".devcontainer"
+DEVCONTAINER_FILE_NAMES = {"devcontainer.json", ".devcontainer.json"}
+PACKAGE_JSON_NAME = "package.json"
+# Dotfiles and dot-directories scanned even though hidden entries are otherwise skipped
+HIDDEN_SCRIPT_FILE_NAMES = (
+    PRE_COMMIT_CONFIG_NAMES
+    | {".justfile", ".devcontainer.json", DEVCONTAINER_DIR}
+    | {name for name in TOOLCHAIN_FILE_NAMES if name.startswith(".")}
+)
+
+# Scope of tools invoked by task-runner recipes, pre-commit hook repositories and Dev Container
+# images/features, as opposed to installed packages
+TOOL_ECOSYSTEM = "tool"
+PRE_COMMIT_ECOSYSTEM = "pre-commit"
+DEVCONTAINER_FEATURE_ECOSYSTEM = "devcontainer-feature"
+CONTAINER_IMAGE_ECOSYSTEM = "container-image"
+TOOLING_SCOPE = "tooling"
+# Key listing where each kind of tooling dependency was found
+OCCURRENCES_KEY = {
+    TOOL_ECOSYSTEM: "invocations",
+    PRE_COMMIT_ECOSYSTEM: "declarations",
+    DEVCONTAINER_FEATURE_ECOSYSTEM: "declarations",
+    CONTAINER_IMAGE_ECOSYSTEM: "declarations",
+}
+# devcontainer.json properties holding shell commands, in the order the lifecycle runs them
+DEVCONTAINER_LIFECYCLE_COMMANDS = (
+    "initializeCommand", "onCreateCommand", "updateContentCommand", "postCreateCommand", "postStartCommand",
+    "postAttachCommand",
+)
+
+# npm binaries whose name differs from the package providing them
+NPM_BIN_PACKAGES = {
+    "tsc": "typescript", "tsserver": "typescript", "ng": "@angular/cli", "nest": "@nestjs/cli",
+    "vue-cli-service": "@vue/cli-service", "svelte-kit": "@sveltejs/kit", "biome": "@biomejs/biome",
+    "playwright": "@playwright/test", "changeset": "@changesets/cli", "commitlint": "@commitlint/cli",
+    "babel": "@babel/cli", "swc": "@swc/cli", "eleventy": "@11ty/eleventy", "graphql-codegen": "@graphql-codegen/cli",
+    "docusaurus": "@docusaurus/core", "sb": "storybook", "api-extractor": "@microsoft/api-extractor",
+    "firebase": "firebase-tools", "run-s": "npm-run-all", "run-p": "npm-run-all", "dotenv": "dotenv-cli",
+}
+# Binaries that set up the environment and run the command following them
+NPM_ENV_WRAPPERS = {"cross-env", "dotenv", "env-cmd"}
+# Commands running a binary: `npx eslint`, `pnpm exec tsc`, `yarn dlx create-vite`, `bunx biome`
+NPM_RUNNERS = {
+    ("npx",), ("bunx",), ("pnpx",), ("npm", "exec"), ("pnpm", "exec"), ("pnpm", "dlx"), ("yarn", "exec"),
+    ("yarn", "dlx"),
+}
+# Runner options whose next token is a value rather than the binary
+NPM_RUNNER_VALUE_OPTIONS = {"-p", "--package", "-c", "--call", "--prefix", "--filter", "-C", "--dir"}
+
+# External tools recognized when invoked from Makefile/justfile/Taskfile recipes
+KNOWN_TOOLS = {
+    "ansible", "ansible-playbook", "aws", "az", "bazel", "bazelisk", "buf", "buildah", "cmake", "cosign",
+    "docker", "docker-compose", "flatc", "gcloud", "ginkgo", "gofumpt", "golangci-lint", "goreleaser",
+    "grpcurl", "hadolint", "helm", "helmfile", "jq", "k3d", "kind", "ko", "kubectl", "kustomize", "migrate",
+    "minikube", "mockery", "mockgen", "ninja", "oapi-codegen", "openapi-generator", "packer", "podman",
+    "pre-commit", "protoc", "pulumi", "shellcheck", "shfmt", "skaffold", "sqlc", "staticcheck", "swag",
+    "swagger", "terraform", "terragrunt", "tflint", "tilt", "trivy", "vault", "wasm-pack", "yq",
+}
+
+# `curl ... | sh` style installers, optionally through sudo or an explicit shell path
+PIPE_TO_SHELL_RE = re.compile(r"\b(?:curl|wget)\b[^|\n]*\|\s*(?:sudo\s+(?:-\S+\s+)*)?(?:/\S*/)?(?:ba|z|da)?sh\b")
+URL_RE = re.compile(r"https?://[^\s'\"|;)]+")
+COMMAND_SEPARATOR_RE = re.compile(r"&&|\|\||;|\|")
+ENV_ASSIGNMENT_RE = re.compile(r"^[A-Za-z_][A-Za-z0-9_]*=")
+PYPI_REQUIREMENT_RE = re.compile(r"^([A-Za-z0-9][A-Za-z0-9._-]*)(\[[^\]]*\])?\s*((?:[=!<>~]=?|===)\s*\S+)?$")
+
+# Options whose next token is a value rather than a package
+PIP_VALUE_OPTIONS = {
+    "-r", "--requirement", "-c", "--constraint", "-e", "--editable", "-i", "--index-url",
+    "--extra-index-url", "-f", "--find-links", "-t", "--target", "--prefix", "--root", "--python",
+    "--platform", "--python-version", "--implementation", "--abi", "--src", "--trusted-host",
+}
+NPM_VALUE_OPTIONS = {"--registry", "--prefix", "--tag", "--cache", "--userconfig"}
+CARGO_VALUE_OPTIONS = {
+    "--version", "--vers", "--git", "--branch", "--tag", "--rev", "--path", "--root", "--index",
+    "--registry", "--features", "-F", "--target", "--target-dir", "--bin", "--example", "--profile", "-j", "--jobs",
+}
+GO_VALUE_OPTIONS = {"-tags", "-ldflags", "-gcflags", "-mod", "-modfile", "-o", "-p"}
+
+
+def is_script_file(basename):
+    """
+    Return True when a file should be scanned for install commands or pinned toolchains
+
+    Args:
+        basename (str): File name without directory
+    """
+    return (
+        basename in TASK_RUNNER_FILE_NAMES
+        or basename in PRE_COMMIT_CONFIG_NAMES
+        or basename in DEVCONTAINER_FILE_NAMES
+        or basename in TOOLCHAIN_FILE_NAMES
+        or basename == PACKAGE_JSON_NAME
+        or Path(basename).suffix in SCRIPT_FILE_EXTENSIONS
+    )
+
+
+def _logical_lines(content, recipes=None):
+    """
+    Join backslash continuations and yield (line_number, command_text) pairs
+
+    Makefile and justfile content is restricted to recipe lines (tab-indented and indented
+    respectively), with the `@`, `-` and `+` prefixes removed
+
+    Args:
+        content (str): File content
+        recipes (str|None): 'make' or 'just' for task-runner files, None for plain scripts
+
+    Returns:
+        list: (1-based line number, logical line) tuples
+    """
+    lines = []
+    buffer = []
+    start = None
+    for index, raw in enumerate(content.splitlines(), start=1):
+        if start is None:
+            start = index
+            if recipes == "make" and not raw.startswith("\t"):
+                buffer = None
+            elif recipes == "just" and raw[:1] not in (" ", "\t"):
+                buffer = None
+        if buffer is not None:
+            buffer.append(raw.rstrip("\\").strip() if raw.rstrip().endswith("\\") else raw.strip())
+        if raw.rstrip().endswith("\\"):
+            continue
+        if buffer:
+            text = " ".join(part for part in buffer if part)
+            if recipes:
+                text = text.lstrip("@-+ ")
+            if text and not text.startswith("#"):
+                lines.append((start, text))
+        buffer = []
+        start = None
+    return lines
+
+
+def _tokenize(command):
+    """
+    Split one shell command into tokens, dropping sudo, env assignments and comments
+
+    Args:
+        command (str): Single command without separators
+
+    Returns:
+        list: Tokens
+    """
+    command = command.split(" #", 1)[0]
+    try:
+        tokens = shlex.split(command)
+    except ValueError:
+        tokens = command.split()
+    while tokens and (tokens[0] in ("sudo", "env", "exec", "time", "command") or ENV_ASSIGNMENT_RE.match(tokens[0])):
+        tokens = tokens[1:]
+        while tokens and tokens[0].startswith("-") and len(tokens) > 1:
+            tokens = tokens[1:]
+    return tokens
+
+
+def _positional_args(tokens, value_options):
+    """
+    Return non-option arguments, skipping values of options that take one
+
+    Args:
+        tokens (list): Arguments after the install subcommand
+        value_options (set): Options consuming the following token
+
+    Returns:
+        Tuple of (positional arguments list, dict of option name to value)
+    """
+    args = []
+    options = {}
+    skip_next = None
+    for token in tokens:
+        if skip_next:
+            options[skip_next] = token
+            skip_next = None
+            continue
+        if token.startswith("-"):
+            name, _, value = token.partition("=")
+            if name in value_options:
+                if value:
+                    options[name] = value
+                else:
+                    skip_next = name
+            else:
+                options[name] = value or True
+            continue
+        args.append(token)
+    return args, options
+
+
+def _is_unresolvable(arg):
+    """
+    Return True for arguments that are paths, URLs or shell expansions rather than package names
+
+    Args:
+        arg (str): Candidate package argument
+    """
+    return (
+        not arg
+        or "$" in arg
+        or "`" in arg
+        or arg.startswith((".", "/", "~"))
+        or "://" in arg
+        or arg.endswith((".txt", ".whl", ".tar.gz", ".zip", ".tgz"))
+    )
+
+
+def _parse_pip(args):
+    """
+    Parse `pip install` arguments
+
+    Args:
+        args (list): Tokens after `install`
+
+    Returns:
+        list: (name, version) tuples
+    """
+    packages = []
+    positional, _ = _positional_args(args, PIP_VALUE_OPTIONS)
+    for arg in positional:
+        if _is_unresolvable(arg) or "/" in arg:
+            continue
+        match = PYPI_REQUIREMENT_RE.match(arg.split(";", 1)[0].strip())
+        if match:
+            version = (match.group(3) or "").replace(" ", "")
+            packages.append((match.group(1), version))
+    return packages
+
+
+def _split_at_version(arg):
+    """
+    Split `name@version` while keeping the leading `@` of npm scopes
+
+    Args:
+        arg (str): Package argument
+
+    Returns:
+        tuple: (name, version)
+    """
+    at = arg.find("@", 1)
+    if at == -1:
+        return arg, ""
+    return arg[:at], arg[at + 1 :]
+
+
+def _parse_npm(args):
+    """
+    Parse global npm/yarn/pnpm install arguments
+
+    Args:
+        args (list): Tokens after the install subcommand
+
+    Returns:
+        list: (name, version) tuples
+    """
+    packages = []
+    positional, _ = _positional_args(args, NPM_VALUE_OPTIONS)
+    for arg in positional:
+        if _is_unresolvable(arg) or arg.startswith(("git+", "github:", "file:")):
+            continue
+        name, version = _split_at_version(arg)
+        if name.count("/") > (1 if name.startswith("@") else 0):
+            continue
+        packages.append((name, version))
+    return packages
+
+
+def _parse_cargo(args):
+    """
+    Parse `cargo install` arguments
+
+    Args:
+        args (list): Tokens after `install`
+
+    Returns:
+        list: (name, version) tuples
+    """
+    positional, options = _positional_args(args, CARGO_VALUE_OPTIONS)
+    if "--path" in options:
+        return []
+    packages = []
+    for arg in positional:
+        if _is_unresolvable(arg):
+            continue
+        name, version = _split_at_version(arg)
+        packages.append((name, version or options.get("--version") or options.get("--vers") or ""))
+    if not positional and isinstance(options.get("--git"), str):
+        packages.append((options["--git"], options.get("--tag") or options.get("--rev") or ""))
+    return packages
+
+
+def _parse_go(args):
+    """
+    Parse `go install` arguments, keeping only remote package paths
+
+    Args:
+        args (list): Tokens after `install`
+
+    Returns:
+        list: (name, version) tuples
+    """
+    packages = []
+    positional, _ = _positional_args(args, GO_VALUE_OPTIONS)
+    for arg in positional:
+        if _is_unresolvable(arg):
+            continue
+        path, _, version = arg.partition("@")
+        if path.endswith("/..."):
+            path = path[: -len("/...")]
+        if "." not in path.split("/", 1)[0]:
+            continue
+        packages.append((path, version))
+    return packages
+
+
+def _parse_install_command(tokens):
+    """
+    Recognize an install command and extract the packages it installs
+
+    Args:
+        tokens (list): Tokens of one command
+
+    Returns:
+        tuple|None: (ecosystem, [(name, version)]) or None when not an install command
+    """
+    if not tokens:
+        return None
+    program = Path(tokens[0]).name
+    rest = tokens[1:]
+
+    if re.match(r"^python(\d(\.\d+)?)?$", program) and rest[:2] == ["-m", "pip"]:
+        program, rest = "pip", rest[2:]
+    if program == "uv" and rest[:1] == ["pip"]:
+        program, rest = "pip", rest[1:]
+    if re.match(r"^pip(x|\d(\.\d+)?)?$", program) and rest[:1] == ["install"]:
+        return "pypi", _parse_pip(rest[1:])
+    if program == "uv" and rest[:2] == ["tool", "install"]:
+        return "pypi", _parse_pip(rest[2:])
+
+    if program == "npm" and rest[:1] in (["install"], ["i"], ["add"]):
+        if "-g" in rest or "--global" in rest or "--location=global" in rest:
+            return "npm", _parse_npm(rest[1:])
+        return None
+    if program == "yarn" and rest[:2] == ["global", "add"]:
+        return "npm", _parse_npm(rest[2:])
+    if program == "pnpm" and rest[:1] in (["add"], ["install"], ["i"]) and ("-g" in rest or "--global" in rest):
+        return "npm", _parse_npm(rest[1:])
+
+    if program == "cargo" and rest[:1] == ["install"]:
+        return "cargo", _parse_cargo(rest[1:])
+    if program == "cargo" and rest[:1] == ["binstall"]:
+        return "cargo", _parse_cargo(rest[1:])
+
+    if program == "go" and rest[:1] == ["install"]:
+        return "go", _parse_go(rest[1:])
+    return None
+
+
+def _find_line(raw_lines, text):
+    """
+    Return the 1-based number of the first line containing text, or 0 when not found
+
+    Args:
+        raw_lines (list): File lines
+        text (str): Text to look for
+    """
+    for index, raw in enumerate(raw_lines, start=1):
+        if text in raw:
+            return index
+    return 0
+
+
+def _taskfile_lines(content):
+    """
+    Return the commands of every task in a Taskfile as (line_number, command_text) pairs
+
+    Args:
+        content (str): Taskfile YAML
+
+    Returns:
+        list: (1-based line number, logical line) tuples; the line is 0 when it cannot be located
+    """
+    try:
+        document = yaml.safe_load(content) or {}
+    except yaml.YAMLError:
+        return []
+    tasks = document.get("tasks") if isinstance(document, dict) else None
+    if not isinstance(tasks, dict):
+        return []
+
+    raw_lines = content.splitlines()
+    lines = []
+    for task in tasks.values():
+        if isinstance(task, dict):
+            commands = task.get("cmds") or ([task["cmd"]] if task.get("cmd") else [])
+        else:
+            commands = task if isinstance(task, list) else [task]
+        for command in commands:
+            if isinstance(command, dict):
+                command = command.get("cmd")
+            if not isinstance(command, str):
+                continue
+            command_lines = command.splitlines()
+            for offset, text in _logical_lines(command):
+                lines.append((_find_line(raw_lines, command_lines[offset - 1].strip()), text))
+    return lines
+
+
+def task_runner_lines(content, basename):
+    """
+    Return the recipe commands of a Makefile, justfile or Taskfile
+
+    Args:
+        content (str): File content
+        basename (str): File name, which selects the format
+
+    Returns:
+        list: (1-based line number, logical line) tuples
+    """
+    if basename in SCRIPT_FILE_NAMES:
+        return _logical_lines(content, "make")
+    if basename in JUSTFILE_NAMES:
+        return _logical_lines(content, "just")
+    return _taskfile_lines(content)
+
+
+def extract_install_commands(content, rel_path, is_makefile=False, command_lines=None):
+    """
+    Extract imperative install commands from a shell script or Makefile
+
+    Args:
+        content (str): File content
+        rel_path (str): Repository-relative path used in findings
+        is_makefile (bool): True when the content is a Makefile
+        command_lines (list): Optional (line_number, command) pairs already split from content,
+            as returned by task_runner_lines
+
+    Returns:
+        list: Finding dicts with keys: ecosystem, name, version, file, line, command
+    """
+    if command_lines is None:
+        command_lines = _logical_lines(content, "make" if is_makefile else None)
+    findings = []
+    for line_number, text in command_lines:
+        if is_makefile:
+            text = text.replace("$$", "$")
+        for match in PIPE_TO_SHELL_RE.finditer(text):
+            url = URL_RE.search(match.group(0))
+            if url:
+                findings.append(
+                    {
+                        "ecosystem": "script",
+                        "name": url.group(0),
+                        "version": "",
+                        "file": rel_path,
+                        "line": line_number,
+                        "command": match.group(0).strip(),
+                    }
+                )
+        for command in COMMAND_SEPARATOR_RE.split(text):
+            parsed = _parse_install_command(_tokenize(command.strip()))
+            if not parsed:
+                continue
+            ecosystem, packages = parsed
+            for name, version in packages:
+                findings.append(
+                    {
+                        "ecosystem": ecosystem,
+                        "name": name,
+                        "version": version,
+                        "file": rel_path,
+                        "line": line_number,
+                        "command": command.strip(),
+                    }
+                )
+    return findings
+
+
+def extract_tool_invocations(command_lines, rel_path):
+    """
+    Extract invocations of known external tools from task-runner recipe commands
+
+    Args:
+        command_lines (list): (line_number, command) pairs from task_runner_lines
+        rel_path (str): Repository-relative path used in findings
+
+    Returns:
+        list: Finding dicts with ecosystem 'tool' and the same keys as extract_install_commands
+    """
+    findings = []
+    for line_number, text in command_lines:
+        for command in COMMAND_SEPARATOR_RE.split(text):
+            tokens = _tokenize(command.strip())
+            if not tokens or Path(tokens[0]).name not in KNOWN_TOOLS:
+                continue
+            findings.append(
+                {
+                    "ecosystem": TOOL_ECOSYSTEM,
+                    "name": Path(tokens[0]).name,
+                    "version": "",
+                    "file": rel_path,
+                    "line": line_number,
+                    "command": command.strip(),
+                }
+            )
+    return findings
+
+
+def pre_commit_repo_name(repo):
+    """
+    Return the '<host>/<path>' identity of a pre-commit hook repository URL
+
+    Args:
+        repo (str): `repo:` value, e.g. 'https://github.com/psf/black' or 'git@github.com:psf/black.git'
+
+    Returns:
+        str|None: Identity such as 'github.com/psf/black', or None for local paths and sentinels
+    """
+    repo = repo.strip()
+    if repo.startswith("git@") and ":" in repo:
+        repo = "ssh://" + repo[len("git@") :].replace(":", "/", 1)
+    parts = urlsplit(repo)
+    if not parts.netloc or parts.scheme == "file":
+        return None
+    path = parts.path[:-4] if parts.path.endswith(".git") else parts.path
+    host = parts.netloc.rsplit("@", 1)[-1]
+    return f"{host}{path}".rstrip("/")
+
+
+def extract_pre_commit_repos(content, rel_path):
+    """
+    Extract the hook repositories of a `.pre-commit-config.yaml`; `local` and `meta` repos are skipped
+
+    Args:
+        content (str): Config YAML
+        rel_path (str): Repository-relative path used in findings
+
+    Returns:
+        list: Finding dicts with keys: ecosystem, name, version (the pinned `rev`), file, line, hooks,
+            repository_url
+    """
+    try:
+        document = yaml.safe_load(content) or {}
+    except yaml.YAMLError:
+        return []
+    repos = document.get("repos") if isinstance(document, dict) else None
+    raw_lines = content.splitlines()
+    findings = []
+    for repo in repos if isinstance(repos, list) else []:
+        if not isinstance(repo, dict) or not isinstance(repo.get("repo"), str):
+            continue
+        name = pre_commit_repo_name(repo["repo"])
+        if not name:
+            continue
+        hooks = [hook["id"] for hook in repo.get("hooks") or [] if isinstance(hook, dict) and hook.get("id")]
+        findings.append(
+            {
+                "ecosystem": PRE_COMMIT_ECOSYSTEM,
+                "name": name,
+                "version": str(repo.get("rev") or ""),
+                "file": rel_path,
+                "line": _find_line(raw_lines, repo["repo"]),
+                "hooks": hooks,
+                "repository_url": f"https://{name}",
+            }
+        )
+    return findings
+
+
+def oci_reference(reference):
+    """
+    Split an OCI image or feature reference into a fully-qualified name and its tag or digest
+
+    Args:
+        reference (str): Reference such as 'node:20', 'mcr.microsoft.com/devcontainers/python:3.12' or
+            'ghcr.io/devcontainers/features/go@sha256:...'
+
+    Returns:
+        tuple: (name, version), e.g. ('docker.io/library/node', '20')
+    """
+    name, version = reference.strip(), ""
+    if "@" in name:
+        name, version = name.split("@", 1)
+    elif ":" in name.rsplit("/", 1)[-1]:
+        name, version = name.rsplit(":", 1)
+    first, _, rest = name.partition("/")
+    if not rest or ("." not in first and ":" not in first and first != "localhost"):
+        # Docker Hub short names: 'node' is 'docker.io/library/node', 'user/app' is 'docker.io/user/app'
+        name = f"docker.io/{name}" if rest else f"docker.io/library/{name}"
+    return name, version
+
+
+def _devcontainer_commands(document, raw_lines):
+    """
+    Return (line number, command) pairs from devcontainer.json lifecycle commands
+
+    Each command may be a string (run by a shell), an argv array, or an object of named commands
+    that run in parallel
+    """
+    command_lines = []
+    for key in DEVCONTAINER_LIFECYCLE_COMMANDS:
+        value = document.get(key)
+        commands = list(value.values()) if isinstance(value, dict) else [value]
+        for command in commands:
+            if isinstance(command, list):
+                command = shlex.join(str(arg) for arg in command)
+            if isinstance(command, str) and command.strip():
+                command_lines.append((_find_line(raw_lines, f'"{key}"'), command))
+    return command_lines
+
+
+def extract_devcontainer_dependencies(content, rel_path):
+    """
+    Extract the image, features and lifecycle-command installs of a devcontainer.json
+
+    Features are OCI-referenced (`ghcr.io/<owner>/<repo>/<feature>:<version>`); those published to
+    ghcr.io get the GitHub repository of their collection as `repository_url`. Local (`./feature`)
+    and legacy short-id features are skipped
+
+    Args:
+        content (str): devcontainer.json content (JSON with comments)
+        rel_path (str): Repository-relative path used in findings
+
+    Returns:
+        list: Finding dicts with ecosystem 'container-image' or 'devcontainer-feature' (keys: name,
+            version, file, line and, when known, repository_url), followed by install commands as from
+            extract_install_commands
+    """
+    try:
+        document = load_jsonc(content)
+    except ValueError:
+        return []
+    if not isinstance(document, dict):
+        return []
+    raw_lines = content.splitlines()
+    findings = []
+    if isinstance(document.get("image"), str) and document["image"].strip():
+        name, version = oci_reference(document["image"])
+        findings.append(
+            {
+                "ecosystem": CONTAINER_IMAGE_ECOSYSTEM,
+                "name": name,
+                "version": version,
+                "file": rel_path,
+                "line": _find_line(raw_lines, document["image"]),
+            }
+        )
+    features = document.get("features")
+    for reference in features if isinstance(features, dict) else {}:
+        if reference.startswith((".", "/")) or "/" not in reference or "://" in reference:
+            continue
+        name, version = oci_reference(reference)
+        finding = {
+            "ecosystem": DEVCONTAINER_FEATURE_ECOSYSTEM,
+            "name": name,
+            "version": version,
+            "file": rel_path,
+            "line": _find_line(raw_lines, reference),
+        }
+        parts = name.split("/")
+        if parts[0] == "ghcr.io" and len(parts) >= 4:
+            finding["repository_url"] = f"https://github.com/{parts[1]}/{parts[2]}"
+        findings.append(finding)
+    command_lines = _devcontainer_commands(document, raw_lines)
+    return findings + extract_install_commands(content, rel_path, command_lines=command_lines)
+
+
+def _script_binary(tokens, declared):
+    """
+    Return (binary, package, version, runner) for one package.json script command, or None
+
+    A binary counts when a runner names it (`npx tool`, `npx -p pkg tool`) or when it is provided by a
+    package the same package.json declares, by name or through NPM_BIN_PACKAGES
+    """
+    runner = next((runner for runner in NPM_RUNNERS if tuple(tokens[: len(runner)]) == runner), None)
+    package = None
+    if runner:
+        rest = tokens[len(runner) :]
+        while rest and rest[0].startswith("-"):
+            option = rest.pop(0)
+            if option in ("-p", "--package") and rest:
+                package = rest.pop(0)
+            elif option.startswith("--package="):
+                package = option.split("=", 1)[1]
+            elif option == "--":
+                break
+            elif option in NPM_RUNNER_VALUE_OPTIONS and rest:
+                rest.pop(0)
+        tokens = rest
+    if not tokens or _is_unresolvable(tokens[0]) or tokens[0].startswith((".", "/")):
+        return None
+    binary, version = _split_at_version(tokens[0])
+    if package:
+        package, version = _split_at_version(package)
+    elif binary in declared:
+        package = binary
+    elif NPM_BIN_PACKAGES.get(binary) in declared:
+        package = NPM_BIN_PACKAGES[binary]
+    elif runner:
+        # Runners download the package named like the binary when nothing provides it
+        package = NPM_BIN_PACKAGES.get(binary, binary)
+    else:
+        return None
+    return binary, package, version or declared.get(package, ""), " ".join(runner or ())
+
+
+def extract_package_scripts(content, rel_path):
+    """
+    Extract the npm binaries, tools and installs that package.json `scripts` run
+
+    Args:
+        content (str): package.json content
+        rel_path (str): Repository-relative path used in findings
+
+    Returns:
+        list: Finding dicts with ecosystem 'npm', scope 'tooling' and the `script` and `bin` they come
+            from, followed by tool invocations and install commands as from the task-runner scanners
+    """
+    try:
+        document = json.loads(content)
+    except ValueError:
+        return []
+    scripts = document.get("scripts") if isinstance(document, dict) else None
+    if not isinstance(scripts, dict):
+        return []
+    declared = {}
+    for section in ("dependencies", "devDependencies", "optionalDependencies", "peerDependencies"):
+        versions = document.get(section)
+        declared.update(versions if isinstance(versions, dict) else {})
+    raw_lines = content.splitlines()
+    command_lines, findings = [], []
+    for script, command_text in scripts.items():
+        if not isinstance(command_text, str):
+            continue
+        line = _find_line(raw_lines, json.dumps(script))
+        command_lines.append((line, command_text))
+        for command in COMMAND_SEPARATOR_RE.split(command_text):
+            tokens = _tokenize(command.strip())
+            # `cross-env NODE_ENV=production webpack` runs the binary after the assignments
+            while len(tokens) > 1 and tokens[0] in NPM_ENV_WRAPPERS:
+                binary = _script_binary(tokens[:1], declared)
+                if binary:
+                    findings.append(_script_finding(binary, script, rel_path, line, command))
+                rest = tokens[1:]
+                tokens = rest[rest.index("--") + 1 :] if "--" in rest else rest
+                tokens = [token for token in tokens if not ENV_ASSIGNMENT_RE.match(token)]
+            binary = _script_binary(tokens, declared)
+            if binary:
+                findings.append(_script_finding(binary, script, rel_path, line, command))
+    return findings + extract_tool_invocations(command_lines, rel_path) + extract_install_commands(
+        content, rel_path, command_lines=command_lines
+    )
+
+
+def _script_finding(binary, script, rel_path, line, command):
+    name, package, version, runner = binary
+    finding = {
+        "ecosystem": "npm",
+        "name": package,
+        "version": version,
+        "file": rel_path,
+        "line": line,
+        "command": command.strip(),
+        "scope": TOOLING_SCOPE,
+        "script": script,
+        "bin": name,
+    }
+    if runner:
+        finding["runner"] = runner
+    return finding
+
+
+def extract_notebook_install_commands(content, rel_path):
+    """
+    Extract install commands from notebook shell escapes, `%pip`/`%conda` magics and `%%bash` cells
+
+    Args:
+        content (str): Raw `.ipynb` JSON
+        rel_path (str): Repository-relative path used in findings
+
+    Returns:
+        list: Finding dicts as from extract_install_commands, with `line` relative to the cell
+            and an added `cell` index
+    """
+    findings = []
+    for index, source in notebook_code_cells(content):
+        lines = source.splitlines()
+        first = lines[0].strip() if lines else ""
+        if first.startswith("%%") and (first[2:].split() or [""])[0] in NOTEBOOK_SHELL_CELL_MAGICS:
+            shell = "\n".join([""] + lines[1:])
+        else:
+            shell = "\n".join(
+                line.strip()[1:] if line.strip().startswith(("!", "%pip", "%conda")) else "" for line in lines
+            )
+        for finding in extract_install_commands(shell, rel_path):
+            finding["cell"] = index
+            findings.append(finding)
+    return findings
+
+
+def declare_notebook_installs(external_packages, tooling_dependencies):
+    """
+    Add PyPI packages installed only by notebook magics to the declared package set
+
+    Notebooks commonly install what they import with `%pip install` instead of shipping a
+    manifest, so these installs stand in for declarations
+
+    Args:
+        external_packages (dict): Package metadata keyed by distribution name, updated in place
+        tooling_dependencies (list): Output of collect_tooling_dependencies
+
+    Returns:
+        list: Names of the packages added
+    """
+    added = []
+    for entry in tooling_dependencies:
+        if entry["ecosystem"] != "pypi" or entry["name"] in external_packages:
+            continue
+        notebooks = sorted({c["file"] for c in entry["install_commands"] if "cell" in c})
+        if not notebooks:
+            continue
+        external_packages[entry["name"]] = {
+            "ecosystem": "pypi",
+            "version": entry["version"],
+            "found_in_manifests": notebooks,
+            "source": "notebook install magic",
+        }
+        added.append(entry["name"])
+    return added
+
+
+def collect_tooling_dependencies(script_files, external_packages, secure_file_ops, logger, repo_path):
+    """
+    Scan script files and aggregate imperatively installed tooling dependencies
+
+    Tools invoked by Makefile, justfile and Taskfile recipes are reported with ecosystem 'tool',
+    scope 'tooling' and their `invocations` in place of `install_commands`, as are the npm packages
+    whose binaries package.json scripts run (ecosystem 'npm'); pre-commit hook
+    repositories (ecosystem 'pre-commit') and Dev Container images and features (ecosystems
+    'container-image' and 'devcontainer-feature') with scope 'tooling', `declarations` and, when
+    known, `repository_url`
+
+    Args:
+        script_files (list): Absolute paths of shell scripts, task-runner files, pre-commit configs,
+            devcontainer.json files, package.json files and notebooks
+        external_packages (dict): Declared package metadata keyed by distribution name
+        secure_file_ops (SecureFileOps|None): Secure file operations or None
+        logger (Logger|None): Optional logger
+        repo_path (str): Absolute repository path
+
+    Returns:
+        list: Dicts with keys: name, ecosystem, version, declared_in_manifest, install_commands (or
+            scope and invocations / declarations for tools and pre-commit repositories)
+    """
+    aggregated = {}
+    for script_path in sorted(script_files):
+        if Path(script_path).name in TOOLCHAIN_FILE_NAMES:
+            continue
+        try:
+            content = read_repo_file(script_path, secure_file_ops)
+        except Exception as exc:
+            if logger:
+                logger.warning(f"Could not read script {script_path}: {exc}")
+            continue
+        try:
+            if secure_file_ops:
+                rel_path = secure_file_ops.get_relative_path(script_path)
+            else:
+                rel_path = str(Path(script_path).relative_to(repo_path))
+        except ValueError:
+            rel_path = script_path
+        basename = Path(script_path).name
+        if script_path.endswith(".ipynb"):
+            findings = extract_notebook_install_commands(content, rel_path)
+        elif basename in PRE_COMMIT_CONFIG_NAMES:
+            findings = extract_pre_commit_repos(content, rel_path)
+        elif basename in DEVCONTAINER_FILE_NAMES:
+            findings = extract_devcontainer_dependencies(content, rel_path)
+        elif basename == PACKAGE_JSON_NAME:
+            findings = extract_package_scripts(content, rel_path)
+        elif basename in TASK_RUNNER_FILE_NAMES:
+            command_lines = task_runner_lines(content, basename)
+            findings = extract_install_commands(
+                content, rel_path, is_makefile=basename in SCRIPT_FILE_NAMES, command_lines=command_lines
+            )
+            findings += extract_tool_invocations(command_lines, rel_path)
+        else:
+            findings = extract_install_commands(content, rel_path)
+        for finding in findings:
+            key = f"{finding['ecosystem']}:{finding['name']}"
+            tooling = finding["ecosystem"] in OCCURRENCES_KEY or finding.get("scope") == TOOLING_SCOPE
+            # npm binaries run by package.json scripts are invocations of an installed package
+            commands_key = OCCURRENCES_KEY.get(finding["ecosystem"], "install_commands")
+            if "script" in finding:
+                commands_key = "invocations"
+            entry = aggregated.get(key)
+            if entry is None:
+                declared = external_packages.get(finding["name"], {})
+                entry = aggregated[key] = {
+                    "name": finding["name"],
+                    "ecosystem": finding["ecosystem"],
+                    "version": finding["version"],
+                    "declared_in_manifest": declared.get("ecosystem") == finding["ecosystem"],
+                }
+                if "repository_url" in finding:
+                    entry["repository_url"] = finding["repository_url"]
+            elif finding["version"] and not entry["version"]:
+                entry["version"] = finding["version"]
+            if tooling:
+                entry["scope"] = TOOLING_SCOPE
+            command = {"file": finding["file"], "line": finding["line"]}
+            entry.setdefault(commands_key, [])
+            for detail in ("command", "hooks", "cell", "script", "bin", "runner"):
+                if detail in finding:
+                    command[detail] = finding[detail]
+            entry[commands_key].append(command)
+
+    if logger and aggregated:
+        logger.info(
+            f"... Found {len(aggregated)} tooling dependencies in scripts, task runners, hooks, dev containers "
+            "and notebooks"
+        )
+    return [aggregated[key] for key in sorted(aggregated)]
@@ -10,12 +10,41 @@ import os
 from collections import defaultdict
 from pathlib import Path
 
+from gardener.analysis import build_scripts
+from gardener.analysis import cargo_dep_kinds
+from gardener.analysis import cargo_metadata
+from gardener.analysis import cargo_packages
+from gardener.analysis import cargo_patches
+from gardener.analysis import cargo_proc_macros
+from gardener.analysis import cargo_targets
+from gardener.analysis import cargo_undeclared
+from gardener.analysis import cargo_unused
+from gardener.analysis import cargo_vendor
+from gardener.analysis import cargo_workspace
+from gardener.analysis import go_workspace
 from gardener.analysis import imports as imports_mod
+from gardener.analysis import js_bundler_aliases
+from gardener.analysis import js_module_system
+from gardener.analysis import js_reexports
 from gardener.analysis import js_ts_aliases
+from gardener.analysis import js_workspace
 from gardener.analysis import manifests
+from gardener.analysis import npm_overrides
+from gardener.analysis import npm_undeclared
+from gardener.analysis import panics as panics_mod
+from gardener.analysis import python_extras
+from gardener.analysis import python_projects
+from gardener.analysis import qualified_paths
+from gardener.analysis import rust_reexports
 from gardener.analysis import scanner
 from gardener.analysis import solidity_meta
+from gardener.analysis import toolchains
+from gardener.analysis import tooling
+from gardener.analysis import ts_projects
+from gardener.analysis import yarn_pnp
+from gardener.treewalk.javascript import DYNAMIC_UNRESOLVED, JavaScriptLanguageHandler
 from gardener.treewalk.solidity import SolidityLanguageHandler
+from gardener.common.defaults import GraphAnalysisConfig as cfg
 from gardener.common.secure_file_ops import FileOperationError, SecureFileOps
 
 TimeoutError = imports_mod.TimeoutError
@@ -29,7 +58,7 @@ class RepositoryAnalyzer:
     Coordinates the analysis of repository source code to extract dependencies
     """
 
-    def __init__(self, repo_path, focus_languages=None, logger=None):
+    def __init__(self, repo_path, focus_languages=None, logger=None, cancellation=None, timings=None):
         """
         Initialize a new analyzer instance
 
@@ -37,6 +66,8 @@ class RepositoryAnalyzer:
             repo_path (str): Absolute path to the repository to analyze
             focus_languages (list|None): Optional list of languages to focus on
             logger (Logger|None): Optional logger instance
+            cancellation (Cancellation|None): Optional flag that stops import extraction early
+            timings (PipelineTimings|None): Optional recorder of the import extraction stages
 
         Returns:
             None
@@ -44,6 +75,8 @@ class RepositoryAnalyzer:
         self.repo_path = repo_path
         self.logger = logger
         self.focus_languages = focus_languages
+        self.cancellation = cancellation
+        self.timings = timings
 
         try:
             self.secure_file_ops = SecureFileOps(repo_path, logger)
@@ -61,6 +94,7 @@ class RepositoryAnalyzer:
         self.file_imports = defaultdict(list)
         self.file_package_components = defaultdict(list)
         self.local_imports_map = defaultdict(list)
+        self.file_import_annotations = {}
         self.root_package_names = set()
         self.go_module_path = None
         self.hardhat_remappings = {}
@@ -68,10 +102,35 @@ class RepositoryAnalyzer:
         self.solidity_src_path = None
         self.js_config_files = []
         self.ts_config_files = []
+        self.script_files = []
+        self.tooling_dependencies = []
+        self.toolchains = []
+        self.workspace_members = {}
         self.js_ts_base_url = None
         self.js_ts_path_aliases = {}
+        self.bundler_aliases = []
         self.alias_resolver = None
         self.submodule_data = {}
+        self.import_disambiguations = []
+        self.idl_package_hints = {}
+        self.panics = panics_mod.PanicLog()
+        self.manifest_diagnostics = []
+        self.build_scripts = []
+        self.cargo_vendor = None
+        self.cargo_manifests = {}
+        self.cargo_metadata = None
+        self.unused_declared = []
+        self.used_undeclared = []
+        self.reexport_chains = []
+        self.rust_targets = []
+        self.file_target_kinds = {}
+        self.rust_packages = []
+        self.ts_projects = []
+        self.python_extras = []
+        self.yarn_pnp = None
+        self.dynamic_imports = []
+        self.module_systems = {}
+        self.skipped_files = []
 
         self.language_handlers = {}
         self._local_resolver = None
@@ -143,11 +202,20 @@ class RepositoryAnalyzer:
         self.root_manifest_files = result["root_manifest_files"]
         self.js_config_files = result["js_config_files"]
         self.ts_config_files = result["ts_config_files"]
+        self.script_files = result["script_files"]
         self.solidity_src_path = result["solidity_src_path"]
         self.submodule_data = result["submodule_data"]
         self.gitignore_spec = result["gitignore_spec"]
         self._local_resolver = None
 
+        self.cargo_vendor = cargo_vendor.read_vendor_config(self.secure_file_ops, self.logger)
+        if self.cargo_vendor:
+            dropped = cargo_vendor.exclude_vendored_files(
+                self.cargo_vendor, self.source_files, self.manifest_files, self.secure_file_ops
+            )
+            if self.logger:
+                self.logger.debug(f"Skipped {dropped} vendored Cargo source files in {self.cargo_vendor['directory']}")
+
         if self.logger:
             self.logger.info(
                 f"... Found {len(self.source_files)} source files, "
@@ -168,7 +236,14 @@ class RepositoryAnalyzer:
         Returns:
             dict: External package metadata keyed by distribution name
         """
-        self.remappings = solidity_meta.parse_remappings_txt(self.secure_file_ops, self.logger)
+        foundry_profile = solidity_meta.load_foundry_profile(self.secure_file_ops, self.logger)
+        foundry_remappings = solidity_meta.parse_foundry_remappings(foundry_profile, self.logger)
+        remappings_txt = solidity_meta.parse_remappings_txt(self.secure_file_ops, self.logger)
+        explicit_remappings = {**foundry_remappings, **remappings_txt}
+        lib_remappings = solidity_meta.infer_lib_remappings(
+            self.secure_file_ops, foundry_profile, self.submodule_data, explicit_remappings, self.logger
+        )
+        self.remappings = {**lib_remappings, **explicit_remappings}
         self.hardhat_remappings = solidity_meta.get_hardhat_remappings(self.repo_path, self.logger)
 
         roots, go_module = manifests.collect_root_package_names_and_workspaces(
@@ -182,15 +257,93 @@ class RepositoryAnalyzer:
             self.go_module_path = go_module
 
         self.external_packages = manifests.process_manifests(
-            self.manifest_files, self.language_handlers, self.secure_file_ops, self.logger
+            self.manifest_files,
+            self.language_handlers,
+            self.secure_file_ops,
+            self.logger,
+            panics=self.panics,
+            diagnostics=self.manifest_diagnostics,
+            strict=cfg.STRICT_MANIFESTS,
+        )
+
+        cargo_manifests = cargo_workspace.read_cargo_manifests(
+            self.manifest_files, self.secure_file_ops, self.logger, self.repo_path
+        )
+        self.workspace_members = cargo_workspace.collect_workspace_members(
+            self.manifest_files,
+            self.secure_file_ops,
+            self.logger,
+            self.repo_path,
+            self.source_files,
+            crates=cargo_manifests,
+        )
+        for name in self.workspace_members:
+            if (self.external_packages.get(name) or {}).get("ecosystem") == "cargo":
+                del self.external_packages[name]
+        js_members = js_workspace.collect_js_workspace_members(
+            self.manifest_files, self.secure_file_ops, self.logger, self.repo_path
         )
+        for name, member in js_members.items():
+            if (self.external_packages.get(name) or {}).get("ecosystem") == "npm":
+                del self.external_packages[name]
+            self.workspace_members.setdefault(name, member)
+        python_members = python_projects.collect_python_projects(
+            self.manifest_files, self.source_files, self.secure_file_ops, self.logger, self.repo_path
+        )
+        python_projects.drop_internal_packages(self.external_packages, python_members, self.logger)
+        for name, member in python_members.items():
+            if self.workspace_members.get(name, member).get("ecosystem") != "pypi":
+                # A pyo3/maturin project usually shares its Cargo crate's name; both stay members
+                name = f"pypi:{name}"
+            self.workspace_members.setdefault(name, member)
+        go_members = go_workspace.collect_go_modules(
+            self.manifest_files, self.secure_file_ops, self.logger, self.repo_path
+        )
+        go_workspace.drop_internal_packages(self.external_packages, go_members, self.logger)
+        for name, member in go_members.items():
+            self.workspace_members.setdefault(name, member)
+        vendored_crates = {}
+        if self.cargo_vendor:
+            vendored_crates = cargo_vendor.read_vendored_crates(self.cargo_vendor, self.secure_file_ops, self.logger)
+            self.cargo_vendor = cargo_vendor.tag_vendored_crates(
+                self.external_packages, self.cargo_vendor, vendored_crates, self.logger
+            )
+        cargo_proc_macros.tag_local_proc_macros(
+            self.external_packages, self.workspace_members, {**cargo_manifests, **vendored_crates}, self.logger
+        )
+        cargo_patches.apply_cargo_patches(self.external_packages, cargo_manifests, self.logger)
+        npm_overrides.apply_npm_overrides(self.external_packages, self.repo_path, self.secure_file_ops, self.logger)
+        self.yarn_pnp = yarn_pnp.apply_yarn_pnp(
+            self.external_packages, self.repo_path, self.secure_file_ops, self.logger
+        )
+        if cfg.USE_CARGO_METADATA and cargo_manifests:
+            self.cargo_metadata = cargo_metadata.merge_cargo_metadata(
+                self.external_packages,
+                self.workspace_members,
+                cargo_metadata.run_cargo_metadata(self.repo_path, cargo_manifests, self.logger),
+                self.repo_path,
+                self.logger,
+            )
+        self.cargo_manifests = cargo_manifests
 
         if self.logger:
             self.logger.info(f"... Found {len(self.external_packages)} unique external packages")
 
+        self.tooling_dependencies = tooling.collect_tooling_dependencies(
+            self.script_files, self.external_packages, self.secure_file_ops, self.logger, self.repo_path
+        )
+        for name in tooling.declare_notebook_installs(self.external_packages, self.tooling_dependencies):
+            if self.logger:
+                self.logger.debug(f"Declared '{name}' from notebook install magics")
+        self.toolchains = toolchains.collect_toolchains(
+            self.script_files + self.manifest_files, self.secure_file_ops, self.logger, self.repo_path
+        )
+
         sol_handler = SolidityLanguageHandler()
         for remap_dict, source_name in [
-            (self.remappings, "remappings.txt"),
+            (remappings_txt, "remappings.txt"),
+            (foundry_remappings, "foundry.toml"),
+            (lib_remappings, "lib/ directory"),
             (self.hardhat_remappings, "hardhat config"),
         ]:
             self._solidity_candidates_from_remappings(remap_dict, source_name, sol_handler)
@@ -211,6 +364,10 @@ class RepositoryAnalyzer:
         self.alias_resolver = js_ts_aliases.create_alias_resolver(
             self.repo_path, self.source_files, self.js_ts_base_url, self.js_ts_path_aliases, self.logger
         )
+        self.bundler_aliases = js_bundler_aliases.collect_bundler_aliases(
+            self.repo_path, self.source_files, self.secure_file_ops, self.logger
+        )
+        js_bundler_aliases.apply_bundler_aliases(self.alias_resolver.config, self.bundler_aliases)
 
         self.external_packages = solidity_meta.associate_submodules_with_solidity_packages(
             self.external_packages,
@@ -274,10 +431,14 @@ class RepositoryAnalyzer:
                         f"  Remapped package '{package_name}' (from {source_name}: '{prefix}' -> '{path}') already identified from {self.external_packages[package_name]['source']}"  # noqa
                     )
 
-    def extract_imports_from_all_files(self):
+    def extract_imports_from_all_files(self, on_file_imports=None):
         """
         Extract imports and components from all source files
 
+        Args:
+            on_file_imports (callable|None): Optional callback run with (rel_path, external_imports) as
+                each file is parsed, before the repository-wide passes over the results
+
         Returns:
             None
         """
@@ -295,20 +456,253 @@ class RepositoryAnalyzer:
             hardhat_remappings=self.hardhat_remappings,
             solidity_src_path=self.solidity_src_path,
             logger=self.logger,
+            external_packages=self.external_packages,
+            rust_workspace_crates=self._rust_workspace_crates(),
+            rust_crate_roots=self._rust_crate_roots(),
+            rust_crate_editions=self._rust_crate_editions(),
+            js_workspace_packages=self._js_workspace_packages(),
+            python_source_roots=self._python_source_roots(),
+            go_module_roots=self._go_module_roots(),
+            secure_file_ops=self.secure_file_ops,
         )
 
-        file_imports, local_imports_map, file_package_components = imports_mod.extract_imports(
+        (
+            file_imports,
+            local_imports_map,
+            file_package_components,
+            file_import_annotations,
+        ) = imports_mod.extract_imports(
             self.source_files,
             self.language_handlers,
             self.repo_path,
             self.secure_file_ops,
             self._local_resolver,
             self.logger,
+            cancellation=self.cancellation,
+            panics=self.panics,
+            skipped=self.skipped_files,
+            timings=self.timings,
+            on_file_imports=on_file_imports,
         )
+        python_projects.internalize_imports(file_imports, self.source_files, self.workspace_members)
+        go_workspace.internalize_imports(file_imports, local_imports_map, self.source_files, self.workspace_members)
 
+        qualified_paths.filter_qualified_path_imports(
+            self.external_packages, file_imports, file_package_components, file_import_annotations, self.logger
+        )
+        self.reexport_chains = rust_reexports.attribute_reexports(
+            self.source_files,
+            self.external_packages,
+            file_imports,
+            local_imports_map,
+            file_package_components,
+            file_import_annotations,
+            self._local_resolver.resolve_rust,
+            self.secure_file_ops,
+            self.logger,
+        )
+        self.reexport_chains += js_reexports.attribute_reexports(
+            self.source_files,
+            file_imports,
+            local_imports_map,
+            file_package_components,
+            file_import_annotations,
+            self._local_resolver.resolve_js,
+            JavaScriptLanguageHandler().normalize_package_name,
+            self.secure_file_ops,
+            self.logger,
+        )
         self.file_imports = file_imports
         self.local_imports_map = local_imports_map
         self.file_package_components = file_package_components
+        self.file_import_annotations = file_import_annotations
+        self.import_disambiguations = self._local_resolver.disambiguations
+        self.dynamic_imports = self._dynamic_imports()
+        self._register_cdn_packages()
+        self.idl_package_hints = self._local_resolver.idl_package_hints()
+        scripts = build_scripts.collect_build_scripts(self.source_files, self.secure_file_ops, self.logger)
+        build_files = {path for script in scripts.values() for path in script["includes"]}
+        cargo_dep_kinds.cross_check_dep_kinds(
+            self.external_packages,
+            file_imports,
+            self.source_files,
+            self.logger,
+            build_files=build_files,
+            file_import_annotations=file_import_annotations,
+        )
+        self.python_extras = python_extras.scope_extras(
+            self.external_packages, file_imports, self.source_files, self.logger
+        )
+        self.rust_targets, self.file_target_kinds = cargo_targets.classify_targets(
+            self.external_packages, self.cargo_manifests, file_imports, self.source_files, self.logger
+        )
+        self.rust_packages = cargo_packages.group_by_package(
+            cargo_targets.crate_target_table(self.cargo_manifests, self.source_files),
+            self.external_packages,
+            self.workspace_members,
+            file_imports,
+            local_imports_map,
+        )
+        self.ts_projects = ts_projects.group_by_project(
+            ts_projects.read_ts_projects(self.repo_path, self.ts_config_files, self.secure_file_ops, self.logger),
+            self.source_files,
+            self.external_packages,
+            file_imports,
+            local_imports_map,
+        )
+        self.build_scripts = build_scripts.annotate_build_scripts(
+            scripts, self.external_packages, file_imports, file_import_annotations
+        )
+        self.unused_declared = cargo_unused.find_unused_dependencies(
+            self.external_packages,
+            self.cargo_manifests,
+            self.workspace_members,
+            file_imports,
+            self.source_files,
+            self.logger,
+        )
+        self.used_undeclared = cargo_undeclared.find_undeclared_imports(
+            self.cargo_manifests,
+            self.workspace_members,
+            file_imports,
+            file_package_components,
+            self.source_files,
+            self.logger,
+        )
+        self.used_undeclared += npm_undeclared.find_phantom_imports(
+            self.manifest_files,
+            self.external_packages,
+            self.workspace_members,
+            file_imports,
+            self.source_files,
+            file_import_annotations,
+            self.secure_file_ops,
+            self.logger,
+            self.repo_path,
+        )
+        handlers = {id(handler): handler for handler in self.language_handlers.values()}
+        self.module_systems = js_module_system.classify_module_systems(
+            self.source_files,
+            {
+                rel_path: record
+                for handler in handlers.values()
+                for rel_path, record in (getattr(handler, "module_systems", None) or {}).items()
+            },
+            self.external_packages,
+            self.manifest_files,
+            self.secure_file_ops,
+            self.logger,
+            self.repo_path,
+        )
+
+    def _dynamic_imports(self):
+        """
+        Collect the import()/require() and import_module()/__import__() calls whose argument names no package or file
+
+        Returns:
+            list: {'file', 'kind', 'expression', 'line', 'status'[, 'prefix']} sorted by file and line
+        """
+        records = []
+        handlers = {id(handler): handler for handler in self.language_handlers.values()}
+        for handler in handlers.values():
+            for rel_path, calls in (getattr(handler, "dynamic_unresolved", None) or {}).items():
+                records.extend({"file": rel_path, **call, "status": DYNAMIC_UNRESOLVED} for call in calls)
+        return sorted(records, key=lambda record: (record["file"], record["line"]))
+
+    def _register_cdn_packages(self):
+        """
+        Add the packages HTML pages load from a CDN and no manifest declares
+
+        Returns:
+            None
+        """
+        handlers = {id(handler): handler for handler in self.language_handlers.values()}
+        for handler in handlers.values():
+            for name, record in (getattr(handler, "cdn_packages", None) or {}).items():
+                if name in self.external_packages:
+                    continue
+                self.external_packages[name] = {
+                    "ecosystem": record["ecosystem"],
+                    "version": record["version"] or "",
+                    "import_names": [name],
+                    "source": "cdn",
+                    "cdn_urls": sorted(record["urls"]),
+                }
+
+    def _rust_workspace_crates(self):
+        """
+        Map the import names of Cargo workspace members to their crate root files
+
+        Returns:
+            dict: Import name -> repo-relative crate root (lib.rs or main.rs)
+        """
+        return {
+            import_name: member["root"]
+            for member in self.workspace_members.values()
+            if member.get("ecosystem", "cargo") == "cargo" and member["root"]
+            for import_name in member["import_names"]
+        }
+
+    def _js_workspace_packages(self):
+        """
+        Map npm/Yarn/pnpm workspace package names to their directories
+
+        Returns:
+            dict: Package name -> repo-relative package directory
+        """
+        return {
+            name: member["path"]
+            for name, member in self.workspace_members.items()
+            if member.get("ecosystem") == "npm"
+        }
+
+    def _python_source_roots(self):
+        """
+        List the source roots of the Python projects the repository builds (src/ of src layouts)
+
+        Returns:
+            list: Repo-relative directories, the repository root left out
+        """
+        return sorted(
+            {
+                root
+                for member in self.workspace_members.values()
+                if member.get("ecosystem") == "pypi"
+                for root in member.get("source_roots") or []
+                if root != "."
+            }
+        )
+
+    def _go_module_roots(self):
+        """
+        Map the module paths of the repository's Go modules to their directories
+
+        Returns:
+            dict: Module path -> repo-relative directory
+        """
+        return go_workspace.module_roots(self.workspace_members)
+
+    def _rust_crate_roots(self):
+        """
+        List the root files of the compile targets of every Cargo package, in or out of a workspace
+
+        Returns:
+            list: Repo-relative target roots (src/lib.rs, src/main.rs, src/bin/*.rs, examples/*.rs ...)
+        """
+        return [
+            target["path"]
+            for crate in cargo_targets.crate_target_table(self.cargo_manifests, self.source_files).values()
+            for target in crate["targets"]
+        ]
+
+    def _rust_crate_editions(self):
+        """
+        Map the directories of the repository's Cargo packages to their Rust editions
+
+        Returns:
+            dict: Crate directory -> edition
+        """
+        return cargo_targets.crate_editions(self.cargo_manifests)
 
     def _get_local_resolver(self):
         """
@@ -329,6 +723,14 @@ class RepositoryAnalyzer:
                 hardhat_remappings=self.hardhat_remappings,
                 solidity_src_path=self.solidity_src_path,
                 logger=self.logger,
+                external_packages=self.external_packages,
+                rust_workspace_crates=self._rust_workspace_crates(),
+                rust_crate_roots=self._rust_crate_roots(),
+                rust_crate_editions=self._rust_crate_editions(),
+                js_workspace_packages=self._js_workspace_packages(),
+                python_source_roots=self._python_source_roots(),
+                go_module_roots=self._go_module_roots(),
+                secure_file_ops=self.secure_file_ops,
             )
         return self._local_resolver
 
@@ -359,18 +761,19 @@ class RepositoryAnalyzer:
         """
         return self._get_local_resolver().resolve_js(importing_file_rel_path, module_str)
 
-    def _resolve_local_import_rust(self, importing_file_rel_path, use_path_parts):
+    def _resolve_local_import_rust(self, importing_file_rel_path, use_path_parts, module_path=None):
         """
         Wrapper for Rust local module resolution
 
         Args:
             importing_file_rel_path (str): Importing file path
             use_path_parts (list): `use` path components
+            module_path (str|None): `#[path = "..."]` value of a `mod` declaration
 
         Returns:
             str|None: Resolved path or None
         """
-        return self._get_local_resolver().resolve_rust(importing_file_rel_path, use_path_parts)
+        return self._get_local_resolver().resolve_rust(importing_file_rel_path, use_path_parts, module_path)
 
     def _resolve_local_import_go(self, importing_file_rel_path, module_str):
         """
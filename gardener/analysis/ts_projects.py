@@ -0,0 +1,184 @@
+"""
+TypeScript project references
+
+Large TypeScript monorepos split their sources into projects, each with its own tsconfig, and tie
+them together with `references` (`{"path": "../core"}`, naming a directory holding a tsconfig.json
+or a config file such as tsconfig.lib.json), often from a solution-style root config with
+`"files": []`. When any tsconfig has references, every config reachable from the tsconfig.json
+files of the repository is read: a project owns the TS files (and JS files with `allowJs`) its
+`files` and `include` select (`**/*` by default, `exclude` and node_modules left out), the
+innermost project claiming a file when several do. The evidence is grouped per project under
+`analyzer_details.ts_projects`: its `project` config, `path`, whether it is `composite`, the
+projects it `references` (and `missing_references` that do not exist), the packages it imports with
+the files importing each, and the projects its files reach through local imports (`depends_on`)
+"""
+
+import os
+import posixpath
+from pathlib import PurePosixPath
+
+from gardener.analysis.js_ts_aliases import _parse_jsonc
+from gardener.analysis.js_workspace import _glob_matches
+from gardener.common.file_helpers import read_repo_file
+from gardener.common.repo_paths import normalize_dir
+
+DEFAULT_INCLUDE = ["**/*"]
+DEFAULT_EXCLUDE = ["node_modules", "bower_components", "jspm_packages"]
+
+
+def _reference_config(config_rel, reference):
+    """
+    Return the repo-relative config a `references` entry names, or None when it leaves the repository
+    """
+    path = reference.get("path") if isinstance(reference, dict) else None
+    if not isinstance(path, str):
+        return None
+    target = normalize_dir(posixpath.join(posixpath.dirname(config_rel) or ".", path))
+    if target is None:
+        return None
+    if target.endswith(".json"):
+        return target
+    return "tsconfig.json" if target == "." else f"{target}/tsconfig.json"
+
+
+def read_ts_projects(repo_path, ts_config_files, secure_file_ops=None, logger=None):
+    """
+    Read the tsconfig files of the repository and the configs their `references` name
+
+    Args:
+        repo_path (str): Absolute repository path
+        ts_config_files (list): Absolute paths to discovered tsconfig.json files
+        secure_file_ops (SecureFileOps|None): Secure file operations or None
+        logger (Logger|None): Optional logger
+
+    Returns:
+        dict: Repo-relative config path -> {'path', 'composite', 'references', 'missing_references',
+            'files', 'include', 'exclude', 'allow_js'}; empty when no config has references
+    """
+    pending = []
+    for path in ts_config_files:
+        rel_path = secure_file_ops.get_relative_path(path) if secure_file_ops else os.path.relpath(path, repo_path)
+        if "node_modules" not in PurePosixPath(rel_path).parts:
+            pending.append(PurePosixPath(rel_path).as_posix())
+    projects, missing = {}, set()
+    while pending:
+        config_rel = pending.pop(0)
+        if config_rel in projects or config_rel in missing:
+            continue
+        try:
+            data = _parse_jsonc(read_repo_file(os.path.join(repo_path, config_rel), secure_file_ops))
+        except Exception as exc:
+            if logger:
+                logger.debug(f"Could not read TypeScript project {config_rel}: {exc}")
+            missing.add(config_rel)
+            continue
+        data = data if isinstance(data, dict) else {}
+        options = data.get("compilerOptions") if isinstance(data.get("compilerOptions"), dict) else {}
+        references = [_reference_config(config_rel, entry) for entry in data.get("references") or []]
+        references = [reference for reference in references if reference]
+        files = data.get("files")
+        projects[config_rel] = {
+            "path": posixpath.dirname(config_rel) or ".",
+            "composite": bool(options.get("composite")),
+            "references": references,
+            "files": [entry for entry in files if isinstance(entry, str)] if isinstance(files, list) else None,
+            "include": data.get("include") if isinstance(data.get("include"), list) else None,
+            "exclude": data.get("exclude") if isinstance(data.get("exclude"), list) else None,
+            "allow_js": bool(options.get("allowJs")),
+        }
+        pending.extend(references)
+    if not any(project["references"] for project in projects.values()):
+        return {}
+    for project in projects.values():
+        project["missing_references"] = [reference for reference in project["references"] if reference in missing]
+        project["references"] = [reference for reference in project["references"] if reference in projects]
+    if logger:
+        logger.info(f"... Found {len(projects)} TypeScript projects linked by project references")
+    return projects
+
+
+def _matches(rel_path, patterns):
+    """
+    Whether a path relative to a project matches tsconfig include/exclude patterns
+    """
+    parts = PurePosixPath(rel_path).parts
+    for pattern in patterns:
+        if not isinstance(pattern, str):
+            continue
+        pattern = PurePosixPath(pattern.strip()).as_posix()
+        if pattern == "." or rel_path == pattern or rel_path.startswith(f"{pattern}/"):
+            return True
+        if _glob_matches(parts, PurePosixPath(pattern).parts):
+            return True
+    return False
+
+
+def _owns(project, rel_path, language):
+    """
+    Whether a project's files, include and exclude select a file
+    """
+    if language != "typescript" and not project["allow_js"]:
+        return False
+    if project["path"] != "." and not rel_path.startswith(f"{project['path']}/"):
+        return False
+    inner = rel_path if project["path"] == "." else rel_path[len(project["path"]) + 1 :]
+    if any(PurePosixPath(entry).as_posix() == inner for entry in project["files"] or []):
+        return True
+    # A config listing `files` (a solution config's `"files": []`) includes nothing else by default
+    include = project["include"]
+    if include is None:
+        include = [] if project["files"] is not None else DEFAULT_INCLUDE
+    exclude = project["exclude"] if project["exclude"] is not None else DEFAULT_EXCLUDE
+    return _matches(inner, include) and not _matches(inner, exclude) and "node_modules" not in inner.split("/")
+
+
+def group_by_project(projects, source_files, external_packages, file_imports, local_imports_map):
+    """
+    Attribute JS/TS files to TypeScript projects and group their import evidence per project
+
+    Args:
+        projects (dict): Result of read_ts_projects
+        source_files (dict): Relative file path -> file info with 'language'
+        external_packages (dict): Package metadata keyed by name
+        file_imports (dict): Relative file path -> list of import names
+        local_imports_map (dict): Relative file path -> local files it imports
+
+    Returns:
+        list: {'project', 'path', 'composite', 'references', 'missing_references', 'files', 'imports',
+            'depends_on'} per project, sorted by config path
+    """
+    # Innermost project first, so nested projects claim their own files
+    order = sorted(projects, key=lambda config: (-len(PurePosixPath(projects[config]["path"]).parts), config))
+    owners = {}
+    for rel_path, info in sorted(source_files.items()):
+        language = info.get("language")
+        if language not in ("javascript", "typescript"):
+            continue
+        owner = next((config for config in order if _owns(projects[config], rel_path, language)), None)
+        if owner is not None:
+            owners[rel_path] = owner
+
+    reports = []
+    for config, project in sorted(projects.items()):
+        files = [rel_path for rel_path, owner in owners.items() if owner == config]
+        imports, depends_on = {}, set()
+        for rel_path in files:
+            for name in file_imports.get(rel_path) or []:
+                if name in external_packages and rel_path not in imports.setdefault(name, []):
+                    imports[name].append(rel_path)
+            for target in local_imports_map.get(rel_path) or []:
+                if owners.get(target) not in (None, config):
+                    depends_on.add(owners[target])
+        reports.append(
+            {
+                "project": config,
+                "path": project["path"],
+                "composite": project["composite"],
+                "references": sorted(project["references"]),
+                "missing_references": sorted(project["missing_references"]),
+                "files": len(files),
+                "imports": {name: sorted(paths) for name, paths in sorted(imports.items())},
+                "depends_on": sorted(depends_on),
+            }
+        )
+    return reports
@@ -0,0 +1,188 @@
+"""
+Yarn Plug'n'Play and zero-install resolution
+
+Yarn 2+ repositories using Plug'n'Play have no node_modules: `.pnp.cjs` (or `.pnp.data.json` next
+to it) maps every workspace to the exact package references it may load, and zero-install
+repositories commit the package archives themselves under `.yarn/cache`. The runtime state is read
+(from `.pnp.data.json`, or the `RAW_RUNTIME_STATE` string embedded in `.pnp.cjs`) to give npm
+packages the version each workspace resolves (`pnp_resolved`, workspace path -> version, with
+`locked_version` set when they all agree), to add packages only installed for others
+(`transitive: true`), and to attribute imports of aliased dependencies (`"foo": "npm:bar@1.0.0"`)
+to the real package, which lists the alias under `import_names`. Packages with an archive in
+`.yarn/cache` get its path as `yarn_cache`
+"""
+
+import json
+import os
+import posixpath
+import re
+from urllib.parse import unquote
+
+from gardener.common.file_helpers import read_repo_file
+from gardener.common.repo_paths import normalize_dir
+
+PNP_DATA_FILE = ".pnp.data.json"
+PNP_RUNTIME_FILE = ".pnp.cjs"
+YARN_CACHE_DIR = ".yarn/cache"
+RAW_RUNTIME_STATE_RE = re.compile(r"RAW_RUNTIME_STATE\s*=\s*'((?:[^'\\]|\\.)*)'", re.S)
+JS_ESCAPE_RE = re.compile(r"\\(.)", re.S)
+# The version in 'npm:4.17.21', 'virtual:<hash>#npm:1.0.0' and decoded 'patch:lodash@npm:4.17.21#...' references
+REFERENCE_VERSION_RE = re.compile(r"npm:(\d[^#&:\s]*)")
+# '<slug>-npm-<version>-<hash>[-<checksum>].zip', the slug of '@scope/name' being '@scope-name'
+CACHE_ARCHIVE_RE = re.compile(r"^(?P<slug>.+?)-npm-(?P<version>\d.*?)-[0-9a-f]{10}(?:-[0-9a-f]+)?\.zip$")
+
+
+def parse_pnp_state(content, basename):
+    """
+    Parse the runtime state of a `.pnp.data.json` or `.pnp.cjs`
+
+    Args:
+        content (str): File content
+        basename (str): File name, which selects the format
+
+    Returns:
+        dict: Runtime state, {} when `.pnp.cjs` embeds none (Yarn 2 `.pnp.js` style setups)
+    """
+    if basename != PNP_RUNTIME_FILE:
+        return json.loads(content)
+    match = RAW_RUNTIME_STATE_RE.search(content)
+    if not match:
+        return {}
+    # Undo the JavaScript string escaping: line continuations, \' and \\
+    return json.loads(JS_ESCAPE_RE.sub(lambda escape: escape.group(1).strip("\n"), match.group(1)))
+
+
+def _reference_version(reference):
+    found = REFERENCE_VERSION_RE.findall(unquote(reference))
+    return found[-1] if found else None
+
+
+def pnp_resolutions(state):
+    """
+    Collect the packages each workspace resolves and every package installed
+
+    Args:
+        state (dict): Runtime state from parse_pnp_state
+
+    Returns:
+        tuple: ({name: {workspace path: version}}, {name: set of versions installed}, {alias: package name})
+    """
+    resolved, installed, aliases = {}, {}, {}
+    for name, references in state.get("packageRegistryData") or []:
+        for reference, info in references or []:
+            info = info or {}
+            if name and reference and not reference.startswith("workspace:"):
+                version = _reference_version(reference)
+                if version:
+                    installed.setdefault(name, set()).add(version)
+                continue
+            location = normalize_dir(str(info.get("packageLocation") or "."))
+            for dependency, target in info.get("packageDependencies") or []:
+                if isinstance(target, list):
+                    aliases[dependency] = target[0]
+                    dependency, target = target
+                if not isinstance(target, str) or target.startswith("workspace:"):
+                    continue
+                version = _reference_version(target)
+                if version and location is not None:
+                    resolved.setdefault(dependency, {})[location] = version
+    return resolved, installed, aliases
+
+
+def _cache_archives(repo_path, secure_file_ops):
+    """
+    List the archive names of `.yarn/cache`
+    """
+    cache_dir = os.path.join(repo_path, YARN_CACHE_DIR)
+    try:
+        if secure_file_ops:
+            return sorted(entry.name for entry in secure_file_ops.list_dir(cache_dir))
+        return sorted(os.listdir(cache_dir))
+    except Exception:
+        return []
+
+
+def _apply_alias(external_packages, alias, package):
+    """
+    Move an aliased dependency's entry to the package it installs, keeping the alias as an import name
+    """
+    entry = external_packages.pop(alias, None)
+    if entry is None:
+        return
+    target = external_packages.get(package)
+    if target is None:
+        external_packages[package] = {**entry, "import_names": [alias]}
+        return
+    names = target.get("import_names") or [package]
+    target["import_names"] = names + [alias] if alias not in names else names
+
+
+def apply_yarn_pnp(external_packages, repo_path, secure_file_ops=None, logger=None):
+    """
+    Apply Plug'n'Play resolutions and zero-install archives to npm package entries
+
+    Args:
+        external_packages (dict): Package metadata keyed by name, updated in place
+        repo_path (str): Absolute repository path
+        secure_file_ops (SecureFileOps|None): Secure file operations or None
+        logger (Logger|None): Optional logger
+
+    Returns:
+        dict|None: {'state', 'workspaces', 'aliases', 'cached'} summary, or None without PnP or a cache
+    """
+    state, state_file = {}, None
+    for basename in (PNP_DATA_FILE, PNP_RUNTIME_FILE):
+        path = os.path.join(repo_path, basename)
+        if not (secure_file_ops.is_file(path) if secure_file_ops else os.path.isfile(path)):
+            continue
+        try:
+            state, state_file = parse_pnp_state(read_repo_file(path, secure_file_ops), basename), basename
+            break
+        except (ValueError, OSError) as exc:
+            if logger:
+                logger.warning(f"Could not read Plug'n'Play state from {basename}: {exc}")
+    archives = _cache_archives(repo_path, secure_file_ops)
+    if not state and not archives:
+        return None
+
+    resolved, installed, aliases = pnp_resolutions(state)
+    for alias, package in sorted(aliases.items()):
+        _apply_alias(external_packages, alias, package)
+    for name, versions in sorted(installed.items()):
+        entry = external_packages.get(name)
+        if entry is None:
+            entry = external_packages[name] = {"ecosystem": "npm", "direct": False, "transitive": True}
+        if entry.get("ecosystem") != "npm":
+            continue
+        if name in resolved:
+            entry["pnp_resolved"] = dict(sorted(resolved[name].items()))
+            workspace_versions = set(resolved[name].values())
+            if len(workspace_versions) == 1:
+                entry["locked_version"] = workspace_versions.pop()
+        elif not entry.get("locked_version") and len(versions) == 1:
+            entry["locked_version"] = next(iter(versions))
+
+    slugs = {name.replace("/", "-"): name for name in external_packages if name.startswith("@")}
+    for archive in archives:
+        match = CACHE_ARCHIVE_RE.match(archive)
+        if not match:
+            continue
+        name = slugs.get(match.group("slug"), match.group("slug"))
+        entry = external_packages.get(name)
+        if entry and entry.get("ecosystem") == "npm":
+            # The archive of the locked version wins over other versions of the package
+            if entry.get("locked_version") in (None, match.group("version")) or "yarn_cache" not in entry:
+                entry["yarn_cache"] = posixpath.join(YARN_CACHE_DIR, archive)
+    cached = sum(1 for entry in external_packages.values() if "yarn_cache" in entry)
+    workspaces = {location for versions in resolved.values() for location in versions}
+    if logger:
+        logger.info(
+            f"... Applied Yarn Plug'n'Play resolutions for {len(workspaces)} workspaces"
+            f" ({cached} packages in {YARN_CACHE_DIR})"
+        )
+    return {
+        "state": state_file,
+        "workspaces": sorted(workspaces),
+        "aliases": dict(sorted(aliases.items())),
+        "cached": cached,
+    }
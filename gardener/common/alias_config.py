@@ -72,6 +72,10 @@ class AliasConfiguration:
     # TypeScript/JavaScript config-based aliases
     base_url: Optional[str] = None
     ts_js_paths: Dict[str, List[str]] = field(default_factory=dict)
+    # `paths` patterns whose target lies in node_modules -> package name
+    ts_js_package_paths: Dict[str, str] = field(default_factory=dict)
+    # webpack/Vite/Rollup aliases naming a package -> package name
+    bundler_package_paths: Dict[str, str] = field(default_factory=dict)
 
     # Framework aliases
     framework_resolver: FrameworkAliasResolver = field(default_factory=FrameworkAliasResolver)
@@ -210,6 +214,10 @@ class AliasConfiguration:
         for pattern, targets in other.ts_js_paths.items():
             if pattern not in self.ts_js_paths:
                 self.ts_js_paths[pattern] = targets
+        for pattern, package_name in other.ts_js_package_paths.items():
+            self.ts_js_package_paths.setdefault(pattern, package_name)
+        for pattern, package_name in other.bundler_package_paths.items():
+            self.bundler_package_paths.setdefault(pattern, package_name)
 
         # Merge custom rules
         self.custom_rules.extend(other.custom_rules)
@@ -284,6 +292,24 @@ class UnifiedAliasResolver:
 
         return None
 
+    def get_package_name(self, module_str):
+        """
+        Return the package a `paths` alias into node_modules, or a bundler alias, maps an import to
+
+        Example: with `"react": ["./node_modules/preact/compat"]`, 'react' -> 'preact'
+
+        Args:
+            module_str (str): Module string to check
+
+        Returns:
+            Package name if a package alias matches, None otherwise
+        """
+        for aliases in (self.config.ts_js_package_paths, self.config.bundler_package_paths):
+            for pattern, package_name in aliases.items():
+                if self._matches_pattern(module_str, pattern):
+                    return package_name
+        return None
+
     def _try_custom_rules(self, module_str):
         """
         Try to resolve using custom alias rules
@@ -0,0 +1,97 @@
+"""
+Cooperative cancellation on SIGINT/SIGTERM
+
+The first SIGINT or SIGTERM only sets a flag. Import extraction and URL resolution check it
+between units of work, finish the one in flight and stop; the run then assembles and persists a
+report marked `partial: true` with completed/remaining counts per phase. A second signal aborts
+immediately with KeyboardInterrupt
+"""
+
+import contextlib
+import signal
+import threading
+
+CANCEL_SIGNALS = ("SIGINT", "SIGTERM")
+
+
+class Cancellation:
+    """
+    Cancellation flag shared by the phases of one run
+
+    Attributes:
+        signal_name (str|None): Name of the signal that requested cancellation, None while running
+        progress (dict): Phase -> {'completed': n, 'remaining': n} for phases that were cut short
+    """
+
+    def __init__(self):
+        self.signal_name = None
+        self.progress = {}
+
+    @property
+    def requested(self):
+        return self.signal_name is not None
+
+    def request(self, signal_name="cancel"):
+        """
+        Request cancellation; later requests keep the first signal name
+
+        Args:
+            signal_name (str): Signal or reason recorded in the report
+        """
+        if self.signal_name is None:
+            self.signal_name = signal_name
+
+    def record(self, phase, completed, remaining):
+        """
+        Record how far a phase got before it stopped
+
+        Args:
+            phase (str): Phase name, e.g. 'imports' or 'url_resolution'
+            completed (int): Units finished
+            remaining (int): Units not started
+        """
+        self.progress[phase] = {"completed": completed, "remaining": remaining}
+
+    def summary(self):
+        """
+        Returns:
+            dict: {'signal': name, <phase>: {'completed', 'remaining'}, ...}
+        """
+        return {"signal": self.signal_name, **self.progress}
+
+
+@contextlib.contextmanager
+def handle_signals(cancellation, logger=None):
+    """
+    Route SIGINT/SIGTERM to cancellation.request for the duration of the block
+
+    Handlers can only be installed from the main thread; elsewhere the block runs unchanged
+
+    Args:
+        cancellation (Cancellation): Flag to set
+        logger (Logger): Optional logger
+    """
+    if threading.current_thread() is not threading.main_thread():
+        yield cancellation
+        return
+
+    def _handler(signum, frame):
+        if cancellation.requested:
+            raise KeyboardInterrupt
+        cancellation.request(signal.Signals(signum).name)
+        if logger:
+            logger.warning(
+                f"Received {cancellation.signal_name}: finishing in-flight work and writing a partial report "
+                "(send again to abort)"
+            )
+
+    previous = {}
+    for name in CANCEL_SIGNALS:
+        signum = getattr(signal, name, None)
+        if signum is not None:
+            previous[signum] = signal.signal(signum, _handler)
+    try:
+        yield cancellation
+    finally:
+        for signum, handler in previous.items():
+            signal.signal(signum, handler)
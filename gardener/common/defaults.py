@@ -2,6 +2,8 @@
 Default parameter values used in data processing, analysis, and visualization stages
 """
 
+import os
+
 
 class GraphAnalysisConfig:
     """
@@ -23,6 +25,69 @@ class GraphAnalysisConfig:
     EDGE_W_CONTAINS_COMPONENT = 1.0
     EDGE_W_USES_COMPONENT = 1.0
 
+    # Imports guarded only by checks for these platforms (e.g. ["windows"]) are left out of the graph
+    EXCLUDED_PLATFORMS = ()
+
+    # Orgs/scopes owned by the analyzed project, as globs matched against package names and repository URLs
+    # (e.g. ["@drips/*", "github.com/drips-network/*"]); matches are `ownership: first-party` and, with
+    # EXCLUDE_FIRST_PARTY, left out of top_dependencies (see analysis/ownership.py)
+    FIRST_PARTY_OWNERS = ()
+    EXCLUDE_FIRST_PARTY = True
+
+    # Minimum import evidence for a package to enter top_dependencies; packages below either threshold are
+    # listed under below_threshold instead (see analysis/thresholds.py). 0 disables a threshold
+    MIN_IMPORT_COUNT = 0
+    MIN_FILES = 0
+
+    # Funding split normalization: 'global' (as ranked), or 'language-files' / 'language-loc' to give each
+    # language's packages that language's share of the codebase by file count or lines of code
+    # (see analysis/normalization.py)
+    WEIGHT_NORMALIZATION = "global"
+
+    # Order of registry URL resolution: 'preliminary' (most-imported packages first), 'ranked' (full
+    # centrality ranking before any lookup), 'declared' (manifest order) or 'streaming' (each package as soon as
+    # a parsed file imports it, concurrently with import extraction; see analysis/pipeline.py)
+    URL_RESOLUTION_ORDER = "preliminary"
+    # Repository URLs seeded from a previous report (--seed) older than this are resolved again
+    URL_SEED_TTL_DAYS = 7.0
+    # Directory of the files kept between runs; relative REGISTRY_PINS_FILE and CRATES_INDEX_CACHE paths are
+    # resolved against it (see cache_path)
+    CACHE_DIR = "~/.gardener"
+    # Registry TLS fingerprints pinned on first use when PIN_REGISTRIES (`--pin-registries`) is set (see
+    # package_metadata/tls_pins.py). A changed public key or an unreadable pins file is a warning, or aborts the
+    # run with STRICT_NETWORK
+    PIN_REGISTRIES = False
+    REGISTRY_PINS_FILE = "registry_pins.json"
+    STRICT_NETWORK = False
+    # crates.io metadata cached as crates are fetched, and a read-only snapshot in the same format; both answer
+    # for crates that cannot be fetched, e.g. offline (see package_metadata/crates_index.py). None disables either
+    CRATES_INDEX_CACHE = "crates_index.json"
+    CRATES_INDEX_SNAPSHOT = None
+
+    # Import evidence layout: 'files' (per-file lists) or 'aggregated' (identical observations collapsed into
+    # counted groups keeping EVIDENCE_SAMPLE_FILES example files each, see analysis/evidence.py)
+    EVIDENCE = "files"
+    EVIDENCE_SAMPLE_FILES = 5
+
+    # Abort on a malformed manifest or lockfile instead of skipping it with a diagnostic (analysis/manifest_check.py)
+    STRICT_MANIFESTS = False
+
+    # Run `cargo metadata` (when `cargo` is on the PATH) and merge its resolved package graph into the static
+    # Cargo analysis (see analysis/cargo_metadata.py)
+    USE_CARGO_METADATA = False
+
+    # Add a `stdlib_usage` section counting standard library module usage per language
+    STDLIB_USAGE = False
+    # JSON/YAML file extending the built-in dataset of package alternatives (see analysis/suggestions.py)
+    ALTERNATIVES_DATASET = None
+    # JSON/YAML file mapping Python import names to PyPI distributions (`cv2: opencv-python`), extending or
+    # overriding the built-in map (see package_metadata/name_resolvers/python.py)
+    PYTHON_IMPORT_MAP = None
+
+    # Add a `timings` section with the seconds spent in each analysis stage and the depth statistics of the
+    # queues between them (see analysis/pipeline.py)
+    TIMINGS = False
+
     # Serialization behavior
     SERIALIZE_SORT_KEYS = True
 
@@ -71,6 +136,9 @@ class ResourceLimits:
     # Timeouts
     PARSE_TIMEOUT = 300  # Seconds to timeout a single file parsing
 
+    # Pipeline limits
+    PIPELINE_QUEUE_SIZE = 64  # Items waiting between two concurrent stages (read files, resolution requests)
+
     # Path and string limits (should not need retuning)
     MAX_PATH_LENGTH = 4096  # Maximum file path length
     MAX_URL_LENGTH = 2048  # Maximum URL length
@@ -80,6 +148,38 @@ class ResourceLimits:
     FOLLOW_SYMLINKS = True
 
 
+def cache_path(path):
+    """
+    Resolve a cache file setting against GraphAnalysisConfig.CACHE_DIR
+
+    Args:
+        path (str|None): File setting; absolute and '~' paths are kept as they are
+
+    Returns:
+        str|None: Expanded path, or None when the setting is empty
+    """
+    if not path:
+        return None
+    return os.path.join(os.path.expanduser(GraphAnalysisConfig.CACHE_DIR), os.path.expanduser(path))
+
+
+def effective_config():
+    """
+    Snapshot the current value of every configuration parameter
+
+    Returns:
+        dict: Class name -> {parameter name: value}, including any active overrides
+    """
+    snapshot = {}
+    for config_class in (GraphAnalysisConfig, VisualizationConfig, ResourceLimits):
+        snapshot[config_class.__name__] = {
+            key: list(value) if isinstance(value, tuple) else value
+            for key, value in sorted(vars(config_class).items())
+            if key.isupper()
+        }
+    return snapshot
+
+
 def apply_config_overrides(overrides, logger=None):
     """
     Apply configuration overrides from an external source
@@ -31,6 +31,23 @@ def read_file_content(file_path, secure_file_ops=None, encoding="utf-8"):
             return f.read()
 
 
+def read_repo_file(path, secure_file_ops=None):
+    """
+    Read a text file by absolute path, through SecureFileOps relative to its root when available
+
+    Args:
+        path (str): Absolute file path
+        secure_file_ops (SecureFileOps|None): Secure file operations instance or None
+
+    Returns:
+        str: File content as text
+    """
+    if secure_file_ops:
+        return secure_file_ops.read_file(secure_file_ops.get_relative_path(path))
+    with open(path, "r", encoding="utf-8") as handle:
+        return handle.read()
+
+
 def safe_json_load(file_path, secure_file_ops=None):
     """
     Load JSON content from a file using secure_file_ops if available
@@ -0,0 +1,105 @@
+"""
+Analyze a git object store without a worktree
+
+Forges keep bare repositories (`repo.git`) only. Pointed at one (or at any repository with
+`--ref`), gardener reads the tree of the requested ref straight from the object store: each blob
+is streamed into a temporary snapshot directory that the analysis runs against and that is
+removed afterwards. No checkout, index or worktree is created in the repository itself, and the
+resolved commit is recorded under `reproduce.git`
+"""
+
+import os
+import shutil
+import tempfile
+from pathlib import PurePosixPath
+
+from gardener.common.defaults import ResourceLimits
+from gardener.common.utils import RepositoryError
+
+SYMLINK_MODE = 0o120000
+
+
+def is_bare_repository(path):
+    """
+    Return True when path is a bare git repository (HEAD, objects/ and refs/ with no .git)
+    """
+    return not os.path.exists(os.path.join(path, ".git")) and all(
+        os.path.exists(os.path.join(path, name)) for name in ("HEAD", "objects", "refs")
+    )
+
+
+class GitSnapshot:
+    """
+    Files of one commit exported from a repository's object store into a temporary directory
+
+    Attributes:
+        path (str): Snapshot directory to analyze
+        info (dict): {'repository', 'ref', 'commit', 'bare', 'files', 'skipped'}
+    """
+
+    def __init__(self, repo_path, ref=None, logger=None):
+        """
+        Export the tree at ref
+
+        Args:
+            repo_path (str): Bare or non-bare repository path
+            ref (str|None): Branch, tag or commit; HEAD when None
+            logger (Logger): Optional logger
+
+        Raises:
+            RepositoryError: If the repository cannot be opened or the ref does not resolve
+        """
+        # Lazy import git - only needed for object store access
+        import git
+
+        ref = ref or "HEAD"
+        try:
+            repo = git.Repo(repo_path)
+            commit = repo.commit(ref)
+        except Exception as e:
+            raise RepositoryError(f"Cannot resolve '{ref}' in git repository {repo_path}: {e}")
+
+        self.path = tempfile.mkdtemp(prefix="gardener-snapshot-")
+        self.info = {
+            "repository": repo_path,
+            "ref": ref,
+            "commit": commit.hexsha,
+            "bare": bool(repo.bare),
+            "files": 0,
+            "skipped": 0,
+        }
+        try:
+            self._export(commit.tree, logger)
+        except Exception:
+            self.cleanup()
+            raise
+        if logger:
+            logger.info(
+                f"Read {self.info['files']} files at {ref} ({commit.hexsha[:12]}) from the object store of {repo_path}"
+            )
+
+    def _export(self, tree, logger):
+        """
+        Write every regular blob of the tree under the snapshot directory
+
+        Symlinks, submodule entries, blobs above ResourceLimits.MAX_FILE_SIZE and paths that would
+        leave the snapshot are skipped
+        """
+        for item in tree.traverse():
+            if item.type != "blob":
+                continue
+            parts = PurePosixPath(item.path).parts
+            if item.mode == SYMLINK_MODE or item.size > ResourceLimits.MAX_FILE_SIZE or ".." in parts:
+                self.info["skipped"] += 1
+                if logger:
+                    logger.debug(f"Skipping {item.path} from the object store (symlink, size or path)")
+                continue
+            target = os.path.join(self.path, *parts)
+            os.makedirs(os.path.dirname(target), exist_ok=True)
+            with open(target, "wb") as handle:
+                shutil.copyfileobj(item.data_stream, handle)
+            self.info["files"] += 1
+
+    def cleanup(self):
+        """Remove the snapshot directory"""
+        shutil.rmtree(self.path, ignore_errors=True)
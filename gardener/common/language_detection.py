@@ -5,12 +5,15 @@ Maps filenames to tree-sitter language keys
 import os
 
 from gardener.treewalk.go import GoLanguageHandler
+from gardener.treewalk.html import HtmlLanguageHandler
+from gardener.treewalk.idl import IdlLanguageHandler
 from gardener.treewalk.javascript import JavaScriptLanguageHandler
+from gardener.treewalk.notebook import NotebookLanguageHandler
 from gardener.treewalk.python import PythonLanguageHandler
 from gardener.treewalk.rust import RustLanguageHandler
 from gardener.treewalk.solidity import SolidityLanguageHandler
+from gardener.treewalk.stylesheet import StylesheetLanguageHandler
 from gardener.treewalk.typescript import TypeScriptLanguageHandler
-from gardener.common.tsl import USING_TSL_PACK
 
 
 def _build_parser_map():
@@ -27,6 +30,10 @@ def _build_parser_map():
         "go": GoLanguageHandler,
         "rust": RustLanguageHandler,
         "solidity": SolidityLanguageHandler,
+        "idl": IdlLanguageHandler,
+        "stylesheet": StylesheetLanguageHandler,
+        "html": HtmlLanguageHandler,
+        "notebook": NotebookLanguageHandler,
     }
 
     mapping = {}
@@ -41,10 +48,6 @@ def _build_parser_map():
     if mapping.get(".pyi") == "python":
         mapping.pop(".pyi", None)  # Don't treat .pyi type stubs as Python source
 
-    # Prefer the dedicated Svelte grammar when the language pack is available; otherwise parse as JavaScript
-    if ".svelte" in mapping:
-        mapping[".svelte"] = "svelte" if USING_TSL_PACK else "javascript"
-
     return mapping
 
 
@@ -0,0 +1,51 @@
+"""
+Process-wide offline switch
+
+Bundle runs (`--from-bundle`) must not reach the network. Registry lookups are replayed from the
+bundle through url_resolver's request hook; every other path that would open a connection (the
+GitHub App token exchange, PyPI archive downloads, `cargo metadata` fetching the crates index)
+checks this switch and refuses, or runs in its own offline mode, while it is set
+"""
+
+import contextlib
+
+_OFFLINE = False
+
+
+class NetworkDisabledError(RuntimeError):
+    """Raised when code tries to reach the network during an offline run"""
+
+
+def is_offline():
+    """
+    Return True while network access is disabled
+    """
+    return _OFFLINE
+
+
+def ensure_online(target):
+    """
+    Refuse a network access during an offline run
+
+    Args:
+        target (str): URL or description of what would be contacted
+
+    Raises:
+        NetworkDisabledError: If network access is disabled
+    """
+    if _OFFLINE:
+        raise NetworkDisabledError(f"Network access to {target} is disabled in offline mode")
+
+
+@contextlib.contextmanager
+def offline():
+    """
+    Disable network access for the duration of the block
+    """
+    global _OFFLINE
+    previous = _OFFLINE
+    _OFFLINE = True
+    try:
+        yield
+    finally:
+        _OFFLINE = previous
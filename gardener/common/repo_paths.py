@@ -0,0 +1,40 @@
+"""
+Repository-relative path helpers shared by the manifest and workspace analyses
+"""
+
+from pathlib import PurePosixPath
+
+
+def normalize_dir(path):
+    """
+    Normalize a repository-relative directory ('./crates/a/' -> 'crates/a', '' -> '.')
+
+    Args:
+        path (str): Repository-relative path, possibly with '.' and '..' components
+
+    Returns:
+        str|None: Normalized path, '.' for the repository root, or None when it leaves the repository
+    """
+    parts = []
+    for part in PurePosixPath(path).parts:
+        if part == "..":
+            if not parts:
+                return None
+            parts.pop()
+        elif part != ".":
+            parts.append(part)
+    return "/".join(parts) or "."
+
+
+def join_dir(base_dir, relative):
+    """
+    Join a manifest directory and a path written in it
+
+    Args:
+        base_dir (str): Normalized repository-relative directory, '.' for the root
+        relative (str): Path relative to base_dir
+
+    Returns:
+        str|None: Normalized repository-relative path, or None when it leaves the repository
+    """
+    return normalize_dir(str(PurePosixPath(base_dir) / relative)) if base_dir != "." else normalize_dir(relative)
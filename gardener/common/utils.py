@@ -33,16 +33,17 @@ class Logger:
     Maintains the same interface and behavior across the codebase
     """
 
-    def __init__(self, verbose=False, name=None):
+    def __init__(self, verbose=False, name=None, quiet=False):
         """
         Args:
             verbose (bool): Enable verbose output
             name (str): Optional logger name
+            quiet (bool): Only report warnings and errors (ignored when verbose)
         """
         self.verbose = verbose
         self.name = name or "gardener"
         self.seen_messages = set()  # Track already seen messages to avoid duplication
-        self.log_level = 1 if not verbose else 0  # 0=debug, 1=info, 2=warning, 3=error
+        self.log_level = 0 if verbose else 2 if quiet else 1  # 0=debug, 1=info, 2=warning, 3=error
 
     def debug(self, message):
         """
@@ -143,6 +144,8 @@ def get_repo(repo_input):
         if os.path.isdir(repo_input):
             if os.path.exists(os.path.join(repo_input, ".git")):
                 logger.info(f"Using existing git repository at {repo_input}")
+            elif all(os.path.exists(os.path.join(repo_input, name)) for name in ("HEAD", "objects", "refs")):
+                logger.info(f"Using bare git repository at {repo_input}")
             else:
                 logger.info(f"Using local directory at {repo_input} (not a git repository)")
             return repo_input
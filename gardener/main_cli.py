@@ -7,11 +7,24 @@ argument parsing, configuration overrides, and error handling for the analysis w
 """
 
 import argparse
+import contextlib
 import json
+import signal
 import sys
 
+from gardener.analysis.evidence import EVIDENCE_MODES
+from gardener.analysis.normalization import NORMALIZATION_MODES
 from gardener.analysis.main import run_analysis
+from gardener.analysis.manifest_check import ManifestError
+from gardener.analysis.smoothing import validate_smoothing
+from gardener.common.cancellation import Cancellation, handle_signals
 from gardener.common.utils import Logger, RepositoryError
+from gardener.package_metadata.enrichment import EnrichmentBudget
+from gardener.package_metadata.github_auth import github_auth_from_options
+from gardener.package_metadata.name_resolvers.python import ImportMapError
+from gardener.package_metadata.tls_pins import PinMismatchError
+from gardener.persistence.sinks import SinkPersistence, is_sink_url
+from gardener.persistence.stream import StreamPersistence
 
 
 def main():
@@ -23,11 +36,53 @@ def main():
     both local paths and remote git repository URLs
 
     Exits with status 1 on errors (repository access failures or unexpected exceptions)
+
+    `gardener conformance <fixtures_dir>` runs the fixture conformance harness instead,
+    `gardener verify <report.json> --key <pub.pem>` checks a signed report,
+    `gardener bundle --repos <repos.txt> --out <dir>` builds an offline analysis bundle,
+    `gardener sample-audit <report.json> --n 20` draws a share-weighted spot-audit worksheet,
+    `gardener fetch ipfs://<cid>` retrieves and validates a report published to IPFS,
+    `gardener reconcile --drip-list <id> <report.json>` diffs a drip list against the report's splits and
+    `gardener intersect <a.json> <b.json> ...` lists the packages shared across several repositories' reports
     """
+    if len(sys.argv) > 1 and sys.argv[1] == "conformance":
+        from gardener.analysis.conformance import main as conformance_main
+
+        sys.exit(conformance_main(sys.argv[2:]))
+    if len(sys.argv) > 1 and sys.argv[1] == "verify":
+        from gardener.analysis.signing import main as verify_main
+
+        sys.exit(verify_main(sys.argv[2:]))
+    if len(sys.argv) > 1 and sys.argv[1] == "bundle":
+        from gardener.analysis.bundle import main as bundle_main
+
+        sys.exit(bundle_main(sys.argv[2:]))
+    if len(sys.argv) > 1 and sys.argv[1] == "sample-audit":
+        from gardener.analysis.sample_audit import main as sample_audit_main
+
+        sys.exit(sample_audit_main(sys.argv[2:]))
+    if len(sys.argv) > 1 and sys.argv[1] == "fetch":
+        from gardener.persistence.ipfs import main as fetch_main
+
+        sys.exit(fetch_main(sys.argv[2:]))
+    if len(sys.argv) > 1 and sys.argv[1] == "reconcile":
+        from gardener.analysis.reconcile import main as reconcile_main
+
+        sys.exit(reconcile_main(sys.argv[2:]))
+    if len(sys.argv) > 1 and sys.argv[1] == "intersect":
+        from gardener.analysis.intersect import main as intersect_main
+
+        sys.exit(intersect_main(sys.argv[2:]))
+
     logger = Logger(verbose=True)  # CLI should show all messages
     parser = argparse.ArgumentParser()
     parser.add_argument("repo_path", help="Path to repo directory, or URL of hosted git repo")
-    parser.add_argument("-o", "--output", help="Output file prefix")
+    parser.add_argument(
+        "-o", "--output", help="Output file prefix, or an s3://bucket/prefix, gs://bucket/prefix or ipfs:// sink"
+    )
+    parser.add_argument(
+        "--ref", help="Git ref to analyze, read from the object store (bare repositories default to HEAD)"
+    )
     parser.add_argument("-v", "--verbose", action="store_true", help="Enable verbose debug logging")
     # Default behavior: minimal outputs (skip visualizations)
     parser.add_argument(
@@ -43,6 +98,79 @@ def main():
         "-l", "--languages", help="Comma-separated list of languages to focus on (e.g., python,javascript)"
     )
     parser.add_argument("-c", "--config", help="JSON string with configuration overrides")
+    parser.add_argument("--sign-key", help="PEM private key used to sign the analysis results")
+    parser.add_argument(
+        "--from-bundle", help="Run offline against a bundle from `gardener bundle` (repo_path may be a bundled URL)"
+    )
+    parser.add_argument(
+        "--enrichment-budget", help="Cap registry requests for URL resolution, e.g. 500-requests (highest-weight first)"
+    )
+    parser.add_argument("--enrichment-timeout", help="Cap URL resolution wall-clock time, e.g. 60s or 2m")
+    parser.add_argument(
+        "--seed", help="Previous analysis report whose resolved repository URLs are reused until URL_SEED_TTL_DAYS"
+    )
+    parser.add_argument(
+        "--stream",
+        action="store_true",
+        help="Write results to stdout as JSON Lines, a report_stats header first (logs go to stderr)",
+    )
+    parser.add_argument(
+        "--evidence",
+        choices=EVIDENCE_MODES,
+        help="Import evidence layout: per-file lists (default) or identical imports collapsed into counted groups",
+    )
+    parser.add_argument(
+        "--normalization",
+        choices=NORMALIZATION_MODES,
+        help="Funding split normalization: as ranked (default), or balanced across languages by their share "
+        "of source files or lines of code",
+    )
+    parser.add_argument(
+        "--timings",
+        action="store_true",
+        help="Report the time spent in each analysis stage and the depth of the queues between them",
+    )
+    parser.add_argument(
+        "--strict-manifests",
+        action="store_true",
+        help="Fail on a malformed manifest or lockfile instead of skipping it with a diagnostic",
+    )
+    parser.add_argument(
+        "--pin-registries",
+        action="store_true",
+        help="Pin the TLS public key of each registry host on first use and check it on later runs",
+    )
+    parser.add_argument(
+        "--strict-network",
+        action="store_true",
+        help="Fail when a registry presents a TLS public key other than the pinned one or the pins file cannot "
+        "be read (with --pin-registries), and run `cargo metadata` offline",
+    )
+    parser.add_argument(
+        "--use-cargo-metadata",
+        action="store_true",
+        help="Merge the package graph resolved by `cargo metadata` when a Rust toolchain is available",
+    )
+    parser.add_argument(
+        "--first-party",
+        action="append",
+        metavar="PATTERN",
+        help="Owned org or scope (e.g. '@drips/*', 'github.com/drips-network/*'); matching packages are "
+        "kept in the inventory but left out of the funding split. Repeatable",
+    )
+    parser.add_argument(
+        "--smooth",
+        type=float,
+        metavar="FACTOR",
+        help="Blend top dependency shares with --previous as an exponential moving average; FACTOR (0-1) "
+        "is the weight of the previous shares",
+    )
+    parser.add_argument("--previous", metavar="REPORT", help="Previous analysis report to smooth against")
+    parser.add_argument("--github-app-id", help="GitHub App ID used to authenticate GitHub API lookups")
+    parser.add_argument("--github-app-installation-id", help="Installation ID of the GitHub App")
+    parser.add_argument(
+        "--github-app-key", help="Path to the GitHub App's PEM private key file, or the PEM contents themselves"
+    )
     args = parser.parse_args()
 
     config_overrides = None
@@ -57,6 +185,33 @@ def main():
                 "See gardener/common/defaults.py for overrideable parameter names"
             )
             sys.exit(1)
+    if args.evidence:
+        config_overrides = {**(config_overrides or {}), "EVIDENCE": args.evidence}
+    if args.normalization:
+        config_overrides = {**(config_overrides or {}), "WEIGHT_NORMALIZATION": args.normalization}
+    if args.timings:
+        config_overrides = {**(config_overrides or {}), "TIMINGS": True}
+    if args.strict_manifests:
+        config_overrides = {**(config_overrides or {}), "STRICT_MANIFESTS": True}
+    if args.pin_registries:
+        config_overrides = {**(config_overrides or {}), "PIN_REGISTRIES": True}
+    if args.strict_network:
+        config_overrides = {**(config_overrides or {}), "STRICT_NETWORK": True}
+    if args.use_cargo_metadata:
+        config_overrides = {**(config_overrides or {}), "USE_CARGO_METADATA": True}
+    if args.first_party:
+        config_overrides = {**(config_overrides or {}), "FIRST_PARTY_OWNERS": args.first_party}
+
+    try:
+        enrichment_budget = EnrichmentBudget.from_options(args.enrichment_budget, args.enrichment_timeout)
+        github_auth = github_auth_from_options(
+            args.github_app_id, args.github_app_installation_id, args.github_app_key, logger=logger
+        )
+        validate_smoothing(args.smooth, args.previous)
+        sink = SinkPersistence(args.output, verbose=args.verbose) if is_sink_url(args.output) else None
+    except ValueError as e:
+        logger.error(str(e))
+        sys.exit(1)
 
     try:
         # Resolve minimal_outputs default: visualizations are opt-in
@@ -66,8 +221,39 @@ def main():
         elif args.minimal_outputs:
             minimal_outputs = True
 
-        run_analysis(args.repo_path, args.output, args.verbose, minimal_outputs, args.languages, config_overrides)
-    except RepositoryError as e:
+        persistence = sink
+        output_prefix = None if sink else args.output
+        log_redirect = contextlib.nullcontext()
+        if args.stream:
+            # Keep stdout for the JSON Lines output; progress messages go to stderr instead
+            persistence = StreamPersistence(sys.stdout, verbose=args.verbose)
+            log_redirect = contextlib.redirect_stdout(sys.stderr)
+            minimal_outputs = True
+
+        cancellation = Cancellation()
+        with log_redirect, handle_signals(cancellation, logger):
+            run_analysis(
+                args.repo_path,
+                output_prefix,
+                args.verbose,
+                minimal_outputs,
+                args.languages,
+                config_overrides,
+                persistence=persistence,
+                sign_key=args.sign_key,
+                from_bundle=args.from_bundle,
+                enrichment_budget=enrichment_budget,
+                seed=args.seed,
+                cancellation=cancellation,
+                ref=args.ref,
+                github_auth=github_auth,
+                smooth=args.smooth,
+                previous=args.previous,
+            )
+        if cancellation.requested:
+            # Conventional exit status for termination by a signal: 128 + signal number
+            sys.exit(128 + signal.Signals[cancellation.signal_name].value)
+    except (RepositoryError, ManifestError, PinMismatchError, ImportMapError) as e:
         logger.error(str(e))
         sys.exit(1)
     except Exception as e:
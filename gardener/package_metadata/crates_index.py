@@ -0,0 +1,183 @@
+"""
+Cached crates.io metadata for offline Rust resolution
+
+Rust crates resolve through the crates.io API, whose `repository`, `homepage` and `documentation`
+fields (plus the categories and keywords marking proc-macro crates) are kept in CRATES_INDEX_CACHE
+as each crate is fetched. When a crate cannot be fetched, the entry from that cache, or from a
+snapshot file in the same format (CRATES_INDEX_SNAPSHOT, e.g. a cache copied into an air-gapped
+CI image), answers instead and the package is marked `index_fallback: cache` or `snapshot`. The
+sparse index (index.crates.io) carries no repository fields, so it serves as the reachability
+probe: after the first failed API request it is asked for its config.json, and when that fails
+too the rest of the run resolves crates from the cache and snapshot without further requests.
+Crates the API answers with 404 are cached as missing (`{"missing": true}`), and later lookups
+answer them as not found. Concurrent runs sharing the cache merge their crates into it: the file
+is re-read under a lock and replaced atomically
+"""
+
+import contextlib
+import datetime
+import json
+import os
+import tempfile
+
+try:
+    import fcntl
+except ImportError:  # pragma: no cover - Windows
+    fcntl = None
+
+CACHED_FIELDS = ("repository", "homepage", "documentation")
+
+
+def _read_entries(path):
+    """
+    Read crate entries from a cache or snapshot file, or return {} when missing or malformed
+    """
+    try:
+        with open(path, "r", encoding="utf-8") as handle:
+            data = json.load(handle)
+    except (OSError, ValueError):
+        return {}
+    crates = data.get("crates") if isinstance(data, dict) else None
+    return {name: entry for name, entry in (crates or {}).items() if isinstance(entry, dict)}
+
+
+def _now():
+    return datetime.datetime.now(datetime.timezone.utc).isoformat(timespec="seconds")
+
+
+@contextlib.contextmanager
+def _locked(lock_path):
+    """
+    Hold an exclusive lock on lock_path for the duration of the block (no-op without fcntl)
+    """
+    with open(lock_path, "a", encoding="utf-8") as handle:
+        if fcntl is not None:
+            fcntl.flock(handle.fileno(), fcntl.LOCK_EX)
+        try:
+            yield
+        finally:
+            if fcntl is not None:
+                fcntl.flock(handle.fileno(), fcntl.LOCK_UN)
+
+
+class CratesIndex:
+    """
+    crates.io metadata cached across runs, with an optional read-only snapshot
+
+    Attributes:
+        path (str|None): Cache file, rewritten with the crates fetched this run
+        snapshot_path (str|None): Snapshot file consulted after the cache
+        entries (dict): Crate name -> {'repository', 'homepage', 'documentation', 'categories',
+            'keywords', 'fetched_at'}, or {'missing': True, 'fetched_at'} for a crate crates.io does not have
+        online (bool|None): Whether the sparse index answered; None until probed
+        updated (set): Crates recorded this run, written to the cache by save
+    """
+
+    def __init__(self, path=None, snapshot_path=None):
+        self.path = os.path.expanduser(path) if path else None
+        self.snapshot_path = os.path.expanduser(snapshot_path) if snapshot_path else None
+        self.entries = _read_entries(self.path) if self.path else {}
+        self.snapshot = _read_entries(self.snapshot_path) if self.snapshot_path else {}
+        self.online = None
+        self.fetched = 0
+        self.updated = set()
+        self.fallbacks = {"cache": 0, "snapshot": 0}
+
+    def record(self, name, data):
+        """
+        Cache the fields of a crates.io API response
+
+        Args:
+            name (str): Crate name
+            data (dict): crates.io crate response
+        """
+        crate = data.get("crate") or {}
+        entry = {field: crate.get(field) for field in CACHED_FIELDS if crate.get(field)}
+        entry["categories"] = crate.get("categories") or [
+            category.get("id") for category in data.get("categories") or [] if isinstance(category, dict)
+        ]
+        entry["keywords"] = crate.get("keywords") or [
+            keyword.get("id") for keyword in data.get("keywords") or [] if isinstance(keyword, dict)
+        ]
+        entry["fetched_at"] = _now()
+        self.entries[name] = entry
+        self.updated.add(name)
+        self.fetched += 1
+
+    def record_missing(self, name):
+        """
+        Cache that crates.io answered 404 for a crate
+
+        Args:
+            name (str): Crate name
+        """
+        self.entries[name] = {"missing": True, "fetched_at": _now()}
+        self.updated.add(name)
+
+    def is_missing(self, name):
+        """
+        Return True when the cache records that crates.io does not have the crate
+        """
+        return bool((self.entries.get(name) or {}).get("missing"))
+
+    def lookup(self, name):
+        """
+        Return a cached crate in the shape of a crates.io API response
+
+        Args:
+            name (str): Crate name
+
+        Returns:
+            tuple: (response dict, 'cache' or 'snapshot'), or (None, None) when neither has the crate
+                or the cache records it as missing
+        """
+        if self.is_missing(name):
+            return None, None
+        for source, entries in (("cache", self.entries), ("snapshot", self.snapshot)):
+            if name in entries:
+                self.fallbacks[source] += 1
+                return {"crate": dict(entries[name])}, source
+        return None, None
+
+    def save(self, logger=None):
+        """
+        Merge the crates recorded this run into the cache file
+
+        The file is re-read under a lock, so crates saved by a concurrent run since this one started
+        are kept, and replaced atomically, so readers never see a partly written file
+        """
+        if not self.path or not self.updated:
+            return
+        directory = os.path.dirname(self.path) or "."
+        try:
+            os.makedirs(directory, exist_ok=True)
+            with _locked(f"{self.path}.lock"):
+                entries = _read_entries(self.path)
+                entries.update((name, self.entries[name]) for name in self.updated)
+                fd, temp_path = tempfile.mkstemp(dir=directory, prefix=".crates_index.", suffix=".tmp")
+                try:
+                    with os.fdopen(fd, "w", encoding="utf-8") as handle:
+                        json.dump({"crates": entries}, handle, indent=2, sort_keys=True)
+                    os.replace(temp_path, self.path)
+                except BaseException:
+                    with contextlib.suppress(OSError):
+                        os.unlink(temp_path)
+                    raise
+        except OSError as exc:
+            logger and logger.warning(f"Could not save the crates.io cache to {self.path}: {exc}")
+            return
+        self.entries = entries
+
+    def summary(self):
+        """
+        Returns:
+            dict: {'cache', 'snapshot', 'online', 'fetched', 'from_cache', 'from_snapshot'}
+        """
+        return {
+            "cache": self.path,
+            "snapshot": self.snapshot_path,
+            "online": self.online,
+            "fetched": self.fetched,
+            "from_cache": self.fallbacks["cache"],
+            "from_snapshot": self.fallbacks["snapshot"],
+        }
@@ -0,0 +1,143 @@
+"""
+Budget controls for enrichment passes
+
+Enrichment is the registry/API work done per package after discovery (repository URL resolution).
+An EnrichmentBudget caps it by request count and/or wall-clock time; packages reached after the
+budget runs out are left unresolved and marked `enrichment: skipped-budget` (or
+`enrichment: skipped-cancelled` when the run was interrupted)
+"""
+
+import re
+import time
+
+SKIPPED_BUDGET = "skipped-budget"
+SKIPPED_CANCELLED = "skipped-cancelled"
+
+_RE_REQUEST_BUDGET = re.compile(r"^\s*(\d+)\s*(?:-?\s*(?:requests?|reqs?))?\s*$", re.IGNORECASE)
+_RE_DURATION = re.compile(r"^\s*(\d+(?:\.\d+)?)\s*(ms|s|m|h)?\s*$", re.IGNORECASE)
+_DURATION_UNITS = {"ms": 0.001, "s": 1, "m": 60, "h": 3600}
+
+
+def parse_request_budget(value):
+    """
+    Parse a request budget such as '500-requests', '500 requests' or '500'
+
+    Args:
+        value (str): Budget as given on the command line
+
+    Returns:
+        int: Maximum number of registry requests
+
+    Raises:
+        ValueError: If the value is not a non-negative request count
+    """
+    match = _RE_REQUEST_BUDGET.match(str(value))
+    if not match:
+        raise ValueError(f"Invalid enrichment budget '{value}' (expected e.g. '500-requests')")
+    return int(match.group(1))
+
+
+def parse_duration(value):
+    """
+    Parse a duration such as '60s', '2m', '1h', '500ms' or a bare number of seconds
+
+    Args:
+        value (str): Duration as given on the command line
+
+    Returns:
+        float: Seconds
+
+    Raises:
+        ValueError: If the value is not a duration
+    """
+    match = _RE_DURATION.match(str(value))
+    if not match:
+        raise ValueError(f"Invalid enrichment timeout '{value}' (expected e.g. '60s')")
+    return float(match.group(1)) * _DURATION_UNITS[(match.group(2) or "s").lower()]
+
+
+class EnrichmentBudget:
+    """
+    Request and time allowance shared by one enrichment pass
+
+    The budget is checked between packages, so a package that starts within budget finishes its
+    lookups even if they take the count slightly past the limit
+
+    Attributes:
+        max_requests (int|None): Request cap, or None for unlimited
+        timeout_seconds (float|None): Wall-clock cap from start(), or None for unlimited
+        requests (int): Requests charged so far
+    """
+
+    def __init__(self, max_requests=None, timeout_seconds=None, clock=time.monotonic):
+        self.max_requests = max_requests
+        self.timeout_seconds = timeout_seconds
+        self.requests = 0
+        self._clock = clock
+        self._started = None
+        self.exhausted_by = None
+
+    @classmethod
+    def from_options(cls, budget=None, timeout=None):
+        """
+        Build a budget from CLI-style strings
+
+        Args:
+            budget (str|None): Request budget such as '500-requests'
+            timeout (str|None): Duration such as '60s'
+
+        Returns:
+            EnrichmentBudget|None: None when neither limit is given
+        """
+        if budget is None and timeout is None:
+            return None
+        return cls(
+            max_requests=parse_request_budget(budget) if budget is not None else None,
+            timeout_seconds=parse_duration(timeout) if timeout is not None else None,
+        )
+
+    def start(self):
+        """Start the clock for the timeout; later calls keep the original start"""
+        if self._started is None:
+            self._started = self._clock()
+
+    def elapsed(self):
+        """Return seconds since start(), or 0.0 before it"""
+        return 0.0 if self._started is None else self._clock() - self._started
+
+    def charge_request(self):
+        """Record one outbound registry request"""
+        self.requests += 1
+
+    def exhausted(self):
+        """
+        Return True once the request count or the time allowance is used up
+
+        Returns:
+            bool: Whether further packages should be skipped
+        """
+        if self.exhausted_by is None:
+            if self.max_requests is not None and self.requests >= self.max_requests:
+                self.exhausted_by = "requests"
+            elif self.timeout_seconds is not None and self.elapsed() >= self.timeout_seconds:
+                self.exhausted_by = "timeout"
+        return self.exhausted_by is not None
+
+    def summary(self, skipped=0):
+        """
+        Describe the budget and its use for the analysis results
+
+        Args:
+            skipped (int): Number of packages marked skipped-budget
+
+        Returns:
+            dict: Limits, usage and the reason the budget ran out (if it did)
+        """
+        return {
+            "max_requests": self.max_requests,
+            "timeout_seconds": self.timeout_seconds,
+            "requests": self.requests,
+            "elapsed_seconds": round(self.elapsed(), 3),
+            "exhausted_by": self.exhausted_by,
+            "skipped_packages": skipped,
+        }
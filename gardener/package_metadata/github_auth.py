@@ -0,0 +1,266 @@
+"""
+Authentication for GitHub API lookups
+
+Unauthenticated api.github.com requests are limited to 60 an hour and a personal access token to
+5,000, which org-wide scans exhaust quickly. Requests to GitHub hosts made during enrichment
+(url_resolver.GITHUB_HOSTS) carry an `Authorization` header when credentials are configured:
+either a token (`GITHUB_TOKEN`) or a GitHub App installation. For an App, a short-lived RS256 JWT
+signed with the App's private key is exchanged for an installation access token, which is cached
+and refreshed shortly before it expires or when GitHub answers 401. Installation tokens get the
+App's higher rate limits
+"""
+
+import base64
+import contextlib
+import json
+import os
+import time
+import urllib.error
+from datetime import datetime
+
+from gardener.common.network import NetworkDisabledError, ensure_online
+from gardener.package_metadata import url_resolver
+
+GITHUB_API = "https://api.github.com"
+# Installation tokens live one hour; refresh this many seconds before GitHub's expires_at
+REFRESH_MARGIN = 300
+# GitHub rejects App JWTs valid for longer than ten minutes; iat is backdated for clock skew
+JWT_LIFETIME = 540
+JWT_CLOCK_SKEW = 60
+
+ENV_APP_ID = "GARDENER_GITHUB_APP_ID"
+ENV_INSTALLATION_ID = "GARDENER_GITHUB_APP_INSTALLATION_ID"
+ENV_PRIVATE_KEY = "GARDENER_GITHUB_APP_PRIVATE_KEY"
+ENV_PRIVATE_KEY_PATH = "GARDENER_GITHUB_APP_PRIVATE_KEY_PATH"
+ENV_TOKEN = "GITHUB_TOKEN"
+
+
+def _b64url(data):
+    return base64.urlsafe_b64encode(data).rstrip(b"=").decode("ascii")
+
+
+def _post_json(url, headers):
+    """
+    POST an empty body and decode the JSON response
+
+    The request goes through url_resolver, so it is charged to the enrichment budget, recorded in
+    the resolution health and answered by the request hook when one is set. Offline runs refuse it
+    before the hook: a bundle holds no installation tokens
+
+    Args:
+        url (str): Endpoint URL
+        headers (dict): Request headers
+
+    Returns:
+        dict: Decoded response
+
+    Raises:
+        NetworkDisabledError: During an offline run
+        urllib.error.HTTPError: If the endpoint answers with an error
+    """
+    ensure_online(url)
+    started = time.monotonic()
+    try:
+        status, data, error = url_resolver._request_once(url, method="POST", headers=headers)
+    except NetworkDisabledError:
+        raise
+    except Exception as e:
+        url_resolver._record_request(url, None, started, e)
+        raise
+    url_resolver._record_request(url, status, started, error)
+    if error is not None:
+        raise error
+    if data is None:
+        raise urllib.error.HTTPError(url, status, "Installation token not issued", None, None)
+    return data
+
+
+class TokenAuth:
+    """
+    Static token (personal access token or a token minted elsewhere)
+
+    Attributes:
+        kind (str): 'token'
+    """
+
+    kind = "token"
+
+    def __init__(self, token):
+        self.token = token
+
+    def authorization(self):
+        """Return the Authorization header value"""
+        return f"Bearer {self.token}"
+
+    def invalidate(self):
+        """Static tokens cannot be refreshed"""
+
+
+class GitHubAppAuth:
+    """
+    GitHub App installation token, minted on first use and refreshed before expiry
+
+    Attributes:
+        kind (str): 'app'
+        app_id (str): GitHub App ID, the JWT issuer
+        installation_id (str): Installation whose token is requested
+    """
+
+    kind = "app"
+
+    def __init__(self, app_id, installation_id, private_key_pem, post_fn=None, clock=time.time, logger=None):
+        """
+        Args:
+            app_id (str|int): GitHub App ID
+            installation_id (str|int): Installation ID of the App on the scanned organization
+            private_key_pem (bytes|str): The App's PEM private key
+            post_fn (callable): Optional fn(url, headers) -> dict used for the token exchange
+            clock (callable): Returns the current Unix time
+            logger (Logger): Optional logger
+
+        Raises:
+            ValueError: If the private key is not an RSA key
+        """
+        from cryptography.hazmat.primitives import serialization
+        from cryptography.hazmat.primitives.asymmetric import rsa
+
+        if isinstance(private_key_pem, str):
+            private_key_pem = private_key_pem.encode("utf-8")
+        self._key = serialization.load_pem_private_key(private_key_pem, password=None)
+        if not isinstance(self._key, rsa.RSAPrivateKey):
+            raise ValueError("GitHub App private keys must be RSA keys")
+        self.app_id = str(app_id)
+        self.installation_id = str(installation_id)
+        self._post = post_fn or _post_json
+        self._clock = clock
+        self._logger = logger
+        self._token = None
+        self._expires_at = 0.0
+        self.refreshes = 0
+
+    def app_jwt(self):
+        """
+        Return an RS256 JWT identifying the App, valid for JWT_LIFETIME seconds
+        """
+        from cryptography.hazmat.primitives import hashes
+        from cryptography.hazmat.primitives.asymmetric import padding
+
+        now = int(self._clock())
+        header = _b64url(json.dumps({"alg": "RS256", "typ": "JWT"}, separators=(",", ":")).encode())
+        claims = {"iat": now - JWT_CLOCK_SKEW, "exp": now + JWT_LIFETIME, "iss": self.app_id}
+        payload = _b64url(json.dumps(claims, separators=(",", ":")).encode())
+        signing_input = f"{header}.{payload}".encode("ascii")
+        signature = self._key.sign(signing_input, padding.PKCS1v15(), hashes.SHA256())
+        return f"{header}.{payload}.{_b64url(signature)}"
+
+    def _refresh(self):
+        url = f"{GITHUB_API}/app/installations/{self.installation_id}/access_tokens"
+        data = self._post(
+            url,
+            {
+                "Authorization": f"Bearer {self.app_jwt()}",
+                "Accept": "application/vnd.github+json",
+            },
+        )
+        self._token = data["token"]
+        expires_at = data.get("expires_at")
+        if expires_at:
+            self._expires_at = datetime.fromisoformat(expires_at.replace("Z", "+00:00")).timestamp()
+        else:
+            self._expires_at = self._clock() + 3600
+        self.refreshes += 1
+        if self._logger:
+            self._logger.debug(f"Minted GitHub App installation token for installation {self.installation_id}")
+
+    def authorization(self):
+        """
+        Return the Authorization header value, minting a new installation token when needed
+        """
+        if self._token is None or self._clock() >= self._expires_at - REFRESH_MARGIN:
+            self._refresh()
+        return f"Bearer {self._token}"
+
+    def invalidate(self):
+        """Drop the cached installation token so the next request mints a new one"""
+        self._token = None
+
+
+def _private_key_bytes(private_key):
+    """
+    Return the PEM given either itself or as the path of a key file
+
+    Args:
+        private_key (str): PEM contents, or the path of a PEM key file
+
+    Returns:
+        bytes: PEM contents
+
+    Raises:
+        ValueError: If the key file cannot be read
+    """
+    if private_key.lstrip().startswith("-----BEGIN"):
+        return private_key.encode("utf-8")
+    try:
+        with open(private_key, "rb") as handle:
+            return handle.read()
+    except OSError as e:
+        raise ValueError(f"Cannot read GitHub App private key {private_key}: {e}")
+
+
+def github_auth_from_options(app_id=None, installation_id=None, private_key=None, environ=None, logger=None):
+    """
+    Build GitHub credentials from options, falling back to environment variables
+
+    An App needs its ID, installation ID and private key (GARDENER_GITHUB_APP_ID,
+    GARDENER_GITHUB_APP_INSTALLATION_ID, and GARDENER_GITHUB_APP_PRIVATE_KEY_PATH or the PEM itself
+    in GARDENER_GITHUB_APP_PRIVATE_KEY); otherwise GITHUB_TOKEN is used when set
+
+    Args:
+        app_id (str): Optional GitHub App ID
+        installation_id (str): Optional installation ID
+        private_key (str): Optional path to the App's PEM private key file, or the PEM itself
+        environ (dict): Environment; os.environ when None
+        logger (Logger): Optional logger
+
+    Returns:
+        GitHubAppAuth|TokenAuth|None: Credentials, or None when nothing is configured
+
+    Raises:
+        ValueError: If the App settings are incomplete or the key cannot be read
+    """
+    environ = os.environ if environ is None else environ
+    app_id = app_id or environ.get(ENV_APP_ID)
+    installation_id = installation_id or environ.get(ENV_INSTALLATION_ID)
+    private_key = private_key or environ.get(ENV_PRIVATE_KEY_PATH) or environ.get(ENV_PRIVATE_KEY)
+
+    if app_id or installation_id or private_key:
+        if not (app_id and installation_id and private_key):
+            raise ValueError("GitHub App authentication needs an App ID, an installation ID and a private key")
+        private_key = _private_key_bytes(private_key)
+        try:
+            auth = GitHubAppAuth(app_id, installation_id, private_key, logger=logger)
+        except ImportError as e:
+            raise ValueError("GitHub App authentication requires the 'cryptography' package") from e
+        except (TypeError, ValueError) as e:
+            raise ValueError(f"Invalid GitHub App private key: {e}")
+        if logger:
+            logger.info(f"Authenticating GitHub API lookups as GitHub App {app_id} (installation {installation_id})")
+        return auth
+
+    token = environ.get(ENV_TOKEN)
+    return TokenAuth(token) if token else None
+
+
+@contextlib.contextmanager
+def github_auth_scope(auth):
+    """
+    Authenticate url_resolver's requests to GitHub hosts with auth for the duration of the block
+
+    Args:
+        auth (GitHubAppAuth|TokenAuth|None): Credentials; None leaves requests unauthenticated
+    """
+    url_resolver.set_github_auth(auth)
+    try:
+        yield auth
+    finally:
+        url_resolver.set_github_auth(None)
@@ -0,0 +1,125 @@
+"""
+Registry health for URL resolution
+
+ResolutionHealth records, per ecosystem, how repository URL resolution went during a run:
+requests sent, errors, latency, cache hits and rate-limit responses. The summary is reported as
+the `resolution_health` section so operators can spot a degrading registry before its failures
+show up as missing repository URLs
+"""
+
+import statistics
+from urllib.parse import urlsplit
+
+# Responses that mean the registry is throttling us
+RATE_LIMIT_STATUSES = {429}
+
+
+def is_rate_limited(status, headers=None):
+    """
+    Return True for a throttling response: 429, or a 403 with an exhausted rate-limit header (GitHub)
+
+    Args:
+        status (int|None): HTTP status
+        headers (Mapping|None): Response headers
+    """
+    if status in RATE_LIMIT_STATUSES:
+        return True
+    return status == 403 and headers is not None and str(headers.get("X-RateLimit-Remaining", "")).strip() == "0"
+
+
+class _EcosystemHealth:
+    """
+    Counters for one ecosystem
+    """
+
+    def __init__(self):
+        self.packages = 0
+        self.cache_hits = 0
+        self.requests = 0
+        self.errors = 0
+        self.not_found = 0
+        self.rate_limited = 0
+        self.latencies = []
+        self.hosts = set()
+
+    def summary(self):
+        """
+        Returns:
+            dict: Counts, rates and median latency for this ecosystem
+        """
+        return {
+            "packages": self.packages,
+            "cache_hits": self.cache_hits,
+            "cache_hit_rate": round(self.cache_hits / self.packages, 3) if self.packages else None,
+            "requests": self.requests,
+            "errors": self.errors,
+            "error_rate": round(self.errors / self.requests, 3) if self.requests else None,
+            "not_found": self.not_found,
+            "rate_limit_events": self.rate_limited,
+            "median_latency_ms": round(statistics.median(self.latencies) * 1000, 1) if self.latencies else None,
+            "hosts": sorted(self.hosts),
+        }
+
+
+class ResolutionHealth:
+    """
+    Per-ecosystem request, error, latency, cache and rate-limit statistics for one resolution pass
+
+    Requests are attributed to the ecosystem of the package being resolved, so a Solidity package
+    looked up on npm counts under 'solidity'
+
+    Attributes:
+        ecosystems (dict): Ecosystem -> counters
+        current (str|None): Ecosystem of the package currently being resolved
+    """
+
+    def __init__(self):
+        self.ecosystems = {}
+        self.current = None
+
+    def _stats(self, ecosystem):
+        return self.ecosystems.setdefault(ecosystem or "unknown", _EcosystemHealth())
+
+    def begin_package(self, ecosystem, cached=False):
+        """
+        Record a package about to be resolved; later requests are attributed to its ecosystem
+
+        Args:
+            ecosystem (str): Package ecosystem
+            cached (bool): True when its URL came from the URL cache
+        """
+        self.current = ecosystem
+        stats = self._stats(ecosystem)
+        stats.packages += 1
+        if cached:
+            stats.cache_hits += 1
+
+    def record_request(self, url, status, seconds, headers=None):
+        """
+        Record one outbound request attempt (retries count separately)
+
+        Args:
+            url (str): Requested URL
+            status (int|None): HTTP status, or None when the request failed without one
+            seconds (float): Time the attempt took
+            headers (Mapping|None): Response headers, used to detect rate limiting
+        """
+        stats = self._stats(self.current)
+        stats.requests += 1
+        stats.latencies.append(seconds)
+        host = urlsplit(url).hostname
+        if host:
+            stats.hosts.add(host)
+        if status == 404:
+            stats.not_found += 1
+        elif status != 200:
+            stats.errors += 1
+            if is_rate_limited(status, headers):
+                stats.rate_limited += 1
+
+    def summary(self):
+        """
+        Returns:
+            dict: Ecosystem -> summary, for ecosystems with at least one package
+        """
+        return {ecosystem: self.ecosystems[ecosystem].summary() for ecosystem in sorted(self.ecosystems)}
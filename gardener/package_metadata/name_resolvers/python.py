@@ -1,5 +1,10 @@
 """
 Resolver for Python package imports
+
+Distribution names often differ from the modules they install (`opencv-python` installs `cv2`).
+Besides PyPI archive metadata and naming heuristics, a built-in map of well-known import names
+to their distributions, which `PYTHON_IMPORT_MAP` can extend or override, gives declared
+distributions the right import names even when the registry cannot be reached
 """
 
 import io
@@ -8,10 +13,120 @@ import tarfile
 import zipfile
 
 import requests
+import yaml
 
+from gardener.common.defaults import GraphAnalysisConfig as cfg
+from gardener.common.network import is_offline
 from gardener.common.secure_file_ops import FileOperationError
 from gardener.package_metadata.name_resolvers.base import BaseResolver
 
+# Built-in import name -> PyPI distribution map for modules named unlike their distribution
+DEFAULT_IMPORT_MAP = {
+    "attr": "attrs",
+    "Bio": "biopython",
+    "bs4": "beautifulsoup4",
+    "Crypto": "pycryptodome",
+    "cv2": "opencv-python",
+    "dateutil": "python-dateutil",
+    "docx": "python-docx",
+    "dotenv": "python-dotenv",
+    "fitz": "PyMuPDF",
+    "gi": "PyGObject",
+    "git": "GitPython",
+    "jose": "python-jose",
+    "jwt": "PyJWT",
+    "magic": "python-magic",
+    "multipart": "python-multipart",
+    "MySQLdb": "mysqlclient",
+    "nacl": "PyNaCl",
+    "OpenSSL": "pyOpenSSL",
+    "PIL": "Pillow",
+    "pkg_resources": "setuptools",
+    "pptx": "python-pptx",
+    "serial": "pyserial",
+    "skimage": "scikit-image",
+    "sklearn": "scikit-learn",
+    "slugify": "python-slugify",
+    "socketio": "python-socketio",
+    "telegram": "python-telegram-bot",
+    "usb": "pyusb",
+    "win32api": "pywin32",
+    "wx": "wxPython",
+    "yaml": "PyYAML",
+    "zmq": "pyzmq",
+}
+
+
+def _canonical_distribution(name):
+    """
+    PEP 503 normalized distribution name ('PyYAML' and 'pyyaml' are the same distribution)
+    """
+    return re.sub(r"[-_.]+", "-", name).lower()
+
+
+class ImportMapError(Exception):
+    """
+    Raised when the PYTHON_IMPORT_MAP file cannot be read or is not a mapping
+    """
+
+
+# Import maps already loaded, keyed by file path
+_LOADED_IMPORT_MAPS = {}
+
+
+def load_import_map(path=None):
+    """
+    Return the import name -> distribution map: the built-in entries, extended or overridden by a JSON/YAML file
+
+    The file maps import names to distribution names; mapping an import name to null removes its
+    built-in entry. Each file is read and validated once, then reused for every later lookup
+
+    Args:
+        path (str): Optional map file
+
+    Returns:
+        dict: Import name -> distribution name
+
+    Raises:
+        ImportMapError: If the file cannot be read or is not a mapping
+    """
+    if not path:
+        return DEFAULT_IMPORT_MAP
+    if path in _LOADED_IMPORT_MAPS:
+        return _LOADED_IMPORT_MAPS[path]
+    try:
+        with open(path, "r", encoding="utf-8") as handle:
+            overrides = yaml.safe_load(handle) or {}
+    except (OSError, yaml.YAMLError) as e:
+        raise ImportMapError(f"Configuration error: could not read PYTHON_IMPORT_MAP {path}: {e}")
+    if not isinstance(overrides, dict):
+        raise ImportMapError(f"Configuration error: PYTHON_IMPORT_MAP {path} must map import names to distributions")
+    import_map = dict(DEFAULT_IMPORT_MAP)
+    for import_name, distribution in overrides.items():
+        if distribution is None:
+            import_map.pop(str(import_name), None)
+        else:
+            import_map[str(import_name)] = str(distribution)
+    _LOADED_IMPORT_MAPS[path] = import_map
+    return import_map
+
+
+def mapped_import_names(dist_name, import_map=None):
+    """
+    Return the import names the map assigns to a distribution
+
+    Args:
+        dist_name (str): Distribution name as declared
+        import_map (dict): Optional import map; loaded from PYTHON_IMPORT_MAP when omitted
+
+    Returns:
+        list: Import names, sorted
+    """
+    if import_map is None:
+        import_map = load_import_map(cfg.PYTHON_IMPORT_MAP)
+    canonical = _canonical_distribution(dist_name)
+    return sorted(name for name, dist in import_map.items() if _canonical_distribution(dist) == canonical)
+
 
 class PythonResolver(BaseResolver):
     """
@@ -165,8 +280,8 @@ class PythonResolver(BaseResolver):
         Returns:
             list: List of possible import names for the package
         """
-        # First use the direct mapping approach
-        import_names = self._process_distribution_name(package_name)
+        # Known import names first, then the direct mapping approach
+        import_names = mapped_import_names(package_name) + self._process_distribution_name(package_name)
 
         # Also use PyPI metadata
         try:
@@ -189,6 +304,26 @@ class PythonResolver(BaseResolver):
         return deduplicated
 
 
+class CondaResolver(PythonResolver):
+    """
+    Resolver for conda channel packages, from known mappings and name heuristics without PyPI metadata
+    """
+
+    def resolve_package_imports(self, package_name, version=None, logger=None):
+        """
+        Resolve a conda package name to its possible import names
+
+        Args:
+            package_name (str): Name of the package in its conda channel
+            version (str): Unused; conda packages are not looked up on PyPI
+            logger (Logger): Unused
+
+        Returns:
+            list: List of possible import names for the package
+        """
+        return list(dict.fromkeys(mapped_import_names(package_name) + self._process_distribution_name(package_name)))
+
+
 def transform_package_name(pkg_name):
     """
     Transform a PyPI package name to a potential import name using heuristics
@@ -460,6 +595,9 @@ def resolve_python_import_names(package_name, version_override=None, logger=None
         List of resolved import names, or empty list if resolution fails
     """
     pypi_url = f"https://pypi.org/pypi/{package_name}/json"
+    if is_offline():
+        logger and logger.debug(f"Offline run; not fetching PyPI metadata for {package_name}")
+        return []
     logger and logger.debug(f"Fetching PyPI metadata for {package_name}")
 
     try:
@@ -0,0 +1,122 @@
+"""
+Warm-start URL resolution from a previous report
+
+`--seed previous-report.json` turns the repository URLs of an earlier run into a URL cache, so
+day-to-day runs only query registries for packages that are new or whose seeded URL has expired.
+An entry is as old as the run that resolved it: the report's modification time, or the time
+carried forward under `analyzer_details.seed.resolved_at` when that run was itself seeded
+"""
+
+import json
+import os
+from datetime import datetime, timedelta, timezone
+
+
+def _parse_timestamp(value):
+    """
+    Parse an ISO-8601 timestamp, returning None when absent or malformed
+
+    Args:
+        value (str|None): Timestamp string
+
+    Returns:
+        datetime|None: Timezone-aware timestamp
+    """
+    if not isinstance(value, str):
+        return None
+    try:
+        parsed = datetime.fromisoformat(value)
+    except ValueError:
+        return None
+    return parsed if parsed.tzinfo else parsed.replace(tzinfo=timezone.utc)
+
+
+class ResolutionSeed:
+    """
+    URL cache seeded from a previous report
+
+    Attributes:
+        path (str): Seed report path
+        ttl_days (float): Age after which seeded URLs are resolved again
+        cache (dict): URL cache keyed 'ecosystem:package_name'
+        resolved_at (dict): Cache key -> datetime the URL was resolved
+        expired (int): Entries dropped because they were older than the TTL
+    """
+
+    def __init__(self, path, ttl_days, cache=None, resolved_at=None, expired=0):
+        self.path = path
+        self.ttl_days = ttl_days
+        self.cache = cache or {}
+        self.resolved_at = resolved_at or {}
+        self.expired = expired
+
+    @classmethod
+    def load(cls, path, ttl_days, now=None, logger=None):
+        """
+        Load resolved repository URLs from a previous report, dropping expired ones
+
+        Packages without a URL (not found, or skipped by an enrichment budget) are not seeded, so
+        they are looked up again
+
+        Args:
+            path (str): Path to a previous `_dependency_analysis.json` report
+            ttl_days (float): Maximum age of a seeded URL in days
+            now (datetime): Optional current time, for tests
+            logger (Logger): Optional logger
+
+        Returns:
+            ResolutionSeed
+
+        Raises:
+            ValueError: If the report cannot be read or is not an analysis report
+        """
+        try:
+            with open(path, "r", encoding="utf-8") as f:
+                report = json.load(f)
+            report_time = datetime.fromtimestamp(os.path.getmtime(path), timezone.utc)
+        except (OSError, json.JSONDecodeError) as e:
+            raise ValueError(f"Could not read seed report {path}: {e}")
+        packages = report.get("external_packages") if isinstance(report, dict) else None
+        if not isinstance(packages, dict):
+            raise ValueError(f"Seed report {path} has no external_packages")
+
+        carried = ((report.get("analyzer_details") or {}).get("seed") or {}).get("resolved_at") or {}
+        cutoff = (now or datetime.now(timezone.utc)) - timedelta(days=ttl_days)
+        seed = cls(path, ttl_days)
+        for name, info in packages.items():
+            if not isinstance(info, dict) or not info.get("repository_url") or not info.get("ecosystem"):
+                continue
+            key = f"{info['ecosystem']}:{name}"
+            resolved_at = _parse_timestamp(carried.get(key)) or report_time
+            if resolved_at < cutoff:
+                seed.expired += 1
+                continue
+            seed.cache[key] = info["repository_url"]
+            seed.resolved_at[key] = resolved_at
+
+        if logger:
+            logger.info(
+                f"Seeded {len(seed.cache)} repository URLs from {path} ({seed.expired} older than {ttl_days} days)"
+            )
+        return seed
+
+    def summary(self, external_packages):
+        """
+        Summarize seed usage for a finished run
+
+        Args:
+            external_packages (dict): External packages of the run
+
+        Returns:
+            dict: report, ttl_days, entries, expired, reused and the resolved_at time of each reused URL
+        """
+        keys = {f"{info.get('ecosystem')}:{name}" for name, info in external_packages.items()}
+        reused = sorted(keys & self.cache.keys())
+        return {
+            "report": self.path,
+            "ttl_days": self.ttl_days,
+            "entries": len(self.cache),
+            "expired": self.expired,
+            "reused": len(reused),
+            "resolved_at": {key: self.resolved_at[key].isoformat() for key in reused},
+        }
@@ -0,0 +1,246 @@
+"""
+Trust-on-first-use fingerprint pinning for registry hosts
+
+URL resolution trusts whatever the registries answer. RegistryPins adds a lightweight, opt-in check
+on the TLS layer (PIN_REGISTRIES, `--pin-registries`): the first time a run talks to a registry host
+it records the SHA-256 fingerprints of the host's certificate and public key (its
+SubjectPublicKeyInfo) in REGISTRY_PINS_FILE under CACHE_DIR, and later runs compare against them.
+A new certificate for the same key (routine renewal) just updates the pin; a new public key is
+reported as a warning, or aborts the run with STRICT_NETWORK (`--strict-network`). The old pin is
+kept until its entry is removed from the file, so an unexpected key keeps being reported. A pins
+file that cannot be read is treated the same way, and left untouched. Each host is checked once
+per run, with a separate handshake before its first request
+"""
+
+import contextlib
+import datetime
+import hashlib
+import json
+import os
+import socket
+import ssl
+import tempfile
+
+HANDSHAKE_TIMEOUT = 10  # seconds
+
+
+class PinMismatchError(Exception):
+    """
+    Raised in strict mode when a registry presents a public key other than the pinned one
+    """
+
+
+class PinsFileError(PinMismatchError):
+    """
+    Raised in strict mode when the pins file exists but cannot be read, so no pin can be checked
+    """
+
+
+def _der_element(data, offset):
+    """
+    Read the DER element at an offset
+
+    Returns:
+        tuple: (tag, content start, element end)
+    """
+    tag, length = data[offset], data[offset + 1]
+    start = offset + 2
+    if length & 0x80:
+        size = length & 0x7F
+        length = int.from_bytes(data[start : start + size], "big")
+        start += size
+    return tag, start, start + length
+
+
+def subject_public_key_info(der):
+    """
+    Extract the DER-encoded SubjectPublicKeyInfo from a DER certificate
+
+    Args:
+        der (bytes): Certificate in DER form
+
+    Returns:
+        bytes: The SubjectPublicKeyInfo element, header included
+
+    Raises:
+        ValueError: The certificate is not a well-formed X.509 structure
+    """
+    try:
+        _, cert_start, _ = _der_element(der, 0)
+        _, offset, tbs_end = _der_element(der, cert_start)
+        fields = []
+        while offset < tbs_end and len(fields) < 7:
+            tag, _, end = _der_element(der, offset)
+            fields.append((tag, offset, end))
+            offset = end
+    except IndexError as exc:
+        raise ValueError("truncated certificate") from exc
+    # tbsCertificate: [0] version (optional), serial, signature, issuer, validity, subject, subjectPublicKeyInfo
+    if fields and fields[0][0] == 0xA0:
+        fields = fields[1:]
+    if len(fields) < 6 or fields[5][0] != 0x30:
+        raise ValueError("certificate has no subjectPublicKeyInfo")
+    _, start, end = fields[5]
+    return der[start:end]
+
+
+def certificate_fingerprints(der):
+    """
+    Compute the SHA-256 fingerprints of a certificate and of its public key
+
+    Args:
+        der (bytes): Certificate in DER form
+
+    Returns:
+        dict: {'certificate', 'public_key'} as hex digests
+    """
+    return {
+        "certificate": hashlib.sha256(der).hexdigest(),
+        "public_key": hashlib.sha256(subject_public_key_info(der)).hexdigest(),
+    }
+
+
+def fetch_fingerprints(host, port=443):
+    """
+    Perform a TLS handshake with a host and fingerprint the certificate it presents
+
+    Args:
+        host (str): Host name
+        port (int): TLS port
+
+    Returns:
+        dict: {'certificate', 'public_key'} as hex digests
+    """
+    context = ssl.create_default_context()
+    with socket.create_connection((host, port), timeout=HANDSHAKE_TIMEOUT) as sock:
+        with context.wrap_socket(sock, server_hostname=host) as tls:
+            return certificate_fingerprints(tls.getpeercert(binary_form=True))
+
+
+class RegistryPins:
+    """
+    Pinned registry fingerprints, checked once per host per run
+
+    Attributes:
+        path (str): JSON file holding the pins
+        strict (bool): Raise PinMismatchError on a changed public key instead of warning
+        pins (dict): Host -> {'certificate', 'public_key', 'first_seen', 'last_seen'}
+        checked (dict): Host -> outcome this run: 'pinned', 'match', 'renewed', 'changed' or 'unavailable'
+    """
+
+    def __init__(self, path, strict=False, fetch=None, logger=None):
+        """
+        Args:
+            path (str): JSON file holding the pins
+            strict (bool): Fail closed on a changed public key or an unreadable pins file
+            fetch (callable): Optional fn(host) -> fingerprints, fetch_fingerprints by default
+            logger (Logger): Optional logger for an unreadable pins file
+
+        Raises:
+            PinsFileError: In strict mode, when the pins file exists but cannot be read
+        """
+        self.path = os.path.expanduser(path)
+        self.strict = strict
+        self.fetch = fetch or fetch_fingerprints
+        self.pins = {}
+        self.checked = {}
+        # An unreadable file is never overwritten: its pins may still be the ones to trust
+        self.writable = True
+        try:
+            with open(self.path, "r", encoding="utf-8") as handle:
+                data = json.load(handle)
+            if not isinstance(data, dict):
+                raise ValueError("expected a JSON object of hosts")
+            self.pins = {host: pin for host, pin in data.items() if isinstance(pin, dict)}
+        except FileNotFoundError:
+            pass
+        except (OSError, ValueError) as exc:
+            message = f"Registry pins file {self.path} cannot be read: {exc}"
+            if strict:
+                raise PinsFileError(message) from exc
+            logger and logger.warning(f"{message}; pins are not checked against it or saved this run")
+            self.writable = False
+
+    def _save(self):
+        """
+        Write the pins atomically, so an interrupted run never leaves a truncated file
+        """
+        if not self.writable:
+            return
+        directory = os.path.dirname(self.path) or "."
+        os.makedirs(directory, exist_ok=True)
+        fd, temp_path = tempfile.mkstemp(dir=directory, prefix=".registry_pins.", suffix=".tmp")
+        try:
+            with os.fdopen(fd, "w", encoding="utf-8") as handle:
+                json.dump(self.pins, handle, indent=2, sort_keys=True)
+            os.replace(temp_path, self.path)
+        except BaseException:
+            with contextlib.suppress(OSError):
+                os.unlink(temp_path)
+            raise
+
+    def check(self, host, logger=None):
+        """
+        Compare a host's fingerprints with its pin, pinning it on first use
+
+        Args:
+            host (str): Registry host name
+            logger (Logger|None): Optional logger
+
+        Returns:
+            str: Outcome for the host ('pinned', 'match', 'renewed', 'changed' or 'unavailable')
+
+        Raises:
+            PinMismatchError: In strict mode, when the host's public key differs from the pinned one
+        """
+        if host in self.checked:
+            if self.strict and self.checked[host] == "changed":
+                raise PinMismatchError(f"TLS public key of {host} does not match its pin in {self.path}")
+            return self.checked[host]
+        if not self.writable:
+            self.checked[host] = "unavailable"
+            return "unavailable"
+        try:
+            seen = self.fetch(host)
+        except (OSError, ValueError) as exc:
+            # The request itself will fail and be reported the usual way if the host is unreachable
+            logger and logger.debug(f"Could not fingerprint {host}: {exc}")
+            self.checked[host] = "unavailable"
+            return "unavailable"
+
+        now = datetime.datetime.now(datetime.timezone.utc).isoformat(timespec="seconds")
+        pin = self.pins.get(host)
+        if pin is None:
+            outcome = "pinned"
+            self.pins[host] = {**seen, "first_seen": now, "last_seen": now}
+        elif pin.get("public_key") != seen["public_key"]:
+            outcome = "changed"
+        else:
+            outcome = "match" if pin.get("certificate") == seen["certificate"] else "renewed"
+            pin.update(certificate=seen["certificate"], last_seen=now)
+        self.checked[host] = outcome
+
+        if outcome == "changed":
+            message = (
+                f"TLS public key of {host} changed since it was pinned on {pin.get('first_seen', 'first use')} "
+                f"(pinned {pin.get('public_key')}, now {seen['public_key']}); remove its entry from "
+                f"{self.path} if the change is expected"
+            )
+            if self.strict:
+                raise PinMismatchError(message)
+            logger and logger.warning(message)
+            return outcome
+        if outcome == "pinned":
+            logger and logger.debug(f"Pinned TLS public key of {host}")
+        try:
+            self._save()
+        except OSError as exc:
+            logger and logger.warning(f"Could not save registry pins to {self.path}: {exc}")
+        return outcome
+
+    def summary(self):
+        """
+        Returns:
+            dict: {'file', 'hosts': {host: outcome}} for the hosts checked this run
+        """
+        return {"file": self.path, "hosts": dict(sorted(self.checked.items()))}
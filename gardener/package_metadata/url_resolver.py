@@ -7,6 +7,11 @@ import re
 import time
 import urllib.error
 import urllib.request
+from urllib.parse import quote, urlparse
+
+from gardener.common.network import NetworkDisabledError, ensure_online, is_offline
+from gardener.package_metadata.enrichment import SKIPPED_BUDGET, SKIPPED_CANCELLED
+from gardener.package_metadata.tls_pins import PinMismatchError
 
 try:
     from gardener.common.input_validation import InputValidator, ValidationError
@@ -21,6 +26,10 @@ USER_AGENT = "Gardener/0.1 (https://drips.network)"
 REQUEST_TIMEOUT = 10  # seconds
 RETRY_COUNT = 3
 RETRY_DELAY = 1  # seconds (initial delay)
+# crates.io category used by procedural macro crates
+CARGO_PROC_MACRO_CATEGORY = "development-tools::procedural-macro-helpers"
+# Sparse index file probed to tell an unreachable crates.io from a missing crate
+CRATES_SPARSE_INDEX_CONFIG = "https://index.crates.io/config.json"
 
 # Allowed registry domains
 ALLOWED_REGISTRY_DOMAINS = {
@@ -28,15 +37,37 @@ ALLOWED_REGISTRY_DOMAINS = {
     "pypi.org",
     "files.pythonhosted.org",
     "crates.io",
+    "index.crates.io",
     "proxy.golang.org",
     "api.github.com",
     "raw.githubusercontent.com",
+    "api.jsr.io",
+    "apiland.deno.dev",
+    "artifacthub.io",
+    "trunk.cocoapods.org",
+    "formulae.brew.sh",
 }
 
 # Optional request hook for testing. When set via set_request_fn, functions should
 # call this to obtain raw response bytes for the given URL instead of performing
 # real network I/O. The function signature is: fn(url: str) -> bytes | str | None
 _REQUEST_FN = None
+# EnrichmentBudget charged for each outbound request while resolve_package_urls runs with a budget
+_ACTIVE_BUDGET = None
+# ResolutionHealth recording each outbound request while resolve_package_urls runs with one
+_ACTIVE_HEALTH = None
+# RegistryPins checking each registry host's TLS fingerprints while resolve_package_urls runs with them
+_ACTIVE_PINS = None
+# CratesIndex caching crates.io metadata while resolve_package_urls runs with one
+_ACTIVE_CRATES_INDEX = None
+# go-import results while resolve_package_urls runs: import prefix -> repository URL, fetched path -> None
+_ACTIVE_GO_IMPORTS = None
+# Credentials (github_auth.TokenAuth / GitHubAppAuth) sent with requests to GITHUB_HOSTS
+_GITHUB_AUTH = None
+# Hosts whose requests carry the GitHub credentials: the API (including the App token exchange),
+# go-get pages of github.com paths and raw file downloads. The header is not copied onto redirects, so
+# no other host ever receives them
+GITHUB_HOSTS = {"api.github.com", "github.com", "raw.githubusercontent.com"}
 
 
 def set_request_fn(fn):
@@ -50,15 +81,27 @@ def set_request_fn(fn):
     _REQUEST_FN = fn
 
 
+def set_github_auth(auth):
+    """
+    Set the credentials sent with requests to GITHUB_HOSTS
+
+    Args:
+        auth (TokenAuth|GitHubAppAuth|None): Credentials, or None for unauthenticated requests
+    """
+    global _GITHUB_AUTH
+    _GITHUB_AUTH = auth
+
+
 # Module-internal regex patterns for repository URL parsing
 # Underscore-prefixed to indicate non-public API usage
 _RE_GH_OWNER_REPO_COLON_OR_SLASH = re.compile(r"github\.com[:/]([^/\s]+/[^/\s]+?)(?:\.git)?(?:\s|$)")
 _RE_GH_OWNER_REPO_SLASH = re.compile(r"github\.com/([^/]+/[^/]+)")
 _RE_GH_PAGES = re.compile(r"https?://([^/]+)\.github\.io/([^/]+)")
 _RE_GL_PAGES = re.compile(r"https?://([^/]+)\.gitlab\.io/([^/]+)")
-_RE_GO_IMPORT_META = re.compile(
-    r'<meta\s+name=["\']go-import["\']\s+content=["\']([^ ]+)\s+(git|hg|svn|bzr)\s+([^"\']+)["\']', re.IGNORECASE
-)
+_RE_HTML_META = re.compile(r"<meta\s[^>]*>", re.IGNORECASE)
+_RE_HTML_ATTR = re.compile(r"""([a-zA-Z-]+)\s*=\s*(?:"([^"]*)"|'([^']*)')""")
+# gopkg.in/pkg.v3 and gopkg.in/user/pkg.v3 serve github.com/go-pkg/pkg and github.com/user/pkg
+_RE_GOPKG_IN = re.compile(r"^gopkg\.in/(?:([a-zA-Z0-9_-]+)/)?([a-zA-Z0-9_.-]+?)\.v\d+(?:/|$)")
 _RE_GH_CANONICAL = re.compile(r"(https?://(?:www\.)?github\.com/[^/]+/[^/]+)")
 _RE_OWNER_REPO_SHORTHAND = re.compile(r"^[a-zA-Z0-9_-]+/[a-zA-Z0-9_.-]+$")
 
@@ -86,17 +129,37 @@ def _validate_or_none(url, logger=None):
         return None
 
 
-def _request_once(url, logger=None):
+def _github_auth_for(url):
+    """
+    Return the GitHub credentials to send with a request to url, or None
+
+    Args:
+        url (str): Request URL
+
+    Returns:
+        TokenAuth|GitHubAppAuth|None
+    """
+    return _GITHUB_AUTH if urlparse(url).hostname in GITHUB_HOSTS else None
+
+
+def _request_once(url, logger=None, method="GET", headers=None):
     """
-    Perform a single HTTP GET with headers and decode JSON if status == 200
+    Perform a single HTTP request with headers and decode JSON if status is 200 or 201
+
+    Requests to GITHUB_HOSTS carry the configured GitHub credentials unless headers already set an
+    Authorization (as the App token exchange does with its JWT)
 
     Args:
         url (str): Validated URL
         logger: Optional logger
+        method (str): HTTP method; a POST sends an empty body
+        headers (dict): Optional extra request headers
 
     Returns:
         tuple: (status_code_or_None, json_dict_or_None, exception_or_None)
     """
+    if _ACTIVE_BUDGET is not None:
+        _ACTIVE_BUDGET.charge_request()
     # If a request hook is provided, use it to get raw content (JSON expected here)
     if _REQUEST_FN is not None:
         try:
@@ -111,16 +174,51 @@ def _request_once(url, logger=None):
         except Exception as e:
             return None, None, e
 
-    req = urllib.request.Request(url, headers={"User-Agent": USER_AGENT})
-    with urllib.request.urlopen(req, timeout=REQUEST_TIMEOUT) as response:
-        if response.status == 200:
-            return 200, json.loads(response.read().decode("utf-8")), None
+    ensure_online(url)
+    if _ACTIVE_PINS is not None:
+        _ACTIVE_PINS.check(urlparse(url).hostname, logger)
+    headers = {"User-Agent": USER_AGENT, **(headers or {})}
+    authorization = headers.pop("Authorization", None)
+    github_auth = None if authorization is not None else _github_auth_for(url)
+    if github_auth is not None:
+        authorization = github_auth.authorization()
+    data = b"" if method == "POST" else None
+    req = urllib.request.Request(url, data=data, headers=headers, method=method)
+    if authorization is not None:
+        # urllib copies ordinary headers onto a redirect to any host; unredirected ones stay on this request
+        req.add_unredirected_header("Authorization", authorization)
+    try:
+        response = urllib.request.urlopen(req, timeout=REQUEST_TIMEOUT)
+    except urllib.error.HTTPError as e:
+        if e.code == 401 and github_auth is not None:
+            # Revoked or expired early; the retry in _make_request mints a fresh token
+            github_auth.invalidate()
+        raise
+    with response:
+        if response.status in (200, 201):
+            return response.status, json.loads(response.read().decode("utf-8")), None
         if response.status == 404:
             return 404, None, None
         http_err = urllib.error.HTTPError(url, response.status, response.reason, response.headers, None)
         return response.status, None, http_err
 
 
+def _record_request(url, status, started, error=None):
+    """
+    Record a request attempt with the active ResolutionHealth, if any
+
+    Args:
+        url (str): Requested URL
+        status (int|None): HTTP status, or None to take it from the error
+        started (float): time.monotonic() when the attempt started
+        error (Exception|None): Failure of the attempt; an HTTPError supplies its code and headers
+    """
+    if _ACTIVE_HEALTH is not None:
+        if status is None:
+            status = getattr(error, "code", None)
+        _ACTIVE_HEALTH.record_request(url, status, time.monotonic() - started, getattr(error, "headers", None))
+
+
 def _make_request(url, logger=None):
     """
     Make an HTTP GET request with retries, security validation, and proper headers
@@ -135,35 +233,58 @@ def _make_request(url, logger=None):
     Returns:
         JSON response data as dict, or None if request fails or returns 404
     """
+    return _fetch_json(url, logger)[0]
+
+
+def _fetch_json(url, logger=None):
+    """
+    Make a request like _make_request, telling a missing resource apart from a failed request
+
+    Args:
+        url (str): URL to request (must be from allowed domains)
+        logger (Logger): Optional logger instance
+
+    Returns:
+        tuple: (JSON response data or None, True when the registry answered 404)
+    """
     # Validate URL for security
     validated = _validate_or_none(url, logger)
     if validated is None:
-        return None
+        return None, False
     url = validated
 
     last_exception = None
     delay = RETRY_DELAY
     for attempt in range(RETRY_COUNT + 1):
+        started = time.monotonic()
         try:
             status, data, single_error = _request_once(url, logger)
+            _record_request(url, status, started, single_error)
             if status == 200:
-                return data
+                return data, False
             if status == 404:
                 logger and logger.debug(f"Package not found (404): {url}")
-                return None
+                return None, True
             if status is not None:
                 last_exception = single_error
                 logger and logger.warning(f"HTTP error {status} for {url} (attempt {attempt + 1}/{RETRY_COUNT + 1})")
 
+        except PinMismatchError:
+            raise
+        except NetworkDisabledError as e:
+            logger and logger.debug(str(e))
+            return None, False
         except urllib.error.HTTPError as e:
+            _record_request(url, e.code, started, e)
             last_exception = e
             if e.code == 404:
                 logger and logger.debug(f"Package not found (404): {url}")
-                return None  # Explicitly return None on 404
+                return None, True
             logger and logger.warning(
                 f"HTTP error {e.code} for {url} " f"(attempt {attempt + 1}/{RETRY_COUNT + 1}): {e.reason}"
             )
         except Exception as e:
+            _record_request(url, None, started, e)
             last_exception = e
             logger and logger.warning(f"Error fetching {url} (attempt {attempt + 1}/{RETRY_COUNT + 1}): {e}")
 
@@ -173,7 +294,7 @@ def _make_request(url, logger=None):
             delay *= 2  # Exponential backoff
 
     logger and logger.error(f"Failed to fetch {url} after {RETRY_COUNT + 1} attempts. Last error: {last_exception}")
-    return None
+    return None, False
 
 
 def _strip_fragment(url_str):
@@ -301,21 +422,71 @@ def _clean_repo_url(repo_url):
 
 # Main resolution logic:
 
+# Ecosystems whose resolution needs registry requests (and therefore enrichment budget)
+_REGISTRY_ECOSYSTEMS = {"npm", "pypi", "cargo", "go", "jsr", "deno", "helm", "cocoapods", "homebrew", "solidity"}
+
 
-def resolve_package_urls(packages_dict, logger=None, cache=None):
+def resolve_package_urls(
+    packages_dict,
+    logger=None,
+    cache=None,
+    budget=None,
+    health=None,
+    cancellation=None,
+    pins=None,
+    crates_index=None,
+):
     """
     Resolve package names to repository URLs for all ecosystems
 
+    Packages are resolved in dict order. With a budget, cache hits and .gitmodules URLs are still
+    used once it runs out, but packages that would need a registry request are marked
+    `enrichment: skipped-budget` instead; once cancellation is requested they are marked
+    `enrichment: skipped-cancelled`
+
     Args:
         packages_dict (dict): Dictionary of packages to resolve
         logger (Logger): Optional logger instance
         cache (dict): Optional pre-populated dictionary for URL caching
+        budget (EnrichmentBudget): Optional request/time allowance for registry lookups
+        health (ResolutionHealth): Optional recorder of per-ecosystem request and cache statistics
+        cancellation (Cancellation): Optional flag checked before each registry lookup
+        pins (RegistryPins): Optional TLS fingerprint pins checked before the first request to each host
+        crates_index (CratesIndex): Optional crates.io metadata cache, the fallback for crates that cannot be fetched
+
+    Returns:
+        Dictionary containing resolved package URLs
+
+    Raises:
+        PinMismatchError: With strict pins, when a registry presents a public key other than the pinned one
+    """
+    global _ACTIVE_BUDGET, _ACTIVE_HEALTH, _ACTIVE_PINS, _ACTIVE_CRATES_INDEX, _ACTIVE_GO_IMPORTS
+    if budget is not None:
+        budget.start()
+    _ACTIVE_BUDGET = budget
+    _ACTIVE_HEALTH = health
+    _ACTIVE_PINS = pins
+    _ACTIVE_CRATES_INDEX = crates_index
+    _ACTIVE_GO_IMPORTS = {}
+    try:
+        return _resolve_package_urls(packages_dict, logger, cache or {}, budget, health, cancellation)
+    finally:
+        _ACTIVE_BUDGET = None
+        _ACTIVE_HEALTH = None
+        _ACTIVE_PINS = None
+        _ACTIVE_CRATES_INDEX = None
+        _ACTIVE_GO_IMPORTS = None
+
+
+def _resolve_package_urls(packages_dict, logger, cache, budget, health=None, cancellation=None):
+    """
+    Resolve URLs for resolve_package_urls, skipping registry lookups once the budget is exhausted
+    or cancellation is requested
 
     Returns:
         Dictionary containing resolved package URLs
     """
     resolved_urls = {}
-    cache = cache or {}
 
     def _is_solidity_alias_like(name):
         """
@@ -338,6 +509,8 @@ def resolve_package_urls(packages_dict, logger=None, cache=None):
 
         # Check cache first
         cache_key = f"{ecosystem}:{package_name}"
+        if health is not None:
+            health.begin_package(ecosystem, cached=cache_key in cache)
         if cache_key in cache:
             resolved_urls[package_name] = cache[cache_key]
             logger and logger.debug(f"Resolved {package_name} from cache -> {cache[cache_key]}")
@@ -351,6 +524,23 @@ def resolve_package_urls(packages_dict, logger=None, cache=None):
                 url = cleaned_gitmodules_url
                 logger and logger.info(f"Resolved {package_name} using .gitmodules URL: {url}")
 
+        if not url and ecosystem == "cargo":
+            if package_data.get("local_path"):
+                # `{ path = "..." }` crates are local code, not the same-named crates.io package
+                logger and logger.debug(f"Skipping URL resolution for local Cargo path dependency {package_name}")
+                continue
+            url = cargo_git_url(package_data)
+            if url:
+                logger and logger.debug(f"Resolved {package_name} from its Cargo git source: {url}")
+
+        if not url and cancellation is not None and ecosystem in _REGISTRY_ECOSYSTEMS and cancellation.requested:
+            package_data["enrichment"] = SKIPPED_CANCELLED
+            continue
+
+        if not url and budget is not None and ecosystem in _REGISTRY_ECOSYSTEMS and budget.exhausted():
+            package_data["enrichment"] = SKIPPED_BUDGET
+            continue
+
         # If URL was not resolved from gitmodules, proceed with ecosystem-specific resolution
         if not url:
             try:
@@ -359,9 +549,27 @@ def resolve_package_urls(packages_dict, logger=None, cache=None):
                 elif ecosystem == "pypi":
                     url = resolve_pypi_package(package_name, logger)
                 elif ecosystem == "cargo":
-                    url = resolve_cargo_package(package_name, logger)
+                    url = resolve_cargo_package(package_name, logger, package_data)
                 elif ecosystem == "go":
                     url = resolve_go_package(package_name, logger)
+                elif ecosystem == "jsr":
+                    url = resolve_jsr_package(package_name, logger)
+                elif ecosystem == "deno":
+                    url = resolve_deno_module(package_name, logger)
+                elif ecosystem == "github":
+                    url = f"https://{package_name}"
+                elif ecosystem == "cocoapods":
+                    url = _clean_repo_url(package_data.get("source")) or resolve_cocoapods_pod(
+                        package_name, package_data.get("locked_version"), logger
+                    )
+                elif ecosystem == "carthage":
+                    url = None if package_data.get("binary") else _clean_repo_url(package_data.get("source"))
+                elif ecosystem == "homebrew":
+                    url = resolve_homebrew_package(package_name, package_data, logger)
+                elif ecosystem == "helm":
+                    url = resolve_helm_chart(
+                        package_data.get("chart") or package_name, package_data.get("repository"), logger
+                    )
                 elif ecosystem == "solidity":
                     # Solidity often uses npm. Avoid lookups for alias-like names.
                     if not _is_solidity_alias_like(package_name):
@@ -369,6 +577,8 @@ def resolve_package_urls(packages_dict, logger=None, cache=None):
                     if not url:
                         # Placeholder for potential Etherscan/Sourcegraph resolution
                         url = resolve_solidity_contract(package_name, package_data.get("source"), logger)
+            except PinMismatchError:
+                raise
             except Exception as e:
                 logger and logger.warning(f"Error resolving URL for {package_name} ({ecosystem}): {e}")
 
@@ -780,8 +990,14 @@ def _cargo_fetch_metadata(package_name, logger=None):
     Returns:
         dict or None
     """
-    url = f"https://crates.io/api/v1/crates/{package_name}"
-    return _make_request(url, logger)
+    return _make_request(_cargo_crate_url(package_name), logger)
+
+
+def _cargo_crate_url(package_name):
+    """
+    Return the crates.io API URL of a crate
+    """
+    return f"https://crates.io/api/v1/crates/{package_name}"
 
 
 def _cargo_from_repository(crate):
@@ -841,21 +1057,98 @@ def _cargo_from_documentation(crate):
     return None
 
 
-def resolve_cargo_package(package_name, logger=None):
+def _cargo_is_proc_macro(data):
+    """
+    Return True when crates.io metadata marks a crate as a procedural macro
+
+    The registry API does not expose `[lib] proc-macro`; crates publish it through the
+    'development-tools::procedural-macro-helpers' category or a 'proc-macro' keyword
+
+    Args:
+        data (dict): crates.io crate response
+
+    Returns:
+        bool
+    """
+    crate = data.get("crate") or {}
+    categories = crate.get("categories") or [c.get("id") for c in data.get("categories") or [] if isinstance(c, dict)]
+    keywords = crate.get("keywords") or [k.get("id") for k in data.get("keywords") or [] if isinstance(k, dict)]
+    return CARGO_PROC_MACRO_CATEGORY in categories or any(k in ("proc-macro", "proc_macro") for k in keywords)
+
+
+def cargo_git_url(package_data):
+    """
+    Return the repository URL of a Cargo git dependency, from Cargo.toml or the Cargo.lock source
+
+    Args:
+        package_data (dict): Package metadata with 'git_url' or a 'git+<url>?rev=...#<commit>' source
+
+    Returns:
+        Cleaned repository URL string, or None for registry crates
+    """
+    git_url = package_data.get("git_url")
+    source = package_data.get("source")
+    if not git_url and isinstance(source, str) and source.startswith("git+"):
+        git_url = source[len("git+") :].split("#", 1)[0].split("?", 1)[0]
+    return _clean_repo_url(git_url) if isinstance(git_url, str) else None
+
+
+def _cargo_fetch_or_cached(package_name, logger=None, package_data=None):
+    """
+    Fetch crates.io metadata, falling back to the active CratesIndex cache and snapshot
+
+    A 404 from crates.io is cached as a missing crate and never triggers the reachability probe; a
+    crate cached as missing is answered as not found, without a probe or a snapshot lookup. Once
+    the sparse index is found unreachable, crates are answered from the cache without requests
+
+    Returns:
+        dict or None
+    """
+    index = _ACTIVE_CRATES_INDEX
+    if index is None:
+        return _cargo_fetch_metadata(package_name, logger)
+    if index.online is not False:
+        data, not_found = _fetch_json(_cargo_crate_url(package_name), logger)
+        if data:
+            index.record(package_name, data)
+            return data
+        # Request hooks (tests, bundles) answer every request; only real runs can be offline
+        if _REQUEST_FN is None:
+            if not_found:
+                index.record_missing(package_name)
+                return None
+            if index.is_missing(package_name):
+                return None
+            if index.online is None:
+                index.online = _make_request(CRATES_SPARSE_INDEX_CONFIG, logger) is not None
+                if not index.online:
+                    logger and logger.warning("index.crates.io is unreachable; resolving crates from the cached index")
+    data, source = index.lookup(package_name)
+    if data and package_data is not None:
+        package_data["index_fallback"] = source
+    return data
+
+
+def resolve_cargo_package(package_name, logger=None, package_data=None):
     """
     Resolve Cargo crate to repository URL
 
     Args:
         package_name (str): The Cargo crate name to resolve
         logger (Logger): Optional logger instance
+        package_data (dict): Optional package metadata; tagged `role: proc-macro` when the registry
+            lists the crate as a procedural macro, and `index_fallback` when answered from the crates.io cache
 
     Returns:
         Repository URL string or None if not found
     """
-    data = _cargo_fetch_metadata(package_name, logger)  # User-Agent is handled by helper
+    data = _cargo_fetch_or_cached(package_name, logger, package_data)  # User-Agent is handled by helper
 
     if data:
         crate = data.get("crate", {})
+        if package_data is not None and "role" not in package_data and _cargo_is_proc_macro(data):
+            package_data["role"] = "proc-macro"
+            package_data["role_source"] = "registry"
 
         # 1. Try repository field
         repository = _cargo_from_repository(crate)
@@ -905,53 +1198,165 @@ def _go_meta_tag_fetch_url(package_name):
     return f"https://{package_name}?go-get=1"
 
 
-def _go_meta_tag_repo_url(fetch_url, logger=None):
+def _go_gopkg_in_repo(package_name):
     """
-    Request page and extract repo URL from go-import meta tag using _RE_GO_IMPORT_META
-    Return cleaned URL or None
+    Return the GitHub repository behind a gopkg.in path, whose own go-import tag points back at gopkg.in
+
+    Args:
+        package_name (str): Go import path
+
+    Returns:
+        str or None: 'https://github.com/<user>/<pkg>' or None
+    """
+    match = _RE_GOPKG_IN.match(package_name)
+    if not match:
+        return None
+    user, pkg = match.groups()
+    return f"https://github.com/{user or 'go-' + pkg}/{pkg}"
+
+
+def _go_import_metas(content):
+    """
+    Return the (import prefix, vcs, repo root) of every go-import meta tag in an HTML page, in any attribute order
+
+    Args:
+        content (str): HTML page
+
+    Returns:
+        list: (prefix, vcs, repo) tuples; `mod` entries, which point at module proxies, are skipped
+    """
+    metas = []
+    for tag in _RE_HTML_META.findall(content):
+        attrs = {name.lower(): double or single for name, double, single in _RE_HTML_ATTR.findall(tag)}
+        if attrs.get("name", "").lower() != "go-import":
+            continue
+        fields = attrs.get("content", "").split()
+        if len(fields) == 3 and fields[1] != "mod":
+            metas.append(tuple(fields))
+    return metas
+
+
+def _go_import_match(package_name, metas):
+    """
+    Pick the go-import entry whose prefix is the package path or one of its parents (the longest)
+
+    Args:
+        package_name (str): Go import path
+        metas (list): Result of _go_import_metas
+
+    Returns:
+        tuple or None: (prefix, vcs, repo)
+    """
+    matching = [meta for meta in metas if package_name == meta[0] or package_name.startswith(meta[0] + "/")]
+    return max(matching, key=lambda meta: len(meta[0])) if matching else None
+
+
+def _go_cached_import(package_name):
+    """
+    Look a path up in the go-import results of this run: a cached prefix covers the paths below it
+
+    Args:
+        package_name (str): Go import path
+
+    Returns:
+        tuple: (found, repository URL or None)
+    """
+    if _ACTIVE_GO_IMPORTS is None:
+        return False, None
+    parts = package_name.split("/")
+    for end in range(len(parts), 0, -1):
+        prefix = "/".join(parts[:end])
+        if prefix in _ACTIVE_GO_IMPORTS and (_ACTIVE_GO_IMPORTS[prefix] or end == len(parts)):
+            return True, _ACTIVE_GO_IMPORTS[prefix]
+    return False, None
+
+
+def _go_fetch_page(fetch_url):
+    """
+    Request a go-get=1 page
 
     Args:
         fetch_url (str): Validated URL to fetch
-        logger: Optional logger
 
     Returns:
-        str or None
+        str or None: Page content, or None when the request failed or did not return 200
     """
+    if _ACTIVE_BUDGET is not None:
+        _ACTIVE_BUDGET.charge_request()
+    started = time.monotonic()
     # Use hook if present to fetch HTML content
     if _REQUEST_FN is not None:
         try:
             raw = _REQUEST_FN(fetch_url)
+            _record_request(fetch_url, 404 if raw is None else 200, started)
             if raw is None:
                 return None
             if isinstance(raw, bytes):
-                content = raw.decode("utf-8", errors="ignore")
-            else:
-                content = str(raw)
-            match = _RE_GO_IMPORT_META.search(content)
-            if match:
-                repo_url = _clean_repo_url(match.group(3))
-                if repo_url:
-                    return repo_url
-            return None
-        except Exception:
+                return raw.decode("utf-8", errors="ignore")
+            return str(raw)
+        except Exception as e:
+            _record_request(fetch_url, None, started, e)
             return None
 
+    if is_offline():
+        return None
     req = urllib.request.Request(fetch_url, headers={"User-Agent": USER_AGENT})
-    with urllib.request.urlopen(req, timeout=REQUEST_TIMEOUT) as response:
+    github_auth = _github_auth_for(fetch_url)
+    if github_auth is not None:
+        req.add_unredirected_header("Authorization", github_auth.authorization())
+    try:
+        response = urllib.request.urlopen(req, timeout=REQUEST_TIMEOUT)
+    except urllib.error.HTTPError as e:
+        _record_request(fetch_url, e.code, started, e)
+        if e.code == 401 and github_auth is not None:
+            github_auth.invalidate()
+        raise
+    except Exception as e:
+        _record_request(fetch_url, None, started, e)
+        raise
+    with response:
+        _record_request(fetch_url, response.status, started)
         if response.status == 200:
-            content = response.read().decode("utf-8", errors="ignore")
-            match = _RE_GO_IMPORT_META.search(content)
-            if match:
-                repo_url = _clean_repo_url(match.group(3))
-                if repo_url:
-                    return repo_url
+            return response.read().decode("utf-8", errors="ignore")
     return None
 
 
+def _go_meta_tag_repo_url(fetch_url, logger=None, package_name=None):
+    """
+    Request page and extract repo URL from the go-import meta tag matching the package path
+    Return cleaned URL or None
+
+    Args:
+        fetch_url (str): Validated URL to fetch
+        logger: Optional logger
+        package_name (str): Go import path; defaults to the path of fetch_url
+
+    Returns:
+        str or None
+    """
+    if package_name is None:
+        package_name = fetch_url.split("://", 1)[-1].split("?", 1)[0]
+    content = _go_fetch_page(fetch_url)
+    if content is None:
+        return None
+    match = _go_import_match(package_name, _go_import_metas(content))
+    if not match:
+        return None
+    repo_url = _clean_repo_url(match[2])
+    if repo_url and _ACTIVE_GO_IMPORTS is not None:
+        _ACTIVE_GO_IMPORTS[match[0]] = repo_url
+    return repo_url
+
+
 def resolve_go_package(package_name, logger=None):
     """
     Resolve Go package to repository URL
 
+    Paths on github.com and gitlab.com name their repository, gopkg.in paths map to GitHub, and
+    other (vanity) paths such as k8s.io/client-go or go.uber.org/zap are looked up through the
+    go-import meta tag served at `https://<path>?go-get=1`. A tag's import prefix is remembered for
+    the rest of the run, so further packages under it resolve without requests
+
     Args:
         package_name (str): The Go package name to resolve
         logger (Logger): Optional logger instance
@@ -960,10 +1365,15 @@ def resolve_go_package(package_name, logger=None):
         Repository URL string or None if not found
     """
     # For Go packages, the import path often IS the repo URL path
-    direct = _go_direct_repo_from_path(package_name)
+    direct = _go_direct_repo_from_path(package_name) or _go_gopkg_in_repo(package_name)
     if direct:
         return direct
 
+    found, cached = _go_cached_import(package_name)
+    if found:
+        logger and logger.debug(f"Resolved {package_name} from a cached go-import prefix -> {cached}")
+        return cached
+
     # Attempting the go-get=1 meta tag approach
     try:
         fetch_url = _go_meta_tag_fetch_url(package_name)
@@ -977,16 +1387,224 @@ def resolve_go_package(package_name, logger=None):
                 logger and logger.debug(f"Go package URL validation failed: {fetch_url} - {e}")
                 return None
 
-        inferred = _go_meta_tag_repo_url(fetch_url, logger)
+        inferred = _go_meta_tag_repo_url(fetch_url, logger, package_name)
         if inferred:
             return inferred
     except Exception as e:
         logger and logger.debug(f"Go package lookup via 'go-get=1' failed for {package_name}: {e}")
         pass
 
+    if _ACTIVE_GO_IMPORTS is not None:
+        _ACTIVE_GO_IMPORTS.setdefault(package_name, None)
     return None  # If direct URL and go-get meta tag failed
 
 
+def resolve_jsr_package(package_name, logger=None):
+    """
+    Resolve a JSR package ('@scope/name') to its linked GitHub repository
+
+    Args:
+        package_name (str): The JSR package name to resolve
+        logger (Logger): Optional logger instance
+
+    Returns:
+        Repository URL string or None if not found
+    """
+    scope, _, name = package_name.lstrip("@").partition("/")
+    if not scope or not name:
+        return None
+    data = _make_request(f"https://api.jsr.io/scopes/{scope}/packages/{name}", logger)
+    repo = (data or {}).get("githubRepository") or {}
+    if repo.get("owner") and repo.get("name"):
+        return f"https://github.com/{repo['owner']}/{repo['name']}"
+    return None
+
+
+def resolve_deno_module(package_name, logger=None):
+    """
+    Resolve a deno.land module ('deno.land/x/<name>' or 'deno.land/std') to its repository
+
+    Args:
+        package_name (str): The module identity to resolve
+        logger (Logger): Optional logger instance
+
+    Returns:
+        Repository URL string or None if not found
+    """
+    if package_name == "deno.land/std":
+        return "https://github.com/denoland/std"
+    if not package_name.startswith("deno.land/x/"):
+        return None
+    module = package_name[len("deno.land/x/") :]
+    data = _make_request(f"https://apiland.deno.dev/v2/modules/{module}", logger)
+    upload_options = (data or {}).get("upload_options") or {}
+    if upload_options.get("type") == "github" and upload_options.get("repository"):
+        return f"https://github.com/{upload_options['repository']}"
+    return None
+
+
+def _forge_repo_url(candidate):
+    """
+    Return the cleaned repository URL when a candidate points into GitHub, GitLab or Bitbucket
+
+    Args:
+        candidate (str|None): Any URL (source link, homepage, download URL)
+
+    Returns:
+        str or None
+    """
+    cleaned = _clean_repo_url(candidate)
+    if cleaned and ("github.com" in cleaned or "gitlab.com" in cleaned or "bitbucket.org" in cleaned):
+        return cleaned.split("/-/", 1)[0]
+    return None
+
+
+def _helm_repository_key(repository):
+    """
+    Return a comparable form of a chart repository URL (no scheme, no trailing slash)
+    """
+    repository = (repository or "").strip().rstrip("/").lower()
+    return repository.split("://", 1)[-1]
+
+
+def _helm_source_from_package(data):
+    """
+    Pick the source repository from an Artifact Hub package document
+
+    Args:
+        data (dict): Package document
+
+    Returns:
+        str or None
+    """
+    links = data.get("links") or []
+    candidates = [link.get("url") for link in links if str(link.get("name", "")).lower() in ("source", "sources")]
+    candidates += [link.get("url") for link in links]
+    candidates.append(data.get("home_url"))
+    for candidate in candidates:
+        cleaned = _forge_repo_url(candidate)
+        if cleaned:
+            return cleaned
+    return None
+
+
+def resolve_helm_chart(chart_name, repository=None, logger=None):
+    """
+    Resolve a Helm chart to its source repository
+
+    Looks the chart up on Artifact Hub, matching the chart repository by URL (or by name for
+    `@alias` references), then falls back to charts served from GitHub Pages
+
+    Args:
+        chart_name (str): Chart name
+        repository (str): Chart repository URL or alias from Chart.yaml
+        logger (Logger): Optional logger instance
+
+    Returns:
+        Repository URL string or None if not found
+    """
+    repository = (repository or "").strip()
+    alias = repository[len("alias:") :] if repository.startswith("alias:") else repository.lstrip("@")
+    wanted = _helm_repository_key(repository)
+    search = _make_request(
+        f"https://artifacthub.io/api/v1/packages/search?ts_query_web={quote(chart_name)}&kind=0&limit=20", logger
+    )
+    for package in (search or {}).get("packages") or []:
+        repo = package.get("repository") or {}
+        if package.get("name") != chart_name or not repo.get("name"):
+            continue
+        if _helm_repository_key(repo.get("url")) != wanted and repo.get("name") != alias:
+            continue
+        detail = _make_request(f"https://artifacthub.io/api/v1/packages/helm/{repo['name']}/{chart_name}", logger)
+        url = _helm_source_from_package(detail or {})
+        if url:
+            return url
+
+    match_gh_pages = _RE_GH_PAGES.match(repository)
+    if match_gh_pages:
+        return f"https://github.com/{match_gh_pages.group(1)}/{match_gh_pages.group(2)}"
+    return None
+
+
+def resolve_cocoapods_pod(package_name, version=None, logger=None):
+    """
+    Resolve a CocoaPods pod to its source repository via the CocoaPods trunk
+
+    Args:
+        package_name (str): Pod name
+        version (str): Optional locked version; the latest published version is used otherwise
+        logger (Logger): Optional logger instance
+
+    Returns:
+        Repository URL string or None if not found
+    """
+    pod = quote(package_name)
+    if not version:
+        data = _make_request(f"https://trunk.cocoapods.org/api/v1/pods/{pod}", logger) or {}
+        versions = data.get("versions") or []
+        if not versions:
+            return None
+        version = versions[-1].get("name")
+    spec = _make_request(f"https://trunk.cocoapods.org/api/v1/pods/{pod}/specs/{quote(str(version))}", logger)
+    if not spec:
+        return None
+    source = spec.get("source") or {}
+    for candidate in (source.get("git"), spec.get("homepage")):
+        cleaned = _clean_repo_url(candidate)
+        if cleaned:
+            return cleaned
+    return None
+
+
+def _homebrew_tap_repo_url(tap, source=None):
+    """
+    Return the repository behind a Homebrew tap ('user/repo' lives at github.com/user/homebrew-repo)
+    """
+    if source:
+        return _clean_repo_url(source)
+    user, _, repo = tap.partition("/")
+    if not user or not repo:
+        return None
+    return f"https://github.com/{user}/{repo if repo.startswith('homebrew-') else 'homebrew-' + repo}"
+
+
+def resolve_homebrew_package(package_name, package_data=None, logger=None):
+    """
+    Resolve a Brewfile formula, cask or tap to its upstream repository
+
+    Formulae and casks from the official taps are looked up in the Homebrew formula API, preferring
+    a GitHub/GitLab/Bitbucket head, download or homepage URL and falling back to the homepage.
+    Third-party taps and their formulae resolve to the tap repository
+
+    Args:
+        package_name (str): Package identity, e.g. 'homebrew/core/jq' or 'hashicorp/tap/terraform'
+        package_data (dict): Package metadata with 'kind' ('brew', 'cask' or 'tap') and optional 'source'
+        logger (Logger): Optional logger instance
+
+    Returns:
+        Repository or homepage URL string, or None if not found
+    """
+    package_data = package_data or {}
+    if package_data.get("kind") == "tap":
+        return _homebrew_tap_repo_url(package_name, package_data.get("source"))
+    tap, _, name = package_name.rpartition("/")
+    if tap not in ("homebrew/core", "homebrew/cask"):
+        return _homebrew_tap_repo_url(tap) if tap.count("/") == 1 else None
+
+    if package_data.get("kind") == "cask":
+        data = _make_request(f"https://formulae.brew.sh/api/cask/{quote(name, safe='@+')}.json", logger) or {}
+        candidates = [data.get("url")]
+    else:
+        data = _make_request(f"https://formulae.brew.sh/api/formula/{quote(name, safe='@+')}.json", logger) or {}
+        urls = data.get("urls") or {}
+        candidates = [(urls.get("head") or {}).get("url"), (urls.get("stable") or {}).get("url")]
+    for candidate in candidates + [data.get("homepage")]:
+        url = _forge_repo_url(candidate)
+        if url:
+            return url
+    return _clean_repo_url(data.get("homepage"))
+
+
 def resolve_solidity_contract(package_name, source=None, logger=None):
     """
     Resolve Solidity contract/library to repository URL
@@ -0,0 +1,222 @@
+"""
+IPFS content identifiers and report retrieval
+
+Reports published with `--output ipfs://` are referenced by CID (for example from on-chain
+records). `gardener fetch ipfs://<cid>` retrieves such a report through the IPFS node at
+IPFS_API_URL, or a public gateway with `--gateway`, and validates it before writing it out: the
+body must be a gardener report, a CIDv1 `raw` CID (single-block reports) must match the SHA-256 of
+the bytes, and with `--key` the report signature must verify. Larger reports have `dag-pb` CIDs
+that cannot be recomputed from the bytes alone; those are only trusted from the node's API, which
+verifies every block it fetches
+"""
+
+import argparse
+import base64
+import hashlib
+import json
+import os
+import sys
+import urllib.request
+
+DEFAULT_IPFS_API_URL = "http://127.0.0.1:5001"
+IPFS_TIMEOUT = 60
+
+CODEC_RAW = 0x55
+CODEC_DAG_PB = 0x70
+MULTIHASH_SHA2_256 = 0x12
+
+_BASE58_ALPHABET = "123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz"
+
+
+def _varint(value):
+    """Encode an unsigned varint"""
+    out = bytearray()
+    while True:
+        byte = value & 0x7F
+        value >>= 7
+        if value:
+            out.append(byte | 0x80)
+        else:
+            out.append(byte)
+            return bytes(out)
+
+
+def _read_varint(data, offset):
+    """Decode an unsigned varint at offset; returns (value, next offset)"""
+    value = shift = 0
+    while offset < len(data):
+        byte = data[offset]
+        value |= (byte & 0x7F) << shift
+        offset += 1
+        if not byte & 0x80:
+            return value, offset
+        shift += 7
+    raise ValueError("truncated varint")
+
+
+def _base58_decode(text):
+    """Decode a base58btc string"""
+    number = 0
+    for char in text:
+        number = number * 58 + _BASE58_ALPHABET.index(char)
+    body = number.to_bytes((number.bit_length() + 7) // 8, "big")
+    return b"\0" * (len(text) - len(text.lstrip("1"))) + body
+
+
+def raw_cid(data):
+    """
+    Return the CIDv1 (base32, `raw` codec, SHA-256) of a single-block payload
+
+    Args:
+        data (bytes): Block content
+
+    Returns:
+        str: CID such as 'bafkrei...'
+    """
+    digest = hashlib.sha256(data).digest()
+    binary = _varint(1) + _varint(CODEC_RAW) + _varint(MULTIHASH_SHA2_256) + _varint(len(digest)) + digest
+    return "b" + base64.b32encode(binary).decode("ascii").lower().rstrip("=")
+
+
+def parse_cid(cid):
+    """
+    Decode a CID into its version, codec, multihash code and digest
+
+    Args:
+        cid (str): CIDv0 ('Qm...') or base32 CIDv1 ('b...')
+
+    Returns:
+        dict: {'version', 'codec', 'hash_code', 'digest'}
+
+    Raises:
+        ValueError: If the CID is malformed or uses an unsupported multibase
+    """
+    try:
+        if cid.startswith("Qm") and len(cid) == 46:
+            binary = _base58_decode(cid)
+            version, codec, offset = 0, CODEC_DAG_PB, 0
+        elif cid.startswith("b"):
+            encoded = cid[1:].upper()
+            binary = base64.b32decode(encoded + "=" * (-len(encoded) % 8))
+            version, offset = _read_varint(binary, 0)
+            codec, offset = _read_varint(binary, offset)
+        else:
+            raise ValueError("only CIDv0 and base32 CIDv1 are supported")
+        hash_code, offset = _read_varint(binary, offset)
+        length, offset = _read_varint(binary, offset)
+    except (ValueError, IndexError) as exc:
+        raise ValueError(f"Invalid CID {cid!r}: {exc}") from exc
+    digest = binary[offset:]
+    if version not in (0, 1) or len(digest) != length:
+        raise ValueError(f"Invalid CID {cid!r}")
+    return {"version": version, "codec": codec, "hash_code": hash_code, "digest": digest}
+
+
+def cid_from_uri(uri):
+    """
+    Return the CID of an 'ipfs://<cid>' URI (or a bare CID), ignoring any path after it
+    """
+    return uri[len("ipfs://") :].split("/", 1)[0] if uri.startswith("ipfs://") else uri.split("/", 1)[0]
+
+
+def fetch_bytes(cid, api_url=None, gateway=None, urlopen=None):
+    """
+    Retrieve the content of a CID from an IPFS node's API or from a gateway
+
+    Args:
+        cid (str): Content identifier
+        api_url (str|None): IPFS API base URL; IPFS_API_URL or the local node by default
+        gateway (str|None): Gateway base URL (e.g. 'https://ipfs.io'); used instead of the API when set
+        urlopen (callable|None): Replacement for urllib.request.urlopen
+
+    Returns:
+        bytes: Retrieved content
+    """
+    urlopen = urlopen or urllib.request.urlopen
+    if gateway:
+        req = urllib.request.Request(f"{gateway.rstrip('/')}/ipfs/{cid}")
+    else:
+        base = (api_url or os.environ.get("IPFS_API_URL") or DEFAULT_IPFS_API_URL).rstrip("/")
+        req = urllib.request.Request(f"{base}/api/v0/cat?arg={cid}", data=b"", method="POST")
+    with urlopen(req, timeout=IPFS_TIMEOUT) as response:
+        return response.read()
+
+
+def report_failures(cid, data, via_gateway=False, public_key_pem=None):
+    """
+    Validate retrieved report bytes against their CID, the report shape and optionally a signature
+
+    Args:
+        cid (str): CID the bytes were retrieved by
+        data (bytes): Retrieved content
+        via_gateway (bool): True when the bytes came from a gateway, which is not trusted to verify blocks
+        public_key_pem (bytes|None): PEM public key of the expected signer
+
+    Returns:
+        tuple: (report dict or None, list of failure messages)
+    """
+    failures = []
+    parsed = parse_cid(cid)
+    if parsed["codec"] == CODEC_RAW and parsed["hash_code"] == MULTIHASH_SHA2_256:
+        if hashlib.sha256(data).digest() != parsed["digest"]:
+            failures.append(f"Content does not match CID {cid}")
+    elif via_gateway:
+        failures.append(f"CID {cid} is not a raw SHA-256 CID and cannot be checked against gateway content")
+
+    try:
+        report = json.loads(data.decode("utf-8"))
+    except (UnicodeDecodeError, ValueError) as exc:
+        return None, failures + [f"Content is not JSON: {exc}"]
+    if not isinstance(report, dict) or not {"external_packages", "top_dependencies"} <= set(report):
+        failures.append("Content is not a gardener analysis report")
+    elif public_key_pem is not None:
+        from gardener.analysis.signing import signature_failures
+
+        failures += signature_failures(report, public_key_pem)
+    return report, failures
+
+
+def main(argv=None):
+    """
+    Entry point for `gardener fetch ipfs://<cid>`
+
+    Args:
+        argv (list|None): Arguments after the `fetch` subcommand
+
+    Returns:
+        int: Process exit status (1 when retrieval or validation fails)
+    """
+    from gardener.common.utils import Logger
+
+    parser = argparse.ArgumentParser(prog="gardener fetch")
+    parser.add_argument("uri", help="ipfs://<cid> of a published report")
+    parser.add_argument("--gateway", help="Fetch through this HTTP gateway instead of the IPFS node API")
+    parser.add_argument("-k", "--key", help="PEM public key of the expected signer")
+    parser.add_argument("-o", "--output", help="Write the report to this file instead of stdout")
+    args = parser.parse_args(argv)
+
+    logger = Logger(verbose=True)
+    cid = cid_from_uri(args.uri)
+    try:
+        parse_cid(cid)
+        public_key_pem = None
+        if args.key:
+            with open(args.key, "rb") as handle:
+                public_key_pem = handle.read()
+        data = fetch_bytes(cid, gateway=args.gateway)
+        _, failures = report_failures(cid, data, via_gateway=bool(args.gateway), public_key_pem=public_key_pem)
+    except (OSError, ValueError, RuntimeError) as exc:
+        logger.error(f"Fetch failed: {exc}")
+        return 1
+
+    for failure in failures:
+        logger.error(failure)
+    if failures:
+        return 1
+    if args.output:
+        with open(args.output, "wb") as handle:
+            handle.write(data)
+        logger.info(f"Fetched and validated {cid} ({len(data)} bytes) to {args.output}")
+    else:
+        sys.stdout.write(data.decode("utf-8"))
+    return 0
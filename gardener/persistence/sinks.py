@@ -0,0 +1,161 @@
+"""
+Object storage and IPFS implementation of the persistence interface
+
+`--output s3://bucket/reports`, `gs://bucket/reports` or `ipfs://` writes the report straight to
+the sink instead of `output/`. Objects are content-addressed: the key is the SHA-256 of the body
+under the URL's prefix (`s3://bucket/reports/<sha256>_dependency_analysis.json`), so identical
+reports share one object and a stored report is never overwritten; IPFS names the report by its
+CID. S3 needs the '[s3]' extra (boto3), GCS the '[gcs]' extra (google-cloud-storage); IPFS uses
+the HTTP API of the node at IPFS_API_URL (default http://127.0.0.1:5001)
+"""
+
+import hashlib
+import json
+import os
+import urllib.request
+import uuid
+from urllib.parse import urlparse
+
+from gardener.common.utils import Logger
+from gardener.persistence.interface import PersistenceInterface
+from gardener.persistence.ipfs import DEFAULT_IPFS_API_URL, IPFS_TIMEOUT
+
+SINK_SCHEMES = ("s3", "gs", "ipfs")
+
+CONTENT_TYPES = {".json": "application/json", ".html": "text/html; charset=utf-8"}
+
+
+def is_sink_url(output):
+    """
+    Return True when an --output value names an object storage or IPFS sink
+    """
+    return isinstance(output, str) and urlparse(output).scheme in SINK_SCHEMES and "://" in output
+
+
+class S3Uploader:
+    """
+    Upload objects to an S3 (or S3-compatible) bucket with boto3
+    """
+
+    def __init__(self, client=None):
+        if client is None:
+            try:
+                import boto3
+            except ImportError as e:
+                raise ValueError("s3:// output requires the '[s3]' extra (boto3)") from e
+            client = boto3.client("s3", endpoint_url=os.environ.get("AWS_ENDPOINT_URL") or None)
+        self.client = client
+
+    def upload(self, bucket, key, data, content_type):
+        """Put the object and return its URI"""
+        self.client.put_object(Bucket=bucket, Key=key, Body=data, ContentType=content_type)
+        return f"s3://{bucket}/{key}"
+
+
+class GCSUploader:
+    """
+    Upload objects to a Google Cloud Storage bucket with google-cloud-storage
+    """
+
+    def __init__(self, client=None):
+        if client is None:
+            try:
+                from google.cloud import storage
+            except ImportError as e:
+                raise ValueError("gs:// output requires the '[gcs]' extra (google-cloud-storage)") from e
+            client = storage.Client()
+        self.client = client
+
+    def upload(self, bucket, key, data, content_type):
+        """Upload the blob and return its URI"""
+        self.client.bucket(bucket).blob(key).upload_from_string(data, content_type=content_type)
+        return f"gs://{bucket}/{key}"
+
+
+class IPFSUploader:
+    """
+    Add objects through the HTTP API of an IPFS node (`/api/v0/add`)
+    """
+
+    def __init__(self, api_url=None, urlopen=None):
+        self.api_url = (api_url or os.environ.get("IPFS_API_URL") or DEFAULT_IPFS_API_URL).rstrip("/")
+        self.urlopen = urlopen or urllib.request.urlopen
+
+    def upload(self, bucket, key, data, content_type):
+        """Add and pin the object and return 'ipfs://<cid>'"""
+        boundary = uuid.uuid4().hex
+        filename = key.rsplit("/", 1)[-1]
+        body = (
+            f"--{boundary}\r\n"
+            f'Content-Disposition: form-data; name="file"; filename="{filename}"\r\n'
+            f"Content-Type: {content_type}\r\n\r\n"
+        ).encode("utf-8")
+        body += data + f"\r\n--{boundary}--\r\n".encode("utf-8")
+        req = urllib.request.Request(
+            f"{self.api_url}/api/v0/add?pin=true&cid-version=1&raw-leaves=true",
+            data=body,
+            headers={"Content-Type": f"multipart/form-data; boundary={boundary}"},
+            method="POST",
+        )
+        with self.urlopen(req, timeout=IPFS_TIMEOUT) as response:
+            cid = json.loads(response.read().decode("utf-8"))["Hash"]
+        return f"ipfs://{cid}"
+
+
+UPLOADERS = {"s3": S3Uploader, "gs": GCSUploader, "ipfs": IPFSUploader}
+
+
+class SinkPersistence(PersistenceInterface):
+    """
+    Write analysis results to S3, GCS or IPFS under content-addressed names
+    """
+
+    def __init__(self, url, uploader=None, verbose=True):
+        """
+        Args:
+            url (str): Sink URL, e.g. 's3://bucket/prefix', 'gs://bucket/prefix' or 'ipfs://'
+            uploader (object): Object with upload(bucket, key, data, content_type) -> URI; built
+                from the URL scheme when not provided
+            verbose (bool): Enable verbose logging
+
+        Raises:
+            ValueError: If the URL is not a sink URL, has no bucket, or the backend is not installed
+        """
+        parsed = urlparse(url)
+        if parsed.scheme not in SINK_SCHEMES:
+            raise ValueError(f"Unsupported output sink {url!r}; expected one of {', '.join(SINK_SCHEMES)}")
+        if parsed.scheme != "ipfs" and not parsed.netloc:
+            raise ValueError(f"Output sink {url!r} has no bucket")
+        self.scheme = parsed.scheme
+        self.bucket = parsed.netloc
+        self.prefix = parsed.path.strip("/")
+        self.uploader = uploader or UPLOADERS[self.scheme]()
+        self.logger = Logger(verbose=verbose)
+        self.written = {}
+
+    def _put(self, data, suffix):
+        """Upload a payload under its content address and record the resulting URI"""
+        name = f"{hashlib.sha256(data).hexdigest()}{suffix}"
+        key = f"{self.prefix}/{name}" if self.prefix else name
+        content_type = CONTENT_TYPES.get(os.path.splitext(suffix)[1], "application/octet-stream")
+        uri = self.uploader.upload(self.bucket, key, data, content_type)
+        self.written[suffix] = uri
+        return uri
+
+    def save_analysis_results(self, results, identifier):
+        """Upload analysis results as JSON"""
+        data = json.dumps(results, indent=2, default=str).encode("utf-8")
+        uri = self._put(data, "_dependency_analysis.json")
+        self.logger.info(f"\nAnalysis results for {identifier} saved to: {uri}")
+
+    def save_graph_visualization(self, graph_html, identifier):
+        """Upload graph visualization as HTML"""
+        uri = self._put(graph_html.encode("utf-8"), "_dependency_graph.html")
+        self.logger.info(f"Interactive dependency graph saved to: {uri}")
+
+    def get_output_path(self, identifier, suffix):
+        """Return the URI written for a suffix, or the sink location before anything is written"""
+        if suffix in self.written:
+            return self.written[suffix]
+        location = f"{self.bucket}/{self.prefix}".strip("/")
+        return f"{self.scheme}://{location}"
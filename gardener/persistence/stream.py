@@ -0,0 +1,71 @@
+"""
+Streaming implementation of the persistence interface
+
+Results are written as JSON Lines: a small `report_stats` header object first, then the full
+report on one line, so consumers reading a pipe can size buffers and workers before parsing the body
+"""
+
+import json
+import sys
+
+from gardener.common.utils import Logger
+from gardener.persistence.interface import PersistenceInterface
+
+
+def report_stats(results, body=None):
+    """
+    Summarize the size and complexity of an analysis report
+
+    Args:
+        results (dict): Analysis results
+        body (str): Optional serialized report; serialized compactly when not provided
+
+    Returns:
+        dict: package_count, evidence_count (file -> import observations), graph_edge_count and
+            serialized_bytes
+    """
+    if body is None:
+        body = json.dumps(results, default=str)
+    details = results.get("analyzer_details") or {}
+    if "evidence" in details:
+        # Aggregated evidence: each group counts the files sharing one observation
+        evidence_count = sum(group["count"] for group in details["evidence"].get("imports") or [])
+    else:
+        evidence_count = sum(len(imports) for imports in (details.get("file_imports") or {}).values())
+    graph = results.get("dependency_graph") or {}
+    return {
+        "package_count": len(results.get("external_packages") or {}),
+        "evidence_count": evidence_count,
+        "graph_edge_count": len(graph.get("links") or graph.get("edges") or []),
+        "serialized_bytes": len(body.encode("utf-8")),
+    }
+
+
+class StreamPersistence(PersistenceInterface):
+    """
+    Write analysis results to a stream (stdout by default) instead of files
+    """
+
+    def __init__(self, stream=None, verbose=False):
+        """
+        Args:
+            stream (file): Text stream to write to; defaults to sys.stdout at construction time
+            verbose (bool): Enable verbose logging
+        """
+        self.stream = stream or sys.stdout
+        self.logger = Logger(verbose=verbose)
+
+    def save_analysis_results(self, results, identifier):
+        """Write the report_stats header line followed by the report line"""
+        body = json.dumps(results, default=str)
+        header = json.dumps({"report_stats": report_stats(results, body)})
+        self.stream.write(f"{header}\n{body}\n")
+        self.stream.flush()
+
+    def save_graph_visualization(self, graph_html, identifier):
+        """Visualizations are not streamed"""
+        self.logger.warning(f"Skipping graph visualization for {identifier} in streaming mode")
+
+    def get_output_path(self, identifier, suffix):
+        """Streamed results have no path"""
+        return "<stream>"
@@ -0,0 +1,162 @@
+"""
+Per-import annotations recorded by language visitors
+
+Visitors note each occurrence of an external import together with any guarding condition
+(platform checks, cfg attributes); handlers then fold those occurrences into a flat
+{import_name: {attribute: value}} mapping that the graph builder attaches to edges
+"""
+
+import re
+from collections import defaultdict
+
+# Platform spellings across languages mapped to one canonical name
+PLATFORM_ALIASES = {
+    "win32": "windows",
+    "windows": "windows",
+    "nt": "windows",
+    "cygwin": "windows",
+    "msys": "windows",
+    "darwin": "macos",
+    "macos": "macos",
+    "ios": "ios",
+    "linux": "linux",
+    "linux2": "linux",
+    "android": "android",
+    "freebsd": "freebsd",
+    "openbsd": "openbsd",
+    "netbsd": "netbsd",
+    "sunos": "solaris",
+    "solaris": "solaris",
+    "aix": "aix",
+    "unix": "unix",
+    "posix": "unix",
+    "wasm": "wasm",
+    "wasm32": "wasm",
+    "emscripten": "wasm",
+    "wasi": "wasm",
+}
+
+_QUOTED_RE = re.compile(r"""["']([A-Za-z0-9_]+)["']""")
+
+
+def normalize_platform(token):
+    """
+    Map a language-specific platform identifier to its canonical name
+
+    Args:
+        token (str): Identifier such as 'win32', 'Darwin', 'nt' or 'linux'
+
+    Returns:
+        str|None: Canonical platform name, or None when unrecognized
+    """
+    if not token:
+        return None
+    lowered = token.lower()
+    if lowered in PLATFORM_ALIASES:
+        return PLATFORM_ALIASES[lowered]
+    for prefix, name in (("linux", "linux"), ("freebsd", "freebsd"), ("openbsd", "openbsd"), ("win", "windows")):
+        if lowered.startswith(prefix):
+            return name
+    return None
+
+
+def platforms_in_condition(condition_text, negated=False):
+    """
+    Extract canonical platform names from quoted literals in a condition
+
+    Args:
+        condition_text (str): Source text of the guarding condition
+        negated (bool): True when the import sits in the branch taken when the condition is false
+
+    Returns:
+        list: Sorted platform names, prefixed with '!' for excluded platforms
+    """
+    inequality = "!=" in condition_text or re.search(r"\bnot\b", condition_text) is not None
+    exclude = negated != inequality
+    platforms = set()
+    for literal in _QUOTED_RE.findall(condition_text):
+        name = normalize_platform(literal)
+        if name:
+            platforms.add(f"!{name}" if exclude else name)
+    return sorted(platforms)
+
+
+class ImportGuards:
+    """
+    Tracks guarded and unguarded occurrences of external imports within one file
+
+    An import counts as guarded only when every occurrence sits under a guard; a single
+    unguarded occurrence makes it unconditional
+    """
+
+    def __init__(self):
+        self._guards = defaultdict(dict)  # import_name -> {condition: [platforms]}
+        self._unguarded = set()
+
+    def record(self, import_name, guard):
+        """
+        Record one occurrence of an import
+
+        Args:
+            import_name (str): External import name
+            guard (tuple|None): (condition_text, platforms) or None when unconditional
+        """
+        if not import_name:
+            return
+        if guard is None:
+            self._unguarded.add(import_name)
+            return
+        condition, platforms = guard
+        self._guards[import_name][condition] = list(platforms)
+
+    def apply(self, import_annotations, condition_key="platform_guard"):
+        """
+        Write guard annotations for imports that are never used unconditionally
+
+        Args:
+            import_annotations (dict): Mapping of import name to attribute dict, updated in place
+            condition_key (str): Attribute name for the combined condition text
+        """
+        if import_annotations is None:
+            return
+        for import_name, conditions in self._guards.items():
+            if import_name in self._unguarded or not conditions:
+                continue
+            entry = import_annotations.setdefault(import_name, {})
+            entry[condition_key] = " || ".join(sorted(conditions))
+            platforms = sorted({p for plats in conditions.values() for p in plats})
+            if platforms:
+                entry["platforms"] = platforms
+
+
+def node_contains(outer, inner):
+    """
+    Return True when `inner` lies within the byte range of `outer`
+
+    Args:
+        outer (object): Tree-sitter node or None
+        inner (object): Tree-sitter node
+    """
+    if outer is None or inner is None:
+        return False
+    return outer.start_byte <= inner.start_byte and inner.end_byte <= outer.end_byte
+
+
+def is_platform_excluded(annotation, excluded_platforms):
+    """
+    Check whether an annotated import only applies to excluded platforms
+
+    Args:
+        annotation (dict|None): Import annotation with optional 'platforms' list
+        excluded_platforms (iterable): Canonical platform names to filter out
+
+    Returns:
+        bool: True when every positive platform of the guard is excluded
+    """
+    if not annotation or not excluded_platforms:
+        return False
+    positive = [p for p in annotation.get("platforms", []) if not p.startswith("!")]
+    if not positive:
+        return False
+    excluded = {normalize_platform(p) or p for p in excluded_platforms}
+    return all(p in excluded for p in positive)
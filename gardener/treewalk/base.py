@@ -2,9 +2,15 @@
 Base classes for tree traversal
 """
 
+import os
 from abc import ABC, abstractmethod
 
 from gardener.common.file_helpers import read_file_content, safe_json_load
+from gardener.common.secure_file_ops import FileOperationError
+from gardener.common.utils import Logger
+
+# Logger used by handlers constructed without one
+logger = Logger(verbose=False)  # Will be configured by the caller
 
 
 class TreeVisitor:
@@ -114,7 +120,9 @@ class LanguageHandler(ABC):
         pass
 
     @abstractmethod
-    def extract_imports(self, tree_node, rel_path, file_components_dict, local_resolver_func, logger=None):
+    def extract_imports(
+        self, tree_node, rel_path, file_components_dict, local_resolver_func, logger=None, import_annotations=None
+    ):
         """
         Extract imports from a tree node
 
@@ -124,6 +132,7 @@ class LanguageHandler(ABC):
             file_components_dict (dict): Dictionary to update with file components
             local_resolver_func (callable): Function to resolve local module paths
             logger (Logger): Optional logger instance for debug output
+            import_annotations (dict): Optional mapping of import name to annotations, updated in place
 
         Returns:
             Tuple of (external_imports, local_imports) where:
@@ -132,6 +141,22 @@ class LanguageHandler(ABC):
         """
         pass
 
+    def parse_source(self, code, rel_path):
+        """
+        Parse file content into the node handed to extract_imports
+
+        Handlers for formats without a tree-sitter grammar override this; returning None
+        parses the file with the tree-sitter grammar registered for the language
+
+        Args:
+            code (str): File content
+            rel_path (str): Relative path of the file
+
+        Returns:
+            object|None: Parsed root object, or None to use tree-sitter
+        """
+        return None
+
     def get_file_extensions(self):
         """
         Get the file extensions supported by this language handler
@@ -183,3 +208,111 @@ class LanguageHandler(ABC):
             The parsed JSON content as a dictionary or list
         """
         return safe_json_load(file_path, secure_file_ops)
+
+
+class ManifestOnlyHandler(LanguageHandler):
+    """
+    Base class for ecosystems declared only in manifests, with no source files to scan
+
+    Subclasses list their manifests in MANIFEST_FILES and implement parse_manifest; reading the
+    file, reporting unreadable or malformed manifests and the empty import extraction are shared
+
+    Attributes:
+        MANIFEST_FILES (tuple): Manifest and lock file names
+        PARSE_ERRORS (tuple): Exceptions parse_manifest raises for malformed content
+    """
+
+    MANIFEST_FILES = ()
+    PARSE_ERRORS = (ValueError,)
+
+    def __init__(self, logger=None):
+        """
+        Args:
+            logger (Logger): Optional logger instance
+        """
+        self.logger = logger
+
+    @property
+    def log(self):
+        """Logger for this handler, the module logger when none was given"""
+        return self.logger or logger
+
+    def get_manifest_files(self):
+        """
+        Get the manifest files of the ecosystem
+
+        Returns:
+            List of manifest filenames
+        """
+        return list(self.MANIFEST_FILES)
+
+    def get_file_extensions(self):
+        """
+        Manifest-only ecosystems have no source files to scan
+
+        Returns:
+            Empty list
+        """
+        return []
+
+    def process_manifest(self, file_path, packages_dict, secure_file_ops=None):
+        """
+        Read a manifest and hand its content to parse_manifest
+
+        Unreadable files are logged as errors and malformed ones as warnings; neither stops the scan
+
+        Args:
+            file_path (str): Path to the manifest file
+            packages_dict (dict): Dictionary to update with package information
+            secure_file_ops (object): Optional SecureFileOps instance for safe file operations
+
+        Returns:
+            Updated packages_dict
+        """
+        basename = os.path.basename(file_path)
+        try:
+            content = self.read_file_content(file_path, secure_file_ops)
+        except (FileOperationError, OSError) as e:
+            self.log.error(f"Failed to read {basename} at {file_path}: {e}")
+            return packages_dict
+        try:
+            self.parse_manifest(basename, content, packages_dict, file_path)
+        except self.PARSE_ERRORS as e:
+            self.log.warning(f"Could not parse {basename} at {file_path}: {e}")
+        return packages_dict
+
+    @abstractmethod
+    def parse_manifest(self, basename, content, packages_dict, file_path):
+        """
+        Record the packages a manifest declares
+
+        Args:
+            basename (str): Manifest file name, one of MANIFEST_FILES
+            content (str): Manifest content
+            packages_dict (dict): Dictionary to update with package information
+            file_path (str): Path to the manifest file, for log messages
+        """
+
+    def normalize_package_name(self, package_path):
+        """
+        Manifest entries are not imported from source, so there is nothing to normalize
+
+        Args:
+            package_path (str): The raw reference
+
+        Returns:
+            The reference unchanged
+        """
+        return package_path
+
+    def extract_imports(
+        self, tree_node, rel_path, file_components_dict, local_resolver_func, logger=None, import_annotations=None
+    ):
+        """
+        Manifest-only ecosystems have no source files, so there are no imports
+
+        Returns:
+            Tuple of (external_imports, local_imports), both empty
+        """
+        return [], []
+
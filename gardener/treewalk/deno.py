@@ -0,0 +1,196 @@
+"""
+Deno specifier and import map helpers
+
+Deno modules import packages by URL (`https://deno.land/x/oak@v12.6.1/mod.ts`), by registry
+prefix (`npm:chalk@5`, `jsr:@std/path@^1`) or through bare aliases declared in `deno.json` or
+an import map. These helpers map all three onto the package identities used by the graph
+"""
+
+import json
+import os
+import re
+from urllib.parse import urlparse
+
+from gardener.common.utils import Logger
+
+# Module-level logger instance
+logger = Logger(verbose=False)  # Will be configured by the caller
+
+DENO_MANIFEST_FILES = ["deno.json", "deno.jsonc", "import_map.json"]
+
+# CDNs that serve npm packages by URL; the value is the path prefix preceding the package name
+NPM_CDN_HOSTS = {
+    "esm.sh": "",
+    "cdn.skypack.dev": "",
+    "unpkg.com": "",
+    "cdn.jsdelivr.net": "npm/",
+    "ga.jspm.io": "npm:",
+}
+
+JSONC_COMMENT_RE = re.compile(r"\"(?:\\.|[^\"\\])*\"|//[^\r\n]*|/\*.*?\*/", re.DOTALL)
+TRAILING_COMMA_RE = re.compile(r",\s*([\]}])")
+ESM_SH_VERSION_SEGMENT_RE = re.compile(r"^(?:v\d+|stable)$")
+
+
+def _split_name_version(path):
+    """
+    Split '<name>@<version>/<rest>' (name optionally scoped) into name and version
+
+    Args:
+        path (str): Specifier path without the registry prefix
+
+    Returns:
+        Tuple (name, version) where version may be None
+    """
+    segments = path.split("/")
+    count = 2 if path.startswith("@") and len(segments) > 1 else 1
+    head = "/".join(segments[:count])
+    at = head.rfind("@")
+    if at > 0:
+        return head[:at], head[at + 1 :] or None
+    return head, None
+
+
+def parse_deno_specifier(specifier):
+    """
+    Map a Deno module specifier to the package it belongs to
+
+    Args:
+        specifier (str): `npm:`/`jsr:` specifier or an https URL
+
+    Returns:
+        Tuple (ecosystem, package name, version) or None for specifiers with no package identity.
+        Ecosystems are 'npm', 'jsr', 'deno' (deno.land/x and deno.land/std) and 'github'
+    """
+    if specifier.startswith("npm:"):
+        name, version = _split_name_version(specifier[4:].lstrip("/"))
+        return ("npm", name, version) if name else None
+    if specifier.startswith("jsr:"):
+        name, version = _split_name_version(specifier[4:].lstrip("/"))
+        return ("jsr", name, version) if name.startswith("@") and "/" in name else None
+    if not specifier.startswith(("https://", "http://")):
+        return None
+
+    parsed = urlparse(specifier)
+    host = parsed.netloc.lower()
+    path = parsed.path.lstrip("/")
+
+    if host == "deno.land":
+        if path.startswith("x/"):
+            name, version = _split_name_version(path[2:])
+            return ("deno", f"deno.land/x/{name}", version) if name else None
+        if path.startswith("std"):
+            _, version = _split_name_version(path)
+            return ("deno", "deno.land/std", version)
+        return None
+    if host == "jsr.io":
+        segments = path.split("/")
+        if len(segments) >= 2 and segments[0].startswith("@"):
+            version = segments[2] if len(segments) > 2 and segments[2][:1].isdigit() else None
+            return ("jsr", f"{segments[0]}/{segments[1]}", version)
+        return None
+    if host in NPM_CDN_HOSTS:
+        prefix = NPM_CDN_HOSTS[host]
+        if not path.startswith(prefix):
+            return None
+        path = path[len(prefix) :]
+        if host == "esm.sh":
+            segments = path.split("/")
+            while segments and ESM_SH_VERSION_SEGMENT_RE.match(segments[0]):
+                segments = segments[1:]
+            path = "/".join(segments)
+        name, version = _split_name_version(path)
+        return ("npm", name, version) if name else None
+    if host == "raw.githubusercontent.com":
+        segments = path.split("/")
+        if len(segments) >= 2:
+            return ("github", f"github.com/{segments[0]}/{segments[1]}", segments[2] if len(segments) > 2 else None)
+    return None
+
+
+def deno_import_name(specifier):
+    """
+    Return the package name an import specifier refers to, or None when it is not a Deno specifier
+
+    Args:
+        specifier (str): Import specifier as written in source
+
+    Returns:
+        str|None: Package name as used for external package keys
+    """
+    parsed = parse_deno_specifier(specifier)
+    return parsed[1] if parsed else None
+
+
+def load_jsonc(content):
+    """
+    Parse JSON that may contain comments and trailing commas (deno.jsonc)
+
+    Args:
+        content (str): File content
+
+    Returns:
+        dict: Parsed object
+    """
+    content = JSONC_COMMENT_RE.sub(lambda m: m.group(0) if m.group(0).startswith('"') else "", content)
+    return json.loads(TRAILING_COMMA_RE.sub(r"\1", content))
+
+
+def _record(packages_dict, alias, target):
+    """
+    Add the package behind one import map entry
+
+    Args:
+        packages_dict (dict): Packages being collected, updated in place
+        alias (str): Import map key
+        target (str): Import map value
+    """
+    parsed = parse_deno_specifier(target)
+    if not parsed:
+        return
+    ecosystem, name, version = parsed
+    entry = packages_dict.setdefault(name, {"ecosystem": ecosystem, "version": version or "", "import_names": [name]})
+    if version and not entry["version"]:
+        entry["version"] = version
+    alias_name = alias.rstrip("/")
+    if alias_name and not alias_name.startswith(".") and alias_name not in entry["import_names"]:
+        entry["import_names"].append(alias_name)
+
+
+def process_deno_manifest(file_path, packages_dict, read_file):
+    """
+    Collect packages declared in `deno.json`/`deno.jsonc` and import maps
+
+    Covers `imports`, `scopes` and an `importMap` file referenced from the config
+
+    Args:
+        file_path (str): Absolute path of the manifest
+        packages_dict (dict): Packages being collected, updated in place
+        read_file (callable): Reads a file path and returns its text
+
+    Returns:
+        dict: The updated packages_dict
+    """
+    data = load_jsonc(read_file(file_path))
+    if not isinstance(data, dict):
+        return packages_dict
+
+    maps = [data]
+    import_map_path = data.get("importMap")
+    if isinstance(import_map_path, str) and not import_map_path.startswith(("http://", "https://")):
+        try:
+            maps.append(load_jsonc(read_file(os.path.join(os.path.dirname(file_path), import_map_path))))
+        except Exception as exc:
+            logger.warning(f"Could not read import map {import_map_path} referenced by {file_path}: {exc}")
+
+    for import_map in maps:
+        imports = import_map.get("imports") if isinstance(import_map, dict) else None
+        scopes = import_map.get("scopes") if isinstance(import_map, dict) else None
+        sections = [imports] + list(scopes.values() if isinstance(scopes, dict) else [])
+        for section in sections:
+            if not isinstance(section, dict):
+                continue
+            for alias, target in section.items():
+                if isinstance(alias, str) and isinstance(target, str):
+                    _record(packages_dict, alias, target)
+    return packages_dict
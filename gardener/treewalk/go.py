@@ -4,14 +4,216 @@ Go-specific visitors and handlers
 
 import os
 import re
+from pathlib import PurePosixPath
 
 from gardener.common.secure_file_ops import FileOperationError
 from gardener.common.utils import Logger
+from gardener.treewalk.annotations import normalize_platform
 from gardener.treewalk.base import LanguageHandler, TreeVisitor
+from gardener.treewalk.go_modules import (
+    GO_MOD_FILE,
+    GO_SUM_FILE,
+    GO_WORK_FILE,
+    parse_go_mod,
+    process_go_sum,
+    replacement_source,
+)
 
 # Module-level logger instance
 logger = Logger(verbose=False)  # Will be configured by the caller
 
+GO_BUILD_RE = re.compile(r"^//go:build\s+(.+)$")
+PLUS_BUILD_RE = re.compile(r"^//\s*\+build\s+(.+)$")
+BUILD_TOKEN_RE = re.compile(r"\s*(&&|\|\||[!()]|[A-Za-z0-9_.]+)")
+# GOOS and GOARCH values recognized in build constraints and file name suffixes
+KNOWN_GOOS = (
+    "aix",
+    "android",
+    "darwin",
+    "dragonfly",
+    "freebsd",
+    "hurd",
+    "illumos",
+    "ios",
+    "js",
+    "linux",
+    "nacl",
+    "netbsd",
+    "openbsd",
+    "plan9",
+    "solaris",
+    "wasip1",
+    "windows",
+    "zos",
+)
+KNOWN_GOARCH = (
+    "386",
+    "amd64",
+    "arm",
+    "arm64",
+    "loong64",
+    "mips",
+    "mips64",
+    "mips64le",
+    "mipsle",
+    "ppc64",
+    "ppc64le",
+    "riscv64",
+    "s390x",
+    "wasm",
+)
+# Tags set by the toolchain rather than chosen by the build: not reported as custom build tags
+TOOLCHAIN_TAGS = ("cgo", "gc", "gccgo", "unix")
+
+
+def _plus_build_expression(lines):
+    """
+    Convert legacy `// +build` lines to a //go:build expression: spaces are ORs, commas ANDs, lines AND
+    """
+    clauses = []
+    for line in lines:
+        options = [" && ".join(option.split(",")) for option in line.split()]
+        clauses.append(options[0] if len(options) == 1 else "(" + " || ".join(options) + ")")
+    return " && ".join(clauses)
+
+
+def header_build_constraint(comments):
+    """
+    Read the build constraint of a Go file from the comments before its package clause
+
+    Args:
+        comments (list): Comment texts preceding the package clause
+
+    Returns:
+        str|None: The //go:build expression, legacy `// +build` lines converted, or None
+    """
+    plus_build = []
+    for comment in comments:
+        for line in comment.splitlines():
+            line = line.strip()
+            match = GO_BUILD_RE.match(line)
+            if match:
+                return match.group(1).strip()
+            match = PLUS_BUILD_RE.match(line)
+            if match:
+                plus_build.append(match.group(1).strip())
+    return _plus_build_expression(plus_build) if plus_build else None
+
+
+def filename_build_constraint(rel_path):
+    """
+    Read the implicit constraints of a Go file name: `_test.go`, `_GOOS.go`, `_GOARCH.go`, `_GOOS_GOARCH.go`
+
+    Args:
+        rel_path (str): Relative path of the file
+
+    Returns:
+        tuple: (expression or None, test file)
+    """
+    stem = PurePosixPath(rel_path).name.removesuffix(".go")
+    parts = stem.split("_")
+    test = len(parts) > 1 and parts[-1] == "test"
+    if test:
+        parts = parts[:-1]
+    # As in the go tool, the part before the first underscore never names a platform ('linux.go')
+    parts = parts[1:]
+    if len(parts) >= 2 and parts[-2] in KNOWN_GOOS and parts[-1] in KNOWN_GOARCH:
+        return f"{parts[-2]} && {parts[-1]}", test
+    if parts and (parts[-1] in KNOWN_GOOS or parts[-1] in KNOWN_GOARCH):
+        return parts[-1], test
+    return None, test
+
+
+def _parse_build_expression(expression):
+    """
+    Parse a //go:build expression into nested ('tag', name), ('!', x), ('&&', x, y) and ('||', x, y) nodes
+
+    `!` binds tighter than `&&`, which binds tighter than `||`; None when the expression is malformed
+    """
+    tokens, position = [], 0
+    while position < len(expression.rstrip()):
+        match = BUILD_TOKEN_RE.match(expression, position)
+        if not match:
+            return None
+        tokens.append(match.group(1))
+        position = match.end()
+
+    def parse_binary(index, operator, operand):
+        node, index = operand(index)
+        while node is not None and index < len(tokens) and tokens[index] == operator:
+            right, index = operand(index + 1)
+            node = (operator, node, right) if right is not None else None
+        return node, index
+
+    def parse_or(index):
+        return parse_binary(index, "||", parse_and)
+
+    def parse_and(index):
+        return parse_binary(index, "&&", parse_not)
+
+    def parse_not(index):
+        if index >= len(tokens):
+            return None, index
+        token = tokens[index]
+        if token == "!":
+            node, index = parse_not(index + 1)
+            return (("!", node) if node is not None else None), index
+        if token == "(":
+            node, index = parse_or(index + 1)
+            if index >= len(tokens) or tokens[index] != ")":
+                return None, index
+            return node, index + 1
+        if token in ("&&", "||", ")"):
+            return None, index
+        return ("tag", token), index + 1
+
+    node, index = parse_or(0)
+    return node if index == len(tokens) else None
+
+
+def _build_tags(node, negated=False):
+    """
+    Yield (tag, negated) for every tag of a parsed build expression, negations pushed down to the tags
+    """
+    if node[0] == "tag":
+        yield node[1], negated
+    elif node[0] == "!":
+        yield from _build_tags(node[1], not negated)
+    else:
+        yield from _build_tags(node[1], negated)
+        yield from _build_tags(node[2], negated)
+
+
+def build_annotation(expression, test):
+    """
+    Describe a Go file's build constraints as an import annotation
+
+    Args:
+        expression (str|None): Combined build constraint expression
+        test (bool): Whether the file is a `_test.go` file
+
+    Returns:
+        dict: {'go_build', 'platforms', 'build_tags', 'test_only'}, each only when present
+    """
+    annotation = {}
+    if expression:
+        annotation["go_build"] = expression
+        platforms, tags = set(), set()
+        parsed = _parse_build_expression(expression)
+        for tag, negated in _build_tags(parsed) if parsed else ():
+            if tag in KNOWN_GOOS or tag == "unix":
+                platform = normalize_platform(tag) or tag
+                platforms.add(f"!{platform}" if negated else platform)
+            elif tag not in KNOWN_GOARCH and tag not in TOOLCHAIN_TAGS and not tag.startswith("go1"):
+                tags.add(f"!{tag}" if negated else tag)
+        if platforms:
+            annotation["platforms"] = sorted(platforms)
+        if tags:
+            annotation["build_tags"] = sorted(tags)
+    if test:
+        annotation["test_only"] = True
+    return annotation
+
 
 class GoImportVisitor(TreeVisitor):
     """
@@ -122,37 +324,52 @@ class GoLanguageHandler(LanguageHandler):
         self.logger = logger
 
     def get_manifest_files(self):
-        return ["go.mod", "go.sum"]
+        # go.work declares no dependencies; it is listed for the workspace modules it uses
+        return [GO_MOD_FILE, GO_SUM_FILE, GO_WORK_FILE]
 
     def get_file_extensions(self):
         return [".go"]
 
     def process_manifest(self, file_path, packages_dict, secure_file_ops=None):
+        """
+        Process a go.mod or go.sum to extract module dependencies
+
+        Args:
+            file_path (str): Path to the manifest file
+            packages_dict (dict): Dictionary to update with package information
+            secure_file_ops (object): Optional SecureFileOps instance for safe file operations
+
+        Returns:
+            Updated packages_dict with Go modules
+        """
         basename = os.path.basename(file_path)
 
-        if basename == "go.mod":
+        if basename == GO_MOD_FILE:
             try:
-                content = self.read_file_content(file_path, secure_file_ops)
-                # Look for require block or individual require lines
-                require_pattern = r"require\s+\((.*?)\)|require\s+([^\s]+)\s+v[^\s]+"
-                matches = re.finditer(require_pattern, content, re.DOTALL)
-
-                for match in matches:
-                    if match.group(1):  # Block require
-                        requires = match.group(1)
-                        dep_pattern = r"([^\s]+)\s+v[0-9.]+"
-                        deps = re.findall(dep_pattern, requires)
-                        for dep in deps:
-                            packages_dict[dep] = {"ecosystem": "go"}
-                    elif match.group(2):  # Single require line
-                        dep = match.group(2)
-                        packages_dict[dep] = {"ecosystem": "go"}
-
+                go_mod = parse_go_mod(self.read_file_content(file_path, secure_file_ops))
+                for require in go_mod["require"]:
+                    entry = packages_dict.setdefault(require["path"], {"ecosystem": "go"})
+                    entry["version"] = require["version"]
+                    if require["indirect"]:
+                        entry["indirect"] = True
+                for replace in go_mod["replace"]:
+                    entry = packages_dict.get(replace["path"])
+                    # A replacement of one version only applies when that version is required
+                    if entry is not None and replace["version"] in (None, entry.get("version")):
+                        entry["replaced_by"] = replacement_source(replace)
             except FileOperationError as e:
                 logger.error(f"Failed to process Go mod file {file_path}: {e}")
             except Exception as e:
                 logger.error(f"Unexpected error processing Go mod file {file_path}", exception=e)
 
+        elif basename == GO_SUM_FILE:
+            try:
+                process_go_sum(file_path, packages_dict, lambda path: self.read_file_content(path, secure_file_ops))
+            except FileOperationError as e:
+                logger.error(f"Failed to process Go sum file {file_path}: {e}")
+            except Exception as e:
+                logger.error(f"Unexpected error processing Go sum file {file_path}", exception=e)
+
         return packages_dict
 
     def normalize_package_name(self, package_path):
@@ -164,7 +381,9 @@ class GoLanguageHandler(LanguageHandler):
             return package_path
         return None  # Likely standard library
 
-    def extract_imports(self, tree_node, rel_path, file_components_dict, local_resolver_func, logger=None):
+    def extract_imports(
+        self, tree_node, rel_path, file_components_dict, local_resolver_func, logger=None, import_annotations=None
+    ):
         """
         Extract external package imports and resolved local imports from a Go source file
 
@@ -174,10 +393,39 @@ class GoLanguageHandler(LanguageHandler):
             file_components_dict (dict): Dictionary to track imported external components
             local_resolver_func (callable): Function to resolve local imports
             logger (Logger): Optional logger instance for debug output
+            import_annotations (dict): Optional mapping of import name to annotations, updated in place
 
         Returns:
             Tuple of (external_imports, local_imports)
         """
         visitor = GoImportVisitor(rel_path, file_components_dict, local_resolver_func)
         visitor.visit(tree_node)
+        annotation = self._build_constraints(tree_node, rel_path) if import_annotations is not None else None
+        for package_path in visitor.imports if annotation else []:
+            import_annotations.setdefault(package_path, {}).update(annotation)
         return visitor.imports, visitor.local_imports
+
+    def _build_constraints(self, tree_node, rel_path):
+        """
+        Return the import annotation for the build constraints of a Go file: its //go:build line
+        (or legacy `// +build` lines) and its file name suffixes
+
+        Args:
+            tree_node (object): Tree-sitter node for the source file
+            rel_path (str): Relative path of the file
+
+        Returns:
+            dict: Result of build_annotation; empty for files built everywhere
+        """
+        comments = []
+        for child in getattr(tree_node, "children", None) or []:
+            if child.type == "package_clause":
+                break
+            if child.type == "comment" and child.text:
+                comments.append(child.text.decode("utf-8", errors="replace"))
+        header = header_build_constraint(comments)
+        suffix, test = filename_build_constraint(rel_path)
+        if header and suffix:
+            header = f"({header})" if "||" in header else header
+        expression = " && ".join(part for part in (header, suffix) if part) or None
+        return build_annotation(expression, test)
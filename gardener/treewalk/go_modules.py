@@ -0,0 +1,203 @@
+"""
+Go module files
+
+go.mod `require` directives (single lines or parenthesized blocks) declare the modules a module
+depends on with their `version`; requirements marked `// indirect` are only needed by other
+modules and get `indirect: true`. `replace` directives swap a module (or one version of it) for
+another module path and version, or for a local directory: entries keep the declared module path
+and record the effective source under `replaced_by` (`path`, `version`, and `local: true` for
+directories). go.sum lists the checksums of every module version the build consulted; versions
+with a module hash, not only a `/go.mod` hash, were downloaded and give each module its
+`locked_version` (`locked_versions` when several were) and `hashes`. Modules the go.mod next to
+it does not require directly are added with `direct: false` and `transitive: true`, and the
+checksums of a replacement module are credited to the module it replaces
+"""
+
+import os
+import re
+
+GO_SUM_FILE = "go.sum"
+GO_MOD_FILE = "go.mod"
+GO_WORK_FILE = "go.work"
+# 'v1.2.3', 'v0.0.0-20230101000000-abcdef123456', 'v2.0.0+incompatible'
+SEMVER_RE = re.compile(r"^v(\d+)(?:\.(\d+))?(?:\.(\d+))?(?:-([^+]*))?")
+
+
+def _directives(content):
+    """
+    Yield (verb, arguments, comment) for every directive of a go.mod, blocks expanded
+    """
+    block = None
+    for raw in content.splitlines():
+        line, _, comment = raw.partition("//")
+        tokens = line.split()
+        if block is not None:
+            if tokens[:1] == [")"]:
+                block = None
+            elif tokens:
+                yield block, tokens, comment.strip()
+            continue
+        if not tokens:
+            continue
+        if tokens[1:2] == ["("]:
+            block = tokens[0]
+            continue
+        yield tokens[0], tokens[1:], comment.strip()
+
+
+def _unquote(token):
+    return token.strip('"`')
+
+
+def parse_go_mod(content):
+    """
+    Parse the module path, requirements and replacements of a go.mod
+
+    Args:
+        content (str): go.mod content
+
+    Returns:
+        dict: {'module', 'require': [{'path', 'version', 'indirect'}], 'replace': [{'path', 'version',
+            'new_path', 'new_version', 'local'}]}; versions a replace does not name are None
+    """
+    parsed = {"module": None, "require": [], "replace": []}
+    for verb, args, comment in _directives(content):
+        if verb == "module" and args:
+            parsed["module"] = _unquote(args[0])
+        elif verb == "require" and len(args) >= 2:
+            indirect = comment.split(";")[0].strip() == "indirect"
+            parsed["require"].append({"path": _unquote(args[0]), "version": args[1], "indirect": indirect})
+        elif verb == "replace" and "=>" in args:
+            arrow = args.index("=>")
+            old, new = args[:arrow], args[arrow + 1 :]
+            if not old or not new:
+                continue
+            parsed["replace"].append(
+                {
+                    "path": _unquote(old[0]),
+                    "version": old[1] if len(old) > 1 else None,
+                    "new_path": _unquote(new[0]),
+                    "new_version": new[1] if len(new) > 1 else None,
+                    # Replacements without a version are directories ('../fork', '/src/fork')
+                    "local": len(new) == 1,
+                }
+            )
+    return parsed
+
+
+def parse_go_work(content):
+    """
+    Parse the module directories a go.work uses
+
+    Args:
+        content (str): go.work content
+
+    Returns:
+        dict: {'use': [directory, ...]}, directories as written ('./api', '.')
+    """
+    return {"use": [_unquote(args[0]) for verb, args, _ in _directives(content) if verb == "use" and args]}
+
+
+def replacement_source(replace):
+    """
+    Describe the effective source of a replaced module: {'path', 'version'} or {'path', 'local': True}
+    """
+    if replace["local"]:
+        return {"path": replace["new_path"], "local": True}
+    return {"path": replace["new_path"], "version": replace["new_version"]}
+
+
+def parse_go_sum(content):
+    """
+    Read the checksums of a go.sum
+
+    Args:
+        content (str): go.sum content
+
+    Returns:
+        dict: Module path -> version -> {'hashes': [...], 'downloaded': bool}, where downloaded
+            versions have a module hash and not only a `/go.mod` one
+    """
+    sums = {}
+    for line in content.splitlines():
+        parts = line.split()
+        if len(parts) != 3:
+            continue
+        path, version, digest = parts
+        go_mod_only = version.endswith("/go.mod")
+        versions = sums.setdefault(path, {})
+        entry = versions.setdefault(version.removesuffix("/go.mod"), {"hashes": [], "downloaded": False})
+        entry["hashes"].append(digest)
+        entry["downloaded"] = entry["downloaded"] or not go_mod_only
+    return sums
+
+
+def version_key(version):
+    """
+    Sort key for Go module versions; pre-releases and pseudo-versions sort before their release
+    """
+    match = SEMVER_RE.match(version or "")
+    if not match:
+        return (-1, 0, 0, 0, version or "")
+    major, minor, patch, pre = match.groups()
+    return (int(major), int(minor or 0), int(patch or 0), 0 if pre else 1, pre or "")
+
+
+def record_go_sum(packages_dict, sums, go_mod):
+    """
+    Write go.sum versions and hashes into go package entries
+
+    `hashes` are those of the locked version; a module stays `direct` once any go.mod requires it directly
+
+    Args:
+        packages_dict (dict): Dictionary to update with package information
+        sums (dict): Result of parse_go_sum
+        go_mod (dict): Result of parse_go_mod for the go.mod next to the go.sum
+    """
+    required = {require["path"]: require for require in go_mod["require"]}
+    # Checksums of a replacement module belong to the module it replaces
+    replaced = {replace["new_path"]: replace for replace in go_mod["replace"] if not replace["local"]}
+    for path, versions in sorted(sums.items()):
+        replace = replaced.get(path)
+        module = replace["path"] if replace else path
+        if module == go_mod["module"]:
+            continue
+        downloaded = sorted((version for version, info in versions.items() if info["downloaded"]), key=version_key)
+        if not downloaded:
+            continue
+        declared = replace["new_version"] if replace else (required.get(module) or {}).get("version")
+        entry = packages_dict.setdefault(module, {"ecosystem": "go"})
+        entry["locked_version"] = declared if declared in downloaded else downloaded[-1]
+        if len(downloaded) > 1:
+            entry["locked_versions"] = downloaded
+        entry["hashes"] = sorted(set(versions[entry["locked_version"]]["hashes"]))
+        # Another go.sum of the repository may already have found the module required directly
+        entry["direct"] = entry.get("direct", False) or (module in required and not required[module]["indirect"])
+        if entry["direct"]:
+            entry.pop("transitive", None)
+        else:
+            entry["transitive"] = True
+    for replace in go_mod["replace"]:
+        version = (required.get(replace["path"]) or {}).get("version")
+        if replace["path"] in packages_dict and replace["version"] in (None, version):
+            packages_dict[replace["path"]].setdefault("replaced_by", replacement_source(replace))
+
+
+def process_go_sum(file_path, packages_dict, read):
+    """
+    Record the module versions of a go.sum
+
+    Args:
+        file_path (str): go.sum path
+        packages_dict (dict): Dictionary to update with package information
+        read (callable): Path -> file content
+
+    Returns:
+        dict: The updated packages_dict
+    """
+    try:
+        go_mod = parse_go_mod(read(os.path.join(os.path.dirname(file_path), GO_MOD_FILE)))
+    except Exception:
+        go_mod = {"module": None, "require": [], "replace": []}
+    record_go_sum(packages_dict, parse_go_sum(read(file_path)), go_mod)
+    return packages_dict
@@ -0,0 +1,98 @@
+"""
+Helm chart dependency handler
+
+Charts declare their dependencies in `Chart.yaml` (apiVersion v2) or `requirements.yaml` (v1),
+with pinned versions in `Chart.lock` / `requirements.lock`. Charts have no source files to scan,
+so this handler only processes manifests; the dependencies are reported alongside code packages
+with ecosystem 'helm'
+"""
+
+from urllib.parse import urlsplit
+
+import yaml
+
+from gardener.treewalk.base import ManifestOnlyHandler
+
+HELM_DEPENDENCY_FILES = ["Chart.yaml", "requirements.yaml"]
+HELM_LOCK_FILES = ["Chart.lock", "requirements.lock"]
+
+
+def chart_repository_id(repository):
+    """
+    Return a stable identifier for a chart repository reference
+
+    Args:
+        repository (str): `repository` value, e.g. 'https://charts.bitnami.com/bitnami',
+            'oci://registry-1.docker.io/bitnamicharts' or a `helm repo add` alias ('@bitnami', 'alias:bitnami')
+
+    Returns:
+        str|None: Host and path for URLs, the alias name for aliases, or None for local (file://) charts
+    """
+    repository = (repository or "").strip()
+    if not repository or repository.startswith("file://"):
+        return None
+    if repository.startswith("@"):
+        return repository[1:] or None
+    if repository.startswith("alias:"):
+        return repository[len("alias:") :] or None
+    parts = urlsplit(repository)
+    if parts.netloc:
+        return f"{parts.netloc}{parts.path}".rstrip("/")
+    return repository.rstrip("/")
+
+
+def chart_package_name(name, repository):
+    """
+    Return the package identity for a chart dependency ('<repository id>/<chart>')
+
+    Qualifying by repository keeps charts apart from same-named packages in other ecosystems
+
+    Args:
+        name (str): Chart name
+        repository (str): Chart repository reference
+
+    Returns:
+        str|None: Package name, or None for local charts
+    """
+    repo_id = chart_repository_id(repository)
+    if not name or not repo_id:
+        return None
+    return f"{repo_id}/{name}"
+
+
+class HelmLanguageHandler(ManifestOnlyHandler):
+    """
+    Handler for Helm chart dependencies (manifest-only)
+    """
+
+    MANIFEST_FILES = tuple(HELM_DEPENDENCY_FILES + HELM_LOCK_FILES)
+    PARSE_ERRORS = (yaml.YAMLError,)
+
+    def parse_manifest(self, basename, content, packages_dict, file_path):
+        """
+        Record Chart.yaml / requirements.yaml dependencies and the versions their lock files pin
+        """
+        document = yaml.safe_load(content) or {}
+        dependencies = document.get("dependencies") if isinstance(document, dict) else None
+        for dependency in dependencies or []:
+            if not isinstance(dependency, dict):
+                continue
+            name = str(dependency.get("name") or "").strip()
+            repository = str(dependency.get("repository") or "").strip()
+            package_name = chart_package_name(name, repository)
+            if not package_name:
+                self.log.debug(f"Skipping local chart dependency '{name}' in {file_path}")
+                continue
+
+            entry = packages_dict.setdefault(
+                package_name, {"ecosystem": "helm", "chart": name, "repository": repository}
+            )
+            version = str(dependency.get("version") or "").strip()
+            if basename in HELM_LOCK_FILES:
+                if version:
+                    entry["locked_version"] = version
+            else:
+                if version:
+                    entry["version"] = version
+                if dependency.get("alias"):
+                    entry.setdefault("aliases", []).append(str(dependency["alias"]))
@@ -0,0 +1,89 @@
+"""
+Homebrew Brewfile dependency handler
+
+A `Brewfile` (Homebrew Bundle) lists the formulae, casks and taps a developer environment needs,
+optionally pinned in `Brewfile.lock.json`. Entries are processed as manifests only; formulae and
+casks resolve to their upstream repository or homepage through the Homebrew formula API
+"""
+
+import json
+import re
+
+from gardener.treewalk.base import ManifestOnlyHandler
+
+BREWFILE = "Brewfile"
+BREWFILE_LOCK = "Brewfile.lock.json"
+
+BREWFILE_LINE_RE = re.compile(r"^\s*(brew|cask|tap)\s+['\"]([^'\"]+)['\"](?:\s*,\s*['\"]([^'\"]+)['\"])?")
+
+# Fully-qualified prefixes Homebrew uses for formulae and casks from its official taps
+CORE_TAPS = {"brew": "homebrew/core", "cask": "homebrew/cask"}
+
+
+def homebrew_package_name(kind, name):
+    """
+    Return the package identity for a Brewfile entry
+
+    Formulae and casks are fully qualified the way Homebrew itself accepts them, so 'jq' becomes
+    'homebrew/core/jq' and the cask 'docker' becomes 'homebrew/cask/docker'; entries that already
+    name their tap ('hashicorp/tap/terraform') and taps themselves ('hashicorp/tap') are kept
+
+    Args:
+        kind (str): 'brew', 'cask' or 'tap'
+        name (str): Name as written in the Brewfile
+
+    Returns:
+        str: Package name
+    """
+    name = name.strip()
+    if kind == "tap" or "/" in name:
+        return name
+    return f"{CORE_TAPS[kind]}/{name}"
+
+
+class HomebrewLanguageHandler(ManifestOnlyHandler):
+    """
+    Handler for Homebrew Brewfile dependencies (manifest-only)
+    """
+
+    MANIFEST_FILES = (BREWFILE, BREWFILE_LOCK)
+    PARSE_ERRORS = (json.JSONDecodeError,)
+
+    def parse_manifest(self, basename, content, packages_dict, file_path):
+        """
+        Record the entries of a Brewfile or the versions Brewfile.lock.json pins
+        """
+        if basename == BREWFILE_LOCK:
+            self._process_lock(json.loads(content), packages_dict)
+        else:
+            self._process_brewfile(content, packages_dict)
+
+    def _process_brewfile(self, content, packages_dict):
+        """
+        Record `brew`, `cask` and `tap` entries; `mas`, `vscode` and `whalebrew` entries are not packages
+        """
+        for line in content.splitlines():
+            match = BREWFILE_LINE_RE.match(line.split("#", 1)[0])
+            if not match:
+                continue
+            kind, name, tap_url = match.groups()
+            entry = packages_dict.setdefault(
+                homebrew_package_name(kind, name), {"ecosystem": "homebrew", "kind": kind, "formula": name}
+            )
+            if kind == "tap" and tap_url:
+                entry["source"] = tap_url
+
+    def _process_lock(self, lock, packages_dict):
+        """
+        Record versions pinned in Brewfile.lock.json for formulae and casks
+        """
+        entries = lock.get("entries") if isinstance(lock, dict) else None
+        for kind in ("brew", "cask"):
+            for name, pinned in ((entries or {}).get(kind) or {}).items():
+                version = pinned.get("version") if isinstance(pinned, dict) else None
+                if not version:
+                    continue
+                entry = packages_dict.setdefault(
+                    homebrew_package_name(kind, name), {"ecosystem": "homebrew", "kind": kind, "formula": name}
+                )
+                entry["locked_version"] = str(version)
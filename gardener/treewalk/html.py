@@ -0,0 +1,239 @@
+"""
+HTML script, stylesheet and import map handler
+
+Pages load packages without any manifest: `<script src="https://unpkg.com/htmx.org@1.9.12">`,
+`<link rel="stylesheet" href="https://cdn.jsdelivr.net/npm/bulma@1.0.0/css/bulma.min.css">` and
+inline `<script type="importmap">` entries mapping bare specifiers to CDN URLs, which inline
+`<script type="module">` blocks then import. Tags are read with regular expressions and CDN URLs
+(unpkg, jsDelivr, esm.sh, Skypack, JSPM, deno.land, jsr.io) map to npm (or Deno/JSR) package
+identities annotated `evidence: cdn` or `evidence: import-map`. Relative `src`/`href` and import map
+targets link the page to local files
+"""
+
+import json
+import re
+
+from gardener.common.utils import Logger
+from gardener.treewalk.base import LanguageHandler
+from gardener.treewalk.deno import parse_deno_specifier
+
+# Module-level logger instance
+logger = Logger(verbose=False)  # Will be configured by the caller
+
+HTML_EXTENSIONS = [".html", ".htm"]
+# Annotation `evidence` of packages a page loads from a CDN with <script src> or <link href>
+CDN_EVIDENCE = "cdn"
+# Annotation `evidence` of packages an inline import map maps a specifier to
+IMPORT_MAP_EVIDENCE = "import-map"
+
+HTML_COMMENT_RE = re.compile(r"<!--.*?-->", re.S)
+SCRIPT_RE = re.compile(r"<script\b([^>]*)>(.*?)</script\s*>", re.S | re.I)
+LINK_RE = re.compile(r"<link\b([^>]*)>", re.I)
+ATTRIBUTE_RE = re.compile(r"""([\w:-]+)\s*=\s*(?:"([^"]*)"|'([^']*)'|([^\s"'>]+))""")
+# Link relations that load styles or code; icons and other links are left out
+LINK_RELS = {"stylesheet", "modulepreload", "preload"}
+MODULE_SPECIFIER_RE = re.compile(
+    r"""\bimport\s*(?:[\w$\s,*{}]*?\s*from\s*)?(['"])([^'"]+)\1|\bimport\(\s*(['"])([^'"]+)\3\s*\)"""
+)
+JAVASCRIPT_TYPES = {"", "module", "text/javascript", "application/javascript"}
+
+
+def _attributes(text):
+    return {
+        match.group(1).lower(): next(value for value in match.groups()[1:] if value is not None).strip()
+        for match in ATTRIBUTE_RE.finditer(text)
+    }
+
+
+class HtmlDocument:
+    """
+    Parsed HTML page
+
+    Attributes:
+        references (list): (kind, url) pairs for <script src> ('script') and <link href> ('stylesheet',
+            'modulepreload', 'preload') in source order
+        import_map (dict): Specifier -> URL from every inline import map, `scopes` included
+        module_specifiers (list): Specifiers imported by inline module scripts
+    """
+
+    def __init__(self, references=None, import_map=None, module_specifiers=None):
+        self.references = references or []
+        self.import_map = import_map or {}
+        self.module_specifiers = module_specifiers or []
+
+
+def parse_html(code):
+    """
+    Collect script and stylesheet references, import maps and inline module imports of a page
+
+    Args:
+        code (str): HTML content
+
+    Returns:
+        HtmlDocument: Parsed page
+    """
+    code = HTML_COMMENT_RE.sub("", code)
+    document = HtmlDocument()
+    for match in SCRIPT_RE.finditer(code):
+        attributes = _attributes(match.group(1))
+        script_type = attributes.get("type", "").lower()
+        if script_type == "importmap":
+            try:
+                data = json.loads(match.group(2))
+            except ValueError:
+                logger.debug("Skipping an import map that is not valid JSON")
+                continue
+            scopes = data.get("scopes") if isinstance(data, dict) else None
+            for section in [data.get("imports") if isinstance(data, dict) else None] + list(
+                scopes.values() if isinstance(scopes, dict) else []
+            ):
+                for specifier, url in section.items() if isinstance(section, dict) else []:
+                    if isinstance(url, str):
+                        document.import_map.setdefault(specifier, url)
+            continue
+        if script_type not in JAVASCRIPT_TYPES:
+            continue
+        if attributes.get("src"):
+            document.references.append(("script", attributes["src"]))
+        elif script_type == "module":
+            for found in MODULE_SPECIFIER_RE.finditer(match.group(2)):
+                document.module_specifiers.append(found.group(2) or found.group(4))
+    for match in LINK_RE.finditer(code):
+        attributes = _attributes(match.group(1))
+        rels = set(attributes.get("rel", "").lower().split()) & LINK_RELS
+        if rels and attributes.get("href"):
+            document.references.append((sorted(rels)[0], attributes["href"]))
+    return document
+
+
+def cdn_package(url):
+    """
+    Map a CDN URL to the package it serves
+
+    Args:
+        url (str): URL as written, protocol-relative URLs included
+
+    Returns:
+        tuple|None: (ecosystem, package name, version or None), or None for other URLs
+    """
+    if url.startswith("//"):
+        url = f"https:{url}"
+    return parse_deno_specifier(url) if url.startswith(("https://", "http://")) else None
+
+
+def _is_local(url):
+    return not re.match(r"^(?:[a-z][a-z0-9+.-]*:|//)", url, re.I)
+
+
+class HtmlLanguageHandler(LanguageHandler):
+    """
+    Handler for HTML (.html, .htm) pages
+    """
+
+    def __init__(self, logger=None):
+        """
+        Args:
+            logger (Logger): Optional logger instance
+        """
+        self.logger = logger
+        self.cdn_packages = {}  # Package -> {'ecosystem', 'version', 'urls'} for every CDN package of a page
+
+    def get_manifest_files(self):
+        return []
+
+    def get_file_extensions(self):
+        return list(HTML_EXTENSIONS)
+
+    def process_manifest(self, file_path, packages_dict, secure_file_ops=None):
+        return packages_dict
+
+    def parse_source(self, code, rel_path):
+        """
+        Parse pages with regular expressions instead of a tree-sitter grammar
+
+        Args:
+            code (str): File content
+            rel_path (str): Relative path of the file
+
+        Returns:
+            HtmlDocument: Parsed document passed to extract_imports
+        """
+        return parse_html(code)
+
+    def normalize_package_name(self, package_path):
+        """
+        Map a CDN URL to the package it serves
+
+        Args:
+            package_path (str): URL, e.g. 'https://esm.sh/react@18.2.0'
+
+        Returns:
+            str|None: Package name, or None for URLs that are not recognized CDN URLs
+        """
+        parsed = cdn_package(package_path)
+        return parsed[1] if parsed else None
+
+    def _import_map_target(self, import_map, specifier):
+        """
+        Return the URL an import map gives a specifier, longest trailing-slash prefix first
+        """
+        if specifier in import_map:
+            return import_map[specifier]
+        prefixes = [key for key in import_map if key.endswith("/") and specifier.startswith(key)]
+        if not prefixes:
+            return None
+        prefix = max(prefixes, key=len)
+        return import_map[prefix] + specifier[len(prefix) :]
+
+    def extract_imports(
+        self, tree_node, rel_path, file_components_dict, local_resolver_func, logger=None, import_annotations=None
+    ):
+        """
+        Extract CDN packages and local files a page loads
+
+        Args:
+            tree_node (HtmlDocument): Document produced by parse_source
+            rel_path (str): Relative path of the file
+            file_components_dict (dict): Dictionary to track imported external components
+            local_resolver_func (callable): Function resolving a `src`/`href` to a local path
+            logger (Logger): Optional logger instance for debug output
+            import_annotations (dict): Optional mapping of import name to annotations, updated in place
+
+        Returns:
+            Tuple of (external_imports, local_imports)
+        """
+        log = logger or self.logger
+        external_imports = []
+        local_imports = []
+        urls = [(CDN_EVIDENCE, url) for _, url in tree_node.references]
+        urls += [(IMPORT_MAP_EVIDENCE, url) for url in tree_node.import_map.values()]
+        for specifier in tree_node.module_specifiers:
+            mapped = self._import_map_target(tree_node.import_map, specifier)
+            urls.append((IMPORT_MAP_EVIDENCE, mapped) if mapped else (CDN_EVIDENCE, specifier))
+
+        for evidence, url in urls:
+            if _is_local(url):
+                resolved = local_resolver_func(rel_path, url) if local_resolver_func else None
+                if resolved and not resolved.startswith("__PACKAGE:") and resolved not in local_imports:
+                    local_imports.append(resolved)
+                elif not resolved and log:
+                    log.debug(f"Unresolved local reference '{url}' in {rel_path}")
+                continue
+            parsed = cdn_package(url)
+            if parsed is None:
+                continue
+            ecosystem, package, version = parsed
+            if package not in external_imports:
+                external_imports.append(package)
+                file_components_dict[rel_path].append((package, package))
+            if import_annotations is not None:
+                entry = import_annotations.setdefault(package, {})
+                # A package loaded by a tag keeps `cdn` even when an import map also names it
+                if entry.get("evidence") != CDN_EVIDENCE:
+                    entry["evidence"] = evidence
+                entry["urls"] = sorted(set(entry.get("urls", [])) | {url})
+            record = self.cdn_packages.setdefault(package, {"ecosystem": ecosystem, "version": version, "urls": []})
+            record["version"] = record["version"] or version
+            if url not in record["urls"]:
+                record["urls"].append(url)
+        return external_imports, local_imports
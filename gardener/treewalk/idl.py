@@ -0,0 +1,197 @@
+"""
+Protobuf, Thrift and Avro IDL import handler
+
+IDL files have no tree-sitter grammar in the language pack, so they are parsed with regular
+expressions into a small document structure that stands in for the syntax tree
+"""
+
+import os
+import re
+
+from gardener.common.utils import Logger
+from gardener.treewalk.base import LanguageHandler
+
+# Module-level logger instance
+logger = Logger(verbose=False)  # Will be configured by the caller
+
+IDL_EXTENSIONS = [".proto", ".thrift", ".avdl"]
+
+BLOCK_COMMENT_RE = re.compile(r"/\*.*?\*/", re.DOTALL)
+LINE_COMMENT_RE = re.compile(r"//[^\n]*")
+HASH_COMMENT_RE = re.compile(r"^\s*#[^\n]*", re.MULTILINE)
+
+PROTO_IMPORT_RE = re.compile(r"\bimport\s+(?:public\s+|weak\s+)?[\"']([^\"']+)[\"']\s*;")
+PROTO_PACKAGE_RE = re.compile(r"\bpackage\s+([\w.]+)\s*;")
+PROTO_OPTION_RE = re.compile(r"\boption\s+(\w+_(?:package|namespace))\s*=\s*\"([^\"]*)\"\s*;")
+THRIFT_INCLUDE_RE = re.compile(r"^\s*include\s+[\"']([^\"']+)[\"']", re.MULTILINE)
+THRIFT_NAMESPACE_RE = re.compile(r"^\s*namespace\s+([\w.*]+)\s+([\w.]+)", re.MULTILINE)
+AVDL_IMPORT_RE = re.compile(r"\bimport\s+(?:idl|protocol|schema)\s+\"([^\"]+)\"\s*;")
+AVDL_NAMESPACE_RE = re.compile(r"@namespace\s*\(\s*\"([^\"]+)\"\s*\)")
+
+# Import path prefixes of commonly shared schema collections mapped to the package providing them
+WELL_KNOWN_IDL_PREFIXES = {
+    "google/protobuf/": "protobuf",
+    "google/api/": "googleapis",
+    "google/rpc/": "googleapis",
+    "google/type/": "googleapis",
+    "google/longrunning/": "googleapis",
+    "validate/": "protoc-gen-validate",
+    "buf/validate/": "protovalidate",
+    "gogoproto/": "gogo-protobuf",
+    "grpc/": "grpc-proto",
+}
+
+
+class IdlDocument:
+    """
+    Parsed IDL file: import paths plus package and target-language package hints
+
+    Attributes:
+        imports (list): Import/include paths in source order
+        package (str|None): Declared schema package or namespace
+        options (dict): Target-language hints such as go_package and java_package
+    """
+
+    def __init__(self, imports=None, package=None, options=None):
+        self.imports = imports or []
+        self.package = package
+        self.options = options or {}
+
+
+def parse_idl(code, rel_path):
+    """
+    Parse IDL source text according to its file extension
+
+    Args:
+        code (str): File content
+        rel_path (str): File path, used to select the dialect
+
+    Returns:
+        IdlDocument: Parsed imports and hints
+    """
+    ext = os.path.splitext(rel_path)[1].lower()
+    text = LINE_COMMENT_RE.sub("", BLOCK_COMMENT_RE.sub("", code))
+    document = IdlDocument()
+
+    if ext == ".thrift":
+        text = HASH_COMMENT_RE.sub("", text)
+        document.imports = THRIFT_INCLUDE_RE.findall(text)
+        for scope, namespace in THRIFT_NAMESPACE_RE.findall(text):
+            key = {"go": "go_package", "java": "java_package"}.get(scope, f"{scope}_namespace")
+            document.options[key] = namespace
+            if scope == "*" or document.package is None:
+                document.package = namespace
+    elif ext == ".avdl":
+        document.imports = AVDL_IMPORT_RE.findall(text)
+        match = AVDL_NAMESPACE_RE.search(text)
+        if match:
+            document.package = match.group(1)
+            document.options["java_package"] = match.group(1)
+    else:
+        document.imports = PROTO_IMPORT_RE.findall(text)
+        match = PROTO_PACKAGE_RE.search(text)
+        if match:
+            document.package = match.group(1)
+        for option, value in PROTO_OPTION_RE.findall(text):
+            document.options[option] = value
+    return document
+
+
+def go_package_import_path(go_package):
+    """
+    Strip the optional `;name` suffix from a go_package option
+
+    Args:
+        go_package (str): Value of `option go_package`
+
+    Returns:
+        str: Go import path
+    """
+    return go_package.split(";", 1)[0].strip()
+
+
+class IdlLanguageHandler(LanguageHandler):
+    """
+    Handler for Protobuf (.proto), Thrift (.thrift) and Avro IDL (.avdl) files
+    """
+
+    def __init__(self, logger=None):
+        """
+        Args:
+            logger (Logger): Optional logger instance
+        """
+        self.logger = logger
+
+    def get_manifest_files(self):
+        return []
+
+    def get_file_extensions(self):
+        return list(IDL_EXTENSIONS)
+
+    def process_manifest(self, file_path, packages_dict, secure_file_ops=None):
+        return packages_dict
+
+    def parse_source(self, code, rel_path):
+        """
+        Parse IDL text with regular expressions instead of a tree-sitter grammar
+
+        Args:
+            code (str): File content
+            rel_path (str): Relative path of the file
+
+        Returns:
+            IdlDocument: Parsed document passed to extract_imports
+        """
+        return parse_idl(code, rel_path)
+
+    def normalize_package_name(self, package_path):
+        """
+        Map an unresolved IDL import path to the package that provides it
+
+        Args:
+            package_path (str): Import path as written, e.g. 'google/protobuf/timestamp.proto'
+
+        Returns:
+            str|None: Package name for well-known schema collections, otherwise None; a path's
+                leading directory says nothing reliable about the package that ships it
+        """
+        for prefix, package in WELL_KNOWN_IDL_PREFIXES.items():
+            if package_path.startswith(prefix):
+                return package
+        return None
+
+    def extract_imports(
+        self, tree_node, rel_path, file_components_dict, local_resolver_func, logger=None, import_annotations=None
+    ):
+        """
+        Extract shared IDL imports, resolving repo-local schema files first
+
+        Args:
+            tree_node (IdlDocument): Document produced by parse_source
+            rel_path (str): Relative path of the file
+            file_components_dict (dict): Dictionary to track imported external components
+            local_resolver_func (callable): Function to resolve local IDL imports
+            logger (Logger): Optional logger instance for debug output
+            import_annotations (dict): Optional mapping of import name to annotations, updated in place
+
+        Returns:
+            Tuple of (external_imports, local_imports)
+        """
+        log = logger or self.logger
+        external_imports = []
+        local_imports = []
+        for import_path in tree_node.imports:
+            resolved = local_resolver_func(rel_path, import_path) if local_resolver_func else None
+            if resolved:
+                if resolved not in local_imports:
+                    local_imports.append(resolved)
+                continue
+            package = self.normalize_package_name(import_path)
+            if not package:
+                if log:
+                    log.debug(f"Unresolved IDL import '{import_path}' in {rel_path}")
+                continue
+            if package not in external_imports:
+                external_imports.append(package)
+            file_components_dict[rel_path].append((package, f"{package}.{os.path.splitext(import_path)[0]}"))
+        return external_imports, local_imports
@@ -0,0 +1,167 @@
+"""
+CocoaPods and Carthage dependency handler
+
+iOS/macOS projects that predate SwiftPM declare dependencies in a `Podfile` (pinned in
+`Podfile.lock`) or a `Cartfile` (pinned in `Cartfile.resolved`). Both are processed as manifests
+only; pods resolve to their source repositories through the CocoaPods trunk, Carthage entries
+carry their repository in the manifest itself
+"""
+
+import re
+from urllib.parse import urlsplit
+
+import yaml
+
+from gardener.treewalk.base import ManifestOnlyHandler
+
+PODFILE = "Podfile"
+PODFILE_LOCK = "Podfile.lock"
+CARTFILES = ["Cartfile", "Cartfile.private"]
+CARTFILE_RESOLVED = "Cartfile.resolved"
+
+POD_LINE_RE = re.compile(r"^\s*pod\s+['\"]([^'\"]+)['\"](.*)$")
+POD_VERSION_RE = re.compile(r"^\s*,\s*['\"]([^'\"]+)['\"]")
+POD_OPTION_RE = re.compile(r":?(\w+)\s*(?:=>|:)\s*['\"]([^'\"]*)['\"]")
+LOCK_ENTRY_RE = re.compile(r"^(\S+)\s+\(([^)]+)\)")
+CARTFILE_LINE_RE = re.compile(r"^\s*(github|git|binary)\s+\"([^\"]+)\"(?:\s+(.*))?$")
+
+
+def pod_name(spec):
+    """
+    Return the pod a spec or subspec belongs to ('Firebase/Analytics' -> 'Firebase')
+
+    Args:
+        spec (str): Pod or subspec name
+
+    Returns:
+        str: Top-level pod name
+    """
+    return spec.split("/", 1)[0].strip()
+
+
+def carthage_package(kind, location):
+    """
+    Return (package_name, package_info) for a Cartfile entry
+
+    GitHub entries use the 'github.com/<owner>/<repo>' identity with ecosystem 'github', so they
+    line up with the same repository declared elsewhere; other git URLs and binary specs use
+    ecosystem 'carthage' and keep their location under `source`
+
+    Args:
+        kind (str): 'github', 'git' or 'binary'
+        location (str): Repository shorthand, git URL or binary spec URL
+
+    Returns:
+        tuple|None: (package_name, package_info), or None for local file:// entries
+    """
+    if kind == "github" and "://" not in location:
+        repo = location[:-4] if location.endswith(".git") else location
+        return f"github.com/{repo}", {"ecosystem": "github"}
+    parts = urlsplit(location)
+    if parts.scheme == "file" or not parts.netloc:
+        return None
+    path = parts.path[:-4] if parts.path.endswith(".git") else parts.path
+    info = {"ecosystem": "carthage", "source": location}
+    if kind == "binary":
+        info["binary"] = True
+    return f"{parts.netloc}{path}".rstrip("/"), info
+
+
+class IosLanguageHandler(ManifestOnlyHandler):
+    """
+    Handler for CocoaPods and Carthage dependencies (manifest-only)
+    """
+
+    MANIFEST_FILES = (PODFILE, PODFILE_LOCK, *CARTFILES, CARTFILE_RESOLVED)
+    PARSE_ERRORS = (yaml.YAMLError,)
+
+    def parse_manifest(self, basename, content, packages_dict, file_path):
+        """
+        Record the pods of a Podfile / Podfile.lock and the entries of a Cartfile / Cartfile.resolved
+        """
+        if basename == PODFILE:
+            self._process_podfile(content, packages_dict)
+        elif basename == PODFILE_LOCK:
+            lock = yaml.safe_load(content) or {}
+            if isinstance(lock, dict):
+                self._process_podfile_lock(lock, packages_dict)
+        else:
+            self._process_cartfile(content, packages_dict, locked=basename == CARTFILE_RESOLVED)
+
+    def _process_podfile(self, content, packages_dict):
+        """
+        Record `pod` declarations; `:path` pods are local and skipped
+        """
+        for line in content.splitlines():
+            match = POD_LINE_RE.match(line.split("#", 1)[0])
+            if not match:
+                continue
+            name = pod_name(match.group(1))
+            rest = match.group(2)
+            options = dict(POD_OPTION_RE.findall(rest))
+            if "path" in options:
+                continue
+            entry = packages_dict.setdefault(name, {"ecosystem": "cocoapods"})
+            version = POD_VERSION_RE.match(rest)
+            if version:
+                entry["version"] = version.group(1)
+            if options.get("git"):
+                entry["source"] = options["git"]
+            elif options.get("podspec"):
+                entry["podspec"] = options["podspec"]
+
+    def _process_podfile_lock(self, lock, packages_dict):
+        """
+        Record locked pod versions and git sources from EXTERNAL SOURCES / CHECKOUT OPTIONS
+        """
+        direct = {pod_name(str(dep).split(" ", 1)[0]) for dep in lock.get("DEPENDENCIES") or []}
+        for pod in lock.get("PODS") or []:
+            spec = next(iter(pod)) if isinstance(pod, dict) else pod
+            match = LOCK_ENTRY_RE.match(str(spec))
+            if not match:
+                continue
+            name = pod_name(match.group(1))
+            entry = packages_dict.setdefault(name, {"ecosystem": "cocoapods"})
+            entry["locked_version"] = match.group(2)
+            if name not in direct:
+                entry["transitive"] = True
+
+        sources = dict(lock.get("EXTERNAL SOURCES") or {})
+        for name, checkout in (lock.get("CHECKOUT OPTIONS") or {}).items():
+            sources.setdefault(name, checkout)
+        for name, source in sources.items():
+            if not isinstance(source, dict) or pod_name(name) not in packages_dict:
+                continue
+            if source.get(":path"):
+                packages_dict.pop(pod_name(name), None)
+            elif source.get(":git"):
+                packages_dict[pod_name(name)]["source"] = source[":git"]
+
+    def _process_cartfile(self, content, packages_dict, locked):
+        """
+        Record Cartfile / Cartfile.private requirements or Cartfile.resolved pins
+        """
+        for line in content.splitlines():
+            match = CARTFILE_LINE_RE.match(line.split("#", 1)[0].rstrip())
+            if not match:
+                continue
+            package = carthage_package(match.group(1), match.group(2))
+            if not package:
+                continue
+            name, info = package
+            entry = packages_dict.setdefault(name, info)
+            requirement = (match.group(3) or "").strip().strip('"')
+            if requirement:
+                entry["locked_version" if locked else "version"] = requirement
+
+    def normalize_package_name(self, package_path):
+        """
+        Map a pod or subspec reference to its pod name
+
+        Args:
+            package_path (str): Pod or subspec name
+
+        Returns:
+            Top-level pod name
+        """
+        return pod_name(package_path)
@@ -6,19 +6,59 @@ import json
 import os
 import re
 
+import yaml
+
 from gardener.common.secure_file_ops import FileOperationError
+from gardener.common.tsl import get_parser
 from gardener.common.utils import Logger
+from gardener.treewalk.annotations import ImportGuards, node_contains, platforms_in_condition
 from gardener.treewalk.base import LanguageHandler, TreeVisitor
+from gardener.treewalk.deno import DENO_MANIFEST_FILES, deno_import_name, process_deno_manifest
+from gardener.treewalk.npm_locks import NPM_LOCK_FILES, process_npm_lock
+from gardener.treewalk.sfc import SFC_EXTENSIONS, component_script
+from gardener.treewalk.stylesheet import STYLESHEET_EXTENSIONS
 
 # Module-level logger instance
 logger = Logger(verbose=False)  # Will be configured by the caller
 
+# package.json dependency sections, most inclusive first; a package declared in several keeps the first as `dep_kind`
+NPM_DEP_KIND_ORDER = ("dependencies", "optionalDependencies", "peerDependencies", "devDependencies")
+
+
+def stronger_npm_dep_kind(kind, other):
+    """
+    Return the more inclusive of two package.json dependency sections; None is ignored
+    """
+    if kind is None or other is None:
+        return kind or other
+    return min(kind, other, key=NPM_DEP_KIND_ORDER.index)
+
+
+# Annotation `evidence` of packages only named by the static prefix of a computed import()/require() specifier
+DYNAMIC_IMPORT_EVIDENCE = "dynamic-import"
+# Status of import()/require() calls whose specifier names no package or file
+DYNAMIC_UNRESOLVED = "dynamic-unresolved"
+# Annotation `evidence` of packages a file only imports for their types (`import type`, `import { type X }`)
+TYPE_ONLY_EVIDENCE = "type-only"
+# Annotation `evidence` of packages a file only imports stylesheets or other assets from
+ASSET_IMPORT_EVIDENCE = "asset-import"
+ASSET_EXTENSIONS = tuple(STYLESHEET_EXTENSIONS) + (
+    ".styl", ".svg", ".png", ".jpg", ".jpeg", ".gif", ".webp", ".avif", ".woff", ".woff2", ".ttf", ".otf", ".eot"
+)
+
 
 class JSImportVisitor(TreeVisitor):
     """
     Visitor for extracting imports from JavaScript/TypeScript tree
     """
 
+    # Conditions that branch on the host platform
+    PLATFORM_CHECK_RE = re.compile(r"process\.platform|\bos\.platform\(\)|\bos\.type\(\)")
+    # Longest expression text kept for a dynamic specifier that cannot be resolved
+    MAX_EXPRESSION_CHARS = 200
+    # Assignment targets that export from a CommonJS module
+    CJS_EXPORT_RE = re.compile(r"^(?:module\.exports|exports)\b")
+
     def __init__(self, rel_path, file_components_dict, local_resolver_func, logger=None):
         super().__init__()
         self.rel_path = rel_path
@@ -27,6 +67,16 @@ class JSImportVisitor(TreeVisitor):
         self.local_imports = []  # Resolved local import paths
         self._resolve_local = local_resolver_func  # Store resolver
         self.logger = logger  # Store logger
+        self.guards = ImportGuards()  # Platform guards per external import
+        self.dynamic_prefixes = {}  # Package -> expressions of import()/require() calls naming it by prefix only
+        self.dynamic_unresolved = []  # {'kind', 'expression', 'line'[, 'prefix']} per unresolvable call
+        self.type_only_imports = set()  # Packages named by type-only import/export statements
+        self.runtime_imports = set()  # Packages named by statements or calls that load them at runtime
+        self.asset_imports = set()  # Packages stylesheets or other assets are imported from
+        self.code_imports = set()  # Packages modules are imported from
+        self.loaded_by = {}  # Package -> {'import', 'require'}: how the file loads it
+        self.esm_syntax = False  # import/export statements seen
+        self.cjs_syntax = False  # require() calls or module.exports/exports.* assignments seen
 
     def normalize_js_package_name(self, module_path):
         """
@@ -41,6 +91,14 @@ class JSImportVisitor(TreeVisitor):
         if module_path.startswith("."):  # Relative
             return None
 
+        # Node subpath imports (`#internal/db`) are always private to the importing package
+        if module_path.startswith("#"):
+            return None
+
+        # Deno `npm:`/`jsr:` specifiers and URL imports
+        if module_path.startswith(("npm:", "jsr:", "https://", "http://")):
+            return deno_import_name(module_path)
+
         if module_path.startswith(("$lib/", "$app/", "$env/")) or module_path in ("$lib", "$app", "$env"):
             return None
 
@@ -121,6 +179,73 @@ class JSImportVisitor(TreeVisitor):
         raw = arg_node.text.decode("utf-8")
         return raw, raw.strip("'\"")
 
+    def _dynamic_specifier(self, call_node):
+        """
+        Classify the first argument of a require()/import() call that is not a string literal
+
+        Returns:
+            Tuple (kind, value): ('literal', specifier) for a template literal without substitutions,
+            ('prefix', static text) for a template literal or `+` concatenation starting with text,
+            ('expression', None) for anything else, or (None, None) without arguments
+        """
+        args = call_node.child_by_field_name("arguments")
+        if not args or args.named_child_count == 0:
+            return None, None
+        arg = args.named_child(0)
+        if arg.type == "template_string":
+            body = arg.text.decode("utf-8")[1:-1]
+            if "${" not in body:
+                return "literal", body
+            return "prefix", body[: body.index("${")]
+        leftmost = arg
+        while leftmost.type == "binary_expression":
+            operator = leftmost.child_by_field_name("operator")
+            if operator is None or operator.text.decode("utf-8") != "+":
+                break
+            leftmost = leftmost.child_by_field_name("left")
+            if leftmost is None:
+                break
+            if leftmost.type == "string":
+                return "prefix", leftmost.text.decode("utf-8")[1:-1]
+        return "expression", None
+
+    def _package_from_prefix(self, prefix):
+        """
+        Return the package a dynamic specifier's static prefix names in full ('lodash/', '@scope/pkg/'), or None
+        """
+        if not prefix or prefix.startswith((".", "/")):
+            return None
+        segments = prefix.split("/")
+        if len(segments) < (3 if prefix.startswith("@") else 2):
+            return None
+        return self.normalize_js_package_name(prefix)
+
+    def _record_dynamic_call(self, node, kind):
+        """
+        Attribute a require()/import() call with a computed specifier to a package when its static prefix names
+        one; otherwise record it as unresolved
+
+        Returns:
+            str|None: Specifier to process like a string literal (template literal without substitutions)
+        """
+        category, value = self._dynamic_specifier(node)
+        if category is None:
+            return None
+        if category == "literal":
+            return value
+        package_name = self._package_from_prefix(value)
+        args = node.child_by_field_name("arguments")
+        expression = args.named_child(0).text.decode("utf-8")[: self.MAX_EXPRESSION_CHARS]
+        if package_name:
+            self.dynamic_prefixes.setdefault(package_name, []).append(expression)
+            self.guards.record(package_name, self._platform_guard(node))
+            return None
+        record = {"kind": kind, "expression": expression, "line": node.start_point[0] + 1}
+        if value:
+            record["prefix"] = value
+        self.dynamic_unresolved.append(record)
+        return None
+
     def _resolve_module_for_import(self, module_path):
         """
         Categorize a module path without mutating state
@@ -224,7 +349,36 @@ class JSImportVisitor(TreeVisitor):
                 return child.text.decode("utf-8").strip("'\"")
         return None
 
-    def _record_export_resolution(self, module_path):
+    def _is_type_only(self, node):
+        """
+        Return True for TypeScript imports and re-exports erased at compile time
+
+        `import type ...`, `export type { ... } from` and statements whose every named specifier is
+        marked `type` (`import { type A, type B }`); a default or namespace binding loads the module
+
+        Args:
+            node (object): import_statement or export_statement node
+        """
+        if any(child.type in ("type", "typeof") for child in node.children):
+            return True
+        clause = next((child for child in node.children if child.type == "import_clause"), node)
+        specifiers = []
+        for child in clause.children:
+            if child.type in ("identifier", "namespace_import", "namespace_export"):
+                return False
+            if child.type in ("named_imports", "export_clause"):
+                specifiers.extend(c for c in child.children if c.type in ("import_specifier", "export_specifier"))
+        return bool(specifiers) and all(
+            any(part.type in ("type", "typeof") for part in specifier.children) for specifier in specifiers
+        )
+
+    def _record_import_kind(self, package_key, module_path, type_only=False, loaded_by="import"):
+        self.loaded_by.setdefault(package_key, set()).add(loaded_by)
+        (self.type_only_imports if type_only else self.runtime_imports).add(package_key)
+        is_asset = module_path.split("?", 1)[0].lower().endswith(ASSET_EXTENSIONS)
+        (self.asset_imports if is_asset else self.code_imports).add(package_key)
+
+    def _record_export_resolution(self, module_path, type_only=False):
         """
         Perform the same resolution/mutation logic used for exports
         """
@@ -232,6 +386,8 @@ class JSImportVisitor(TreeVisitor):
 
         if resolved_local_path and resolved_local_path.startswith("__PACKAGE:"):
             package_name = resolved_local_path[10:]
+            self.guards.record(package_name, None)
+            self._record_import_kind(package_name, module_path, type_only)
             if package_name not in self.imports:
                 self.imports.append(package_name)
         elif resolved_local_path:
@@ -239,9 +395,60 @@ class JSImportVisitor(TreeVisitor):
                 self.local_imports.append(resolved_local_path)
         elif not module_path.startswith("."):  # External or unresolvable alias
             package_name = self.normalize_js_package_name(module_path)
+            self.guards.record(package_name, None)
+            if package_name:
+                self._record_import_kind(package_name, module_path, type_only)
             if package_name and package_name not in self.imports:
                 self.imports.append(package_name)
 
+    def _strip_parens(self, text):
+        text = text.strip()
+        if text.startswith("(") and text.endswith(")"):
+            return text[1:-1].strip()
+        return text
+
+    def _platform_guard(self, node):
+        """
+        Return the platform condition(s) guarding a call expression, if any
+
+        Walks up through `if`/ternary branches and `switch (process.platform)` cases. Imports in
+        the alternative branch are recorded with the negated condition
+
+        Args:
+            node (object): The require()/import() call_expression node
+
+        Returns:
+            Tuple (condition_text, platforms) or None when unguarded
+        """
+        conditions = []
+        platforms = set()
+        child = node
+        parent = node.parent
+        while parent is not None:
+            if parent.type in ("if_statement", "ternary_expression"):
+                condition = parent.child_by_field_name("condition")
+                if condition is not None and not node_contains(condition, child):
+                    text = self._strip_parens(condition.text.decode("utf-8"))
+                    if self.PLATFORM_CHECK_RE.search(text):
+                        negated = node_contains(parent.child_by_field_name("alternative"), child)
+                        conditions.append(f"!({text})" if negated else text)
+                        platforms.update(platforms_in_condition(text, negated))
+            elif parent.type == "switch_case":
+                switch_stmt = parent.parent.parent if parent.parent is not None else None
+                value = switch_stmt.child_by_field_name("value") if switch_stmt is not None else None
+                case_value = parent.child_by_field_name("value")
+                if value is not None and case_value is not None:
+                    value_text = self._strip_parens(value.text.decode("utf-8"))
+                    if self.PLATFORM_CHECK_RE.search(value_text):
+                        text = f"{value_text} === {case_value.text.decode('utf-8')}"
+                        conditions.append(text)
+                        platforms.update(platforms_in_condition(text))
+            child = parent
+            parent = parent.parent
+        if not conditions:
+            return None
+        return " && ".join(reversed(conditions)), sorted(platforms)
+
     def visit_import_statement(self, node):
         """
         Process JavaScript/TypeScript import statements to extract dependencies
@@ -255,6 +462,8 @@ class JSImportVisitor(TreeVisitor):
             node (object): AST node representing an import statement
         """
 
+        self.esm_syntax = True
+        type_only = self._is_type_only(node)
         for child in node.children:
             if child.type == "string":
                 module_path = child.text.decode("utf-8").strip("'\"")
@@ -264,6 +473,8 @@ class JSImportVisitor(TreeVisitor):
                 if category == "framework":
                     # For framework package aliases, create a component using the original module path
                     self.imports.append(package_key)
+                    self.guards.record(package_key, None)
+                    self._record_import_kind(package_key, module_path, type_only)
                     self._ensure_component_bucket()
                     self.file_components_dict[self.rel_path].append((package_key, module_path))
                     continue
@@ -277,6 +488,8 @@ class JSImportVisitor(TreeVisitor):
                     component_prefix_for_name = name_prefix
                 elif category == "external":
                     self.imports.append(package_key)
+                    self.guards.record(package_key, None)
+                    self._record_import_kind(package_key, module_path, type_only)
                     package_name_for_components_key = package_key
                     component_prefix_for_name = name_prefix
 
@@ -301,9 +514,10 @@ class JSImportVisitor(TreeVisitor):
         Args:
             node (object): AST node representing an export statement
         """
+        self.esm_syntax = True
         module_path = self._export_source_string(node)
         if module_path:
-            self._record_export_resolution(module_path)
+            self._record_export_resolution(module_path, self._is_type_only(node))
 
         super().generic_visit(node)
 
@@ -313,17 +527,24 @@ class JSImportVisitor(TreeVisitor):
 
         Handles require() calls for CommonJS modules and dynamic import() expressions
         Extracts both the module being imported and any destructured components from
-        require() calls. Resolves local paths, framework aliases, and external packages
+        require() calls. Resolves local paths, framework aliases, and external packages.
+        Computed specifiers are resolved on a best-effort basis: template literals without
+        substitutions as literals, `import(`lodash/${name}`)` or `require('lodash/' + name)` to
+        the package the static prefix names, and the rest recorded as unresolved
 
         Args:
             node (object): AST node representing a call expression
         """
         is_require = self._is_require_call(node)
         is_dynamic_import = self._is_dynamic_import_call(node)
+        self.cjs_syntax = self.cjs_syntax or is_require
+        loaded_by = "require" if is_require else "import"
 
         raw_text_from_node, module_path = (None, None)
         if is_require or is_dynamic_import:
             raw_text_from_node, module_path = self._string_literal_from_call(node)
+            if module_path is None:
+                module_path = self._record_dynamic_call(node, "require" if is_require else "import")
 
         if (is_require or is_dynamic_import) and module_path:
             category, resolved_value, package_key, _ = self._resolve_module_for_import(module_path)
@@ -332,12 +553,16 @@ class JSImportVisitor(TreeVisitor):
 
             if category == "framework":
                 self.imports.append(package_key)
+                self.guards.record(package_key, self._platform_guard(node))
+                self._record_import_kind(package_key, module_path, loaded_by=loaded_by)
                 package_name_for_components = package_key
             elif category == "local":
                 self.local_imports.append(resolved_value)
                 package_name_for_components = resolved_value
             elif category == "external":
                 self.imports.append(package_key)
+                self.guards.record(package_key, self._platform_guard(node))
+                self._record_import_kind(package_key, module_path, loaded_by=loaded_by)
                 package_name_for_components = package_key
 
             if is_require and package_name_for_components:
@@ -347,6 +572,18 @@ class JSImportVisitor(TreeVisitor):
         for child in node.children:
             self.visit(child)
 
+    def visit_assignment_expression(self, node):
+        """
+        Note CommonJS exports (`module.exports = ...`, `exports.name = ...`) as CommonJS syntax
+
+        Args:
+            node (object): AST node representing an assignment expression
+        """
+        target = node.child_by_field_name("left")
+        if target is not None and self.CJS_EXPORT_RE.match(target.text.decode("utf-8")):
+            self.cjs_syntax = True
+        super().generic_visit(node)
+
 
 class JavaScriptLanguageHandler(LanguageHandler):
     """Handler for JavaScript language"""
@@ -357,14 +594,53 @@ class JavaScriptLanguageHandler(LanguageHandler):
             logger (Logger): Optional logger instance
         """
         self.logger = logger
+        # File -> import()/require() calls whose specifier could not be resolved
+        self.dynamic_unresolved = {}
+        # File -> {'syntax': 'esm'|'cjs'|'mixed'|None, 'loaded_by': package -> ['import'|'require', ...]}
+        self.module_systems = {}
 
     def get_manifest_files(self):
-        return ["package.json"]
+        return ["package.json"] + DENO_MANIFEST_FILES + NPM_LOCK_FILES
 
     def get_file_extensions(self):
-        return [".js", ".jsx", ".mjs", ".cjs", ".svelte"]
+        return [".js", ".jsx", ".mjs", ".cjs"] + list(SFC_EXTENSIONS)
+
+    def parse_source(self, code, rel_path):
+        """
+        Parse the script blocks of Vue, Svelte and Astro components with the JS/TS grammar they use
+
+        Args:
+            code (str): File content
+            rel_path (str): Relative path of the file
+
+        Returns:
+            object|None: Root node of the component's scripts, or None to parse other files with tree-sitter
+        """
+        extension = os.path.splitext(rel_path)[1].lower()
+        if extension not in SFC_EXTENSIONS:
+            return None
+        source, grammar = component_script(code, extension)
+        return get_parser(grammar).parse(bytes(source, "utf-8")).root_node
 
     def process_manifest(self, file_path, packages_dict, secure_file_ops=None):
+        if os.path.basename(file_path) in DENO_MANIFEST_FILES:
+            try:
+                return process_deno_manifest(
+                    file_path, packages_dict, lambda path: self.read_file_content(path, secure_file_ops)
+                )
+            except (ValueError, FileOperationError) as e:
+                logger.error(f"Failed to parse Deno config {file_path}: {e}")
+                return packages_dict
+
+        if os.path.basename(file_path) in NPM_LOCK_FILES:
+            try:
+                return process_npm_lock(
+                    file_path, packages_dict, lambda path: self.read_file_content(path, secure_file_ops)
+                )
+            except (ValueError, yaml.YAMLError, FileOperationError) as e:
+                logger.error(f"Failed to parse lockfile {file_path}: {e}")
+                return packages_dict
+
         try:
             data = self.safe_json_load(file_path, secure_file_ops)
 
@@ -377,15 +653,21 @@ class JavaScriptLanguageHandler(LanguageHandler):
             # Ensure items are strings
             bundle_dependencies = {pkg: "bundled" for pkg in bundle_dependencies_list if isinstance(pkg, str)}
 
-            pnpm_overrides = {}
+            # `pnpm.overrides` only change versions; they are applied after all manifests are read
             pnpm_patched = {}
             if "pnpm" in data and isinstance(data["pnpm"], dict):
                 pnpm_data = data["pnpm"]
-                if "overrides" in pnpm_data and isinstance(pnpm_data["overrides"], dict):
-                    pnpm_overrides = {pkg: version for pkg, version in pnpm_data["overrides"].items()}
                 if "patchedDependencies" in pnpm_data and isinstance(pnpm_data["patchedDependencies"], dict):
                     pnpm_patched = {pkg: "patched" for pkg in pnpm_data["patchedDependencies"].keys()}
 
+            # Section each package is declared in, most inclusive first
+            dep_kinds = {}
+            for kind in NPM_DEP_KIND_ORDER:
+                section = data.get(kind)
+                for name in section if isinstance(section, dict) else {}:
+                    dep_kinds[name] = stronger_npm_dep_kind(dep_kinds.get(name), kind)
+            manifest = secure_file_ops.get_relative_path(file_path) if secure_file_ops else file_path
+
             # Merge all dependency sources
             all_deps = {
                 **dependencies,
@@ -393,7 +675,6 @@ class JavaScriptLanguageHandler(LanguageHandler):
                 **peer_dependencies,
                 **optional_dependencies,
                 **bundle_dependencies,
-                **pnpm_overrides,
                 **pnpm_patched,
             }
 
@@ -403,6 +684,9 @@ class JavaScriptLanguageHandler(LanguageHandler):
                 # Basic check to avoid adding non-package keys if parsing was too broad
                 if isinstance(name, str) and (name.startswith("@") or "/" not in name or "." not in name):
                     packages_dict[name] = {"ecosystem": "npm", "version": version_str}
+                    if name in dep_kinds:
+                        packages_dict[name]["dep_kind"] = dep_kinds[name]
+                        packages_dict[name]["manifest_dep_kinds"] = {manifest: dep_kinds[name]}
 
         except json.JSONDecodeError as e:
             logger.error(f"JSON parse error in {os.path.basename(file_path)} at {file_path}: {e}")
@@ -432,6 +716,9 @@ class JavaScriptLanguageHandler(LanguageHandler):
         if package_path.startswith("."):
             return None
 
+        if package_path.startswith(("npm:", "jsr:", "https://", "http://")):
+            return deno_import_name(package_path)
+
         if package_path.startswith("@"):
             parts = package_path.split("/")
             if len(parts) >= 2:
@@ -441,10 +728,31 @@ class JavaScriptLanguageHandler(LanguageHandler):
         else:
             return package_path.split("/")[0]
 
-    def extract_imports(self, tree_node, rel_path, file_components_dict, local_resolver_func, logger=None):
+    def extract_imports(
+        self, tree_node, rel_path, file_components_dict, local_resolver_func, logger=None, import_annotations=None
+    ):
         """
         Extract external package imports and resolved local imports from a JS/TS source file
         """
         visitor = JSImportVisitor(rel_path, file_components_dict, local_resolver_func, logger)
         visitor.visit(tree_node)
-        return list(set(visitor.imports)), list(set(visitor.local_imports))
+        visitor.guards.apply(import_annotations)
+        for package_name, expressions in visitor.dynamic_prefixes.items():
+            # A package also imported with a literal specifier needs no dynamic evidence
+            if package_name not in visitor.imports and import_annotations is not None:
+                entry = import_annotations.setdefault(package_name, {})
+                entry.update(evidence=DYNAMIC_IMPORT_EVIDENCE, expressions=sorted(set(expressions)))
+        if import_annotations is not None:
+            for package_name in visitor.type_only_imports - visitor.runtime_imports:
+                import_annotations.setdefault(package_name, {})["evidence"] = TYPE_ONLY_EVIDENCE
+            for package_name in visitor.asset_imports - visitor.code_imports:
+                import_annotations.setdefault(package_name, {})["evidence"] = ASSET_IMPORT_EVIDENCE
+        if visitor.dynamic_unresolved:
+            self.dynamic_unresolved[rel_path] = visitor.dynamic_unresolved
+        syntax = {(True, False): "esm", (False, True): "cjs", (True, True): "mixed"}
+        self.module_systems[rel_path] = {
+            "syntax": syntax.get((visitor.esm_syntax, visitor.cjs_syntax)),
+            "loaded_by": {package: sorted(kinds) for package, kinds in visitor.loaded_by.items()},
+        }
+        imports = set(visitor.imports) | set(visitor.dynamic_prefixes)
+        return list(imports), list(set(visitor.local_imports))
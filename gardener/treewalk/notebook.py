@@ -0,0 +1,177 @@
+"""
+Jupyter notebook handler
+
+Extracts the code cells of `.ipynb` files and runs the Python import visitor over each one,
+annotating imports with the indices of the cells they appear in
+"""
+
+import json
+import re
+
+from gardener.common.tsl import get_parser
+from gardener.common.utils import Logger
+from gardener.treewalk.python import PythonLanguageHandler
+
+# Module-level logger instance
+logger = Logger(verbose=False)  # Will be configured by the caller
+
+# Cell magics whose body is still Python
+PYTHON_CELL_MAGICS = {"time", "timeit", "capture", "prun", "debug"}
+
+MAGIC_LINE_RE = re.compile(r"^(\s*)[%!?]")
+
+
+class NotebookDocument:
+    """
+    Parsed notebook: one Python syntax tree per code cell
+
+    Attributes:
+        cells (list): (cell index, tree-sitter root node) tuples in notebook order
+    """
+
+    def __init__(self, cells=None):
+        self.cells = cells or []
+
+
+def notebook_code_cells(content):
+    """
+    Return the source of each code cell in a notebook
+
+    Args:
+        content (str): Raw `.ipynb` JSON
+
+    Returns:
+        list: (cell index, source text) tuples; empty for non-Python kernels or invalid JSON
+    """
+    try:
+        notebook = json.loads(content)
+    except (ValueError, TypeError):
+        return []
+    if not isinstance(notebook, dict):
+        return []
+
+    metadata = notebook.get("metadata") or {}
+    language = (metadata.get("kernelspec") or {}).get("language") or (metadata.get("language_info") or {}).get("name")
+    if language and str(language).lower() != "python":
+        return []
+
+    cells = notebook.get("cells")
+    if cells is None:
+        # nbformat 3 keeps cells inside worksheets and the source under "input"
+        cells = [cell for sheet in notebook.get("worksheets") or [] for cell in sheet.get("cells") or []]
+
+    code_cells = []
+    for index, cell in enumerate(cells):
+        if not isinstance(cell, dict) or cell.get("cell_type") != "code":
+            continue
+        source = cell.get("source", cell.get("input", ""))
+        if isinstance(source, list):
+            source = "".join(source)
+        code_cells.append((index, source or ""))
+    return code_cells
+
+
+def strip_magics(source):
+    """
+    Turn IPython syntax into parseable Python
+
+    Line magics, shell escapes and help lines become `pass` at the same indentation; cells
+    run through a non-Python cell magic (e.g. `%%bash`) are dropped
+
+    Args:
+        source (str): Code cell source
+
+    Returns:
+        str|None: Python source, or None when the cell is not Python
+    """
+    lines = source.splitlines()
+    first = lines[0].lstrip() if lines else ""
+    if first.startswith("%%"):
+        magic = (first[2:].split() or [""])[0]
+        if magic not in PYTHON_CELL_MAGICS:
+            return None
+        lines = lines[1:]
+    return "\n".join(MAGIC_LINE_RE.sub(r"\1pass  # ", line) if MAGIC_LINE_RE.match(line) else line for line in lines)
+
+
+class NotebookLanguageHandler(PythonLanguageHandler):
+    """
+    Handler for Jupyter notebooks
+
+    Reuses the Python handler for import extraction; manifests are left to the Python handler
+    """
+
+    def get_manifest_files(self):
+        return []
+
+    def get_file_extensions(self):
+        return [".ipynb"]
+
+    def parse_source(self, code, rel_path):
+        """
+        Parse every Python code cell of a notebook
+
+        Args:
+            code (str): Raw `.ipynb` JSON
+            rel_path (str): Relative path of the notebook
+
+        Returns:
+            NotebookDocument: Parsed cells passed to extract_imports
+        """
+        parser = get_parser("python")
+        cells = []
+        for index, source in notebook_code_cells(code):
+            python_source = strip_magics(source)
+            if python_source is None or not python_source.strip():
+                continue
+            cells.append((index, parser.parse(bytes(python_source, "utf-8")).root_node))
+        return NotebookDocument(cells)
+
+    def extract_imports(
+        self, tree_node, rel_path, file_components_dict, local_resolver_func, logger=None, import_annotations=None
+    ):
+        """
+        Extract imports cell by cell and record the cell indices as evidence
+
+        Args:
+            tree_node (NotebookDocument): Document produced by parse_source
+            rel_path (str): Relative path of the notebook
+            file_components_dict (dict): Dictionary to track imported external components
+            local_resolver_func (callable): Function to resolve local module paths
+            logger (Logger): Optional logger instance for debug output
+            import_annotations (dict): Optional mapping of import name to annotations, updated in place
+
+        Returns:
+            Tuple of (list of external package names, list of resolved local file paths)
+        """
+        external_imports = []
+        local_imports = []
+        cells_by_import = {}
+        guards_by_import = {}
+
+        for index, root_node in tree_node.cells:
+            cell_annotations = {}
+            cell_external, cell_local = super().extract_imports(
+                root_node, rel_path, file_components_dict, local_resolver_func, logger, cell_annotations
+            )
+            for name in cell_external:
+                if name not in external_imports:
+                    external_imports.append(name)
+                cells_by_import.setdefault(name, []).append(index)
+                guards_by_import.setdefault(name, []).append(cell_annotations.get(name))
+            for path in cell_local:
+                if path not in local_imports:
+                    local_imports.append(path)
+
+        if import_annotations is not None:
+            for name, cells in cells_by_import.items():
+                entry = {"cells": sorted(set(cells))}
+                guards = guards_by_import[name]
+                # A guard only survives when every cell importing the name is guarded
+                if all(guard and guard.get("platform_guard") for guard in guards):
+                    entry["platform_guard"] = " || ".join(sorted({guard["platform_guard"] for guard in guards}))
+                    platforms = sorted({p for guard in guards for p in guard.get("platforms", [])})
+                    if platforms:
+                        entry["platforms"] = platforms
+                import_annotations[name] = entry
+        return external_imports, local_imports
@@ -0,0 +1,242 @@
+"""
+npm, Yarn and pnpm lockfiles
+
+package-lock.json (and npm-shrinkwrap.json, lockfile versions 1-3), yarn.lock (Yarn 1 and the
+YAML lockfiles of Yarn 2+) and pnpm-lock.yaml (v5-v9) add the exact `locked_version` of every
+installed package (`locked_versions` when several are installed), its `integrity` hash (Yarn 2+
+`checksum`) and whether it is a direct dependency of the project or one of its workspaces
+(`direct: true`) or only installed for another package (`direct: false`, `transitive: true`).
+Formats that do not list the project's own dependencies (lockfile v1, Yarn 1) take them from the
+package.json next to the lockfile
+"""
+
+import json
+import os
+import re
+
+import yaml
+
+NPM_LOCK_FILES = ["package-lock.json", "npm-shrinkwrap.json", "yarn.lock", "pnpm-lock.yaml"]
+DEPENDENCY_SECTIONS = ("dependencies", "devDependencies", "optionalDependencies", "peerDependencies")
+# pnpm peer suffixes: '1.2.3(react@18.2.0)' (v6+) and '1.2.3_react@18.2.0' (v5)
+_PNPM_PEER_SUFFIX = re.compile(r"[(_].*$")
+
+
+def _selector_name(selector):
+    """
+    Return the package name of a Yarn selector ('@babel/core@^7.0.0', 'lodash@npm:^4.17.0')
+    """
+    selector = selector.strip().strip('"')
+    at = selector.find("@", 1)
+    return selector[:at] if at > 0 else selector
+
+
+def _manifest_dependencies(data):
+    """
+    Return the names a package.json declares in any dependency section
+    """
+    names = set()
+    for section in DEPENDENCY_SECTIONS:
+        if isinstance(data.get(section), dict):
+            names.update(data[section])
+    return names
+
+
+def parse_package_lock(data, manifest=None):
+    """
+    Read a package-lock.json or npm-shrinkwrap.json
+
+    Args:
+        data (dict): Parsed lockfile
+        manifest (dict|None): Parsed package.json next to the lockfile, for lockfile v1
+
+    Returns:
+        tuple: ({name: [{'version', 'integrity'}]}, direct names, {name: hoisted version})
+    """
+    locked, direct, preferred = {}, set(), {}
+    packages = data.get("packages")
+    if isinstance(packages, dict) and packages:
+        for path, info in packages.items():
+            if not isinstance(info, dict):
+                continue
+            if "node_modules/" not in path:
+                # The project itself ('') and its workspace packages
+                direct.update(_manifest_dependencies(info))
+                continue
+            if info.get("link"):
+                continue
+            name = path.rsplit("node_modules/", 1)[1]
+            locked.setdefault(name, []).append({"version": info.get("version"), "integrity": info.get("integrity")})
+            if path == f"node_modules/{name}":
+                preferred[name] = info.get("version")
+        return locked, direct, preferred
+
+    def _walk(dependencies, top_level):
+        for name, info in (dependencies or {}).items():
+            if not isinstance(info, dict):
+                continue
+            locked.setdefault(name, []).append({"version": info.get("version"), "integrity": info.get("integrity")})
+            if top_level:
+                preferred[name] = info.get("version")
+            _walk(info.get("dependencies"), False)
+
+    _walk(data.get("dependencies"), True)
+    return locked, _manifest_dependencies(manifest or {}), preferred
+
+
+def _yarn_v1_entries(content):
+    """
+    Yield (selectors, fields) for each entry of a Yarn 1 lockfile
+    """
+    selectors, fields = None, {}
+    for line in content.splitlines():
+        if not line.strip() or line.lstrip().startswith("#"):
+            continue
+        indent = len(line) - len(line.lstrip(" "))
+        text = line.strip()
+        if indent == 0 and text.endswith(":"):
+            if selectors:
+                yield selectors, fields
+            selectors, fields = [part.strip().strip('"') for part in text[:-1].split(",")], {}
+        elif selectors and indent == 2 and not text.endswith(":"):
+            key, _, value = text.partition(" ")
+            fields[key.strip('"')] = value.strip().strip('"')
+    if selectors:
+        yield selectors, fields
+
+
+def parse_yarn_lock(content, manifest=None):
+    """
+    Read a yarn.lock of any Yarn version
+
+    Args:
+        content (str): Lockfile text
+        manifest (dict|None): Parsed package.json next to the lockfile, for Yarn 1
+
+    Returns:
+        tuple: ({name: [{'version', 'integrity'|'checksum'}]}, direct names, {name: preferred version})
+    """
+    locked, direct = {}, set()
+    if "__metadata:" in content:
+        data = yaml.safe_load(content) or {}
+        for key, info in data.items():
+            if key == "__metadata" or not isinstance(info, dict):
+                continue
+            selectors = str(key).split(",")
+            if "@workspace:" in selectors[0]:
+                direct.update(_manifest_dependencies(info))
+                continue
+            version = str(info.get("version", ""))
+            for name in dict.fromkeys(_selector_name(selector) for selector in selectors):
+                candidates = locked.setdefault(name, [])
+                if all(candidate["version"] != version for candidate in candidates):
+                    candidates.append({"version": version, "checksum": info.get("checksum")})
+        return locked, direct, {}
+
+    for selectors, fields in _yarn_v1_entries(content):
+        for name in dict.fromkeys(_selector_name(selector) for selector in selectors):
+            locked.setdefault(name, []).append({"version": fields.get("version"), "integrity": fields.get("integrity")})
+    return locked, _manifest_dependencies(manifest or {}), {}
+
+
+def _pnpm_package_key(key):
+    """
+    Split a pnpm `packages` key into (name, version): '/lodash/4.17.21' or '/react-dom/17.0.2_react@17.0.2'
+    (v5), '/@a/b@1.0.0(peer@1.0.0)' (v6), 'lodash@4.17.21' (v9)
+    """
+    key = str(key).lstrip("/").split("(", 1)[0]
+    # v5 separates the version with a slash; its `_peer@x` suffix holds an '@' of its own
+    name, _, version = key.rpartition("/")
+    version = _PNPM_PEER_SUFFIX.sub("", version)
+    if name and version[:1].isdigit() and "@" not in version:
+        return name, version
+    at = key.find("@", 1)
+    if at > 0:
+        return key[:at], _PNPM_PEER_SUFFIX.sub("", key[at + 1 :])
+    return name, version
+
+
+def parse_pnpm_lock(data):
+    """
+    Read a pnpm-lock.yaml
+
+    Args:
+        data (dict): Parsed lockfile
+
+    Returns:
+        tuple: ({name: [{'version', 'integrity'}]}, direct names, {name: version the project uses})
+    """
+    locked, direct, preferred = {}, set(), {}
+    importers = data.get("importers") if isinstance(data.get("importers"), dict) else {".": data}
+    for importer in importers.values():
+        for section in ("dependencies", "devDependencies", "optionalDependencies"):
+            for name, spec in ((importer or {}).get(section) or {}).items():
+                version = str(spec.get("version", "") if isinstance(spec, dict) else spec)
+                if version.startswith(("link:", "file:", "workspace:")):
+                    continue
+                direct.add(name)
+                preferred.setdefault(name, _PNPM_PEER_SUFFIX.sub("", version))
+    for key, info in (data.get("packages") or {}).items():
+        name, version = _pnpm_package_key(key)
+        if name and version:
+            resolution = (info or {}).get("resolution") or {}
+            locked.setdefault(name, []).append({"version": version, "integrity": resolution.get("integrity")})
+    return locked, direct, preferred
+
+
+def record_locked_packages(packages_dict, locked, direct, preferred):
+    """
+    Write lockfile versions, hashes and the direct/transitive distinction into package entries
+
+    Args:
+        packages_dict (dict): Dictionary to update with package information
+        locked (dict): Name -> list of {'version', 'integrity'|'checksum'} installed
+        direct (set): Names the project or its workspaces depend on
+        preferred (dict): Name -> version the project itself uses, among several installed
+    """
+    for name, candidates in locked.items():
+        candidates = [candidate for candidate in candidates if candidate.get("version")]
+        if not candidates:
+            continue
+        chosen = next((c for c in candidates if c["version"] == preferred.get(name)), candidates[0])
+        entry = packages_dict.setdefault(name, {"ecosystem": "npm"})
+        entry["locked_version"] = str(chosen["version"])
+        versions = list(dict.fromkeys(str(candidate["version"]) for candidate in candidates))
+        if len(versions) > 1:
+            entry["locked_versions"] = versions
+        for key in ("integrity", "checksum"):
+            if chosen.get(key):
+                entry[key] = chosen[key]
+        entry["direct"] = name in direct
+        if name not in direct:
+            entry["transitive"] = True
+
+
+def process_npm_lock(file_path, packages_dict, read):
+    """
+    Record the packages of an npm, Yarn or pnpm lockfile
+
+    Args:
+        file_path (str): Lockfile path
+        packages_dict (dict): Dictionary to update with package information
+        read (callable): Path -> file content
+
+    Returns:
+        dict: The updated packages_dict
+    """
+    content = read(file_path)
+    basename = os.path.basename(file_path)
+    manifest = None
+    if basename in ("package-lock.json", "npm-shrinkwrap.json", "yarn.lock"):
+        try:
+            manifest = json.loads(read(os.path.join(os.path.dirname(file_path), "package.json")))
+        except Exception:
+            manifest = None
+    if basename == "yarn.lock":
+        locked, direct, preferred = parse_yarn_lock(content, manifest)
+    elif basename == "pnpm-lock.yaml":
+        locked, direct, preferred = parse_pnpm_lock(yaml.safe_load(content) or {})
+    else:
+        locked, direct, preferred = parse_package_lock(json.loads(content), manifest)
+    record_locked_packages(packages_dict, locked, direct, preferred)
+    return packages_dict
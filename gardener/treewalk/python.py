@@ -2,20 +2,134 @@
 Python-specific visitors and handlers
 """
 
+import ast
 import configparser
 import os
 import re
 
+import yaml
+
 from gardener.common.defaults import ResourceLimits
 from gardener.common.secure_file_ops import FileOperationError
 from gardener.common.utils import Logger
+from gardener.treewalk.annotations import ImportGuards, node_contains, normalize_platform, platforms_in_condition
 from gardener.treewalk.base import LanguageHandler, TreeVisitor
+from gardener.treewalk.python_locks import PYTHON_LOCK_FILES, process_python_lock
+from gardener.treewalk.requirements import parse_requirement, read_requirements
 
 import tomllib as _toml
 
 # Module-level logger instance
 logger = Logger(verbose=False)  # Will be configured by the caller
 
+# Python dependency kinds, most inclusive first; a package declared under several keeps the first as `dep_kind`
+PYPI_DEP_KIND_ORDER = ("dependencies", "optional-dependencies", "build-dependencies", "dev-dependencies")
+
+# Evidence of modules only imported by importlib.import_module() or __import__() calls
+DYNAMIC_IMPORT_EVIDENCE = "dynamic-import"
+
+# Evidence of imports tried in a try/except ImportError, with the fallbacks tried after them
+OPTIONAL_FALLBACK_EVIDENCE = "optional-fallback"
+
+# Evidence of modules a file only imports under `if TYPE_CHECKING:`, for type checkers
+TYPE_ONLY_EVIDENCE = "type-only"
+
+# Stub-only distributions: typeshed's `types-requests`, PEP 561 `pandas-stubs`
+TYPE_STUBS_RE = re.compile(r"^types[-_.](?P<prefixed>.+)$|^(?P<suffixed>.+?)[-_.]stubs$", re.I)
+
+# Requirements files declaring development or test dependencies
+DEV_REQUIREMENTS_RE = re.compile(r"(^|[-_.])(dev|develop|test|tests|testing|lint|docs)([-_.]|$)", re.I)
+
+# setuptools requirement keywords and the dependency kind they declare; `extras_require` adds optional dependencies
+SETUPTOOLS_DEP_KINDS = {
+    "install_requires": "dependencies",
+    "setup_requires": "build-dependencies",
+    "tests_require": "dev-dependencies",
+}
+
+# Conda match spec: optional 'channel[/subdir]::' prefix, package name, then the version and build
+CONDA_SPEC_RE = re.compile(r"^(?:(?P<channel>\S+?)::)?(?P<name>[A-Za-z0-9_][A-Za-z0-9._-]*)\s*(?P<spec>.*)$")
+
+
+def stronger_pypi_dep_kind(kind, other):
+    """
+    Return the more inclusive of two Python dependency kinds; None is ignored
+    """
+    if kind is None or other is None:
+        return kind or other
+    return min(kind, other, key=PYPI_DEP_KIND_ORDER.index)
+
+
+def type_stubs_for(name):
+    """
+    Return the distribution a stub-only distribution types, or None for other distributions
+
+    Args:
+        name (str): Distribution name, e.g. 'types-requests' or 'pandas-stubs'
+    """
+    match = TYPE_STUBS_RE.match(name or "")
+    return (match.group("prefixed") or match.group("suffixed")) if match else None
+
+
+def _conda_version(spec):
+    """
+    Return the version of a conda match spec remainder (`=1.26=py311h...`, `>=1.11`), without its build string
+    """
+    spec = re.sub(r"\[.*\]", "", spec or "").strip()
+    if not spec:
+        return None
+    version = spec.split()[0]
+    if version.startswith("=") and not version.startswith("=="):
+        version = version[1:]
+    # A single '=' after the version separates the build string
+    return re.split(r"(?<=[\w.*])=(?!=)", version)[0] or None
+
+
+def condition_platforms(condition_text, negated=False):
+    """
+    Extract canonical platform names from a Python condition, following its negations
+
+    Each comparison is read on its own: `!=` and `not in` exclude the platforms they name,
+    and every enclosing `not` flips the comparisons below it. Conditions that do not parse
+    fall back to the literal scan of platforms_in_condition
+
+    Args:
+        condition_text (str): Source text of the guarding condition
+        negated (bool): True when the import sits in the branch taken when the condition is false
+
+    Returns:
+        list: Sorted platform names, prefixed with '!' for excluded platforms
+    """
+    try:
+        tree = ast.parse(condition_text.strip(), mode="eval")
+    except SyntaxError:
+        return platforms_in_condition(condition_text, negated)
+
+    platforms = set()
+
+    def add_literals(node, exclude):
+        for sub in ast.walk(node):
+            if isinstance(sub, ast.Constant) and isinstance(sub.value, str):
+                name = normalize_platform(sub.value)
+                if name:
+                    platforms.add(f"!{name}" if exclude else name)
+
+    def visit(node, exclude):
+        if isinstance(node, ast.UnaryOp) and isinstance(node.op, ast.Not):
+            visit(node.operand, not exclude)
+        elif isinstance(node, ast.BoolOp):
+            for value in node.values:
+                visit(value, exclude)
+        elif isinstance(node, ast.Compare):
+            inequality = any(isinstance(op, (ast.NotEq, ast.NotIn, ast.IsNot)) for op in node.ops)
+            add_literals(node, exclude != inequality)
+        else:
+            # `sys.platform.startswith('linux')` and other calls test for the platforms they name
+            add_literals(node, exclude)
+
+    visit(tree.body, negated)
+    return sorted(platforms)
+
 
 class PythonImportVisitor(TreeVisitor):
     """
@@ -25,6 +139,15 @@ class PythonImportVisitor(TreeVisitor):
     package dependencies and imported components
     """
 
+    # Longest import_module()/__import__() argument kept as evidence
+    MAX_EXPRESSION_CHARS = 200
+    # Conditions that branch on the host platform
+    PLATFORM_CHECK_RE = re.compile(r"\bsys\.platform\b|\bplatform\.system\(\)|\bos\.name\b")
+    # Except clauses handling a failed import: `except ImportError`, `except (ModuleNotFoundError, ...)`, bare `except:`
+    IMPORT_ERROR_CLAUSE_RE = re.compile(r"^except\s*(?::|[^:]*\b(?:ImportError|ModuleNotFoundError)\b)")
+    # `if TYPE_CHECKING:` and `if typing.TYPE_CHECKING:` conditions, true only for type checkers
+    TYPE_CHECKING_RE = re.compile(r"^(?:typing(?:_extensions)?\.)?TYPE_CHECKING$")
+
     def __init__(self, rel_path, file_components_dict, local_resolver_func):
         """
         Args:
@@ -40,6 +163,16 @@ class PythonImportVisitor(TreeVisitor):
         self._resolve_local = local_resolver_func  # Store resolver
         self._max_imports = None  # Will be loaded lazily from ResourceLimits
         self._import_limit_logged = False  # To avoid spamming logs
+        self.guards = ImportGuards()  # Platform guards per external import
+        self._current_guard = None  # Guard of the import statement being processed
+        self.fallback_chains = {}  # try statement (start byte) -> external imports tried in order
+        self.unconditional_imports = set()  # External imports made outside any try/except ImportError
+        self._current_try = None  # Outermost try/except ImportError around the import statement being processed
+        self.dynamic_imports = {}  # Top-level module -> import_module()/__import__() arguments naming it
+        self.dynamic_unresolved = []  # {'kind', 'expression', 'line'[, 'prefix']} per unresolvable call
+        self.type_only_imports = set()  # External imports made under `if TYPE_CHECKING:`
+        self.runtime_imports = set()  # External imports made anywhere else
+        self._current_type_checking = False  # Whether the import statement being processed is type-checking only
 
     def _ensure_component_bucket(self):
         """
@@ -61,6 +194,19 @@ class PythonImportVisitor(TreeVisitor):
         if component not in self.file_components_dict[self.rel_path]:
             self.file_components_dict[self.rel_path].append(component)
 
+    def _record_external(self, top_level_package):
+        """
+        Record the platform guard and try/except ImportError fallback of one external import occurrence
+        """
+        self.guards.record(top_level_package, self._current_guard)
+        (self.type_only_imports if self._current_type_checking else self.runtime_imports).add(top_level_package)
+        if self._current_try is None:
+            self.unconditional_imports.add(top_level_package)
+            return
+        chain = self.fallback_chains.setdefault(self._current_try.start_byte, [])
+        if top_level_package not in chain:
+            chain.append(top_level_package)
+
     def _check_import_limit(self):
         """Check if we've hit the import limit for this file"""
         if self._max_imports is None:
@@ -91,6 +237,7 @@ class PythonImportVisitor(TreeVisitor):
             return
 
         top_level_package = package_name_full.split(".")[0]
+        self._record_external(top_level_package)
         if top_level_package not in self.imports:
             if self._check_import_limit():
                 return
@@ -113,11 +260,114 @@ class PythonImportVisitor(TreeVisitor):
                     self.local_imports.append(resolved_local_path)
                 return
             top_level_package = original_package_name_full.split(".")[0]
+            self._record_external(top_level_package)
             if top_level_package not in self.imports:
                 self.imports.append(top_level_package)
             # Component uses original name, not alias
             self._append_component_if_missing(top_level_package, original_package_name_full)
 
+    def _condition_text(self, clause_node):
+        condition = clause_node.child_by_field_name("condition") if clause_node is not None else None
+        return condition.text.decode("utf-8") if condition is not None else ""
+
+    def _platform_guard(self, node):
+        """
+        Return the platform condition(s) guarding an import statement, if any
+
+        Walks up through `if`/`elif`/`else` branches whose conditions test `sys.platform`,
+        `platform.system()` or `os.name`; an `elif` branch also records the negated conditions
+        of the branches before it, and `else` branches record the negated conditions of all
+
+        Args:
+            node (object): The import statement node
+
+        Returns:
+            Tuple (condition_text, platforms) or None when unguarded
+        """
+        conditions = []
+        platforms = set()
+        child = node
+        parent = node.parent
+        while parent is not None:
+            branch_conditions = []
+            if parent.type in ("if_statement", "elif_clause"):
+                if node_contains(parent.child_by_field_name("consequence"), child):
+                    if parent.type == "elif_clause" and parent.parent is not None:
+                        if_node = parent.parent
+                        earlier = [
+                            c for c in if_node.children if c.type == "elif_clause" and c.start_byte < parent.start_byte
+                        ]
+                        for clause in [if_node] + earlier:
+                            branch_conditions.append((self._condition_text(clause), True))
+                    branch_conditions.append((self._condition_text(parent), False))
+            elif parent.type == "else_clause" and parent.parent is not None:
+                if_node = parent.parent
+                for clause in [if_node] + [c for c in if_node.children if c.type == "elif_clause"]:
+                    branch_conditions.append((self._condition_text(clause), True))
+            branch_texts = []
+            for text, negated in branch_conditions:
+                if not text or not self.PLATFORM_CHECK_RE.search(text):
+                    continue
+                branch_texts.append(f"not ({text})" if negated else text)
+                platforms.update(condition_platforms(text, negated))
+            if branch_texts:
+                conditions.append(" and ".join(branch_texts))
+            child = parent
+            parent = parent.parent
+        if not conditions:
+            return None
+        return " and ".join(reversed(conditions)), sorted(platforms)
+
+    def _fallback_try(self, node):
+        """
+        Return the outermost try statement importing with a fallback around an import statement
+
+        The import must sit in the `try` body or in an except clause of a try statement that
+        handles ImportError (or ModuleNotFoundError, or everything); nested fallbacks
+        (`try: import orjson` / `except ImportError: try: import ujson` / ...) belong to the outermost
+
+        Args:
+            node (object): The import statement node
+
+        Returns:
+            object|None: Try statement node, or None when the import is unconditional
+        """
+        outermost = None
+        child = node
+        parent = node.parent
+        while parent is not None:
+            if parent.type == "try_statement":
+                handlers = [
+                    clause
+                    for clause in parent.children
+                    if clause.type == "except_clause" and self.IMPORT_ERROR_CLAUSE_RE.match(clause.text.decode("utf-8"))
+                ]
+                if handlers and (
+                    node_contains(parent.child_by_field_name("body"), child)
+                    or any(node_contains(clause, child) for clause in handlers)
+                ):
+                    outermost = parent
+            child = parent
+            parent = parent.parent
+        return outermost
+
+    def _type_checking(self, node):
+        """
+        Whether an import statement sits in the body of an `if TYPE_CHECKING:` block
+
+        Args:
+            node (object): The import statement node
+        """
+        child = node
+        parent = node.parent
+        while parent is not None:
+            if parent.type == "if_statement" and self.TYPE_CHECKING_RE.match(self._condition_text(parent).strip()):
+                if node_contains(parent.child_by_field_name("consequence"), child):
+                    return True
+            child = parent
+            parent = parent.parent
+        return False
+
     def visit_import_statement(self, node):
         """
         Process Python import statements to extract package and component information
@@ -137,6 +387,9 @@ class PythonImportVisitor(TreeVisitor):
 
         # An 'import_statement' can have multiple 'dotted_name' or 'aliased_import' children
         # e.g. 'import os, sys'
+        self._current_guard = self._platform_guard(node)
+        self._current_try = self._fallback_try(node)
+        self._current_type_checking = self._type_checking(node)
         for child_node in node.children:
             if child_node.type == "dotted_name":
                 self._handle_import_dotted_name(child_node)
@@ -254,6 +507,7 @@ class PythonImportVisitor(TreeVisitor):
         if module_name is None:
             return
         top_level = module_name.split(".")[0]
+        self._record_external(top_level)
         if top_level and top_level not in self.imports:
             if not self._check_import_limit():
                 self.imports.append(top_level)
@@ -282,6 +536,9 @@ class PythonImportVisitor(TreeVisitor):
             return
 
         module_name, relative_level = self._parse_from_header(node)
+        self._current_guard = self._platform_guard(node)
+        self._current_try = self._fallback_try(node)
+        self._current_type_checking = self._type_checking(node)
 
         # Handle __future__ imports
         if module_name == "__future__":
@@ -356,6 +613,83 @@ class PythonImportVisitor(TreeVisitor):
                 if item_name != "*":
                     self._append_component_if_missing("__future__", f"__future__.{item_name}")
 
+    def _dynamic_specifier(self, node):
+        """
+        Classify the module name argument of an import_module()/__import__() call
+
+        Returns:
+            tuple: ('literal', name), ('prefix', static prefix) for f-strings and `'a.' + name`,
+                ('expression', None) otherwise, or (None, None) without arguments
+        """
+        args = node.child_by_field_name("arguments")
+        arg = args.named_children[0] if args is not None and args.named_children else None
+        if arg is None or arg.type == "keyword_argument":
+            return None, None
+        leftmost = arg
+        while leftmost.type == "binary_operator":
+            operator = leftmost.child_by_field_name("operator")
+            if operator is None or operator.text.decode("utf-8") != "+":
+                break
+            leftmost = leftmost.child_by_field_name("left")
+        if leftmost is None or leftmost.type != "string":
+            return "expression", None
+        parts = []
+        for child in leftmost.children:
+            if child.type == "interpolation":
+                return "prefix", "".join(parts)
+            if child.type == "string_content":
+                parts.append(child.text.decode("utf-8"))
+        if not any(child.type == "string_content" for child in leftmost.children):
+            parts = [leftmost.text.decode("utf-8").lstrip("rRbBuUfF").strip("'\"")]
+        return ("literal" if leftmost is arg else "prefix"), "".join(parts)
+
+    def visit_call(self, node):
+        """
+        Process importlib.import_module() and __import__() calls naming the module to import
+
+        String literals import that module, recorded with dynamic-import evidence as the call may
+        never run; f-strings and concatenations whose static prefix names a top-level module
+        (`f"plugins.{name}"`) are attributed to it, and other calls are recorded as unresolved
+
+        Args:
+            node (object): AST node representing a call
+        """
+        function = node.child_by_field_name("function")
+        name = function.text.decode("utf-8") if function is not None else ""
+        # A bare import_module() only after `from importlib import import_module`
+        imported = ("importlib", "import_module") in self.file_components_dict.get(self.rel_path, [])
+        if name in ("importlib.import_module", "__import__") or (name == "import_module" and imported):
+            kind = "__import__" if name == "__import__" else "import_module"
+            category, value = self._dynamic_specifier(node)
+            if category is not None:
+                self._record_dynamic_call(node, kind, category, value)
+        self.generic_visit(node)
+
+    def _record_dynamic_call(self, node, kind, category, value):
+        """
+        Attribute an import_module()/__import__() call to the module it names, or record it as unresolved
+        """
+        args = node.child_by_field_name("arguments")
+        expression = args.named_children[0].text.decode("utf-8")[: self.MAX_EXPRESSION_CHARS]
+        module_name = value if category == "literal" else (value or "").rpartition(".")[0]
+        if module_name and not module_name.startswith("."):
+            resolved_local_path = self._resolve_local(self.rel_path, module_name, 0)
+            if resolved_local_path:
+                if category == "literal" and resolved_local_path not in self.local_imports:
+                    self.local_imports.append(resolved_local_path)
+                return
+            top_level = module_name.split(".")[0]
+            self._current_guard = self._platform_guard(node)
+            self._current_try = self._fallback_try(node)
+            self._current_type_checking = self._type_checking(node)
+            self._record_external(top_level)
+            self.dynamic_imports.setdefault(top_level, []).append(expression)
+            return
+        record = {"kind": kind, "expression": expression, "line": node.start_point[0] + 1}
+        if category == "prefix" and value:
+            record["prefix"] = value
+        self.dynamic_unresolved.append(record)
+
     def visit(self, node):
         """
         Generic visitor method that delegates to parent class
@@ -384,6 +718,8 @@ class PythonLanguageHandler(LanguageHandler):
             logger (Logger): Optional logger instance
         """
         self.logger = logger
+        # File -> import_module()/__import__() calls whose argument names no module
+        self.dynamic_unresolved = {}
 
     def get_manifest_files(self):
         """
@@ -402,10 +738,9 @@ class PythonLanguageHandler(LanguageHandler):
             "setup.py",
             "setup.cfg",
             "Pipfile",
-            "Pipfile.lock",
             "environment.yml",
             "environment.yaml",
-        ]
+        ] + PYTHON_LOCK_FILES
 
     def get_file_extensions(self):
         """
@@ -440,9 +775,7 @@ class PythonLanguageHandler(LanguageHandler):
                 "requirements-pinned.txt",
             ]:
                 try:
-                    content = self.read_file_content(file_path, secure_file_ops)
-                    for name in self._parse_requirements_text(content):
-                        self._add_package(packages_dict, name)
+                    self._process_requirements(file_path, packages_dict, secure_file_ops)
                 except FileOperationError as e:
                     logger.error(f"Failed to read requirements file at {file_path}: {e}")
                 except Exception as e:
@@ -453,93 +786,69 @@ class PythonLanguageHandler(LanguageHandler):
             if basename == "pyproject.toml":
                 try:
                     content = self.read_file_content(file_path, secure_file_ops)
-                    names = self._parse_pyproject(content)
-                    for name in names:
-                        self._add_package(packages_dict, name)
+                    manifest = secure_file_ops.get_relative_path(file_path) if secure_file_ops else file_path
+                    for name, dep_kind, version, extra in self._parse_pyproject(content):
+                        self._add_package(packages_dict, name, version, dep_kind, manifest, extra)
                 except FileOperationError as e:
                     logger.error(f"Failed to read pyproject.toml at {file_path}: {e}")
                 except Exception as e:
                     logger.error(f"Unexpected error processing pyproject.toml at {file_path}", exception=e)
                 return packages_dict
 
-            # 3) setup.cfg (options.install_requires and options.extras_require)
-            if basename == "setup.cfg":
+            # 3) setup.cfg and 4) setup.py (install_requires, extras_require, setup_requires, tests_require)
+            if basename in ("setup.cfg", "setup.py"):
                 try:
-                    cfg = configparser.ConfigParser()
                     content = self.read_file_content(file_path, secure_file_ops)
-                    cfg.read_string(content)
-                    # install_requires can be a newline/comma separated list
-                    if cfg.has_section("options") and cfg.has_option("options", "install_requires"):
-                        raw = cfg.get("options", "install_requires")
-                        for line in raw.splitlines():
-                            name = self._extract_name_from_req_string(line)
-                            if name:
-                                self._add_package(packages_dict, name)
-                    # extras_require groups
-                    if cfg.has_section("options.extras_require"):
-                        for _, value in cfg.items("options.extras_require"):
-                            for line in value.splitlines():
-                                name = self._extract_name_from_req_string(line)
-                                if name:
-                                    self._add_package(packages_dict, name)
+                    manifest = secure_file_ops.get_relative_path(file_path) if secure_file_ops else file_path
+                    parse = self._parse_setup_cfg if basename == "setup.cfg" else self._parse_setup_py
+                    for name, dep_kind, version, extra in parse(content):
+                        self._add_package(packages_dict, name, version, dep_kind, manifest, extra)
                 except FileOperationError as e:
-                    logger.error(f"Failed to read setup.cfg at {file_path}: {e}")
-                except Exception as e:
-                    logger.error(f"Unexpected error processing setup.cfg at {file_path}", exception=e)
-                return packages_dict
-
-            # 4) setup.py (existing simple parser)
-            if basename == "setup.py":
-                try:
-                    content = self.read_file_content(file_path, secure_file_ops)
-                    install_pattern = r"install_requires\s*=\s*\[(.*?)\]"
-                    install_match = re.search(install_pattern, content, re.DOTALL)
-                    if install_match:
-                        deps_content = install_match.group(1)
-                        string_pattern = r"[\'\"]([^\'\"]+)[\'\"]"
-                        for package_str in re.findall(string_pattern, deps_content):
-                            name = self._extract_name_from_req_string(package_str)
-                            if name:
-                                self._add_package(packages_dict, name)
-                except FileOperationError as e:
-                    logger.error(f"Failed to read setup.py at {file_path}: {e}")
+                    logger.error(f"Failed to read {basename} at {file_path}: {e}")
                 except Exception as e:
-                    logger.error(f"Unexpected error processing setup.py at {file_path}", exception=e)
+                    logger.error(f"Unexpected error processing {basename} at {file_path}", exception=e)
                 return packages_dict
 
             # 5) Pipfile (TOML format: [packages], [dev-packages])
             if basename == "Pipfile":
                 try:
                     content = self.read_file_content(file_path, secure_file_ops)
-                    names = self._parse_pipfile(content)
-                    for name in names:
-                        self._add_package(packages_dict, name)
+                    manifest = secure_file_ops.get_relative_path(file_path) if secure_file_ops else file_path
+                    for name, dep_kind, version in self._parse_pipfile(content):
+                        self._add_package(packages_dict, name, version=version, dep_kind=dep_kind, manifest=manifest)
                 except FileOperationError as e:
                     logger.error(f"Failed to read Pipfile at {file_path}: {e}")
                 except Exception as e:
                     logger.error(f"Unexpected error processing Pipfile at {file_path}", exception=e)
                 return packages_dict
 
-            # 6) Pipfile.lock (JSON with "default" and "develop")
-            if basename == "Pipfile.lock":
+            # 6) Lockfiles (Pipfile.lock, poetry.lock, uv.lock)
+            if basename in PYTHON_LOCK_FILES:
                 try:
-                    content = self.read_file_content(file_path, secure_file_ops)
-                    names = self._parse_pipfile_lock(content)
-                    for name in names:
-                        self._add_package(packages_dict, name)
+                    return process_python_lock(
+                        file_path,
+                        packages_dict,
+                        lambda path: self.read_file_content(path, secure_file_ops),
+                        self._manifest_names,
+                    )
                 except FileOperationError as e:
-                    logger.error(f"Failed to read Pipfile.lock at {file_path}: {e}")
+                    logger.error(f"Failed to read {basename} at {file_path}: {e}")
                 except Exception as e:
-                    logger.error(f"Unexpected error processing Pipfile.lock at {file_path}", exception=e)
+                    logger.error(f"Unexpected error processing {basename} at {file_path}", exception=e)
                 return packages_dict
 
             # 7) Conda environment (environment.yml / environment.yaml)
             if basename in ["environment.yml", "environment.yaml"]:
                 try:
                     content = self.read_file_content(file_path, secure_file_ops)
-                    names = self._parse_environment_yml(content)
-                    for name in names:
-                        self._add_package(packages_dict, name)
+                    manifest = secure_file_ops.get_relative_path(file_path) if secure_file_ops else file_path
+                    for name, version, evidence in self._parse_environment_yml(content):
+                        # Channel packages come from conda, not PyPI; only the `pip:` list installs from PyPI
+                        ecosystem = "pypi" if evidence["installer"] == "pip" else "conda"
+                        self._add_package(packages_dict, name, version, "dependencies", manifest, ecosystem=ecosystem)
+                        declarations = packages_dict[name].setdefault("conda_environment", [])
+                        if {"file": manifest, **evidence} not in declarations:
+                            declarations.append({"file": manifest, **evidence})
                 except FileOperationError as e:
                     logger.error(f"Failed to read {basename} at {file_path}: {e}")
                 except Exception as e:
@@ -553,18 +862,72 @@ class PythonLanguageHandler(LanguageHandler):
         return packages_dict
 
     # --- Manifest parsing helpers (Python) ---
-    def _add_package(self, packages_dict, name):
+    def _process_requirements(self, file_path, packages_dict, secure_file_ops=None):
         """
-        Add a package to packages_dict with ecosystem 'pypi'
+        Add the requirements of a requirements file and the files it includes
+
+        Files named for development or tests (requirements-dev.txt, requirements-test.txt) declare
+        `dev-dependencies`, other files `dependencies`; included files take the kind of the file
+        including them. Constraints only set `constraint` on packages required in the same tree
+
+        Args:
+            file_path (str): Requirements file path
+            packages_dict (dict): Dictionary to update with package information
+            secure_file_ops (object): Optional SecureFileOps instance for safe file operations
+        """
+        dep_kind = "dev-dependencies" if DEV_REQUIREMENTS_RE.search(os.path.basename(file_path)) else "dependencies"
+        requirements, constraints = read_requirements(
+            file_path,
+            lambda path: self.read_file_content(path, secure_file_ops),
+            secure_file_ops.get_relative_path if secure_file_ops else None,
+        )
+        for record in requirements:
+            name = record["name"]
+            self._add_package(packages_dict, name, record.get("version"), dep_kind, record["file"])
+            origin = {key: value for key, value in record.items() if key not in ("name", "version")}
+            origins = packages_dict[name].setdefault("requirements", [])
+            if origin not in origins:
+                origins.append(origin)
+        for name, pin in constraints.items():
+            if name in packages_dict:
+                packages_dict[name]["constraint"] = pin
+
+    def _add_package(
+        self, packages_dict, name, version=None, dep_kind=None, manifest=None, extra=None, ecosystem="pypi"
+    ):
+        """
+        Add a package to packages_dict with ecosystem 'pypi', or 'conda' for conda channel packages
+
+        Stub-only distributions (`types-requests`, `pandas-stubs`) get `type_only` and `stubs_for`. A package
+        also declared for PyPI is 'pypi', since pip installs it from there
 
         Args:
             packages_dict (dict): Target dictionary
             name (str): Distribution name
+            version (str|None): Version specifier; the first one declared is kept
+            dep_kind (str|None): Dependency kind (one of PYPI_DEP_KIND_ORDER); the most inclusive is kept
+            manifest (str|None): Manifest declaring the package, recorded under `manifest_dep_kinds`
+            extra (str|None): Extra declaring an optional dependency, added to `extras`
+            ecosystem (str): 'pypi', or 'conda' for a conda channel package
         """
         if not name:
             return
-        if name not in packages_dict:
-            packages_dict[name] = {"ecosystem": "pypi"}
+        entry = packages_dict.setdefault(name, {"ecosystem": ecosystem})
+        if ecosystem == "pypi":
+            entry["ecosystem"] = "pypi"
+        stubs_for = type_stubs_for(name)
+        if stubs_for:
+            # Stubs are only read by type checkers and never imported
+            entry.update(type_only=True, stubs_for=stubs_for)
+        if version and not entry.get("version"):
+            entry["version"] = version
+        if dep_kind:
+            entry["dep_kind"] = stronger_pypi_dep_kind(entry.get("dep_kind"), dep_kind)
+            if manifest:
+                kinds = entry.setdefault("manifest_dep_kinds", {})
+                kinds[manifest] = stronger_pypi_dep_kind(kinds.get(manifest), dep_kind)
+        if extra and extra not in entry.setdefault("extras", []):
+            entry["extras"].append(extra)
 
     def _extract_name_from_req_string(self, s):
         """
@@ -592,6 +955,9 @@ class PythonLanguageHandler(LanguageHandler):
         # Strip environment markers
         if ";" in line:
             line = line.split(";", 1)[0].strip()
+        # Strip direct URL references ('name @ https://...')
+        if "@" in line:
+            line = line.split("@", 1)[0].strip()
         # Strip extras
         if "[" in line:
             line = line.split("[", 1)[0].strip()
@@ -603,270 +969,328 @@ class PythonLanguageHandler(LanguageHandler):
                 break
         return line or None
 
-    def _parse_requirements_text(self, content):
+    def _requirement_version(self, s):
         """
-        Parse requirements file content into a set of distribution names
+        Extract the version specifier of a requirement-like string
 
         Args:
-            content (str): Raw file content
+            s (str): Requirement string (e.g., 'psycopg[binary]>=3.1.0; python_version>"3.10"')
 
         Returns:
-            set[str]: Unique package names
+            str|None: Specifier such as '>=3.1.0', or None when the requirement has none
         """
-        names = set()
-        for raw in content.splitlines():
-            name = self._extract_name_from_req_string(raw)
-            if name:
-                names.add(name)
-        return names
+        line = s.split("#", 1)[0].split(";", 1)[0].strip()
+        line = re.sub(r"\[[^\]]*\]", "", line)
+        if "@" in line:
+            return None  # Direct URL reference
+        match = re.search(r"(===|==|>=|<=|~=|!=|>|<|\^|~)", line)
+        if not match:
+            return None
+        return re.sub(r"\s+", "", line[match.start() :]).strip("()") or None
+
+    def _setuptools_records(self, requirements):
+        """
+        Turn setuptools requirement keywords into (name, dep_kind, version, extra) records
+
+        Args:
+            requirements (dict): Keyword -> list of requirement strings, `extras_require` -> {extra: list}
+
+        Returns:
+            list: (name, dep_kind, version specifier or None, extra name or None) tuples
+        """
+        records = []
+        groups = [(kind, None, requirements[key]) for key, kind in SETUPTOOLS_DEP_KINDS.items() if key in requirements]
+        extras = requirements.get("extras_require")
+        for extra, items in extras.items() if isinstance(extras, dict) else []:
+            # 'name:marker' keys make an extra conditional; ':marker' keys add conditional install requirements
+            extra = str(extra).split(":", 1)[0].strip()
+            groups.append(("optional-dependencies", extra, items) if extra else ("dependencies", None, items))
+        for dep_kind, extra, items in groups:
+            if isinstance(items, str):
+                items = items.splitlines()
+            for raw in items if isinstance(items, (list, tuple)) else []:
+                name = self._extract_name_from_req_string(raw) if isinstance(raw, str) else None
+                if name:
+                    records.append((name, dep_kind, self._requirement_version(raw), extra))
+        return records
+
+    def _parse_setup_cfg(self, content):
+        """
+        Parse the setuptools requirements of a setup.cfg
+
+        Reads `install_requires`, `setup_requires` and `tests_require` under `[options]` and every
+        extra of `[options.extras_require]`, each a newline-separated list
+
+        Args:
+            content (str): setup.cfg text
+
+        Returns:
+            list: (name, dep_kind, version specifier or None, extra name or None) tuples in declaration order
+        """
+        cfg = configparser.ConfigParser(interpolation=None)
+        cfg.read_string(content)
+        requirements = {}
+        if cfg.has_section("options"):
+            options = dict(cfg.items("options"))
+            requirements = {key: options[key] for key in SETUPTOOLS_DEP_KINDS if key in options}
+        if cfg.has_section("options.extras_require"):
+            requirements["extras_require"] = dict(cfg.items("options.extras_require"))
+        return self._setuptools_records(requirements)
+
+    def _parse_setup_py(self, content):
+        """
+        Statically parse the setuptools requirements passed to `setup()` in a setup.py
+
+        Keyword values must be literals (lists, tuples, a dict for `extras_require`) or names assigned
+        a literal at module level (`install_requires=REQUIREMENTS`); computed values are skipped.
+        Files that do not parse as Python 3 fall back to a regex over an `install_requires` list
+
+        Args:
+            content (str): setup.py text
+
+        Returns:
+            list: (name, dep_kind, version specifier or None, extra name or None) tuples in declaration order
+        """
+        try:
+            module = ast.parse(content)
+        except SyntaxError:
+            match = re.search(r"install_requires\s*=\s*\[(.*?)\]", content, re.DOTALL)
+            items = re.findall(r"[\'\"]([^\'\"]+)[\'\"]", match.group(1)) if match else []
+            return self._setuptools_records({"install_requires": items})
+
+        assigned = {}
+        for statement in module.body:
+            if isinstance(statement, ast.Assign) and len(statement.targets) == 1:
+                if isinstance(statement.targets[0], ast.Name):
+                    assigned[statement.targets[0].id] = statement.value
+
+        def _literal(node):
+            if isinstance(node, ast.Name) and node.id in assigned:
+                node = assigned[node.id]
+            try:
+                return ast.literal_eval(node)
+            except (ValueError, TypeError, SyntaxError, MemoryError, RecursionError):
+                return None
+
+        requirements = {}
+        for node in ast.walk(module):
+            if not isinstance(node, ast.Call):
+                continue
+            func = node.func
+            func_name = func.attr if isinstance(func, ast.Attribute) else getattr(func, "id", None)
+            if func_name != "setup":
+                continue
+            for keyword in node.keywords:
+                if keyword.arg in SETUPTOOLS_DEP_KINDS or keyword.arg == "extras_require":
+                    value = _literal(keyword.value)
+                    if value is not None:
+                        requirements[keyword.arg] = value
+        return self._setuptools_records(requirements)
 
     def _parse_pyproject(self, content):
         """
-        Parse pyproject.toml content and collect prod + dev dependencies
+        Parse pyproject.toml content into dependency records with their kind
+
+        PEP 621 `project.dependencies` are `dependencies` and `project.optional-dependencies`
+        `optional-dependencies`; `build-system.requires` are `build-dependencies`; PEP 735
+        `dependency-groups`, Poetry dev groups, PDM and uv dev dependencies and Hatch environments
+        are `dev-dependencies`. Poetry dependencies marked `optional = true` are
+        `optional-dependencies`, with the extras of `tool.poetry.extras` listing them
 
         Args:
             content (str): pyproject.toml text
 
         Returns:
-            set[str]: Unique package names discovered
+            list: (name, dep_kind, version specifier or None, extra name or None) tuples in declaration order
         """
-        names = set()
+        records = []
+
+        def _add(raw, dep_kind, version=None, extra=None):
+            name = self._extract_name_from_req_string(raw)
+            if name and name.lower() not in ("python", "python-version"):
+                version = version if version is not None else self._requirement_version(raw)
+                records.append((name, dep_kind, version, extra))
+
+        def _add_list(items, dep_kind, extra=None):
+            for item in items if isinstance(items, list) else []:
+                if isinstance(item, str):
+                    _add(item, dep_kind, extra=extra)
+
         if _toml is None:
             if self.logger:
                 self.logger.warning("tomllib/tomli not available; limited pyproject.toml parsing will occur")
             # Minimal fallback: attempt to find simple PEP 621 arrays via regex
-            try:
-                deps_match = re.search(
-                    r"\[project\][^\[]*?\bdependencies\s*=\s*\[(.*?)\]", content, re.DOTALL | re.IGNORECASE
-                )
-                if deps_match:
-                    inner = deps_match.group(1)
-                    for m in re.findall(r"[\'\"]([^\'\"]+)[\'\"]", inner):
-                        name = self._extract_name_from_req_string(m)
-                        if name and name.lower() != "python":
-                            names.add(name)
-                optit = re.finditer(r"\[project\.optional-dependencies\][\s\S]*?(?=\n\[|\Z)", content, re.IGNORECASE)
-                for _ in optit:
-                    for m in re.findall(r"[\'\"]([^\'\"]+)[\'\"]", _.group(0)):
-                        name = self._extract_name_from_req_string(m)
-                        if name and name.lower() != "python":
-                            names.add(name)
-                return names
-            except Exception:
-                return names
+            deps_match = re.search(
+                r"\[project\][^\[]*?\bdependencies\s*=\s*\[(.*?)\]", content, re.DOTALL | re.IGNORECASE
+            )
+            if deps_match:
+                for item in re.findall(r"[\'\"]([^\'\"]+)[\'\"]", deps_match.group(1)):
+                    _add(item, "dependencies")
+            for section in re.finditer(r"\[project\.optional-dependencies\][\s\S]*?(?=\n\[|\Z)", content, re.I):
+                for extra, items in re.findall(r"^\s*([\w.-]+)\s*=\s*\[(.*?)\]", section.group(0), re.M | re.S):
+                    for item in re.findall(r"[\'\"]([^\'\"]+)[\'\"]", items):
+                        _add(item, "optional-dependencies", extra=extra)
+            return records
 
         try:
             data = _toml.loads(content)
         except Exception:
-            return names
-
-        project = data.get("project", {}) if isinstance(data, dict) else {}
-        # PEP 621: project.dependencies (list[str])
-        for item in project.get("dependencies", []) or []:
-            name = self._extract_name_from_req_string(item)
-            if name and name.lower() != "python":
-                names.add(name)
-        # PEP 621: project.optional-dependencies (dict[str, list[str]])
-        opt_deps = project.get("optional-dependencies", {}) or {}
-        if isinstance(opt_deps, dict):
-            for group_list in opt_deps.values():
-                for item in group_list or []:
-                    name = self._extract_name_from_req_string(item)
-                    if name and name.lower() != "python":
-                        names.add(name)
-
-        tool = data.get("tool", {}) or {}
-
-        # Poetry
-        poetry = tool.get("poetry", {}) or {}
-
-        def _collect_poetry_table(tbl):
-            if not isinstance(tbl, dict):
-                return
-            for key, val in tbl.items():
-                if not key or key.lower() in ["python", "python-version"]:
+            return records
+        if not isinstance(data, dict):
+            return records
+
+        project = data.get("project") if isinstance(data.get("project"), dict) else {}
+        _add_list(project.get("dependencies"), "dependencies")
+        optional = project.get("optional-dependencies")
+        for extra, group in optional.items() if isinstance(optional, dict) else []:
+            _add_list(group, "optional-dependencies", extra)
+        build_system = data.get("build-system") if isinstance(data.get("build-system"), dict) else {}
+        _add_list(build_system.get("requires"), "build-dependencies")
+        groups = data.get("dependency-groups")
+        for group in groups.values() if isinstance(groups, dict) else []:
+            _add_list(group, "dev-dependencies")
+
+        tool = data.get("tool") if isinstance(data.get("tool"), dict) else {}
+
+        # Poetry: keys are package names, values a version string or a table
+        poetry = tool.get("poetry") if isinstance(tool.get("poetry"), dict) else {}
+
+        poetry_extras = poetry.get("extras") if isinstance(poetry.get("extras"), dict) else {}
+
+        def _collect_poetry_table(tbl, dep_kind):
+            for key, val in tbl.items() if isinstance(tbl, dict) else []:
+                spec = val.get("version") if isinstance(val, dict) else val
+                spec = spec if isinstance(spec, str) and spec != "*" else None
+                if not (isinstance(val, dict) and val.get("optional")):
+                    _add(key, dep_kind, spec)
                     continue
-                # Keys are the package names; prefer them
-                name = self._extract_name_from_req_string(key)
-                if name:
-                    names.add(name)
+                extras = [extra for extra, names in poetry_extras.items() if isinstance(names, list) and key in names]
+                for extra in extras or [None]:
+                    _add(key, "optional-dependencies", spec, extra)
 
-        _collect_poetry_table(poetry.get("dependencies", {}))
-        _collect_poetry_table(poetry.get("dev-dependencies", {}))
-        groups = poetry.get("group", {}) or {}
-        if isinstance(groups, dict):
-            for grp in groups.values():
-                _collect_poetry_table((grp or {}).get("dependencies", {}))
+        _collect_poetry_table(poetry.get("dependencies"), "dependencies")
+        _collect_poetry_table(poetry.get("dev-dependencies"), "dev-dependencies")
+        poetry_groups = poetry.get("group")
+        for group in poetry_groups.values() if isinstance(poetry_groups, dict) else []:
+            _collect_poetry_table((group or {}).get("dependencies"), "dev-dependencies")
 
         # PDM
-        pdm = tool.get("pdm", {}) or {}
+        pdm = tool.get("pdm") if isinstance(tool.get("pdm"), dict) else {}
         pdm_deps = pdm.get("dependencies")
         if isinstance(pdm_deps, dict):
-            for key in pdm_deps.keys():
-                name = self._extract_name_from_req_string(key)
-                if name and name.lower() != "python":
-                    names.add(name)
-        elif isinstance(pdm_deps, list):
-            for item in pdm_deps:
-                name = self._extract_name_from_req_string(item)
-                if name and name.lower() != "python":
-                    names.add(name)
-        pdm_dev = pdm.get("dev-dependencies", {}) or {}
-        if isinstance(pdm_dev, dict):
-            for lst in pdm_dev.values():
-                for item in lst or []:
-                    name = self._extract_name_from_req_string(item)
-                    if name and name.lower() != "python":
-                        names.add(name)
+            for key in pdm_deps:
+                _add(key, "dependencies")
+        _add_list(pdm_deps, "dependencies")
+        pdm_dev = pdm.get("dev-dependencies")
+        for group in pdm_dev.values() if isinstance(pdm_dev, dict) else []:
+            _add_list(group, "dev-dependencies")
+
+        # uv
+        uv = tool.get("uv") if isinstance(tool.get("uv"), dict) else {}
+        _add_list(uv.get("dev-dependencies"), "dev-dependencies")
 
         # Hatch
-        hatch = tool.get("hatch", {}) or {}
-        hatch_meta = hatch.get("metadata", {}) or {}
-        for item in hatch_meta.get("dependencies", []) or []:
-            name = self._extract_name_from_req_string(item)
-            if name and name.lower() != "python":
-                names.add(name)
-        envs = hatch.get("envs", {}) or {}
-        if isinstance(envs, dict):
-            for env in envs.values():
-                for item in (env or {}).get("dependencies", []) or []:
-                    name = self._extract_name_from_req_string(item)
-                    if name and name.lower() != "python":
-                        names.add(name)
-
-        return names
+        hatch = tool.get("hatch") if isinstance(tool.get("hatch"), dict) else {}
+        hatch_meta = hatch.get("metadata") if isinstance(hatch.get("metadata"), dict) else {}
+        _add_list(hatch_meta.get("dependencies"), "dependencies")
+        envs = hatch.get("envs")
+        for env in envs.values() if isinstance(envs, dict) else []:
+            if isinstance(env, dict):
+                _add_list(env.get("dependencies"), "dev-dependencies")
+                _add_list(env.get("extra-dependencies"), "dev-dependencies")
+
+        return records
 
     def _parse_pipfile(self, content):
         """
-        Parse Pipfile (TOML) and collect prod + dev dependencies
+        Parse a Pipfile into dependency records with their kind
+
+        `[packages]` are `dependencies` and `[dev-packages]` `dev-dependencies`; versions are the
+        string value or the `version` key of a table, `*` meaning none
 
         Args:
             content (str): Pipfile content
 
         Returns:
-            set[str]: Unique package names
+            list: (name, dep_kind, version specifier or None) tuples in declaration order
         """
-        names = set()
-        if _toml is None:
-            return names
+        records = []
         try:
             data = _toml.loads(content)
         except Exception:
-            return names
+            return records
 
-        for section in ["packages", "dev-packages"]:
+        for section, dep_kind in (("packages", "dependencies"), ("dev-packages", "dev-dependencies")):
             pkgs = data.get(section, {}) or {}
-            if isinstance(pkgs, dict):
-                for key, val in pkgs.items():
-                    name = self._extract_name_from_req_string(key)
-                    if name and name.lower() != "python":
-                        names.add(name)
-        return names
+            if not isinstance(pkgs, dict):
+                continue
+            for key, val in pkgs.items():
+                name = self._extract_name_from_req_string(key)
+                if not name or name.lower() == "python":
+                    continue
+                version = val.get("version") if isinstance(val, dict) else val
+                records.append((name, dep_kind, version if isinstance(version, str) and version != "*" else None))
+        return records
 
-    def _parse_pipfile_lock(self, content):
+    def _manifest_names(self, basename, content):
         """
-        Parse Pipfile.lock JSON and collect default + develop dependencies
+        Return the names a Pipfile or pyproject.toml declares, for the lockfile next to it
 
         Args:
-            content (str): Pipfile.lock content
+            basename (str): 'Pipfile' or 'pyproject.toml'
+            content (str): Manifest content
 
         Returns:
-            set[str]: Unique package names
+            list: Distribution names as declared
         """
-        import json as _json
-
-        names = set()
-        try:
-            data = _json.loads(content)
-        except Exception:
-            return names
-        for section in ["default", "develop"]:
-            pkgs = data.get(section, {}) or {}
-            if isinstance(pkgs, dict):
-                for key in pkgs.keys():
-                    name = self._extract_name_from_req_string(key)
-                    if name and name.lower() != "python":
-                        names.add(name)
-        return names
+        records = self._parse_pipfile(content) if basename == "Pipfile" else self._parse_pyproject(content)
+        return [record[0] for record in records]
 
     def _parse_environment_yml(self, content):
         """
-        Parse a minimal subset of Conda environment.yml to collect pip and conda-style deps
+        Parse a Conda environment.yml into its conda and pip dependency records
 
-        The parser is YAML-free to avoid adding dependencies. It handles:
-        dependencies:
-          - package==ver
-          - python=3.10  (ignored)
-          - pip
-          - pip:
-            - pkgA==1.2
+        Conda entries are match specs (`numpy=1.26=py311h...`, `scipy>=1.11`, `conda-forge::pandas`);
+        their channel is the `channel::` prefix when present, else the first of the file's `channels`
+        (`defaults` without any), as conda's channel priority picks it. Entries of the nested `pip:`
+        list are PyPI requirements. `python` and `pip` themselves are skipped
 
         Args:
             content (str): environment.yml text
 
         Returns:
-            set[str]: Unique package names
+            list: (name, version or None, evidence) tuples in declaration order; evidence has
+                'installer' ('conda' or 'pip'), plus 'channel' and 'channel_pinned' for conda entries
+                and the marker, extras, editable and url fields of pip requirements
         """
-        names = set()
-        lines = content.splitlines()
-        in_deps = False
-        in_pip_block = False
-        base_indent = None
-
-        def _add_candidate(raw):
-            if not raw:
-                return
-            cand = raw.strip()
-            if not cand or cand.startswith("#"):
-                return
-            # Ignore explicit python pins
-            if cand.startswith("python=") or cand.startswith("python "):
-                return
-            # Strip conda-forge channel prefix
-            cand = cand.split("::")[-1]
-            # Strip version constraints (both conda and pip styles)
-            for op in ["===", "==", ">=", "<=", "~=", "!=", ">", "<", "="]:
-                if op in cand:
-                    cand = cand.split(op, 1)[0].strip()
-                    break
-            name = self._extract_name_from_req_string(cand)
-            if name and name.lower() != "pip":
-                names.add(name)
-
-        for raw in lines:
-            line = raw.rstrip("\n")
-            if not in_deps:
-                if re.match(r"^\s*dependencies\s*:\s*$", line):
-                    in_deps = True
-                    continue
-            else:
-                if re.match(r"^\S", line):
-                    break
-                m_item = re.match(r"^(\s*)-\s+(.+)$", line)
-                if m_item:
-                    indent, item = m_item.groups()
-                    # Treat both 'pip' and 'pip:' (optionally with trailing inline comment) as block opener
-                    if re.fullmatch(r"(?i)pip\s*:?\s*(?:#.*)?", item.strip()):
-                        in_pip_block = True
-                        base_indent = len(indent)
-                        continue
-                    if in_pip_block:
-                        # nested pip entries must be more indented than the 'pip' marker
-                        if base_indent is not None and len(indent) > base_indent:
-                            _add_candidate(item)
-                            continue
-                        else:
-                            # we've left the pip block; fall through to treat this as a top-level dep
-                            in_pip_block = False
-                            base_indent = None
-                            # do not continue here; allow top-level handling below
-                    # Optional safety: skip raw 'pip:' items that aren't block markers
-                    if item.strip().lower().startswith("pip:"):
-                        continue
-                    if not in_pip_block:
-                        _add_candidate(item)
-                    continue
-        return names
+        data = yaml.safe_load(content) or {}
+        if not isinstance(data, dict):
+            return []
+        channels = [
+            str(channel) for channel in data.get("channels") or [] if channel is not None and channel != "nodefaults"
+        ]
+        records = []
+        for item in data.get("dependencies") or []:
+            if isinstance(item, dict):
+                for requirement in item.get("pip") or []:
+                    record = parse_requirement(str(requirement).split(" #", 1)[0].strip())
+                    if record:
+                        name, version = record.pop("name"), record.pop("version", None)
+                        records.append((name, version, {"installer": "pip", **record}))
+                continue
+            match = CONDA_SPEC_RE.match(str(item).strip())
+            if not match or match.group("name").lower() in ("python", "pip"):
+                continue
+            channel = match.group("channel")
+            if channel and "://" not in channel:
+                channel = channel.split("/")[0]
+            evidence = {
+                "installer": "conda",
+                "channel": channel or (channels[0] if channels else "defaults"),
+                "channel_pinned": bool(channel),
+            }
+            records.append((match.group("name"), _conda_version(match.group("spec")), evidence))
+        return records
 
     def normalize_package_name(self, package_path):
         """
@@ -883,7 +1307,9 @@ class PythonLanguageHandler(LanguageHandler):
 
         return package_path.split(".")[0]
 
-    def extract_imports(self, tree_node, rel_path, file_components_dict, local_resolver_func, logger=None):
+    def extract_imports(
+        self, tree_node, rel_path, file_components_dict, local_resolver_func, logger=None, import_annotations=None
+    ):
         """
         Extract external package imports and resolved local imports from a Python source file
 
@@ -893,10 +1319,31 @@ class PythonLanguageHandler(LanguageHandler):
             file_components_dict (dict): Dictionary to track imported external components
             local_resolver_func (callable): Function to resolve local module paths
             logger (Logger): Optional logger instance for debug output
+            import_annotations (dict): Optional mapping of import name to annotations, updated in place
 
         Returns:
             Tuple of (list of external package names, list of resolved local file paths)
         """
         visitor = PythonImportVisitor(rel_path, file_components_dict, local_resolver_func)
         visitor.visit(tree_node)
-        return visitor.imports, visitor.local_imports
+        visitor.guards.apply(import_annotations)
+        dynamic = [name for name in visitor.dynamic_imports if name not in visitor.imports]
+        if import_annotations is not None:
+            for chain in visitor.fallback_chains.values():
+                # An import also made unconditionally is a hard dependency of the file
+                for package_name in [name for name in chain if name not in visitor.unconditional_imports]:
+                    entry = import_annotations.setdefault(package_name, {})
+                    if entry.get("evidence") != OPTIONAL_FALLBACK_EVIDENCE:
+                        entry.update(evidence=OPTIONAL_FALLBACK_EVIDENCE, fallback_chain=list(chain))
+            # Modules only imported by import_module()/__import__() calls, which may never run
+            for package_name in dynamic:
+                entry = import_annotations.setdefault(package_name, {})
+                entry.setdefault("evidence", DYNAMIC_IMPORT_EVIDENCE)
+                entry["expressions"] = sorted(set(visitor.dynamic_imports[package_name]))
+            # Modules only imported for type checkers do not load at runtime
+            for package_name in visitor.type_only_imports - visitor.runtime_imports:
+                import_annotations.setdefault(package_name, {})["evidence"] = TYPE_ONLY_EVIDENCE
+        if visitor.dynamic_unresolved:
+            # Notebooks are extracted cell by cell
+            self.dynamic_unresolved.setdefault(rel_path, []).extend(visitor.dynamic_unresolved)
+        return visitor.imports + dynamic, visitor.local_imports
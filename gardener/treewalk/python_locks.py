@@ -0,0 +1,160 @@
+"""
+Pipenv, Poetry and uv lockfiles
+
+Pipfile.lock, poetry.lock (lock versions 1 and 2) and uv.lock add the exact `locked_version` of
+every installed distribution (`locked_versions` when several are locked, e.g. per platform), its
+`hashes` and whether it is a direct dependency of the project (`direct: true`) or only installed
+for another distribution (`direct: false`, `transitive: true`). uv.lock lists the dependencies of
+the project and its workspace members itself; Pipfile.lock and poetry.lock take them from the
+Pipfile or pyproject.toml next to the lockfile. Lockfiles spell names in their normalized form
+(`pyyaml`), so entries take the spelling that manifest declares (`PyYAML`) by their PEP 503 name
+"""
+
+import json
+import os
+import re
+import tomllib
+
+PYTHON_LOCK_FILES = ["Pipfile.lock", "poetry.lock", "uv.lock"]
+# Manifest next to each lockfile declaring the project's dependencies
+LOCK_MANIFESTS = {"Pipfile.lock": "Pipfile", "poetry.lock": "pyproject.toml", "uv.lock": "pyproject.toml"}
+# uv.lock sources of the project itself and its workspace members
+UV_PROJECT_SOURCES = ("editable", "virtual")
+
+
+def canonical_name(name):
+    """
+    Return the PEP 503 normalized form of a distribution name ('PyYAML' -> 'pyyaml')
+    """
+    return re.sub(r"[-_.]+", "-", str(name)).lower()
+
+
+def parse_pipfile_lock(data):
+    """
+    Read a Pipfile.lock
+
+    Args:
+        data (dict): Parsed lockfile
+
+    Returns:
+        dict: Name -> list of {'version', 'hashes'}
+    """
+    locked = {}
+    for section in ("default", "develop"):
+        for name, info in (data.get(section) or {}).items():
+            if not isinstance(info, dict):
+                continue
+            version = str(info.get("version") or "").lstrip("=") or None
+            locked.setdefault(name, []).append({"version": version, "hashes": list(info.get("hashes") or [])})
+    return locked
+
+
+def parse_poetry_lock(data):
+    """
+    Read a poetry.lock; lock version 1 keeps hashes under `metadata.files`, version 2 with each package
+
+    Args:
+        data (dict): Parsed lockfile
+
+    Returns:
+        dict: Name -> list of {'version', 'hashes'}
+    """
+    legacy_files = (data.get("metadata") or {}).get("files") or {}
+    locked = {}
+    for package in data.get("package") or []:
+        name = package.get("name")
+        if not name:
+            continue
+        files = package.get("files") or legacy_files.get(name) or []
+        hashes = [entry["hash"] for entry in files if isinstance(entry, dict) and entry.get("hash")]
+        locked.setdefault(name, []).append({"version": package.get("version"), "hashes": hashes})
+    return locked
+
+
+def parse_uv_lock(data):
+    """
+    Read a uv.lock
+
+    Args:
+        data (dict): Parsed lockfile
+
+    Returns:
+        tuple: ({name: [{'version', 'hashes'}]}, direct names)
+    """
+    locked, direct = {}, set()
+    for package in data.get("package") or []:
+        name = package.get("name")
+        if not name:
+            continue
+        source = package.get("source") or {}
+        if any(key in source for key in UV_PROJECT_SOURCES):
+            # The project or a workspace member: its dependencies, extras and dependency groups are direct
+            groups = [package.get("dependencies") or []]
+            for table in ("optional-dependencies", "dev-dependencies"):
+                groups.extend((package.get(table) or {}).values())
+            direct.update(dependency["name"] for group in groups for dependency in group if dependency.get("name"))
+            continue
+        artifacts = [package.get("sdist") or {}] + list(package.get("wheels") or [])
+        hashes = [artifact["hash"] for artifact in artifacts if artifact.get("hash")]
+        locked.setdefault(name, []).append({"version": package.get("version"), "hashes": hashes})
+    return locked, direct
+
+
+def record_locked_distributions(packages_dict, locked, direct, spellings=()):
+    """
+    Write lockfile versions, hashes and the direct/transitive distinction into pypi package entries
+
+    Args:
+        packages_dict (dict): Dictionary to update with package information
+        locked (dict): Name -> list of {'version', 'hashes'} locked
+        direct (set): Names the project depends on, in any spelling
+        spellings (iterable): Names as the manifest declares them, used as keys for their entries
+    """
+    names = {canonical_name(name): name for name in list(spellings) + list(packages_dict)}
+    direct = {canonical_name(name) for name in direct}
+    for name, candidates in locked.items():
+        candidates = [candidate for candidate in candidates if candidate.get("version")]
+        if not candidates:
+            continue
+        canonical = canonical_name(name)
+        entry = packages_dict.setdefault(names.get(canonical, name), {"ecosystem": "pypi"})
+        entry["locked_version"] = str(candidates[0]["version"])
+        versions = list(dict.fromkeys(str(candidate["version"]) for candidate in candidates))
+        if len(versions) > 1:
+            entry["locked_versions"] = versions
+        hashes = sorted({digest for candidate in candidates for digest in candidate["hashes"]})
+        if hashes:
+            entry["hashes"] = hashes
+        entry["direct"] = canonical in direct
+        if canonical not in direct:
+            entry["transitive"] = True
+
+
+def process_python_lock(file_path, packages_dict, read, manifest_names):
+    """
+    Record the distributions of a Pipfile.lock, poetry.lock or uv.lock
+
+    Args:
+        file_path (str): Lockfile path
+        packages_dict (dict): Dictionary to update with package information
+        read (callable): Path -> file content
+        manifest_names (callable): (manifest basename, content) -> names the manifest declares
+
+    Returns:
+        dict: The updated packages_dict
+    """
+    content = read(file_path)
+    basename = os.path.basename(file_path)
+    manifest = LOCK_MANIFESTS[basename]
+    try:
+        declared = list(manifest_names(manifest, read(os.path.join(os.path.dirname(file_path), manifest))))
+    except Exception:
+        declared = []
+    if basename == "uv.lock":
+        locked, direct = parse_uv_lock(tomllib.loads(content))
+    elif basename == "Pipfile.lock":
+        locked, direct = parse_pipfile_lock(json.loads(content)), set(declared)
+    else:
+        locked, direct = parse_poetry_lock(tomllib.loads(content)), set(declared)
+    record_locked_distributions(packages_dict, locked, direct, declared)
+    return packages_dict
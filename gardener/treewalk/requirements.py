@@ -0,0 +1,161 @@
+"""
+pip requirements files
+
+Besides `name[extras] <specifier>; <marker>` lines, requirements files include other files
+(`-r base.txt`, `--requirement`), apply constraints files (`-c constraints.txt`, `--constraint`)
+whose pins only restrict the versions of packages required elsewhere, install local or VCS
+checkouts in editable mode (`-e .`, `-e git+https://...#egg=name`, local directories with
+`#egg=name` keeping their `path`) and name direct URLs (`name @ https://...`, or archive URLs with
+`#egg=name`). Included files are followed relative to the including file, once each;
+per-requirement options (`--hash`), comments and line continuations are dropped. Each requirement
+keeps the file declaring it, its line, marker condition, extras, and whether it is editable or a
+direct URL
+"""
+
+import os
+import re
+
+INCLUDE_OPTIONS = ("-r", "--requirement")
+CONSTRAINT_OPTIONS = ("-c", "--constraint")
+EDITABLE_OPTIONS = ("-e", "--editable")
+NAME_RE = re.compile(r"^([A-Za-z0-9][A-Za-z0-9._-]*)\s*(?:\[([^\]]*)\])?\s*(.*)$")
+EGG_RE = re.compile(r"[#&]egg=([A-Za-z0-9][A-Za-z0-9._-]*)")
+URL_RE = re.compile(r"^(?:[a-z][a-z0-9+.-]*://|git\+|hg\+|svn\+|bzr\+)", re.I)
+# pip options allowed after a requirement on the same line
+TRAILING_OPTION_RE = re.compile(r"\s+--?[a-z][\w-]*(?:[=\s]\S+)?", re.I)
+
+
+def _logical_lines(content):
+    """
+    Yield (line number, text) with comments removed and backslash continuations joined
+    """
+    pending, start = "", None
+    for number, raw in enumerate(content.splitlines(), 1):
+        line = re.sub(r"(^|\s)#.*$", "", raw).rstrip()
+        if start is None:
+            start = number
+        if line.endswith("\\"):
+            pending += line[:-1] + " "
+            continue
+        text = (pending + line).strip()
+        pending = ""
+        if text:
+            yield start, text
+        start = None
+    if pending.strip():
+        yield start, pending.strip()
+
+
+def _option(text, options):
+    """
+    Return the value of a `-r file` / `--requirement=file` style option, or None
+    """
+    for option in options:
+        if text == option:
+            return ""
+        for separator in (" ", "="):
+            if text.startswith(option + separator):
+                return text[len(option) + 1 :].strip()
+    return None
+
+
+def parse_requirement(text):
+    """
+    Parse one requirement line
+
+    Args:
+        text (str): Requirement without comments, e.g. 'uvicorn[standard]>=0.29; python_version < "3.12"'
+
+    Returns:
+        dict|None: {'name', 'version', 'extras', 'marker', 'editable', 'url', 'path'} with empty fields left out,
+            or None for lines that are not requirements (options, local paths without a name)
+    """
+    record = {}
+    editable = _option(text, EDITABLE_OPTIONS)
+    if editable is not None:
+        record["editable"] = True
+        text = editable
+    elif text.startswith("-"):
+        return None
+    text, _, marker = text.partition(";")
+    if marker.strip():
+        record["marker"] = marker.strip()
+    text = TRAILING_OPTION_RE.sub("", text).strip()
+
+    if URL_RE.match(text) or text.startswith((".", "/")) or ("@" not in text and "/" in text):
+        egg = EGG_RE.search(text)
+        if not egg:
+            return None
+        result = {"name": egg.group(1)}
+        if URL_RE.match(text):
+            result["url"] = text
+        else:
+            result["path"] = text.split("#", 1)[0]
+        return {**result, **record}
+
+    match = NAME_RE.match(text)
+    if not match:
+        return None
+    name, extras, rest = match.groups()
+    result = {"name": name}
+    rest = rest.strip()
+    if rest.startswith("@"):
+        result["url"] = rest[1:].strip()
+    elif rest:
+        result["version"] = re.sub(r"\s+", "", rest).strip("()")
+    if extras:
+        result["extras"] = sorted(extra.strip() for extra in extras.split(",") if extra.strip())
+    return {**result, **record}
+
+
+def read_requirements(file_path, read, relative=None, _seen=None):
+    """
+    Read a requirements file and the files it includes or constrains with
+
+    Args:
+        file_path (str): Requirements file path
+        read (callable): Path -> file content
+        relative (callable|None): Path -> path reported in `file`, the path itself by default
+        _seen (set|None): Files already read, to break include cycles
+
+    Returns:
+        tuple: (requirements, constraints): requirements are parse_requirement records with 'file'
+            and 'line'; constraints map names to {'version', 'file'}
+    """
+    relative = relative or (lambda path: path)
+    seen = _seen if _seen is not None else set()
+    normalized = os.path.normpath(file_path)
+    requirements, constraints = [], {}
+    if normalized in seen:
+        return requirements, constraints
+    seen.add(normalized)
+    content = read(file_path)
+    directory = os.path.dirname(file_path)
+    for number, text in _logical_lines(content):
+        include = _option(text, INCLUDE_OPTIONS)
+        constraint = _option(text, CONSTRAINT_OPTIONS)
+        for target, is_constraint in ((include, False), (constraint, True)):
+            if not target or URL_RE.match(target):
+                continue
+            try:
+                nested, nested_constraints = read_requirements(os.path.join(directory, target), read, relative, seen)
+            except Exception:
+                continue
+            if is_constraint:
+                nested_constraints.update(
+                    {
+                        record["name"]: {"version": record.get("version"), "file": record["file"]}
+                        for record in nested
+                        if record.get("version")
+                    }
+                )
+                nested = []
+            requirements.extend(nested)
+            for name, pin in nested_constraints.items():
+                constraints.setdefault(name, pin)
+        if include is not None or constraint is not None:
+            continue
+        record = parse_requirement(text)
+        if record:
+            requirements.append({**record, "file": relative(file_path), "line": number})
+    return requirements, constraints
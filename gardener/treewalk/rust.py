@@ -11,12 +11,345 @@ except Exception:  # pragma: no cover - optional dependency
     _toml = None
 
 from gardener.common.secure_file_ops import FileOperationError
+from gardener.common.tsl import get_parser
 from gardener.common.utils import Logger
+from gardener.treewalk.annotations import ImportGuards, normalize_platform
 from gardener.treewalk.base import LanguageHandler, TreeVisitor
 
 # Module-level logger instance
 logger = Logger(verbose=False)  # Will be configured by the caller
 
+# Cargo dependency table -> dep_kind; a crate declared under several kinds keeps the first in DEP_KIND_ORDER
+DEPENDENCY_KINDS = {"dependencies": "normal", "dev-dependencies": "dev", "build-dependencies": "build"}
+DEP_KIND_ORDER = ("normal", "build", "dev")
+# `{ git = "..." }` keys that pin the checkout; Cargo accepts at most one of them
+GIT_PIN_KEYS = ("rev", "tag", "branch")
+
+
+def stronger_dep_kind(kind, other):
+    """
+    Return the more inclusive of two dependency kinds (normal > build > dev); None is ignored
+    """
+    if kind is None or other is None:
+        return kind or other
+    return min(kind, other, key=DEP_KIND_ORDER.index)
+
+
+# Annotation `evidence` of crates seen only in fully-qualified paths (`serde_json::from_str(...)`)
+QUALIFIED_PATH_EVIDENCE = "qualified-path"
+IDENTIFIER_RE = re.compile(r"\b[A-Za-z_][A-Za-z0-9_]*\b")
+
+# Annotation `scope` of crates imported only by doctests (fenced code blocks in doc comments)
+DOCTEST_SCOPE = "doctest"
+DOCTEST_FENCE_RE = re.compile(r"^\s*(`{3,}|~{3,})\s*([^`]*)$")
+# Code block attributes rustdoc still compiles as a Rust doctest; `ignore`, `text` or another language is skipped
+DOCTEST_ATTRIBUTES = {
+    "rust",
+    "no_run",
+    "should_panic",
+    "compile_fail",
+    "test_harness",
+    "allow_fail",
+    "standalone_crate",
+}
+
+# `#[cfg(<predicate>)]` / `#![cfg(<predicate>)]`, and `#[test]` which implies cfg(test)
+CFG_ATTRIBUTE_RE = re.compile(r"^#!?\s*\[\s*cfg\s*\((.*)\)\s*\]$", re.DOTALL)
+TEST_ATTRIBUTE_RE = re.compile(r"^#\s*\[\s*test\s*\]$")
+# `#[path = "..."]`, which loads a `mod foo;` declaration from another file
+PATH_ATTRIBUTE_RE = re.compile(r'^#\s*\[\s*path\s*=\s*"([^"]+)"\s*\]$')
+CFG_FEATURE_RE = re.compile(r'\bfeature\s*=\s*"([^"]+)"')
+# Tokens of a cfg predicate: an identifier, a string literal or one of ( ) , =
+CFG_TOKEN_RE = re.compile(r'\s*(?:([A-Za-z_][A-Za-z0-9_]*)|"((?:[^"\\]|\\.)*)"|([(),=]))')
+
+
+def parse_cfg(predicate):
+    """
+    Parse a cfg predicate into a tree
+
+    Args:
+        predicate (str): Predicate such as 'all(unix, not(target_os = "macos"))'
+
+    Returns:
+        tuple|None: ('all'|'any', [children]), ('not', child) or ('option', key, value or None);
+            None when the predicate is malformed
+    """
+    tokens = []
+    position = 0
+    predicate = predicate.strip()
+    while position < len(predicate):
+        match = CFG_TOKEN_RE.match(predicate, position)
+        if not match or match.end() == position:
+            return None
+        tokens.append(match.groups())
+        position = match.end()
+
+    def parse(index):
+        if index >= len(tokens) or tokens[index][0] is None:
+            raise ValueError("expected a cfg option or operator")
+        name = tokens[index][0]
+        index += 1
+        if name in ("all", "any", "not") and index < len(tokens) and tokens[index][2] == "(":
+            children = []
+            index += 1
+            while tokens[index][2] != ")":
+                child, index = parse(index)
+                children.append(child)
+                if tokens[index][2] == ",":
+                    index += 1
+                elif tokens[index][2] != ")":
+                    raise ValueError("expected ',' or ')'")
+            if name == "not":
+                if len(children) != 1:
+                    raise ValueError("not() takes exactly one predicate")
+                return ("not", children[0]), index + 1
+            return (name, children), index + 1
+        if index < len(tokens) and tokens[index][2] == "=":
+            if index + 1 >= len(tokens) or tokens[index + 1][1] is None:
+                raise ValueError("expected a string value")
+            return ("option", name, tokens[index + 1][1]), index + 2
+        return ("option", name, None), index
+
+    try:
+        tree, end = parse(0)
+    except (ValueError, IndexError):
+        return None
+    return tree if end == len(tokens) else None
+
+
+def _cfg_platform_names(node, negated, names):
+    """
+    Collect platform names below a cfg node, each prefixed with '!' under an odd number of not()s
+    """
+    if node[0] == "not":
+        _cfg_platform_names(node[1], not negated, names)
+    elif node[0] in ("all", "any"):
+        for child in node[1]:
+            _cfg_platform_names(child, negated, names)
+    else:
+        _, key, value = node
+        if key in ("target_os", "target_family", "target_arch") and value is not None:
+            name = normalize_platform(value)
+        else:
+            name = key if key in ("windows", "unix") and value is None else None
+        if name:
+            names.add(f"!{name}" if negated else name)
+
+
+def cfg_platforms(predicate):
+    """
+    Extract canonical platform names from a cfg predicate
+
+    Args:
+        predicate (str): Predicate such as 'windows', 'target_os = "linux"' or 'all(unix, not(target_os = "macos"))'
+
+    Returns:
+        list: Sorted platform names, prefixed with '!' for platforms a not() excludes
+    """
+    tree = parse_cfg(predicate)
+    names = set()
+    if tree is not None:
+        _cfg_platform_names(tree, False, names)
+    return sorted(names)
+
+
+def _cfg_implies_test(node, negated=False):
+    """
+    Return True when a cfg node (negated when `negated`) can only hold with cfg(test) set
+    """
+    kind = node[0]
+    if kind == "not":
+        return _cfg_implies_test(node[1], not negated)
+    if kind == "option":
+        return not negated and node[1] == "test" and node[2] is None
+    children = node[1]
+    # all() holds only if every child does, so one test-only child suffices; any() needs every
+    # alternative to be test-only. Under not() the two swap (De Morgan)
+    if (kind == "all") != negated:
+        return any(_cfg_implies_test(child, negated) for child in children)
+    return bool(children) and all(_cfg_implies_test(child, negated) for child in children)
+
+
+def cfg_requires_test(predicate):
+    """
+    Return True when a cfg predicate only holds in test builds
+    """
+    tree = parse_cfg(predicate)
+    return tree is not None and _cfg_implies_test(tree)
+
+
+def inherit_workspace_dependency(member_spec, workspace_spec):
+    """
+    Merge a `{ workspace = true, ... }` member entry with its [workspace.dependencies] spec
+
+    The member may only add `features` (unioned with the workspace's), `optional` and
+    `default-features`; version, registry, git and package come from the workspace
+
+    Args:
+        member_spec (dict): Member entry containing `workspace = true`
+        workspace_spec (str|dict|None): Entry from [workspace.dependencies]
+
+    Returns:
+        dict: Effective dependency spec
+    """
+    merged = {"version": workspace_spec} if isinstance(workspace_spec, str) else dict(workspace_spec or {})
+    for key, value in member_spec.items():
+        if key == "workspace":
+            continue
+        if key == "features" and isinstance(value, list):
+            features = list(merged.get("features") or [])
+            merged["features"] = features + [feature for feature in value if feature not in features]
+        else:
+            merged[key] = value
+    return merged
+
+
+def optional_dependency_features(features, optional_deps):
+    """
+    Map optional dependencies to the [features] that enable them, directly or through other features
+
+    A feature enables an optional dependency through `dep:name`, `name/feature` or the implicit
+    feature `name` (only defined when no feature uses `dep:name`); `name?/feature` does not enable it
+
+    Args:
+        features (dict): The manifest's [features] table
+        optional_deps (iterable): Dependency keys declared with `optional = true`
+
+    Returns:
+        dict: Dependency key -> {'features': sorted feature names, 'default': enabled by `default`}
+    """
+    optional_deps = set(optional_deps)
+    table = {}
+    if isinstance(features, dict):
+        for name, values in features.items():
+            if isinstance(values, list):
+                table[str(name)] = [value for value in values if isinstance(value, str)]
+    explicit = {value[4:] for values in table.values() for value in values if value.startswith("dep:")}
+    for dep in optional_deps - explicit:
+        table.setdefault(dep, [f"dep:{dep}"])
+
+    def _enables(value):
+        if value.startswith("dep:"):
+            return {value[4:]} & optional_deps, set()
+        name, _, _ = value.partition("/")
+        if name.endswith("?"):
+            return set(), set()
+        return ({name} & optional_deps) if "/" in value else set(), {name} & set(table)
+
+    closures = {}
+
+    def _closure(feature, seen):
+        if feature not in closures:
+            deps = set()
+            for value in table.get(feature, []):
+                direct, nested = _enables(value)
+                deps |= direct
+                for other in nested - seen:
+                    deps |= _closure(other, seen | {other})
+            closures[feature] = deps
+        return closures[feature]
+
+    result = {dep: {"features": [], "default": False} for dep in optional_deps}
+    for feature in sorted(table):
+        for dep in _closure(feature, {feature}):
+            result[dep]["features"].append(feature)
+            if feature == "default":
+                result[dep]["default"] = True
+    return result
+
+
+def doc_comment_text(text):
+    """
+    Return the content of a `///` or `//!` line doc comment or a `/** */` or `/*! */` block, or None
+
+    Args:
+        text (str): Comment text including its delimiters
+    """
+    text = text.rstrip("\r\n")
+    if text.startswith(("///", "//!")) and not text.startswith("////"):
+        body = text[3:]
+        return body[1:] if body.startswith(" ") else body
+    if text.startswith(("/**", "/*!")) and not text.startswith(("/***", "/**/")) and text.endswith("*/"):
+        return "\n".join(re.sub(r"^\s*(\* ?)?", "", line) for line in text[3:-2].splitlines())
+    return None
+
+
+def doc_comments(node):
+    """
+    Yield the content of every doc comment under a node, with consecutive line comments joined
+
+    Args:
+        node (object): Tree-sitter node, usually the 'source_file'
+    """
+    lines = []
+    for child in node.children:
+        content = None
+        if child.type in ("line_comment", "block_comment"):
+            content = doc_comment_text(child.text.decode("utf-8", errors="replace"))
+        if content is not None and child.type == "line_comment":
+            lines.append(content)
+            continue
+        if lines:
+            yield "\n".join(lines)
+            lines = []
+        if content is not None:
+            yield content
+        elif child.children:
+            yield from doc_comments(child)
+    if lines:
+        yield "\n".join(lines)
+
+
+def _is_doctest_fence(info):
+    """
+    Return True when a code fence info string (e.g. 'rust,no_run', 'edition2018') marks a compiled doctest
+    """
+    attributes = [attribute for attribute in re.split(r"[\s,]+", info.strip()) if attribute]
+    return all(attribute in DOCTEST_ATTRIBUTES or re.fullmatch(r"edition\d{4}", attribute) for attribute in attributes)
+
+
+def _unhide_doctest_line(line):
+    """
+    Return a doctest line as compiled: rustdoc hides lines starting with '# ' but still compiles them
+    """
+    stripped = line.lstrip()
+    if stripped == "#":
+        return ""
+    if stripped.startswith("# "):
+        return stripped[2:]
+    if stripped.startswith("##"):
+        return stripped[1:]
+    return line
+
+
+def doctest_blocks(doc_text):
+    """
+    Yield the code of the fenced blocks in doc comment content that rustdoc compiles as doctests
+
+    Unlabelled fences and `rust` fences with attributes such as `no_run` or `should_panic` are
+    doctests; hidden `# ` lines are kept since they are compiled too
+
+    Args:
+        doc_text (str): Doc comment content, as returned by doc_comment_text
+    """
+    fence, compiled, code = None, False, []
+    for line in doc_text.splitlines():
+        stripped = line.strip()
+        if fence is None:
+            match = DOCTEST_FENCE_RE.match(line)
+            if match:
+                fence, compiled, code = match.group(1), _is_doctest_fence(match.group(2)), []
+            continue
+        if stripped.startswith(fence) and set(stripped) == {fence[0]}:
+            if compiled:
+                yield "\n".join(code)
+            fence = None
+        elif compiled:
+            code.append(_unhide_doctest_line(line))
+    if fence is not None and compiled:
+        # rustdoc closes a fence left open at the end of the comment
+        yield "\n".join(code)
+
 
 class RustImportVisitor(TreeVisitor):
     """
@@ -45,13 +378,22 @@ class RustImportVisitor(TreeVisitor):
         self.local_imports = []  # Resolved local import paths
         self._resolve_local = local_resolver_func  # Store resolver
         self.current_file_inline_modules = set()  # Stores names of inline modules in the current file
+        self.guards = ImportGuards()  # cfg conditions per external crate
+        self._cfg_scope = []  # cfg predicates of the enclosing file, modules and functions
+        self._current_guard = None
+        self.crate_aliases = {}  # `extern crate foo as bar;` alias -> crate name
+        self.path_imports = []  # Crates used through fully-qualified paths, without a `use`
+        self._declared_modules = set()  # `mod foo;` names, which shadow crates in paths
+        self._use_bound_names = set()  # Names brought into scope by use declarations
 
     def _scan_for_inline_modules(self, node):
         """
         Perform a preliminary scan for top-level inline module names in the current file
 
         This method populates `self.current_file_inline_modules` with names of modules
-        defined like `mod foo { ... }` at the top level of the file being processed
+        defined like `mod foo { ... }` at the top level of the file being processed, along with
+        the `mod foo;` names, the names bound by `use` and the `extern crate` aliases that
+        fully-qualified paths are checked against
         It should be called once on the 'source_file' node before other visitations
 
         Args:
@@ -59,9 +401,21 @@ class RustImportVisitor(TreeVisitor):
         """
         if node.type == "source_file":
             for child in node.children:  # Iterate over top-level items in the file
+                if child.type == "use_declaration":
+                    # Names a `use` brings into scope are not crates, wherever the path appears
+                    self._use_bound_names.update(IDENTIFIER_RE.findall(child.text.decode("utf-8")))
+                if child.type == "extern_crate_declaration":
+                    # Aliases apply to `use` declarations anywhere in the file, even above the declaration
+                    crate_name, alias = self._extern_crate_names(child)
+                    if crate_name and alias and alias != crate_name:
+                        self.crate_aliases[alias] = crate_name
                 if child.type == "mod_item":
                     # An inline module has a 'body' field, which is a 'block' or similar node
                     body_node = child.child_by_field_name("body")
+                    if not body_node:
+                        name_node, _ = self._mod_name_and_body(child)
+                        if name_node:
+                            self._declared_modules.add(name_node.text.decode("utf-8"))
                     if body_node:  # This confirms it's an inline module
                         name_node = None
                         for sub_child in child.children:  # Iterate children of mod_item
@@ -84,6 +438,12 @@ class RustImportVisitor(TreeVisitor):
         # Perform the pre-scan for inline modules at the root of the file
         if node.type == "source_file":  # Assuming the top node passed is always source_file
             self._scan_for_inline_modules(node)
+            # `#![cfg(...)]` inner attributes guard the whole file
+            for child in node.children:
+                if child.type == "inner_attribute_item":
+                    match = CFG_ATTRIBUTE_RE.match(child.text.decode("utf-8").strip())
+                    if match:
+                        self._cfg_scope.append(match.group(1).strip())
 
         # Then proceed with the normal visitation logic for all nodes
         super().visit(node)
@@ -145,7 +505,93 @@ class RustImportVisitor(TreeVisitor):
 
         return collected
 
+    def _cfg_attributes(self, node):
+        """
+        Return the cfg predicates of the outer attributes directly preceding an item
+
+        Args:
+            node (object): Item node (use declaration, module, function, impl)
+
+        Returns:
+            list: Predicates in source order; `#[test]` contributes 'test'
+        """
+        predicates = []
+        sibling = node.prev_named_sibling
+        while sibling is not None and sibling.type in ("attribute_item", "line_comment", "block_comment"):
+            if sibling.type == "attribute_item":
+                text = sibling.text.decode("utf-8").strip()
+                match = CFG_ATTRIBUTE_RE.match(text)
+                if match:
+                    predicates.append(match.group(1).strip())
+                elif TEST_ATTRIBUTE_RE.match(text):
+                    predicates.append("test")
+            sibling = sibling.prev_named_sibling
+        return predicates[::-1]
+
+    def _guard(self, own_predicates=()):
+        """
+        Combine the enclosing cfg scope with an item's own predicates into an ImportGuards guard
+
+        Returns:
+            tuple|None: (condition, platforms), or None when the item is unconditional
+        """
+        predicates = list(dict.fromkeys(self._cfg_scope + list(own_predicates)))
+        if not predicates:
+            return None
+        condition = predicates[0] if len(predicates) == 1 else f"all({', '.join(predicates)})"
+        return condition, cfg_platforms(condition)
+
+    def _visit_in_cfg_scope(self, node, visit_body):
+        """
+        Run visit_body with the item's cfg predicates added to the scope
+        """
+        own = self._cfg_attributes(node)
+        self._cfg_scope.extend(own)
+        try:
+            visit_body()
+        finally:
+            del self._cfg_scope[len(self._cfg_scope) - len(own) :]
+
+    def visit_function_item(self, node):
+        self._visit_in_cfg_scope(node, lambda: self.generic_visit(node))
+
+    def visit_impl_item(self, node):
+        self._visit_in_cfg_scope(node, lambda: self.generic_visit(node))
+
+    def _extern_crate_names(self, node):
+        """
+        Return (crate name, alias or None) of an `extern crate foo [as bar];` declaration
+
+        The crate name is None for `extern crate self` and for the standard library crates
+        """
+        name_node = node.child_by_field_name("name")
+        alias_node = node.child_by_field_name("alias")
+        crate_name = name_node.text.decode("utf-8") if name_node else None
+        alias = alias_node.text.decode("utf-8") if alias_node else None
+        if crate_name in self.STD_CRATES or crate_name in ("self", "proc_macro", "test"):
+            return None, alias
+        return crate_name, alias
+
+    def visit_extern_crate_declaration(self, node):
+        """
+        Record `extern crate foo;` (2015 edition) as evidence of the external crate foo
+        """
+        crate_name, _ = self._extern_crate_names(node)
+        if not crate_name:
+            return
+        if crate_name not in self.imports:
+            self.imports.append(crate_name)
+        self.guards.record(crate_name, self._guard(self._cfg_attributes(node)))
+
     def visit_use_declaration(self, node):
+        self._use_bound_names.update(IDENTIFIER_RE.findall(node.text.decode("utf-8")))
+        self._current_guard = self._guard(self._cfg_attributes(node))
+        try:
+            self._visit_use_declaration(node)
+        finally:
+            self._current_guard = None
+
+    def _visit_use_declaration(self, node):
         path_node = self._find_use_path_node(node)
         if not path_node:
             return
@@ -282,8 +728,13 @@ class RustImportVisitor(TreeVisitor):
                 crate_name = full_path_parts[0]
                 if crate_name == "*":
                     return
+                if crate_name in self.crate_aliases:
+                    # `use bar::X` after `extern crate foo as bar;` is foo::X
+                    crate_name = self.crate_aliases[crate_name]
+                    full_path_str = "::".join([crate_name] + full_path_parts[1:])
                 if crate_name not in self.imports:
                     self.imports.append(crate_name)
+                self.guards.record(crate_name, self._current_guard)
                 if len(full_path_parts) > 1 and full_path_parts[-1] != "*":
                     self.file_components_dict[self.rel_path].append((crate_name, full_path_str))
             else:
@@ -309,6 +760,7 @@ class RustImportVisitor(TreeVisitor):
         if self._is_crate_based_attribute(package_name):
             if package_name not in self.imports:
                 self.imports.append(package_name)
+            self.guards.record(package_name, self._guard())
             self._append_attribute_component_if_needed(package_name, path_text_to_process)
 
     def _attribute_meta_item(self, node):
@@ -375,11 +827,51 @@ class RustImportVisitor(TreeVisitor):
             return
 
         if body_block_node:
-            self._visit_inline_mod_body(body_block_node)
+            self._visit_in_cfg_scope(node, lambda: self._visit_inline_mod_body(body_block_node))
             return
 
         module_name = name_node.text.decode("utf-8")
-        self._resolve_and_record_file_module(module_name)
+        self._declared_modules.add(module_name)
+        self._resolve_and_record_file_module(module_name, self._path_attribute(node))
+
+    def visit_scoped_identifier(self, node):
+        self._record_qualified_path(node)
+
+    def visit_scoped_type_identifier(self, node):
+        self._record_qualified_path(node)
+
+    def _record_qualified_path(self, node):
+        """
+        Record `some_crate::item` paths in expressions, types and macro names as usage evidence
+
+        Paths rooted at `crate`/`self`/`super`, a capitalized type, a standard library crate, a
+        module declared in the file or a name bound by a `use` are skipped. The crates found are
+        only candidates: the analyzer keeps those matching a declared Cargo dependency
+        """
+        parts = []
+        current = node
+        while current is not None and current.type in ("scoped_identifier", "scoped_type_identifier"):
+            name_node = current.child_by_field_name("name")
+            parts.append(name_node.text.decode("utf-8") if name_node else "")
+            current = current.child_by_field_name("path")
+        if current is None or current.type != "identifier":
+            return
+        first = current.text.decode("utf-8")
+        if (
+            first[:1].isupper()
+            or first in self.STD_CRATES
+            or first in self.current_file_inline_modules
+            or first in self._declared_modules
+            or first in self._use_bound_names
+        ):
+            return
+        crate_name = self.crate_aliases.get(first, first)
+        if crate_name not in self.path_imports:
+            self.path_imports.append(crate_name)
+        self.guards.record(crate_name, self._guard())
+        component = (crate_name, "::".join([crate_name] + parts[::-1]))
+        if component not in self.file_components_dict[self.rel_path]:
+            self.file_components_dict[self.rel_path].append(component)
 
     def _mod_name_and_body(self, node):
         """
@@ -404,13 +896,33 @@ class RustImportVisitor(TreeVisitor):
                 self.visit_attribute_item(item_in_body)
             elif item_in_body.type == "mod_item":
                 self.visit_mod_item(item_in_body)
+            elif item_in_body.type in ("function_item", "impl_item"):
+                self.visit(item_in_body)
+
+    def _path_attribute(self, node):
+        """
+        Return the `#[path = "..."]` value among the outer attributes directly preceding a `mod` item, or None
+        """
+        sibling = node.prev_named_sibling
+        while sibling is not None and sibling.type in ("attribute_item", "line_comment", "block_comment"):
+            if sibling.type == "attribute_item":
+                match = PATH_ATTRIBUTE_RE.match(sibling.text.decode("utf-8").strip())
+                if match:
+                    return match.group(1)
+            sibling = sibling.prev_named_sibling
+        return None
 
-    def _resolve_and_record_file_module(self, module_name):
+    def _resolve_and_record_file_module(self, module_name, module_path=None):
         """
         Resolve with self._resolve_local and append to self.local_imports if needed
+
+        A `#[path]` module is resolved from its attribute value rather than its name
         """
         module_path_parts = [module_name]
-        resolved_local_path = self._resolve_local(self.rel_path, module_path_parts)
+        if module_path:
+            resolved_local_path = self._resolve_local(self.rel_path, module_path_parts, module_path=module_path)
+        else:
+            resolved_local_path = self._resolve_local(self.rel_path, module_path_parts)
         if resolved_local_path:
             if resolved_local_path not in self.local_imports:
                 self.local_imports.append(resolved_local_path)
@@ -430,6 +942,8 @@ class RustLanguageHandler(LanguageHandler):
             logger (Logger): Optional logger instance
         """
         self.logger = logger
+        # Workspace root manifest path -> its [workspace.dependencies] table
+        self._workspace_dependencies_cache = {}
 
     def get_manifest_files(self):
         """
@@ -438,7 +952,7 @@ class RustLanguageHandler(LanguageHandler):
         Returns:
             List of manifest filenames
         """
-        return ["Cargo.toml"]
+        return ["Cargo.toml", "Cargo.lock"]
 
     def get_file_extensions(self):
         """
@@ -470,9 +984,19 @@ class RustLanguageHandler(LanguageHandler):
                 if _toml is not None:
                     try:
                         data = _toml.loads(content)
-
-                        def _add_dep(pkg_key, pkg_val):
-                            nonlocal added
+                        workspace_deps = None
+                        unconditional = set()
+                        # Dependency key -> crate for `optional = true` entries; required crates are always built
+                        optional = {}
+                        required = set()
+
+                        def _add_dep(pkg_key, pkg_val, dep_kind, target=None):
+                            nonlocal added, workspace_deps
+                            if isinstance(pkg_val, dict) and pkg_val.get("workspace") is True:
+                                # `foo = { workspace = true }`: the spec lives in [workspace.dependencies]
+                                if workspace_deps is None:
+                                    workspace_deps = self._workspace_dependencies(file_path, data, secure_file_ops)
+                                pkg_val = inherit_workspace_dependency(pkg_val, workspace_deps.get(pkg_key))
                             # Use the canonical package name from 'package' field when present
                             if isinstance(pkg_val, dict) and "package" in pkg_val:
                                 dist_name = str(pkg_val["package"])
@@ -493,36 +1017,77 @@ class RustLanguageHandler(LanguageHandler):
                                 if alias_import not in alias_names:
                                     alias_names.append(alias_import)
                             entry["import_names"] = alias_names
+                            # Rename map: the names this crate is declared (and imported) under
+                            if alias and alias != dist_name and alias not in entry.get("aliases", []):
+                                entry.setdefault("aliases", []).append(alias)
+                            version = pkg_val if isinstance(pkg_val, str) else None
+                            if isinstance(pkg_val, dict):
+                                version = pkg_val.get("version")
+                                if isinstance(pkg_val.get("registry"), str):
+                                    entry["registry"] = pkg_val["registry"]
+                                if isinstance(pkg_val.get("path"), str):
+                                    # Local crate outside the workspace; never resolved against crates.io
+                                    entry.setdefault("local_path", pkg_val["path"])
+                                elif isinstance(pkg_val.get("git"), str):
+                                    entry.setdefault("git_url", pkg_val["git"])
+                                    pin = {k: str(pkg_val[k]) for k in GIT_PIN_KEYS if isinstance(pkg_val.get(k), str)}
+                                    if pin and "git_pin" not in entry:
+                                        entry["git_pin"] = pin
+                            if isinstance(version, str) and not entry.get("version"):
+                                entry["version"] = version
+                            entry["dep_kind"] = stronger_dep_kind(entry.get("dep_kind"), dep_kind)
+                            if isinstance(pkg_val, dict) and pkg_val.get("optional") is True:
+                                optional.setdefault(str(pkg_key), dist_name)
+                            else:
+                                required.add(dist_name)
+                            # `targets` lists the cfg(...) of crates declared only under [target.*] tables
+                            if target is None:
+                                unconditional.add(dist_name)
+                            elif target not in entry.setdefault("targets", []):
+                                entry["targets"].append(target)
                             packages_dict[dist_name] = entry
                             added = True
 
                         # Top-level dependency tables
-                        for key in ["dependencies", "dev-dependencies", "build-dependencies"]:
+                        for key, dep_kind in DEPENDENCY_KINDS.items():
                             tbl = data.get(key, {}) or {}
                             if isinstance(tbl, dict):
                                 for dep_name, dep_val in tbl.items():
-                                    _add_dep(dep_name, dep_val)
+                                    _add_dep(dep_name, dep_val, dep_kind)
                         # Target-specific dependency tables
                         tgt = data.get("target", {}) or {}
                         if isinstance(tgt, dict):
-                            for _cfg, cfg_tbl in tgt.items():
+                            for target, cfg_tbl in tgt.items():
                                 if not isinstance(cfg_tbl, dict):
                                     continue
-                                for key in ["dependencies", "dev-dependencies", "build-dependencies"]:
+                                for key, dep_kind in DEPENDENCY_KINDS.items():
                                     tbl = cfg_tbl.get(key, {}) or {}
                                     if isinstance(tbl, dict):
                                         for dep_name, dep_val in tbl.items():
-                                            _add_dep(dep_name, dep_val)
+                                            _add_dep(dep_name, dep_val, dep_kind, target)
+                        for dist_name in unconditional:
+                            packages_dict[dist_name].pop("targets", None)
+                        enabled_by = optional_dependency_features(data.get("features"), optional)
+                        for dep_key, dist_name in optional.items():
+                            if dist_name in required:
+                                continue
+                            entry = packages_dict[dist_name]
+                            entry["optional"] = True
+                            features = entry.setdefault("optional_features", [])
+                            features.extend(f for f in enabled_by[dep_key]["features"] if f not in features)
+                            if enabled_by[dep_key]["default"] or not entry.get("default_enabled"):
+                                entry["default_enabled"] = enabled_by[dep_key]["default"]
                     except Exception:
                         # Fall back to regex if TOML parsing fails
                         pass
 
                 if not added:
                     # Regex fallback: also match target.*.dependencies headers
-                    deps_pattern = r"\[(?:target\.[^\]]+\.)?(?:dev-|build-)?dependencies\](.*?)(\n\[|\Z)"
+                    deps_pattern = r"\[(?:target\.[^\]]+\.)?((?:dev-|build-)?dependencies)\](.*?)(\n\[|\Z)"
                     matches = re.finditer(deps_pattern, content, re.DOTALL | re.IGNORECASE)
                     for m in matches:
-                        deps_section = m.group(1)
+                        dep_kind = DEPENDENCY_KINDS.get(m.group(1).lower())
+                        deps_section = m.group(2)
                         lines = deps_section.strip().split("\n")
                         for line in lines:
                             line = line.strip()
@@ -531,16 +1096,157 @@ class RustLanguageHandler(LanguageHandler):
                             mm = re.match(r"^([a-zA-Z0-9_-]+)\s*=", line)
                             if mm:
                                 dep_name = mm.group(1)
-                                packages_dict[dep_name] = {"ecosystem": "cargo"}
+                                renamed = re.search(r"\bpackage\s*=\s*[\"']([^\"']+)[\"']", line)
+                                if renamed:
+                                    # `alias = { package = "real-crate", ... }`: code imports the alias
+                                    packages_dict[renamed.group(1)] = {
+                                        "ecosystem": "cargo",
+                                        "import_names": [
+                                            renamed.group(1).replace("-", "_"),
+                                            dep_name.replace("-", "_"),
+                                        ],
+                                        "aliases": [dep_name],
+                                        "dep_kind": dep_kind,
+                                    }
+                                else:
+                                    previous = (packages_dict.get(dep_name) or {}).get("dep_kind")
+                                    packages_dict[dep_name] = {
+                                        "ecosystem": "cargo",
+                                        "dep_kind": stronger_dep_kind(previous, dep_kind),
+                                    }
             except FileOperationError as e:
                 if self.logger:
                     self.logger.error(f"Failed to read Cargo.toml at {file_path}: {e}")
             except Exception as e:
                 if self.logger:
                     self.logger.error(f"Unexpected error processing Cargo.toml at {file_path}", exception=e)
+        elif basename == "Cargo.lock":
+            try:
+                lock = _toml.loads(self.read_file_content(file_path, secure_file_ops)) if _toml is not None else None
+            except FileOperationError as e:
+                if self.logger:
+                    self.logger.error(f"Failed to read Cargo.lock at {file_path}: {e}")
+                return packages_dict
+            except Exception as e:
+                if self.logger:
+                    self.logger.warning(f"Could not parse Cargo.lock at {file_path}: {e}")
+                return packages_dict
+            if isinstance(lock, dict):
+                self._process_cargo_lock(lock, packages_dict)
 
         return packages_dict
 
+    def _process_cargo_lock(self, lock, packages_dict):
+        """
+        Record resolved versions and checksums from a Cargo.lock, including transitive crates
+
+        Packages without a `source` are the workspace's own crates; the crates they depend on are
+        the direct dependencies (`direct: true`), every other locked crate is transitive
+        (`direct: false`, `transitive: true`)
+
+        Args:
+            lock (dict): Parsed Cargo.lock
+            packages_dict (dict): Dictionary to update with package information
+        """
+        packages = [p for p in lock.get("package") or [] if isinstance(p, dict) and p.get("name")]
+        # Lockfile v1 keeps checksums in [metadata] as "checksum <name> <version> (<source>)" keys
+        legacy_checksums = {}
+        for key, value in (lock.get("metadata") or {}).items():
+            fields = key.split(" ")
+            if len(fields) >= 3 and fields[0] == "checksum":
+                legacy_checksums[(fields[1], fields[2])] = value
+
+        # Direct dependency name -> version when the lock disambiguates ("serde 1.0.190")
+        direct = {}
+        for package in packages:
+            if package.get("source"):
+                continue
+            for dependency in package.get("dependencies") or []:
+                fields = str(dependency).split(" ")
+                direct.setdefault(fields[0], fields[1] if len(fields) > 1 else None)
+
+        locked = {}
+        for package in packages:
+            if package.get("source"):
+                locked.setdefault(str(package["name"]), []).append(package)
+
+        for name, candidates in locked.items():
+            # Several versions of one crate can be locked; prefer the one the workspace depends on
+            chosen = next((p for p in candidates if direct.get(name) == str(p.get("version"))), candidates[-1])
+            version = str(chosen.get("version", ""))
+            is_direct = name in direct
+            entry = packages_dict.setdefault(name, {"ecosystem": "cargo"})
+            entry["locked_version"] = version
+            if len(candidates) > 1:
+                entry["locked_versions"] = [str(p.get("version", "")) for p in candidates]
+            checksum = chosen.get("checksum") or legacy_checksums.get((name, version))
+            if checksum:
+                entry["checksum"] = checksum
+            if str(chosen["source"]).startswith("git+"):
+                entry["source"] = str(chosen["source"])
+                # 'git+https://github.com/org/repo?rev=v1#<commit>': the fragment is the locked commit
+                if "#" in entry["source"]:
+                    entry["git_commit"] = entry["source"].rsplit("#", 1)[1]
+            entry["direct"] = is_direct
+            if not is_direct:
+                entry["transitive"] = True
+
+    def _workspace_dependencies(self, file_path, data, secure_file_ops=None):
+        """
+        Return the [workspace.dependencies] table of the workspace a Cargo.toml belongs to
+
+        The workspace root is the manifest named by `package.workspace` when set, otherwise the
+        nearest Cargo.toml with a [workspace] table in the manifest's directory or its parents
+        (within the repository when secure_file_ops is given)
+
+        Args:
+            file_path (str): Path of the member manifest
+            data (dict): Parsed member manifest
+            secure_file_ops (object): Optional SecureFileOps instance for safe file operations
+
+        Returns:
+            dict: Dependency name -> spec; empty when no workspace root is found
+        """
+        manifest_path = os.path.realpath(file_path)
+        manifest_dir = os.path.dirname(manifest_path)
+        package = data.get("package") if isinstance(data.get("package"), dict) else {}
+        if isinstance(package.get("workspace"), str):
+            candidates = [os.path.normpath(os.path.join(manifest_dir, package["workspace"], "Cargo.toml"))]
+        else:
+            candidates = []
+            directory = manifest_dir
+            while True:
+                candidates.append(os.path.join(directory, "Cargo.toml"))
+                parent = os.path.dirname(directory)
+                if parent == directory:
+                    break
+                directory = parent
+        repo_root = str(secure_file_ops.repo_path) if secure_file_ops else None
+
+        for candidate in candidates:
+            if repo_root and os.path.commonpath([repo_root, candidate]) != repo_root:
+                break
+            if candidate in self._workspace_dependencies_cache:
+                return self._workspace_dependencies_cache[candidate]
+            if candidate == manifest_path:
+                root = data
+            elif os.path.isfile(candidate):
+                try:
+                    root = _toml.loads(self.read_file_content(candidate, secure_file_ops))
+                except Exception as e:
+                    if self.logger:
+                        self.logger.warning(f"Could not read workspace manifest {candidate}: {e}")
+                    continue
+            else:
+                continue
+            workspace = root.get("workspace")
+            if isinstance(workspace, dict):
+                dependencies = workspace.get("dependencies")
+                dependencies = dependencies if isinstance(dependencies, dict) else {}
+                self._workspace_dependencies_cache[candidate] = dependencies
+                return dependencies
+        return {}
+
     def normalize_package_name(self, package_path):
         """
         Normalize Rust crate names from import paths
@@ -564,7 +1270,9 @@ class RustLanguageHandler(LanguageHandler):
             else:
                 return None
 
-    def extract_imports(self, tree_node, rel_path, file_components_dict, local_resolver_func, logger=None):
+    def extract_imports(
+        self, tree_node, rel_path, file_components_dict, local_resolver_func, logger=None, import_annotations=None
+    ):
         """
         Extract external crate imports and resolved local imports from a Rust source file
 
@@ -574,11 +1282,77 @@ class RustLanguageHandler(LanguageHandler):
             file_components_dict (dict): Dictionary to track imported external components
             local_resolver_func (callable): Function to resolve local Rust module paths
             logger (Logger): Optional logger instance for debug output
+            import_annotations (dict): Optional mapping of import name to annotations, updated in place
 
         Returns:
             Tuple of (list of external crate names, list of resolved local file paths)
         """
         visitor = RustImportVisitor(rel_path, file_components_dict, local_resolver_func)
         visitor.visit(tree_node)
-        # Deduplicate external imports before returning
-        return list(set(visitor.imports)), visitor.local_imports
+        visitor.guards.apply(import_annotations, condition_key="cfg")
+        if import_annotations is not None:
+            for alias, crate_name in visitor.crate_aliases.items():
+                aliases = import_annotations.setdefault(crate_name, {}).setdefault("extern_crate_aliases", [])
+                if alias not in aliases:
+                    aliases.append(alias)
+        for annotation in (import_annotations or {}).values():
+            if "cfg" not in annotation:
+                continue
+            alternatives = annotation["cfg"].split(" || ")
+            if all(cfg_requires_test(condition) for condition in alternatives):
+                annotation["test_only"] = True
+            features = sorted({feature for condition in alternatives for feature in CFG_FEATURE_RE.findall(condition)})
+            if features:
+                annotation["features"] = features
+        external = set(visitor.imports)
+        for crate_name in visitor.path_imports:
+            if crate_name not in external:
+                external.add(crate_name)
+                if import_annotations is not None:
+                    import_annotations.setdefault(crate_name, {})["evidence"] = QUALIFIED_PATH_EVIDENCE
+        doctest_imports, doctest_path_imports = self._doctest_imports(tree_node, rel_path, local_resolver_func, logger)
+        for crate_name in doctest_imports + doctest_path_imports:
+            if crate_name in external:
+                continue
+            external.add(crate_name)
+            if import_annotations is not None:
+                annotation = import_annotations.setdefault(crate_name, {})
+                annotation["scope"] = DOCTEST_SCOPE
+                if crate_name not in doctest_imports:
+                    annotation["evidence"] = QUALIFIED_PATH_EVIDENCE
+        return list(external), visitor.local_imports
+
+    def _doctest_imports(self, tree_node, rel_path, local_resolver_func, logger=None):
+        """
+        Collect the crates imported by the doctests in a file's doc comments
+
+        Each doctest is parsed on its own, wrapped in `fn main` unless it defines one as rustdoc
+        does; local imports and components found in doctests are not recorded
+
+        Args:
+            tree_node (object): Tree-sitter node for the source file
+            rel_path (str): Relative path of the source file
+            local_resolver_func (callable): Function to resolve local Rust module paths
+            logger (Logger): Optional logger instance for debug output
+
+        Returns:
+            tuple: (crates named by use or extern crate, crates seen only in fully-qualified paths)
+        """
+        imports, path_imports = [], []
+        for doc_text in doc_comments(tree_node):
+            for code in doctest_blocks(doc_text):
+                if not code.strip():
+                    continue
+                if not re.search(r"\bfn\s+main\s*\(", code):
+                    code = f"fn main() {{\n{code}\n}}"
+                try:
+                    root = get_parser("rust").parse(bytes(code, "utf-8")).root_node
+                except Exception as e:
+                    if logger:
+                        logger.debug(f"Could not parse doctest in {rel_path}: {e}")
+                    continue
+                visitor = RustImportVisitor(rel_path, {rel_path: []}, local_resolver_func)
+                visitor.visit(root)
+                imports += [name for name in visitor.imports if name not in imports]
+                path_imports += [name for name in visitor.path_imports if name not in path_imports]
+        return imports, [name for name in path_imports if name not in imports]
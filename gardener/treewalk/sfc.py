@@ -0,0 +1,67 @@
+"""
+Vue, Svelte and Astro single-file components
+
+Components keep their JavaScript/TypeScript in `<script>` blocks (Vue's `<script setup>`, Svelte's
+`<script context="module">`) and, for Astro, in the `---` frontmatter fence whose imports bring
+in the components the template renders. These parts are kept at their original offsets and the
+markup around them is blanked, so the JS/TS import visitor sees every import with its real line.
+Blocks loading their code from a file (`<script src="./logic.ts">`) count as an import of it;
+Astro `is:inline` scripts and non-JavaScript script types are not bundled and are left out
+"""
+
+import re
+
+SFC_EXTENSIONS = (".vue", ".svelte", ".astro")
+
+SCRIPT_BLOCK_RE = re.compile(r"<script\b([^>]*)>(.*?)</script\s*>", re.S | re.I)
+ASTRO_FRONTMATTER_RE = re.compile(r"\A\s*---[ \t]*\r?\n(.*?)^---", re.S | re.M)
+ATTRIBUTE_RE = re.compile(r"""\b(lang|type|src)\s*=\s*["']([^"']*)["']""", re.I)
+TYPESCRIPT_LANGS = {"ts", "typescript"}
+TSX_LANGS = {"tsx", "jsx"}
+# Script types the bundler processes; others (JSON-LD, templates) carry no imports
+JAVASCRIPT_TYPES = {"", "module", "text/javascript", "application/javascript", "text/typescript"}
+
+
+def _blank(text):
+    return re.sub(r"[^\n]", " ", text)
+
+
+def component_script(code, extension):
+    """
+    Return the script parts of a component as one source with the markup blanked out
+
+    Args:
+        code (str): Component file content
+        extension (str): File extension ('.vue', '.svelte', '.astro')
+
+    Returns:
+        tuple: (source, grammar) where grammar is 'javascript', 'typescript' or 'tsx'
+    """
+    spans, langs, sources = [], set(), []
+    if extension == ".astro":
+        frontmatter = ASTRO_FRONTMATTER_RE.match(code)
+        if frontmatter:
+            spans.append(frontmatter.span(1))
+            # Astro frontmatter is always TypeScript
+            langs.add("ts")
+    for block in SCRIPT_BLOCK_RE.finditer(code):
+        attributes = {name.lower(): value.strip() for name, value in ATTRIBUTE_RE.findall(block.group(1))}
+        if re.search(r"\bis:inline\b", block.group(1)) or attributes.get("type", "").lower() not in JAVASCRIPT_TYPES:
+            continue
+        if attributes.get("src"):
+            sources.append(attributes["src"])
+        spans.append(block.span(2))
+        langs.add(attributes.get("lang", "").lower())
+
+    masked = list(_blank(code))
+    for start, end in spans:
+        masked[start:end] = code[start:end]
+    source = "".join(masked)
+    if sources:
+        source += "\n" + "".join(f'import "{src}";\n' for src in sources)
+
+    if langs & TSX_LANGS:
+        return source, "tsx"
+    if langs & TYPESCRIPT_LANGS:
+        return source, "typescript"
+    return source, "javascript"
@@ -491,7 +491,9 @@ class SolidityLanguageHandler(LanguageHandler):
             # Otherwise, return the first part as before
             return parts[0]
 
-    def extract_imports(self, tree_node, rel_path, file_components_dict, local_resolver_func, logger=None):
+    def extract_imports(
+        self, tree_node, rel_path, file_components_dict, local_resolver_func, logger=None, import_annotations=None
+    ):
         """
         Extract external package imports and resolved local imports from a Solidity source file
 
@@ -503,6 +505,7 @@ class SolidityLanguageHandler(LanguageHandler):
             file_components_dict (dict): Dictionary to track imported external components
             local_resolver_func (callable): Function to resolve local Solidity import paths
             logger (Logger): Optional logger instance for debug output
+            import_annotations (dict): Optional mapping of import name to annotations, updated in place
 
         Returns:
             Tuple of (list of external package names, list of resolved local file paths)
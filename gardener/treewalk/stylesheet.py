@@ -0,0 +1,180 @@
+"""
+CSS, Sass and Less import handler
+
+Stylesheets have no tree-sitter grammar in the language pack, so `@import`, `@use` and `@forward`
+rules are read with regular expressions, as are `url(~pkg/...)` asset references that webpack
+resolves from node_modules. Design-system packages are often only referenced from styles
+(`@use '~bootstrap/scss/bootstrap'`), so these imports are attributed to npm packages and
+annotated `evidence: stylesheet`
+"""
+
+import os
+import re
+
+from gardener.common.utils import Logger
+from gardener.treewalk.base import LanguageHandler
+
+# Module-level logger instance
+logger = Logger(verbose=False)  # Will be configured by the caller
+
+STYLESHEET_EXTENSIONS = [".css", ".scss", ".sass", ".less"]
+# Annotation `evidence` of packages imported by stylesheets
+STYLESHEET_EVIDENCE = "stylesheet"
+
+# Strings are kept; `//` line comments (Sass and Less) only start after whitespace or punctuation, so URLs survive
+COMMENT_RE = re.compile(r"'(?:\\.|[^'\\\n])*'|\"(?:\\.|[^\"\\\n])*\"|/\*.*?\*/|(?:^|(?<=[\s;{}]))//[^\n]*", re.S)
+RULE_RE = re.compile(r"@(import|use|forward)\b([^;{}]*)")
+# Indented Sass ends rules at the line break
+SASS_RULE_RE = re.compile(r"@(import|use|forward)\b([^;{}\n]*)")
+LESS_OPTIONS_RE = re.compile(r"^\s*\([^)]*\)")
+TARGET_RE = re.compile(r"url\(\s*(['\"]?)([^'\")]+)\1\s*\)|(['\"])([^'\"]+)\3")
+TILDE_URL_RE = re.compile(r"url\(\s*['\"]?(~[^'\")]+)['\"]?\s*\)")
+# Targets loaded by the browser or built into Sass
+EXTERNAL_PREFIXES = ("http://", "https://", "//", "data:", "sass:")
+
+
+class StylesheetDocument:
+    """
+    Parsed stylesheet
+
+    Attributes:
+        imports (list): (rule, target) pairs in source order; rule is 'import', 'use', 'forward' or 'url'
+    """
+
+    def __init__(self, imports=None):
+        self.imports = imports or []
+
+
+def parse_stylesheet(code, rel_path):
+    """
+    Collect the import targets of a CSS, Sass or Less file
+
+    Args:
+        code (str): File content
+        rel_path (str): File path, used to select the dialect
+
+    Returns:
+        StylesheetDocument: Parsed imports
+    """
+    text = COMMENT_RE.sub(lambda match: match.group(0) if match.group(0)[0] in "'\"" else "", code)
+    rule_re = SASS_RULE_RE if os.path.splitext(rel_path)[1].lower() == ".sass" else RULE_RE
+    imports = []
+    for rule, arguments in rule_re.findall(text):
+        arguments = LESS_OPTIONS_RE.sub("", arguments)
+        targets = [match.group(2) or match.group(4) for match in TARGET_RE.finditer(arguments)]
+        # `@use`/`@forward` take one URL followed by `as`/`with`/`show` clauses
+        for target in targets[:1] if rule != "import" else targets:
+            target = target.strip()
+            if target and not target.startswith(EXTERNAL_PREFIXES):
+                imports.append((rule, target))
+    imported = {target for _, target in imports}
+    for target in TILDE_URL_RE.findall(text):
+        if target not in imported:
+            imported.add(target)
+            imports.append(("url", target))
+    return StylesheetDocument(imports)
+
+
+def stylesheet_package_name(target):
+    """
+    Return the npm package a node_modules stylesheet target names ('~bootstrap/scss/x' -> 'bootstrap')
+
+    Args:
+        target (str): Import target without the `~` or `pkg:` prefix
+
+    Returns:
+        str|None: Package name
+    """
+    parts = target.split("/")
+    if target.startswith("@"):
+        return "/".join(parts[:2]) if len(parts) >= 2 and parts[1] else None
+    return parts[0] or None
+
+
+class StylesheetLanguageHandler(LanguageHandler):
+    """
+    Handler for CSS (.css), Sass (.scss, .sass) and Less (.less) files
+    """
+
+    def __init__(self, logger=None):
+        """
+        Args:
+            logger (Logger): Optional logger instance
+        """
+        self.logger = logger
+
+    def get_manifest_files(self):
+        return []
+
+    def get_file_extensions(self):
+        return list(STYLESHEET_EXTENSIONS)
+
+    def process_manifest(self, file_path, packages_dict, secure_file_ops=None):
+        return packages_dict
+
+    def parse_source(self, code, rel_path):
+        """
+        Parse stylesheet rules with regular expressions instead of a tree-sitter grammar
+
+        Args:
+            code (str): File content
+            rel_path (str): Relative path of the file
+
+        Returns:
+            StylesheetDocument: Parsed document passed to extract_imports
+        """
+        return parse_stylesheet(code, rel_path)
+
+    def normalize_package_name(self, package_path):
+        """
+        Map a `~` or `pkg:` stylesheet target to the npm package it loads from
+
+        Args:
+            package_path (str): Target as written, e.g. '~@fontsource/inter/400.css'
+
+        Returns:
+            str|None: Package name, or None for targets that are not node_modules references
+        """
+        for prefix in ("~", "pkg:"):
+            if package_path.startswith(prefix):
+                return stylesheet_package_name(package_path[len(prefix) :])
+        return None
+
+    def extract_imports(
+        self, tree_node, rel_path, file_components_dict, local_resolver_func, logger=None, import_annotations=None
+    ):
+        """
+        Extract stylesheet imports, resolving repo-local partials first
+
+        Args:
+            tree_node (StylesheetDocument): Document produced by parse_source
+            rel_path (str): Relative path of the file
+            file_components_dict (dict): Dictionary to track imported external components
+            local_resolver_func (callable): Function resolving a target to a local path or '__PACKAGE:<name>'
+            logger (Logger): Optional logger instance for debug output
+            import_annotations (dict): Optional mapping of import name to annotations, updated in place
+
+        Returns:
+            Tuple of (external_imports, local_imports)
+        """
+        log = logger or self.logger
+        external_imports = []
+        local_imports = []
+        for rule, target in tree_node.imports:
+            resolved = local_resolver_func(rel_path, target) if local_resolver_func else None
+            if resolved and not resolved.startswith("__PACKAGE:"):
+                if resolved not in local_imports:
+                    local_imports.append(resolved)
+                continue
+            package = resolved[len("__PACKAGE:") :] if resolved else self.normalize_package_name(target)
+            if not package:
+                if log:
+                    log.debug(f"Unresolved stylesheet @{rule} '{target}' in {rel_path}")
+                continue
+            if package not in external_imports:
+                external_imports.append(package)
+            if import_annotations is not None:
+                import_annotations.setdefault(package, {})["evidence"] = STYLESHEET_EVIDENCE
+            component = target[1:] if target.startswith("~") else target[4:] if target.startswith("pkg:") else target
+            file_components_dict[rel_path].append((package, component))
+        return external_imports, local_imports
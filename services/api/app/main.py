@@ -7,6 +7,7 @@ import sys
 from decimal import Decimal
 from contextlib import asynccontextmanager
 from datetime import datetime, timedelta, timezone
+from typing import Optional
 from uuid import UUID
 
 import redis
@@ -24,6 +25,7 @@ from starlette.responses import JSONResponse as StarletteJSONResponse
 from gardener.common.subprocess import SecureSubprocess
 from gardener.common.utils import get_logger
 from services.api.app.schemas import (
+    AnalysisDeltaResponse,
     AnalysisResultsResponse,
     AnalysisRunRequest,
     AnalysisRunResponse,
@@ -37,6 +39,7 @@ from services.api.app.security import verify_auth_token
 from services.shared.celery_client import celery_client
 from services.shared.config import settings
 from services.shared.database import check_db_connection, get_db
+from services.shared.drip_list_processor import diff_drip_lists
 from services.shared.models import AnalysisJob, JobStatus, Repository
 from services.shared.utils import canonicalize_repo_url
 from services.shared.estimator import estimate_duration_seconds
@@ -534,6 +537,112 @@ async def get_latest_results_by_url(repository_url: str, db: Session = Depends(g
     )
 
 
+def _latest_completed_job(db, repository_id):
+    """
+    Return the most recent completed analysis job for a repository, or None
+    """
+    return (
+        db.query(AnalysisJob)
+        .options(joinedload(AnalysisJob.drip_list_items))
+        .filter(AnalysisJob.repository_id == repository_id, AnalysisJob.status == JobStatus.COMPLETED)
+        .order_by(AnalysisJob.completed_at.desc())
+        .first()
+    )
+
+
+def _build_delta_response(db, job, since_job_id=None):
+    """
+    Diff a job's Drip List against an earlier completed job of the same repository
+
+    Args:
+        db (Session): Database session
+        job (AnalysisJob): Latest completed job
+        since_job_id (UUID|None): Base job; defaults to the completed job preceding `job`
+
+    Returns:
+        AnalysisDeltaResponse
+
+    Raises:
+        HTTPException: 404 if since_job_id is not a completed job of the same repository
+    """
+    completed = (
+        db.query(AnalysisJob)
+        .options(joinedload(AnalysisJob.drip_list_items))
+        .filter(AnalysisJob.repository_id == job.repository_id, AnalysisJob.status == JobStatus.COMPLETED)
+    )
+    if since_job_id:
+        base = completed.filter(AnalysisJob.id == since_job_id).first()
+        if not base:
+            raise HTTPException(status_code=404, detail="Base analysis not found for this repository")
+    else:
+        base = (
+            completed.filter(AnalysisJob.id != job.id, AnalysisJob.completed_at < job.completed_at)
+            .order_by(AnalysisJob.completed_at.desc())
+            .first()
+        )
+
+    def items(analysis_job):
+        return [
+            {"package_name": i.package_name, "package_url": i.package_url, "split_percentage": i.split_percentage}
+            for i in analysis_job.drip_list_items
+        ]
+
+    delta = diff_drip_lists(items(base) if base else [], items(job))
+    return AnalysisDeltaResponse(
+        job_id=job.id,
+        repository_id=job.repository_id,
+        commit_sha=job.commit_sha,
+        completed_at=job.completed_at,
+        base_job_id=base.id if base else None,
+        base_commit_sha=base.commit_sha if base else None,
+        added=delta["added"],
+        removed=delta["removed"],
+        changed=delta["changed"],
+        unchanged_count=delta["unchanged"],
+    )
+
+
+@app.get(
+    "/api/v1/repositories/{repository_id}/results/delta", response_model=AnalysisDeltaResponse, tags=["Results"]
+)
+async def get_results_delta(repository_id: UUID, since_job_id: Optional[UUID] = None, db: Session = Depends(get_db)):
+    """
+    Get only what changed in the latest results versus a previous analysis of the repository
+
+    Compares against `since_job_id` when given, otherwise against the preceding completed analysis
+    """
+    job = _latest_completed_job(db, repository_id)
+    if not job:
+        raise HTTPException(
+            status_code=status.HTTP_404_NOT_FOUND, detail="No completed analysis found for this repository"
+        )
+    return _build_delta_response(db, job, since_job_id)
+
+
+@app.get("/api/v1/repositories/results/delta", response_model=AnalysisDeltaResponse, tags=["Results"])
+async def get_results_delta_by_url(
+    repository_url: str, since_job_id: Optional[UUID] = None, db: Session = Depends(get_db)
+):
+    """
+    Get only what changed in the latest results versus a previous analysis, by repository URL
+
+    Accepts the same URL forms as /api/v1/repositories/results/latest
+    """
+    try:
+        canonical = canonicalize_repo_url(repository_url)
+    except Exception:
+        raise HTTPException(status_code=400, detail="Invalid repository_url")
+
+    repo = db.query(Repository).filter(Repository.canonical_url == canonical).first()
+    if not repo:
+        raise HTTPException(status_code=404, detail="Repository not found")
+
+    job = _latest_completed_job(db, repo.id)
+    if not job:
+        raise HTTPException(status_code=404, detail="No completed analysis found for this repository")
+    return _build_delta_response(db, job, since_job_id)
+
+
 # Error handlers
 @app.exception_handler(HTTPException)
 async def http_exception_handler(request, exc):
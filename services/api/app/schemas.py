@@ -117,6 +117,31 @@ class AnalysisResultsResponse(BaseModel):
     pass
 
 
+class DripListChangeResponse(DripListItemResponse):
+    """Drip List item whose split changed since the base analysis"""
+
+    previous_split_percentage: Decimal
+
+    @field_serializer("previous_split_percentage")
+    def serialize_previous_split_percentage(self, v):
+        return float(v)
+
+
+class AnalysisDeltaResponse(BaseModel):
+    """Changes in the latest analysis results relative to an earlier analysis of the same repository"""
+
+    job_id: UUID
+    repository_id: UUID
+    commit_sha: str
+    completed_at: datetime
+    base_job_id: Optional[UUID] = Field(None, description="Analysis compared against; null when there is none")
+    base_commit_sha: Optional[str] = None
+    added: List[DripListItemResponse]
+    removed: List[DripListItemResponse]
+    changed: List[DripListChangeResponse]
+    unchanged_count: int
+
+
 # Health check models
 class HealthResponse(BaseModel):
     """Health check response"""
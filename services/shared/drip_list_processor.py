@@ -180,3 +180,35 @@ def build_normalized_drip_list(items, max_length, analyzed_repo_url):
         return []
     aggregates = aggregate_by_repository_url(filtered)
     return truncate_and_normalize(aggregates, max_length)
+
+
+def diff_drip_lists(previous_items, current_items):
+    """
+    Compare two drip lists, keyed by repository URL (package name when there is no URL)
+
+    Args:
+        previous_items (list[dict]): Items of the base report with package_name, package_url and split_percentage
+        current_items (list[dict]): Items of the newer report, same shape
+
+    Returns:
+        dict: added (current items not in the base), removed (base items no longer present), changed
+            (current items whose URL's split changed, with previous_split_percentage) and unchanged (count)
+    """
+
+    def key(item):
+        return item.get("package_url") or item["package_name"]
+
+    previous = {key(item): item for item in previous_items}
+    current = {key(item): item for item in current_items}
+
+    added, changed, unchanged = [], [], 0
+    for item_key, item in current.items():
+        base = previous.get(item_key)
+        if base is None:
+            added.append(item)
+        elif Decimal(str(base["split_percentage"])) != Decimal(str(item["split_percentage"])):
+            changed.append(dict(item, previous_split_percentage=base["split_percentage"]))
+        else:
+            unchanged += 1
+    removed = [item for item_key, item in previous.items() if item_key not in current]
+    return {"added": added, "removed": removed, "changed": changed, "unchanged": unchanged}
@@ -306,6 +306,7 @@ def analyze_repo_task(job_id, drip_list_max_length=200, force_url_refresh=False)
 
                 # 3. Resolve URLs using the cache
                 logger.info("Resolving URLs...")
+                analyzer.record_cache_state(external_packages, None if force_url_refresh else preloaded_url_cache)
                 resolved_urls = _resolve_repository_urls(external_packages, analyzer.logger, preloaded_url_cache)
 
                 # Update external packages with resolved URLs
@@ -8,6 +8,7 @@ import random
 
 import pytest
 
+from gardener.common.defaults import ConfigOverride
 from gardener.package_metadata import url_resolver
 
 
@@ -49,3 +50,12 @@ def offline_mode():
                 url_resolver.set_request_fn(None)
 
     return Offline()
+
+
+@pytest.fixture(autouse=True)
+def isolated_crates_index(tmp_path):
+    """
+    Keep the crates.io metadata cache per test, so crates cached by one test never answer for another
+    """
+    with ConfigOverride({"CRATES_INDEX_CACHE": str(tmp_path / "crates_index.json")}):
+        yield
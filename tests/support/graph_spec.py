@@ -4,6 +4,8 @@ Graph spec loader and matcher (minimal implementation)
 
 import json
 
+from gardener.analysis.conformance import graph_spec_failures
+
 try:
     import yaml
 except Exception:  # pragma: no cover - optional dependency
@@ -32,28 +34,6 @@ def assert_graph_matches_spec(graph_data, spec, lax=True):
         spec (dict): spec with sections like {'nodes': {'file': {'include': [...]}}}
         lax (bool): if True, ignore extra nodes/edges
     """
-    nodes = graph_data.get("nodes", [])
-    links = graph_data.get("links", [])
-
-    by_id = {n.get("id"): n for n in nodes}
-
-    # Nodes
-    for node_type, rules in (spec.get("nodes") or {}).items():
-        include = rules.get("include", [])
-        for nid in include:
-            if nid not in by_id:
-                raise AssertionError(f"Missing required node: {nid}")
-            n = by_id[nid]
-            if node_type and n.get("type") != node_type:
-                raise AssertionError(f"Node {nid} expected type {node_type} but got {n.get('type')}")
-
-    # Edges
-    for edge_type, pairs in (spec.get("edges") or {}).items():
-        for src, dst in pairs:
-            match = False
-            for e in links:
-                if e.get("source") == src and e.get("target") == dst and e.get("type") == edge_type:
-                    match = True
-                    break
-            if not match:
-                raise AssertionError(f"Missing required edge: {src} -[{edge_type}]-> {dst}")
+    failures = graph_spec_failures(graph_data, spec)
+    if failures:
+        raise AssertionError(failures[0])
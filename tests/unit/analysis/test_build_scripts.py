@@ -0,0 +1,79 @@
+"""
+Cargo build scripts as the build-script scope
+"""
+
+import pytest
+
+from gardener.analysis.build_scripts import annotate_build_scripts, collect_build_scripts, parse_link_lib
+from gardener.analysis.cargo_dep_kinds import cross_check_dep_kinds
+
+
+def _source_files(root, files):
+    source_files = {}
+    for rel_path, content in files.items():
+        path = root / rel_path
+        path.parent.mkdir(parents=True, exist_ok=True)
+        path.write_text(content)
+        source_files[rel_path] = {"absolute_path": str(path), "language": "rust"}
+    return source_files
+
+
+@pytest.mark.unit
+def test_link_lib_values_split_into_name_and_kind():
+    assert parse_link_lib("ssl") == ("ssl", None)
+    assert parse_link_lib("static=z") == ("z", "static")
+    assert parse_link_lib("static:+whole-archive=foo:bar") == ("foo", "static")
+    assert parse_link_lib("dylib={}") is None
+
+
+@pytest.mark.unit
+def test_build_script_imports_are_scoped_and_native_libraries_reported(tmp_path):
+    source_files = _source_files(
+        tmp_path,
+        {
+            "build.rs": (
+                'include!("build/probe.rs");\n'
+                "fn main() {\n"
+                '    println!("cargo:rustc-link-lib=static=sqlite3");\n'
+                '    println!("cargo::rustc-link-lib=framework=Security");\n'
+                "}\n"
+            ),
+            "build/probe.rs": 'fn probe() { println!("cargo:rustc-link-lib=ssl"); }\n',
+            "src/lib.rs": "use serde::Serialize;\n",
+        },
+    )
+    external_packages = {
+        "cc": {"ecosystem": "cargo", "dep_kind": "build", "import_names": ["cc"]},
+        "pkg-config": {"ecosystem": "cargo", "dep_kind": "normal", "import_names": ["pkg_config"]},
+        "serde": {"ecosystem": "cargo", "dep_kind": "normal", "import_names": ["serde"]},
+    }
+    file_imports = {"build.rs": ["cc"], "build/probe.rs": ["pkg_config"], "src/lib.rs": ["serde"]}
+    file_import_annotations = {}
+
+    scripts = collect_build_scripts(source_files)
+    assert scripts["build.rs"]["includes"] == ["build/probe.rs"]
+    cross_check_dep_kinds(external_packages, file_imports, source_files, build_files={"build/probe.rs"})
+    reports = annotate_build_scripts(scripts, external_packages, file_imports, file_import_annotations)
+
+    assert reports == [
+        {
+            "file": "build.rs",
+            "includes": ["build/probe.rs"],
+            "native_libraries": [
+                {"name": "sqlite3", "kind": "static", "file": "build.rs", "line": 3},
+                {"name": "Security", "kind": "framework", "file": "build.rs", "line": 4},
+                {"name": "ssl", "kind": None, "file": "build/probe.rs", "line": 1},
+            ],
+            "imports": [
+                {"import": "cc", "package": "cc", "dep_kind": "build"},
+                {"import": "pkg_config", "package": "pkg-config", "dep_kind": "normal"},
+            ],
+        }
+    ]
+    assert file_import_annotations == {
+        "build.rs": {"cc": {"scope": "build-script"}},
+        "build/probe.rs": {"pkg_config": {"scope": "build-script"}},
+    }
+    assert external_packages["pkg-config"]["dep_kind_mismatch"] == (
+        "only imported from build.rs; should be a build-dependency"
+    )
@@ -0,0 +1,122 @@
+"""
+Air-gapped bundle creation and offline replay
+"""
+
+import io
+import json
+import socket
+
+import pytest
+
+from gardener.analysis import bundle as bundle_mod, main as main_mod
+from gardener.analysis.bundle import BUNDLE_FORMAT, Bundle, create_bundle, read_repo_list, request_hook
+from gardener.analysis.cargo_metadata import run_cargo_metadata
+from gardener.common import network
+from gardener.common.utils import Logger
+from gardener.package_metadata import github_auth
+from gardener.package_metadata.name_resolvers.python import resolve_python_import_names
+from gardener.package_metadata.url_resolver import resolve_package_urls
+
+LODASH_META = {
+    "dist-tags": {"latest": "4.17.21"},
+    "versions": {"4.17.21": {"repository": {"type": "git", "url": "git+https://github.com/lodash/lodash.git"}}},
+}
+
+
+class _Response(io.BytesIO):
+    status = 200
+
+
+@pytest.mark.unit
+def test_bundle_records_registry_responses_and_replays_offline(tmp_path, monkeypatch):
+    repo = tmp_path / "app"
+    repo.mkdir()
+    (repo / "package.json").write_text(json.dumps({"name": "app", "dependencies": {"lodash": "^4.17.21"}}))
+    (repo / "index.js").write_text("const _ = require('lodash');\n")
+    (tmp_path / "repos.txt").write_text(f"# funding round\n{repo}\n\n")
+
+    fetched = []
+
+    def fake_urlopen(request, timeout=None):
+        fetched.append(request.full_url)
+        return _Response(json.dumps(LODASH_META).encode("utf-8"))
+
+    monkeypatch.setattr(bundle_mod.urllib.request, "urlopen", fake_urlopen)
+    manifest = create_bundle(read_repo_list(str(tmp_path / "repos.txt")), str(tmp_path / "bundle"), Logger())
+
+    assert fetched == ["https://registry.npmjs.org/lodash"]
+    assert manifest["repos"][0]["path"] == "repos/app"
+    assert (tmp_path / "bundle" / "repos" / "app" / "index.js").is_file()
+
+    def no_network(request, timeout=None):
+        raise AssertionError(f"network access during offline replay: {request.full_url}")
+
+    monkeypatch.setattr(bundle_mod.urllib.request, "urlopen", no_network)
+    offline = Bundle(str(tmp_path / "bundle"))
+    assert offline.repo_path(str(repo) + "/") == str(tmp_path / "bundle" / "repos" / "app")
+
+    packages = {"lodash": {"ecosystem": "npm"}, "left-pad": {"ecosystem": "npm"}}
+    with request_hook(offline.replayer):
+        resolved = resolve_package_urls(packages, logger=None, cache={})
+
+    assert resolved == {"lodash": "https://github.com/lodash/lodash"}
+    assert offline.replayer.misses == ["https://registry.npmjs.org/left-pad"]
+
+
+@pytest.mark.unit
+def test_unknown_bundle_format_is_rejected(tmp_path):
+    (tmp_path / "bundle.json").write_text(json.dumps({"format": 99}))
+    (tmp_path / "responses.json").write_text("{}")
+
+    with pytest.raises(ValueError, match="Unsupported bundle format"):
+        Bundle(str(tmp_path))
+
+
+@pytest.mark.unit
+def test_bundle_runs_make_no_network_calls(tmp_path, monkeypatch):
+    snapshot = tmp_path / "bundle" / "repos" / "app"
+    snapshot.mkdir(parents=True)
+    (snapshot / "requirements.txt").write_text("requests\n")
+    (snapshot / "main.py").write_text("import requests\n")
+    manifest = {"format": BUNDLE_FORMAT, "repos": [{"source": "https://github.com/acme/app", "path": "repos/app"}]}
+    (tmp_path / "bundle" / "bundle.json").write_text(json.dumps(manifest))
+    (tmp_path / "bundle" / "responses.json").write_text("{}")
+
+    def no_network(*args, **kwargs):
+        raise AssertionError(f"network access during a bundle run: {args}")
+
+    monkeypatch.setattr(socket.socket, "connect", no_network)
+    monkeypatch.setattr(socket, "create_connection", no_network)
+    monkeypatch.chdir(tmp_path)
+
+    class RecordingRunner:
+        def __init__(self, **kwargs):
+            pass
+
+        def run(self, command, **kwargs):
+            commands.append(command)
+            return type("Completed", (), {"stdout": '{"packages": []}'})()
+
+    commands = []
+    monkeypatch.setattr("gardener.analysis.cargo_metadata.SecureSubprocess", RecordingRunner)
+    analyze_repository = main_mod.analyze_repository
+
+    def checked_analyze_repository(*args, **kwargs):
+        # Paths that bypass the registry replay refuse or stay local while the run is offline
+        assert network.is_offline()
+        with pytest.raises(network.NetworkDisabledError):
+            github_auth._post_json("https://api.github.com/app/installations/1/access_tokens", {})
+        assert resolve_python_import_names("requests") == []
+        run_cargo_metadata(str(snapshot), {".": ("Cargo.toml", {})}, cargo="/usr/bin/cargo")
+        return analyze_repository(*args, **kwargs)
+
+    monkeypatch.setattr(main_mod, "analyze_repository", checked_analyze_repository)
+
+    results = main_mod.run_analysis(
+        "https://github.com/acme/app", from_bundle=str(tmp_path / "bundle"), minimal_outputs=True, output_prefix="app"
+    )
+
+    assert "requests" in results["external_packages"]
+    assert "--offline" in commands[0]
+    assert not network.is_offline()
+
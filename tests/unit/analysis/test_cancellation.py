@@ -0,0 +1,77 @@
+"""
+Graceful cancellation with partial results
+"""
+
+import json
+import os
+import signal
+
+import pytest
+
+from gardener.analysis.bundle import request_hook
+from gardener.analysis.main import DependencyAnalyzer
+from gardener.common.cancellation import Cancellation, handle_signals
+from gardener.common.defaults import ConfigOverride
+
+
+def _npm_meta(repo):
+    return json.dumps(
+        {"dist-tags": {"latest": "1.0.0"}, "versions": {"1.0.0": {"repository": f"git+https://github.com/{repo}.git"}}}
+    )
+
+
+def _js_repo(tmp_path):
+    (tmp_path / "package.json").write_text(
+        json.dumps({"name": "app", "dependencies": {"react": "^18.2.0", "lodash": "^4.17.21", "chalk": "^5.3.0"}})
+    )
+    (tmp_path / "index.js").write_text("import _ from 'lodash';\n")
+    (tmp_path / "cli.js").write_text("const chalk = require('chalk');\n")
+    return str(tmp_path)
+
+
+@pytest.mark.unit
+def test_first_signal_requests_cancellation_and_second_aborts():
+    cancellation = Cancellation()
+
+    with handle_signals(cancellation):
+        os.kill(os.getpid(), signal.SIGTERM)
+        assert cancellation.signal_name == "SIGTERM"
+        with pytest.raises(KeyboardInterrupt):
+            os.kill(os.getpid(), signal.SIGINT)
+
+    assert signal.getsignal(signal.SIGINT) is signal.default_int_handler
+
+
+@pytest.mark.unit
+def test_cancellation_during_url_resolution_marks_results_partial(tmp_path):
+    cancellation = Cancellation()
+    requested = []
+
+    def registry(url):
+        # The signal arrives while the first lookup is in flight; that lookup still completes
+        requested.append(url)
+        cancellation.request("SIGINT")
+        return _npm_meta("facebook/react")
+
+    analyzer = DependencyAnalyzer(verbose=False, cancellation=cancellation)
+    with ConfigOverride({"URL_RESOLUTION_ORDER": "declared"}), request_hook(registry):
+        results = analyzer.analyze(_js_repo(tmp_path), url_cache={"npm:chalk": "https://github.com/chalk/chalk"})
+
+    assert requested == ["https://registry.npmjs.org/react"]
+    packages = results["external_packages"]
+    assert packages["react"]["repository_url"] == "https://github.com/facebook/react"
+    assert packages["chalk"]["repository_url"] == "https://github.com/chalk/chalk"
+    assert packages["lodash"]["enrichment"] == "skipped-cancelled"
+    assert results["partial"] is True
+    assert results["cancellation"]["signal"] == "SIGINT"
+    assert results["cancellation"]["url_resolution"] == {"completed": 2, "remaining": 1}
+    assert results["cancellation"]["imports"] == {"completed": 0, "remaining": 2}
+
+
+@pytest.mark.unit
+def test_uncancelled_run_is_not_partial(tmp_path):
+    with request_hook(lambda url: None):
+        results = DependencyAnalyzer(verbose=False).analyze(_js_repo(tmp_path))
+
+    assert "partial" not in results
+    assert "cancellation" not in results
@@ -0,0 +1,67 @@
+"""
+Fixture conformance harness expectation matching
+"""
+
+import pytest
+
+from gardener.analysis.conformance import discover_fixtures, expectation_failures, reloadable_modules
+
+RESULTS = {
+    "external_packages": {"requests": {"ecosystem": "pypi"}},
+    "analyzer_details": {"file_imports": {"main.py": ["requests", "os"]}},
+    "dependency_graph": {
+        "nodes": [{"id": "main.py", "type": "file"}, {"id": "requests", "type": "package"}],
+        "links": [{"source": "main.py", "target": "requests", "type": "imports_package"}],
+    },
+}
+
+
+@pytest.mark.unit
+def test_expectations_met():
+    expectations = {
+        "packages": {"include": ["requests"], "exclude": ["flask"]},
+        "imports": {"main.py": ["requests"]},
+        "nodes": {"file": {"include": ["main.py"]}},
+        "edges": {"imports_package": [["main.py", "requests"]]},
+    }
+    assert expectation_failures(RESULTS, expectations) == []
+
+
+@pytest.mark.unit
+def test_expectation_failures_are_all_reported():
+    expectations = {
+        "packages": ["numpy"],
+        "imports": {"main.py": ["numpy"]},
+        "nodes": {"package": {"include": ["main.py"]}},
+        "edges": {"imports_local": [["main.py", "utils.py"]]},
+    }
+    assert expectation_failures(RESULTS, expectations) == [
+        "Missing expected package: numpy",
+        "Missing import evidence: main.py -> numpy",
+        "Node main.py expected type package but got file",
+        "Missing required edge: main.py -[imports_local]-> utils.py",
+    ]
+
+
+@pytest.mark.unit
+def test_discover_fixtures_requires_expectation_file(tmp_path):
+    (tmp_path / "with_spec").mkdir()
+    (tmp_path / "with_spec" / "gardener-expected.json").write_text("{}")
+    (tmp_path / "without_spec").mkdir()
+
+    fixtures = discover_fixtures(str(tmp_path))
+
+    assert [name for name, _, _ in fixtures] == ["with_spec"]
+
+
+@pytest.mark.unit
+def test_every_loaded_handler_module_is_reloadable():
+    import gardener.analysis.tree  # noqa: F401
+    import gardener.treewalk.npm_locks  # noqa: F401
+
+    modules = reloadable_modules()
+
+    assert {"gardener.treewalk.npm_locks", "gardener.analysis.go_workspace", "gardener.analysis.tree"} <= set(modules)
+    assert "gardener.analysis.conformance" not in modules
+    assert modules.index("gardener.treewalk.base") < modules.index("gardener.treewalk.javascript")
+    assert modules.index("gardener.treewalk.javascript") < modules.index("gardener.analysis.tree")
@@ -0,0 +1,59 @@
+"""
+Aggregated import evidence
+"""
+
+import pytest
+
+from gardener.analysis.bundle import request_hook
+from gardener.analysis.evidence import aggregate_evidence
+from gardener.analysis.main import DependencyAnalyzer
+from gardener.common.defaults import ConfigOverride
+from gardener.persistence.stream import report_stats
+from gardener.treewalk.python import PythonLanguageHandler
+
+
+@pytest.mark.unit
+def test_identical_observations_collapse_into_counted_groups():
+    file_imports = {f"gen/m{i}.py": ["protobuf"] for i in range(8)}
+    file_imports["app.py"] = ["protobuf", "requests"]
+    components = {path: [("protobuf", "google.protobuf.message")] for path in file_imports}
+    components["app.py"].append(("requests", "requests.get"))
+
+    evidence = aggregate_evidence(file_imports, components, sample_size=3)
+
+    assert evidence["imports"] == [
+        {"import": "protobuf", "count": 9, "sample_files": ["app.py", "gen/m0.py", "gen/m1.py"]},
+        {"import": "requests", "count": 1, "sample_files": ["app.py"]},
+    ]
+    assert evidence["components"][0] == {
+        "package": "protobuf",
+        "component": "google.protobuf.message",
+        "count": 9,
+        "sample_files": ["app.py", "gen/m0.py", "gen/m1.py"],
+    }
+
+
+@pytest.mark.unit
+def test_aggregated_mode_replaces_per_file_evidence(tmp_path, monkeypatch):
+    for i in range(6):
+        (tmp_path / f"gen{i}.py").write_text("from google.protobuf import message\n")
+
+    def extract_imports(self, tree_node, rel_path, file_components_dict, *args, **kwargs):
+        file_components_dict[rel_path].append(("protobuf", "google.protobuf.message"))
+        return ["google.protobuf"], []
+
+    monkeypatch.setattr(PythonLanguageHandler, "parse_source", lambda self, code, rel_path: object())
+    monkeypatch.setattr(PythonLanguageHandler, "extract_imports", extract_imports)
+
+    with request_hook(lambda url: None):
+        files = DependencyAnalyzer(verbose=False).analyze(str(tmp_path))
+        with ConfigOverride({"EVIDENCE": "aggregated"}):
+            aggregated = DependencyAnalyzer(verbose=False).analyze(str(tmp_path))
+
+    details = aggregated["analyzer_details"]
+    assert "file_imports" not in details and "file_package_components" not in details
+    sample = ["gen0.py", "gen1.py", "gen2.py", "gen3.py", "gen4.py"]
+    assert details["evidence"]["imports"] == [{"import": "google.protobuf", "count": 6, "sample_files": sample}]
+    assert details["evidence"]["components"][0]["count"] == 6
+    assert report_stats(aggregated)["evidence_count"] == report_stats(files)["evidence_count"] == 6
+    assert aggregated["dependency_graph"] == files["dependency_graph"]
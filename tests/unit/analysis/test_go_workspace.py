@@ -0,0 +1,96 @@
+"""
+Go modules of the repository as internal packages
+"""
+
+import pytest
+
+from gardener.analysis.go_workspace import collect_go_modules, drop_internal_packages, internalize_imports, module_roots
+from gardener.analysis.imports import LocalImportResolver
+
+FILES = {
+    "go.mod": (
+        "module example.com/app\n\ngo 1.22\n\n"
+        "require (\n\tgithub.com/spf13/cobra v1.8.0\n\texample.com/app/tools v0.0.0\n\texample.com/shared v0.1.0\n)\n\n"
+        "replace example.com/app/tools => ./tools\n\nreplace example.com/shared => ./libs/shared\n"
+    ),
+    "go.work": "go 1.22\n\nuse (\n\t.\n\t./tools\n)\n",
+    "tools/go.mod": "module example.com/app/tools\n\ngo 1.22\n",
+    "cmd/app/main.go": "",
+    "internal/db/db.go": "",
+    "internal/db/conn.go": "",
+    "internal/db/db_test.go": "",
+    "tools/gen/gen.go": "",
+    "libs/shared/shared.go": "",
+}
+
+
+def _write(tmp_path):
+    for rel_path, content in FILES.items():
+        path = tmp_path / rel_path
+        path.parent.mkdir(parents=True, exist_ok=True)
+        path.write_text(content)
+    manifests = [str(tmp_path / rel_path) for rel_path in FILES if not rel_path.endswith(".go")]
+    source_files = {
+        rel_path: {"absolute_path": str(tmp_path / rel_path), "language": "go"}
+        for rel_path in FILES
+        if rel_path.endswith(".go")
+    }
+    return manifests, source_files
+
+
+@pytest.mark.unit
+def test_nested_modules_and_local_replacements_are_the_repositorys_own(tmp_path):
+    manifests, source_files = _write(tmp_path)
+
+    members = collect_go_modules(manifests, None, None, str(tmp_path))
+
+    assert members["example.com/app"] == {
+        "annotation": "workspace-member",
+        "ecosystem": "go",
+        "manifest": "go.mod",
+        "path": ".",
+        "via": ["go.mod", "go.work"],
+    }
+    assert members["example.com/app/tools"]["path"] == "tools"
+    # A replacement directory without a go.mod of its own
+    assert members["example.com/shared"]["path"] == "libs/shared"
+    assert members["example.com/shared"]["via"] == ["replace"]
+
+    packages = {name: {"ecosystem": "go"} for name in ("github.com/spf13/cobra", "example.com/app/tools")}
+    packages["example.com/shared"] = {"ecosystem": "go"}
+    assert drop_internal_packages(packages, members) == ["example.com/app/tools", "example.com/shared"]
+    assert list(packages) == ["github.com/spf13/cobra"]
+
+    resolver = LocalImportResolver(
+        repo_path=str(tmp_path),
+        source_files=source_files,
+        alias_resolver=None,
+        js_ts_base_url=None,
+        js_ts_path_aliases=None,
+        go_module_path="example.com/app",
+        remappings=None,
+        hardhat_remappings=None,
+        solidity_src_path=None,
+        logger=None,
+        go_module_roots=module_roots(members),
+    )
+    # The nested module owns its import paths, not the root module's tools/ directory
+    assert resolver.resolve_go("cmd/app/main.go", "example.com/app/tools/gen") == "tools/gen/gen.go"
+    assert resolver.resolve_go("cmd/app/main.go", "example.com/shared") == "libs/shared/shared.go"
+    # A path sharing only a string prefix with the module is another module
+    assert resolver.resolve_go("cmd/app/main.go", "example.com/application/db") is None
+
+
+@pytest.mark.unit
+def test_imports_of_own_packages_become_local_edges(tmp_path):
+    manifests, source_files = _write(tmp_path)
+    members = collect_go_modules(manifests, None, None, str(tmp_path))
+    file_imports = {"cmd/app/main.go": ["fmt", "github.com/spf13/cobra", "example.com/app/internal/db"]}
+    local_imports = {}
+
+    internal = internalize_imports(file_imports, local_imports, source_files, members)
+
+    assert internal == {"cmd/app/main.go": ["example.com/app/internal/db"]}
+    assert file_imports == {"cmd/app/main.go": ["fmt", "github.com/spf13/cobra"]}
+    # Every non-test file of the package directory
+    assert local_imports == {"cmd/app/main.go": ["internal/db/conn.go", "internal/db/db.go"]}
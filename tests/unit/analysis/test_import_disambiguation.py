@@ -0,0 +1,220 @@
+"""
+Local module vs external package disambiguation in LocalImportResolver
+"""
+
+import pytest
+
+from gardener.analysis.imports import LocalImportResolver
+from gardener.common.defaults import ResourceLimits
+from gardener.common.secure_file_ops import SecureFileOps
+
+
+def _write(tmp_path, rel, content=""):
+    path = tmp_path / rel
+    path.parent.mkdir(parents=True, exist_ok=True)
+    path.write_text(content)
+    return str(path)
+
+
+def _resolver(tmp_path, files, external_packages, js_ts_base_url=None, **kwargs):
+    source_files = {}
+    for rel, (language, content) in files.items():
+        source_files[rel] = {"absolute_path": _write(tmp_path, rel, content), "language": language}
+    return LocalImportResolver(
+        repo_path=str(tmp_path),
+        source_files=source_files,
+        alias_resolver=None,
+        js_ts_base_url=js_ts_base_url,
+        js_ts_path_aliases=None,
+        go_module_path=None,
+        remappings=None,
+        hardhat_remappings=None,
+        solidity_src_path=None,
+        logger=None,
+        external_packages=external_packages,
+        **kwargs,
+    )
+
+
+@pytest.mark.unit
+def test_rust_declared_mod_shadows_crate(tmp_path):
+    files = {
+        "src/main.rs": ("rust", "mod log;\nuse log::info;\n"),
+        "src/log.rs": ("rust", "pub fn info() {}\n"),
+    }
+    resolver = _resolver(tmp_path, files, {"log": {"ecosystem": "cargo", "import_names": ["log"]}})
+
+    assert resolver.resolve_rust("src/main.rs", ["log", "info"]) == "src/log.rs"
+    assert resolver.disambiguations[0]["chosen"] == "local"
+    assert resolver.disambiguations[0]["local_candidate"] == "src/log.rs"
+
+
+@pytest.mark.unit
+def test_rust_undeclared_mod_file_is_extern_crate(tmp_path):
+    files = {
+        "src/main.rs": ("rust", "use log::info;\n"),
+        "src/log.rs": ("rust", "pub fn info() {}\n"),
+    }
+    resolver = _resolver(tmp_path, files, {"log": {"ecosystem": "cargo", "import_names": ["log"]}})
+
+    assert resolver.resolve_rust("src/main.rs", ["log", "info"]) is None
+    decision = resolver.disambiguations[0]
+    assert decision["chosen"] == "external"
+    assert decision["external_candidate"] == "log"
+
+
+@pytest.mark.unit
+def test_js_bare_specifier_ignores_local_directory(tmp_path):
+    files = {
+        "src/app.js": ("javascript", "import utils from 'utils'\n"),
+        "src/utils/index.js": ("javascript", "export default {}\n"),
+    }
+    resolver = _resolver(tmp_path, files, {"utils": {"ecosystem": "npm"}})
+
+    assert resolver.resolve_js("src/app.js", "utils") is None
+    assert resolver.disambiguations == [
+        {
+            "language": "javascript",
+            "file": "src/app.js",
+            "specifier": "utils",
+            "chosen": "external",
+            "local_candidate": "src/utils/index.js",
+            "external_candidate": "utils",
+            "rule": "bare specifiers resolve through node_modules, never sibling paths",
+        }
+    ]
+
+
+@pytest.mark.unit
+def test_rust_2015_edition_paths_start_at_the_crate_root(tmp_path):
+    files = {
+        "src/lib.rs": ("rust", "mod log;\nmod net;\n"),
+        "src/log.rs": ("rust", "pub fn info() {}\n"),
+        "src/net/mod.rs": ("rust", "use log::info;\nuse serde::Serialize;\n"),
+        "src/serde.rs": ("rust", ""),
+    }
+    external = {name: {"ecosystem": "cargo", "import_names": [name]} for name in ("log", "serde")}
+    resolver = _resolver(
+        tmp_path, files, external, rust_crate_roots=["src/lib.rs"], rust_crate_editions={".": "2015"}
+    )
+
+    # A nested file's `use log` names the crate root's `mod log`, which shadows the extern crate
+    assert resolver.resolve_rust("src/net/mod.rs", ["log", "info"]) == "src/log.rs"
+    assert resolver.resolve_rust("src/net/mod.rs", ["serde", "Serialize"]) is None
+    assert [d["chosen"] for d in resolver.disambiguations] == ["local", "external"]
+
+
+@pytest.mark.unit
+def test_js_undeclared_bare_specifier_records_no_disambiguation(tmp_path):
+    files = {
+        "src/app.js": ("javascript", "import utils from 'utils'\n"),
+        "src/utils/index.js": ("javascript", "export default {}\n"),
+    }
+    resolver = _resolver(tmp_path, files, {})
+
+    assert resolver.resolve_js("src/app.js", "utils") is None
+    assert resolver.disambiguations == []
+
+
+@pytest.mark.unit
+def test_js_base_url_prefers_local_module(tmp_path):
+    files = {
+        "src/app.ts": ("typescript", "import utils from 'utils'\n"),
+        "src/utils/index.ts": ("typescript", "export default {}\n"),
+    }
+    resolver = _resolver(tmp_path, files, {"utils": {"ecosystem": "npm"}}, js_ts_base_url="src")
+
+    assert resolver.resolve_js("src/app.ts", "utils") == "src/utils/index.ts"
+    assert resolver.disambiguations[0]["chosen"] == "local"
+
+
+@pytest.mark.unit
+def test_python_script_directory_shadows_package(tmp_path):
+    files = {
+        "service/app.py": ("python", "import utils\n"),
+        "service/utils.py": ("python", "def helper():\n    pass\n"),
+    }
+    resolver = _resolver(tmp_path, files, {"utils": {"ecosystem": "pypi", "import_names": ["utils"]}})
+
+    assert resolver.resolve_python("service/app.py", "utils", 0) == "service/utils.py"
+    assert resolver.disambiguations[0]["chosen"] == "local"
+
+
+@pytest.mark.unit
+def test_python_package_directory_does_not_shadow(tmp_path):
+    files = {
+        "pkg/__init__.py": ("python", ""),
+        "pkg/app.py": ("python", "import utils\n"),
+        "pkg/utils.py": ("python", ""),
+    }
+    resolver = _resolver(tmp_path, files, {"utils": {"ecosystem": "pypi", "import_names": ["utils"]}})
+
+    assert resolver.resolve_python("pkg/app.py", "utils", 0) is None
+    assert resolver.disambiguations == []
+
+
+@pytest.mark.unit
+def test_rust_path_attribute_modules_resolve_to_the_relocated_file(tmp_path):
+    files = {
+        "src/lib.rs": (
+            "rust",
+            '#[cfg(unix)]\n#[path = "../platform/unix.rs"]\nmod sys;\nuse sys::fd::Fd;\npub struct Config;\n',
+        ),
+        "platform/unix.rs": ("rust", "mod fd;\nuse crate::config::Config;\nuse super::Config;\n"),
+        "platform/fd.rs": ("rust", "pub struct Fd;\n"),
+        "src/config.rs": ("rust", "pub struct Config;\n"),
+        "platform/config.rs": ("rust", ""),
+    }
+    resolver = _resolver(tmp_path, files, {"sys": {"ecosystem": "cargo", "import_names": ["sys"]}})
+
+    assert resolver.resolve_rust("src/lib.rs", ["sys"], module_path="../platform/unix.rs") == "platform/unix.rs"
+    assert resolver.resolve_rust("src/lib.rs", ["sys", "fd", "Fd"]) == "platform/fd.rs"
+    assert resolver.resolve_rust("src/lib.rs", ["crate", "sys"]) == "platform/unix.rs"
+    assert resolver.resolve_rust("platform/unix.rs", ["fd"]) == "platform/fd.rs"
+    assert resolver.resolve_rust("platform/unix.rs", ["crate", "config", "Config"]) == "src/config.rs"
+    assert resolver.resolve_rust("platform/unix.rs", ["super", "Config"]) == "src/lib.rs"
+
+
+@pytest.mark.unit
+def test_rust_module_scan_reads_through_secure_file_ops_within_the_size_limit(tmp_path, monkeypatch):
+    files = {
+        "src/lib.rs": ("rust", '#[path = "../platform/unix.rs"]\nmod sys;\n'),
+        "platform/unix.rs": ("rust", "pub struct Fd;\n"),
+    }
+    resolver = _resolver(tmp_path, files, {}, secure_file_ops=SecureFileOps(str(tmp_path)))
+    assert resolver.resolve_rust("src/lib.rs", ["crate", "sys"]) == "platform/unix.rs"
+
+    monkeypatch.setattr(ResourceLimits, "MAX_FILE_SIZE", 16)
+    oversized = _resolver(tmp_path, files, {}, secure_file_ops=SecureFileOps(str(tmp_path)))
+    assert oversized.resolve_rust("src/lib.rs", ["crate", "sys"]) is None
+
+
+@pytest.mark.unit
+def test_rust_mod_declarations_are_not_read_through_symlinks_leaving_the_repository(tmp_path):
+    outside = tmp_path / "outside.rs"
+    outside.write_text("use log::info;\n")
+    repo = tmp_path / "repo"
+    _write(repo, "src/log.rs", "pub fn info() {}\n")
+    (repo / "src" / "main.rs").symlink_to(outside)
+    source_files = {
+        "src/main.rs": {"absolute_path": str(repo / "src" / "main.rs"), "language": "rust"},
+        "src/log.rs": {"absolute_path": str(repo / "src" / "log.rs"), "language": "rust"},
+    }
+    resolver = LocalImportResolver(
+        repo_path=str(repo),
+        source_files=source_files,
+        alias_resolver=None,
+        js_ts_base_url=None,
+        js_ts_path_aliases=None,
+        go_module_path=None,
+        remappings=None,
+        hardhat_remappings=None,
+        solidity_src_path=None,
+        logger=None,
+        external_packages={"log": {"ecosystem": "cargo", "import_names": ["log"]}},
+        secure_file_ops=SecureFileOps(str(repo)),
+    )
+
+    # The outside file declares no `mod log`, but its contents are never read: declarations are unknown
+    assert resolver.resolve_rust("src/main.rs", ["log", "info"]) == "src/log.rs"
+    assert resolver.disambiguations == []
@@ -0,0 +1,60 @@
+"""
+Shared dependencies across repositories
+"""
+
+import json
+
+import pytest
+
+from gardener.analysis.intersect import intersect_reports, main
+
+
+def _report(*deps):
+    return {
+        "top_dependencies": [
+            {"package_name": name, "ecosystem": ecosystem, "percentage": share, "package_url": url}
+            for name, ecosystem, share, url in deps
+        ]
+    }
+
+
+@pytest.mark.unit
+def test_shared_packages_are_ranked_by_reach_then_combined_share():
+    reports = {
+        "a.json": _report(
+            ("serde", "cargo", 60, "https://github.com/serde-rs/serde"),
+            ("serde_derive", "cargo", 10, "https://github.com/serde-rs/serde"),
+            ("tokio", "cargo", 30, ""),
+        ),
+        "b.json": _report(("serde", "cargo", 20, "https://github.com/serde-rs/serde"), ("tokio", "cargo", 80, "")),
+        "c.json": _report(("serde", "cargo", 40, "https://github.com/serde-rs/serde"), ("log", "cargo", 60, "")),
+    }
+
+    result = intersect_reports(reports)
+
+    assert [entry["key"] for entry in result["shared"]] == ["cargo:serde", "cargo:tokio"]
+    assert result["shared"][0]["repositories"] == 3
+    assert result["shared"][0]["combined_percentage"] == pytest.approx(40)
+    assert result["shared"][1]["shares"] == {"a.json": 30, "b.json": 80}
+    assert result["shared"][1]["combined_percentage"] == pytest.approx(110 / 3)
+
+    by_repo = intersect_reports(reports, by="repository")
+    assert by_repo["shared"][0]["packages"] == ["serde", "serde_derive"]
+    assert by_repo["shared"][0]["shares"]["a.json"] == 70
+    assert by_repo["unmatched"] == 3
+
+
+@pytest.mark.unit
+def test_intersect_cli_writes_the_comparison(tmp_path):
+    paths = []
+    for index, deps in enumerate(([("react", "npm", 100, "")], [("react", "npm", 50, ""), ("vue", "npm", 50, "")])):
+        path = tmp_path / f"r{index}.json"
+        path.write_text(json.dumps(_report(*deps)))
+        paths.append(str(path))
+    output = tmp_path / "shared.json"
+
+    assert main(paths + ["-o", str(output)]) == 0
+    shared = json.loads(output.read_text())["shared"]
+    assert [(entry["key"], entry["combined_percentage"]) for entry in shared] == [("npm:react", 75)]
+    (tmp_path / "bad.json").write_text("{}")
+    assert main([paths[0], str(tmp_path / "bad.json")]) == 1
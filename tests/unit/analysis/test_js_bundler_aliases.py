@@ -0,0 +1,103 @@
+"""
+webpack, Vite and Rollup alias maps
+"""
+
+import pytest
+
+from gardener.analysis.imports import LocalImportResolver
+from gardener.analysis.js_bundler_aliases import (
+    apply_bundler_aliases,
+    collect_bundler_aliases,
+    is_bundler_config,
+    parse_bundler_aliases,
+)
+from gardener.analysis.js_ts_aliases import create_alias_resolver
+
+WEBPACK_CONFIG = """
+const path = require('path');
+
+module.exports = {
+  resolve: {
+    alias: {
+      // local sources
+      '@': path.resolve(__dirname, 'src'),
+      Utilities: path.join(__dirname, './src/utilities/'),
+      'config$': './src/config.js',
+      react: 'preact/compat',
+      'lodash-es': path.resolve(__dirname, 'node_modules/lodash'),
+      env: process.env.NODE_ENV === 'production' ? './prod' : './dev',
+      ignored: false,
+    },
+  },
+};
+"""
+
+VITE_CONFIG = """
+import { fileURLToPath, URL } from 'node:url'
+import { defineConfig } from 'vite'
+
+export default defineConfig({
+  resolve: {
+    alias: [
+      { find: '~', replacement: fileURLToPath(new URL('./app', import.meta.url)) },
+      { find: /^@\\/(.*)/, replacement: '/src/$1' },
+      { find: 'vue', replacement: 'vue/dist/vue.esm-bundler.js' },
+    ],
+  },
+})
+"""
+
+
+@pytest.mark.unit
+def test_static_alias_entries_are_read_and_dynamic_ones_skipped():
+    assert [is_bundler_config(name) for name in ("vite.config.ts", "webpack.config.prod.js", "vite.ts")] == [
+        True,
+        True,
+        False,
+    ]
+
+    assert parse_bundler_aliases(WEBPACK_CONFIG, "web/webpack.config.js") == [
+        {"config": "web/webpack.config.js", "alias": "@", "exact": False, "target": "web/src"},
+        {"config": "web/webpack.config.js", "alias": "Utilities", "exact": False, "target": "web/src/utilities"},
+        {"config": "web/webpack.config.js", "alias": "config", "exact": True, "target": "web/src/config.js"},
+        {"config": "web/webpack.config.js", "alias": "react", "exact": False, "package": "preact"},
+        {"config": "web/webpack.config.js", "alias": "lodash-es", "exact": False, "package": "lodash"},
+    ]
+    assert parse_bundler_aliases(VITE_CONFIG, "vite.config.ts") == [
+        {"config": "vite.config.ts", "alias": "~", "exact": False, "target": "app"},
+        {"config": "vite.config.ts", "alias": "vue", "exact": False, "package": "vue"},
+    ]
+    rollup = "export default { plugins: [alias({ entries: { '@lib': path.resolve(__dirname, 'lib') } })] }"
+    assert parse_bundler_aliases(rollup, "rollup.config.mjs")[0]["target"] == "lib"
+
+
+@pytest.mark.unit
+def test_bundler_aliases_resolve_to_local_files_and_packages(tmp_path):
+    (tmp_path / "web/src/components").mkdir(parents=True)
+    (tmp_path / "web/webpack.config.js").write_text(WEBPACK_CONFIG)
+    source_files = {
+        rel_path: {"absolute_path": str(tmp_path / rel_path), "language": "javascript"}
+        for rel_path in ("web/webpack.config.js", "web/src/components/Button.js", "web/src/config.js")
+    }
+    aliases = collect_bundler_aliases(str(tmp_path), source_files, None, None)
+    alias_resolver = create_alias_resolver(str(tmp_path), source_files, None, {}, None)
+    apply_bundler_aliases(alias_resolver.config, aliases)
+    resolver = LocalImportResolver(
+        repo_path=str(tmp_path),
+        source_files=source_files,
+        alias_resolver=alias_resolver,
+        js_ts_base_url=None,
+        js_ts_path_aliases={},
+        go_module_path=None,
+        remappings=None,
+        hardhat_remappings=None,
+        solidity_src_path=None,
+        logger=None,
+    )
+
+    assert resolver.resolve_js("web/src/main.js", "@/components/Button") == "web/src/components/Button.js"
+    assert resolver.resolve_js("web/src/main.js", "config") == "web/src/config.js"
+    assert resolver.resolve_js("web/src/main.js", "config/extra") is None
+    assert resolver.resolve_js("web/src/main.js", "react/hooks") == "__PACKAGE:preact"
+    assert resolver.resolve_js("web/src/main.js", "lodash-es") == "__PACKAGE:lodash"
+    assert resolver.resolve_js("web/src/main.js", "@scope/pkg") is None
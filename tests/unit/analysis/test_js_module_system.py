@@ -0,0 +1,51 @@
+"""
+ESM/CommonJS classification of JS/TS files
+"""
+
+import json
+
+import pytest
+
+from gardener.analysis.js_module_system import classify_module_systems
+
+
+@pytest.mark.unit
+def test_extension_package_type_and_syntax_decide_the_format(tmp_path):
+    (tmp_path / "package.json").write_text(json.dumps({"name": "app"}))
+    (tmp_path / "packages/esm").mkdir(parents=True)
+    (tmp_path / "packages/esm/package.json").write_text(json.dumps({"name": "esm", "type": "module"}))
+    module_systems = {
+        "packages/esm/index.js": {"syntax": "cjs", "loaded_by": {"debug": ["require"]}},
+        "packages/esm/legacy.cjs": {"syntax": "cjs", "loaded_by": {"debug": ["require"]}},
+        "src/server.ts": {"syntax": "mixed", "loaded_by": {"express": ["import"], "debug": ["require"]}},
+        "src/config.js": {"syntax": None, "loaded_by": {}},
+        "src/App.vue": {"syntax": None, "loaded_by": {"vue": ["import"]}},
+        "scripts/build.py": {"syntax": None, "loaded_by": {}},
+    }
+    source_files = {rel_path: {"language": "javascript"} for rel_path in module_systems}
+    source_files["src/server.ts"]["language"] = "typescript"
+    source_files["scripts/build.py"]["language"] = "python"
+    external_packages = {
+        "debug": {"ecosystem": "npm"},
+        "express": {"ecosystem": "npm"},
+        "vue": {"ecosystem": "npm"},
+    }
+
+    files = classify_module_systems(
+        source_files,
+        module_systems,
+        external_packages,
+        [str(tmp_path / "package.json"), str(tmp_path / "packages/esm/package.json")],
+        repo_path=str(tmp_path),
+    )
+
+    assert {rel_path: (record["format"], record["basis"]) for rel_path, record in files.items()} == {
+        "packages/esm/index.js": ("esm", "package.json"),
+        "packages/esm/legacy.cjs": ("cjs", "extension"),
+        "src/server.ts": ("esm", "syntax"),
+        "src/config.js": ("cjs", "default"),
+        "src/App.vue": ("esm", "extension"),
+    }
+    assert files["src/server.ts"]["imports"] == {"express": ["import"], "debug": ["require"]}
+    assert external_packages["debug"]["loaded_by"] == ["require"]
+    assert external_packages["express"]["loaded_by"] == ["import"]
@@ -0,0 +1,116 @@
+"""
+JavaScript/TypeScript barrel re-export chains
+"""
+
+import pytest
+
+from gardener.analysis.imports import LocalImportResolver
+from gardener.analysis.js_reexports import attribute_reexports, parse_imported_names, parse_reexports
+from gardener.treewalk.javascript import JavaScriptLanguageHandler
+
+FILES = {
+    "src/app.ts": (
+        "import { Button, debounce } from './lib';\n"
+        "import type { Theme } from './lib';\n"
+        "import * as ui from './ui';\n"
+    ),
+    "src/lib/index.ts": (
+        "// export { stale } from 'left-pad';\n"
+        "export * from './components';\n"
+        "export { debounce } from 'lodash-es';\n"
+        "export type { Theme } from '@emotion/react';\n"
+    ),
+    "src/lib/components/index.ts": "export * from './Button';\n",
+    "src/lib/components/Button.tsx": "export function Button() {}\n",
+    "src/ui.ts": "export * from 'chart.js';\n",
+}
+
+
+@pytest.mark.unit
+def test_export_from_and_import_clauses_are_parsed():
+    code = (
+        "export * from './a';\nexport * as ns from 'b';\n"
+        "export { c, d as e, type F } from \"./c\";\nexport const local = 1;\n"
+    )
+
+    assert parse_reexports(code) == {
+        "*": ["./a"],
+        "ns": ("b", "*"),
+        "c": ("./c", "c"),
+        "e": ("./c", "d"),
+        "F": ("./c", "F"),
+    }
+    imports = "import React, { useState as useS } from 'react';\nimport * as lib from './lib';\n"
+    assert parse_imported_names(imports) == [
+        ("react", "default", False),
+        ("react", "useState", False),
+        ("./lib", "*", False),
+    ]
+
+
+@pytest.mark.unit
+def test_barrel_imports_are_attributed_to_the_defining_module_or_package(tmp_path):
+    source_files = {}
+    for rel_path, content in FILES.items():
+        path = tmp_path / rel_path
+        path.parent.mkdir(parents=True, exist_ok=True)
+        path.write_text(content)
+        source_files[rel_path] = {"absolute_path": str(path), "language": "typescript"}
+    resolver = LocalImportResolver(
+        repo_path=str(tmp_path),
+        source_files=source_files,
+        alias_resolver=None,
+        js_ts_base_url=None,
+        js_ts_path_aliases={},
+        go_module_path=None,
+        remappings=None,
+        hardhat_remappings=None,
+        solidity_src_path=None,
+        logger=None,
+    )
+    file_imports = {"src/lib/index.ts": ["lodash-es", "@emotion/react"], "src/ui.ts": ["chart.js"]}
+    local_imports = {"src/app.ts": ["src/lib/index.ts", "src/ui.ts"]}
+    components, annotations = {}, {}
+
+    chains = attribute_reexports(
+        source_files,
+        file_imports,
+        local_imports,
+        components,
+        annotations,
+        resolver.resolve_js,
+        JavaScriptLanguageHandler().normalize_package_name,
+    )
+
+    assert chains == [
+        {
+            "file": "src/app.ts",
+            "import": "Button",
+            "from": "./lib",
+            "chain": ["src/lib/index.ts", "src/lib/components/index.ts", "src/lib/components/Button.tsx"],
+            "defined_in": "src/lib/components/Button.tsx",
+        },
+        {
+            "file": "src/app.ts",
+            "import": "debounce",
+            "from": "./lib",
+            "chain": ["src/lib/index.ts"],
+            "package": "lodash-es",
+        },
+        {
+            "file": "src/app.ts",
+            "import": "Theme",
+            "from": "./lib",
+            "chain": ["src/lib/index.ts"],
+            "package": "@emotion/react",
+        },
+        {"file": "src/app.ts", "import": "*", "from": "./ui", "chain": ["src/ui.ts"], "package": "chart.js"},
+    ]
+    assert local_imports["src/app.ts"][2:] == ["src/lib/components/Button.tsx"]
+    assert file_imports["src/app.ts"] == ["lodash-es", "@emotion/react", "chart.js"]
+    assert ("lodash-es", "lodash-es.debounce") in components["src/app.ts"]
+    assert annotations["src/app.ts"]["lodash-es"] == {"reexported_via": ["src/lib/index.ts"]}
+    assert annotations["src/app.ts"]["@emotion/react"] == {
+        "reexported_via": ["src/lib/index.ts"],
+        "evidence": "type-only",
+    }
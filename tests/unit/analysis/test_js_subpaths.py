@@ -0,0 +1,84 @@
+"""
+package.json `exports` and `imports` subpath maps
+"""
+
+import json
+
+import pytest
+
+from gardener.analysis.imports import LocalImportResolver
+from gardener.analysis.js_subpaths import PackageScopes, match_subpath
+from gardener.common.secure_file_ops import SecureFileOps
+
+MANIFEST = {
+    "name": "@acme/app",
+    "exports": {".": "./dist/index.js", "./utils": {"types": "./dist/utils.d.ts", "import": "./dist/utils.js"}},
+    "imports": {
+        "#db": {"node": "pg-native", "default": "./src/db/browser.js"},
+        "#internal/*": "./src/internal/*.js",
+        "#config": "./config.json",
+    },
+}
+
+
+@pytest.mark.unit
+def test_subpath_maps_follow_conditions_and_the_longest_pattern():
+    mapping = {
+        "./features/*": "./src/features/*.js",
+        "./features/private/*": None,
+        "./legacy/": "./old/",
+        "./node": {"browser": "./shim.js", "node": ["./node.js"]},
+    }
+
+    assert match_subpath(mapping, "./features/auth") == "./src/features/auth.js"
+    assert match_subpath(mapping, "./features/private/keys") is None
+    assert match_subpath(mapping, "./legacy/a/b.js") == "./old/a/b.js"
+    assert match_subpath(mapping, "./node") == "./node.js"
+    assert match_subpath(mapping, "./missing") is None
+
+
+@pytest.mark.unit
+def test_hash_imports_and_self_references_resolve_to_files_or_packages(tmp_path):
+    files = ["src/main.ts", "src/internal/cache.ts", "src/utils.ts", "config.json"]
+    (tmp_path / "package.json").write_text(json.dumps(MANIFEST))
+    for rel_path in files:
+        (tmp_path / rel_path).parent.mkdir(parents=True, exist_ok=True)
+        (tmp_path / rel_path).write_text("")
+    source_files = {rel_path: {"absolute_path": str(tmp_path / rel_path)} for rel_path in files}
+    resolver = LocalImportResolver(
+        repo_path=str(tmp_path),
+        source_files=source_files,
+        alias_resolver=None,
+        js_ts_base_url=None,
+        js_ts_path_aliases=None,
+        go_module_path=None,
+        remappings=None,
+        hardhat_remappings=None,
+        solidity_src_path=None,
+        logger=None,
+    )
+
+    assert resolver.resolve_js("src/main.ts", "#internal/cache") == "src/internal/cache.ts"
+    assert resolver.resolve_js("src/main.ts", "#db") == "__PACKAGE:pg-native"
+    assert resolver.resolve_js("src/main.ts", "#config") == "config.json"
+    assert resolver.resolve_js("src/main.ts", "#missing") is None
+    # The export targets build output; without dist/ the self-reference stays unresolved
+    assert resolver.resolve_js("src/main.ts", "@acme/app/utils") is None
+    (tmp_path / "dist").mkdir()
+    (tmp_path / "dist/utils.js").write_text("")
+    source_files["dist/utils.js"] = {"absolute_path": str(tmp_path / "dist/utils.js")}
+    assert resolver.resolve_js("src/main.ts", "@acme/app/utils") == "dist/utils.js"
+
+
+@pytest.mark.unit
+def test_package_manifests_outside_the_repository_are_not_read(tmp_path):
+    repo, outside = tmp_path / "repo", tmp_path / "outside"
+    (repo / "pkg").mkdir(parents=True)
+    outside.mkdir()
+    (outside / "package.json").write_text(json.dumps(MANIFEST))
+    (repo / "pkg/package.json").symlink_to(outside / "package.json")
+    (repo / "package.json").write_text(json.dumps({"name": "root", "imports": {"#config": "./config.json"}}))
+
+    scopes = PackageScopes(str(repo), secure_file_ops=SecureFileOps(str(repo)))
+
+    assert scopes.nearest("pkg/index.js") == (".", {"name": "root", "imports": {"#config": "./config.json"}})
@@ -0,0 +1,68 @@
+"""
+tsconfig `extends` chains and paths aliases
+"""
+
+import json
+
+import pytest
+
+from gardener.analysis.imports import LocalImportResolver
+from gardener.analysis.js_ts_aliases import create_alias_resolver, parse_ts_js_config
+
+
+def _write(tmp_path, files):
+    for rel_path, content in files.items():
+        path = tmp_path / rel_path
+        path.parent.mkdir(parents=True, exist_ok=True)
+        path.write_text(content if isinstance(content, str) else json.dumps(content))
+
+
+@pytest.mark.unit
+def test_extends_chains_are_followed_and_rebased_onto_the_repository_root(tmp_path):
+    _write(
+        tmp_path,
+        {
+            "tsconfig.json": '{\n  // app config\n  "extends": ["@tsconfig/strictest", "./configs/paths"],\n}\n',
+            "configs/paths.json": {"extends": "./base.json", "compilerOptions": {"paths": {"@lib/*": ["../lib/*"]}}},
+            "configs/base.json": {"compilerOptions": {"paths": {"@old/*": ["old/*"]}}},
+        },
+    )
+
+    base_url, paths = parse_ts_js_config(str(tmp_path), [], [str(tmp_path / "tsconfig.json")], None, None)
+
+    assert base_url is None
+    assert paths == {"@lib/*": ["lib/*"]}
+
+    _write(tmp_path, {"configs/base.json": {"compilerOptions": {"baseUrl": "..", "paths": {"@app/*": ["src/*"]}}}})
+    _write(tmp_path, {"configs/paths.json": {"extends": "./base.json"}})
+
+    assert parse_ts_js_config(str(tmp_path), [], [str(tmp_path / "tsconfig.json")], None, None) == (
+        ".",
+        {"@app/*": ["src/*"]},
+    )
+
+
+@pytest.mark.unit
+def test_paths_aliases_classify_internal_files_and_package_aliases(tmp_path):
+    _write(tmp_path, {"src/utils.ts": "export const x = 1\n", "src/main.ts": "import { x } from '@app/utils'\n"})
+    source_files = {
+        rel_path: {"absolute_path": str(tmp_path / rel_path), "language": "typescript"}
+        for rel_path in ("src/utils.ts", "src/main.ts")
+    }
+    paths = {"@app/*": ["src/*"], "react": ["./node_modules/preact/compat"]}
+    resolver = LocalImportResolver(
+        repo_path=str(tmp_path),
+        source_files=source_files,
+        alias_resolver=create_alias_resolver(str(tmp_path), source_files, ".", paths, None),
+        js_ts_base_url=".",
+        js_ts_path_aliases=paths,
+        go_module_path=None,
+        remappings=None,
+        hardhat_remappings=None,
+        solidity_src_path=None,
+        logger=None,
+    )
+
+    assert resolver.resolve_js("src/main.ts", "@app/utils") == "src/utils.ts"
+    assert resolver.resolve_js("src/main.ts", "react") == "__PACKAGE:preact"
+    assert resolver.resolve_js("src/main.ts", "react-dom") is None
@@ -0,0 +1,75 @@
+"""
+npm, Yarn and pnpm workspace members
+"""
+
+import json
+
+import pytest
+
+from gardener.analysis.imports import LocalImportResolver
+from gardener.analysis.js_workspace import collect_js_workspace_members
+
+FILES = {
+    "package.json": json.dumps({"name": "acme", "workspaces": {"packages": ["packages/*", "!packages/legacy"]}}),
+    "pnpm-workspace.yaml": "packages:\n  - 'apps/**'\n",
+    "packages/ui/package.json": json.dumps({"name": "@acme/ui", "main": "dist/index.js"}),
+    "packages/ui/src/index.ts": "export * from './button'\n",
+    "packages/ui/src/button.tsx": "export const Button = () => null\n",
+    "packages/legacy/package.json": json.dumps({"name": "@acme/legacy"}),
+    "apps/web/admin/package.json": json.dumps({"name": "admin", "dependencies": {"@acme/ui": "^1.0.0"}}),
+    "apps/web/admin/src/app.ts": "import { Button } from '@acme/ui/button'\n",
+    "tools/gen/package.json": json.dumps({"name": "gen"}),
+}
+
+
+def _write(tmp_path):
+    for rel_path, content in FILES.items():
+        path = tmp_path / rel_path
+        path.parent.mkdir(parents=True, exist_ok=True)
+        path.write_text(content)
+    return [str(tmp_path / rel_path) for rel_path in FILES if rel_path.endswith("package.json")]
+
+
+@pytest.mark.unit
+def test_workspace_globs_and_pnpm_workspace_select_members(tmp_path):
+    members = collect_js_workspace_members(_write(tmp_path), None, None, str(tmp_path))
+
+    assert sorted(members) == ["@acme/ui", "admin"]
+    assert members["@acme/ui"] == {
+        "annotation": "workspace-member",
+        "ecosystem": "npm",
+        "manifest": "packages/ui/package.json",
+        "path": "packages/ui",
+        "import_names": ["@acme/ui"],
+        "via": ["workspaces"],
+        "dependencies": {},
+    }
+    assert members["admin"]["via"] == ["pnpm-workspace"]
+    assert members["admin"]["dependencies"] == {"dependencies": ["@acme/ui"]}
+
+
+@pytest.mark.unit
+def test_imports_of_sibling_packages_resolve_to_their_sources(tmp_path):
+    _write(tmp_path)
+    source_files = {
+        rel_path: {"absolute_path": str(tmp_path / rel_path), "language": "typescript"}
+        for rel_path in FILES
+        if rel_path.endswith((".ts", ".tsx"))
+    }
+    resolver = LocalImportResolver(
+        repo_path=str(tmp_path),
+        source_files=source_files,
+        alias_resolver=None,
+        js_ts_base_url=None,
+        js_ts_path_aliases=None,
+        go_module_path=None,
+        remappings=None,
+        hardhat_remappings=None,
+        solidity_src_path=None,
+        logger=None,
+        js_workspace_packages={"@acme/ui": "packages/ui", "admin": "apps/web/admin"},
+    )
+
+    assert resolver.resolve_js("apps/web/admin/src/app.ts", "@acme/ui") == "packages/ui/src/index.ts"
+    assert resolver.resolve_js("apps/web/admin/src/app.ts", "@acme/ui/button") == "packages/ui/src/button.tsx"
+    assert resolver.resolve_js("apps/web/admin/src/app.ts", "@acme/legacy") is None
@@ -0,0 +1,65 @@
+"""
+Robust manifest parsing
+"""
+
+import pytest
+
+from gardener.analysis.main import DependencyAnalyzer
+from gardener.analysis.manifest_check import ManifestError, check_manifest_content
+from gardener.common.defaults import ConfigOverride
+
+
+def _malformed_repo(tmp_path):
+    (tmp_path / "go.mod").write_text("module example.com/app\n\nrequire github.com/pkg/errors v0.9.1\n")
+    (tmp_path / "package.json").write_text('{"name": "app", "dependencies": {"react": "^18')
+    (tmp_path / "crate").mkdir()
+    (tmp_path / "crate" / "Cargo.toml").write_text('[package]\nname = "c"\n[dependencies\nserde = "1"\n')
+    (tmp_path / "Chart.yaml").write_text("apiVersion: v2\ndependencies:\n  - name: redis\n   version: [1\n")
+    (tmp_path / "requirements-dev.txt").write_bytes("pytest\n".encode("utf-16"))
+    return str(tmp_path)
+
+
+@pytest.mark.unit
+def test_malformed_manifests_are_skipped_with_diagnostics(tmp_path):
+    analyzer = DependencyAnalyzer(verbose=False)
+    packages = analyzer.discover_packages(_malformed_repo(tmp_path))
+
+    assert list(packages) == ["github.com/pkg/errors"]
+    diagnostics = {d["file"]: d for d in analyzer.repo_analyzer.manifest_diagnostics}
+    assert sorted(diagnostics) == ["Chart.yaml", "crate/Cargo.toml", "package.json", "requirements-dev.txt"]
+    assert diagnostics["package.json"]["format"] == "json"
+    assert diagnostics["crate/Cargo.toml"]["format"] == "toml"
+    assert diagnostics["crate/Cargo.toml"]["line"] == 3
+    assert diagnostics["Chart.yaml"]["format"] == "yaml"
+    assert diagnostics["requirements-dev.txt"]["format"] == "encoding"
+    assert analyzer.repo_analyzer.panics.summary()["counts"] == {}
+
+
+@pytest.mark.unit
+def test_strict_manifests_fail_on_the_first_malformed_manifest(tmp_path):
+    repo = _malformed_repo(tmp_path)
+
+    with ConfigOverride({"STRICT_MANIFESTS": True}), pytest.raises(ManifestError) as excinfo:
+        DependencyAnalyzer(verbose=False).discover_packages(repo)
+
+    malformed = ("Chart.yaml", "crate/Cargo.toml", "package.json", "requirements-dev.txt")
+    assert excinfo.value.diagnostic["file"] in malformed
+
+
+@pytest.mark.unit
+def test_diagnostics_locate_the_error():
+    assert check_manifest_content("package.json", b'{\n  "name": "app",\n  "version": }') == {
+        "file": "package.json",
+        "format": "json",
+        "error": "Expecting value",
+        "line": 3,
+        "column": 14,
+    }
+    assert check_manifest_content("go.mod", b"module example.com/m\n\xff\xfe") == {
+        "file": "go.mod",
+        "format": "encoding",
+        "error": "invalid UTF-8 byte 0xff",
+        "line": 2,
+        "column": 1,
+    }
+    assert check_manifest_content("Cargo.lock", b"version = 3\n") is None
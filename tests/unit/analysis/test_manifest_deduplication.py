@@ -193,3 +193,83 @@ def test_get_conflict_summary():
 
         # Check react has no conflicts (only in one manifest)
         assert "react" not in conflicts
+
+
+@pytest.mark.unit
+def test_npm_dependency_kinds_are_reported_per_package_manifest_and_workspace_member(tmp_path):
+    (tmp_path / "package.json").write_text(
+        json.dumps({"name": "app", "workspaces": ["packages/*"], "dependencies": {"react": "^18.2.0"}})
+    )
+    (tmp_path / "packages/lib").mkdir(parents=True)
+    (tmp_path / "packages/lib/package.json").write_text(
+        json.dumps(
+            {
+                "name": "lib",
+                "peerDependencies": {"react": ">=17"},
+                "devDependencies": {"react": "^18.2.0", "jest": "^29.0.0"},
+                "optionalDependencies": {"fsevents": "^2.3.0"},
+            }
+        )
+    )
+    analyzer = RepositoryAnalyzer(str(tmp_path))
+    analyzer.register_language_handler("javascript", JavaScriptLanguageHandler())
+    analyzer.scan_repo()
+    packages = analyzer.process_manifest_files()
+
+    assert packages["react"]["dep_kind"] == "dependencies"
+    assert packages["react"]["manifest_dep_kinds"] == {
+        "package.json": "dependencies",
+        "packages/lib/package.json": "peerDependencies",
+    }
+    assert packages["jest"]["dep_kind"] == "devDependencies"
+    assert packages["fsevents"]["dep_kind"] == "optionalDependencies"
+    assert analyzer.workspace_members["lib"]["dependencies"] == {
+        "optionalDependencies": ["fsevents"],
+        "peerDependencies": ["react"],
+        "devDependencies": ["jest", "react"],
+    }
+
+
+@pytest.mark.unit
+def test_locked_optional_cargo_dependency_keeps_its_features(tmp_path):
+    from gardener.treewalk.rust import RustLanguageHandler
+
+    (tmp_path / "Cargo.toml").write_text(
+        '[package]\nname = "app"\nversion = "0.1.0"\n\n'
+        '[dependencies]\nserde = { version = "1", optional = true }\n\n'
+        '[features]\ndefault = ["serde"]\n'
+    )
+    (tmp_path / "Cargo.lock").write_text(
+        "version = 3\n\n"
+        '[[package]]\nname = "app"\nversion = "0.1.0"\ndependencies = ["serde"]\n\n'
+        '[[package]]\nname = "serde"\nversion = "1.0.190"\n'
+        'source = "registry+https://github.com/rust-lang/crates.io-index"\nchecksum = "abc"\n'
+    )
+    analyzer = RepositoryAnalyzer(str(tmp_path))
+    analyzer.register_language_handler("rust", RustLanguageHandler())
+    analyzer.scan_repo()
+    packages = analyzer.process_manifest_files()
+
+    assert packages["serde"]["optional"] is True
+    assert packages["serde"]["optional_features"] == ["default", "serde"]
+    assert packages["serde"]["default_enabled"] is True
+    assert packages["serde"]["locked_version"] == "1.0.190"
+    assert packages["serde"]["checksum"] == "abc"
+
+
+@pytest.mark.unit
+def test_module_required_directly_by_any_go_mod_is_direct(tmp_path):
+    from gardener.treewalk.go import GoLanguageHandler
+
+    (tmp_path / "go.mod").write_text("module example.com/app\n\nrequire github.com/pkg/errors v0.9.1 // indirect\n")
+    (tmp_path / "go.sum").write_text("github.com/pkg/errors v0.9.1 h1:errors=\n")
+    (tmp_path / "tools").mkdir()
+    (tmp_path / "tools/go.mod").write_text("module example.com/app/tools\n\nrequire github.com/pkg/errors v0.9.1\n")
+    (tmp_path / "tools/go.sum").write_text("github.com/pkg/errors v0.9.1 h1:errors=\n")
+    analyzer = RepositoryAnalyzer(str(tmp_path))
+    analyzer.register_language_handler("go", GoLanguageHandler())
+    analyzer.scan_repo()
+    packages = analyzer.process_manifest_files()
+
+    assert packages["github.com/pkg/errors"]["direct"] is True
+    assert "transitive" not in packages["github.com/pkg/errors"]
@@ -0,0 +1,47 @@
+"""
+Minified and bundled JavaScript is left out of import extraction
+"""
+
+import pytest
+
+from gardener.analysis.main import DependencyAnalyzer
+from gardener.analysis.minified import minified_reason
+from gardener.treewalk.javascript import JavaScriptLanguageHandler
+
+HAND_WRITTEN = "import express from 'express';\n\nconst app = express();\napp.listen(3000);\n"
+MANGLED = "!function(e,t){var n=t(e),r=n.a,o=n.b;e.x=function(i,a){return r(i)+o(a)}}(this,require);\n" * 40
+
+
+@pytest.mark.unit
+def test_names_source_maps_long_lines_and_short_identifiers_mark_minified_code():
+    assert minified_reason("vendor/jquery.min.js", HAND_WRITTEN) == "min-name"
+    assert minified_reason("dist/index.js", HAND_WRITTEN + "//# sourceMappingURL=index.js.map\n") == "source-map"
+    assert minified_reason("dist/chunk.js", "var a=" + "1+" * 3000 + "1;") == "long-line"
+    assert minified_reason("dist/vendor.js", MANGLED) == "short-identifiers"
+    assert minified_reason("src/server.js", HAND_WRITTEN) is None
+    assert minified_reason("src/admin.js", HAND_WRITTEN * 100) is None
+
+
+@pytest.mark.unit
+def test_minified_files_are_skipped_and_reported(tmp_path, monkeypatch):
+    (tmp_path / "package.json").write_text('{"name": "app", "dependencies": {"express": "^4.19.0"}}')
+    (tmp_path / "server.js").write_text(HAND_WRITTEN)
+    (tmp_path / "public").mkdir()
+    (tmp_path / "public/vue.min.js").write_text("require('vue')")
+    (tmp_path / "public/app.js").write_text("require('react');\n//# sourceMappingURL=app.js.map\n")
+
+    def extract_imports(self, tree_node, rel_path, file_components_dict, *args, **kwargs):
+        return ["express"], []
+
+    monkeypatch.setattr(JavaScriptLanguageHandler, "parse_source", lambda self, code, rel_path: object())
+    monkeypatch.setattr(JavaScriptLanguageHandler, "extract_imports", extract_imports)
+
+    analyzer = DependencyAnalyzer(verbose=False)
+    analyzer.discover_packages(str(tmp_path))
+    analyzer.repo_analyzer.extract_imports_from_all_files()
+
+    assert dict(analyzer.repo_analyzer.file_imports) == {"server.js": ["express"]}
+    assert analyzer.repo_analyzer.skipped_files == [
+        {"file": "public/app.js", "reason": "source-map"},
+        {"file": "public/vue.min.js", "reason": "min-name"},
+    ]
@@ -0,0 +1,82 @@
+"""
+Language-weighted normalization of the funding split
+"""
+
+import pytest
+
+from gardener.analysis.bundle import request_hook
+from gardener.analysis.main import DependencyAnalyzer
+from gardener.analysis.normalization import language_sizes, normalize_by_language, package_languages
+from gardener.common.defaults import ConfigOverride
+from gardener.treewalk.go import GoLanguageHandler
+from gardener.treewalk.rust import RustLanguageHandler
+
+
+@pytest.mark.unit
+def test_language_groups_get_their_share_of_the_codebase():
+    source_files = {
+        "a.js": {"language": "javascript"},
+        "b.py": {"language": "python"},
+        "c.py": {"language": "python"},
+        "d.py": {"language": "python"},
+    }
+    packages = {"left-pad": {}, "lodash": {}, "requests": {}, "orphan": {}}
+    file_imports = {"a.js": ["left-pad", "lodash"], "b.py": ["requests", "lodash"], "c.py": ["requests"]}
+
+    languages = package_languages(packages, file_imports, source_files)
+    assert languages == {"left-pad": "javascript", "lodash": "javascript", "requests": "python"}
+
+    sizes = language_sizes(source_files, "files")
+    rescaled, details = normalize_by_language(
+        [("left-pad", 0.4), ("lodash", 0.4), ("requests", 0.1), ("orphan", 0.1)], languages, sizes
+    )
+
+    scores = dict(rescaled)
+    assert scores["orphan"] == pytest.approx(0.1)
+    assert scores["requests"] == pytest.approx(0.9 * 3 / 4)
+    assert scores["left-pad"] == scores["lodash"] == pytest.approx(0.9 / 4 / 2)
+    assert [name for name, _ in rescaled][0] == "requests"
+    assert details["python"] == {"size": 3, "raw_share": pytest.approx(10.0), "share": pytest.approx(67.5)}
+    assert language_sizes({"x.py": {"language": "python"}}, "loc", lambda path: "import os\n\nprint(1)\n") == {
+        "python": 2
+    }
+
+
+@pytest.mark.unit
+def test_zero_score_language_groups_keep_a_zero_share():
+    languages = {"lodash": "javascript", "requests": "python", "unused": "go"}
+    sizes = {"javascript": 1, "python": 3, "go": 4}
+
+    rescaled, details = normalize_by_language([("lodash", 0.5), ("requests", 0.5), ("unused", 0.0)], languages, sizes)
+
+    assert dict(rescaled) == {"requests": pytest.approx(0.75), "lodash": pytest.approx(0.25), "unused": 0.0}
+    assert details["go"] == {"size": 4, "raw_share": 0.0, "share": 0.0}
+    assert sum(detail["share"] for detail in details.values()) == pytest.approx(100)
+
+@pytest.mark.unit
+def test_language_normalization_mode_is_recorded_in_results(tmp_path, monkeypatch):
+    (tmp_path / "go.mod").write_text("module example.com/app\n\ngo 1.21\n\nrequire github.com/spf13/cobra v1.8.0\n")
+    (tmp_path / "Cargo.toml").write_text('[package]\nname = "app"\n\n[dependencies]\nserde = "1"\n')
+    (tmp_path / "main.go").write_text("package main\n")
+    for name in ("a.rs", "b.rs", "c.rs"):
+        (tmp_path / name).write_text("use serde::Serialize;\n")
+
+    monkeypatch.setattr(GoLanguageHandler, "parse_source", lambda self, code, rel_path: object())
+    monkeypatch.setattr(
+        GoLanguageHandler, "extract_imports", lambda self, tree, rel_path, *a, **k: (["github.com/spf13/cobra"], [])
+    )
+    monkeypatch.setattr(RustLanguageHandler, "parse_source", lambda self, code, rel_path: object())
+    monkeypatch.setattr(RustLanguageHandler, "extract_imports", lambda self, tree, rel_path, *a, **k: (["serde"], []))
+
+    with request_hook(lambda url: None), ConfigOverride({"WEIGHT_NORMALIZATION": "language-files"}):
+        results = DependencyAnalyzer(verbose=False).analyze(str(tmp_path))
+
+    shares = {dep["package_name"]: dep["percentage"] for dep in results["top_dependencies"]}
+    assert shares["serde"] == pytest.approx(75)
+    assert shares["github.com/spf13/cobra"] == pytest.approx(25)
+    assert {dep["package_name"]: dep["language"] for dep in results["top_dependencies"]} == {
+        "serde": "rust",
+        "github.com/spf13/cobra": "go",
+    }
+    assert results["analyzer_details"]["normalization"]["mode"] == "language-files"
+    assert results["analyzer_details"]["normalization"]["languages"]["go"]["size"] == 1
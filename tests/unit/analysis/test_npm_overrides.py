@@ -0,0 +1,94 @@
+"""
+pnpm catalogs and npm/Yarn/pnpm version overrides
+"""
+
+import json
+
+import pytest
+
+from gardener.analysis.npm_overrides import apply_npm_overrides, catalog_version
+from gardener.treewalk.javascript import JavaScriptLanguageHandler
+
+PNPM_WORKSPACE = """
+packages:
+  - packages/*
+catalog:
+  react: ^18.2.0
+  lodash: ^4.17.21
+catalogs:
+  react17:
+    react: ^17.0.2
+overrides:
+  axios: 1.6.8
+"""
+
+ROOT_PACKAGE = {
+    "name": "monorepo",
+    "devDependencies": {"typescript": "~5.4.0"},
+    "pnpm": {"overrides": {"semver@<7.5.2": ">=7.5.2", "webpack>lodash": "4.17.20", "typescript": "$typescript"}},
+    "resolutions": {"**/left-pad": "1.3.0", "axios": "0.27.0"},
+}
+
+
+@pytest.mark.unit
+def test_default_and_named_catalogs_are_looked_up():
+    workspace = {"catalog": {"react": "^18"}, "catalogs": {"react17": {"react": "^17"}, "default": {"vue": "^3"}}}
+
+    assert catalog_version("catalog:react17", "react", workspace) == ("react17", "^17")
+    assert catalog_version("catalog:", "vue", workspace) == ("default", "^3")
+    assert catalog_version("catalog:", "react", {"catalog": {"react": "^18"}}) == ("default", "^18")
+    assert catalog_version("catalog:missing", "react", workspace) is None
+
+
+@pytest.mark.unit
+def test_catalog_specifiers_and_root_overrides_set_installed_versions(tmp_path):
+    (tmp_path / "pnpm-workspace.yaml").write_text(PNPM_WORKSPACE)
+    (tmp_path / "package.json").write_text(json.dumps(ROOT_PACKAGE))
+    (tmp_path / "packages/web").mkdir(parents=True)
+    web = {
+        "name": "web",
+        "dependencies": {"react": "catalog:", "lodash": "catalog:default", "axios": "^1.0.0", "semver": "^7.0.0"},
+        "devDependencies": {"left-pad": "^1.1.0", "vitest": "catalog:testing"},
+    }
+    (tmp_path / "packages/web/package.json").write_text(json.dumps(web))
+    handler = JavaScriptLanguageHandler()
+    packages = handler.process_manifest(str(tmp_path / "package.json"), {})
+    packages = handler.process_manifest(str(tmp_path / "packages/web/package.json"), packages)
+
+    summary = apply_npm_overrides(packages, str(tmp_path))
+
+    assert summary == {"catalog": ["lodash", "react"], "overridden": ["axios", "left-pad", "semver", "typescript"]}
+    assert packages["react"]["version"] == "^18.2.0"
+    assert packages["react"]["catalog"] == "default" and packages["react"]["declared_version"] == "catalog:"
+    assert packages["vitest"]["version"] == "catalog:testing"
+    assert packages["axios"]["version"] == "1.6.8"
+    assert packages["axios"]["declared_version"] == "^1.0.0"
+    assert packages["axios"]["override"] == {
+        "manifest": "pnpm-workspace.yaml",
+        "section": "overrides",
+        "selector": "axios",
+    }
+    assert packages["semver"]["version"] == ">=7.5.2"
+    assert packages["left-pad"]["override"]["selector"] == "**/left-pad"
+    assert packages["typescript"]["version"] == "~5.4.0"
+    assert packages["lodash"]["version"] == "^4.17.21" and "override" not in packages["lodash"]
+
+
+@pytest.mark.unit
+def test_ranged_selectors_apply_only_to_versions_they_match(tmp_path):
+    overrides = {"semver@<7.5.2": "7.5.2", "debug@~2.6": "2.6.9", "qs@>=6 <6.2 || 5.x": "6.2.4", "webpack@5>ws": "8"}
+    (tmp_path / "package.json").write_text(json.dumps({"name": "app", "pnpm": {"overrides": overrides}}))
+    packages = {
+        "semver": {"ecosystem": "npm", "version": "^7.0.0", "locked_version": "7.6.0"},
+        "debug": {"ecosystem": "npm", "version": "^2.6.0"},
+        "qs": {"ecosystem": "npm", "version": "^5.2.0"},
+        "webpack": {"ecosystem": "npm", "version": "5.90.0"},
+    }
+
+    summary = apply_npm_overrides(packages, str(tmp_path))
+
+    assert summary["overridden"] == ["debug", "qs"]
+    assert packages["semver"]["version"] == "^7.0.0" and "override" not in packages["semver"]
+    assert packages["debug"]["version"] == "2.6.9"
+    assert packages["qs"]["version"] == "6.2.4"
+    assert packages["webpack"]["version"] == "5.90.0"
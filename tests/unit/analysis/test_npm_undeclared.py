@@ -0,0 +1,75 @@
+"""
+Phantom npm dependencies: imports resolved through hoisting only
+"""
+
+import json
+
+import pytest
+
+from gardener.analysis.npm_undeclared import find_phantom_imports
+
+MANIFESTS = {
+    "package.json": {"name": "monorepo", "private": True, "devDependencies": {"typescript": "^5.4.0"}},
+    "packages/api/package.json": {"name": "@acme/api", "dependencies": {"express": "^4.19.0", "@acme/db": "*"}},
+    "packages/web/package.json": {"name": "@acme/web", "dependencies": {"react": "^18.2.0"}},
+}
+
+
+@pytest.mark.unit
+def test_imports_missing_from_the_nearest_package_json_are_reported(tmp_path):
+    for rel_path, data in MANIFESTS.items():
+        path = tmp_path / rel_path
+        path.parent.mkdir(parents=True, exist_ok=True)
+        path.write_text(json.dumps(data))
+    source_files = {
+        rel_path: {"language": "typescript"}
+        for rel_path in ("packages/api/src/server.ts", "packages/api/src/log.ts", "packages/web/src/App.tsx")
+    }
+    source_files["scripts/build.py"] = {"language": "python"}
+    file_imports = {
+        "packages/api/src/server.ts": ["express", "react", "debug", "fs", "@acme/db", "typescript"],
+        "packages/api/src/log.ts": ["debug", "chalk"],
+        "packages/web/src/App.tsx": ["react", "@acme/api", "lodash"],
+        "scripts/build.py": ["react"],
+    }
+    external_packages = {
+        "express": {"ecosystem": "npm", "manifest_dep_kinds": {"packages/api/package.json": "dependencies"}},
+        "react": {"ecosystem": "npm", "manifest_dep_kinds": {"packages/web/package.json": "dependencies"}},
+        "typescript": {"ecosystem": "npm", "manifest_dep_kinds": {"package.json": "devDependencies"}},
+        "debug": {"ecosystem": "npm", "direct": False, "transitive": True},
+        "lodash": {"ecosystem": "npm", "manifest_dep_kinds": {"packages/api/package.json": "dependencies"}},
+    }
+    workspace_members = {"@acme/api": {"ecosystem": "npm"}, "@acme/db": {"ecosystem": "npm"}}
+    annotations = {"packages/web/src/App.tsx": {"lodash": {"reexported_via": ["packages/api/src/index.ts"]}}}
+
+    reports = find_phantom_imports(
+        [str(tmp_path / rel_path) for rel_path in MANIFESTS],
+        external_packages,
+        workspace_members,
+        file_imports,
+        source_files,
+        annotations,
+        repo_path=str(tmp_path),
+    )
+
+    assert reports == [
+        {
+            "ecosystem": "npm",
+            "package": "@acme/api",
+            "manifest": "packages/api/package.json",
+            "imports": [
+                {
+                    "name": "debug",
+                    "files": ["packages/api/src/log.ts", "packages/api/src/server.ts"],
+                    "declared_in": [],
+                    "transitive": True,
+                },
+                {
+                    "name": "react",
+                    "files": ["packages/api/src/server.ts"],
+                    "declared_in": ["packages/web/package.json"],
+                },
+                {"name": "typescript", "files": ["packages/api/src/server.ts"], "declared_in": ["package.json"]},
+            ],
+        }
+    ]
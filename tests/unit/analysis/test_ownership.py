@@ -0,0 +1,77 @@
+"""
+First-party vs third-party package classification
+"""
+
+import pytest
+
+from gardener.analysis.bundle import request_hook
+from gardener.analysis.main import DependencyAnalyzer
+from gardener.analysis.ownership import classify_ownership, repository_key
+from gardener.common.defaults import ConfigOverride
+from gardener.treewalk.go import GoLanguageHandler
+
+
+@pytest.mark.unit
+def test_packages_match_owned_scopes_by_name_or_repository_url():
+    packages = {
+        "@drips/sdk": {"ecosystem": "npm"},
+        "drips-contracts": {
+            "ecosystem": "npm",
+            "repository_url": "git+ssh://git@github.com:Drips-Network/contracts.git",
+        },
+        "react": {"ecosystem": "npm", "repository_url": "https://github.com/facebook/react"},
+    }
+
+    first_party = classify_ownership(packages, ["@drips/*", "github.com/drips-network/*"])
+
+    assert first_party == {"@drips/sdk", "drips-contracts"}
+    assert packages["@drips/sdk"]["ownership_rule"] == "@drips/*"
+    assert packages["drips-contracts"]["ownership"] == "first-party"
+    assert packages["react"] == {
+        "ecosystem": "npm",
+        "repository_url": "https://github.com/facebook/react",
+        "ownership": "third-party",
+    }
+    assert classify_ownership({"react": {}}, ()) == set()
+
+
+@pytest.mark.unit
+def test_repository_key_reduces_url_spellings_to_one_key():
+    spellings = [
+        "https://github.com/Drips-Network/contracts",
+        "git+ssh://git@github.com:Drips-Network/contracts.git",
+        "https://www.github.com/drips-network/contracts/",
+    ]
+
+    assert {repository_key(url) for url in spellings} == {"github.com/drips-network/contracts"}
+    assert repository_key("https://gitlab.example.com:8443/team/app.git") == "gitlab.example.com:8443/team/app"
+    assert repository_key("") == ""
+
+
+@pytest.mark.unit
+def test_first_party_packages_stay_in_inventory_but_leave_the_funding_split(tmp_path, monkeypatch):
+    (tmp_path / "go.mod").write_text(
+        "module github.com/drips-network/app\n\ngo 1.21\n\n"
+        "require (\n\tgithub.com/drips-network/sdk v1.0.0\n\tgithub.com/spf13/cobra v1.8.0\n)\n"
+    )
+    (tmp_path / "main.go").write_text("package main\n")
+
+    def extract_imports(self, tree_node, rel_path, *args, **kwargs):
+        return ["github.com/drips-network/sdk", "github.com/spf13/cobra"], []
+
+    monkeypatch.setattr(GoLanguageHandler, "parse_source", lambda self, code, rel_path: object())
+    monkeypatch.setattr(GoLanguageHandler, "extract_imports", extract_imports)
+
+    with request_hook(lambda url: None), ConfigOverride({"FIRST_PARTY_OWNERS": ["github.com/drips-network/*"]}):
+        results = DependencyAnalyzer(verbose=False).analyze(str(tmp_path))
+        with ConfigOverride({"EXCLUDE_FIRST_PARTY": False}):
+            included = DependencyAnalyzer(verbose=False).analyze(str(tmp_path))
+
+    assert results["external_packages"]["github.com/drips-network/sdk"]["ownership"] == "first-party"
+    assert results["external_packages"]["github.com/spf13/cobra"]["ownership"] == "third-party"
+    assert [dep["package_name"] for dep in results["top_dependencies"]] == ["github.com/spf13/cobra"]
+    assert results["top_dependencies"][0]["percentage"] == 100
+    assert {dep["package_name"] for dep in included["top_dependencies"]} == {
+        "github.com/drips-network/sdk",
+        "github.com/spf13/cobra",
+    }
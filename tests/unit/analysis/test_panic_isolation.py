@@ -0,0 +1,43 @@
+"""
+Per-file panic isolation
+"""
+
+import pytest
+
+from gardener.analysis.main import DependencyAnalyzer
+from gardener.treewalk.python import PythonLanguageHandler
+
+
+@pytest.mark.unit
+def test_failing_analyzer_skips_only_the_offending_files(tmp_path, monkeypatch):
+    (tmp_path / "requirements.txt").write_text("requests==2.31.0\n")
+    (tmp_path / "package.json").write_text('{"name": "app", "dependencies": {"react": "^18.2.0"}}')
+    (tmp_path / "ok.py").write_text("import requests\n")
+    (tmp_path / "deep.py").write_text("x = " + "(" * 50 + "1" + ")" * 50 + "\n")
+
+    def extract_imports(self, tree_node, rel_path, file_components_dict, *args, **kwargs):
+        file_components_dict[rel_path].append(("requests", "requests.get"))
+        if rel_path == "deep.py":
+            raise RecursionError("maximum recursion depth exceeded")
+        return ["requests"], []
+
+    def process_manifest(self, file_path, packages_dict, secure_file_ops=None):
+        raise ValueError("unexpected token")
+
+    monkeypatch.setattr(PythonLanguageHandler, "parse_source", lambda self, code, rel_path: object())
+    monkeypatch.setattr(PythonLanguageHandler, "extract_imports", extract_imports)
+    monkeypatch.setattr(PythonLanguageHandler, "process_manifest", process_manifest)
+
+    analyzer = DependencyAnalyzer(verbose=False)
+    packages = analyzer.discover_packages(str(tmp_path))
+    analyzer.repo_analyzer.extract_imports_from_all_files()
+
+    assert "react" in packages
+    assert "requests" not in packages
+    assert dict(analyzer.repo_analyzer.file_imports) == {"ok.py": ["requests"]}
+    assert "deep.py" not in analyzer.repo_analyzer.file_package_components
+    panics = analyzer.repo_analyzer.panics.summary()
+    assert panics["counts"] == {"python": 2}
+    assert {"analyzer": "python", "stage": "imports", "file": "deep.py"}.items() <= panics["diagnostics"][1].items()
+    assert panics["diagnostics"][1]["error"] == "RecursionError: maximum recursion depth exceeded"
+    assert panics["diagnostics"][0]["file"] == "requirements.txt"
@@ -0,0 +1,100 @@
+"""
+Bounded queues between the concurrent analysis stages and their `--timings` report
+"""
+
+import json
+import time
+
+import pytest
+
+from gardener.analysis.bundle import request_hook
+from gardener.analysis.main import DependencyAnalyzer
+from gardener.analysis.pipeline import BoundedQueue, PipelineTimings, StageThread
+from gardener.common.defaults import ConfigOverride, ResourceLimits
+
+
+def _npm_meta(repo):
+    return json.dumps(
+        {"dist-tags": {"latest": "1.0.0"}, "versions": {"1.0.0": {"repository": f"git+https://github.com/{repo}.git"}}}
+    )
+
+
+@pytest.mark.unit
+def test_full_queue_holds_the_producer_back_until_the_consumer_catches_up():
+    timings = PipelineTimings()
+    bounded = timings.queue("work", 2)
+
+    def produce():
+        for item in range(6):
+            bounded.put(item)
+        bounded.close()
+
+    producer = StageThread("produce", produce)
+    producer.start()
+    consumed = []
+    for item in bounded:
+        time.sleep(0.02)
+        consumed.append(item)
+    producer.join_result()
+
+    assert consumed == list(range(6))
+    stats = timings.summary()["queues"]["work"]
+    assert stats["capacity"] == 2
+    assert stats["items"] == 6
+    assert 1 <= stats["max_depth"] <= 2
+    assert stats["blocked_seconds"] > 0
+
+
+@pytest.mark.unit
+def test_abandoned_queue_releases_its_producer_and_stage_errors_propagate():
+    bounded = BoundedQueue("work", 1)
+    bounded.put("first")
+    producer = StageThread("produce", lambda: bounded.put("second"))
+    producer.start()
+    bounded.abandon()
+    assert producer.join_result() is False
+
+    def fail():
+        raise ValueError("stage failed")
+
+    failing = StageThread("fail", fail)
+    failing.start()
+    with pytest.raises(ValueError, match="stage failed"):
+        failing.join_result()
+
+
+@pytest.mark.unit
+def test_streaming_order_resolves_imported_packages_while_parsing(tmp_path, monkeypatch):
+    (tmp_path / "package.json").write_text(
+        json.dumps({"name": "app", "dependencies": {"react": "^18.2.0", "lodash": "^4.17.21", "chalk": "^5.3.0"}})
+    )
+    (tmp_path / "index.js").write_text("import _ from 'lodash';\n")
+    (tmp_path / "cli.js").write_text("const chalk = require('chalk');\n")
+    monkeypatch.setattr(ResourceLimits, "PIPELINE_QUEUE_SIZE", 1)
+    repos = {"react": "facebook/react", "lodash": "lodash/lodash", "chalk": "chalk/chalk"}
+    requested = []
+
+    def registry(url):
+        name = url.rsplit("/", 1)[-1]
+        requested.append(name)
+        return _npm_meta(repos[name])
+
+    with request_hook(registry), ConfigOverride({"TIMINGS": True}):
+        preliminary = DependencyAnalyzer(verbose=False).analyze(str(tmp_path))
+        requested.clear()
+        with ConfigOverride({"URL_RESOLUTION_ORDER": "streaming"}):
+            streamed = DependencyAnalyzer(verbose=False).analyze(str(tmp_path))
+
+    # Imported packages are requested as files are parsed; the unimported one follows
+    assert sorted(requested[:2]) == ["chalk", "lodash"]
+    assert requested[2] == "react"
+    for name in repos:
+        assert streamed["external_packages"][name]["repository_url"] == (
+            preliminary["external_packages"][name]["repository_url"]
+        )
+    assert streamed["top_dependencies"] == preliminary["top_dependencies"]
+    queues = streamed["timings"]["queues"]
+    assert queues["sources"]["items"] == 2
+    assert queues["resolution_requests"] == {**queues["resolution_requests"], "capacity": 1, "items": 3, "max_depth": 1}
+    assert {"read", "parse", "imports", "url_resolution", "assembly"} <= set(streamed["timings"]["stages"])
+    assert "resolution_requests" not in preliminary["timings"]["queues"]
@@ -0,0 +1,52 @@
+"""
+Python extras scoped by where their packages are imported
+"""
+
+import pytest
+
+from gardener.analysis.python_extras import import_context, scope_extras
+
+
+def _pypi(extras=None, dep_kind="optional-dependencies"):
+    info = {"ecosystem": "pypi", "dep_kind": dep_kind}
+    if extras:
+        info["extras"] = extras
+    return info
+
+
+@pytest.mark.unit
+def test_import_context_from_path():
+    assert import_context("tests/unit/test_api.py") == "test"
+    assert import_context("src/pkg/api_test.py") == "test"
+    assert import_context("conftest.py") == "test"
+    assert import_context("docs/conf.py") == "docs"
+    assert import_context("src/pkg/api.py") == "runtime"
+
+
+@pytest.mark.unit
+def test_extra_only_packages_are_scoped_by_import_context():
+    packages = {
+        "pytest": _pypi(["test"]),
+        "sphinx": _pypi(["docs"]),
+        "PyYAML": _pypi(["yaml"]),
+        "boto3": _pypi(["s3"]),
+        "ipdb": _pypi(["dev"]),
+        "requests": _pypi(["http"], dep_kind="dependencies"),
+    }
+    file_imports = {
+        "tests/test_api.py": ["pytest", "yaml"],
+        "docs/conf.py": ["sphinx.ext"],
+        "src/pkg/config.py": ["yaml", "ipdb"],
+    }
+    source_files = {path: {"language": "python"} for path in file_imports}
+
+    reports = scope_extras(packages, file_imports, source_files)
+
+    assert [report["package"] for report in reports] == ["PyYAML", "boto3", "ipdb", "pytest", "sphinx"]
+    assert packages["pytest"]["scope"] == "extra"
+    assert (packages["pytest"]["import_contexts"], packages["pytest"]["production"]) == (["test"], False)
+    assert (packages["sphinx"]["import_contexts"], packages["sphinx"]["production"]) == (["docs"], False)
+    assert (packages["PyYAML"]["import_contexts"], packages["PyYAML"]["production"]) == (["runtime", "test"], True)
+    assert (packages["boto3"]["import_contexts"], packages["boto3"]["production"]) == ([], True)
+    assert packages["ipdb"]["extra_mismatch"] == "declared only under dev but imported from runtime code"
+    assert "scope" not in packages["requests"]
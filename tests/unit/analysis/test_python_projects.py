@@ -0,0 +1,126 @@
+"""
+Python projects of the repository as internal packages
+"""
+
+import pytest
+
+from gardener.analysis.imports import LocalImportResolver
+from gardener.analysis.python_projects import collect_python_projects, drop_internal_packages, internalize_imports
+from gardener.analysis.tree import RepositoryAnalyzer
+from gardener.treewalk.python import PythonLanguageHandler
+from gardener.treewalk.rust import RustLanguageHandler
+
+FILES = {
+    "pyproject.toml": '[project]\nname = "acme-service"\ndependencies = ["requests"]\n',
+    "src/acme_service/__init__.py": "",
+    "src/acme_service/api.py": "import requests\n",
+    "src/acme/plugins/cli.py": "import acme_service\n",
+    "tests/test_api.py": "from acme_service import api\nfrom acme.plugins import cli\n",
+    "libs/legacy/setup.cfg": "[metadata]\nname = legacy\n\n[options]\npackages = legacy\n",
+    "libs/legacy/legacy/__init__.py": "",
+    "libs/legacy/setup_helpers.py": "",
+    "libs/legacy/tests/test_legacy.py": "",
+}
+
+
+def _write(tmp_path):
+    for rel_path, content in FILES.items():
+        path = tmp_path / rel_path
+        path.parent.mkdir(parents=True, exist_ok=True)
+        path.write_text(content)
+    manifests = [str(tmp_path / rel_path) for rel_path in FILES if not rel_path.endswith(".py")]
+    source_files = {
+        rel_path: {"absolute_path": str(tmp_path / rel_path), "language": "python"}
+        for rel_path in FILES
+        if rel_path.endswith(".py")
+    }
+    return manifests, source_files
+
+
+@pytest.mark.unit
+def test_src_layout_and_namespace_packages_are_the_projects_own(tmp_path):
+    manifests, source_files = _write(tmp_path)
+
+    members = collect_python_projects(manifests, source_files, None, None, str(tmp_path))
+
+    assert members["acme-service"] == {
+        "annotation": "workspace-member",
+        "ecosystem": "pypi",
+        "manifest": "pyproject.toml",
+        "path": ".",
+        "source_roots": ["src"],
+        "import_names": ["acme", "acme_service"],
+        "namespace_packages": ["acme"],
+        "via": ["pyproject.toml"],
+    }
+    # Flat layout: only the listed package, not helper modules or tests
+    assert members["legacy"]["source_roots"] == ["libs/legacy"]
+    assert members["legacy"]["import_names"] == ["legacy"]
+
+    resolver = LocalImportResolver(
+        repo_path=str(tmp_path),
+        source_files=source_files,
+        alias_resolver=None,
+        js_ts_base_url=None,
+        js_ts_path_aliases=None,
+        go_module_path=None,
+        remappings=None,
+        hardhat_remappings=None,
+        solidity_src_path=None,
+        logger=None,
+        python_source_roots=["src"],
+    )
+    assert resolver.resolve_python("tests/test_api.py", "acme_service", 0) == "src/acme_service/__init__.py"
+    assert resolver.resolve_python("tests/test_api.py", "acme.plugins.cli", 0) == "src/acme/plugins/cli.py"
+    # A namespace package has no file to resolve to, but is not an external import either
+    file_imports = {"tests/test_api.py": ["acme", "requests"]}
+    assert internalize_imports(file_imports, source_files, members) == {"tests/test_api.py": ["acme"]}
+    assert file_imports == {"tests/test_api.py": ["requests"]}
+
+
+@pytest.mark.unit
+def test_self_dependencies_and_editable_installs_are_not_external(tmp_path):
+    manifests, source_files = _write(tmp_path)
+    members = collect_python_projects(manifests, source_files, None, None, str(tmp_path))
+    packages = {
+        "requests": {"ecosystem": "pypi", "dep_kind": "dependencies"},
+        "Acme_Service": {
+            "ecosystem": "pypi",
+            "requirements": [{"editable": True, "path": ".", "file": "requirements-dev.txt", "line": 1}],
+        },
+        "core": {
+            "ecosystem": "pypi",
+            "requirements": [{"editable": True, "path": "./libs/core", "file": "requirements-dev.txt", "line": 2}],
+        },
+        "plugin": {
+            "ecosystem": "pypi",
+            "requirements": [{"editable": True, "url": "git+https://github.com/org/plugin.git#egg=plugin"}],
+        },
+    }
+
+    assert drop_internal_packages(packages, members) == ["Acme_Service", "core"]
+    assert sorted(packages) == ["plugin", "requests"]
+    assert members["acme-service"]["via"] == ["pyproject.toml", "editable"]
+    assert members["core"]["path"] == "libs/core"
+    assert members["core"]["import_names"] == ["core"]
+
+
+@pytest.mark.unit
+def test_maturin_project_named_after_its_crate_stays_a_member(tmp_path):
+    (tmp_path / "Cargo.toml").write_text('[workspace]\nmembers = ["crates/fastjson"]\n')
+    (tmp_path / "crates/fastjson/src").mkdir(parents=True)
+    (tmp_path / "crates/fastjson/Cargo.toml").write_text('[package]\nname = "fastjson"\nversion = "0.1.0"\n')
+    (tmp_path / "crates/fastjson/src/lib.rs").write_text("")
+    (tmp_path / "pyproject.toml").write_text('[project]\nname = "fastjson"\n\n[tool.maturin]\nfeatures = ["pyo3"]\n')
+    (tmp_path / "src/fastjson").mkdir(parents=True)
+    (tmp_path / "src/fastjson/__init__.py").write_text("")
+    analyzer = RepositoryAnalyzer(str(tmp_path))
+    analyzer.register_language_handler("rust", RustLanguageHandler())
+    analyzer.register_language_handler("python", PythonLanguageHandler())
+    analyzer.scan_repo()
+    analyzer.process_manifest_files()
+
+    assert analyzer.workspace_members["fastjson"].get("ecosystem", "cargo") == "cargo"
+    assert analyzer.workspace_members["pypi:fastjson"]["source_roots"] == ["src"]
+    assert analyzer.workspace_members["pypi:fastjson"]["import_names"] == ["fastjson"]
+    assert analyzer._python_source_roots() == ["src"]
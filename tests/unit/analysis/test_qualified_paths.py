@@ -0,0 +1,40 @@
+"""
+Fully-qualified crate paths kept only for declared Cargo dependencies
+"""
+
+import pytest
+
+from gardener.analysis.qualified_paths import filter_qualified_path_imports
+
+
+@pytest.mark.unit
+def test_only_declared_crates_are_kept_with_low_confidence():
+    packages = {
+        "serde_json": {"ecosystem": "cargo", "import_names": ["serde_json"]},
+        "tokio-util": {"ecosystem": "cargo"},
+        "anyhow": {"ecosystem": "cargo", "import_names": ["anyhow"]},
+    }
+    file_imports = {"src/lib.rs": ["serde_json", "tokio_util", "widgets", "anyhow"], "src/bin.rs": ["widgets"]}
+    components = {
+        "src/lib.rs": [("serde_json", "serde_json::from_str"), ("widgets", "widgets::draw")],
+        "src/bin.rs": [("widgets", "widgets::draw")],
+    }
+    annotations = {
+        "src/lib.rs": {
+            "serde_json": {"evidence": "qualified-path"},
+            "tokio_util": {"evidence": "qualified-path", "cfg": "unix"},
+            "widgets": {"evidence": "qualified-path"},
+        },
+        "src/bin.rs": {"widgets": {"evidence": "qualified-path"}},
+    }
+
+    assert filter_qualified_path_imports(packages, file_imports, components, annotations) == 2
+
+    assert file_imports == {"src/lib.rs": ["serde_json", "tokio_util", "anyhow"]}
+    assert components == {"src/lib.rs": [("serde_json", "serde_json::from_str")], "src/bin.rs": []}
+    assert annotations == {
+        "src/lib.rs": {
+            "serde_json": {"evidence": "qualified-path", "confidence": "low"},
+            "tokio_util": {"evidence": "qualified-path", "cfg": "unix", "confidence": "low"},
+        }
+    }
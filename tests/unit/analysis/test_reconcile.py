@@ -0,0 +1,101 @@
+"""
+Drip list reconciliation against computed splits
+"""
+
+import json
+
+import pytest
+
+from gardener.analysis.reconcile import TOTAL_WEIGHT, fetch_drip_list, main, reconcile, target_splits
+
+REPORT = {
+    "top_dependencies": [
+        {"package_name": "react", "percentage": 50.0, "package_url": "https://github.com/facebook/react"},
+        {"package_name": "react-dom", "percentage": 20.0, "package_url": "https://github.com/facebook/react"},
+        {"package_name": "lodash", "percentage": 20.0, "package_url": "https://github.com/lodash/lodash"},
+        {"package_name": "left-pad", "percentage": 10.0, "package_url": ""},
+    ],
+    "external_packages": {},
+}
+
+DRIP_LIST = {
+    "data": {
+        "dripList": {
+            "name": "Deps",
+            "splits": [
+                {
+                    "__typename": "ProjectReceiver",
+                    "weight": 500000,
+                    "project": {"source": {"url": "https://github.com/Facebook/react"}},
+                },
+                {
+                    "__typename": "ProjectReceiver",
+                    "weight": 400000,
+                    "project": {"source": {"url": "https://github.com/moment/moment"}},
+                },
+                {"__typename": "AddressReceiver", "weight": 100000, "account": {"address": "0xABC"}},
+            ],
+        }
+    }
+}
+
+
+@pytest.mark.unit
+def test_target_splits_group_by_repository_and_sum_to_total_weight():
+    targets, unresolved, unresolved_share = target_splits(REPORT)
+
+    assert {key: target["weight"] for key, target in targets.items()} == {
+        "github.com/facebook/react": 777778,
+        "github.com/lodash/lodash": 222222,
+    }
+    assert sum(target["weight"] for target in targets.values()) == TOTAL_WEIGHT
+    assert targets["github.com/facebook/react"]["packages"] == ["react", "react-dom"]
+    assert (unresolved, unresolved_share) == (["left-pad"], 10.0)
+    assert list(target_splits(REPORT, max_receivers=1)[0]) == ["github.com/facebook/react"]
+
+
+@pytest.mark.unit
+def test_target_splits_skip_entries_without_a_package_name():
+    report = {
+        "top_dependencies": [
+            {"percentage": 5.0},
+            {"percentage": 5.0, "package_url": "https://github.com/chalk/chalk"},
+            "left-pad",
+            *REPORT["top_dependencies"],
+        ]
+    }
+
+    assert target_splits(report) == target_splits(REPORT)
+
+
+@pytest.mark.unit
+def test_reconcile_lists_receivers_to_add_remove_and_reweight():
+    payloads = []
+
+    def post(url, payload):
+        payloads.append(payload)
+        return DRIP_LIST
+
+    current = fetch_drip_list("42", "https://api.example/graphql", post_fn=post)
+
+    diff = reconcile(REPORT, current)
+
+    assert payloads[0]["variables"] == {"id": "42", "chain": "MAINNET"}
+    assert [entry["receiver"] for entry in diff["add"]] == ["https://github.com/lodash/lodash"]
+    assert diff["remove"] == [
+        {"receiver": "https://github.com/moment/moment", "weight": 400000, "type": "ProjectReceiver"}
+    ]
+    assert diff["reweight"][0]["current_weight"] == 500000 and diff["reweight"][0]["weight"] == 777778
+    assert diff["unmanaged"][0]["receiver"] == "0xABC"
+    assert reconcile(REPORT, current, tolerance=300000)["reweight"] == []
+    with pytest.raises(ValueError):
+        fetch_drip_list("7", "https://api.example/graphql", post_fn=lambda url, p: {"data": {"dripList": None}})
+
+
+@pytest.mark.unit
+def test_reconcile_command_requires_an_endpoint(tmp_path, monkeypatch):
+    report = tmp_path / "report.json"
+    report.write_text(json.dumps(REPORT))
+    monkeypatch.setenv("DRIPS_API_URL", "")
+
+    assert main([str(report), "--drip-list", "42"]) == 1
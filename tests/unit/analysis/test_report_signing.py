@@ -0,0 +1,103 @@
+"""
+Report signing and `gardener verify`
+"""
+
+import json
+
+import pytest
+from cryptography.hazmat.primitives import serialization
+from cryptography.hazmat.primitives.asymmetric import ed25519
+
+from gardener.analysis.reproduce import input_digest
+from gardener.analysis.signing import input_digest_failures, main, sign_report, signature_failures
+
+
+def _keypair(tmp_path, name="signer"):
+    private_key = ed25519.Ed25519PrivateKey.generate()
+    private_path = tmp_path / f"{name}.pem"
+    private_path.write_bytes(
+        private_key.private_bytes(
+            serialization.Encoding.PEM, serialization.PrivateFormat.PKCS8, serialization.NoEncryption()
+        )
+    )
+    public_pem = private_key.public_key().public_bytes(
+        serialization.Encoding.PEM, serialization.PublicFormat.SubjectPublicKeyInfo
+    )
+    return str(private_path), public_pem
+
+
+def _report(tmp_path):
+    repo = tmp_path / "repo"
+    repo.mkdir()
+    (repo / "main.py").write_text("import os\n")
+    return {
+        "top_dependencies": [{"package_name": "requests", "percentage": 100.0}],
+        "reproduce": {"focus_languages": ["python"], "input": input_digest(str(repo), [str(repo / "main.py")])},
+    }
+
+
+@pytest.mark.unit
+def test_signed_report_verifies_and_detects_tampering(tmp_path):
+    private_path, public_pem = _keypair(tmp_path)
+    report = _report(tmp_path)
+    sign_report(report, private_path)
+
+    # The signature must survive the JSON round trip the report writer performs
+    report = json.loads(json.dumps(report, indent=2))
+    assert signature_failures(report, public_pem) == []
+
+    report["top_dependencies"][0]["percentage"] = 50.0
+    assert signature_failures(report, public_pem) == [
+        "Report content does not match the signed payload digest",
+        "Signature is invalid",
+    ]
+
+
+@pytest.mark.unit
+def test_wrong_key_and_unsigned_reports_fail(tmp_path):
+    private_path, _ = _keypair(tmp_path)
+    _, other_public_pem = _keypair(tmp_path, "other")
+    report = _report(tmp_path)
+
+    assert signature_failures(report, other_public_pem) == ["Report is not signed"]
+    sign_report(report, private_path)
+    assert "Report was signed with a different key" in signature_failures(report, other_public_pem)
+
+
+@pytest.mark.unit
+def test_verify_command_checks_input_digest(tmp_path):
+    private_path, public_pem = _keypair(tmp_path)
+    report = _report(tmp_path)
+    sign_report(report, private_path)
+    report_path = tmp_path / "report.json"
+    report_path.write_text(json.dumps(report))
+    (tmp_path / "signer.pub").write_bytes(public_pem)
+    repo = tmp_path / "repo"
+
+    assert input_digest_failures(report, str(repo)) == []
+    assert main([str(report_path), "--key", str(tmp_path / "signer.pub"), "--repo", str(repo)]) == 0
+
+    (repo / "main.py").write_text("import sys\n")
+    assert main([str(report_path), "--key", str(tmp_path / "signer.pub"), "--repo", str(repo)]) == 1
+
+
+@pytest.mark.unit
+def test_input_digest_rescan_applies_the_recorded_configuration(tmp_path, monkeypatch):
+    from gardener.analysis.main import DependencyAnalyzer
+    from gardener.common.defaults import GraphAnalysisConfig
+
+    report = _report(tmp_path)
+    report["reproduce"]["config"] = {"GraphAnalysisConfig": {"STRICT_MANIFESTS": True}}
+    seen = []
+    discover = DependencyAnalyzer.discover_packages
+
+    def recording_discover(self, *args, **kwargs):
+        seen.append(GraphAnalysisConfig.STRICT_MANIFESTS)
+        return discover(self, *args, **kwargs)
+
+    monkeypatch.setattr(DependencyAnalyzer, "discover_packages", recording_discover)
+
+    assert input_digest_failures(report, str(tmp_path / "repo")) == []
+    assert seen == [True]
+    assert GraphAnalysisConfig.STRICT_MANIFESTS is False
+
@@ -0,0 +1,84 @@
+"""
+Reproducibility manifest attached to analysis results
+"""
+
+from types import SimpleNamespace
+
+import pytest
+
+from gardener.analysis.reproduce import analyzer_versions, build_reproduce_block, input_digest
+from gardener.common.defaults import ConfigOverride
+from gardener.common.secure_file_ops import SecureFileOps
+from gardener.treewalk.idl import IdlLanguageHandler
+from gardener.treewalk.python import PythonLanguageHandler
+
+
+def _repo_analyzer(tmp_path, focus_languages=None):
+    (tmp_path / "main.py").write_text("import requests\n")
+    (tmp_path / "requirements.txt").write_text("requests==2.32.3\n")
+    return SimpleNamespace(
+        repo_path=str(tmp_path),
+        focus_languages=focus_languages,
+        language_handlers={"python": PythonLanguageHandler(), "idl": IdlLanguageHandler()},
+        source_files={"main.py": {"absolute_path": str(tmp_path / "main.py"), "language": "python"}},
+        manifest_files=[str(tmp_path / "requirements.txt")],
+        script_files=[],
+        js_config_files=[],
+        ts_config_files=[],
+        secure_file_ops=SecureFileOps(str(tmp_path)),
+    )
+
+
+@pytest.mark.unit
+def test_input_digest_tracks_file_contents(tmp_path):
+    analyzer = _repo_analyzer(tmp_path)
+    paths = [str(tmp_path / "main.py"), str(tmp_path / "requirements.txt")]
+
+    first = input_digest(analyzer.repo_path, paths)
+    assert first == input_digest(analyzer.repo_path, list(reversed(paths)))
+    assert first["file_count"] == 2
+
+    (tmp_path / "main.py").write_text("import flask\n")
+    assert input_digest(analyzer.repo_path, paths)["digest"] != first["digest"]
+
+
+@pytest.mark.unit
+def test_input_digest_does_not_follow_symlinks_out_of_the_repository(tmp_path):
+    repo = tmp_path / "repo"
+    repo.mkdir()
+    outside = tmp_path / "secret.txt"
+    outside.write_text("token=1\n")
+    (repo / "main.py").symlink_to(outside)
+    paths = [str(repo / "main.py")]
+
+    first = input_digest(str(repo), paths, SecureFileOps(str(repo)))
+    outside.write_text("token=2\n")
+    assert input_digest(str(repo), paths, SecureFileOps(str(repo))) == first
+    assert input_digest(str(repo), paths)["digest"] != first["digest"]
+
+
+@pytest.mark.unit
+def test_reproduce_block_captures_config_and_analyzers(tmp_path):
+    analyzer = _repo_analyzer(tmp_path, focus_languages=["python"])
+
+    with ConfigOverride({"PAGERANK_ALPHA": 0.9}):
+        block = build_reproduce_block(analyzer)
+
+    assert block["config"]["GraphAnalysisConfig"]["PAGERANK_ALPHA"] == 0.9
+    assert list(block["analyzers"]) == ["python"]
+    assert block["analyzers"]["python"]["handler"] == "PythonLanguageHandler"
+    assert block["cache"]["url_cache"]["provided"] is False
+    assert block["input"]["file_count"] == 2
+    assert build_reproduce_block(analyzer)["config"]["GraphAnalysisConfig"]["PAGERANK_ALPHA"] == 0.85
+
+
+@pytest.mark.unit
+def test_regex_analyzers_report_no_grammar():
+    analyzers = analyzer_versions({"idl": IdlLanguageHandler()})
+
+    assert analyzers["idl"] == {
+        "handler": "IdlLanguageHandler",
+        "parser": "regex",
+        "grammar": None,
+        "grammar_version": None,
+    }
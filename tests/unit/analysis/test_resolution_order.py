@@ -0,0 +1,61 @@
+"""
+Priority-ordered repository URL resolution
+"""
+
+import json
+
+import pytest
+
+from gardener.analysis.bundle import request_hook
+from gardener.analysis.graph import preliminary_weights
+from gardener.analysis.main import DependencyAnalyzer
+from gardener.common.defaults import ConfigOverride
+
+
+@pytest.mark.unit
+def test_preliminary_weights_count_importing_files_per_distribution():
+    external_packages = {
+        "beautifulsoup4": {"ecosystem": "pypi", "import_names": ["bs4"]},
+        "github.com/spf13/cobra": {"ecosystem": "go"},
+        "requests": {"ecosystem": "pypi"},
+    }
+    file_imports = {
+        "scrape.py": ["bs4", "requests", "bs4"],
+        "fetch.py": ["requests"],
+        "cmd/root.go": ["github.com/spf13/cobra/doc", "fmt"],
+    }
+
+    weights = preliminary_weights(external_packages, file_imports)
+
+    assert weights == {"requests": 2, "beautifulsoup4": 1, "github.com/spf13/cobra": 1}
+
+
+def _js_repo(tmp_path):
+    (tmp_path / "package.json").write_text(
+        json.dumps({"name": "app", "dependencies": {"react": "^18.2.0", "lodash": "^4.17.21", "chalk": "^5.3.0"}})
+    )
+    (tmp_path / "report.js").write_text("import _ from 'lodash';\nimport chalk from 'chalk';\n")
+    (tmp_path / "cli.js").write_text("const chalk = require('chalk');\n")
+    return str(tmp_path)
+
+
+@pytest.mark.unit
+@pytest.mark.parametrize(
+    "order, expected",
+    [
+        ("preliminary", ["chalk", "lodash", "react"]),
+        ("declared", ["react", "lodash", "chalk"]),
+    ],
+)
+def test_registry_lookups_follow_configured_resolution_order(tmp_path, order, expected):
+    repo = _js_repo(tmp_path)
+    requested = []
+
+    def record(url):
+        requested.append(url)
+        return None
+
+    with ConfigOverride({"URL_RESOLUTION_ORDER": order}), request_hook(record):
+        DependencyAnalyzer(verbose=False).analyze(repo, ["javascript"], url_cache={})
+
+    assert requested == [f"https://registry.npmjs.org/{name}" for name in expected]
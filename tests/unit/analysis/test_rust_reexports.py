@@ -0,0 +1,89 @@
+"""
+Rust `pub use` re-export chains
+"""
+
+import pytest
+
+from gardener.analysis.imports import LocalImportResolver
+from gardener.analysis.rust_reexports import attribute_reexports, parse_reexports
+
+FILES = {
+    "src/main.rs": "mod api;\nmod models;\nmod net;\nuse crate::api::ApiClient;\n",
+    "src/api/mod.rs": "pub mod client;\n// pub use client::Stale;\npub use client::ApiClient;\n",
+    "src/api/client.rs": "pub struct ApiClient;\n",
+    "src/models/mod.rs": "mod user;\npub use self::user::*;\n",
+    "src/models/user.rs": "pub struct User {\n    pub id: u64,\n}\n",
+    "src/net.rs": "pub use reqwest::Client as HttpClient;\n",
+}
+
+
+@pytest.mark.unit
+def test_pub_use_trees_expand_aliases_groups_and_globs():
+    code = "pub use a::{B, c::{D as E, self}, f::*};\npub(crate) use g::H as I;\nuse private::X;\n"
+
+    assert parse_reexports(code) == {
+        "B": [["a", "B"]],
+        "E": [["a", "c", "D"]],
+        "c": [["a", "c"]],
+        "*": [["a", "f"]],
+        "I": [["g", "H"]],
+    }
+
+
+@pytest.mark.unit
+def test_reexported_items_are_attributed_to_the_defining_module_or_crate(tmp_path):
+    source_files = {}
+    for rel_path, content in FILES.items():
+        path = tmp_path / rel_path
+        path.parent.mkdir(parents=True, exist_ok=True)
+        path.write_text(content)
+        source_files[rel_path] = {"absolute_path": str(path), "language": "rust"}
+    packages = {"reqwest": {"ecosystem": "cargo", "import_names": ["reqwest"]}}
+    resolver = LocalImportResolver(
+        repo_path=str(tmp_path),
+        source_files=source_files,
+        alias_resolver=None,
+        js_ts_base_url=None,
+        js_ts_path_aliases=None,
+        go_module_path=None,
+        remappings=None,
+        hardhat_remappings=None,
+        solidity_src_path=None,
+        logger=None,
+        external_packages=packages,
+    )
+    file_imports = {}
+    local_imports = {"src/main.rs": ["src/api/mod.rs", "src/models/mod.rs", "src/net.rs"]}
+    components = {
+        "src/main.rs": [
+            ("crate", "crate::api::ApiClient"),
+            ("crate", "crate::api"),
+            ("crate", "crate::models::User"),
+            ("crate", "crate::net::HttpClient"),
+        ]
+    }
+    annotations = {}
+
+    chains = attribute_reexports(
+        source_files, packages, file_imports, local_imports, components, annotations, resolver.resolve_rust
+    )
+
+    assert chains == [
+        {
+            "file": "src/main.rs",
+            "import": "crate::api::ApiClient",
+            "chain": ["src/api/mod.rs", "src/api/client.rs"],
+            "defined_in": "src/api/client.rs",
+        },
+        {
+            "file": "src/main.rs",
+            "import": "crate::models::User",
+            "chain": ["src/models/mod.rs", "src/models/user.rs"],
+            "defined_in": "src/models/user.rs",
+        },
+        {"file": "src/main.rs", "import": "crate::net::HttpClient", "chain": ["src/net.rs"], "crate": "reqwest"},
+    ]
+    assert local_imports["src/main.rs"][3:] == ["src/api/client.rs", "src/models/user.rs"]
+    assert file_imports == {"src/main.rs": ["reqwest"]}
+    assert ("reqwest", "reqwest::Client") in components["src/main.rs"]
+    assert annotations["src/main.rs"]["reqwest"] == {"reexported_via": ["src/net.rs"]}
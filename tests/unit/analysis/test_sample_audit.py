@@ -0,0 +1,94 @@
+"""
+Share-weighted spot-audit worksheets
+"""
+
+import csv
+import io
+import json
+import random
+
+import pytest
+
+from gardener.analysis.sample_audit import audit_worksheet, main, weighted_sample, write_csv
+
+REPORT = {
+    "top_dependencies": [
+        {"package_name": "react", "percentage": 70.0},
+        {"package_name": "lodash", "percentage": 29.0},
+        {"package_name": "left-pad", "percentage": 1.0},
+    ],
+    "external_packages": {
+        "react": {
+            "ecosystem": "npm",
+            "repository_url": "https://github.com/facebook/react",
+            "found_in_manifests": ["package.json"],
+            "import_names": ["react"],
+        },
+        "lodash": {
+            "ecosystem": "npm",
+            "repository_url": "https://github.com/lodash/lodash",
+            "found_in_manifests": ["package.json"],
+        },
+        "left-pad": {"ecosystem": "npm", "repository_url": "", "found_in_manifests": ["package.json"]},
+    },
+    "analyzer_details": {
+        "file_imports": {"src/App.js": ["react", "lodash"], "src/index.js": ["react"]},
+        "seed": {"resolved_at": {"npm:lodash": "2026-09-01T00:00:00+00:00"}},
+    },
+    "reproduce": {"input": {"digest": "sha256:abc"}},
+}
+
+
+@pytest.mark.unit
+def test_weighted_sample_favors_larger_shares_without_repeats():
+    counts = {"big": 0, "small": 0}
+    rng = random.Random(7)
+    for _ in range(2000):
+        drawn = weighted_sample({"big": 90.0, "small": 10.0, "zero": 0}, 1, rng)
+        counts[drawn[0]] += 1
+    assert counts["big"] > 5 * counts["small"] > 0
+    assert sorted(weighted_sample({"a": 1, "b": 2, "c": 3}, 5, rng)) == ["a", "b", "c"]
+
+
+@pytest.mark.unit
+def test_worksheet_lists_evidence_receipts_and_urls_to_verify():
+    worksheet = audit_worksheet(REPORT, n=3, seed=42, report_path="report.json")
+
+    assert worksheet["seed"] == 42 and worksheet["population"] == 3
+    assert worksheet["input_digest"] == "sha256:abc"
+    assert worksheet["sampled_share"] == pytest.approx(100.0)
+    entries = {entry["package"]: entry for entry in worksheet["packages"]}
+    assert entries["react"]["evidence"] == {
+        "manifests": ["package.json"],
+        "files": 2,
+        "sample_files": ["src/App.js", "src/index.js"],
+    }
+    assert entries["react"]["resolution"] == {
+        "repository_url": "https://github.com/facebook/react",
+        "source": "registry",
+    }
+    assert entries["react"]["verify_urls"] == [
+        "https://github.com/facebook/react",
+        "https://www.npmjs.com/package/react",
+    ]
+    assert entries["lodash"]["resolution"]["source"] == "seed"
+    assert entries["left-pad"]["resolution"] == {"repository_url": None, "source": "unresolved"}
+    assert audit_worksheet(REPORT, n=2, seed=42)["packages"] == worksheet["packages"][:2]
+
+    out = io.StringIO()
+    write_csv(worksheet, out)
+    rows = list(csv.DictReader(io.StringIO(out.getvalue())))
+    assert [row["package"] for row in rows] == [entry["package"] for entry in worksheet["packages"]]
+    assert rows[0]["verified"] == "" and rows[0]["notes"] == ""
+
+
+@pytest.mark.unit
+def test_sample_audit_command_writes_worksheet(tmp_path):
+    report = tmp_path / "report.json"
+    report.write_text(json.dumps(REPORT))
+    output = tmp_path / "audit.json"
+
+    assert main([str(report), "--n", "1", "--seed", "3", "-o", str(output)]) == 0
+    worksheet = json.loads(output.read_text())
+    assert len(worksheet["packages"]) == 1 and worksheet["seed"] == 3
+    assert main([str(tmp_path / "missing.json")]) == 1
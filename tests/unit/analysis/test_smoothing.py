@@ -0,0 +1,59 @@
+"""
+Exponential moving average of top dependency shares across runs
+"""
+
+import json
+
+import pytest
+
+from gardener.analysis.smoothing import load_previous_report, smooth_top_dependencies, validate_smoothing
+
+PREVIOUS = {
+    "top_dependencies": [
+        {"package_name": "react", "percentage": 60.0, "package_url": "https://github.com/facebook/react"},
+        {"package_name": "moment", "percentage": 30.0, "package_url": "https://github.com/moment/moment"},
+        {"package_name": "left-pad", "percentage": 10.0, "package_url": ""},
+    ]
+}
+
+
+@pytest.mark.unit
+def test_shares_move_part_of_the_way_and_dropped_packages_decay():
+    current = [
+        {"package_name": "react", "percentage": 20.0, "package_url": "https://github.com/facebook/react"},
+        {"package_name": "lodash", "percentage": 80.0, "package_url": "https://github.com/lodash/lodash"},
+    ]
+    external = {"react": {}, "lodash": {}, "moment": {}}
+
+    smoothed, summary = smooth_top_dependencies(current, PREVIOUS, 0.5, external)
+
+    shares = {entry["package_name"]: entry["percentage"] for entry in smoothed}
+    # react 0.5*20 + 0.5*60, lodash 0.5*80, moment 0.5*30; left-pad is no longer declared
+    assert shares == pytest.approx({"react": 40 / 95 * 100, "lodash": 40 / 95 * 100, "moment": 15 / 95 * 100})
+    assert {entry["package_name"]: entry["raw_percentage"] for entry in smoothed} == {
+        "react": 20.0,
+        "lodash": 80.0,
+        "moment": 0,
+    }
+    assert summary == {"factor": 0.5, "carried_over": 1, "dropped": 1}
+    assert [entry["package_name"] for entry in smoothed][-1] == "moment"
+
+    raw, _ = smooth_top_dependencies(current, PREVIOUS, 0, external)
+    assert [(entry["package_name"], entry["percentage"]) for entry in raw] == [("lodash", 80.0), ("react", 20.0)]
+
+
+@pytest.mark.unit
+def test_options_and_previous_report_are_validated(tmp_path):
+    validate_smoothing(None, None)
+    validate_smoothing(0.3, "last.json")
+    for factor, previous in ((0.5, None), (None, "last.json"), (1.0, "last.json"), (-0.1, "last.json")):
+        with pytest.raises(ValueError):
+            validate_smoothing(factor, previous)
+
+    path = tmp_path / "last.json"
+    path.write_text(json.dumps(PREVIOUS))
+    report, digest = load_previous_report(str(path))
+    assert report == PREVIOUS and digest.startswith("sha256:")
+    path.write_text("{}")
+    with pytest.raises(ValueError):
+        load_previous_report(str(path))
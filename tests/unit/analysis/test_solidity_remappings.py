@@ -0,0 +1,88 @@
+"""
+Foundry remappings, lib/ submodules and Hardhat npm packages for Solidity projects
+"""
+
+import json
+
+import pytest
+
+from gardener.analysis.main import DependencyAnalyzer
+from gardener.analysis.solidity_meta import parse_remapping
+
+GITMODULES = """[submodule "lib/forge-std"]
+	path = lib/forge-std
+	url = https://github.com/foundry-rs/forge-std
+[submodule "lib/openzeppelin-contracts"]
+	path = lib/openzeppelin-contracts
+	url = https://github.com/OpenZeppelin/openzeppelin-contracts
+[submodule "lib/solady"]
+	path = lib/solady
+	url = https://github.com/Vectorized/solady
+"""
+
+FOUNDRY_TOML = """[profile.default]
+src = "src"
+libs = ["lib"]
+remappings = ["@openzeppelin/contracts/=lib/openzeppelin-contracts/contracts/"]
+"""
+
+
+def _foundry_repo(tmp_path):
+    repo = tmp_path / "vault"
+    for library in ("forge-std/src", "openzeppelin-contracts/contracts", "solady/src"):
+        (repo / "lib" / library).mkdir(parents=True)
+    (repo / "src").mkdir()
+    (repo / "src" / "Vault.sol").write_text(
+        'import "forge-std/Test.sol";\nimport "@openzeppelin/contracts/token/ERC20/ERC20.sol";\n'
+    )
+    (repo / ".gitmodules").write_text(GITMODULES)
+    (repo / "foundry.toml").write_text(FOUNDRY_TOML)
+    return repo
+
+
+@pytest.mark.unit
+def test_remapping_lines_drop_context_and_normalize_target():
+    assert parse_remapping("@openzeppelin/=lib/openzeppelin-contracts/") == (
+        "@openzeppelin/",
+        "lib/openzeppelin-contracts",
+    )
+    assert parse_remapping("lib/uniswap:@uniswap/=./lib/v3-core/") == ("@uniswap/", "lib/v3-core")
+    assert parse_remapping("not-a-remapping") is None
+
+
+@pytest.mark.unit
+def test_foundry_remappings_and_lib_submodules_resolve_to_packages(tmp_path):
+    repo = _foundry_repo(tmp_path)
+    analyzer = DependencyAnalyzer(verbose=False)
+
+    packages = analyzer.discover_packages(str(repo), ["solidity"])
+    remappings = analyzer.repo_analyzer.remappings
+
+    assert remappings["@openzeppelin/contracts/"] == "lib/openzeppelin-contracts/contracts"
+    # forge infers `<name>/=lib/<name>/src/` for libraries without an explicit remapping
+    assert remappings["forge-std/"] == "lib/forge-std/src"
+    assert remappings["solady/"] == "lib/solady/src"
+    assert "openzeppelin-contracts/" not in remappings
+
+    assert packages["@openzeppelin/contracts"]["gitmodules_url"] == (
+        "https://github.com/OpenZeppelin/openzeppelin-contracts"
+    )
+    assert packages["@openzeppelin/contracts"]["source"].startswith("foundry.toml:")
+    assert packages["forge-std"]["gitmodules_url"] == "https://github.com/foundry-rs/forge-std"
+    assert packages["solady"]["gitmodules_source_path"] == "lib/solady"
+
+
+@pytest.mark.unit
+def test_hardhat_openzeppelin_stays_an_npm_package(tmp_path):
+    repo = tmp_path / "token"
+    (repo / "contracts").mkdir(parents=True)
+    (repo / "contracts" / "Token.sol").write_text('import "@openzeppelin/contracts/token/ERC20/ERC20.sol";\n')
+    (repo / "package.json").write_text(
+        json.dumps({"name": "token", "devDependencies": {"@openzeppelin/contracts": "^5.0.2", "hardhat": "^2.22.0"}})
+    )
+    analyzer = DependencyAnalyzer(verbose=False)
+
+    packages = analyzer.discover_packages(str(repo), ["solidity"])
+
+    assert packages["@openzeppelin/contracts"]["ecosystem"] == "npm"
+    assert analyzer.repo_analyzer.remappings == {}
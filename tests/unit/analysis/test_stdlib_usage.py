@@ -0,0 +1,52 @@
+"""
+Standard library usage statistics
+"""
+
+import pytest
+
+from gardener.analysis.stdlib_usage import stdlib_module, stdlib_usage
+
+
+@pytest.mark.unit
+@pytest.mark.parametrize(
+    "language, import_name, expected",
+    [
+        ("python", "os", "os"),
+        ("python", "requests", None),
+        ("javascript", "node:fs", "fs"),
+        ("typescript", "fs/promises", "fs"),
+        ("javascript", "node:test", "test"),
+        ("javascript", "test", None),
+        ("go", "net/http", "net/http"),
+        ("go", "github.com/spf13/cobra", None),
+        ("rust", "std", "std"),
+        ("rust", "serde", None),
+        ("solidity", "std", None),
+    ],
+)
+def test_stdlib_module_classifies_imports_per_language(language, import_name, expected):
+    assert stdlib_module(language, import_name) == expected
+
+
+@pytest.mark.unit
+def test_stdlib_usage_counts_importing_files_per_module():
+    file_imports = {
+        "app.py": ["os", "json", "requests"],
+        "util.py": ["os"],
+        "setup.py": ["setuptools"],
+        "index.js": ["node:fs", "fs", "lodash"],
+        "main.go": ["fmt", "net/http"],
+    }
+    source_files = {
+        "app.py": {"language": "python"},
+        "util.py": {"language": "python"},
+        "setup.py": {"language": "python"},
+        "index.js": {"language": "javascript"},
+        "main.go": {"language": "go"},
+    }
+
+    assert stdlib_usage(file_imports, source_files) == {
+        "go": {"files": 1, "modules": {"fmt": 1, "net/http": 1}},
+        "javascript": {"files": 1, "modules": {"fs": 1}},
+        "python": {"files": 2, "modules": {"os": 2, "json": 1}},
+    }
@@ -0,0 +1,70 @@
+"""
+Advisory suggestion passes
+"""
+
+import pytest
+
+from gardener.analysis import suggestions
+from gardener.analysis.suggestions import load_equivalences, run_suggestion_passes, suggest_alternatives
+from gardener.common.defaults import ConfigOverride
+
+PACKAGES = {
+    "request": {"ecosystem": "npm"},
+    "react": {"ecosystem": "npm"},
+    "mock": {"ecosystem": "pypi"},
+    "left-pad": {"ecosystem": "npm"},
+}
+
+
+@pytest.mark.unit
+def test_builtin_dataset_flags_packages_with_alternatives():
+    findings = suggest_alternatives({"request": {"ecosystem": "npm"}, "react": {"ecosystem": "npm"}})
+
+    assert findings == [
+        {
+            "kind": "alternative",
+            "severity": "advisory",
+            "package": "request",
+            "ecosystem": "npm",
+            "alternatives": ["undici", "fetch (built-in)"],
+            "reason": "Deprecated since 2020",
+        }
+    ]
+
+
+@pytest.mark.unit
+def test_configured_dataset_extends_overrides_and_prunes(tmp_path):
+    dataset = tmp_path / "alternatives.yml"
+    dataset.write_text(
+        "npm:\n"
+        "  react:\n"
+        "    alternatives: [preact]\n"
+        "    reason: Smaller runtime\n"
+        "  left-pad: null\n"
+        "pypi:\n"
+        "  mock:\n"
+        "    alternatives: [pytest-mock]\n"
+    )
+
+    with ConfigOverride({"ALTERNATIVES_DATASET": str(dataset)}):
+        findings = suggest_alternatives(PACKAGES)
+
+    assert [(f["package"], f["alternatives"], f["reason"]) for f in findings] == [
+        ("mock", ["pytest-mock"], ""),
+        ("react", ["preact"], "Smaller runtime"),
+        ("request", ["undici", "fetch (built-in)"], "Deprecated since 2020"),
+    ]
+    with pytest.raises(ValueError, match="must map ecosystems"):
+        dataset.write_text("- request\n")
+        load_equivalences(str(dataset))
+
+
+@pytest.mark.unit
+def test_registered_passes_run_and_failures_are_skipped(monkeypatch):
+    monkeypatch.setattr(suggestions, "SUGGESTION_PASSES", dict(suggestions.SUGGESTION_PASSES))
+    suggestions.register_suggestion_pass("pinned", lambda packages: [{"kind": "unpinned", "package": "react"}])
+    suggestions.register_suggestion_pass("broken", lambda packages: 1 / 0)
+
+    findings = run_suggestion_passes({"request": {"ecosystem": "npm"}, "react": {"ecosystem": "npm"}})
+
+    assert [(f["pass"], f["package"]) for f in findings] == [("alternatives", "request"), ("pinned", "react")]
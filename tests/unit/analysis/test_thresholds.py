@@ -0,0 +1,58 @@
+"""
+Minimum-evidence thresholds for the funding split
+"""
+
+import pytest
+
+from gardener.analysis.bundle import request_hook
+from gardener.analysis.main import DependencyAnalyzer
+from gardener.analysis.thresholds import apply_evidence_thresholds, evidence_counts
+from gardener.common.defaults import ConfigOverride
+from gardener.treewalk.go import GoLanguageHandler
+
+
+@pytest.mark.unit
+def test_evidence_counts_imports_per_file_and_distinct_files():
+    packages = {"lodash": {"ecosystem": "npm"}, "react": {"ecosystem": "npm", "import_names": ["react", "react-dom"]}}
+    file_imports = {
+        "a.js": ["react", "react-dom", "lodash/get"],
+        "b.js": ["react"],
+        "c.js": ["left-pad"],
+    }
+
+    assert evidence_counts(packages, file_imports) == {
+        "react": {"import_count": 3, "files": 2},
+        "lodash": {"import_count": 1, "files": 1},
+    }
+    kept, below = apply_evidence_thresholds([("react", 0.6), ("lodash", 0.4)], packages, file_imports, min_files=2)
+    assert kept == [("react", 0.6)]
+    assert below == [{"package_name": "lodash", "ecosystem": "npm", "import_count": 1, "files": 1}]
+    assert apply_evidence_thresholds([("lodash", 0.4)], packages, file_imports) == ([("lodash", 0.4)], [])
+
+
+@pytest.mark.unit
+def test_packages_below_threshold_leave_the_funding_split(tmp_path, monkeypatch):
+    (tmp_path / "go.mod").write_text(
+        "module example.com/app\n\ngo 1.21\n\n"
+        "require (\n\tgithub.com/spf13/cobra v1.8.0\n\tgithub.com/kr/pretty v0.3.1\n)\n"
+    )
+    (tmp_path / "main.go").write_text("package main\n")
+    (tmp_path / "cmd.go").write_text("package main\n")
+
+    def extract_imports(self, tree_node, rel_path, *args, **kwargs):
+        if rel_path == "main.go":
+            return ["github.com/spf13/cobra", "github.com/kr/pretty"], []
+        return ["github.com/spf13/cobra"], []
+
+    monkeypatch.setattr(GoLanguageHandler, "parse_source", lambda self, code, rel_path: object())
+    monkeypatch.setattr(GoLanguageHandler, "extract_imports", extract_imports)
+
+    with request_hook(lambda url: None), ConfigOverride({"MIN_FILES": 2}):
+        results = DependencyAnalyzer(verbose=False).analyze(str(tmp_path))
+
+    assert [dep["package_name"] for dep in results["top_dependencies"]] == ["github.com/spf13/cobra"]
+    assert results["top_dependencies"][0]["percentage"] == 100
+    assert results["below_threshold"] == [
+        {"package_name": "github.com/kr/pretty", "ecosystem": "go", "import_count": 1, "files": 1}
+    ]
+    assert "github.com/kr/pretty" in results["external_packages"]
@@ -0,0 +1,54 @@
+"""
+Pinned toolchains from version manifests
+"""
+
+import json
+
+import pytest
+
+from gardener.analysis.main import DependencyAnalyzer
+from gardener.analysis.toolchains import extract_toolchains
+
+
+@pytest.mark.unit
+def test_version_manifests_are_collected_into_toolchains(tmp_path):
+    (tmp_path / ".tool-versions").write_text("# asdf\nnodejs 20.11.0 18.19.0\nterraform 1.7.4\n")
+    (tmp_path / ".nvmrc").write_text("v20.11.0\n")
+    (tmp_path / "rust-toolchain.toml").write_text(
+        '[toolchain]\nchannel = "1.76.0"\ncomponents = ["clippy", "rustfmt"]\n'
+    )
+    (tmp_path / "package.json").write_text(
+        json.dumps({"name": "app", "volta": {"node": "20.11.0", "pnpm": "8.15.4"}}, indent=2)
+    )
+
+    analyzer = DependencyAnalyzer(verbose=False)
+    analyzer.discover_packages(str(tmp_path), ["javascript"])
+    toolchains = analyzer.repo_analyzer.toolchains
+
+    assert [(t["tool"], t["version"], t["source"], t["file"], t["line"]) for t in toolchains] == [
+        ("node", "20.11.0", "nvm", ".nvmrc", 1),
+        ("node", "20.11.0", "asdf", ".tool-versions", 2),
+        ("terraform", "1.7.4", "asdf", ".tool-versions", 3),
+        ("node", "20.11.0", "volta", "package.json", 4),
+        ("pnpm", "8.15.4", "volta", "package.json", 5),
+        ("rust", "1.76.0", "rustup", "rust-toolchain.toml", 2),
+    ]
+    assert toolchains[1]["fallbacks"] == ["18.19.0"]
+    assert toolchains[-1]["components"] == ["clippy", "rustfmt"]
+    assert analyzer.repo_analyzer.tooling_dependencies == []
+
+
+@pytest.mark.unit
+def test_mise_tools_and_legacy_rust_toolchain():
+    mise = '[tools]\nnode = "lts"\npython = ["3.12", "3.11"]\nterraform = { version = "1.7" }\n'
+
+    toolchains = extract_toolchains(mise, "mise.toml", "mise.toml")
+
+    assert [(t["tool"], t["version"], t.get("fallbacks")) for t in toolchains] == [
+        ("node", "lts", None),
+        ("python", "3.12", ["3.11"]),
+        ("terraform", "1.7", None),
+    ]
+    assert extract_toolchains("nightly-2024-02-01\n", "rust-toolchain", "rust-toolchain")[0]["version"] == (
+        "nightly-2024-02-01"
+    )
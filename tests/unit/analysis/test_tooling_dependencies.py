@@ -0,0 +1,258 @@
+"""
+Imperative install-command and tool-invocation detection in shell scripts and task runners
+"""
+
+import json
+
+import pytest
+
+from gardener.analysis.main import DependencyAnalyzer
+from gardener.analysis.tooling import (
+    collect_tooling_dependencies,
+    extract_install_commands,
+    extract_tool_invocations,
+    task_runner_lines,
+)
+
+
+def _names(findings):
+    return [(f["ecosystem"], f["name"], f["version"]) for f in findings]
+
+
+@pytest.mark.unit
+def test_shell_script_install_commands():
+    script = (
+        "#!/usr/bin/env bash\n"
+        "set -euo pipefail\n"
+        "# pip install commented-out\n"
+        "python3 -m pip install --upgrade pip 'black==24.1.0' -r requirements.txt\n"
+        "sudo npm install -g typescript@5.4.2 @nestjs/cli\n"
+        "npm install lodash\n"
+        "cargo install --locked cargo-nextest --version 0.9.67 && cargo install --path .\n"
+        "go install golang.org/x/tools/cmd/goimports@latest\n"
+        "curl --proto '=https' -sSf https://sh.rustup.rs | sh -s -- -y\n"
+    )
+    findings = extract_install_commands(script, "scripts/setup.sh")
+
+    assert _names(findings) == [
+        ("pypi", "pip", ""),
+        ("pypi", "black", "==24.1.0"),
+        ("npm", "typescript", "5.4.2"),
+        ("npm", "@nestjs/cli", ""),
+        ("cargo", "cargo-nextest", "0.9.67"),
+        ("go", "golang.org/x/tools/cmd/goimports", "latest"),
+        ("script", "https://sh.rustup.rs", ""),
+    ]
+    assert findings[2]["line"] == 5
+
+
+@pytest.mark.unit
+def test_makefile_only_scans_recipes_and_joins_continuations():
+    makefile = (
+        "PIP := pip install not-a-recipe\n"
+        "tools:\n"
+        "\t@pip install \\\n"
+        "\t\truff mypy\n"
+        "\t-go install github.com/golangci/golangci-lint/cmd/golangci-lint@v1.57.0\n"
+    )
+    findings = extract_install_commands(makefile, "Makefile", is_makefile=True)
+
+    assert _names(findings) == [
+        ("pypi", "ruff", ""),
+        ("pypi", "mypy", ""),
+        ("go", "github.com/golangci/golangci-lint/cmd/golangci-lint", "v1.57.0"),
+    ]
+    assert findings[0]["line"] == 3
+
+
+@pytest.mark.unit
+def test_collect_aggregates_and_flags_declared_packages(tmp_path):
+    (tmp_path / "a.sh").write_text("pip install black\n")
+    (tmp_path / "b.sh").write_text("pip3 install black==24.1.0 httpie\n")
+    scripts = [str(tmp_path / "a.sh"), str(tmp_path / "b.sh")]
+
+    tooling = collect_tooling_dependencies(scripts, {"black": {"ecosystem": "pypi"}}, None, None, str(tmp_path))
+
+    assert [t["name"] for t in tooling] == ["black", "httpie"]
+    black = tooling[0]
+    assert black["declared_in_manifest"] is True
+    assert black["version"] == "==24.1.0"
+    assert [c["file"] for c in black["install_commands"]] == ["a.sh", "b.sh"]
+    assert tooling[1]["declared_in_manifest"] is False
+
+
+@pytest.mark.unit
+def test_justfile_and_taskfile_recipes_report_invoked_tools():
+    justfile = (
+        "set shell := [\"bash\", \"-c\"]\n"
+        "proto_dir := \"api\"\n"
+        "\n"
+        "gen:\n"
+        "    protoc -I {{proto_dir}} --go_out=. {{proto_dir}}/*.proto\n"
+        "    @buf lint && echo ok\n"
+    )
+    taskfile = (
+        "version: '3'\n"
+        "tasks:\n"
+        "  infra:\n"
+        "    cmds:\n"
+        "      - terraform -chdir=infra apply -auto-approve\n"
+        "      - cmd: kubectl get pods -o json | jq '.items[].metadata.name'\n"
+        "  lint: golangci-lint run ./...\n"
+    )
+
+    just = extract_tool_invocations(task_runner_lines(justfile, "justfile"), "justfile")
+    task = extract_tool_invocations(task_runner_lines(taskfile, "Taskfile.yml"), "Taskfile.yml")
+
+    assert [(f["name"], f["line"]) for f in just] == [("protoc", 5), ("buf", 6)]
+    assert [(f["name"], f["line"]) for f in task] == [("terraform", 5), ("kubectl", 6), ("jq", 6), ("golangci-lint", 7)]
+    assert task[2]["command"] == "jq '.items[].metadata.name'"
+
+
+@pytest.mark.unit
+def test_collect_reports_makefile_tools_with_tooling_scope(tmp_path):
+    (tmp_path / "Makefile").write_text(
+        "DOCKER_TAG ?= dev\n"
+        "image:\n"
+        "\tdocker build -t app:$(DOCKER_TAG) .\n"
+        "tools:\n"
+        "\tgo install github.com/golangci/golangci-lint/cmd/golangci-lint@v1.57.0\n"
+        "lint:\n"
+        "\t@golangci-lint run\n"
+        "\tdocker run --rm -i hadolint/hadolint < Dockerfile\n"
+    )
+    (tmp_path / "setup.sh").write_text("docker pull postgres:16\n")
+
+    tooling = collect_tooling_dependencies(
+        [str(tmp_path / "Makefile"), str(tmp_path / "setup.sh")], {}, None, None, str(tmp_path)
+    )
+
+    assert [(t["ecosystem"], t["name"]) for t in tooling] == [
+        ("go", "github.com/golangci/golangci-lint/cmd/golangci-lint"),
+        ("tool", "docker"),
+        ("tool", "golangci-lint"),
+    ]
+    docker = tooling[1]
+    assert docker["scope"] == "tooling"
+    assert [(c["file"], c["line"]) for c in docker["invocations"]] == [("Makefile", 3), ("Makefile", 8)]
+    assert "scope" not in tooling[0]
+
+
+@pytest.mark.unit
+def test_pre_commit_hook_repositories_are_reported_with_pinned_revs(tmp_path):
+    (tmp_path / ".pre-commit-config.yaml").write_text(
+        "repos:\n"
+        "  - repo: https://github.com/pre-commit/pre-commit-hooks\n"
+        "    rev: v4.5.0\n"
+        "    hooks:\n"
+        "      - id: trailing-whitespace\n"
+        "      - id: end-of-file-fixer\n"
+        "  - repo: git@github.com:psf/black.git\n"
+        "    rev: 24.2.0\n"
+        "    hooks:\n"
+        "      - id: black\n"
+        "  - repo: local\n"
+        "    hooks:\n"
+        "      - id: pytest\n"
+        "  - repo: meta\n"
+        "    hooks:\n"
+        "      - id: check-useless-excludes\n"
+    )
+
+    analyzer = DependencyAnalyzer(verbose=False)
+    analyzer.discover_packages(str(tmp_path))
+    tooling = analyzer.repo_analyzer.tooling_dependencies
+
+    assert [(t["ecosystem"], t["name"], t["version"]) for t in tooling] == [
+        ("pre-commit", "github.com/pre-commit/pre-commit-hooks", "v4.5.0"),
+        ("pre-commit", "github.com/psf/black", "24.2.0"),
+    ]
+    hooks = tooling[0]
+    assert hooks["scope"] == "tooling"
+    assert hooks["repository_url"] == "https://github.com/pre-commit/pre-commit-hooks"
+    assert hooks["declarations"] == [
+        {"file": ".pre-commit-config.yaml", "line": 2, "hooks": ["trailing-whitespace", "end-of-file-fixer"]}
+    ]
+
+
+@pytest.mark.unit
+def test_devcontainer_image_features_and_post_create_installs_are_reported(tmp_path):
+    (tmp_path / ".devcontainer").mkdir()
+    (tmp_path / ".devcontainer" / "devcontainer.json").write_text(
+        "{\n"
+        "  // Python dev environment\n"
+        '  "image": "mcr.microsoft.com/devcontainers/python:1-3.12-bullseye",\n'
+        '  "features": {\n'
+        '    "ghcr.io/devcontainers/features/node:1": {"version": "lts"},\n'
+        '    "./local-feature": {},\n'
+        "  },\n"
+        '  "postCreateCommand": "pip install pre-commit==3.6.0 && npm install -g pnpm",\n'
+        "}\n"
+    )
+
+    analyzer = DependencyAnalyzer(verbose=False)
+    analyzer.discover_packages(str(tmp_path))
+    tooling = {t["ecosystem"]: t for t in analyzer.repo_analyzer.tooling_dependencies}
+
+    image = tooling["container-image"]
+    assert (image["name"], image["version"], image["scope"]) == (
+        "mcr.microsoft.com/devcontainers/python",
+        "1-3.12-bullseye",
+        "tooling",
+    )
+    feature = tooling["devcontainer-feature"]
+    assert (feature["name"], feature["version"]) == ("ghcr.io/devcontainers/features/node", "1")
+    assert feature["repository_url"] == "https://github.com/devcontainers/features"
+    assert feature["declarations"] == [{"file": ".devcontainer/devcontainer.json", "line": 5}]
+    assert (tooling["pypi"]["name"], tooling["pypi"]["version"]) == ("pre-commit", "==3.6.0")
+    assert tooling["npm"]["install_commands"][0]["line"] == 8
+
+
+@pytest.mark.unit
+def test_package_json_scripts_link_binaries_to_providing_packages(tmp_path):
+    package = {
+        "name": "web",
+        "scripts": {
+            "build": "cross-env NODE_ENV=production tsup src/index.ts && tsc --noEmit",
+            "lint": "eslint . --ext .ts",
+            "test": "vitest run",
+            "scaffold": "npx -y create-vite@5.2.0 app --template react-ts",
+            "e2e": "pnpm exec playwright test",
+            "gen": "node scripts/gen.js && protoc --ts_out=src proto/*.proto",
+            "start": "npm run build && node dist/index.js",
+        },
+        "devDependencies": {
+            "cross-env": "^7.0.3",
+            "eslint": "^9.0.0",
+            "tsup": "^8.0.0",
+            "typescript": "^5.4.0",
+            "vitest": "^1.5.0",
+            "@playwright/test": "^1.43.0",
+        },
+    }
+    (tmp_path / "package.json").write_text(json.dumps(package, indent=2))
+
+    tooling = collect_tooling_dependencies(
+        [str(tmp_path / "package.json")], {"eslint": {"ecosystem": "npm"}}, None, None, str(tmp_path)
+    )
+    by_name = {(t["ecosystem"], t["name"]): t for t in tooling}
+
+    assert sorted(by_name) == [
+        ("npm", "@playwright/test"),
+        ("npm", "create-vite"),
+        ("npm", "cross-env"),
+        ("npm", "eslint"),
+        ("npm", "tsup"),
+        ("npm", "typescript"),
+        ("npm", "vitest"),
+        ("tool", "protoc"),
+    ]
+    eslint = by_name[("npm", "eslint")]
+    assert (eslint["version"], eslint["scope"], eslint["declared_in_manifest"]) == ("^9.0.0", "tooling", True)
+    assert eslint["invocations"] == [
+        {"file": "package.json", "line": 5, "command": "eslint . --ext .ts", "script": "lint", "bin": "eslint"}
+    ]
+    assert by_name[("npm", "typescript")]["invocations"][0]["bin"] == "tsc"
+    assert by_name[("npm", "create-vite")]["version"] == "5.2.0"
+    assert by_name[("npm", "create-vite")]["invocations"][0]["runner"] == "npx"
+    assert by_name[("npm", "@playwright/test")]["invocations"][0]["script"] == "e2e"
@@ -0,0 +1,60 @@
+"""
+TypeScript project references and per-project evidence
+"""
+
+import pytest
+
+from gardener.analysis.ts_projects import group_by_project, read_ts_projects
+
+CONFIGS = {
+    "tsconfig.json": '{\n  // Solution config\n  "files": [],\n  "references": [{"path": "./packages/core"}, '
+    '{"path": "./packages/app/tsconfig.app.json"}, {"path": "./packages/gone"}],\n}',
+    "packages/core/tsconfig.json": '{"compilerOptions": {"composite": true}, "include": ["src"]}',
+    "packages/app/tsconfig.app.json": '{"compilerOptions": {"composite": true, "allowJs": true},'
+    ' "include": ["src/**/*"], "exclude": ["**/*.test.ts"], "references": [{"path": "../core"}]}',
+}
+
+
+@pytest.mark.unit
+def test_files_are_attributed_to_referenced_projects(tmp_path):
+    for rel_path, content in CONFIGS.items():
+        (tmp_path / rel_path).parent.mkdir(parents=True, exist_ok=True)
+        (tmp_path / rel_path).write_text(content)
+    found = [str(tmp_path / "tsconfig.json"), str(tmp_path / "packages/core/tsconfig.json")]
+
+    projects = read_ts_projects(str(tmp_path), found)
+
+    assert sorted(projects) == ["packages/app/tsconfig.app.json", "packages/core/tsconfig.json", "tsconfig.json"]
+    assert projects["tsconfig.json"]["missing_references"] == ["packages/gone/tsconfig.json"]
+    source_files = {
+        "packages/core/src/index.ts": {"language": "typescript"},
+        "packages/core/scripts/gen.ts": {"language": "typescript"},
+        "packages/app/src/main.ts": {"language": "typescript"},
+        "packages/app/src/legacy.js": {"language": "javascript"},
+        "packages/app/src/main.test.ts": {"language": "typescript"},
+    }
+    file_imports = {
+        "packages/core/src/index.ts": ["zod"],
+        "packages/app/src/main.ts": ["react", "zod"],
+        "packages/app/src/legacy.js": ["jquery"],
+        "packages/app/src/main.test.ts": ["vitest"],
+    }
+    external_packages = {name: {"ecosystem": "npm"} for name in ("zod", "react", "jquery", "vitest")}
+    local_imports_map = {"packages/app/src/main.ts": ["packages/core/src/index.ts"]}
+
+    reports = group_by_project(projects, source_files, external_packages, file_imports, local_imports_map)
+
+    assert [(report["project"], report["files"]) for report in reports] == [
+        ("packages/app/tsconfig.app.json", 2),
+        ("packages/core/tsconfig.json", 1),
+        ("tsconfig.json", 0),
+    ]
+    assert reports[0]["imports"] == {
+        "jquery": ["packages/app/src/legacy.js"],
+        "react": ["packages/app/src/main.ts"],
+        "zod": ["packages/app/src/main.ts"],
+    }
+    assert reports[0]["references"] == ["packages/core/tsconfig.json"]
+    assert reports[0]["depends_on"] == ["packages/core/tsconfig.json"]
+    assert reports[1]["imports"] == {"zod": ["packages/core/src/index.ts"]} and reports[1]["composite"] is True
+    assert reports[2]["references"] == ["packages/app/tsconfig.app.json", "packages/core/tsconfig.json"]
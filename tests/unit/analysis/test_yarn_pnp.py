@@ -0,0 +1,87 @@
+"""
+Yarn Plug'n'Play runtime state and zero-install caches
+"""
+
+import json
+
+import pytest
+
+from gardener.analysis.yarn_pnp import apply_yarn_pnp, parse_pnp_state
+
+STATE = {
+    "packageRegistryData": [
+        [None, [[None, {"packageLocation": "./", "packageDependencies": [["monorepo", "workspace:."]]}]]],
+        [
+            "web",
+            [
+                [
+                    "workspace:packages/web",
+                    {
+                        "packageLocation": "./packages/web/",
+                        "packageDependencies": [
+                            ["lodash", "npm:4.17.21"],
+                            ["react-dom", "virtual:3f6a#npm:18.2.0"],
+                            ["legacy-query", ["jquery", "npm:3.7.1"]],
+                            ["types", None],
+                        ],
+                    },
+                ]
+            ],
+        ],
+        ["lodash", [["npm:4.17.21", {"linkType": "HARD"}]]],
+        ["react-dom", [["virtual:3f6a#npm:18.2.0", {}], ["npm:18.2.0", {}]]],
+        ["jquery", [["npm:3.7.1", {}]]],
+        ["scheduler", [["npm:0.23.0", {}]]],
+    ]
+}
+
+
+@pytest.mark.unit
+def test_runtime_state_embedded_in_pnp_cjs_is_parsed():
+    content = (
+        "#!/usr/bin/env node\n"
+        "const RAW_RUNTIME_STATE =\n"
+        "'{\\\n"
+        '  "__info": ["Don\\\'t touch"],\\\n'
+        '  "packageRegistryData": []\\\n'
+        "}';\n"
+    )
+
+    assert parse_pnp_state(content, ".pnp.cjs") == {"__info": ["Don't touch"], "packageRegistryData": []}
+
+
+@pytest.mark.unit
+def test_workspace_resolutions_aliases_and_cache_archives_are_applied(tmp_path):
+    (tmp_path / ".pnp.data.json").write_text(json.dumps(STATE))
+    (tmp_path / ".yarn/cache").mkdir(parents=True)
+    for archive in ("lodash-npm-4.17.21-6382451519-eb835a2e51.zip", "@types-react-npm-18.2.7-4b8a2d8f3c-1c2f.zip"):
+        (tmp_path / ".yarn/cache" / archive).write_bytes(b"")
+    packages = {
+        "lodash": {"ecosystem": "npm", "version": "^4.17.0"},
+        "react-dom": {"ecosystem": "npm", "version": "^18.0.0"},
+        "legacy-query": {"ecosystem": "npm", "version": "npm:jquery@3.7.1"},
+        "@types/react": {"ecosystem": "npm", "version": "^18.0.0"},
+    }
+
+    summary = apply_yarn_pnp(packages, str(tmp_path))
+
+    assert summary == {
+        "state": ".pnp.data.json",
+        "workspaces": ["packages/web"],
+        "aliases": {"legacy-query": "jquery"},
+        "cached": 2,
+    }
+    assert packages["lodash"]["pnp_resolved"] == {"packages/web": "4.17.21"}
+    assert packages["lodash"]["locked_version"] == "4.17.21"
+    assert packages["lodash"]["yarn_cache"] == ".yarn/cache/lodash-npm-4.17.21-6382451519-eb835a2e51.zip"
+    assert packages["react-dom"]["locked_version"] == "18.2.0"
+    assert "legacy-query" not in packages
+    assert packages["jquery"]["import_names"] == ["legacy-query"]
+    assert packages["jquery"]["locked_version"] == "3.7.1"
+    assert packages["scheduler"] == {
+        "ecosystem": "npm",
+        "direct": False,
+        "transitive": True,
+        "locked_version": "0.23.0",
+    }
+    assert packages["@types/react"]["yarn_cache"] == ".yarn/cache/@types-react-npm-18.2.7-4b8a2d8f3c-1c2f.zip"
@@ -0,0 +1,62 @@
+"""
+Analyzing bare repositories from the object store
+"""
+
+import glob
+import io
+import json
+import os
+import subprocess
+import tempfile
+
+import pytest
+
+from gardener.analysis.bundle import request_hook
+from gardener.analysis.main import run_analysis
+from gardener.common.git_snapshot import is_bare_repository
+from gardener.persistence.stream import StreamPersistence
+
+
+def _git(cwd, *args):
+    env = {**os.environ, "GIT_AUTHOR_NAME": "t", "GIT_AUTHOR_EMAIL": "t@t", "GIT_COMMITTER_NAME": "t"}
+    env["GIT_COMMITTER_EMAIL"] = "t@t"
+    return subprocess.run(["git", *args], cwd=cwd, env=env, check=True, capture_output=True, text=True).stdout
+
+
+def _bare_repo(tmp_path):
+    work = tmp_path / "work"
+    work.mkdir()
+    _git(work, "init", "-q")
+    (work / "package.json").write_text(json.dumps({"name": "app", "dependencies": {"lodash": "^4.17.21"}}))
+    _git(work, "add", "-A")
+    _git(work, "commit", "-qm", "v1")
+    _git(work, "tag", "v1")
+    (work / "package.json").write_text(json.dumps({"name": "app", "dependencies": {"react": "^18.2.0"}}))
+    _git(work, "commit", "-qam", "v2")
+    _git(tmp_path, "clone", "-q", "--bare", str(work), "app.git")
+    return tmp_path / "app.git"
+
+
+@pytest.mark.unit
+def test_bare_repository_is_read_from_the_object_store_at_a_ref(tmp_path):
+    bare = _bare_repo(tmp_path)
+    assert is_bare_repository(str(bare))
+    snapshots = glob.glob(os.path.join(tempfile.gettempdir(), "gardener-snapshot-*"))
+
+    with request_hook(lambda url: None):
+        head = run_analysis(str(bare), persistence=StreamPersistence(io.StringIO()))
+        tagged = run_analysis(str(bare), persistence=StreamPersistence(io.StringIO()), ref="v1")
+
+    assert list(head["external_packages"]) == ["react"]
+    assert list(tagged["external_packages"]) == ["lodash"]
+    assert tagged["reproduce"]["git"] == {
+        "repository": str(bare),
+        "ref": "v1",
+        "commit": _git(bare, "rev-parse", "v1^{commit}").strip(),
+        "bare": True,
+        "files": 1,
+        "skipped": 0,
+    }
+    # Nothing is checked out into the repository and the snapshots are removed
+    assert not (bare / "index").exists() and not (bare / "package.json").exists()
+    assert glob.glob(os.path.join(tempfile.gettempdir(), "gardener-snapshot-*")) == snapshots
@@ -0,0 +1,93 @@
+"""
+Deno specifiers, deno.json import maps and registry URL resolution
+"""
+
+import json
+
+import pytest
+
+from gardener.package_metadata.url_resolver import resolve_package_urls
+from gardener.treewalk.deno import parse_deno_specifier
+from gardener.treewalk.javascript import JavaScriptLanguageHandler
+
+
+@pytest.mark.unit
+@pytest.mark.parametrize(
+    "specifier,expected",
+    [
+        ("https://deno.land/x/oak@v12.6.1/mod.ts", ("deno", "deno.land/x/oak", "v12.6.1")),
+        ("https://deno.land/std@0.200.0/path/mod.ts", ("deno", "deno.land/std", "0.200.0")),
+        ("npm:chalk@5.3.0", ("npm", "chalk", "5.3.0")),
+        ("npm:@octokit/rest@20/dist", ("npm", "@octokit/rest", "20")),
+        ("jsr:@std/path@^1.0.0", ("jsr", "@std/path", "^1.0.0")),
+        ("https://jsr.io/@std/assert/1.0.2/mod.ts", ("jsr", "@std/assert", "1.0.2")),
+        ("https://esm.sh/v135/preact@10.19.2/hooks", ("npm", "preact", "10.19.2")),
+        ("https://cdn.jsdelivr.net/npm/lodash-es@4.17.21/lodash.js", ("npm", "lodash-es", "4.17.21")),
+        (
+            "https://raw.githubusercontent.com/denoland/deno_graph/0.69.0/mod.ts",
+            ("github", "github.com/denoland/deno_graph", "0.69.0"),
+        ),
+        ("https://example.com/lib.ts", None),
+        ("./local.ts", None),
+    ],
+)
+def test_parse_deno_specifier(specifier, expected):
+    assert parse_deno_specifier(specifier) == expected
+
+
+@pytest.mark.unit
+def test_deno_json_and_import_map(tmp_path):
+    (tmp_path / "deno.jsonc").write_text(
+        """{
+  // Published to JSR
+  "name": "@acme/server",
+  "importMap": "./vendor_map.json",
+  "imports": {
+    "oak": "https://deno.land/x/oak@v12.6.1/mod.ts",
+    "@std/path": "jsr:@std/path@^1.0.0",
+    "chalk": "npm:chalk@5.3.0",
+    "~/": "./src/",
+  },
+}"""
+    )
+    (tmp_path / "vendor_map.json").write_text(
+        json.dumps({"scopes": {"https://deno.land/x/": {"std/": "https://deno.land/std@0.200.0/"}}})
+    )
+    handler = JavaScriptLanguageHandler()
+
+    packages = handler.process_manifest(str(tmp_path / "deno.jsonc"), {})
+
+    assert packages == {
+        "deno.land/x/oak": {"ecosystem": "deno", "version": "v12.6.1", "import_names": ["deno.land/x/oak", "oak"]},
+        "@std/path": {"ecosystem": "jsr", "version": "^1.0.0", "import_names": ["@std/path"]},
+        "chalk": {"ecosystem": "npm", "version": "5.3.0", "import_names": ["chalk"]},
+        "deno.land/std": {"ecosystem": "deno", "version": "0.200.0", "import_names": ["deno.land/std", "std"]},
+    }
+    assert handler.normalize_package_name("jsr:@std/path@1/posix") == "@std/path"
+    assert handler.normalize_package_name("https://deno.land/x/oak/mod.ts") == "deno.land/x/oak"
+
+
+@pytest.mark.unit
+def test_jsr_and_deno_land_url_resolution(offline_mode):
+    packages = {
+        "@std/path": {"ecosystem": "jsr"},
+        "deno.land/x/oak": {"ecosystem": "deno"},
+        "deno.land/std": {"ecosystem": "deno"},
+    }
+    responses = {
+        "https://api.jsr.io/scopes/std/packages/path": json.dumps(
+            {"githubRepository": {"owner": "denoland", "name": "std"}}
+        ),
+        "https://apiland.deno.dev/v2/modules/oak": json.dumps(
+            {"upload_options": {"type": "github", "repository": "oakserver/oak"}}
+        ),
+    }
+
+    with offline_mode.set_responses(responses):
+        resolved = resolve_package_urls(packages, logger=None, cache={})
+
+    assert resolved == {
+        "@std/path": "https://github.com/denoland/std",
+        "deno.land/x/oak": "https://github.com/oakserver/oak",
+        "deno.land/std": "https://github.com/denoland/std",
+    }
@@ -7,7 +7,7 @@ from collections import defaultdict
 
 import pytest
 
-from gardener.treewalk.go import GoLanguageHandler
+from gardener.treewalk.go import GoLanguageHandler, build_annotation, filename_build_constraint, header_build_constraint
 
 
 def mock_resolve_local_go(importing_file_rel_path, module_str):
@@ -63,3 +63,52 @@ def test_main_go_fixture_imports_extracted(tree_parser, logger):
     file_key = f"tests/fixtures/go/{fixture_rel_path}"
     assert file_key in components_dict
     assert ("github.com/gin-gonic/gin", "github.com/gin-gonic/gin.gin") in set(components_dict[file_key])
+
+
+@pytest.mark.unit
+def test_build_constraints_annotate_imports(tree_parser, logger):
+    code = (
+        "//go:build (linux || darwin) && !integration\n\n"
+        "package watcher\n\n"
+        'import (\n\t"os"\n\t"github.com/fsnotify/fsnotify"\n)\n'
+    )
+    annotations = {}
+    external, _ = GoLanguageHandler(logger=logger).extract_imports(
+        tree_parser("go", code), "watch/watch_amd64_test.go", defaultdict(list), lambda *_: None, None, annotations
+    )
+
+    assert "github.com/fsnotify/fsnotify" in external
+    assert annotations["github.com/fsnotify/fsnotify"] == {
+        "go_build": "((linux || darwin) && !integration) && amd64",
+        "platforms": ["linux", "macos"],
+        "build_tags": ["!integration"],
+        "test_only": True,
+    }
+
+
+@pytest.mark.unit
+def test_build_constraints_from_file_names_and_legacy_lines():
+    assert filename_build_constraint("sys/proc_windows.go") == ("windows", False)
+    assert filename_build_constraint("sys/proc_linux_arm64_test.go") == ("linux && arm64", True)
+    # The part before the first underscore is the file's name, not a platform
+    assert filename_build_constraint("linux.go") == (None, False)
+    assert header_build_constraint(["// +build linux,cgo darwin\n// +build !nomsgpack"]) == (
+        "(linux && cgo || darwin) && !nomsgpack"
+    )
+    assert build_annotation("!windows", False) == {"go_build": "!windows", "platforms": ["!windows"]}
+
+
+@pytest.mark.unit
+def test_build_expressions_negate_grouped_terms():
+    assert build_annotation("!(linux || darwin)", False)["platforms"] == ["!linux", "!macos"]
+    assert build_annotation("integration && !(nomsgpack || purego)", False)["build_tags"] == [
+        "!nomsgpack",
+        "!purego",
+        "integration",
+    ]
+    assert build_annotation("!!linux && (windows || !darwin && nocgo)", False) == {
+        "go_build": "!!linux && (windows || !darwin && nocgo)",
+        "platforms": ["!macos", "linux", "windows"],
+        "build_tags": ["nocgo"],
+    }
+    assert build_annotation("linux && (", False) == {"go_build": "linux && ("}
@@ -0,0 +1,96 @@
+"""
+go.mod requirements and replacements, go.sum module versions
+"""
+
+import pytest
+
+from gardener.treewalk.go import GoLanguageHandler
+
+GO_MOD = """module example.com/app
+
+go 1.21
+
+require (
+\tgithub.com/spf13/cobra v1.8.0
+\tgithub.com/pkg/errors v0.9.1 // indirect
+\tgolang.org/x/text v0.14.0
+\texample.com/shared v0.0.0-00010101000000-000000000000
+)
+
+require github.com/kr/pretty v0.3.1
+
+replace golang.org/x/text => github.com/fork/text v0.14.1
+
+replace example.com/shared => ../shared
+
+replace github.com/kr/pretty v0.2.0 => github.com/fork/pretty v0.2.1
+"""
+
+GO_SUM = """github.com/spf13/cobra v1.7.0/go.mod h1:cobra-old-mod=
+github.com/spf13/cobra v1.8.0 h1:cobra=
+github.com/spf13/cobra v1.8.0/go.mod h1:cobra-mod=
+github.com/pkg/errors v0.9.1 h1:errors=
+github.com/pkg/errors v0.9.1/go.mod h1:errors-mod=
+github.com/spf13/pflag v1.0.5 h1:pflag=
+github.com/spf13/pflag v1.0.5/go.mod h1:pflag-mod=
+github.com/fork/text v0.14.1 h1:text=
+github.com/fork/text v0.14.1/go.mod h1:text-mod=
+"""
+
+
+def _process(tmp_path, *names):
+    (tmp_path / "go.mod").write_text(GO_MOD)
+    (tmp_path / "go.sum").write_text(GO_SUM)
+    handler = GoLanguageHandler()
+    packages = {}
+    for name in names:
+        handler.process_manifest(str(tmp_path / name), packages)
+    return packages
+
+
+@pytest.mark.unit
+def test_go_mod_records_versions_indirect_requirements_and_replacements(tmp_path):
+    packages = _process(tmp_path, "go.mod")
+
+    assert packages["github.com/spf13/cobra"] == {"ecosystem": "go", "version": "v1.8.0"}
+    assert packages["github.com/pkg/errors"]["indirect"] is True
+    assert packages["golang.org/x/text"]["replaced_by"] == {"path": "github.com/fork/text", "version": "v0.14.1"}
+    assert packages["example.com/shared"]["replaced_by"] == {"path": "../shared", "local": True}
+    # The replace only applies to v0.2.0, not the required v0.3.1
+    assert "replaced_by" not in packages["github.com/kr/pretty"]
+
+
+@pytest.mark.unit
+def test_go_sum_records_downloaded_versions_and_transitive_modules(tmp_path):
+    packages = _process(tmp_path, "go.sum")
+
+    cobra = packages["github.com/spf13/cobra"]
+    # v1.7.0 only had its go.mod consulted during version selection
+    assert cobra["locked_version"] == "v1.8.0"
+    assert "locked_versions" not in cobra
+    assert cobra["hashes"] == ["h1:cobra-mod=", "h1:cobra="]
+    assert cobra["direct"] is True
+    assert packages["github.com/pkg/errors"]["transitive"] is True
+    assert packages["github.com/spf13/pflag"] == {
+        "ecosystem": "go",
+        "locked_version": "v1.0.5",
+        "hashes": ["h1:pflag-mod=", "h1:pflag="],
+        "direct": False,
+        "transitive": True,
+    }
+    # Checksums of the fork are credited to the module it replaces
+    text = packages["golang.org/x/text"]
+    assert text["locked_version"] == "v0.14.1"
+    assert text["replaced_by"] == {"path": "github.com/fork/text", "version": "v0.14.1"}
+    assert "github.com/fork/text" not in packages
+
+
+@pytest.mark.unit
+def test_go_sums_of_nested_modules_keep_a_direct_requirement(tmp_path):
+    (tmp_path / "tools").mkdir()
+    (tmp_path / "tools/go.mod").write_text("module example.com/app/tools\n\nrequire github.com/pkg/errors v0.9.1\n")
+    (tmp_path / "tools/go.sum").write_text("github.com/pkg/errors v0.9.1 h1:errors=\n")
+    packages = _process(tmp_path, "go.sum", "tools/go.sum")
+
+    assert packages["github.com/pkg/errors"]["direct"] is True
+    assert "transitive" not in packages["github.com/pkg/errors"]
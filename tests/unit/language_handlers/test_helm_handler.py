@@ -0,0 +1,125 @@
+"""
+Helm chart dependency handler
+"""
+
+import json
+
+import pytest
+
+from gardener.analysis.main import DependencyAnalyzer
+from gardener.package_metadata.url_resolver import resolve_package_urls
+from gardener.treewalk.helm import HelmLanguageHandler, chart_package_name
+
+CHART_YAML = """apiVersion: v2
+name: shop
+version: 0.3.0
+dependencies:
+  - name: postgresql
+    version: "~12.1.0"
+    repository: https://charts.bitnami.com/bitnami
+  - name: redis
+    version: 17.x.x
+    repository: oci://registry-1.docker.io/bitnamicharts
+    alias: cache
+  - name: common
+    version: 0.1.0
+    repository: file://../common
+"""
+
+CHART_LOCK = """dependencies:
+- name: postgresql
+  repository: https://charts.bitnami.com/bitnami
+  version: 12.1.9
+- name: redis
+  repository: oci://registry-1.docker.io/bitnamicharts
+  version: 17.3.2
+digest: sha256:0000
+"""
+
+REQUIREMENTS_YAML = """dependencies:
+  - name: nginx-ingress
+    version: 1.41.3
+    repository: "@stable"
+"""
+
+
+@pytest.mark.unit
+def test_chart_dependencies_and_lock_versions_are_reported(tmp_path):
+    (tmp_path / "deploy" / "shop").mkdir(parents=True)
+    (tmp_path / "deploy" / "shop" / "Chart.yaml").write_text(CHART_YAML)
+    (tmp_path / "deploy" / "shop" / "Chart.lock").write_text(CHART_LOCK)
+    (tmp_path / "deploy" / "legacy").mkdir()
+    (tmp_path / "deploy" / "legacy" / "requirements.yaml").write_text(REQUIREMENTS_YAML)
+
+    packages = DependencyAnalyzer(verbose=False).discover_packages(str(tmp_path), ["helm"])
+
+    assert set(packages) == {
+        "charts.bitnami.com/bitnami/postgresql",
+        "registry-1.docker.io/bitnamicharts/redis",
+        "stable/nginx-ingress",
+    }
+    postgresql = packages["charts.bitnami.com/bitnami/postgresql"]
+    assert (postgresql["ecosystem"], postgresql["chart"], postgresql["version"]) == ("helm", "postgresql", "~12.1.0")
+    assert postgresql["locked_version"] == "12.1.9"
+    assert packages["registry-1.docker.io/bitnamicharts/redis"]["aliases"] == ["cache"]
+    assert packages["stable/nginx-ingress"]["repository"] == "@stable"
+
+
+@pytest.mark.unit
+def test_chart_sources_resolve_through_artifact_hub_and_github_pages(offline_mode):
+    postgresql = chart_package_name("postgresql", "https://charts.bitnami.com/bitnami")
+    packages = {
+        postgresql: {"ecosystem": "helm", "chart": "postgresql", "repository": "https://charts.bitnami.com/bitnami"},
+        "prometheus-community.github.io/helm-charts/prometheus": {
+            "ecosystem": "helm",
+            "chart": "prometheus",
+            "repository": "https://prometheus-community.github.io/helm-charts",
+        },
+    }
+    search = {
+        "packages": [
+            {"name": "postgresql", "repository": {"name": "other", "url": "https://example.com/charts"}},
+            {"name": "postgresql", "repository": {"name": "bitnami", "url": "https://charts.bitnami.com/bitnami/"}},
+        ]
+    }
+    detail = {
+        "home_url": "https://bitnami.com",
+        "links": [{"name": "Source", "url": "https://github.com/bitnami/charts/tree/main/bitnami/postgresql"}],
+    }
+    responses = {
+        "https://artifacthub.io/api/v1/packages/search?ts_query_web=postgresql&kind=0&limit=20": json.dumps(search),
+        "https://artifacthub.io/api/v1/packages/helm/bitnami/postgresql": json.dumps(detail),
+    }
+
+    with offline_mode.set_responses(responses):
+        resolved = resolve_package_urls(packages, cache={})
+
+    assert resolved == {
+        postgresql: "https://github.com/bitnami/charts",
+        "prometheus-community.github.io/helm-charts/prometheus": "https://github.com/prometheus-community/helm-charts",
+    }
+
+
+@pytest.mark.unit
+def test_v1_requirements_lock_pins_and_malformed_charts_are_skipped(tmp_path, capsys):
+    (tmp_path / "requirements.yaml").write_text(REQUIREMENTS_YAML)
+    (tmp_path / "requirements.lock").write_text(
+        "dependencies:\n- name: nginx-ingress\n  repository: '@stable'\n  version: 1.41.3\n"
+    )
+    (tmp_path / "Chart.yaml").write_text("dependencies: [name: broken\n")
+    handler = HelmLanguageHandler()
+    packages = {}
+
+    for name in ("requirements.yaml", "requirements.lock", "Chart.yaml"):
+        handler.process_manifest(str(tmp_path / name), packages)
+
+    assert packages == {
+        "stable/nginx-ingress": {
+            "ecosystem": "helm",
+            "chart": "nginx-ingress",
+            "repository": "@stable",
+            "version": "1.41.3",
+            "locked_version": "1.41.3",
+        }
+    }
+    assert "Could not parse Chart.yaml" in capsys.readouterr().err
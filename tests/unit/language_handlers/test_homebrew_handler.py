@@ -0,0 +1,108 @@
+"""
+Homebrew Brewfile handler
+"""
+
+import json
+
+import pytest
+
+from gardener.analysis.main import DependencyAnalyzer
+from gardener.package_metadata.url_resolver import resolve_package_urls
+from gardener.treewalk.homebrew import HomebrewLanguageHandler
+
+BREWFILE = """tap "hashicorp/tap"
+tap "acme/tools", "https://gitlab.com/acme/homebrew-tools.git"
+brew "jq"
+brew "postgresql@16", restart_service: true
+brew "hashicorp/tap/terraform"
+cask "docker"
+mas "Xcode", id: 497799835
+# brew "disabled"
+"""
+
+BREWFILE_LOCK = {
+    "entries": {
+        "brew": {"jq": {"version": "1.7.1"}, "postgresql@16": {"version": "16.2_1"}},
+        "cask": {"docker": {"version": "4.27.2,137060"}},
+    }
+}
+
+
+@pytest.mark.unit
+def test_brewfile_entries_and_locked_versions_are_reported(tmp_path):
+    (tmp_path / "Brewfile").write_text(BREWFILE)
+    (tmp_path / "Brewfile.lock.json").write_text(json.dumps(BREWFILE_LOCK))
+
+    packages = DependencyAnalyzer(verbose=False).discover_packages(str(tmp_path), ["homebrew"])
+
+    assert set(packages) == {
+        "hashicorp/tap",
+        "acme/tools",
+        "homebrew/core/jq",
+        "homebrew/core/postgresql@16",
+        "hashicorp/tap/terraform",
+        "homebrew/cask/docker",
+    }
+    jq = packages["homebrew/core/jq"]
+    assert (jq["ecosystem"], jq["kind"], jq["locked_version"]) == ("homebrew", "brew", "1.7.1")
+    assert packages["homebrew/cask/docker"]["kind"] == "cask"
+    assert packages["acme/tools"]["source"] == "https://gitlab.com/acme/homebrew-tools.git"
+
+
+@pytest.mark.unit
+def test_formulae_casks_and_taps_resolve_to_upstream_repositories(offline_mode):
+    packages = {
+        "homebrew/core/jq": {"ecosystem": "homebrew", "kind": "brew", "formula": "jq"},
+        "homebrew/core/postgresql@16": {"ecosystem": "homebrew", "kind": "brew", "formula": "postgresql@16"},
+        "homebrew/cask/docker": {"ecosystem": "homebrew", "kind": "cask", "formula": "docker"},
+        "hashicorp/tap/terraform": {"ecosystem": "homebrew", "kind": "brew", "formula": "hashicorp/tap/terraform"},
+        "acme/tools": {"ecosystem": "homebrew", "kind": "tap", "source": "https://gitlab.com/acme/homebrew-tools.git"},
+    }
+    responses = {
+        "https://formulae.brew.sh/api/formula/jq.json": json.dumps(
+            {
+                "homepage": "https://jqlang.github.io/jq/",
+                "urls": {
+                    "stable": {"url": "https://github.com/jqlang/jq/releases/download/jq-1.7.1/jq-1.7.1.tar.gz"},
+                    "head": {"url": "https://github.com/jqlang/jq.git"},
+                },
+            }
+        ),
+        "https://formulae.brew.sh/api/formula/postgresql@16.json": json.dumps(
+            {
+                "homepage": "https://www.postgresql.org/",
+                "urls": {"stable": {"url": "https://ftp.postgresql.org/pub/source/v16.2/postgresql-16.2.tar.bz2"}},
+            }
+        ),
+        "https://formulae.brew.sh/api/cask/docker.json": json.dumps(
+            {"homepage": "https://www.docker.com/products/docker-desktop", "url": "https://desktop.docker.com/x.dmg"}
+        ),
+    }
+
+    with offline_mode.set_responses(responses):
+        resolved = resolve_package_urls(packages, cache={})
+
+    assert resolved == {
+        "homebrew/core/jq": "https://github.com/jqlang/jq",
+        "homebrew/core/postgresql@16": "https://www.postgresql.org/",
+        "homebrew/cask/docker": "https://www.docker.com/products/docker-desktop",
+        "hashicorp/tap/terraform": "https://github.com/hashicorp/homebrew-tap",
+        "acme/tools": "https://gitlab.com/acme/homebrew-tools",
+    }
+
+
+@pytest.mark.unit
+def test_malformed_lock_keeps_brewfile_entries(tmp_path, capsys):
+    (tmp_path / "Brewfile").write_text('brew "jq"\nwhalebrew "whalebrew/wget"\n')
+    (tmp_path / "Brewfile.lock.json").write_text("{not json")
+    handler = HomebrewLanguageHandler()
+    packages = {}
+
+    handler.process_manifest(str(tmp_path / "Brewfile"), packages)
+    handler.process_manifest(str(tmp_path / "Brewfile.lock.json"), packages)
+    handler.process_manifest(str(tmp_path / "missing" / "Brewfile"), packages)
+
+    assert packages == {"homebrew/core/jq": {"ecosystem": "homebrew", "kind": "brew", "formula": "jq"}}
+    errors = capsys.readouterr().err
+    assert "Could not parse Brewfile.lock.json" in errors
+    assert "Failed to read Brewfile" in errors
@@ -0,0 +1,97 @@
+"""
+HTML script tags, stylesheet links and import maps
+"""
+
+from collections import defaultdict
+
+import pytest
+
+from gardener.analysis.imports import LocalImportResolver
+from gardener.treewalk.html import HtmlLanguageHandler, cdn_package, parse_html
+
+PAGE = """<!doctype html>
+<html>
+<head>
+  <!-- <script src="https://unpkg.com/jquery@3.7.1"></script> -->
+  <link rel="stylesheet" href="https://cdn.jsdelivr.net/npm/bulma@1.0.0/css/bulma.min.css">
+  <link rel="icon" href="https://cdn.example.com/favicon.ico">
+  <link rel="stylesheet" href="/css/site.css?v=3">
+  <script src="//unpkg.com/htmx.org@1.9.12/dist/htmx.min.js" defer></script>
+  <script type="importmap">
+    {
+      "imports": {
+        "vue": "https://esm.sh/vue@3.4.21",
+        "lodash/": "https://ga.jspm.io/npm:lodash-es@4.17.21/",
+        "app/": "./js/"
+      }
+    }
+  </script>
+</head>
+<body>
+  <script type="module">
+    import { createApp } from 'vue';
+    import debounce from 'lodash/debounce.js';
+    import { start } from 'app/main.js';
+  </script>
+  <script type="text/x-template" src="https://unpkg.com/ignored@1.0.0"></script>
+</body>
+</html>
+"""
+
+
+@pytest.mark.unit
+def test_tags_import_maps_and_module_imports_are_parsed():
+    document = parse_html(PAGE)
+
+    assert document.references == [
+        ("script", "//unpkg.com/htmx.org@1.9.12/dist/htmx.min.js"),
+        ("stylesheet", "https://cdn.jsdelivr.net/npm/bulma@1.0.0/css/bulma.min.css"),
+        ("stylesheet", "/css/site.css?v=3"),
+    ]
+    assert document.import_map["vue"] == "https://esm.sh/vue@3.4.21"
+    assert document.module_specifiers == ["vue", "lodash/debounce.js", "app/main.js"]
+    assert cdn_package("https://ga.jspm.io/npm:lodash-es@4.17.21/debounce.js") == ("npm", "lodash-es", "4.17.21")
+    assert cdn_package("https://cdn.example.com/favicon.ico") is None
+
+
+@pytest.mark.unit
+def test_cdn_urls_map_to_npm_packages_and_relative_paths_to_files(tmp_path):
+    source_files = {
+        rel_path: {"absolute_path": str(tmp_path / rel_path), "language": "html"}
+        for rel_path in ("web/index.html", "css/site.css", "web/js/main.js")
+    }
+    resolver = LocalImportResolver(
+        repo_path=str(tmp_path),
+        source_files=source_files,
+        alias_resolver=None,
+        js_ts_base_url=None,
+        js_ts_path_aliases={},
+        go_module_path=None,
+        remappings=None,
+        hardhat_remappings=None,
+        solidity_src_path=None,
+        logger=None,
+    )
+    handler = HtmlLanguageHandler()
+    components, annotations = defaultdict(list), {}
+
+    external, local = handler.extract_imports(
+        handler.parse_source(PAGE, "web/index.html"),
+        "web/index.html",
+        components,
+        resolver.resolve_html,
+        import_annotations=annotations,
+    )
+
+    assert external == ["htmx.org", "bulma", "vue", "lodash-es"]
+    assert local == ["css/site.css", "web/js/main.js"]
+    assert annotations["bulma"] == {
+        "evidence": "cdn",
+        "urls": ["https://cdn.jsdelivr.net/npm/bulma@1.0.0/css/bulma.min.css"],
+    }
+    assert annotations["vue"] == {"evidence": "import-map", "urls": ["https://esm.sh/vue@3.4.21"]}
+    assert handler.cdn_packages["htmx.org"] == {
+        "ecosystem": "npm",
+        "version": "1.9.12",
+        "urls": ["//unpkg.com/htmx.org@1.9.12/dist/htmx.min.js"],
+    }
@@ -0,0 +1,123 @@
+"""
+Protobuf/Thrift/Avro IDL handler and schema resolution
+"""
+
+from collections import defaultdict
+
+import pytest
+
+from gardener.analysis.imports import LocalImportResolver
+from gardener.common.defaults import ResourceLimits
+from gardener.common.secure_file_ops import SecureFileOps
+from gardener.treewalk.idl import IdlLanguageHandler, parse_idl
+
+USER_PROTO = """
+syntax = "proto3";
+// import "commented/out.proto";
+package acme.user.v1;
+
+import "google/protobuf/timestamp.proto";
+import public "common/v1/money.proto";
+import "validate/validate.proto";
+
+option go_package = "github.com/acme/platform/gen/go/user/v1;userv1";
+option java_package = "com.acme.user.v1";
+"""
+
+
+def _resolver(tmp_path, files, **kwargs):
+    source_files = {}
+    for rel, content in files.items():
+        path = tmp_path / rel
+        path.parent.mkdir(parents=True, exist_ok=True)
+        path.write_text(content)
+        language = "idl" if rel.endswith((".proto", ".thrift", ".avdl")) else "go"
+        source_files[rel] = {"absolute_path": str(path), "language": language}
+    return LocalImportResolver(
+        repo_path=str(tmp_path),
+        source_files=source_files,
+        alias_resolver=None,
+        js_ts_base_url=None,
+        js_ts_path_aliases=None,
+        go_module_path="github.com/acme/service",
+        remappings=None,
+        hardhat_remappings=None,
+        solidity_src_path=None,
+        logger=None,
+        **kwargs,
+    )
+
+
+@pytest.mark.unit
+def test_parse_proto_imports_and_package_hints():
+    document = parse_idl(USER_PROTO, "proto/user/v1/user.proto")
+
+    assert document.imports == [
+        "google/protobuf/timestamp.proto",
+        "common/v1/money.proto",
+        "validate/validate.proto",
+    ]
+    assert document.package == "acme.user.v1"
+    assert document.options == {
+        "go_package": "github.com/acme/platform/gen/go/user/v1;userv1",
+        "java_package": "com.acme.user.v1",
+    }
+
+
+@pytest.mark.unit
+def test_parse_thrift_and_avdl():
+    thrift = parse_idl('include "shared.thrift"\n# include "old.thrift"\nnamespace go acme.shared\n', "svc.thrift")
+    assert thrift.imports == ["shared.thrift"]
+    assert thrift.options == {"go_package": "acme.shared"}
+
+    avdl = parse_idl('@namespace("com.acme")\nprotocol P { import idl "common.avdl"; }\n', "p.avdl")
+    assert avdl.imports == ["common.avdl"]
+    assert avdl.package == "com.acme"
+
+
+@pytest.mark.unit
+def test_extract_imports_links_shared_schemas(tmp_path):
+    resolver = _resolver(
+        tmp_path,
+        {"proto/user/v1/user.proto": USER_PROTO, "proto/common/v1/money.proto": 'syntax = "proto3";\n'},
+    )
+    handler = IdlLanguageHandler()
+    components = defaultdict(list)
+
+    external, local = handler.extract_imports(
+        handler.parse_source(USER_PROTO, "proto/user/v1/user.proto"),
+        "proto/user/v1/user.proto",
+        components,
+        resolver.resolve_idl,
+    )
+
+    assert local == ["proto/common/v1/money.proto"]
+    assert external == ["protobuf", "protoc-gen-validate"]
+    assert ("protobuf", "protobuf.google/protobuf/timestamp") in components["proto/user/v1/user.proto"]
+    assert handler.normalize_package_name("thirdparty/acme/types.proto") is None
+
+
+@pytest.mark.unit
+def test_go_import_of_generated_package_links_to_schema(tmp_path):
+    resolver = _resolver(tmp_path, {"proto/user/v1/user.proto": USER_PROTO, "cmd/server/main.go": ""})
+
+    assert resolver.resolve_go("cmd/server/main.go", "github.com/acme/platform/gen/go/user/v1") == (
+        "proto/user/v1/user.proto"
+    )
+    assert resolver.resolve_go("cmd/server/main.go", "github.com/other/lib") is None
+    assert resolver.idl_package_hints()["proto/user/v1/user.proto"]["java_package"] == "com.acme.user.v1"
+
+
+@pytest.mark.unit
+def test_package_hints_skip_schemas_outside_the_repository_or_over_the_size_limit(tmp_path, monkeypatch):
+    outside = tmp_path / "outside.proto"
+    outside.write_text("package leaked.secret;\n")
+    repo = tmp_path / "repo"
+    repo.mkdir()
+    files = {"proto/user/v1/user.proto": USER_PROTO, "proto/small.proto": "package acme.small;\n"}
+    resolver = _resolver(repo, files, secure_file_ops=SecureFileOps(str(repo)))
+    (repo / "proto" / "linked.proto").symlink_to(outside)
+    resolver.source_files["proto/linked.proto"] = {"absolute_path": str(repo / "proto/linked.proto"), "language": "idl"}
+    monkeypatch.setattr(ResourceLimits, "MAX_FILE_SIZE", len(USER_PROTO) - 1)
+
+    assert resolver.idl_package_hints() == {"proto/small.proto": {"package": "acme.small"}}
@@ -0,0 +1,137 @@
+"""
+CocoaPods and Carthage handler
+"""
+
+import json
+
+import pytest
+
+from gardener.analysis.main import DependencyAnalyzer
+from gardener.package_metadata.url_resolver import resolve_package_urls
+from gardener.treewalk.ios import IosLanguageHandler
+
+PODFILE = """platform :ios, '13.0'
+use_frameworks!
+
+target 'Shop' do
+  pod 'Alamofire', '~> 5.6'
+  pod 'Firebase/Analytics'
+  pod 'Charts', :git => 'https://github.com/danielgindi/Charts.git', :tag => 'v4.1.0'
+  pod 'DesignSystem', :path => '../DesignSystem'
+  # pod 'Disabled'
+end
+"""
+
+PODFILE_LOCK = """PODS:
+  - Alamofire (5.6.4)
+  - Charts (4.1.0)
+  - Firebase/Analytics (10.0.0):
+    - FirebaseAnalytics (~> 10.0.0)
+  - FirebaseAnalytics (10.0.0)
+  - DesignSystem (1.0.0)
+
+DEPENDENCIES:
+  - Alamofire (~> 5.6)
+  - Charts (from `https://github.com/danielgindi/Charts.git`, tag `v4.1.0`)
+  - DesignSystem (from `../DesignSystem`)
+  - Firebase/Analytics
+
+EXTERNAL SOURCES:
+  Charts:
+    :git: https://github.com/danielgindi/Charts.git
+    :tag: v4.1.0
+  DesignSystem:
+    :path: "../DesignSystem"
+
+COCOAPODS: 1.12.1
+"""
+
+CARTFILE = """github "ReactiveX/RxSwift" ~> 6.5
+git "https://gitlab.com/acme/networking.git" "main"
+binary "https://example.com/Analytics.json" ~> 2.0
+git "file:///Users/me/LocalKit"
+"""
+
+CARTFILE_RESOLVED = """github "ReactiveX/RxSwift" "6.5.0"
+git "https://gitlab.com/acme/networking.git" "3f2a9c1"
+"""
+
+
+@pytest.mark.unit
+def test_podfile_and_lock_report_pods_with_sources(tmp_path):
+    (tmp_path / "Podfile").write_text(PODFILE)
+    (tmp_path / "Podfile.lock").write_text(PODFILE_LOCK)
+
+    packages = DependencyAnalyzer(verbose=False).discover_packages(str(tmp_path), ["ios"])
+
+    assert set(packages) == {"Alamofire", "Firebase", "FirebaseAnalytics", "Charts"}
+    assert packages["Alamofire"]["version"] == "~> 5.6"
+    assert packages["Alamofire"]["locked_version"] == "5.6.4"
+    assert packages["Charts"]["source"] == "https://github.com/danielgindi/Charts.git"
+    assert packages["FirebaseAnalytics"]["transitive"] is True
+    assert "transitive" not in packages["Firebase"]
+
+
+@pytest.mark.unit
+def test_cartfile_entries_keep_repository_identity(tmp_path):
+    (tmp_path / "Cartfile").write_text(CARTFILE)
+    (tmp_path / "Cartfile.resolved").write_text(CARTFILE_RESOLVED)
+
+    packages = DependencyAnalyzer(verbose=False).discover_packages(str(tmp_path), ["ios"])
+
+    assert set(packages) == {"github.com/ReactiveX/RxSwift", "gitlab.com/acme/networking", "example.com/Analytics.json"}
+    rxswift = packages["github.com/ReactiveX/RxSwift"]
+    assert (rxswift["ecosystem"], rxswift["version"], rxswift["locked_version"]) == ("github", "~> 6.5", "6.5.0")
+    assert packages["example.com/Analytics.json"]["binary"] is True
+
+    resolved = resolve_package_urls(packages, cache={})
+    assert resolved == {
+        "github.com/ReactiveX/RxSwift": "https://github.com/ReactiveX/RxSwift",
+        "gitlab.com/acme/networking": "https://gitlab.com/acme/networking",
+    }
+
+
+@pytest.mark.unit
+def test_trunk_pods_resolve_to_podspec_source(offline_mode):
+    packages = {
+        "Alamofire": {"ecosystem": "cocoapods", "locked_version": "5.6.4"},
+        "Kingfisher": {"ecosystem": "cocoapods"},
+        "Charts": {"ecosystem": "cocoapods", "source": "https://github.com/danielgindi/Charts.git"},
+    }
+    responses = {
+        "https://trunk.cocoapods.org/api/v1/pods/Alamofire/specs/5.6.4": json.dumps(
+            {"source": {"git": "https://github.com/Alamofire/Alamofire.git", "tag": "5.6.4"}}
+        ),
+        "https://trunk.cocoapods.org/api/v1/pods/Kingfisher": json.dumps(
+            {"versions": [{"name": "7.0.0"}, {"name": "7.9.1"}]}
+        ),
+        "https://trunk.cocoapods.org/api/v1/pods/Kingfisher/specs/7.9.1": json.dumps(
+            {"homepage": "https://github.com/onevcat/Kingfisher", "source": {"http": "https://example.com/k.zip"}}
+        ),
+    }
+
+    with offline_mode.set_responses(responses):
+        resolved = resolve_package_urls(packages, cache={})
+
+    assert resolved == {
+        "Alamofire": "https://github.com/Alamofire/Alamofire",
+        "Kingfisher": "https://github.com/onevcat/Kingfisher",
+        "Charts": "https://github.com/danielgindi/Charts",
+    }
+
+
+@pytest.mark.unit
+def test_private_cartfile_and_pod_subspecs_share_one_package(tmp_path):
+    (tmp_path / "Podfile").write_text("pod 'Firebase/Analytics', '10.0.0'\npod 'Firebase/Crashlytics'\n")
+    (tmp_path / "Cartfile.private").write_text('github "Quick/Nimble" == 12.0.0\n')
+    handler = IosLanguageHandler()
+    packages = {}
+
+    handler.process_manifest(str(tmp_path / "Podfile"), packages)
+    handler.process_manifest(str(tmp_path / "Cartfile.private"), packages)
+
+    assert packages == {
+        "Firebase": {"ecosystem": "cocoapods", "version": "10.0.0"},
+        "github.com/Quick/Nimble": {"ecosystem": "github", "version": "== 12.0.0"},
+    }
+    assert handler.normalize_package_name("Firebase/Crashlytics") == "Firebase"
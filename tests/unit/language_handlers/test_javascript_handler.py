@@ -87,3 +87,67 @@ def test_no_imports_file_has_none(tree_parser, logger):
     comps = defaultdict(list)
     external, local = handler.extract_imports(root, "no_imports_fixture.mjs", comps, _mock_resolve_local, logger=logger)
     assert not external and not local and not comps.get("no_imports_fixture.mjs")
+
+
+@pytest.mark.unit
+def test_computed_dynamic_specifiers_resolve_by_prefix_or_are_reported(tree_parser, logger):
+    code = (
+        "const chart = await import(`chart.js`);\n"
+        "const locale = await import(`date-fns/locale/${lang}`);\n"
+        "const icon = require('@mdi/js/' + name);\n"
+        "const plugin = require(pluginName);\n"
+        "const page = import(`./pages/${route}.js`);\n"
+    )
+    root = tree_parser("javascript", code)
+    handler = JavaScriptLanguageHandler(logger)
+    annotations = {}
+    external, _ = handler.extract_imports(
+        root, "app.js", defaultdict(list), _mock_resolve_local, logger=logger, import_annotations=annotations
+    )
+
+    assert {"chart.js", "date-fns", "@mdi/js"} == set(external)
+    assert annotations["date-fns"] == {"evidence": "dynamic-import", "expressions": ["`date-fns/locale/${lang}`"]}
+    assert handler.dynamic_unresolved["app.js"] == [
+        {"kind": "require", "expression": "pluginName", "line": 4},
+        {"kind": "import", "expression": "`./pages/${route}.js`", "line": 5, "prefix": "./pages/"},
+    ]
+
+
+@pytest.mark.unit
+def test_stylesheet_and_asset_imports_from_packages_are_annotated(tree_parser, logger):
+    code = (
+        "import 'bootstrap/dist/css/bootstrap.min.css';\n"
+        "import '@fontsource/inter/400.css';\n"
+        "import { loadFont } from '@fontsource/inter';\n"
+        "import logo from 'brand-assets/logo.svg?url';\n"
+    )
+    root = tree_parser("javascript", code)
+    handler = JavaScriptLanguageHandler(logger)
+    annotations = {}
+    external, _ = handler.extract_imports(
+        root, "app.js", defaultdict(list), _mock_resolve_local, logger=logger, import_annotations=annotations
+    )
+
+    assert {"bootstrap", "@fontsource/inter", "brand-assets"} == set(external)
+    assert annotations == {"bootstrap": {"evidence": "asset-import"}, "brand-assets": {"evidence": "asset-import"}}
+
+
+@pytest.mark.unit
+def test_module_syntax_and_import_or_require_use_are_recorded(tree_parser, logger):
+    code = (
+        "const express = require('express');\n"
+        "const { join } = require('path');\n"
+        "const chalk = await import('chalk');\n"
+        "module.exports = { app: express() };\n"
+    )
+    root = tree_parser("javascript", code)
+    handler = JavaScriptLanguageHandler(logger)
+    handler.extract_imports(root, "server.js", defaultdict(list), _mock_resolve_local, logger=logger)
+    root = tree_parser("javascript", "import express from 'express';\nexport const router = express.Router();\n")
+    handler.extract_imports(root, "router.js", defaultdict(list), _mock_resolve_local, logger=logger)
+
+    assert handler.module_systems["server.js"] == {
+        "syntax": "cjs",
+        "loaded_by": {"express": ["require"], "path": ["require"], "chalk": ["import"]},
+    }
+    assert handler.module_systems["router.js"] == {"syntax": "esm", "loaded_by": {"express": ["import"]}}
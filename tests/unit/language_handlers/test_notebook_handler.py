@@ -0,0 +1,88 @@
+"""
+Jupyter notebook cell extraction and install magic detection
+"""
+
+import json
+
+import pytest
+
+from gardener.analysis.tooling import declare_notebook_installs, extract_notebook_install_commands
+from gardener.treewalk.notebook import notebook_code_cells, strip_magics
+
+
+def _notebook(*cells, language="python"):
+    return json.dumps(
+        {
+            "nbformat": 4,
+            "metadata": {"kernelspec": {"name": "python3", "language": language}},
+            "cells": [
+                {"cell_type": cell_type, "source": source.splitlines(keepends=True), "metadata": {}}
+                for cell_type, source in cells
+            ],
+        }
+    )
+
+
+@pytest.mark.unit
+def test_code_cells_keep_notebook_indices():
+    content = _notebook(
+        ("markdown", "# Analysis\nimport not_code\n"),
+        ("code", "import pandas as pd\n"),
+        ("code", "%pip install seaborn==0.13.2\nimport seaborn\n"),
+    )
+
+    assert notebook_code_cells(content) == [
+        (1, "import pandas as pd\n"),
+        (2, "%pip install seaborn==0.13.2\nimport seaborn\n"),
+    ]
+    assert notebook_code_cells(_notebook(("code", "library(dplyr)\n"), language="R")) == []
+    assert notebook_code_cells("not json") == []
+
+
+@pytest.mark.unit
+def test_nbformat3_worksheets():
+    content = json.dumps(
+        {"nbformat": 3, "worksheets": [{"cells": [{"cell_type": "code", "input": ["import numpy\n"]}]}]}
+    )
+
+    assert notebook_code_cells(content) == [(0, "import numpy\n")]
+
+
+@pytest.mark.unit
+def test_strip_magics():
+    assert strip_magics("%pip install x\n  !ls\nimport os") == "pass  # pip install x\n  pass  # ls\nimport os"
+    assert strip_magics("%%time\nimport requests") == "import requests"
+    assert strip_magics("%%bash\npip install x") is None
+
+
+@pytest.mark.unit
+def test_install_magics_are_tooling_with_cell_evidence():
+    content = _notebook(
+        ("code", "import torch\n"),
+        ("code", "%pip install -q torch==2.3.0\n!pip install transformers\n"),
+        ("code", "%%bash\npip install datasets\n"),
+        ("code", "%%capture\n!echo pip install nothing\n"),
+    )
+
+    findings = extract_notebook_install_commands(content, "train.ipynb")
+
+    assert [(f["name"], f["version"], f["cell"], f["line"]) for f in findings] == [
+        ("torch", "==2.3.0", 1, 1),
+        ("transformers", "", 1, 2),
+        ("datasets", "", 2, 2),
+    ]
+
+    tooling_dependencies = [
+        {
+            "name": f["name"],
+            "ecosystem": f["ecosystem"],
+            "version": f["version"],
+            "install_commands": [{"file": f["file"], "line": f["line"], "command": f["command"], "cell": f["cell"]}],
+        }
+        for f in findings
+    ]
+    external_packages = {"torch": {"ecosystem": "pypi", "version": "2.2.0"}}
+
+    assert declare_notebook_installs(external_packages, tooling_dependencies) == ["transformers", "datasets"]
+    assert external_packages["torch"]["version"] == "2.2.0"
+    assert external_packages["transformers"]["found_in_manifests"] == ["train.ipynb"]
@@ -0,0 +1,119 @@
+"""
+npm, Yarn and pnpm lockfiles
+"""
+
+import json
+
+import pytest
+
+from gardener.treewalk.javascript import JavaScriptLanguageHandler
+from gardener.treewalk.npm_locks import _pnpm_package_key
+
+PACKAGE_LOCK = {
+    "lockfileVersion": 3,
+    "packages": {
+        "": {"name": "app", "dependencies": {"express": "^4.18.0"}, "devDependencies": {"jest": "^29.0.0"}},
+        "node_modules/express": {"version": "4.18.2", "integrity": "sha512-express"},
+        "node_modules/debug": {"version": "4.3.4", "integrity": "sha512-debug4"},
+        "node_modules/express/node_modules/debug": {"version": "2.6.9", "integrity": "sha512-debug2"},
+        "node_modules/jest": {"version": "29.7.0", "dev": True},
+        "node_modules/@acme/ui": {"resolved": "packages/ui", "link": True},
+    },
+}
+
+YARN_V1 = """# yarn lockfile v1
+
+
+"@babel/core@^7.0.0", "@babel/core@^7.12.3":
+  version "7.23.0"
+  resolved "https://registry.yarnpkg.com/@babel/core/-/core-7.23.0.tgz#abc"
+  integrity sha512-babel
+  dependencies:
+    debug "^4.1.0"
+
+debug@^4.1.0:
+  version "4.3.4"
+  integrity sha512-debug4
+"""
+
+PNPM_LOCK = """lockfileVersion: '6.0'
+importers:
+  .:
+    dependencies:
+      react:
+        specifier: ^18.2.0
+        version: 18.2.0
+      '@acme/ui':
+        specifier: workspace:*
+        version: link:packages/ui
+packages:
+  /react@18.2.0:
+    resolution: {integrity: sha512-react}
+  /loose-envify@1.4.0:
+    resolution: {integrity: sha512-loose}
+  /@types/prop-types@15.7.5(react@18.2.0):
+    resolution: {integrity: sha512-types}
+"""
+
+
+def _process(tmp_path, name, content, manifest=None):
+    (tmp_path / name).write_text(content)
+    if manifest is not None:
+        (tmp_path / "package.json").write_text(json.dumps(manifest))
+    return JavaScriptLanguageHandler().process_manifest(str(tmp_path / name), {})
+
+
+@pytest.mark.unit
+def test_package_lock_records_hoisted_versions_integrity_and_direct_dependencies(tmp_path):
+    packages = _process(tmp_path, "package-lock.json", json.dumps(PACKAGE_LOCK))
+
+    assert packages["express"] == {
+        "ecosystem": "npm",
+        "locked_version": "4.18.2",
+        "integrity": "sha512-express",
+        "direct": True,
+    }
+    assert packages["debug"]["locked_version"] == "4.3.4"
+    assert packages["debug"]["locked_versions"] == ["4.3.4", "2.6.9"]
+    assert packages["debug"]["transitive"] is True and packages["debug"]["direct"] is False
+    assert packages["jest"]["direct"] is True
+    assert "@acme/ui" not in packages
+
+
+@pytest.mark.unit
+def test_yarn_v1_lock_takes_direct_dependencies_from_the_sibling_manifest(tmp_path):
+    manifest = {"name": "app", "devDependencies": {"@babel/core": "^7.12.3"}}
+    packages = _process(tmp_path, "yarn.lock", YARN_V1, manifest)
+
+    assert packages["@babel/core"] == {
+        "ecosystem": "npm",
+        "locked_version": "7.23.0",
+        "integrity": "sha512-babel",
+        "direct": True,
+    }
+    assert packages["debug"]["direct"] is False and packages["debug"]["transitive"] is True
+
+
+@pytest.mark.unit
+def test_pnpm_lock_reads_importers_and_peer_suffixed_package_keys(tmp_path):
+    packages = _process(tmp_path, "pnpm-lock.yaml", PNPM_LOCK)
+
+    assert packages["react"] == {
+        "ecosystem": "npm",
+        "locked_version": "18.2.0",
+        "integrity": "sha512-react",
+        "direct": True,
+    }
+    assert packages["@types/prop-types"]["locked_version"] == "15.7.5"
+    assert packages["loose-envify"]["transitive"] is True
+    assert "@acme/ui" not in packages
+
+
+@pytest.mark.unit
+def test_pnpm_package_keys_split_across_lockfile_versions():
+    assert _pnpm_package_key("/react-dom/17.0.2_react@17.0.2") == ("react-dom", "17.0.2")
+    assert _pnpm_package_key("/@a/b/1.0.0_react@17.0.2") == ("@a/b", "1.0.0")
+    assert _pnpm_package_key("/string_decoder/1.3.0") == ("string_decoder", "1.3.0")
+    assert _pnpm_package_key("/@a/b@1.0.0(react@17.0.2)") == ("@a/b", "1.0.0")
+    assert _pnpm_package_key("/@a/3d-view@2.0.0") == ("@a/3d-view", "2.0.0")
+    assert _pnpm_package_key("lodash@4.17.21") == ("lodash", "4.17.21")
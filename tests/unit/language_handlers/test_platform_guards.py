@@ -0,0 +1,150 @@
+"""
+Platform-guarded imports in JavaScript and Python, and cfg-guarded imports in Rust
+"""
+
+from collections import defaultdict
+
+import pytest
+
+from gardener.treewalk.annotations import is_platform_excluded, platforms_in_condition
+from gardener.treewalk.javascript import JavaScriptLanguageHandler
+from gardener.treewalk.python import PythonLanguageHandler, condition_platforms
+from gardener.treewalk.rust import RustLanguageHandler, cfg_platforms, cfg_requires_test
+
+
+def _no_local(*_args):
+    return None
+
+
+@pytest.mark.unit
+def test_js_require_under_process_platform_is_annotated(tree_parser, logger):
+    code = (
+        "const path = require('path');\n"
+        "if (process.platform === 'win32') {\n"
+        "  const reg = require('winreg');\n"
+        "} else {\n"
+        "  const fsevents = require('fsevents');\n"
+        "}\n"
+        "const notifier = process.platform === 'darwin' ? require('node-mac-notifier') : null;\n"
+    )
+    annotations = {}
+    external, _ = JavaScriptLanguageHandler(logger).extract_imports(
+        tree_parser("javascript", code), "index.js", defaultdict(list), _no_local, import_annotations=annotations
+    )
+
+    assert {"path", "winreg", "fsevents", "node-mac-notifier"} <= set(external)
+    assert "path" not in annotations
+    assert annotations["winreg"] == {"platform_guard": "process.platform === 'win32'", "platforms": ["windows"]}
+    assert annotations["fsevents"]["platforms"] == ["!windows"]
+    assert annotations["node-mac-notifier"]["platforms"] == ["macos"]
+
+
+@pytest.mark.unit
+def test_js_unconditional_use_clears_guard(tree_parser, logger):
+    code = "import chalk from 'chalk';\nif (process.platform === 'linux') { require('chalk'); }\n"
+    annotations = {}
+    JavaScriptLanguageHandler(logger).extract_imports(
+        tree_parser("javascript", code), "index.js", defaultdict(list), _no_local, import_annotations=annotations
+    )
+    assert annotations == {}
+
+
+@pytest.mark.unit
+def test_python_sys_platform_branches_are_annotated(tree_parser, logger):
+    code = (
+        "import sys\n"
+        "if sys.platform == 'win32':\n"
+        "    import winreg\n"
+        "elif sys.platform.startswith('linux'):\n"
+        "    import pyudev\n"
+        "else:\n"
+        "    from AppKit import NSWorkspace\n"
+    )
+    annotations = {}
+    PythonLanguageHandler(logger).extract_imports(
+        tree_parser("python", code), "app.py", defaultdict(list), lambda *_: None, import_annotations=annotations
+    )
+
+    assert "sys" not in annotations
+    assert annotations["winreg"]["platforms"] == ["windows"]
+    assert annotations["pyudev"]["platform_guard"] == (
+        "not (sys.platform == 'win32') and sys.platform.startswith('linux')"
+    )
+    assert annotations["pyudev"]["platforms"] == ["!windows", "linux"]
+    assert annotations["AppKit"]["platforms"] == ["!linux", "!windows"]
+
+
+@pytest.mark.unit
+def test_python_elif_chains_and_inequalities_are_annotated(tree_parser, logger):
+    code = (
+        "import sys\n"
+        "if sys.platform == 'win32':\n"
+        "    import winreg\n"
+        "elif sys.platform != 'darwin':\n"
+        "    import pyudev\n"
+        "else:\n"
+        "    import AppKit\n"
+    )
+    annotations = {}
+    PythonLanguageHandler(logger).extract_imports(
+        tree_parser("python", code), "app.py", defaultdict(list), lambda *_: None, import_annotations=annotations
+    )
+
+    # `elif` branches only run when every earlier condition is false
+    assert annotations["pyudev"]["platform_guard"] == "not (sys.platform == 'win32') and sys.platform != 'darwin'"
+    assert annotations["pyudev"]["platforms"] == ["!macos", "!windows"]
+    assert annotations["AppKit"]["platforms"] == ["!windows", "macos"]
+    assert condition_platforms("not (sys.platform != 'darwin' and os.name == 'nt')") == ["!windows", "macos"]
+
+
+@pytest.mark.unit
+def test_rust_cfg_attributes_annotate_use_declarations(tree_parser, logger):
+    code = (
+        "use serde::Serialize;\n"
+        '#[cfg(target_os = "windows")]\n'
+        "use winapi::um::winuser;\n"
+        "#[cfg(not(unix))]\n"
+        "use serde::Deserialize;\n"
+        '#[cfg(feature = "json")]\n'
+        "use serde_json::Value;\n"
+        "#[cfg(test)]\n"
+        "mod tests {\n"
+        "    use proptest::prelude::*;\n"
+        "    #[test]\n"
+        "    fn roundtrip() {\n"
+        "        use tempfile::tempdir;\n"
+        "    }\n"
+        "}\n"
+    )
+    annotations = {}
+    external, _ = RustLanguageHandler(logger).extract_imports(
+        tree_parser("rust", code), "src/lib.rs", defaultdict(list), _no_local, import_annotations=annotations
+    )
+
+    assert {"serde", "winapi", "serde_json", "proptest", "tempfile"} <= set(external)
+    assert "serde" not in annotations
+    assert annotations["winapi"] == {"cfg": 'target_os = "windows"', "platforms": ["windows"]}
+    assert annotations["serde_json"] == {"cfg": 'feature = "json"', "features": ["json"]}
+    assert annotations["proptest"] == {"cfg": "test", "test_only": True}
+    assert annotations["tempfile"] == {"cfg": "test", "test_only": True}
+    assert cfg_platforms("not(windows)") == ["!windows"]
+    assert cfg_platforms('target_arch = "wasm32"') == ["wasm"]
+
+
+@pytest.mark.unit
+def test_cfg_predicates_negate_per_not_node():
+    assert cfg_platforms('all(unix, not(target_os = "macos"))') == ["!macos", "unix"]
+    assert cfg_platforms('not(any(windows, target_os = "linux"))') == ["!linux", "!windows"]
+    assert cfg_requires_test('all(test, not(feature = "x"))')
+    assert cfg_requires_test("not(any(not(test), miri))")
+    assert not cfg_requires_test("any(test, unix)")
+    assert not cfg_requires_test("not(test)")
+
+
+@pytest.mark.unit
+def test_platform_helpers_normalize_and_filter():
+    assert platforms_in_condition("os.name == 'nt'") == ["windows"]
+    assert platforms_in_condition("process.platform !== 'darwin'") == ["!macos"]
+    assert is_platform_excluded({"platforms": ["windows"]}, ["win32"])
+    assert not is_platform_excluded({"platforms": ["!windows"]}, ["windows"])
+    assert not is_platform_excluded({"platforms": ["linux", "macos"]}, ["linux"])
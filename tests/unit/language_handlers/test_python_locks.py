@@ -0,0 +1,121 @@
+"""
+Pipenv, Poetry and uv lockfiles
+"""
+
+import json
+
+import pytest
+
+from gardener.treewalk.python import PythonLanguageHandler
+
+PIPFILE = """
+[packages]
+requests = "==2.31.0"
+PyYAML = "*"
+
+[dev-packages]
+pytest = {version = ">=8"}
+"""
+
+PIPFILE_LOCK = {
+    "_meta": {"hash": {"sha256": "abc"}},
+    "default": {
+        "requests": {"hashes": ["sha256:req"], "version": "==2.31.0"},
+        "pyyaml": {"hashes": ["sha256:yaml"], "version": "==6.0.1"},
+        "urllib3": {"hashes": ["sha256:url"], "version": "==2.2.1"},
+    },
+    "develop": {"pytest": {"hashes": ["sha256:pytest"], "version": "==8.1.1"}},
+}
+
+POETRY_LOCK = """
+[[package]]
+name = "fastapi"
+version = "0.110.0"
+files = [{file = "fastapi-0.110.0-py3-none-any.whl", hash = "sha256:fastapi"}]
+
+[[package]]
+name = "starlette"
+version = "0.36.3"
+files = []
+
+[metadata]
+lock-version = "2.0"
+"""
+
+UV_LOCK = """
+version = 1
+
+[[package]]
+name = "service"
+version = "0.1.0"
+source = { editable = "." }
+dependencies = [{ name = "httpx" }]
+
+[package.optional-dependencies]
+cli = [{ name = "typer" }]
+
+[[package]]
+name = "httpx"
+version = "0.27.0"
+source = { registry = "https://pypi.org/simple" }
+sdist = { url = "https://files/httpx-0.27.0.tar.gz", hash = "sha256:sdist" }
+wheels = [{ url = "https://files/httpx-0.27.0-py3-none-any.whl", hash = "sha256:wheel" }]
+
+[[package]]
+name = "typer"
+version = "0.12.3"
+source = { registry = "https://pypi.org/simple" }
+
+[[package]]
+name = "anyio"
+version = "4.3.0"
+source = { registry = "https://pypi.org/simple" }
+"""
+
+
+@pytest.mark.unit
+def test_pipfile_lock_matches_declared_spellings_and_marks_transitive(tmp_path):
+    (tmp_path / "Pipfile").write_text(PIPFILE)
+    (tmp_path / "Pipfile.lock").write_text(json.dumps(PIPFILE_LOCK))
+    handler = PythonLanguageHandler()
+
+    declared = handler.process_manifest(str(tmp_path / "Pipfile"), {})
+    locked = handler.process_manifest(str(tmp_path / "Pipfile.lock"), {})
+
+    assert {name: (info["dep_kind"], info.get("version")) for name, info in declared.items()} == {
+        "requests": ("dependencies", "==2.31.0"),
+        "PyYAML": ("dependencies", None),
+        "pytest": ("dev-dependencies", ">=8"),
+    }
+    assert locked["PyYAML"] == {
+        "ecosystem": "pypi",
+        "locked_version": "6.0.1",
+        "hashes": ["sha256:yaml"],
+        "direct": True,
+    }
+    assert locked["pytest"]["direct"] is True
+    assert locked["urllib3"]["transitive"] is True
+
+
+@pytest.mark.unit
+def test_poetry_and_uv_locks_record_versions_hashes_and_direct_dependencies(tmp_path):
+    (tmp_path / "poetry").mkdir()
+    (tmp_path / "poetry" / "pyproject.toml").write_text('[tool.poetry.dependencies]\nfastapi = "^0.110"\n')
+    (tmp_path / "poetry" / "poetry.lock").write_text(POETRY_LOCK)
+    (tmp_path / "uv.lock").write_text(UV_LOCK)
+    handler = PythonLanguageHandler()
+
+    poetry = handler.process_manifest(str(tmp_path / "poetry" / "poetry.lock"), {})
+    uv = handler.process_manifest(str(tmp_path / "uv.lock"), {})
+
+    assert poetry["fastapi"] == {
+        "ecosystem": "pypi",
+        "locked_version": "0.110.0",
+        "hashes": ["sha256:fastapi"],
+        "direct": True,
+    }
+    assert poetry["starlette"] == {"ecosystem": "pypi", "locked_version": "0.36.3", "direct": False, "transitive": True}
+    assert sorted(uv) == ["anyio", "httpx", "typer"]
+    assert uv["httpx"]["hashes"] == ["sha256:sdist", "sha256:wheel"]
+    assert uv["typer"]["direct"] is True
+    assert uv["anyio"]["transitive"] is True
@@ -0,0 +1,239 @@
+"""
+Python manifests: dependency kinds and versions
+"""
+
+import pytest
+
+from gardener.treewalk.python import PythonLanguageHandler
+
+PYPROJECT = """
+[build-system]
+requires = ["hatchling>=1.18"]
+
+[project]
+name = "service"
+dependencies = ["requests>=2.31,<3", "psycopg[binary] (>=3.1); python_version > '3.10'"]
+
+[project.optional-dependencies]
+docs = ["sphinx~=7.0", "requests[socks]"]
+
+[dependency-groups]
+test = ["pytest>=8"]
+
+[tool.poetry.dependencies]
+python = "^3.11"
+fastapi = "^0.110"
+boto3 = { version = "^1.34", optional = true }
+
+[tool.poetry.group.lint.dependencies]
+ruff = "*"
+
+[tool.pdm.dev-dependencies]
+lint = ["black"]
+
+[tool.uv]
+dev-dependencies = ["mypy==1.9.0"]
+
+[tool.hatch.envs.docs]
+dependencies = ["mkdocs"]
+"""
+
+
+@pytest.mark.unit
+def test_pyproject_tables_set_versions_and_dependency_kinds(tmp_path):
+    (tmp_path / "pyproject.toml").write_text(PYPROJECT)
+    manifest = str(tmp_path / "pyproject.toml")
+
+    packages = PythonLanguageHandler().process_manifest(manifest, {})
+
+    assert {name: info["dep_kind"] for name, info in packages.items()} == {
+        "requests": "dependencies",
+        "psycopg": "dependencies",
+        "sphinx": "optional-dependencies",
+        "hatchling": "build-dependencies",
+        "pytest": "dev-dependencies",
+        "fastapi": "dependencies",
+        "boto3": "optional-dependencies",
+        "ruff": "dev-dependencies",
+        "black": "dev-dependencies",
+        "mypy": "dev-dependencies",
+        "mkdocs": "dev-dependencies",
+    }
+    assert packages["requests"] == {
+        "ecosystem": "pypi",
+        "version": ">=2.31,<3",
+        "dep_kind": "dependencies",
+        "manifest_dep_kinds": {manifest: "dependencies"},
+        "extras": ["docs"],
+    }
+    assert packages["psycopg"]["version"] == ">=3.1"
+    assert packages["boto3"]["version"] == "^1.34"
+    assert "version" not in packages["ruff"]
+
+
+@pytest.mark.unit
+def test_requirements_follow_includes_and_constraints(tmp_path):
+    (tmp_path / "requirements").mkdir()
+    (tmp_path / "requirements" / "base.txt").write_text(
+        "-c constraints.txt\n"
+        "uvicorn[standard]>=0.29  # server\n"
+        'tomli>=2.0; python_version < "3.11"\n'
+        "requests\n"
+    )
+    (tmp_path / "requirements" / "constraints.txt").write_text("requests==2.31.0\nurllib3==2.2.1\n")
+    (tmp_path / "requirements-dev.txt").write_text(
+        "-r requirements/base.txt\n"
+        "--requirement requirements-dev.txt\n"
+        "-e git+https://github.com/org/plugin.git@main#egg=plugin\n"
+        "-e .\n"
+        "wheelhouse @ https://example.com/wheelhouse-1.0.tar.gz \\\n"
+        "    --hash=sha256:abc\n"
+    )
+    manifest = str(tmp_path / "requirements-dev.txt")
+    base = str(tmp_path / "requirements" / "base.txt")
+
+    packages = PythonLanguageHandler().process_manifest(manifest, {})
+
+    assert sorted(packages) == ["plugin", "requests", "tomli", "uvicorn", "wheelhouse"]
+    assert packages["uvicorn"]["version"] == ">=0.29"
+    assert packages["uvicorn"]["requirements"] == [{"extras": ["standard"], "file": base, "line": 2}]
+    assert packages["tomli"]["requirements"][0]["marker"] == 'python_version < "3.11"'
+    assert packages["requests"]["constraint"] == {
+        "version": "==2.31.0",
+        "file": str(tmp_path / "requirements" / "constraints.txt"),
+    }
+    assert packages["plugin"]["requirements"] == [
+        {"editable": True, "url": "git+https://github.com/org/plugin.git@main#egg=plugin", "file": manifest, "line": 3}
+    ]
+    assert packages["wheelhouse"]["requirements"][0]["url"] == "https://example.com/wheelhouse-1.0.tar.gz"
+    assert {info["dep_kind"] for info in packages.values()} == {"dev-dependencies"}
+
+
+@pytest.mark.unit
+def test_conda_environment_distinguishes_channels_from_pip(tmp_path):
+    (tmp_path / "environment.yml").write_text(
+        "name: analysis\n"
+        "channels:\n"
+        "  - conda-forge\n"
+        "  - nodefaults\n"
+        "dependencies:\n"
+        "  - python=3.11\n"
+        "  - numpy=1.26=py311h64a7726_0\n"
+        "  - bioconda::samtools>=1.17\n"
+        "  - pip\n"
+        "  - pip:\n"
+        "    - uvicorn[standard]>=0.29\n"
+    )
+    manifest = str(tmp_path / "environment.yml")
+
+    packages = PythonLanguageHandler().process_manifest(manifest, {})
+
+    assert sorted(packages) == ["numpy", "samtools", "uvicorn"]
+    assert packages["numpy"]["version"] == "1.26"
+    assert packages["numpy"]["conda_environment"] == [
+        {"file": manifest, "installer": "conda", "channel": "conda-forge", "channel_pinned": False}
+    ]
+    assert packages["samtools"]["conda_environment"][0]["channel"] == "bioconda"
+    assert packages["samtools"]["conda_environment"][0]["channel_pinned"] is True
+    assert packages["uvicorn"]["conda_environment"] == [{"file": manifest, "installer": "pip", "extras": ["standard"]}]
+    assert packages["uvicorn"]["dep_kind"] == "dependencies"
+    assert packages["numpy"]["ecosystem"] == packages["samtools"]["ecosystem"] == "conda"
+    assert packages["uvicorn"]["ecosystem"] == "pypi"
+
+
+@pytest.mark.unit
+def test_setup_cfg_requirement_keywords(tmp_path):
+    (tmp_path / "setup.cfg").write_text(
+        "[metadata]\n"
+        "name = legacy\n\n"
+        "[options]\n"
+        "install_requires =\n"
+        "    requests>=2.20\n"
+        "    click\n"
+        "setup_requires = setuptools_scm>=6\n"
+        "tests_require = pytest\n\n"
+        "[options.extras_require]\n"
+        "yaml = PyYAML>=5.1\n"
+    )
+
+    packages = PythonLanguageHandler().process_manifest(str(tmp_path / "setup.cfg"), {})
+
+    assert {name: (info["dep_kind"], info.get("version")) for name, info in packages.items()} == {
+        "requests": ("dependencies", ">=2.20"),
+        "click": ("dependencies", None),
+        "setuptools_scm": ("build-dependencies", ">=6"),
+        "pytest": ("dev-dependencies", None),
+        "PyYAML": ("optional-dependencies", ">=5.1"),
+    }
+
+
+@pytest.mark.unit
+def test_setup_py_literals_and_module_level_names(tmp_path):
+    (tmp_path / "setup.py").write_text(
+        "from setuptools import setup\n\n"
+        "REQUIREMENTS = ['numpy>=1.22', 'scipy']\n\n"
+        "setup(\n"
+        "    name='legacy',\n"
+        "    install_requires=REQUIREMENTS,\n"
+        "    setup_requires=('cython',),\n"
+        "    extras_require={'plot': ['matplotlib>=3'], 'all': ['matplotlib', 'pandas']},\n"
+        "    tests_require=[line for line in open('test-requirements.txt')],\n"
+        ")\n"
+    )
+
+    packages = PythonLanguageHandler().process_manifest(str(tmp_path / "setup.py"), {})
+
+    assert {name: (info["dep_kind"], info.get("version")) for name, info in packages.items()} == {
+        "numpy": ("dependencies", ">=1.22"),
+        "scipy": ("dependencies", None),
+        "cython": ("build-dependencies", None),
+        "matplotlib": ("optional-dependencies", ">=3"),
+        "pandas": ("optional-dependencies", None),
+    }
+
+
+@pytest.mark.unit
+def test_extras_are_recorded_by_name(tmp_path):
+    (tmp_path / "pyproject.toml").write_text(
+        "[project]\n"
+        'dependencies = ["requests"]\n\n'
+        "[project.optional-dependencies]\n"
+        'docs = ["sphinx", "requests[socks]"]\n'
+        'dev = ["sphinx", "pytest"]\n\n'
+        "[tool.poetry.dependencies]\n"
+        'psycopg2 = { version = "^2.9", optional = true }\n\n'
+        "[tool.poetry.extras]\n"
+        'pgsql = ["psycopg2"]\n'
+    )
+    (tmp_path / "setup.py").write_text(
+        "from setuptools import setup\n\n"
+        "setup(extras_require={'yaml:python_version>=\"3.8\"': ['PyYAML'], ':sys_platform==\"win32\"': ['colorama']})\n"
+    )
+    handler = PythonLanguageHandler()
+
+    packages = handler.process_manifest(str(tmp_path / "pyproject.toml"), {})
+    handler.process_manifest(str(tmp_path / "setup.py"), packages)
+
+    assert packages["sphinx"]["extras"] == ["docs", "dev"]
+    assert packages["pytest"]["extras"] == ["dev"]
+    assert packages["psycopg2"]["extras"] == ["pgsql"]
+    assert packages["PyYAML"]["extras"] == ["yaml"]
+    assert packages["requests"]["dep_kind"] == "dependencies"
+    assert packages["colorama"]["dep_kind"] == "dependencies"
+    assert "extras" not in packages["colorama"]
+
+
+@pytest.mark.unit
+def test_stub_only_distributions_are_type_only(tmp_path):
+    (tmp_path / "requirements-dev.txt").write_text("types-requests>=2.31\npandas-stubs\ntypes_PyYAML\nrequests\n")
+
+    packages = PythonLanguageHandler().process_manifest(str(tmp_path / "requirements-dev.txt"), {})
+
+    assert {name: info.get("stubs_for") for name, info in packages.items()} == {
+        "types-requests": "requests",
+        "pandas-stubs": "pandas",
+        "types_PyYAML": "PyYAML",
+        "requests": None,
+    }
+    assert packages["types-requests"]["type_only"] is True
+    assert "type_only" not in packages["requests"]
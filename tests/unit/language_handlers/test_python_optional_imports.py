@@ -0,0 +1,104 @@
+"""
+Python imports that are optional at runtime
+"""
+
+from collections import defaultdict
+
+import pytest
+
+from gardener.treewalk.python import PythonLanguageHandler
+
+
+def _extract(tree_parser, logger, code):
+    annotations = {}
+    external, _ = PythonLanguageHandler(logger).extract_imports(
+        tree_parser("python", code), "app.py", defaultdict(list), lambda *_: None, import_annotations=annotations
+    )
+    return external, annotations
+
+
+@pytest.mark.unit
+def test_try_except_import_error_records_the_fallback_chain(tree_parser, logger):
+    code = (
+        "import requests\n"
+        "try:\n"
+        "    import orjson as json\n"
+        "except ImportError:\n"
+        "    try:\n"
+        "        import ujson as json\n"
+        "    except ModuleNotFoundError:\n"
+        "        import json\n"
+        "try:\n"
+        "    from lxml import etree\n"
+        "except (ImportError, AttributeError):\n"
+        "    etree = None\n"
+        "try:\n"
+        "    import requests\n"
+        "    import yaml\n"
+        "except ValueError:\n"
+        "    pass\n"
+    )
+
+    external, annotations = _extract(tree_parser, logger, code)
+
+    assert {"requests", "orjson", "ujson", "json", "lxml", "yaml"} <= set(external)
+    assert annotations["orjson"] == {"evidence": "optional-fallback", "fallback_chain": ["orjson", "ujson", "json"]}
+    assert annotations["json"]["fallback_chain"] == ["orjson", "ujson", "json"]
+    assert annotations["lxml"] == {"evidence": "optional-fallback", "fallback_chain": ["lxml"]}
+    # Imported unconditionally as well, or under a handler that is not for ImportError
+    assert "requests" not in annotations
+    assert "yaml" not in annotations
+
+
+@pytest.mark.unit
+def test_import_module_and_dunder_import_are_dynamic_evidence(tree_parser, logger):
+    code = (
+        "import importlib\n"
+        "from importlib import import_module\n"
+        "yaml = importlib.import_module('yaml')\n"
+        "backend = import_module(f'storages.backends.{name}')\n"
+        "json = __import__('json')\n"
+        "plugin = importlib.import_module(plugin_name)\n"
+        "ext = __import__('ext_' + name)\n"
+        "import json\n"
+    )
+    handler = PythonLanguageHandler(logger)
+    annotations = {}
+
+    external, _ = handler.extract_imports(
+        tree_parser("python", code), "app.py", defaultdict(list), lambda *_: None, import_annotations=annotations
+    )
+
+    assert {"yaml", "storages", "json"} <= set(external)
+    assert annotations["yaml"] == {"evidence": "dynamic-import", "expressions": ["'yaml'"]}
+    assert annotations["storages"]["expressions"] == ["f'storages.backends.{name}'"]
+    assert "json" not in annotations
+    assert handler.dynamic_unresolved["app.py"] == [
+        {"kind": "import_module", "expression": "plugin_name", "line": 6},
+        {"kind": "__import__", "expression": "'ext_' + name", "line": 7, "prefix": "ext_"},
+    ]
+
+
+@pytest.mark.unit
+def test_type_checking_imports_are_type_only(tree_parser, logger):
+    code = (
+        "import typing\n"
+        "from typing import TYPE_CHECKING\n"
+        "import attrs\n"
+        "if TYPE_CHECKING:\n"
+        "    from pandas import DataFrame\n"
+        "    import attrs\n"
+        "else:\n"
+        "    import numpy\n"
+        "if typing.TYPE_CHECKING:\n"
+        "    from botocore.client import BaseClient\n"
+    )
+
+    external, annotations = _extract(tree_parser, logger, code)
+
+    assert {"pandas", "attrs", "numpy", "botocore"} <= set(external)
+    assert annotations["pandas"] == {"evidence": "type-only"}
+    assert annotations["botocore"] == {"evidence": "type-only"}
+    # Also imported at runtime, or in the branch type checkers skip
+    assert "attrs" not in annotations
+    assert "numpy" not in annotations
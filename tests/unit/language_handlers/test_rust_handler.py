@@ -2,12 +2,24 @@
 Rust handler – focused unit checks
 """
 
+import json
 import os
 from collections import defaultdict
 
 import pytest
 
-from gardener.treewalk.rust import RustLanguageHandler
+from gardener.analysis import cargo_targets
+from gardener.analysis.bundle import request_hook
+from gardener.analysis.cargo_dep_kinds import cross_check_dep_kinds
+from gardener.analysis.cargo_metadata import merge_cargo_metadata, run_cargo_metadata
+from gardener.analysis.cargo_packages import group_by_package
+from gardener.analysis.cargo_targets import classify_targets
+from gardener.analysis.cargo_undeclared import find_undeclared_imports
+from gardener.analysis.cargo_unused import find_unused_dependencies
+from gardener.analysis.main import DependencyAnalyzer
+from gardener.common.defaults import ConfigOverride
+from gardener.package_metadata.url_resolver import resolve_package_urls
+from gardener.treewalk.rust import RustLanguageHandler, doc_comment_text, doctest_blocks, inherit_workspace_dependency
 
 
 def _mock_resolve(importing_file, use_path_parts):
@@ -91,3 +103,730 @@ def test_services_mod_rs_internal_helper(tree_parser, logger):
     assert "src/models/user.rs" in set(local)
     _comps = set(comps["src/services/mod.rs"])
     assert any(s.endswith("internal_helper::perform_action") for (_, s) in _comps)
+
+
+@pytest.mark.unit
+def test_renamed_dependencies_map_alias_imports_to_the_real_crate(tmp_path, graph_builder):
+    (tmp_path / "crates" / "a").mkdir(parents=True)
+    (tmp_path / "crates" / "b").mkdir(parents=True)
+    (tmp_path / "crates" / "a" / "Cargo.toml").write_text('[package]\nname = "a"\n\n[dependencies]\nreal-crate = "1"\n')
+    (tmp_path / "crates" / "b" / "Cargo.toml").write_text(
+        '[package]\nname = "b"\n\n[dependencies]\nzod_like = { package = "real-crate", version = "1" }\n'
+    )
+
+    packages = DependencyAnalyzer(verbose=False).discover_packages(str(tmp_path), ["rust"])
+
+    assert "zod_like" not in packages
+    assert packages["real-crate"]["import_names"] == ["real_crate", "zod_like"]
+    assert packages["real-crate"]["aliases"] == ["zod_like"]
+
+    graph_builder.build_dependency_graph(
+        source_files={"crates/b/src/lib.rs": "crates/b/src/lib.rs"},
+        external_packages=packages,
+        file_imports={"crates/b/src/lib.rs": ["zod_like"]},
+        file_package_components={"crates/b/src/lib.rs": [("zod_like", "zod_like::Schema")]},
+        local_imports_map={},
+    )
+    graph = graph_builder.graph
+    assert graph.has_edge("crates/b/src/lib.rs", "real-crate")
+    assert graph.nodes["zod_like::Schema"]["distribution_name"] == "real-crate"
+
+
+@pytest.mark.unit
+def test_workspace_member_crates_are_internal(tmp_path):
+    (tmp_path / "Cargo.toml").write_text('[workspace]\nmembers = ["crates/*"]\nexclude = ["crates/old"]\n')
+    for crate in ("core", "app", "old"):
+        (tmp_path / "crates" / crate / "src").mkdir(parents=True)
+    (tmp_path / "crates" / "core" / "Cargo.toml").write_text('[package]\nname = "my-core"\n')
+    (tmp_path / "crates" / "core" / "src" / "lib.rs").write_text("pub mod model;\n")
+    (tmp_path / "crates" / "core" / "src" / "model.rs").write_text("pub struct Thing;\n")
+    (tmp_path / "crates" / "app" / "Cargo.toml").write_text(
+        '[package]\nname = "app"\n\n[dependencies]\nmy-core = { path = "../core" }\nserde = "1"\n'
+    )
+    (tmp_path / "crates" / "app" / "src" / "main.rs").write_text("use my_core::model::Thing;\nfn main() {}\n")
+    (tmp_path / "crates" / "old" / "Cargo.toml").write_text('[package]\nname = "old"\n')
+
+    analyzer = DependencyAnalyzer(verbose=False)
+    packages = analyzer.discover_packages(str(tmp_path), ["rust"])
+
+    assert set(packages) == {"serde"}
+    members = analyzer.repo_analyzer.workspace_members
+    assert set(members) == {"my-core", "app"}
+    assert members["my-core"] == {
+        "annotation": "workspace-member",
+        "manifest": "crates/core/Cargo.toml",
+        "path": "crates/core",
+        "root": "crates/core/src/lib.rs",
+        "import_names": ["my_core"],
+        "via": ["workspace", "path-dependency"],
+    }
+    resolver = analyzer.repo_analyzer._get_local_resolver()
+    assert resolver.resolve_rust("crates/app/src/main.rs", ["my_core", "model", "Thing"]) == "crates/core/src/model.rs"
+    assert resolver.resolve_rust("crates/app/src/main.rs", ["my_core", "Config"]) == "crates/core/src/lib.rs"
+    assert resolver.resolve_rust("crates/app/src/main.rs", ["serde", "Serialize"]) is None
+
+
+@pytest.mark.unit
+def test_workspace_inherited_dependencies_take_the_root_spec(tmp_path):
+    (tmp_path / "Cargo.toml").write_text(
+        '[workspace]\nmembers = ["crates/*"]\n\n[workspace.dependencies]\n'
+        'serde = { version = "1.0.190", features = ["derive"] }\ntokio = "1.35"\n'
+        'json = { package = "serde_json", version = "1.0.108" }\n'
+        'internal-log = { version = "0.3", registry = "acme" }\n'
+    )
+    (tmp_path / "crates" / "a").mkdir(parents=True)
+    manifest = tmp_path / "crates" / "a" / "Cargo.toml"
+    manifest.write_text(
+        '[package]\nname = "a"\n\n[dependencies]\nserde = { workspace = true, features = ["rc"] }\n'
+        "tokio.workspace = true\njson = { workspace = true }\ninternal-log = { workspace = true }\n"
+    )
+
+    packages = RustLanguageHandler().process_manifest(str(manifest), {})
+
+    assert packages["serde"]["version"] == "1.0.190"
+    assert packages["tokio"]["version"] == "1.35"
+    assert packages["serde_json"]["version"] == "1.0.108"
+    assert packages["serde_json"]["aliases"] == ["json"]
+    assert packages["internal-log"]["version"] == "0.3"
+    assert packages["internal-log"]["registry"] == "acme"
+    assert inherit_workspace_dependency({"workspace": True, "features": ["rc"]}, {"features": ["derive"]}) == {
+        "features": ["derive", "rc"]
+    }
+
+
+@pytest.mark.unit
+def test_cargo_lock_reports_resolved_versions_and_transitive_crates(tmp_path):
+    lock = tmp_path / "Cargo.lock"
+    lock.write_text(
+        "version = 3\n\n"
+        '[[package]]\nname = "app"\nversion = "0.1.0"\ndependencies = ["serde", "syn 2.0.39", "util"]\n\n'
+        '[[package]]\nname = "util"\nversion = "0.1.0"\n\n'
+        '[[package]]\nname = "serde"\nversion = "1.0.190"\n'
+        'source = "registry+https://github.com/rust-lang/crates.io-index"\nchecksum = "91d3c334"\n'
+        'dependencies = ["serde_derive"]\n\n'
+        '[[package]]\nname = "serde_derive"\nversion = "1.0.190"\n'
+        'source = "registry+https://github.com/rust-lang/crates.io-index"\nchecksum = "67c5609f"\n\n'
+        '[[package]]\nname = "syn"\nversion = "2.0.39"\n'
+        'source = "registry+https://github.com/rust-lang/crates.io-index"\nchecksum = "23e78b90"\n\n'
+        '[[package]]\nname = "syn"\nversion = "1.0.109"\n'
+        'source = "registry+https://github.com/rust-lang/crates.io-index"\nchecksum = "72b64191"\n'
+    )
+
+    packages = RustLanguageHandler().process_manifest(str(lock), {})
+
+    assert "app" not in packages and "util" not in packages
+    assert packages["serde"] == {
+        "ecosystem": "cargo",
+        "locked_version": "1.0.190",
+        "checksum": "91d3c334",
+        "direct": True,
+    }
+    assert packages["serde_derive"]["direct"] is False
+    assert packages["serde_derive"]["transitive"] is True
+    assert packages["syn"]["locked_version"] == "2.0.39"
+    assert packages["syn"]["locked_versions"] == ["2.0.39", "1.0.109"]
+    assert packages["syn"]["checksum"] == "23e78b90"
+
+
+@pytest.mark.unit
+def test_dependency_kinds_are_cross_checked_against_import_contexts(tmp_path):
+    manifest = tmp_path / "Cargo.toml"
+    manifest.write_text(
+        '[package]\nname = "app"\n\n[dependencies]\nserde = "1"\nanyhow = "1"\n\n'
+        '[dev-dependencies]\nproptest = "1"\nserde = "1"\ntempfile = "3"\n\n'
+        '[build-dependencies]\ncc = "1"\n\n'
+        "[target.'cfg(windows)'.dependencies]\nwinapi = \"0.3\"\n"
+    )
+
+    packages = RustLanguageHandler().process_manifest(str(manifest), {})
+
+    assert {name: info["dep_kind"] for name, info in packages.items()} == {
+        "serde": "normal",
+        "anyhow": "normal",
+        "proptest": "dev",
+        "tempfile": "dev",
+        "cc": "build",
+        "winapi": "normal",
+    }
+    assert packages["winapi"]["targets"] == ["cfg(windows)"]
+    assert "targets" not in packages["serde"]
+
+    file_imports = {
+        "src/lib.rs": ["serde", "tempfile"],
+        "tests/it.rs": ["serde", "proptest", "anyhow"],
+        "build.rs": ["cc"],
+    }
+    source_files = {path: {"language": "rust"} for path in file_imports}
+    mismatches = cross_check_dep_kinds(packages, file_imports, source_files)
+
+    assert packages["serde"]["import_contexts"] == ["dev", "normal"]
+    assert packages["cc"]["import_contexts"] == ["build"]
+    assert mismatches == {
+        "tempfile": "dev-dependency imported outside tests/, benches/ and examples/",
+        "anyhow": "only imported from tests/, benches/ or examples/; could be a dev-dependency",
+    }
+
+
+@pytest.mark.unit
+def test_optional_dependencies_report_the_features_enabling_them(tmp_path):
+    manifest = tmp_path / "Cargo.toml"
+    manifest.write_text(
+        '[package]\nname = "app"\n\n'
+        '[dependencies]\nanyhow = "1"\n'
+        'serde = { version = "1", optional = true }\n'
+        'serde_json = { version = "1", optional = true }\n'
+        'rayon = { version = "1", optional = true }\n'
+        'tokio = { version = "1", optional = true }\n\n'
+        "[features]\n"
+        'default = ["std"]\nstd = ["json"]\n'
+        'serde-support = ["dep:serde"]\njson = ["serde-support", "serde_json/std"]\n'
+        'runtime = ["tokio?/rt"]\n'
+    )
+
+    packages = RustLanguageHandler().process_manifest(str(manifest), {})
+
+    assert "optional" not in packages["anyhow"]
+    assert packages["serde"]["optional"] is True
+    assert packages["serde"]["optional_features"] == ["default", "json", "serde-support", "std"]
+    assert packages["serde"]["default_enabled"] is True
+    # Implicit feature named after the dependency, plus `name/feature` syntax
+    assert packages["serde_json"]["optional_features"] == ["default", "json", "serde_json", "std"]
+    assert packages["rayon"]["optional_features"] == ["rayon"]
+    assert packages["rayon"]["default_enabled"] is False
+    # `tokio?/rt` only configures tokio when something else enables it
+    assert packages["tokio"]["optional_features"] == ["tokio"]
+
+
+@pytest.mark.unit
+def test_proc_macro_crates_are_tagged_from_local_manifests_and_the_registry(tmp_path):
+    (tmp_path / "Cargo.toml").write_text(
+        '[package]\nname = "app"\n\n[dependencies]\n'
+        'app-macros = { path = "macros" }\nserde_derive = "1"\nthiserror-impl = "1"\nserde = "1"\n'
+    )
+    (tmp_path / "macros").mkdir()
+    (tmp_path / "macros" / "Cargo.toml").write_text('[package]\nname = "app-macros"\n\n[lib]\nproc-macro = true\n')
+    (tmp_path / "vendor" / "serde_derive").mkdir(parents=True)
+    (tmp_path / "vendor" / "serde_derive" / "Cargo.toml").write_text(
+        '[package]\nname = "serde_derive"\n\n[lib]\nproc-macro = true\n'
+    )
+
+    analyzer = DependencyAnalyzer(verbose=False)
+    packages = analyzer.discover_packages(str(tmp_path), ["rust"])
+
+    assert analyzer.repo_analyzer.workspace_members["app-macros"]["role"] == "proc-macro"
+    assert packages["serde_derive"]["role"] == "proc-macro"
+    assert packages["serde_derive"]["role_source"] == "manifest"
+    assert "role" not in packages["serde"]
+
+    crates_io = {
+        "https://crates.io/api/v1/crates/thiserror-impl": json.dumps(
+            {"crate": {"categories": ["development-tools::procedural-macro-helpers"], "keywords": []}}
+        ),
+        "https://crates.io/api/v1/crates/serde": json.dumps({"crate": {"categories": ["encoding"]}}),
+    }
+    with request_hook(crates_io.get):
+        resolve_package_urls({name: packages[name] for name in ("thiserror-impl", "serde")})
+
+    assert packages["thiserror-impl"]["role"] == "proc-macro"
+    assert packages["thiserror-impl"]["role_source"] == "registry"
+    assert "role" not in packages["serde"]
+
+
+@pytest.mark.unit
+def test_path_and_git_dependencies_resolve_without_crates_io(tmp_path):
+    (tmp_path / "Cargo.toml").write_text(
+        '[package]\nname = "app"\n\n[dependencies]\n'
+        'sibling = { path = "../sibling", version = "0.3" }\n'
+        'tokio = { git = "https://github.com/tokio-rs/tokio.git", tag = "tokio-1.38.0" }\n'
+        'serde = "1"\n'
+    )
+    (tmp_path / "Cargo.lock").write_text(
+        'version = 3\n\n[[package]]\nname = "app"\nversion = "0.1.0"\ndependencies = ["serde", "sibling", "tokio"]\n\n'
+        '[[package]]\nname = "tokio"\nversion = "1.38.0"\n'
+        'source = "git+https://github.com/tokio-rs/tokio.git?tag=tokio-1.38.0#0123abc"\n'
+    )
+
+    analyzer = DependencyAnalyzer(verbose=False)
+    packages = analyzer.discover_packages(str(tmp_path), ["rust"])
+
+    assert packages["sibling"]["local_path"] == "../sibling"
+    assert packages["tokio"]["git_url"] == "https://github.com/tokio-rs/tokio.git"
+    assert packages["tokio"]["git_pin"] == {"tag": "tokio-1.38.0"}
+    assert packages["tokio"]["git_commit"] == "0123abc"
+    assert "sibling" in analyzer._collect_self_package_names()
+
+    requested = []
+
+    def crates_io(url):
+        requested.append(url)
+        return json.dumps({"crate": {"repository": "https://github.com/serde-rs/serde"}})
+
+    with request_hook(crates_io):
+        urls = resolve_package_urls(packages)
+
+    assert urls == {"tokio": "https://github.com/tokio-rs/tokio", "serde": "https://github.com/serde-rs/serde"}
+    assert requested == ["https://crates.io/api/v1/crates/serde"]
+
+
+@pytest.mark.unit
+def test_patch_and_replace_sections_report_original_and_effective_sources(tmp_path):
+    (tmp_path / "Cargo.toml").write_text(
+        '[package]\nname = "app"\n\n[dependencies]\nserde = "1"\nlibc = "0.2"\nrand = "0.8"\nlog = "0.4"\n\n'
+        '[patch.crates-io]\nserde = { git = "https://github.com/me/serde", branch = "fix" }\n'
+        'libc = { path = "../libc" }\nunused = { path = "../unused" }\n\n'
+        '[replace]\n"rand:0.8.5" = { git = "https://github.com/me/rand", rev = "abc123" }\n'
+    )
+
+    packages = DependencyAnalyzer(verbose=False).discover_packages(str(tmp_path), ["rust"])
+
+    assert packages["serde"]["patched"] is True
+    assert packages["serde"]["patch"] == {
+        "manifest": "Cargo.toml",
+        "section": "patch.crates-io",
+        "original": {"name": "serde", "source": "crates-io"},
+        "effective": {"git": "https://github.com/me/serde", "branch": "fix"},
+    }
+    assert packages["libc"]["patch"]["effective"] == {"path": "../libc"}
+    assert packages["rand"]["patch"]["section"] == "replace"
+    assert packages["rand"]["patch"]["original"] == {"name": "rand", "source": "crates-io", "version": "0.8.5"}
+    assert packages["rand"]["patch"]["effective"] == {"git": "https://github.com/me/rand", "rev": "abc123"}
+    assert "patched" not in packages["log"] and "unused" not in packages
+
+
+@pytest.mark.unit
+def test_extern_crate_declarations_are_external_evidence_with_aliases(tree_parser, logger):
+    code = (
+        "use rand_core::RngCore;\n\n"
+        "#[macro_use]\nextern crate serde;\n"
+        "extern crate rand as rand_core;\n"
+        "extern crate std;\n"
+        '#[cfg(target_os = "windows")]\nextern crate winapi;\n'
+    )
+    root = tree_parser("rust", code)
+    comps = defaultdict(list)
+    annotations = {}
+
+    external, _ = RustLanguageHandler(logger).extract_imports(
+        root, "src/lib.rs", comps, _mock_resolve, import_annotations=annotations
+    )
+
+    assert set(external) == {"serde", "rand", "winapi"}
+    assert comps["src/lib.rs"] == [("rand", "rand::RngCore")]
+    assert annotations["rand"] == {"extern_crate_aliases": ["rand_core"]}
+    assert annotations["winapi"]["platforms"] == ["windows"]
+
+
+@pytest.mark.unit
+def test_fully_qualified_paths_are_reported_as_candidate_crates(tree_parser, logger):
+    code = (
+        "mod utils;\n"
+        "use std::collections::HashMap;\n\n"
+        "fn main() {\n"
+        '    let value: serde_json::Value = serde_json::from_str("{}").unwrap();\n'
+        "    tokio::spawn(async {});\n"
+        "    let map: HashMap<u8, u8> = HashMap::new();\n"
+        "    utils::helper();\n"
+        "    std::process::exit(0);\n"
+        "}\n"
+    )
+    root = tree_parser("rust", code)
+    comps = defaultdict(list)
+    annotations = {}
+
+    external, _ = RustLanguageHandler(logger).extract_imports(
+        root, "src/main.rs", comps, _mock_resolve, import_annotations=annotations
+    )
+
+    assert {"serde_json", "tokio"} <= set(external)
+    assert "utils" not in external and "HashMap" not in external
+    assert annotations["serde_json"] == {"evidence": "qualified-path"}
+    assert {("serde_json", "serde_json::from_str"), ("tokio", "tokio::spawn")} <= set(comps["src/main.rs"])
+
+
+@pytest.mark.unit
+def test_doctest_blocks_follow_rustdoc_fences():
+    doc = "\n".join(
+        doc_comment_text(line)
+        for line in [
+            "/// Parses input.",
+            "///",
+            "/// ```",
+            "/// # use proptest::prelude::*;",
+            "/// let x = serde_json::json!({});",
+            "/// ```",
+            "///",
+            "/// ```rust,no_run,edition2021",
+            "/// tokio::spawn(async {});",
+            "/// ```",
+            "///",
+            "/// ```text",
+            "/// use not_code::Thing;",
+            "/// ```",
+            "/// ```ignore",
+            "/// use broken::Thing;",
+            "/// ```",
+        ]
+    )
+
+    assert list(doctest_blocks(doc)) == [
+        "use proptest::prelude::*;\nlet x = serde_json::json!({});",
+        "tokio::spawn(async {});",
+    ]
+    assert doc_comment_text("//// not a doc comment") is None
+    assert doc_comment_text("/*! ```\n * use criterion;\n * ```\n */") == "```\nuse criterion;\n```\n"
+
+
+@pytest.mark.unit
+def test_doctest_imports_are_scoped_and_count_as_dev_context(tree_parser, logger):
+    code = (
+        "use serde::Serialize;\n\n"
+        "/// ```\n"
+        "/// # use proptest::prelude::*;\n"
+        "/// use serde::Deserialize;\n"
+        '/// let v = serde_json::from_str::<u8>("1");\n'
+        "/// ```\n"
+        "pub fn parse() {}\n"
+    )
+    root = tree_parser("rust", code)
+    annotations = {}
+
+    external, _ = RustLanguageHandler(logger).extract_imports(
+        root, "src/lib.rs", defaultdict(list), _mock_resolve, import_annotations=annotations
+    )
+
+    assert {"serde", "proptest", "serde_json"} <= set(external)
+    assert "serde" not in annotations
+    assert annotations["proptest"] == {"scope": "doctest"}
+    assert annotations["serde_json"] == {"scope": "doctest", "evidence": "qualified-path"}
+
+    packages = {"proptest": {"ecosystem": "cargo", "dep_kind": "dev"}}
+    mismatches = cross_check_dep_kinds(
+        packages,
+        {"src/lib.rs": external},
+        {"src/lib.rs": {"language": "rust"}},
+        file_import_annotations={"src/lib.rs": annotations},
+    )
+    assert packages["proptest"]["import_contexts"] == ["dev"]
+    assert mismatches == {}
+
+
+@pytest.mark.unit
+def test_vendored_sources_are_flagged_and_not_scanned(tmp_path):
+    (tmp_path / "Cargo.toml").write_text('[package]\nname = "app"\n\n[dependencies]\nserde = "1"\nserde_derive = "1"\n')
+    (tmp_path / "src").mkdir()
+    (tmp_path / "src" / "main.rs").write_text("use serde::Serialize;\nfn main() {}\n")
+    (tmp_path / ".cargo").mkdir()
+    (tmp_path / ".cargo" / "config.toml").write_text(
+        '[source.crates-io]\nreplace-with = "vendored-sources"\n\n[source.vendored-sources]\ndirectory = "vendor"\n'
+    )
+    vendored = {"serde": '[dependencies]\nserde_derive = "1"\n', "serde_derive": "[lib]\nproc-macro = true\n"}
+    for name, extra in vendored.items():
+        (tmp_path / "vendor" / name / "src").mkdir(parents=True)
+        (tmp_path / "vendor" / name / "Cargo.toml").write_text(
+            f'[package]\nname = "{name}"\nversion = "1.0.200"\n\n{extra}\n[dev-dependencies]\ntrybuild = "1"\n'
+        )
+        (tmp_path / "vendor" / name / "src" / "lib.rs").write_text("use trybuild;\n")
+
+    analyzer = DependencyAnalyzer(verbose=False)
+    packages = analyzer.discover_packages(str(tmp_path), ["rust"])
+
+    assert set(packages) == {"serde", "serde_derive"}
+    assert packages["serde"]["vendored"] is True
+    assert packages["serde"]["vendored_path"] == "vendor/serde"
+    assert packages["serde_derive"]["role"] == "proc-macro"
+    assert set(analyzer.repo_analyzer.source_files) == {"src/main.rs"}
+    assert analyzer.repo_analyzer.cargo_vendor == {
+        "config": ".cargo/config.toml",
+        "source": "vendored-sources",
+        "directory": "vendor",
+        "crates": [
+            {"name": "serde", "version": "1.0.200", "path": "vendor/serde"},
+            {"name": "serde_derive", "version": "1.0.200", "path": "vendor/serde_derive"},
+        ],
+    }
+
+
+@pytest.mark.unit
+def test_declared_dependencies_without_imports_are_reported_per_crate():
+    external_packages = {
+        name: {"ecosystem": "cargo"} for name in ("serde", "anyhow", "proptest", "winapi", "rand_core")
+    }
+    crates = {
+        ".": (
+            "Cargo.toml",
+            {
+                "package": {"name": "app"},
+                "dependencies": {"serde": "1", "anyhow": "1", "core": {"path": "core"}},
+                "dev-dependencies": {"proptest": "1"},
+                "target": {"cfg(windows)": {"dependencies": {"winapi": "0.3"}}},
+            },
+        ),
+        "core": (
+            "core/Cargo.toml",
+            {
+                "package": {"name": "core"},
+                "dependencies": {"rand-core": {"version": "0.6", "package": "rand_core"}, "serde": "1"},
+            },
+        ),
+    }
+    file_imports = {"src/main.rs": ["serde", "core"], "tests/props.rs": ["proptest"], "core/src/lib.rs": ["rand_core"]}
+    source_files = {path: {"language": "rust"} for path in file_imports}
+
+    reports = find_unused_dependencies(external_packages, crates, {"core": {}}, file_imports, source_files)
+
+    assert reports == [
+        {
+            "crate": "app",
+            "manifest": "Cargo.toml",
+            "unused": [
+                {"name": "anyhow", "package": "anyhow", "dep_kind": "normal"},
+                {"name": "winapi", "package": "winapi", "dep_kind": "normal", "target": "cfg(windows)"},
+            ],
+        },
+        {
+            "crate": "core",
+            "manifest": "core/Cargo.toml",
+            "unused": [{"name": "serde", "package": "serde", "dep_kind": "normal"}],
+        },
+    ]
+    assert external_packages["anyhow"]["unused_declared"] is True
+    assert "unused_declared" not in external_packages["serde"]
+    assert "unused_declared" not in external_packages["rand_core"]
+
+
+@pytest.mark.unit
+def test_crates_imported_without_declaration_are_reported_per_crate():
+    crates = {
+        ".": ("Cargo.toml", {"workspace": {"members": ["app", "util"]}}),
+        "app": (
+            "app/Cargo.toml",
+            {"package": {"name": "my-app"}, "dependencies": {"serde": "1", "util": {"path": "../util"}}},
+        ),
+        "util": ("util/Cargo.toml", {"package": {"name": "util"}, "dependencies": {"regex": "1"}}),
+    }
+    file_imports = {
+        "app/src/main.rs": ["serde", "util", "regex", "inline", "std"],
+        "app/tests/it.rs": ["my_app", "regex"],
+        "util/src/lib.rs": ["regex"],
+    }
+    file_package_components = {
+        "app/src/main.rs": [
+            ("serde", "serde::Serialize"),
+            ("util", "util::f"),
+            ("regex", "regex::Regex"),
+            ("std", "std::io"),
+        ],
+        "app/tests/it.rs": [("my_app", "my_app::run"), ("regex", "regex::Regex")],
+        "util/src/lib.rs": [("regex", "regex::Regex")],
+    }
+    source_files = {path: {"language": "rust"} for path in file_imports}
+
+    reports = find_undeclared_imports(crates, {"util": {}}, file_imports, file_package_components, source_files)
+
+    assert reports == [
+        {
+            "ecosystem": "cargo",
+            "package": "my-app",
+            "manifest": "app/Cargo.toml",
+            "imports": [{"name": "regex", "files": ["app/src/main.rs", "app/tests/it.rs"]}],
+        }
+    ]
+
+
+@pytest.mark.unit
+def test_cargo_metadata_graph_is_merged_into_the_static_analysis(tmp_path):
+    registry = "registry+https://github.com/rust-lang/crates.io-index"
+    metadata = {
+        "packages": [
+            {
+                "id": "app 0.1.0",
+                "name": "app",
+                "version": "0.1.0",
+                "source": None,
+                "manifest_path": str(tmp_path / "Cargo.toml"),
+                "targets": [{"name": "app", "kind": ["bin"], "src_path": str(tmp_path / "src/main.rs")}],
+            },
+            {"id": "serde 1.0.190", "name": "serde", "version": "1.0.190", "source": registry, "targets": []},
+            {
+                "id": "serde_derive 1.0.190",
+                "name": "serde_derive",
+                "version": "1.0.190",
+                "source": registry,
+                "targets": [{"name": "serde_derive", "kind": ["proc-macro"]}],
+            },
+            {"id": "itoa 1.0.9", "name": "itoa", "version": "1.0.9", "source": registry, "targets": []},
+        ],
+        "workspace_members": ["app 0.1.0"],
+        "resolve": {
+            "nodes": [
+                {
+                    "id": "app 0.1.0",
+                    "deps": [
+                        {"name": "ser", "pkg": "serde 1.0.190", "dep_kinds": [{"kind": None}]},
+                        {"name": "serde_derive", "pkg": "serde_derive 1.0.190", "dep_kinds": [{"kind": "dev"}]},
+                    ],
+                },
+                {"id": "serde 1.0.190", "deps": [], "features": ["derive", "std"]},
+            ]
+        },
+    }
+    (tmp_path / "metadata.json").write_text(json.dumps(metadata))
+    cargo = tmp_path / "cargo"
+    cargo.write_text('#!/bin/sh\ncat "$(dirname "$0")/metadata.json"\n')
+    cargo.chmod(0o755)
+    crates = {".": ("Cargo.toml", {"package": {"name": "app"}})}
+    packages = {"serde": {"ecosystem": "cargo", "import_names": ["serde"], "dep_kind": "normal"}}
+    members = {}
+
+    results = run_cargo_metadata(str(tmp_path), crates, cargo=str(cargo))
+    details = merge_cargo_metadata(packages, members, results, str(tmp_path))
+
+    assert details == {
+        "manifests": ["Cargo.toml"],
+        "packages": 4,
+        "workspace_members": ["app"],
+        "added": ["itoa", "serde_derive"],
+    }
+    assert packages["serde"]["import_names"] == ["serde", "ser"]
+    assert packages["serde"]["enabled_features"] == ["derive", "std"]
+    assert packages["serde"]["locked_version"] == "1.0.190"
+    assert packages["serde_derive"]["dep_kind"] == "dev"
+    assert packages["serde_derive"]["role_source"] == "cargo-metadata"
+    assert packages["itoa"]["transitive"] is True
+    assert members["app"]["via"] == ["cargo-metadata"]
+
+
+@pytest.mark.unit
+def test_cargo_metadata_is_locked_and_offline_under_strict_network(tmp_path):
+    cargo = tmp_path / "cargo"
+    cargo.write_text('#!/bin/sh\necho "$@" >> "$(dirname "$0")/args"\necho \'{"packages": []}\'\n')
+    cargo.chmod(0o755)
+    crates = {".": ("Cargo.toml", {"package": {"name": "app"}})}
+
+    run_cargo_metadata(str(tmp_path), crates, cargo=str(cargo))
+    with ConfigOverride({"STRICT_NETWORK": True}):
+        run_cargo_metadata(str(tmp_path), crates, cargo=str(cargo))
+
+    default_run, strict_run = (tmp_path / "args").read_text().splitlines()
+    assert "--locked" in default_run.split() and "--offline" not in default_run.split()
+    assert {"--locked", "--offline"} <= set(strict_run.split())
+
+@pytest.mark.unit
+def test_cargo_metadata_without_a_toolchain_keeps_the_static_analysis(tmp_path, monkeypatch):
+    monkeypatch.setattr("shutil.which", lambda name: None)
+
+    results = run_cargo_metadata(str(tmp_path), {".": ("Cargo.toml", {})})
+
+    assert results == []
+    assert merge_cargo_metadata({}, {}, results, str(tmp_path)) is None
+
+
+@pytest.mark.unit
+def test_files_are_tagged_with_the_kind_of_their_compile_target():
+    crates = {
+        ".": (
+            "Cargo.toml",
+            {
+                "package": {"name": "app", "autobenches": False},
+                "dependencies": {"serde": "1"},
+                "dev-dependencies": {"clap": "4"},
+                "example": [{"name": "demo", "path": "samples/demo.rs"}],
+            },
+        )
+    }
+    paths = [
+        "src/lib.rs",
+        "src/model.rs",
+        "src/main.rs",
+        "src/bin/tool/main.rs",
+        "src/bin/tool/args.rs",
+        "samples/demo.rs",
+        "examples/cli.rs",
+        "benches/speed.rs",
+        "tests/it.rs",
+        "build.rs",
+    ]
+    source_files = {path: {"language": "rust"} for path in paths}
+    packages = {"serde": {"ecosystem": "cargo"}, "clap": {"ecosystem": "cargo"}}
+    file_imports = {"src/model.rs": ["serde"], "tests/it.rs": ["serde"], "samples/demo.rs": ["clap"]}
+
+    reports, kinds = classify_targets(packages, crates, file_imports, source_files)
+
+    assert [(target["kind"], target["name"]) for target in reports[0]["targets"]] == [
+        ("example", "demo"),
+        ("lib", "app"),
+        ("bin", "app"),
+        ("bin", "tool"),
+        ("example", "cli"),
+        ("test", "it"),
+        ("build-script", "build-script-build"),
+    ]
+    assert kinds == {
+        "benches/speed.rs": "bench",
+        "build.rs": "build-script",
+        "examples/cli.rs": "example",
+        "samples/demo.rs": "example",
+        "src/bin/tool/args.rs": "bin",
+        "src/bin/tool/main.rs": "bin",
+        "src/lib.rs": "lib",
+        "src/main.rs": "bin",
+        "src/model.rs": "lib",
+        "tests/it.rs": "test",
+    }
+    assert packages["serde"]["target_kinds"] == ["lib", "test"]
+    assert packages["clap"]["target_kinds"] == ["example"]
+
+
+@pytest.mark.unit
+def test_independent_and_nested_packages_resolve_and_report_separately(tmp_path):
+    files = {
+        "core/Cargo.toml": '[package]\nname = "core-lib"\n\n[dependencies]\nserde = "1"\n',
+        "core/src/lib.rs": "pub mod model;\n",
+        "core/src/model.rs": "use serde::Serialize;\n",
+        "core/src/main.rs": "use core_lib::model;\nfn main() {}\n",
+        "core/fuzz/Cargo.toml": '[package]\nname = "core-fuzz"\n\n[dependencies]\nlibfuzzer-sys = "0.4"\n',
+        "core/fuzz/src/main.rs": "mod target;\nuse libfuzzer_sys::fuzz_target;\n",
+        "core/fuzz/src/target.rs": "",
+        "tools/cli/Cargo.toml": '[package]\nname = "cli"\n\n[dependencies]\nclap = "4"\n',
+        "tools/cli/src/main.rs": "mod cmd;\nfn main() {}\n",
+        "tools/cli/src/cmd.rs": "use crate::args::Args;\n",
+        "tools/cli/src/args.rs": "use clap::Parser;\n",
+    }
+    for rel_path, content in files.items():
+        (tmp_path / rel_path).parent.mkdir(parents=True, exist_ok=True)
+        (tmp_path / rel_path).write_text(content)
+
+    analyzer = DependencyAnalyzer(verbose=False)
+    packages = analyzer.discover_packages(str(tmp_path), ["rust"])
+    repo = analyzer.repo_analyzer
+    resolver = repo._get_local_resolver()
+
+    assert {"serde", "libfuzzer-sys", "clap"} <= set(packages)
+    assert resolver.resolve_rust("tools/cli/src/cmd.rs", ["crate", "args", "Args"]) == "tools/cli/src/args.rs"
+    assert resolver.resolve_rust("core/fuzz/src/main.rs", ["crate", "target"]) == "core/fuzz/src/target.rs"
+
+    table = cargo_targets.crate_target_table(repo.cargo_manifests, repo.source_files)
+    file_imports = {
+        "core/src/model.rs": ["serde"],
+        "core/fuzz/src/main.rs": ["libfuzzer_sys"],
+        "tools/cli/src/args.rs": ["clap"],
+    }
+    local_imports = {
+        "core/src/main.rs": ["core/src/lib.rs", "core/src/model.rs"],
+        "core/fuzz/src/main.rs": ["core/fuzz/src/target.rs", "core/src/lib.rs"],
+        "tools/cli/src/cmd.rs": ["tools/cli/src/args.rs"],
+    }
+    reports = {
+        report["path"]: report
+        for report in group_by_package(table, packages, repo.workspace_members, file_imports, local_imports)
+    }
+
+    assert sorted(reports) == ["core", "core/fuzz", "tools/cli"]
+    assert reports["core"]["roots"] == ["core/src/lib.rs", "core/src/main.rs"]
+    assert reports["core"]["files"] == 3
+    assert reports["core"]["imports"] == {"serde": ["core/src/model.rs"]}
+    assert reports["core"]["modules"] == {"core/src/main.rs": ["core/src/lib.rs", "core/src/model.rs"]}
+    assert reports["core/fuzz"]["imports"] == {"libfuzzer-sys": ["core/fuzz/src/main.rs"]}
+    assert reports["core/fuzz"]["depends_on"] == ["core"]
+    assert reports["tools/cli"]["workspace_member"] is False
+    assert reports["tools/cli"]["modules"] == {"tools/cli/src/cmd.rs": ["tools/cli/src/args.rs"]}
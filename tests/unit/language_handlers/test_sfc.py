@@ -0,0 +1,71 @@
+"""
+Vue, Svelte and Astro component script extraction
+"""
+
+import pytest
+
+from gardener.treewalk.javascript import JavaScriptLanguageHandler
+from gardener.treewalk.sfc import component_script
+
+VUE_COMPONENT = """<template>
+  <Chart :data="data" />
+</template>
+
+<script setup lang="ts">
+import { ref } from 'vue'
+import Chart from 'chart.js'
+</script>
+
+<script src="./legacy.js"></script>
+
+<style>
+@import 'normalize.css';
+</style>
+"""
+
+ASTRO_COMPONENT = """---
+import Layout from '../layouts/Layout.astro';
+import { format } from 'date-fns';
+---
+<Layout title="Blog">
+  <script>
+    import confetti from 'canvas-confetti';
+  </script>
+  <script is:inline>
+    import tracker from 'https://cdn.example.com/tracker.js';
+  </script>
+  <script type="application/ld+json">{"@type": "import x from 'nope'"}</script>
+</Layout>
+"""
+
+
+@pytest.mark.unit
+def test_vue_and_svelte_scripts_keep_their_lines_and_grammar():
+    source, grammar = component_script(VUE_COMPONENT, ".vue")
+
+    assert grammar == "typescript"
+    lines = source.splitlines()
+    assert lines[5] == "import { ref } from 'vue'"
+    assert lines[6] == "import Chart from 'chart.js'"
+    assert "Chart :data" not in source and "normalize.css" not in source
+    assert source.rstrip().endswith('import "./legacy.js";')
+
+    svelte = '<script context="module">\n  import { writable } from "svelte/store";\n</script>\n<h1>{title}</h1>\n'
+    source, grammar = component_script(svelte, ".svelte")
+    assert grammar == "javascript"
+    assert source.splitlines()[1] == '  import { writable } from "svelte/store";'
+    assert "<h1>" not in source
+
+
+@pytest.mark.unit
+def test_astro_frontmatter_and_bundled_scripts_are_extracted():
+    source, grammar = component_script(ASTRO_COMPONENT, ".astro")
+
+    assert grammar == "typescript"
+    assert "import Layout from '../layouts/Layout.astro';" in source
+    assert "import { format } from 'date-fns';" in source
+    assert "import confetti from 'canvas-confetti';" in source
+    assert "tracker" not in source and "nope" not in source and "<Layout" not in source
+
+    assert {".vue", ".svelte", ".astro"} <= set(JavaScriptLanguageHandler().get_file_extensions())
+    assert JavaScriptLanguageHandler().parse_source("import x from 'y'\n", "src/app.js") is None
@@ -0,0 +1,79 @@
+"""
+CSS, Sass and Less import extraction
+"""
+
+from collections import defaultdict
+
+import pytest
+
+from gardener.analysis.imports import LocalImportResolver
+from gardener.treewalk.stylesheet import StylesheetLanguageHandler, parse_stylesheet
+
+SCSS = """
+// @import 'commented-out';
+@use 'sass:math';
+@use '~bootstrap/scss/bootstrap' with ($primary: #333);
+@forward "pkg:@material/button/styles" show mdc-button;
+@import 'variables', 'mixins/breakpoints';
+@import url("https://fonts.googleapis.com/css?family=Inter");
+@import 'normalize.css/normalize.css';
+@import 'unknown-load-path';
+
+.logo { background: url('~@fontsource/inter/files/inter-latin-400.woff2'); }
+"""
+
+
+@pytest.mark.unit
+def test_rules_and_node_modules_urls_are_parsed():
+    assert parse_stylesheet(SCSS, "styles/app.scss").imports == [
+        ("use", "~bootstrap/scss/bootstrap"),
+        ("forward", "pkg:@material/button/styles"),
+        ("import", "variables"),
+        ("import", "mixins/breakpoints"),
+        ("import", "normalize.css/normalize.css"),
+        ("import", "unknown-load-path"),
+        ("url", "~@fontsource/inter/files/inter-latin-400.woff2"),
+    ]
+    less = '@import (reference) "~antd/lib/style/themes/default.less";\n@import "./theme";\n'
+    assert parse_stylesheet(less, "theme.less").imports == [
+        ("import", "~antd/lib/style/themes/default.less"),
+        ("import", "./theme"),
+    ]
+    sass = "@use 'config'\n@import url(base.css)\n"
+    assert parse_stylesheet(sass, "main.sass").imports == [("use", "config"), ("import", "base.css")]
+
+
+@pytest.mark.unit
+def test_partials_resolve_locally_and_packages_are_attributed(tmp_path):
+    source_files = {
+        rel_path: {"absolute_path": str(tmp_path / rel_path), "language": "stylesheet"}
+        for rel_path in ("styles/app.scss", "styles/_variables.scss", "styles/mixins/_breakpoints.scss")
+    }
+    resolver = LocalImportResolver(
+        repo_path=str(tmp_path),
+        source_files=source_files,
+        alias_resolver=None,
+        js_ts_base_url=None,
+        js_ts_path_aliases={},
+        go_module_path=None,
+        remappings=None,
+        hardhat_remappings=None,
+        solidity_src_path=None,
+        logger=None,
+        external_packages={"normalize.css": {"ecosystem": "npm"}},
+    )
+    handler = StylesheetLanguageHandler()
+    components, annotations = defaultdict(list), {}
+
+    external, local = handler.extract_imports(
+        handler.parse_source(SCSS, "styles/app.scss"),
+        "styles/app.scss",
+        components,
+        resolver.resolve_stylesheet,
+        import_annotations=annotations,
+    )
+
+    assert external == ["bootstrap", "@material/button", "normalize.css", "@fontsource/inter"]
+    assert local == ["styles/_variables.scss", "styles/mixins/_breakpoints.scss"]
+    assert annotations["bootstrap"] == {"evidence": "stylesheet"}
+    assert ("bootstrap", "bootstrap/scss/bootstrap") in components["styles/app.scss"]
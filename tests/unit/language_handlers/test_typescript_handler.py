@@ -64,3 +64,28 @@ def test_button_tsx_local_and_external(tree_parser, logger):
 
     assert {"react", "lodash"}.issubset(set(external))
     assert {"utils/index.ts", "utils/helpers.ts"}.issubset(set(local))
+
+
+@pytest.mark.unit
+def test_type_only_imports_are_annotated(tree_parser, logger):
+    code = (
+        "import type { ZodType } from 'zod'\n"
+        "import { type FC } from 'react'\n"
+        "import React from 'react'\n"
+        "import { type Request, type Response } from 'express'\n"
+        "import { type Dictionary, map } from 'lodash'\n"
+        "export type { Schema } from 'yup'\n"
+    )
+    root = tree_parser("typescript", code)
+    handler = TypeScriptLanguageHandler(logger)
+    annotations = {}
+    external, _ = handler.extract_imports(
+        root, "main.ts", defaultdict(list), _mock_resolve, import_annotations=annotations
+    )
+
+    assert {"zod", "react", "express", "lodash", "yup"} == set(external)
+    assert annotations == {
+        "zod": {"evidence": "type-only"},
+        "express": {"evidence": "type-only"},
+        "yup": {"evidence": "type-only"},
+    }
@@ -0,0 +1,104 @@
+"""
+crates.io metadata cache and snapshot for offline Rust resolution
+"""
+
+import json
+import threading
+
+import pytest
+
+from gardener.package_metadata import url_resolver
+from gardener.package_metadata.crates_index import CratesIndex
+from gardener.package_metadata.url_resolver import resolve_package_urls
+
+SERDE = {
+    "crate": {"name": "serde", "repository": "https://github.com/serde-rs/serde", "homepage": "https://serde.rs"},
+    "categories": [{"id": "encoding"}],
+    "keywords": [{"id": "serde"}],
+}
+
+
+@pytest.mark.unit
+def test_fetched_crates_are_cached_and_answer_when_the_registry_fails(tmp_path):
+    path = tmp_path / "crates_index.json"
+    index = CratesIndex(str(path))
+    url_resolver.set_request_fn(lambda url: json.dumps(SERDE) if url.endswith("/crates/serde") else None)
+    try:
+        resolve_package_urls({"serde": {"ecosystem": "cargo"}}, crates_index=index)
+        index.save()
+        url_resolver.set_request_fn(lambda url: None)
+        packages = {"serde": {"ecosystem": "cargo"}, "rand": {"ecosystem": "cargo"}}
+        offline = CratesIndex(str(path))
+        resolved = resolve_package_urls(packages, crates_index=offline)
+    finally:
+        url_resolver.set_request_fn(None)
+
+    cached = json.loads(path.read_text())["crates"]["serde"]
+    assert cached["repository"] == "https://github.com/serde-rs/serde"
+    assert cached["categories"] == ["encoding"] and cached["homepage"] == "https://serde.rs"
+    assert resolved == {"serde": "https://github.com/serde-rs/serde"}
+    assert packages["serde"]["index_fallback"] == "cache"
+    assert "index_fallback" not in packages["rand"]
+    assert offline.summary()["from_cache"] == 1
+
+
+@pytest.mark.unit
+def test_unreachable_sparse_index_resolves_from_the_snapshot_without_requests(tmp_path, monkeypatch):
+    snapshot = tmp_path / "snapshot.json"
+    snapshot.write_text(json.dumps({"crates": {"syn": {"repository": "https://github.com/dtolnay/syn"}}}))
+    requested = []
+    monkeypatch.setattr(url_resolver, "_fetch_json", lambda url, logger=None: (requested.append(url), False))
+    index = CratesIndex(None, str(snapshot))
+    packages = {"tokio": {"ecosystem": "cargo"}, "syn": {"ecosystem": "cargo"}}
+
+    resolved = resolve_package_urls(packages, crates_index=index)
+
+    assert requested == ["https://crates.io/api/v1/crates/tokio", url_resolver.CRATES_SPARSE_INDEX_CONFIG]
+    assert resolved == {"syn": "https://github.com/dtolnay/syn"}
+    assert packages["syn"]["index_fallback"] == "snapshot"
+    assert index.summary()["online"] is False
+
+
+@pytest.mark.unit
+def test_crates_missing_from_crates_io_are_cached_and_never_probe(tmp_path, monkeypatch):
+    path = tmp_path / "crates_index.json"
+    snapshot = tmp_path / "snapshot.json"
+    snapshot.write_text(json.dumps({"crates": {"gone": {"repository": "https://github.com/old/gone"}}}))
+    requested = []
+
+    def fetch_json(url, logger=None):
+        requested.append(url)
+        return None, url.endswith("/crates/gone")
+
+    monkeypatch.setattr(url_resolver, "_fetch_json", fetch_json)
+    index = CratesIndex(str(path), str(snapshot))
+    assert resolve_package_urls({"gone": {"ecosystem": "cargo"}}, crates_index=index) == {}
+    index.save()
+    assert requested == ["https://crates.io/api/v1/crates/gone"]
+    assert json.loads(path.read_text())["crates"]["gone"]["missing"] is True
+
+    # A failed request for a crate cached as missing is answered without the probe or the snapshot
+    monkeypatch.setattr(url_resolver, "_fetch_json", lambda url, logger=None: (requested.append(url), False))
+    packages = {"gone": {"ecosystem": "cargo"}}
+    assert resolve_package_urls(packages, crates_index=CratesIndex(str(path), str(snapshot))) == {}
+    assert url_resolver.CRATES_SPARSE_INDEX_CONFIG not in requested
+    assert "index_fallback" not in packages["gone"]
+
+
+@pytest.mark.unit
+def test_concurrent_saves_merge_their_crates(tmp_path):
+    path = tmp_path / "crates_index.json"
+    CratesIndex(str(path)).save()
+    indexes = [CratesIndex(str(path)) for _ in range(8)]
+    for number, index in enumerate(indexes):
+        index.record(f"crate-{number}", {"crate": {"repository": f"https://github.com/o/crate-{number}"}})
+
+    threads = [threading.Thread(target=index.save) for index in indexes]
+    for thread in threads:
+        thread.start()
+    for thread in threads:
+        thread.join()
+
+    crates = json.loads(path.read_text())["crates"]
+    assert sorted(crates) == [f"crate-{number}" for number in range(8)]
+    assert [p.name for p in tmp_path.iterdir() if p.name.endswith(".tmp")] == []
@@ -0,0 +1,89 @@
+"""
+Budget controls for URL enrichment
+"""
+
+import json
+
+import pytest
+
+from gardener.analysis.bundle import request_hook
+from gardener.analysis.main import DependencyAnalyzer
+from gardener.package_metadata.enrichment import EnrichmentBudget, parse_duration, parse_request_budget
+from gardener.package_metadata.url_resolver import resolve_package_urls
+
+
+def _npm_meta(repo):
+    return json.dumps(
+        {"dist-tags": {"latest": "1.0.0"}, "versions": {"1.0.0": {"repository": f"git+https://github.com/{repo}.git"}}}
+    )
+
+
+RESPONSES = {
+    "https://registry.npmjs.org/react": _npm_meta("facebook/react"),
+    "https://registry.npmjs.org/lodash": _npm_meta("lodash/lodash"),
+    "https://registry.npmjs.org/chalk": _npm_meta("chalk/chalk"),
+}
+
+
+@pytest.mark.unit
+def test_budget_options_parse():
+    assert parse_request_budget("500-requests") == 500
+    assert parse_request_budget("25") == 25
+    assert parse_duration("60s") == 60
+    assert parse_duration("2m") == 120
+    assert parse_duration("1500ms") == 1.5
+    assert EnrichmentBudget.from_options(None, None) is None
+    with pytest.raises(ValueError, match="Invalid enrichment budget"):
+        parse_request_budget("lots")
+
+
+@pytest.mark.unit
+def test_exhausted_request_budget_skips_remaining_lookups_but_uses_cache(offline_mode):
+    packages = {
+        "react": {"ecosystem": "npm"},
+        "lodash": {"ecosystem": "npm"},
+        "chalk": {"ecosystem": "npm"},
+        "forge-std": {"ecosystem": "solidity", "gitmodules_url": "https://github.com/foundry-rs/forge-std"},
+    }
+    budget = EnrichmentBudget(max_requests=1, clock=lambda: 0.0)
+
+    with offline_mode.set_responses(RESPONSES):
+        resolved = resolve_package_urls(packages, cache={"npm:chalk": "https://github.com/chalk/chalk"}, budget=budget)
+
+    assert resolved == {
+        "react": "https://github.com/facebook/react",
+        "chalk": "https://github.com/chalk/chalk",
+        "forge-std": "https://github.com/foundry-rs/forge-std",
+    }
+    assert packages["lodash"]["enrichment"] == "skipped-budget"
+    assert "enrichment" not in packages["chalk"]
+    assert budget.summary(skipped=1) == {
+        "max_requests": 1,
+        "timeout_seconds": None,
+        "requests": 1,
+        "elapsed_seconds": 0.0,
+        "exhausted_by": "requests",
+        "skipped_packages": 1,
+    }
+
+
+@pytest.mark.unit
+def test_timeout_budget_spends_time_on_highest_weight_packages_first():
+    now = [0.0]
+
+    def slow_registry(url):
+        now[0] += 30
+        return RESPONSES.get(url)
+
+    budget = EnrichmentBudget(timeout_seconds=60, clock=lambda: now[0])
+    packages = {"react": {"ecosystem": "npm"}, "lodash": {"ecosystem": "npm"}, "chalk": {"ecosystem": "npm"}}
+    analyzer = DependencyAnalyzer(verbose=False)
+
+    with request_hook(slow_registry):
+        analyzer._resolve_repository_urls(packages, budget=budget, priority=["chalk", "lodash"])
+
+    assert packages["chalk"]["repository_url"] == "https://github.com/chalk/chalk"
+    assert packages["lodash"]["repository_url"] == "https://github.com/lodash/lodash"
+    assert packages["react"] == {"ecosystem": "npm", "enrichment": "skipped-budget", "repository_url": ""}
+    assert analyzer.enrichment_state["exhausted_by"] == "timeout"
+    assert analyzer.enrichment_state["skipped_packages"] == 1
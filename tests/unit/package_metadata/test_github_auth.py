@@ -0,0 +1,200 @@
+"""
+GitHub App installation tokens for GitHub API lookups
+"""
+
+import base64
+import io
+import json
+import threading
+import urllib.error
+from datetime import datetime, timezone
+from http.server import BaseHTTPRequestHandler, HTTPServer
+
+import pytest
+from cryptography.hazmat.primitives import hashes, serialization
+from cryptography.hazmat.primitives.asymmetric import padding, rsa
+
+from gardener.package_metadata import url_resolver
+from gardener.package_metadata.github_auth import GitHubAppAuth, TokenAuth, github_auth_from_options, github_auth_scope
+
+
+def _pem(key):
+    return key.private_bytes(
+        serialization.Encoding.PEM, serialization.PrivateFormat.PKCS8, serialization.NoEncryption()
+    )
+
+
+def _b64decode(segment):
+    return base64.urlsafe_b64decode(segment + "=" * (-len(segment) % 4))
+
+
+class _Exchange:
+    """Fake installation token endpoint issuing tok-1, tok-2, ... valid for one hour"""
+
+    def __init__(self, clock):
+        self.clock = clock
+        self.calls = []
+
+    def __call__(self, url, headers):
+        self.calls.append((url, headers))
+        return {"token": f"tok-{len(self.calls)}", "expires_at": _iso(self.clock.now + 3600)}
+
+
+class _Clock:
+    def __init__(self, now):
+        self.now = now
+
+    def __call__(self):
+        return self.now
+
+
+def _iso(timestamp):
+    return datetime.fromtimestamp(timestamp, timezone.utc).strftime("%Y-%m-%dT%H:%M:%SZ")
+
+
+@pytest.mark.unit
+def test_app_jwt_is_signed_and_installation_token_refreshes_before_expiry():
+    key = rsa.generate_private_key(public_exponent=65537, key_size=2048)
+    clock = _Clock(1_800_000_000)
+    exchange = _Exchange(clock)
+    auth = GitHubAppAuth(12345, 678, _pem(key), post_fn=exchange, clock=clock)
+
+    header, payload, signature = auth.app_jwt().split(".")
+    assert json.loads(_b64decode(header)) == {"alg": "RS256", "typ": "JWT"}
+    assert json.loads(_b64decode(payload)) == {"iat": 1_800_000_000 - 60, "exp": 1_800_000_000 + 540, "iss": "12345"}
+    key.public_key().verify(_b64decode(signature), f"{header}.{payload}".encode(), padding.PKCS1v15(), hashes.SHA256())
+
+    assert auth.authorization() == "Bearer tok-1"
+    url, headers = exchange.calls[0]
+    assert url == "https://api.github.com/app/installations/678/access_tokens"
+    assert headers["Authorization"].startswith("Bearer ey")
+
+    clock.now += 3000
+    assert auth.authorization() == "Bearer tok-1"
+    clock.now += 400  # inside the refresh margin before expires_at
+    assert auth.authorization() == "Bearer tok-2"
+    auth.invalidate()
+    assert auth.authorization() == "Bearer tok-3"
+
+
+@pytest.mark.unit
+def test_github_api_requests_carry_token_and_401_invalidates_it(monkeypatch):
+    seen = []
+
+    class _Auth(TokenAuth):
+        invalidated = 0
+
+        def invalidate(self):
+            self.invalidated += 1
+
+    def fake_urlopen(req, timeout=None):
+        seen.append((req.full_url, req.get_header("Authorization")))
+        raise urllib.error.HTTPError(req.full_url, 401, "Bad credentials", {}, io.BytesIO())
+
+    monkeypatch.setattr(url_resolver.urllib.request, "urlopen", fake_urlopen)
+    auth = _Auth("secret")
+    with github_auth_scope(auth):
+        with pytest.raises(urllib.error.HTTPError):
+            url_resolver._request_once("https://api.github.com/repos/a/b")
+        with pytest.raises(urllib.error.HTTPError):
+            url_resolver._request_once("https://registry.npmjs.org/react")
+        with pytest.raises(urllib.error.HTTPError):
+            url_resolver._go_fetch_page("https://github.com/owner?go-get=1")
+        with pytest.raises(urllib.error.HTTPError):
+            url_resolver._go_fetch_page("https://go.uber.org/zap?go-get=1")
+
+    assert seen == [
+        ("https://api.github.com/repos/a/b", "Bearer secret"),
+        ("https://registry.npmjs.org/react", None),
+        ("https://github.com/owner?go-get=1", "Bearer secret"),
+        ("https://go.uber.org/zap?go-get=1", None),
+    ]
+    assert auth.invalidated == 2
+    assert url_resolver._GITHUB_AUTH is None
+
+
+@pytest.mark.unit
+def test_github_credentials_are_not_forwarded_on_redirects_to_other_hosts(monkeypatch):
+    seen = []
+
+    class _Handler(BaseHTTPRequestHandler):
+        def do_GET(self):
+            seen.append((self.headers["Host"].split(":")[0], self.path, self.headers.get("Authorization")))
+            if self.path.startswith("/start"):
+                # github.com stands in as 127.0.0.1; the redirect target is another host name
+                self.send_response(302)
+                self.send_header("Location", f"http://localhost:{self.server.server_port}/landing{self.path[6:]}")
+                self.end_headers()
+                return
+            body = b'{"ok": true}' if self.path == "/landing/api" else b"<html></html>"
+            self.send_response(200)
+            self.send_header("Content-Length", str(len(body)))
+            self.end_headers()
+            self.wfile.write(body)
+
+        def log_message(self, *args):
+            pass
+
+    server = HTTPServer(("127.0.0.1", 0), _Handler)
+    threading.Thread(target=server.serve_forever, daemon=True).start()
+    monkeypatch.setattr(url_resolver, "GITHUB_HOSTS", {"127.0.0.1"})
+    monkeypatch.setenv("no_proxy", "*")
+    base = f"http://127.0.0.1:{server.server_port}"
+    try:
+        with github_auth_scope(TokenAuth("secret")):
+            assert url_resolver._request_once(f"{base}/start/api")[1] == {"ok": True}
+            assert url_resolver._go_fetch_page(f"{base}/start/page?go-get=1") == "<html></html>"
+    finally:
+        server.shutdown()
+        server.server_close()
+
+    assert seen == [
+        ("127.0.0.1", "/start/api", "Bearer secret"),
+        ("localhost", "/landing/api", None),
+        ("127.0.0.1", "/start/page?go-get=1", "Bearer secret"),
+        ("localhost", "/landing/page?go-get=1", None),
+    ]
+
+
+@pytest.mark.unit
+def test_token_exchange_goes_through_the_request_hook_and_budget(monkeypatch):
+    key = rsa.generate_private_key(public_exponent=65537, key_size=2048)
+    requested = []
+
+    class _Budget:
+        charged = 0
+
+        def charge_request(self):
+            self.charged += 1
+
+    def hook(url):
+        requested.append(url)
+        return json.dumps({"token": "tok-hook", "expires_at": "2099-01-01T00:00:00Z"})
+
+    budget = _Budget()
+    monkeypatch.setattr(url_resolver, "_ACTIVE_BUDGET", budget)
+    monkeypatch.setattr(url_resolver, "_REQUEST_FN", hook)
+    auth = GitHubAppAuth(1, 2, _pem(key))
+
+    assert auth.authorization() == "Bearer tok-hook"
+    assert requested == ["https://api.github.com/app/installations/2/access_tokens"]
+    assert budget.charged == 1
+
+
+@pytest.mark.unit
+def test_github_auth_from_options_reads_app_settings_and_token(tmp_path):
+    pem = _pem(rsa.generate_private_key(public_exponent=65537, key_size=2048))
+    key_path = tmp_path / "app.pem"
+    key_path.write_bytes(pem)
+
+    environ = {"GARDENER_GITHUB_APP_INSTALLATION_ID": "9"}
+    auth = github_auth_from_options(app_id="1", private_key=str(key_path), environ=environ)
+    assert isinstance(auth, GitHubAppAuth)
+    assert (auth.app_id, auth.installation_id) == ("1", "9")
+    # The key option also takes the PEM itself
+    assert isinstance(github_auth_from_options(app_id="1", private_key=pem.decode(), environ=environ), GitHubAppAuth)
+
+    assert github_auth_from_options(environ={"GITHUB_TOKEN": "pat"}).authorization() == "Bearer pat"
+    assert github_auth_from_options(environ={}) is None
+    with pytest.raises(ValueError, match="installation ID"):
+        github_auth_from_options(app_id="1", environ={})
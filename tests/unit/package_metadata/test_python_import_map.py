@@ -0,0 +1,66 @@
+"""
+Python import name to distribution map
+"""
+
+import pytest
+
+from gardener.common.defaults import ConfigOverride
+from gardener.package_metadata.name_resolvers import python as python_resolver
+from gardener.package_metadata.name_resolvers.python import (
+    CondaResolver,
+    ImportMapError,
+    PythonResolver,
+    load_import_map,
+    mapped_import_names,
+)
+
+
+@pytest.mark.unit
+def test_declared_distributions_get_known_import_names_offline(monkeypatch):
+    monkeypatch.setattr(python_resolver, "resolve_python_import_names", lambda *args, **kwargs: [])
+    resolver = PythonResolver()
+
+    assert resolver.resolve_package_imports("opencv-python")[0] == "cv2"
+    assert resolver.resolve_package_imports("pyyaml")[0] == "yaml"
+    assert resolver.resolve_package_imports("Pillow") == ["PIL", "Pillow"]
+    assert mapped_import_names("requests") == []
+
+
+@pytest.mark.unit
+def test_configured_map_extends_and_overrides_builtin_entries(tmp_path):
+    (tmp_path / "imports.yml").write_text("cv2: opencv-python-headless\nmy_sdk: acme-sdk\nPIL: null\n")
+
+    import_map = load_import_map(str(tmp_path / "imports.yml"))
+
+    assert import_map["cv2"] == "opencv-python-headless"
+    assert import_map["my_sdk"] == "acme-sdk"
+    assert "PIL" not in import_map
+    with ConfigOverride({"PYTHON_IMPORT_MAP": str(tmp_path / "imports.yml")}):
+        assert mapped_import_names("acme_sdk") == ["my_sdk"]
+        assert mapped_import_names("opencv-python") == []
+
+
+@pytest.mark.unit
+def test_import_map_is_loaded_once_and_bad_files_are_configuration_errors(tmp_path):
+    path = tmp_path / "imports.yml"
+    path.write_text("my_sdk: acme-sdk\n")
+    with ConfigOverride({"PYTHON_IMPORT_MAP": str(path)}):
+        assert mapped_import_names("acme-sdk") == ["my_sdk"]
+        path.write_text("- not a mapping\n")
+        assert mapped_import_names("acme-sdk") == ["my_sdk"]
+
+    (tmp_path / "bad.yml").write_text("- cv2\n")
+    with pytest.raises(ImportMapError, match="Configuration error"):
+        load_import_map(str(tmp_path / "bad.yml"))
+    with pytest.raises(ImportMapError, match="Configuration error"):
+        load_import_map(str(tmp_path / "missing.yml"))
+
+
+@pytest.mark.unit
+def test_conda_packages_are_not_looked_up_on_pypi(monkeypatch):
+    def fail(*args, **kwargs):
+        raise AssertionError("conda packages must not be resolved through PyPI")
+
+    monkeypatch.setattr(python_resolver, "resolve_python_import_names", fail)
+
+    assert CondaResolver().resolve_package_imports("pyyaml")[0] == "yaml"
@@ -0,0 +1,104 @@
+"""
+Trust-on-first-use registry fingerprint pinning
+"""
+
+import json
+
+import pytest
+
+from gardener.package_metadata import url_resolver
+from gardener.package_metadata.tls_pins import PinMismatchError, PinsFileError, RegistryPins, subject_public_key_info
+from gardener.package_metadata.url_resolver import resolve_package_urls
+
+
+def _tlv(tag, body):
+    if len(body) < 0x80:
+        return bytes([tag, len(body)]) + body
+    size = len(body).to_bytes(2, "big")
+    return bytes([tag, 0x82]) + size + body
+
+
+def _certificate(key):
+    spki = _tlv(0x30, _tlv(0x30, _tlv(0x06, b"\x2a\x86\x48\xce\x3d\x02\x01")) + _tlv(0x03, b"\x00" + key))
+    tbs = _tlv(
+        0x30,
+        _tlv(0xA0, _tlv(0x02, b"\x02"))
+        + _tlv(0x02, b"\x01\x23")
+        + _tlv(0x30, b"")
+        + _tlv(0x30, b"issuer" * 30)
+        + _tlv(0x30, b"")
+        + _tlv(0x30, b"subject")
+        + spki,
+    )
+    return _tlv(0x30, tbs + _tlv(0x30, b"") + _tlv(0x03, b"\x00sig")), spki
+
+
+@pytest.mark.unit
+def test_subject_public_key_info_is_read_from_the_certificate():
+    der, spki = _certificate(b"k" * 65)
+
+    assert subject_public_key_info(der) == spki
+    with pytest.raises(ValueError):
+        subject_public_key_info(der[:40])
+
+
+@pytest.mark.unit
+def test_hosts_are_pinned_on_first_use_and_renewals_keep_the_pin(tmp_path):
+    seen = {"certificate": "c1", "public_key": "k1"}
+    path = tmp_path / "pins" / "registry_pins.json"
+
+    assert RegistryPins(str(path), fetch=lambda host: dict(seen)).check("crates.io") == "pinned"
+    assert json.loads(path.read_text())["crates.io"]["public_key"] == "k1"
+
+    pins = RegistryPins(str(path), fetch=lambda host: dict(seen))
+    assert pins.check("crates.io") == "match"
+    seen["certificate"] = "c2"
+    assert RegistryPins(str(path), fetch=lambda host: dict(seen)).check("crates.io") == "renewed"
+    assert json.loads(path.read_text())["crates.io"]["certificate"] == "c2"
+    assert pins.summary() == {"file": str(path), "hosts": {"crates.io": "match"}}
+
+
+@pytest.mark.unit
+def test_changed_public_key_warns_or_fails_in_strict_mode(tmp_path):
+    path = tmp_path / "registry_pins.json"
+    path.write_text(json.dumps({"pypi.org": {"certificate": "c1", "public_key": "k1", "first_seen": "2026-01-01"}}))
+    fetch = lambda host: {"certificate": "c2", "public_key": "k2"}  # noqa: E731
+
+    pins = RegistryPins(str(path), fetch=fetch)
+    assert pins.check("pypi.org") == "changed"
+    assert json.loads(path.read_text())["pypi.org"]["public_key"] == "k1"
+
+    with pytest.raises(PinMismatchError, match="pypi.org"):
+        RegistryPins(str(path), strict=True, fetch=fetch).check("pypi.org")
+
+
+@pytest.mark.unit
+def test_unreadable_pins_file_fails_closed_in_strict_mode_and_is_left_untouched(tmp_path):
+    path = tmp_path / "registry_pins.json"
+    path.write_text('{"pypi.org": {"public_key": ')
+    fetch = lambda host: {"certificate": "c1", "public_key": "k1"}  # noqa: E731
+
+    with pytest.raises(PinsFileError, match="cannot be read"):
+        RegistryPins(str(path), strict=True, fetch=fetch)
+
+    pins = RegistryPins(str(path), fetch=fetch)
+    assert pins.check("pypi.org") == "unavailable"
+    assert path.read_text() == '{"pypi.org": {"public_key": '
+    # Saves replace the file in one step and leave no temporary files behind
+    fresh = tmp_path / "fresh" / "registry_pins.json"
+    assert RegistryPins(str(fresh), fetch=fetch).check("pypi.org") == "pinned"
+    assert [p.name for p in fresh.parent.iterdir()] == ["registry_pins.json"]
+
+
+@pytest.mark.unit
+def test_strict_pin_mismatch_aborts_url_resolution(tmp_path, monkeypatch):
+    path = tmp_path / "registry_pins.json"
+    path.write_text(json.dumps({"registry.npmjs.org": {"certificate": "c1", "public_key": "k1"}}))
+    pins = RegistryPins(str(path), strict=True, fetch=lambda host: {"certificate": "c2", "public_key": "k2"})
+
+    def urlopen(*args, **kwargs):
+        raise AssertionError("no request is sent to a registry with a changed key")
+
+    monkeypatch.setattr(url_resolver.urllib.request, "urlopen", urlopen)
+    with pytest.raises(PinMismatchError):
+        resolve_package_urls({"react": {"ecosystem": "npm"}}, pins=pins)
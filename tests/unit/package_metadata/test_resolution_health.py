@@ -0,0 +1,70 @@
+"""
+Per-ecosystem resolution health
+"""
+
+import json
+import urllib.error
+
+import pytest
+
+from gardener.analysis.bundle import request_hook
+from gardener.package_metadata import url_resolver
+from gardener.package_metadata.health import ResolutionHealth, is_rate_limited
+from gardener.package_metadata.url_resolver import resolve_package_urls
+
+REACT_META = json.dumps(
+    {
+        "dist-tags": {"latest": "1.0.0"},
+        "versions": {"1.0.0": {"repository": "git+https://github.com/facebook/react.git"}},
+    }
+)
+
+
+@pytest.mark.unit
+def test_is_rate_limited():
+    assert is_rate_limited(429)
+    assert is_rate_limited(403, {"X-RateLimit-Remaining": "0"})
+    assert not is_rate_limited(403, {"X-RateLimit-Remaining": "12"})
+    assert not is_rate_limited(500)
+
+
+@pytest.mark.unit
+def test_health_counts_requests_not_found_and_cache_hits_per_ecosystem(offline_mode):
+    packages = {
+        "react": {"ecosystem": "npm"},
+        "missing-pkg": {"ecosystem": "npm"},
+        "chalk": {"ecosystem": "npm"},
+    }
+    health = ResolutionHealth()
+
+    with offline_mode.set_responses({"https://registry.npmjs.org/react": REACT_META}):
+        resolve_package_urls(packages, cache={"npm:chalk": "https://github.com/chalk/chalk"}, health=health)
+
+    npm = health.summary()["npm"]
+    assert npm["packages"] == 3
+    assert npm["cache_hits"] == 1
+    assert npm["cache_hit_rate"] == 0.333
+    assert npm["requests"] == 2
+    assert npm["not_found"] == 1
+    assert npm["errors"] == 0
+    assert npm["error_rate"] == 0.0
+    assert npm["median_latency_ms"] is not None
+    assert npm["hosts"] == ["registry.npmjs.org"]
+
+
+@pytest.mark.unit
+def test_health_counts_rate_limit_events(monkeypatch):
+    def throttled(url):
+        raise urllib.error.HTTPError(url, 429, "Too Many Requests", {"Retry-After": "1"}, None)
+
+    monkeypatch.setattr(url_resolver, "RETRY_DELAY", 0)
+    health = ResolutionHealth()
+
+    with request_hook(throttled):
+        resolve_package_urls({"requests": {"ecosystem": "pypi"}}, cache={}, health=health)
+
+    pypi = health.summary()["pypi"]
+    assert pypi["requests"] == url_resolver.RETRY_COUNT + 1
+    assert pypi["errors"] == pypi["requests"]
+    assert pypi["error_rate"] == 1.0
+    assert pypi["rate_limit_events"] == pypi["requests"]
@@ -0,0 +1,74 @@
+"""
+Warm-start URL resolution from a previous report
+"""
+
+import json
+import os
+from datetime import datetime, timezone
+
+import pytest
+
+from gardener.package_metadata.seed import ResolutionSeed
+from gardener.package_metadata.url_resolver import resolve_package_urls
+
+NOW = datetime(2026, 3, 10, tzinfo=timezone.utc)
+
+
+def _write_report(path, external_packages, resolved_at=None, mtime=NOW):
+    report = {"external_packages": external_packages, "analyzer_details": {}}
+    if resolved_at:
+        report["analyzer_details"]["seed"] = {"resolved_at": resolved_at}
+    path.write_text(json.dumps(report))
+    os.utime(path, (mtime.timestamp(), mtime.timestamp()))
+    return str(path)
+
+
+@pytest.mark.unit
+def test_seed_keeps_unexpired_urls_and_drops_stale_or_missing_ones(tmp_path):
+    report = _write_report(
+        tmp_path / "previous.json",
+        {
+            "react": {"ecosystem": "npm", "repository_url": "https://github.com/facebook/react"},
+            "lodash": {"ecosystem": "npm", "repository_url": "https://github.com/lodash/lodash"},
+            "left-pad": {"ecosystem": "npm", "repository_url": ""},
+            "chalk": {"ecosystem": "npm", "repository_url": "", "enrichment": "skipped-budget"},
+        },
+        resolved_at={"npm:lodash": "2026-02-01T00:00:00+00:00"},
+        mtime=datetime(2026, 3, 8, tzinfo=timezone.utc),
+    )
+
+    seed = ResolutionSeed.load(report, ttl_days=7, now=NOW)
+
+    assert seed.cache == {"npm:react": "https://github.com/facebook/react"}
+    assert seed.expired == 1
+    assert seed.summary({"react": {"ecosystem": "npm"}, "vite": {"ecosystem": "npm"}}) == {
+        "report": report,
+        "ttl_days": 7,
+        "entries": 1,
+        "expired": 1,
+        "reused": 1,
+        "resolved_at": {"npm:react": "2026-03-08T00:00:00+00:00"},
+    }
+
+
+@pytest.mark.unit
+def test_seeded_packages_skip_registry_lookups(tmp_path, offline_mode):
+    report = _write_report(
+        tmp_path / "previous.json",
+        {"react": {"ecosystem": "npm", "repository_url": "https://github.com/facebook/react"}},
+    )
+    seed = ResolutionSeed.load(report, ttl_days=7, now=NOW)
+    vite_meta = {"dist-tags": {"latest": "5.0.0"}, "versions": {"5.0.0": {"repository": "github:vitejs/vite"}}}
+
+    with offline_mode.set_responses({"https://registry.npmjs.org/vite": json.dumps(vite_meta)}):
+        resolved = resolve_package_urls({"react": {"ecosystem": "npm"}, "vite": {"ecosystem": "npm"}}, cache=seed.cache)
+
+    assert resolved == {"react": "https://github.com/facebook/react", "vite": "https://github.com/vitejs/vite"}
+
+
+@pytest.mark.unit
+def test_unreadable_seed_report_is_rejected(tmp_path):
+    (tmp_path / "broken.json").write_text("{not json")
+
+    with pytest.raises(ValueError, match="Could not read seed report"):
+        ResolutionSeed.load(str(tmp_path / "broken.json"), ttl_days=7)
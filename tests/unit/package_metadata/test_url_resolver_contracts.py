@@ -2,6 +2,7 @@ import json
 
 import pytest
 
+from gardener.analysis.bundle import request_hook
 from gardener.package_metadata.url_resolver import resolve_package_urls
 
 
@@ -73,3 +74,43 @@ def test_go_go_get_meta_tag_parsing(offline_mode):
     with offline_mode.set_responses({fetch_url: html}):
         resolved = resolve_package_urls(packages, logger=None, cache={})
     assert resolved["golang.org/x/crypto"] == "https://github.com/golang/crypto"
+
+
+@pytest.mark.unit
+def test_go_vanity_paths_resolve_through_cached_go_import_prefixes():
+    packages = {
+        "k8s.io/client-go": {"ecosystem": "go"},
+        "k8s.io/client-go/tools/cache": {"ecosystem": "go"},
+        "go.uber.org/zap": {"ecosystem": "go"},
+        "gopkg.in/yaml.v3": {"ecosystem": "go"},
+        "gopkg.in/check.v1": {"ecosystem": "go"},
+        "gopkg.in/alecthomas/kingpin.v2": {"ecosystem": "go"},
+    }
+    pages = {
+        "https://k8s.io/client-go?go-get=1": (
+            '<meta name="go-import" content="k8s.io/client-go git https://github.com/kubernetes/client-go">'
+        ),
+        # Attributes in either order; `mod` entries point at a module proxy, not a repository
+        "https://go.uber.org/zap?go-get=1": (
+            '<meta content="go.uber.org/zap mod https://proxy.example.com" name="go-import">'
+            "<meta content='go.uber.org/zap git https://github.com/uber-go/zap' name='go-import'>"
+        ),
+    }
+    requested = []
+
+    def hook(url):
+        requested.append(url)
+        return pages.get(url)
+
+    with request_hook(hook):
+        resolved = resolve_package_urls(packages, logger=None, cache={})
+
+    assert resolved == {
+        "k8s.io/client-go": "https://github.com/kubernetes/client-go",
+        "k8s.io/client-go/tools/cache": "https://github.com/kubernetes/client-go",
+        "go.uber.org/zap": "https://github.com/uber-go/zap",
+        "gopkg.in/yaml.v3": "https://github.com/go-yaml/yaml",
+        "gopkg.in/check.v1": "https://github.com/go-check/check",
+        "gopkg.in/alecthomas/kingpin.v2": "https://github.com/alecthomas/kingpin",
+    }
+    assert requested == ["https://k8s.io/client-go?go-get=1", "https://go.uber.org/zap?go-get=1"]
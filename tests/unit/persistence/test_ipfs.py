@@ -0,0 +1,54 @@
+"""
+IPFS CIDs and retrieval of published reports
+"""
+
+import io
+import json
+
+import pytest
+
+from gardener.persistence import ipfs
+from gardener.persistence.ipfs import CODEC_DAG_PB, main, parse_cid, raw_cid, report_failures
+
+REPORT = {"external_packages": {"react": {"ecosystem": "npm"}}, "top_dependencies": []}
+
+
+@pytest.mark.unit
+def test_raw_cids_round_trip_and_cidv0_parses_as_dag_pb():
+    assert raw_cid(b"") == "bafkreihdwdcefgh4dqkjv67uzcmw7ojee6xedzdetojuzjevtenxquvyku"
+    data = json.dumps(REPORT).encode("utf-8")
+    assert report_failures(raw_cid(data), data) == (REPORT, [])
+    assert report_failures(raw_cid(b"other"), data)[1] == [f"Content does not match CID {raw_cid(b'other')}"]
+
+    assert parse_cid("QmbWqxBEKC3P8tqsKc98xmWNzrzDtRLMiMPL8wBuTGsMnR")["codec"] == CODEC_DAG_PB
+    _, failures = report_failures("QmbWqxBEKC3P8tqsKc98xmWNzrzDtRLMiMPL8wBuTGsMnR", data, via_gateway=True)
+    assert failures and "cannot be checked" in failures[0]
+    with pytest.raises(ValueError):
+        parse_cid("zNotACid")
+
+
+@pytest.mark.unit
+def test_fetch_command_validates_and_writes_the_report(tmp_path, monkeypatch):
+    data = json.dumps(REPORT).encode("utf-8")
+    requested = []
+
+    class Response(io.BytesIO):
+        def __enter__(self):
+            return self
+
+        def __exit__(self, *exc):
+            return False
+
+    def urlopen(req, timeout):
+        requested.append(req.full_url)
+        return Response(data)
+
+    monkeypatch.setattr(ipfs.urllib.request, "urlopen", urlopen)
+    monkeypatch.setenv("IPFS_API_URL", "http://node:5001")
+    output = tmp_path / "report.json"
+
+    assert main([f"ipfs://{raw_cid(data)}", "-o", str(output)]) == 0
+    assert requested == [f"http://node:5001/api/v0/cat?arg={raw_cid(data)}"]
+    assert json.loads(output.read_text()) == REPORT
+    assert main([f"ipfs://{raw_cid(b'{}')}", "--gateway", "https://gateway.example"]) == 1
+    assert main(["ipfs://not-a-cid"]) == 1
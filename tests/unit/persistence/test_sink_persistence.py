@@ -0,0 +1,65 @@
+"""
+Writing results to object storage and IPFS sinks
+"""
+
+import hashlib
+import io
+import json
+
+import pytest
+
+from gardener.persistence.sinks import IPFSUploader, SinkPersistence, is_sink_url
+
+RESULTS = {"external_packages": {"react": {"ecosystem": "npm"}}, "top_dependencies": []}
+
+
+class RecordingUploader:
+    def __init__(self):
+        self.objects = []
+
+    def upload(self, bucket, key, data, content_type):
+        self.objects.append((bucket, key, data, content_type))
+        return f"s3://{bucket}/{key}"
+
+
+@pytest.mark.unit
+def test_results_are_uploaded_under_content_addressed_keys():
+    uploader = RecordingUploader()
+    sink = SinkPersistence("s3://reports/gardener/", uploader=uploader, verbose=False)
+
+    sink.save_analysis_results(RESULTS, "owner_repo")
+    sink.save_analysis_results(RESULTS, "owner_repo")
+
+    bucket, key, data, content_type = uploader.objects[0]
+    digest = hashlib.sha256(data).hexdigest()
+    assert (bucket, key, content_type) == ("reports", f"gardener/{digest}_dependency_analysis.json", "application/json")
+    assert json.loads(data) == RESULTS
+    assert uploader.objects[1][1] == key
+    assert sink.get_output_path("owner_repo", "_dependency_analysis.json") == f"s3://reports/{key}"
+    assert is_sink_url("gs://bucket/x") and is_sink_url("ipfs://")
+    assert not is_sink_url("owner_repo") and not is_sink_url("output/s3")
+    with pytest.raises(ValueError):
+        SinkPersistence("s3:///no-bucket", uploader=uploader)
+
+
+@pytest.mark.unit
+def test_ipfs_sink_names_report_by_cid():
+    requests = []
+
+    class Response(io.BytesIO):
+        def __enter__(self):
+            return self
+
+        def __exit__(self, *exc):
+            return False
+
+    def urlopen(req, timeout):
+        requests.append(req)
+        return Response(json.dumps({"Name": "x", "Hash": "bafkreiexample"}).encode("utf-8"))
+
+    sink = SinkPersistence("ipfs://", uploader=IPFSUploader("http://ipfs:5001/", urlopen=urlopen), verbose=False)
+    sink.save_analysis_results(RESULTS, "owner_repo")
+
+    assert requests[0].full_url == "http://ipfs:5001/api/v0/add?pin=true&cid-version=1&raw-leaves=true"
+    assert b'"react"' in requests[0].data
+    assert sink.get_output_path("owner_repo", "_dependency_analysis.json") == "ipfs://bafkreiexample"
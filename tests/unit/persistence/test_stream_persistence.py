@@ -0,0 +1,45 @@
+"""
+Streaming results with a report_stats header
+"""
+
+import io
+import json
+
+import pytest
+
+from gardener.persistence.stream import StreamPersistence, report_stats
+
+RESULTS = {
+    "external_packages": {"react": {"ecosystem": "npm"}, "lodash": {"ecosystem": "npm"}},
+    "dependency_graph": {
+        "nodes": [{"id": "app.js"}, {"id": "react"}, {"id": "lodash"}],
+        "links": [{"source": "app.js", "target": "react"}, {"source": "app.js", "target": "lodash"}],
+    },
+    "top_dependencies": [],
+    "analyzer_details": {"file_imports": {"app.js": ["react", "lodash"], "util.js": ["lodash"], "empty.js": []}},
+}
+
+
+@pytest.mark.unit
+def test_report_stats_counts_packages_evidence_and_edges():
+    stats = report_stats(RESULTS)
+
+    assert stats == {
+        "package_count": 2,
+        "evidence_count": 3,
+        "graph_edge_count": 2,
+        "serialized_bytes": len(json.dumps(RESULTS).encode("utf-8")),
+    }
+    assert report_stats({"external_packages": {}})["graph_edge_count"] == 0
+
+
+@pytest.mark.unit
+def test_stream_emits_header_before_body():
+    stream = io.StringIO()
+
+    StreamPersistence(stream).save_analysis_results(RESULTS, "app")
+
+    header, body = stream.getvalue().splitlines()
+    assert list(json.loads(header)) == ["report_stats"]
+    assert json.loads(header)["report_stats"]["serialized_bytes"] == len(body.encode("utf-8"))
+    assert json.loads(body) == RESULTS
@@ -5,6 +5,7 @@ import pytest
 from services.shared.drip_list_processor import (
     aggregate_by_repository_url,
     build_normalized_drip_list,
+    diff_drip_lists,
     filter_valid_github_items,
     truncate_and_normalize,
 )
@@ -61,3 +62,40 @@ def test_build_normalized_drip_list_end_to_end():
     out = build_normalized_drip_list(items, max_length=10, analyzed_repo_url=None)
     assert len(out) == 2
     assert sum(i["split_percentage"] for i in out) == Decimal("100.0000")
+
+
+@pytest.mark.unit
+def test_diff_drip_lists_reports_added_removed_and_changed():
+    previous = [
+        {"package_name": "requests", "package_url": "https://github.com/psf/requests", "split_percentage": Decimal(60)},
+        {"package_name": "flask", "package_url": "https://github.com/pallets/flask", "split_percentage": Decimal(30)},
+        {"package_name": "six", "package_url": "https://github.com/benjaminp/six", "split_percentage": Decimal(10)},
+    ]
+    current = [
+        {"package_name": "requests", "package_url": "https://github.com/psf/requests", "split_percentage": Decimal(60)},
+        {"package_name": "flask", "package_url": "https://github.com/pallets/flask", "split_percentage": Decimal(25)},
+        {"package_name": "httpx", "package_url": "https://github.com/encode/httpx", "split_percentage": Decimal(15)},
+    ]
+
+    delta = diff_drip_lists(previous, current)
+
+    assert [i["package_name"] for i in delta["added"]] == ["httpx"]
+    assert [i["package_name"] for i in delta["removed"]] == ["six"]
+    assert delta["changed"] == [
+        {
+            "package_name": "flask",
+            "package_url": "https://github.com/pallets/flask",
+            "split_percentage": Decimal(25),
+            "previous_split_percentage": Decimal(30),
+        }
+    ]
+    assert delta["unchanged"] == 1
+
+
+@pytest.mark.unit
+def test_diff_drip_lists_without_base_reports_everything_added():
+    current = [{"package_name": "requests", "package_url": "https://github.com/psf/requests", "split_percentage": 100}]
+
+    delta = diff_drip_lists([], current)
+
+    assert delta == {"added": current, "removed": [], "changed": [], "unchanged": 0}